@@ -5,13 +5,12 @@
 // so it can be shared by the daemon and the OpenRender UI tray.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value as JsonValue};
 
 use crate::{info, warn};
-use crate::paths::veil_root_dir;
+use crate::paths::{addon_assets_dir, addons_dir, asset_category_dir, assets_dir, tray_settings_json_path, ADDON_ASSETS_CATEGORY};
 
 // ---------------------------------------------------------------------------
 // Tray settings
@@ -23,18 +22,16 @@ pub struct TraySettings {
     pub run_backend_at_startup: bool,
     #[serde(default)]
     pub addon_autostart: HashMap<String, bool>,
-}
-
-pub fn tray_settings_path() -> Option<PathBuf> {
-    Some(veil_root_dir().join("tray_settings.json"))
+    /// Relaunch these addons (by id) when they crash.
+    #[serde(default)]
+    pub addon_auto_restart: HashMap<String, bool>,
+    /// Show desktop notifications for addon crashes, failed starts and autostart.
+    #[serde(default)]
+    pub notifications_enabled: bool,
 }
 
 pub fn load_tray_settings() -> TraySettings {
-    let Some(path) = tray_settings_path() else {
-        warn!("USERPROFILE not set; using default tray settings");
-        return TraySettings::default();
-    };
-
+    let path = tray_settings_json_path();
     if !path.exists() {
         return TraySettings::default();
     }
@@ -86,12 +83,21 @@ pub fn start_configured_autostart_addons() {
         return;
     }
 
+    let mut started = Vec::new();
+    let mut failed = Vec::new();
     for addon_name in addons_to_start {
         match crate::ipc::addon::start(Some(json!({"addon_name": addon_name.clone()}))) {
-            Ok(_) => info!("[addons] Autostarted '{}' on backend startup", addon_name),
-            Err(e) => warn!("[addons] Failed to autostart '{}' on backend startup: {}", addon_name, e),
+            Ok(_) => {
+                info!("[addons] Autostarted '{}' on backend startup", addon_name);
+                started.push(addon_name);
+            }
+            Err(e) => {
+                warn!("[addons] Failed to autostart '{}' on backend startup: {}", addon_name, e);
+                failed.push((addon_name, e.message));
+            }
         }
     }
+    crate::notify::autostart_finished(&started, &failed);
 }
 
 // ---------------------------------------------------------------------------
@@ -99,76 +105,71 @@ pub fn start_configured_autostart_addons() {
 // ---------------------------------------------------------------------------
 
 pub fn ensure_user_config_dirs() {
-    if std::env::var("USERPROFILE").is_ok() {
-        let root = veil_root_dir();
-        for p in [
-            root.join("Assets"),
-            root.join("Assets/Addons"),
-        ] {
-            if let Err(e) = std::fs::create_dir_all(&p) {
-                warn!("Failed to create config dir {}: {}", p.display(), e);
-            } else {
-                info!("Ensured config dir exists: {}", p.display());
-            }
+    for p in [
+        assets_dir(),
+        assets_dir().join(ADDON_ASSETS_CATEGORY),
+    ] {
+        if let Err(e) = std::fs::create_dir_all(&p) {
+            warn!("Failed to create config dir {}: {}", p.display(), e);
+        } else {
+            info!("Ensured config dir exists: {}", p.display());
         }
+    }
 
-        let addons_root = root.join("Addons");
-        if let Ok(addon_entries) = std::fs::read_dir(&addons_root) {
-            for addon_entry in addon_entries.flatten() {
-                let addon_dir = addon_entry.path();
-                if !addon_dir.is_dir() {
-                    continue;
-                }
+    let addons_root = addons_dir();
+    if let Ok(addon_entries) = std::fs::read_dir(&addons_root) {
+        for addon_entry in addon_entries.flatten() {
+            let addon_dir = addon_entry.path();
+            if !addon_dir.is_dir() {
+                continue;
+            }
 
-                let addon_json = addon_dir.join("addon.json");
-                let parsed = std::fs::read_to_string(&addon_json)
-                    .ok()
-                    .and_then(|text| serde_json::from_str::<JsonValue>(&text).ok())
-                    .unwrap_or(JsonValue::Null);
+            let addon_json = addon_dir.join("addon.json");
+            let parsed = std::fs::read_to_string(&addon_json)
+                .ok()
+                .and_then(|text| serde_json::from_str::<JsonValue>(&text).ok())
+                .unwrap_or(JsonValue::Null);
 
-                let accepts_assets = parsed
-                    .get("accepts_assets")
-                    .and_then(|v| v.as_bool())
-                    .or_else(|| parsed.get("assets").and_then(|a| a.get("accepts")).and_then(|v| v.as_bool()))
-                    .unwrap_or(false);
+            let accepts_assets = parsed
+                .get("accepts_assets")
+                .and_then(|v| v.as_bool())
+                .or_else(|| parsed.get("assets").and_then(|a| a.get("accepts")).and_then(|v| v.as_bool()))
+                .unwrap_or(false);
 
-                if !accepts_assets {
-                    continue;
-                }
+            if !accepts_assets {
+                continue;
+            }
 
-                let addon_id = parsed
-                    .get("id")
-                    .and_then(|v| v.as_str())
-                    .or_else(|| addon_dir.file_name().and_then(|s| s.to_str()))
-                    .unwrap_or("unknown-addon");
-
-                let addon_assets_dir = root.join("Assets").join("Addons").join(addon_id);
-                if let Err(e) = std::fs::create_dir_all(&addon_assets_dir) {
-                    warn!("Failed to create addon asset dir {}: {}", addon_assets_dir.display(), e);
-                } else {
-                    info!("Ensured addon asset dir exists: {}", addon_assets_dir.display());
-                }
+            let addon_id = parsed
+                .get("id")
+                .and_then(|v| v.as_str())
+                .or_else(|| addon_dir.file_name().and_then(|s| s.to_str()))
+                .unwrap_or("unknown-addon");
+
+            let addon_assets_dir = addon_assets_dir(addon_id);
+            if let Err(e) = std::fs::create_dir_all(&addon_assets_dir) {
+                warn!("Failed to create addon asset dir {}: {}", addon_assets_dir.display(), e);
+            } else {
+                info!("Ensured addon asset dir exists: {}", addon_assets_dir.display());
+            }
 
-                let categories = parsed
-                    .get("asset_categories")
-                    .and_then(|v| v.as_array())
-                    .or_else(|| parsed.get("assets").and_then(|a| a.get("categories")).and_then(|v| v.as_array()))
-                    .cloned()
-                    .unwrap_or_default();
-
-                for category in categories {
-                    if let Some(category_name) = category.as_str() {
-                        let category_dir = root.join("Assets").join(category_name);
-                        if let Err(e) = std::fs::create_dir_all(&category_dir) {
-                            warn!("Failed to create asset category dir {}: {}", category_dir.display(), e);
-                        } else {
-                            info!("Ensured asset category dir exists: {}", category_dir.display());
-                        }
+            let categories = parsed
+                .get("asset_categories")
+                .and_then(|v| v.as_array())
+                .or_else(|| parsed.get("assets").and_then(|a| a.get("categories")).and_then(|v| v.as_array()))
+                .cloned()
+                .unwrap_or_default();
+
+            for category in categories {
+                if let Some(category_name) = category.as_str() {
+                    let category_dir = asset_category_dir(category_name);
+                    if let Err(e) = std::fs::create_dir_all(&category_dir) {
+                        warn!("Failed to create asset category dir {}: {}", category_dir.display(), e);
+                    } else {
+                        info!("Ensured asset category dir exists: {}", category_dir.display());
                     }
                 }
             }
         }
-    } else {
-        warn!("USERPROFILE not set; cannot create user config directories");
     }
 }
@@ -7,7 +7,7 @@ use std::{
     path::{Path, PathBuf},
     io::{self},
 };
-use crate::paths::{user_home_dir, veil_root_dir};
+use crate::paths::{addons_dir, veil_root_dir};
 use crate::{info, warn, error};
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -138,11 +138,9 @@ fn resolve_content_dirs(cli: &Cli) -> Vec<PathBuf> {
             info!("Added CLI content-dir: {}", dir);
         }
     }
-    if user_home_dir().is_some() {
-        let default_root = veil_root_dir();
-        info!("Adding default content-dir: {}", default_root.display());
-        roots.push(default_root);
-    }
+    let default_root = veil_root_dir();
+    info!("Adding default content-dir: {}", default_root.display());
+    roots.push(default_root);
     roots
 }
 
@@ -202,55 +200,53 @@ pub fn bootstrap_user_root() {
 }
 
 fn route_to_addon_executable(first_arg: &str) -> Option<(PathBuf, Vec<String>)> {
-    if user_home_dir().is_some() {
-        let addons_root = veil_root_dir().join("Addons");
-        if !addons_root.is_dir() { return None; }
-
-        let mut candidates: Vec<(String, PathBuf)> = Vec::new();
-        if let Ok(entries) = fs::read_dir(&addons_root) {
-            for e in entries.flatten() {
-                let addon_dir = e.path();
-                if !addon_dir.is_dir() { continue; }
-                let folder_name = addon_dir.file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
-                let addon_json = addon_dir.join("addon.json");
-                let mut resolved: Option<PathBuf> = None;
-                if addon_json.is_file() {
-                    if let Ok(text) = fs::read_to_string(&addon_json) {
-                        if let Ok(j) = json::parse(&text) {
-                            if let Some(entry) = j["entry"].as_str() {
-                                if entry.contains('*') {
-                                    let bin_dir = addon_dir.join("bin");
-                                    if let Ok(bin_entries) = fs::read_dir(&bin_dir) {
-                                        for be in bin_entries.flatten() {
-                                            let p = be.path();
-                                            if p.is_file() && p.extension().map(|e| e.eq_ignore_ascii_case("exe")).unwrap_or(false) {
-                                                resolved = Some(p);
-                                                break;
-                                            }
+    let addons_root = addons_dir();
+    if !addons_root.is_dir() { return None; }
+
+    let mut candidates: Vec<(String, PathBuf)> = Vec::new();
+    if let Ok(entries) = fs::read_dir(&addons_root) {
+        for e in entries.flatten() {
+            let addon_dir = e.path();
+            if !addon_dir.is_dir() { continue; }
+            let folder_name = addon_dir.file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let addon_json = addon_dir.join("addon.json");
+            let mut resolved: Option<PathBuf> = None;
+            if addon_json.is_file() {
+                if let Ok(text) = fs::read_to_string(&addon_json) {
+                    if let Ok(j) = json::parse(&text) {
+                        if let Some(entry) = j["entry"].as_str() {
+                            if entry.contains('*') {
+                                let bin_dir = addon_dir.join("bin");
+                                if let Ok(bin_entries) = fs::read_dir(&bin_dir) {
+                                    for be in bin_entries.flatten() {
+                                        let p = be.path();
+                                        if p.is_file() && p.extension().map(|e| e.eq_ignore_ascii_case("exe")).unwrap_or(false) {
+                                            resolved = Some(p);
+                                            break;
                                         }
                                     }
-                                } else {
-                                    let candidate = addon_dir.join(entry);
-                                    if candidate.is_file() { resolved = Some(candidate); }
                                 }
+                            } else {
+                                let candidate = addon_dir.join(entry);
+                                if candidate.is_file() { resolved = Some(candidate); }
                             }
                         }
                     }
                 }
-                if resolved.is_none() {
-                    let fallback = addon_dir.join("bin").join(format!("{}.exe", folder_name));
-                    if fallback.is_file() { resolved = Some(fallback); }
-                }
-                if let Some(exe) = resolved { candidates.push((folder_name, exe)); }
             }
+            if resolved.is_none() {
+                let fallback = addon_dir.join("bin").join(format!("{}.exe", folder_name));
+                if fallback.is_file() { resolved = Some(fallback); }
+            }
+            if let Some(exe) = resolved { candidates.push((folder_name, exe)); }
         }
+    }
 
-        for (cmd, exe) in candidates {
-            if first_arg.eq_ignore_ascii_case(&cmd) {
-                info!("Routing to addon executable: {}", exe.display());
-                let passthrough: Vec<String> = std::env::args().skip(2).collect();
-                return Some((exe, passthrough));
-            }
+    for (cmd, exe) in candidates {
+        if first_arg.eq_ignore_ascii_case(&cmd) {
+            info!("Routing to addon executable: {}", exe.display());
+            let passthrough: Vec<String> = std::env::args().skip(2).collect();
+            return Some((exe, passthrough));
         }
     }
     None
@@ -277,12 +273,23 @@ pub fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     if let Some(flag_index) = args.iter().position(|a| a == "--addon-config-ui") {
+        // Without an addon the UI reopens on the view it was last left on.
         let addon_ref = args
             .get(flag_index + 1)
-            .ok_or("Missing addon id/name after --addon-config-ui")?;
-        info!("Launching addon config UI for '{}'", addon_ref);
+            .filter(|a| !a.starts_with("--"));
+        let tab = args
+            .iter()
+            .position(|a| a == "--tab")
+            .and_then(|idx| args.get(idx + 1))
+            .map(|s| s.as_str());
+        let Some(addon_ref) = addon_ref else {
+            info!("Launching config UI on the last view");
+            crate::config_ui::run_veil_ui(None, tab)?;
+            return Ok(());
+        };
+        info!("Launching addon config UI for '{}' (tab {:?})", addon_ref, tab);
         // Fall back to legacy config_ui for addon-specific config
-        crate::config_ui::run_addon_config_ui(addon_ref)?;
+        crate::config_ui::run_addon_config_ui(addon_ref, tab)?;
         return Ok(());
     }
 
@@ -300,6 +307,14 @@ pub fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    if let Some(flag_index) = args.iter().position(|a| a == "--capture-wallpaper-preview") {
+        let manifest_path = args
+            .get(flag_index + 1)
+            .ok_or("Missing manifest path after --capture-wallpaper-preview")?;
+        crate::config_ui::run_wallpaper_preview_capture(manifest_path)?;
+        return Ok(());
+    }
+
     if std::env::args().count() == 1 {
         info!("No CLI args provided, skipping CLI execution");
         return Ok(());
@@ -352,4 +367,384 @@ pub fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
     // Remaining code uses existing eprintln!/println! and logs added similarly...
     // For brevity, every user prompt, ID validation, and print already has info/warn/error logging as shown above
     Ok(())
-}
\ No newline at end of file
+}
+/// `VEIL get <section>[.<field>...] [--pretty]`: print one sysdata value as
+/// JSON for scripts and status bars, e.g. `VEIL get cpu.usage_percent`.
+/// Returns the process exit code.
+pub fn run_get(args: &[String]) -> i32 {
+    attach_parent_console();
+
+    let pretty = args.iter().any(|a| a == "--pretty");
+    let Some(path) = args.iter().find(|a| !a.starts_with("--")) else {
+        eprintln!("usage: VEIL get <section>[.<field>...] [--pretty]");
+        return 2;
+    };
+
+    let mut segments = path.split('.');
+    let section = segments.next().unwrap_or_default();
+    let req = crate::ipc::request::IpcRequest {
+        ns: "sysdata".to_string(),
+        cmd: format!("get_{}", section),
+        args: None,
+        addon_id: None,
+        token: None,
+    };
+    let resp = match crate::ipc::request::send_ipc_request(req) {
+        Ok(resp) => resp,
+        Err(e) => {
+            eprintln!("{} (is the VEIL backend running?)", e);
+            return 1;
+        }
+    };
+    if !resp.ok {
+        eprintln!("{}", resp.error.unwrap_or_else(|| "Request failed".to_string()));
+        return 1;
+    }
+
+    let mut value = resp.data.unwrap_or(serde_json::Value::Null);
+    for field in segments {
+        let next = match field.parse::<usize>() {
+            Ok(idx) if value.is_array() => value.get(idx),
+            _ => value.get(field),
+        };
+        match next {
+            Some(v) => value = v.clone(),
+            None => {
+                eprintln!("No field '{}' in {}", field, path);
+                return 1;
+            }
+        }
+    }
+
+    let text = if pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    };
+    match text {
+        Ok(text) => {
+            println!("{}", text);
+            0
+        }
+        Err(e) => {
+            eprintln!("Failed to serialize value: {}", e);
+            1
+        }
+    }
+}
+
+/// `VEIL watch <section>[.<field>...] [--interval ms]`: keep printing a
+/// sysdata value as it changes until Ctrl+C. Returns the process exit code.
+pub fn run_watch(args: &[String]) -> i32 {
+    attach_parent_console();
+
+    const USAGE: &str = "usage: VEIL watch <section>[.<field>...] [--interval ms]";
+    let mut path = None;
+    let mut interval_ms = 1000;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--interval" => match iter.next().and_then(|v| v.parse::<u64>().ok()) {
+                Some(ms) => interval_ms = ms,
+                None => {
+                    eprintln!("{}", USAGE);
+                    return 2;
+                }
+            },
+            a if a.starts_with("--") || path.is_some() => {
+                eprintln!("{}", USAGE);
+                return 2;
+            }
+            a => path = Some(a),
+        }
+    }
+    let Some(path) = path else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+    watch::run(path, interval_ms)
+}
+
+/// Streams the value over a `sysdata.subscribe` connection, which only sends
+/// a frame when it changed.
+#[cfg(target_os = "windows")]
+mod watch {
+    use std::io::{IsTerminal, Write};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use windows::core::BOOL;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::Console::{SetConsoleCtrlHandler, CTRL_BREAK_EVENT, CTRL_C_EVENT};
+    use windows::Win32::System::IO::CancelIoEx;
+
+    use crate::ipc::request::{IpcRequest, IpcStream};
+
+    static STOP: AtomicBool = AtomicBool::new(false);
+    /// Pipe handle of the open stream, 0 when there is none.
+    static STREAM: AtomicUsize = AtomicUsize::new(0);
+
+    /// Ctrl+C and Ctrl+Break unblock the pending read instead of killing the
+    /// process, so the line can be finished first.
+    unsafe extern "system" fn on_ctrl(ctrl_type: u32) -> BOOL {
+        if ctrl_type != CTRL_C_EVENT && ctrl_type != CTRL_BREAK_EVENT {
+            return BOOL(0);
+        }
+        STOP.store(true, Ordering::Relaxed);
+        let raw = STREAM.load(Ordering::Relaxed);
+        if raw != 0 {
+            let _ = CancelIoEx(HANDLE(raw as *mut _), None);
+        }
+        BOOL(1)
+    }
+
+    /// Rewrites one line in place on a console; prints a line per value
+    /// when stdout is a file or pipe.
+    struct Line {
+        console: bool,
+        width: usize,
+    }
+
+    impl Line {
+        fn show(&mut self, text: &str) {
+            let mut out = std::io::stdout();
+            if self.console {
+                let width = text.chars().count();
+                let pad = self.width.saturating_sub(width);
+                let _ = write!(out, "\r{}{}", text, " ".repeat(pad));
+                self.width = width;
+            } else {
+                let _ = writeln!(out, "{}", text);
+            }
+            let _ = out.flush();
+        }
+
+        /// Leave the cursor on a fresh line.
+        fn finish(&mut self) {
+            if self.console && self.width > 0 {
+                println!();
+                self.width = 0;
+            }
+        }
+    }
+
+    pub fn run(path: &str, interval_ms: u64) -> i32 {
+        let req = IpcRequest {
+            ns: "sysdata".to_string(),
+            cmd: "subscribe".to_string(),
+            args: Some(serde_json::json!({ "keys": [path], "rate_ms": interval_ms })),
+            addon_id: None,
+            token: None,
+        };
+        let mut stream = match IpcStream::open(req) {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("{} (is the VEIL backend running?)", e);
+                return 1;
+            }
+        };
+        STREAM.store(stream.raw_handle(), Ordering::Relaxed);
+        unsafe {
+            let _ = SetConsoleCtrlHandler(Some(on_ctrl), true);
+        }
+
+        let mut line = Line { console: std::io::stdout().is_terminal(), width: 0 };
+        let mut acknowledged = false;
+        let code = loop {
+            let resp = match stream.next_frame() {
+                Ok(resp) => resp,
+                Err(_) if STOP.load(Ordering::Relaxed) => break 0,
+                Err(e) => {
+                    line.finish();
+                    eprintln!("{}; stopped watching {}", e, path);
+                    break 1;
+                }
+            };
+            if !resp.ok {
+                line.finish();
+                eprintln!("{}", resp.error.unwrap_or_else(|| "Request failed".to_string()));
+                break 1;
+            }
+            // The first frame acknowledges the subscription.
+            if !acknowledged {
+                acknowledged = true;
+                continue;
+            }
+            let value = resp.data.as_ref().and_then(|d| d.get("data")).and_then(|d| d.get(path));
+            if let Some(value) = value {
+                line.show(&serde_json::to_string(value).unwrap_or_default());
+            }
+        };
+
+        line.finish();
+        STREAM.store(0, Ordering::Relaxed);
+        unsafe {
+            let _ = SetConsoleCtrlHandler(Some(on_ctrl), false);
+        }
+        code
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod watch {
+    pub fn run(_path: &str, _interval_ms: u64) -> i32 {
+        eprintln!("VEIL watch needs the Windows backend");
+        1
+    }
+}
+
+/// `VEIL export <path.zip> [--with-assets]`: bundle the backend, tray and
+/// addon configs into one archive. Returns the process exit code.
+pub fn run_export(args: &[String]) -> i32 {
+    attach_parent_console();
+
+    let with_assets = args.iter().any(|a| a == "--with-assets");
+    let Some(path) = args.iter().find(|a| !a.starts_with("--")) else {
+        eprintln!("usage: VEIL export <path.zip> [--with-assets]");
+        return 2;
+    };
+
+    match crate::config_archive::export(Path::new(path), with_assets) {
+        Ok(summary) => {
+            println!("Exported to {}", path);
+            if summary.backend_config { println!("  config.yaml"); }
+            if summary.tray_settings { println!("  tray_settings.json"); }
+            for addon in &summary.addon_configs {
+                println!("  Addons/{}/config.yaml", addon);
+            }
+            if with_assets { println!("  {} asset file(s)", summary.asset_files); }
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// `VEIL import <path.zip>`: restore an archive made by `VEIL export`.
+/// Returns the process exit code.
+pub fn run_import(args: &[String]) -> i32 {
+    attach_parent_console();
+
+    let Some(path) = args.iter().find(|a| !a.starts_with("--")) else {
+        eprintln!("usage: VEIL import <path.zip>");
+        return 2;
+    };
+
+    match crate::config_archive::import(Path::new(path)) {
+        Ok(summary) => {
+            println!("Imported {}", path);
+            if let Some(issues) = &summary.backend_config {
+                println!("  config.yaml");
+                for issue in issues {
+                    println!("    corrected {}", issue);
+                }
+            }
+            if summary.tray_settings { println!("  tray_settings.json"); }
+            for addon in &summary.addon_configs {
+                println!("  Addons/{}/config.yaml", addon);
+            }
+            if summary.asset_files > 0 { println!("  {} asset file(s)", summary.asset_files); }
+            for addon in &summary.skipped_addons {
+                println!("Skipped config for '{}': addon is not installed", addon);
+            }
+            for name in &summary.ignored {
+                println!("Ignored unknown entry '{}'", name);
+            }
+            match &summary.backup_dir {
+                Some(dir) => println!("Replaced files were backed up to {}", dir.display()),
+                None => println!("No existing files were replaced"),
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Import failed: {}", e);
+            1
+        }
+    }
+}
+
+/// `VEIL diag [<folder>]`: zip the logs, configs and a sysdata snapshot for a
+/// bug report, into the Downloads folder unless another is given. Returns the
+/// process exit code.
+pub fn run_diag(args: &[String]) -> i32 {
+    attach_parent_console();
+
+    let dir = args.iter().find(|a| !a.starts_with("--")).map(Path::new);
+    match crate::diagnostics::create(dir) {
+        Ok(summary) => {
+            println!("{}", summary.path.display());
+            for name in &summary.included {
+                println!("  {}", name);
+            }
+            for note in &summary.notes {
+                println!("Note: {}", note);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// `VEIL check-migrations <addon dir>`: run the config migrations from the
+/// addon's schema.yaml over each `migrations/<name>.before.yaml` fixture and
+/// compare with `<name>.after.yaml`. Returns the process exit code.
+pub fn run_check_migrations(args: &[String]) -> i32 {
+    attach_parent_console();
+
+    let Some(dir) = args.iter().find(|a| !a.starts_with("--")) else {
+        eprintln!("usage: VEIL check-migrations <addon dir>");
+        return 2;
+    };
+    let dir = Path::new(dir);
+    let plan = match crate::config::migrations::load_plan(&dir.join("schema.yaml")) {
+        Ok(Some(plan)) => plan,
+        Ok(None) => {
+            eprintln!("{} declares no config_version", dir.join("schema.yaml").display());
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    match crate::config::migrations::check_fixtures(&plan, &dir.join("migrations")) {
+        Ok((0, _)) => {
+            eprintln!("No *.before.yaml fixtures in {}", dir.join("migrations").display());
+            1
+        }
+        Ok((checked, failures)) => {
+            for (name, problem) in &failures {
+                println!("FAIL {}: {}", name, problem);
+            }
+            println!("{} of {} fixture(s) passed", checked - failures.len(), checked);
+            if failures.is_empty() { 0 } else { 1 }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// The binary is built for the GUI subsystem, so it starts without a console.
+/// Borrow the parent's console unless stdout was already redirected to a file
+/// or pipe.
+#[cfg(target_os = "windows")]
+fn attach_parent_console() {
+    use windows::Win32::System::Console::{AttachConsole, GetStdHandle, ATTACH_PARENT_PROCESS, STD_OUTPUT_HANDLE};
+    unsafe {
+        let redirected = GetStdHandle(STD_OUTPUT_HANDLE)
+            .map(|h| !h.is_invalid() && !h.0.is_null())
+            .unwrap_or(false);
+        if !redirected {
+            let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_parent_console() {}
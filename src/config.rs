@@ -1,15 +1,24 @@
 // ~/veil/veil-backend/src/config.rs
 
+pub mod migrations;
+pub mod yaml_path;
+
 use serde::{Deserialize, Serialize};
+use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    ops::RangeInclusive,
     path::PathBuf,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
-        OnceLock, RwLock,
+        mpsc::channel,
+        Mutex, OnceLock, RwLock,
     },
+    time::Duration,
 };
 use crate::{info, warn, error};
-use crate::paths::veil_root_dir;
+use crate::paths::config_yaml_path;
 
 /// Backend configuration persisted in config.yaml next to the executable.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,14 +43,257 @@ pub struct BackendConfig {
     #[serde(default = "default_true")]
     pub ui_data_exception_enabled: bool,
 
+    /// Reject IPC requests from addons that call commands outside their declared capabilities.
+    #[serde(default = "default_false")]
+    pub enforce_addon_permissions: bool,
+
+    /// Let the updater watchdog respawn a tier thread that stops ticking.
+    #[serde(default = "default_true")]
+    pub restart_stalled_updaters: bool,
+
+    /// Keep assets whose id collides with another by appending their folder name,
+    /// instead of hiding all but the first.
+    #[serde(default = "default_false")]
+    pub disambiguate_asset_ids: bool,
+
+    /// Gzip the on-disk registry snapshot to `registry.json.gz`.
+    #[serde(default = "default_false")]
+    pub compress_snapshot: bool,
+
+    /// Report `network.connectivity` from Windows' own internet check (NLM).
+    #[serde(default = "default_false")]
+    pub connectivity_check: bool,
+
+    /// Poll interval (ms) per section, overriding its tier's rate (`cpu: 250`).
+    #[serde(default)]
+    pub pull_rate_overrides: BTreeMap<String, u64>,
+
+    /// Monthly data cap (MB) for metered connections; 0 disables tracking warnings.
+    #[serde(default)]
+    pub data_cap_mb: u64,
+
+    /// Percentage of the data cap at which `data_cap_warning` fires.
+    #[serde(default = "default_data_cap_warn_percent")]
+    pub data_cap_warn_percent: u64,
+
+    /// Exponential smoothing per metric path (`cpu.usage_percent: 0.3`). The factor is
+    /// the weight of each new sample; listed metrics gain a `*_smoothed` sibling.
+    #[serde(default)]
+    pub smoothing: BTreeMap<String, f64>,
+
+    /// How long (seconds) a `wifi.scan` result is reused before netsh is run again.
+    #[serde(default = "default_wifi_scan_cache_secs")]
+    pub wifi_scan_cache_secs: u64,
+
+    /// How long (ms) `addon.stop` waits for an addon to exit after asking it
+    /// to shut down before the process is killed.
+    #[serde(default = "default_addon_stop_grace_ms")]
+    pub addon_stop_grace_ms: u64,
+
+    /// How long (ms) an IPC client waits to connect and for a reply before
+    /// giving up with `TIMEOUT`.
+    #[serde(default = "default_ipc_timeout_ms")]
+    pub ipc_timeout_ms: u64,
+
+    /// Browser origins besides localhost that may call the HTTP bridge,
+    /// e.g. `http://192.168.1.20:5500`.
+    #[serde(default)]
+    pub http_bridge_origins: Vec<String>,
+
+    /// Idle time (seconds) at which `idle.idle_state` turns `idle`, then `away`.
+    #[serde(default)]
+    pub idle_thresholds: IdleThresholds,
+
+    /// Which addons under `Addons/` are surfaced to the tray and UI.
+    #[serde(default)]
+    pub addons: AddonFilterConfig,
+
+    /// Which processes may appear in the processes panel's top lists.
+    #[serde(default)]
+    pub processes: ProcessFilterConfig,
+
+    /// Global hotkey that opens or focuses the UI, e.g. `Ctrl+Alt+V`.
+    /// Empty disables it.
+    #[serde(default = "default_ui_hotkey")]
+    pub ui_hotkey: String,
+
+    /// Colour scheme of the config UI and its webview shell.
+    #[serde(default)]
+    pub theme: UiTheme,
+
+    /// Most verbose level written to the log file.
+    #[serde(default)]
+    pub log_level: LogLevel,
+
     // -- back-compat: silently absorb the old single-rate field if present --
     #[serde(default, skip_serializing)]
     #[allow(dead_code)]
     data_pull_rate_ms: Option<u64>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IdleThresholds {
+    #[serde(default = "default_idle_secs")]
+    pub idle_secs: u64,
+    #[serde(default = "default_away_secs")]
+    pub away_secs: u64,
+}
+
+impl Default for IdleThresholds {
+    fn default() -> Self {
+        Self { idle_secs: default_idle_secs(), away_secs: default_away_secs() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UiTheme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl UiTheme {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UiTheme::Dark => "dark",
+            UiTheme::Light => "light",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "dark" => Some(UiTheme::Dark),
+            "light" => Some(UiTheme::Light),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(LogLevel::Error),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            _ => None,
+        }
+    }
+
+    pub fn filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+        }
+    }
+}
+
+/// Allow/deny list applied to addon discovery.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AddonFilterConfig {
+    /// Addon ids (or folder names) hidden from discovery.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+
+    /// When set, only addons listed in `enabled` are discovered.
+    #[serde(default)]
+    pub enabled_only: bool,
+
+    /// Explicitly enabled addons; only consulted when `enabled_only` is set.
+    #[serde(default)]
+    pub enabled: Vec<String>,
+}
+
+impl AddonFilterConfig {
+    /// Whether an addon (matched by id or folder name, case-insensitive) should be discovered.
+    pub fn allows(&self, id: &str, folder: &str) -> bool {
+        let matches = |list: &[String]| {
+            list.iter().any(|e| {
+                (!id.is_empty() && e.eq_ignore_ascii_case(id)) || e.eq_ignore_ascii_case(folder)
+            })
+        };
+        if matches(&self.disabled) {
+            return false;
+        }
+        !self.enabled_only || matches(&self.enabled)
+    }
+}
+
+/// Ignore/include lists for `processes.top_cpu` and `processes.top_memory`.
+/// Entries match the executable name case-insensitively, with or without
+/// `.exe`; a trailing `*` matches any name starting with the rest.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProcessFilterConfig {
+    #[serde(default)]
+    pub ignore: Vec<String>,
+
+    /// When non-empty, only matching processes are listed and `ignore` is not consulted.
+    #[serde(default)]
+    pub include_only: Vec<String>,
+}
+
+impl ProcessFilterConfig {
+    pub fn is_empty(&self) -> bool {
+        self.ignore.is_empty() && self.include_only.is_empty()
+    }
+
+    /// Whether a process named `name` (e.g. `chrome.exe`) passes the filter.
+    pub fn allows(&self, name: &str) -> bool {
+        let name = name.to_ascii_lowercase();
+        let stem = name.strip_suffix(".exe").unwrap_or(&name);
+        let matches = |list: &[String]| {
+            list.iter().any(|pattern| {
+                let pattern = pattern.trim().to_ascii_lowercase();
+                match pattern.strip_suffix('*') {
+                    Some(prefix) => name.starts_with(prefix),
+                    None => name == pattern || stem == pattern,
+                }
+            })
+        };
+        if !self.include_only.is_empty() {
+            return matches(&self.include_only);
+        }
+        !matches(&self.ignore)
+    }
+}
+
+/// Accepted pull rates (ms). Values outside are clamped on load and by the setters.
+pub const FAST_RATE_RANGE: RangeInclusive<u64> = 10..=5000;
+pub const SLOW_RATE_RANGE: RangeInclusive<u64> = 100..=60000;
+pub const ADDON_STOP_GRACE_RANGE: RangeInclusive<u64> = 0..=30000;
+pub const IPC_TIMEOUT_RANGE: RangeInclusive<u64> = 100..=60000;
+
 fn default_fast_rate() -> u64 { 50 }
 fn default_slow_rate() -> u64 { 1000 }
+fn default_data_cap_warn_percent() -> u64 { 90 }
+fn default_wifi_scan_cache_secs() -> u64 { 30 }
+fn default_addon_stop_grace_ms() -> u64 { 3000 }
+fn default_ipc_timeout_ms() -> u64 { 2000 }
+fn default_ui_hotkey() -> String { "Ctrl+Alt+V".to_string() }
+fn default_idle_secs() -> u64 { 300 }
+fn default_away_secs() -> u64 { 900 }
 fn default_false()     -> bool { false }
 fn default_true()      -> bool { true }
 
@@ -53,6 +305,25 @@ impl Default for BackendConfig {
             data_pull_paused: false,
             refresh_on_request: default_false(),
             ui_data_exception_enabled: default_true(),
+            enforce_addon_permissions: default_false(),
+            restart_stalled_updaters: default_true(),
+            disambiguate_asset_ids: default_false(),
+            compress_snapshot: default_false(),
+            connectivity_check: default_false(),
+            pull_rate_overrides: BTreeMap::new(),
+            data_cap_mb: 0,
+            data_cap_warn_percent: default_data_cap_warn_percent(),
+            smoothing: BTreeMap::new(),
+            wifi_scan_cache_secs: default_wifi_scan_cache_secs(),
+            addon_stop_grace_ms: default_addon_stop_grace_ms(),
+            ipc_timeout_ms: default_ipc_timeout_ms(),
+            http_bridge_origins: Vec::new(),
+            idle_thresholds: IdleThresholds::default(),
+            addons: AddonFilterConfig::default(),
+            processes: ProcessFilterConfig::default(),
+            ui_hotkey: default_ui_hotkey(),
+            theme: UiTheme::default(),
+            log_level: LogLevel::default(),
             data_pull_rate_ms: None,
         }
     }
@@ -65,16 +336,63 @@ static SLOW_PULL_RATE_MS: AtomicU64  = AtomicU64::new(1000);
 static PULL_PAUSED:       AtomicBool = AtomicBool::new(false);
 static REFRESH_ON_REQ:    AtomicBool = AtomicBool::new(false);
 static UI_DATA_EXCEPTION_ENABLED: AtomicBool = AtomicBool::new(true);
+static ENFORCE_ADDON_PERMISSIONS: AtomicBool = AtomicBool::new(false);
+static RESTART_STALLED_UPDATERS: AtomicBool = AtomicBool::new(true);
+static DISAMBIGUATE_ASSET_IDS: AtomicBool = AtomicBool::new(false);
+static COMPRESS_SNAPSHOT: AtomicBool = AtomicBool::new(false);
+static CONNECTIVITY_CHECK: AtomicBool = AtomicBool::new(false);
+static DATA_CAP_MB: AtomicU64 = AtomicU64::new(0);
+static DATA_CAP_WARN_PERCENT: AtomicU64 = AtomicU64::new(90);
+static WIFI_SCAN_CACHE_SECS: AtomicU64 = AtomicU64::new(30);
+static ADDON_STOP_GRACE_MS: AtomicU64 = AtomicU64::new(3000);
+static IPC_TIMEOUT_MS: AtomicU64 = AtomicU64::new(2000);
+/// Set by `load_config`; until then the atomics above hold defaults.
+static CONFIG_LOADED: AtomicBool = AtomicBool::new(false);
 
 pub fn fast_pull_rate_ms() -> u64    { FAST_PULL_RATE_MS.load(Ordering::Relaxed) }
 pub fn slow_pull_rate_ms() -> u64    { SLOW_PULL_RATE_MS.load(Ordering::Relaxed) }
 pub fn pull_paused()       -> bool   { PULL_PAUSED.load(Ordering::Relaxed) }
 pub fn refresh_on_request() -> bool  { REFRESH_ON_REQ.load(Ordering::Relaxed) }
 pub fn ui_data_exception_enabled() -> bool { UI_DATA_EXCEPTION_ENABLED.load(Ordering::Relaxed) }
+pub fn enforce_addon_permissions() -> bool { ENFORCE_ADDON_PERMISSIONS.load(Ordering::Relaxed) }
+pub fn restart_stalled_updaters() -> bool { RESTART_STALLED_UPDATERS.load(Ordering::Relaxed) }
+pub fn disambiguate_asset_ids() -> bool { DISAMBIGUATE_ASSET_IDS.load(Ordering::Relaxed) }
+pub fn compress_snapshot() -> bool { COMPRESS_SNAPSHOT.load(Ordering::Relaxed) }
+pub fn connectivity_check() -> bool { CONNECTIVITY_CHECK.load(Ordering::Relaxed) }
+pub fn data_cap_mb() -> u64 { DATA_CAP_MB.load(Ordering::Relaxed) }
+pub fn data_cap_warn_percent() -> u64 { DATA_CAP_WARN_PERCENT.load(Ordering::Relaxed) }
+pub fn wifi_scan_cache_secs() -> u64 { WIFI_SCAN_CACHE_SECS.load(Ordering::Relaxed) }
+pub fn addon_stop_grace_ms() -> u64 { ADDON_STOP_GRACE_MS.load(Ordering::Relaxed) }
+pub fn ipc_timeout_ms() -> u64 { IPC_TIMEOUT_MS.load(Ordering::Relaxed) }
+
+/// config.yaml as read once by a process that never ran `load_config`.
+fn client_disk_config() -> &'static BackendConfig {
+    static FROM_DISK: OnceLock<BackendConfig> = OnceLock::new();
+    FROM_DISK.get_or_init(|| read_config_from_disk().unwrap_or_default())
+}
+
+/// `ipc_timeout_ms` for the IPC client side. Processes that ran
+/// `load_config` (the daemon, the UI) use the live value; the CLI never does,
+/// so it reads config.yaml once instead of using the default.
+pub fn client_ipc_timeout_ms() -> u64 {
+    if CONFIG_LOADED.load(Ordering::Relaxed) {
+        return ipc_timeout_ms();
+    }
+    client_disk_config().ipc_timeout_ms
+}
+
+/// `addon_stop_grace_ms` for the IPC client side, resolved like
+/// `client_ipc_timeout_ms`.
+pub fn client_addon_stop_grace_ms() -> u64 {
+    if CONFIG_LOADED.load(Ordering::Relaxed) {
+        return addon_stop_grace_ms();
+    }
+    client_disk_config().addon_stop_grace_ms
+}
 
 /// Set the fast-tier pull rate at runtime and persist to disk.
 pub fn set_fast_pull_rate_ms(ms: u64) {
-    let clamped = ms.min(5000);
+    let clamped = ms.clamp(*FAST_RATE_RANGE.start(), *FAST_RATE_RANGE.end());
     FAST_PULL_RATE_MS.store(clamped, Ordering::Relaxed);
     update_and_save(|cfg| cfg.fast_pull_rate_ms = clamped);
     info!("Fast pull rate set to {}ms", clamped);
@@ -83,7 +401,7 @@ pub fn set_fast_pull_rate_ms(ms: u64) {
 
 /// Set the slow-tier pull rate at runtime and persist to disk.
 pub fn set_slow_pull_rate_ms(ms: u64) {
-    let clamped = ms.min(10000);
+    let clamped = ms.clamp(*SLOW_RATE_RANGE.start(), *SLOW_RATE_RANGE.end());
     SLOW_PULL_RATE_MS.store(clamped, Ordering::Relaxed);
     update_and_save(|cfg| cfg.slow_pull_rate_ms = clamped);
     info!("Slow pull rate set to {}ms", clamped);
@@ -112,6 +430,223 @@ pub fn set_ui_data_exception_enabled(enabled: bool) {
     info!("UI data exception enabled: {}", enabled);
 }
 
+/// Turn addon capability enforcement on/off at runtime and persist to disk.
+pub fn set_enforce_addon_permissions(enabled: bool) {
+    ENFORCE_ADDON_PERMISSIONS.store(enabled, Ordering::Relaxed);
+    update_and_save(|cfg| cfg.enforce_addon_permissions = enabled);
+    info!("Addon permission enforcement: {}", enabled);
+}
+
+/// Allow/forbid the watchdog to restart stalled updater threads.
+pub fn set_restart_stalled_updaters(enabled: bool) {
+    RESTART_STALLED_UPDATERS.store(enabled, Ordering::Relaxed);
+    update_and_save(|cfg| cfg.restart_stalled_updaters = enabled);
+    info!("Restart stalled updaters: {}", enabled);
+}
+
+/// Toggle folder-name suffixes for assets with duplicate ids.
+pub fn set_disambiguate_asset_ids(enabled: bool) {
+    DISAMBIGUATE_ASSET_IDS.store(enabled, Ordering::Relaxed);
+    update_and_save(|cfg| cfg.disambiguate_asset_ids = enabled);
+    info!("Disambiguate duplicate asset ids: {}", enabled);
+}
+
+/// Switch the registry snapshot between `registry.json` and `registry.json.gz`.
+pub fn set_compress_snapshot(enabled: bool) {
+    COMPRESS_SNAPSHOT.store(enabled, Ordering::Relaxed);
+    update_and_save(|cfg| cfg.compress_snapshot = enabled);
+    info!("Compress registry snapshot: {}", enabled);
+}
+
+/// Toggle the `network.connectivity` field.
+pub fn set_connectivity_check(enabled: bool) {
+    CONNECTIVITY_CHECK.store(enabled, Ordering::Relaxed);
+    update_and_save(|cfg| cfg.connectivity_check = enabled);
+    info!("Connectivity check: {}", enabled);
+}
+
+/// Set the monthly metered data cap (0 = off) and warning threshold, and persist to disk.
+pub fn set_data_cap(cap_mb: u64, warn_percent: u64) {
+    let warn_percent = warn_percent.clamp(1, 100);
+    DATA_CAP_MB.store(cap_mb, Ordering::Relaxed);
+    DATA_CAP_WARN_PERCENT.store(warn_percent, Ordering::Relaxed);
+    update_and_save(|cfg| {
+        cfg.data_cap_mb = cap_mb;
+        cfg.data_cap_warn_percent = warn_percent;
+    });
+    info!("Data cap set to {} MB (warn at {}%)", cap_mb, warn_percent);
+}
+
+/// Set how long Wi-Fi scan results are cached and persist to disk.
+pub fn set_wifi_scan_cache_secs(secs: u64) {
+    let clamped = secs.min(3600);
+    WIFI_SCAN_CACHE_SECS.store(clamped, Ordering::Relaxed);
+    update_and_save(|cfg| cfg.wifi_scan_cache_secs = clamped);
+    info!("Wi-Fi scan cache set to {}s", clamped);
+}
+
+/// Set how long stopping an addon waits for it to exit on its own and persist to disk.
+pub fn set_addon_stop_grace_ms(ms: u64) {
+    let clamped = ms.min(*ADDON_STOP_GRACE_RANGE.end());
+    ADDON_STOP_GRACE_MS.store(clamped, Ordering::Relaxed);
+    update_and_save(|cfg| cfg.addon_stop_grace_ms = clamped);
+    info!("Addon stop grace period set to {}ms", clamped);
+}
+
+/// Set how long IPC clients wait for the backend and persist to disk.
+pub fn set_ipc_timeout_ms(ms: u64) {
+    let clamped = ms.clamp(*IPC_TIMEOUT_RANGE.start(), *IPC_TIMEOUT_RANGE.end());
+    IPC_TIMEOUT_MS.store(clamped, Ordering::Relaxed);
+    update_and_save(|cfg| cfg.ipc_timeout_ms = clamped);
+    info!("IPC timeout set to {}ms", clamped);
+}
+
+/// Thresholds used to classify `idle_state`. A config with `away_secs` at or
+/// below `idle_secs` falls back to the defaults.
+pub fn idle_thresholds() -> IdleThresholds {
+    let t = global_config().read().unwrap().idle_thresholds;
+    if t.away_secs > t.idle_secs { t } else { IdleThresholds::default() }
+}
+
+/// Set the idle/away thresholds (seconds) and persist to disk.
+pub fn set_idle_thresholds(idle_secs: u64, away_secs: u64) {
+    update_and_save(|cfg| cfg.idle_thresholds = IdleThresholds { idle_secs, away_secs });
+    info!("Idle thresholds set to idle {}s, away {}s", idle_secs, away_secs);
+}
+
+/// Colour scheme for the config UI.
+pub fn ui_theme() -> UiTheme {
+    global_config().read().unwrap().theme
+}
+
+/// Set the config UI colour scheme and persist to disk.
+pub fn set_ui_theme(theme: UiTheme) {
+    update_and_save(|cfg| cfg.theme = theme);
+    info!("UI theme set to {}", theme.as_str());
+}
+
+/// Most verbose level written to the log file.
+pub fn log_level() -> LogLevel {
+    global_config().read().unwrap().log_level
+}
+
+/// Set the log level, apply it to the running logger and persist to disk.
+pub fn set_log_level(level: LogLevel) {
+    update_and_save(|cfg| cfg.log_level = level);
+    crate::logging::set_level(level.filter());
+    info!("Log level set to {}", level.as_str());
+}
+
+/// Override for `section`'s poll interval, if one is configured.
+pub fn pull_rate_override(section: &str) -> Option<u64> {
+    global_config().read().unwrap().pull_rate_overrides.get(section).copied()
+}
+
+/// Configured per-section poll intervals.
+pub fn pull_rate_overrides() -> BTreeMap<String, u64> {
+    global_config().read().unwrap().pull_rate_overrides.clone()
+}
+
+/// Clamp range for a section's override: the range of the tier it runs in.
+fn section_rate_range(section: &str) -> RangeInclusive<u64> {
+    if crate::ipc::data_updater::is_fast_rate_section(section) {
+        FAST_RATE_RANGE
+    } else {
+        SLOW_RATE_RANGE
+    }
+}
+
+/// Poll `section` (normalized) every `ms`, or at its tier's rate again when `ms` is `None`.
+pub fn set_pull_rate_override(section: &str, ms: Option<u64>) {
+    let range = section_rate_range(section);
+    let ms = ms.map(|ms| ms.clamp(*range.start(), *range.end()));
+    update_and_save(|cfg| match ms {
+        Some(ms) => { cfg.pull_rate_overrides.insert(section.to_string(), ms); }
+        None => { cfg.pull_rate_overrides.remove(section); }
+    });
+    match ms {
+        Some(ms) => info!("Pull rate for {} set to {}ms", section, ms),
+        None => info!("Pull rate override cleared for {}", section),
+    }
+    crate::ipc::data_updater::wake_updaters();
+}
+
+/// Configured smoothing factors, keyed by metric path.
+pub fn smoothing() -> BTreeMap<String, f64> {
+    global_config().read().unwrap().smoothing.clone()
+}
+
+/// Smooth `metric` with `factor` (0–1], or stop smoothing it when `factor` is `None`.
+pub fn set_smoothing(metric: &str, factor: Option<f64>) {
+    let factor = factor.map(|f| f.clamp(0.01, 1.0));
+    update_and_save(|cfg| match factor {
+        Some(f) => { cfg.smoothing.insert(metric.to_string(), f); }
+        None => { cfg.smoothing.remove(metric); }
+    });
+    match factor {
+        Some(f) => info!("Smoothing {} with factor {}", metric, f),
+        None => info!("Smoothing disabled for {}", metric),
+    }
+}
+
+/// Enable or disable a single addon in discovery and persist to disk.
+pub fn set_addon_enabled(addon_id: &str, enabled: bool) {
+    update_and_save(|cfg| {
+        let filter = &mut cfg.addons;
+        filter.disabled.retain(|e| !e.eq_ignore_ascii_case(addon_id));
+        filter.enabled.retain(|e| !e.eq_ignore_ascii_case(addon_id));
+        if enabled {
+            filter.enabled.push(addon_id.to_string());
+        } else {
+            filter.disabled.push(addon_id.to_string());
+        }
+    });
+    info!("Addon '{}' enabled: {}", addon_id, enabled);
+}
+
+/// Toggle `enabled_only` discovery mode and persist to disk.
+pub fn set_addons_enabled_only(enabled_only: bool) {
+    update_and_save(|cfg| cfg.addons.enabled_only = enabled_only);
+    info!("Addon discovery enabled-only mode: {}", enabled_only);
+}
+
+/// Addon discovery filter from the in-memory config.
+pub fn addon_filter() -> AddonFilterConfig {
+    global_config().read().unwrap().addons.clone()
+}
+
+/// Replace the processes panel filter and persist to disk.
+pub fn set_process_filter(filter: ProcessFilterConfig) {
+    info!("Process filter: ignore {:?}, include_only {:?}", filter.ignore, filter.include_only);
+    update_and_save(|cfg| cfg.processes = filter);
+}
+
+/// Processes panel filter from the in-memory config.
+pub fn process_filter() -> ProcessFilterConfig {
+    global_config().read().unwrap().processes.clone()
+}
+
+/// Extra origins the HTTP bridge accepts browser requests from.
+pub fn http_bridge_origins() -> Vec<String> {
+    global_config().read().unwrap().http_bridge_origins.clone()
+}
+
+/// The configured UI hotkey, empty when disabled.
+pub fn ui_hotkey() -> String {
+    global_config().read().unwrap().ui_hotkey.clone()
+}
+
+/// Set the UI hotkey, re-register it and persist to disk. An empty string
+/// turns it off.
+pub fn set_ui_hotkey(hotkey: &str) -> Result<(), String> {
+    crate::hotkey::parse(hotkey)?;
+    let hotkey = hotkey.trim().to_string();
+    info!("UI hotkey set to '{}'", hotkey);
+    update_and_save(|cfg| cfg.ui_hotkey = hotkey);
+    crate::hotkey::reload();
+    Ok(())
+}
+
 // ── Persistent on-disk config ──
 
 static CONFIG: OnceLock<RwLock<BackendConfig>> = OnceLock::new();
@@ -121,7 +656,7 @@ fn global_config() -> &'static RwLock<BackendConfig> {
 }
 
 fn config_path() -> PathBuf {
-    veil_root_dir().join("config.yaml")
+    config_yaml_path()
 }
 
 /// Load config.yaml from disk (or create defaults). Call once at startup.
@@ -130,9 +665,12 @@ pub fn load_config() -> BackendConfig {
 
     let cfg = if path.exists() {
         match std::fs::read_to_string(&path) {
-            Ok(text) => match serde_yaml::from_str::<BackendConfig>(&text) {
-                Ok(c) => {
+            Ok(text) => match parse_and_validate(&text) {
+                Ok((c, issues)) => {
                     info!("Loaded backend config from {}", path.display());
+                    for issue in &issues {
+                        warn!("config.yaml: {}: {}", issue.field, issue.message);
+                    }
                     c
                 }
                 Err(e) => {
@@ -153,11 +691,23 @@ pub fn load_config() -> BackendConfig {
     };
 
     // Sync atomics
-    FAST_PULL_RATE_MS.store(cfg.fast_pull_rate_ms.min(5000), Ordering::Relaxed);
-    SLOW_PULL_RATE_MS.store(cfg.slow_pull_rate_ms.min(10000), Ordering::Relaxed);
+    FAST_PULL_RATE_MS.store(cfg.fast_pull_rate_ms, Ordering::Relaxed);
+    SLOW_PULL_RATE_MS.store(cfg.slow_pull_rate_ms, Ordering::Relaxed);
     PULL_PAUSED.store(cfg.data_pull_paused, Ordering::Relaxed);
     REFRESH_ON_REQ.store(cfg.refresh_on_request, Ordering::Relaxed);
     UI_DATA_EXCEPTION_ENABLED.store(cfg.ui_data_exception_enabled, Ordering::Relaxed);
+    ENFORCE_ADDON_PERMISSIONS.store(cfg.enforce_addon_permissions, Ordering::Relaxed);
+    RESTART_STALLED_UPDATERS.store(cfg.restart_stalled_updaters, Ordering::Relaxed);
+    DISAMBIGUATE_ASSET_IDS.store(cfg.disambiguate_asset_ids, Ordering::Relaxed);
+    COMPRESS_SNAPSHOT.store(cfg.compress_snapshot, Ordering::Relaxed);
+    CONNECTIVITY_CHECK.store(cfg.connectivity_check, Ordering::Relaxed);
+    DATA_CAP_MB.store(cfg.data_cap_mb, Ordering::Relaxed);
+    DATA_CAP_WARN_PERCENT.store(cfg.data_cap_warn_percent, Ordering::Relaxed);
+    WIFI_SCAN_CACHE_SECS.store(cfg.wifi_scan_cache_secs, Ordering::Relaxed);
+    ADDON_STOP_GRACE_MS.store(cfg.addon_stop_grace_ms, Ordering::Relaxed);
+    IPC_TIMEOUT_MS.store(cfg.ipc_timeout_ms, Ordering::Relaxed);
+    CONFIG_LOADED.store(true, Ordering::Relaxed);
+    crate::logging::set_level(cfg.log_level.filter());
 
     // Store in global
     *global_config().write().unwrap() = cfg.clone();
@@ -165,6 +715,13 @@ pub fn load_config() -> BackendConfig {
     cfg
 }
 
+/// Parse config.yaml from disk without touching the in-memory state.
+/// Used by UI processes that don't own the config (the daemon does).
+pub fn read_config_from_disk() -> Option<BackendConfig> {
+    let text = std::fs::read_to_string(config_path()).ok()?;
+    parse_and_validate(&text).ok().map(|(cfg, _)| cfg)
+}
+
 /// Return a snapshot of the current in-memory config.
 pub fn current_config() -> BackendConfig {
     global_config().read().unwrap().clone()
@@ -173,17 +730,480 @@ pub fn current_config() -> BackendConfig {
 fn update_and_save(f: impl FnOnce(&mut BackendConfig)) {
     let mut cfg = global_config().write().unwrap();
     f(&mut cfg);
-    save_config_to_disk(&cfg);
+    if !APPLYING_RELOAD.with(Cell::get) {
+        save_config_to_disk(&cfg);
+    }
 }
 
 fn save_config_to_disk(cfg: &BackendConfig) {
     let path = config_path();
     match serde_yaml::to_string(cfg) {
         Ok(text) => {
-            if let Err(e) = std::fs::write(&path, text) {
+            if let Err(e) = std::fs::write(&path, &text) {
                 error!("Failed to write config.yaml: {e}");
             }
+            *LAST_WRITTEN.lock().unwrap() = text;
         }
         Err(e) => error!("Failed to serialize config: {e}"),
     }
 }
+
+// ── Hot reload ──
+
+/// Quiet period after the last change before config.yaml is re-read.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Text of the last config.yaml this process wrote (or reloaded). A change
+/// event whose file still matches it is our own write and is ignored.
+static LAST_WRITTEN: Mutex<String> = Mutex::new(String::new());
+
+thread_local! {
+    /// Set while the watcher applies a reload: the file already holds the new
+    /// values, so the setters must not write it back.
+    static APPLYING_RELOAD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Watch config.yaml and apply hand edits live. Daemon only.
+pub fn start_config_watcher() {
+    let spawned = std::thread::Builder::new()
+        .name("veil-config-watch".into())
+        .spawn(|| {
+            if let Err(e) = config_watcher() {
+                error!("Config watcher failed: {e}");
+            }
+        });
+    if let Err(e) = spawned {
+        error!("Failed to spawn config watcher: {e}");
+    }
+}
+
+fn config_watcher() -> Result<(), Box<dyn std::error::Error>> {
+    let path = config_path();
+    let dir = path.parent().ok_or("config.yaml has no parent directory")?.to_path_buf();
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher =
+        Watcher::new(tx, Config::default().with_poll_interval(Duration::from_millis(250)))?;
+    // Watch the directory, not the file: editors often save by replacing it.
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+    info!("Watching '{}' for changes", path.display());
+
+    loop {
+        match rx.recv() {
+            Ok(Ok(event)) => {
+                let touches_config = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                    && event.paths.iter().any(|p| p.file_name() == path.file_name());
+                if !touches_config {
+                    continue;
+                }
+                // Editors and our own saves can fire several events per write.
+                while rx.recv_timeout(RELOAD_DEBOUNCE).is_ok() {}
+                reload_from_disk();
+            }
+            Ok(Err(e)) => error!("[ConfigWatcher] notify error: {e}"),
+            Err(_) => return Ok(()),
+        }
+    }
+}
+
+fn reload_from_disk() {
+    // Compare under the config lock: saves happen while it is held, so the
+    // file and LAST_WRITTEN can't be caught mid-update.
+    let text = {
+        let _cfg = global_config().read().unwrap();
+        let Ok(text) = std::fs::read_to_string(config_path()) else { return };
+        if text.trim().is_empty() || text == *LAST_WRITTEN.lock().unwrap() {
+            return;
+        }
+        text
+    };
+
+    let new = match parse_and_validate(&text) {
+        Ok((c, issues)) => {
+            for issue in &issues {
+                warn!("config.yaml: {}: {}", issue.field, issue.message);
+            }
+            c
+        }
+        Err(e) => {
+            warn!("Ignoring config.yaml change, failed to parse: {e}");
+            return;
+        }
+    };
+
+    APPLYING_RELOAD.with(|f| f.set(true));
+    let changed = apply_reloaded(&new);
+    APPLYING_RELOAD.with(|f| f.set(false));
+    *LAST_WRITTEN.lock().unwrap() = text;
+
+    if !changed.is_empty() {
+        info!("Reloaded config.yaml: {} changed", changed.join(", "));
+    }
+}
+
+/// Push every field that differs from the running config through its setter.
+/// Returns the names of the fields that changed.
+fn apply_reloaded(new: &BackendConfig) -> Vec<&'static str> {
+    let old = current_config();
+    let mut changed = Vec::new();
+
+    macro_rules! apply {
+        ($field:ident, $setter:expr) => {
+            if new.$field != old.$field {
+                $setter(new.$field);
+                changed.push(stringify!($field));
+            }
+        };
+    }
+    apply!(fast_pull_rate_ms, set_fast_pull_rate_ms);
+    apply!(slow_pull_rate_ms, set_slow_pull_rate_ms);
+    apply!(data_pull_paused, set_pull_paused);
+    apply!(refresh_on_request, set_refresh_on_request);
+    apply!(ui_data_exception_enabled, set_ui_data_exception_enabled);
+    apply!(enforce_addon_permissions, set_enforce_addon_permissions);
+    apply!(restart_stalled_updaters, set_restart_stalled_updaters);
+    apply!(disambiguate_asset_ids, set_disambiguate_asset_ids);
+    apply!(compress_snapshot, set_compress_snapshot);
+    apply!(connectivity_check, set_connectivity_check);
+    apply!(wifi_scan_cache_secs, set_wifi_scan_cache_secs);
+    apply!(addon_stop_grace_ms, set_addon_stop_grace_ms);
+    apply!(ipc_timeout_ms, set_ipc_timeout_ms);
+    apply!(theme, set_ui_theme);
+    apply!(log_level, set_log_level);
+
+    if new.data_cap_mb != old.data_cap_mb || new.data_cap_warn_percent != old.data_cap_warn_percent {
+        set_data_cap(new.data_cap_mb, new.data_cap_warn_percent);
+        changed.push("data_cap");
+    }
+    if new.idle_thresholds != old.idle_thresholds {
+        set_idle_thresholds(new.idle_thresholds.idle_secs, new.idle_thresholds.away_secs);
+        changed.push("idle_thresholds");
+    }
+    if new.smoothing != old.smoothing {
+        for metric in old.smoothing.keys().filter(|m| !new.smoothing.contains_key(*m)) {
+            set_smoothing(metric, None);
+        }
+        for (metric, factor) in &new.smoothing {
+            if old.smoothing.get(metric) != Some(factor) {
+                set_smoothing(metric, Some(*factor));
+            }
+        }
+        changed.push("smoothing");
+    }
+    if new.pull_rate_overrides != old.pull_rate_overrides {
+        for section in old.pull_rate_overrides.keys().filter(|s| !new.pull_rate_overrides.contains_key(*s)) {
+            set_pull_rate_override(section, None);
+        }
+        for (section, ms) in &new.pull_rate_overrides {
+            if old.pull_rate_overrides.get(section) != Some(ms) {
+                set_pull_rate_override(section, Some(*ms));
+            }
+        }
+        changed.push("pull_rate_overrides");
+    }
+    if new.processes != old.processes {
+        set_process_filter(new.processes.clone());
+        changed.push("processes");
+    }
+    if new.ui_hotkey != old.ui_hotkey {
+        if let Err(e) = set_ui_hotkey(&new.ui_hotkey) {
+            warn!("Ignoring ui_hotkey: {}", e);
+        }
+        changed.push("ui_hotkey");
+    }
+    if new.http_bridge_origins != old.http_bridge_origins {
+        update_and_save(|cfg| cfg.http_bridge_origins = new.http_bridge_origins.clone());
+        changed.push("http_bridge_origins");
+    }
+    if new.addons != old.addons {
+        update_and_save(|cfg| cfg.addons = new.addons.clone());
+        crate::ipc::registry::reload_registry();
+        changed.push("addons");
+    }
+
+    changed
+}
+
+// ── Validation ──
+
+/// One correction made while loading config.yaml.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigIssue {
+    /// Dotted field path, e.g. `idle_thresholds.away_secs`.
+    pub field: String,
+    pub message: String,
+}
+
+fn issue(issues: &mut Vec<ConfigIssue>, field: &str, message: String) {
+    issues.push(ConfigIssue { field: field.to_string(), message });
+}
+
+#[derive(Clone, Copy)]
+enum FieldKind { U64, Bool, Theme, LogLevel }
+
+/// Scalar fields whose YAML type is coerced before deserializing.
+const TYPED_FIELDS: &[(&str, FieldKind)] = &[
+    ("fast_pull_rate_ms", FieldKind::U64),
+    ("slow_pull_rate_ms", FieldKind::U64),
+    ("data_pull_paused", FieldKind::Bool),
+    ("refresh_on_request", FieldKind::Bool),
+    ("ui_data_exception_enabled", FieldKind::Bool),
+    ("enforce_addon_permissions", FieldKind::Bool),
+    ("restart_stalled_updaters", FieldKind::Bool),
+    ("disambiguate_asset_ids", FieldKind::Bool),
+    ("compress_snapshot", FieldKind::Bool),
+    ("connectivity_check", FieldKind::Bool),
+    ("data_cap_mb", FieldKind::U64),
+    ("data_cap_warn_percent", FieldKind::U64),
+    ("wifi_scan_cache_secs", FieldKind::U64),
+    ("addon_stop_grace_ms", FieldKind::U64),
+    ("ipc_timeout_ms", FieldKind::U64),
+    ("idle_thresholds.idle_secs", FieldKind::U64),
+    ("idle_thresholds.away_secs", FieldKind::U64),
+    ("addons.enabled_only", FieldKind::Bool),
+    ("theme", FieldKind::Theme),
+    ("log_level", FieldKind::LogLevel),
+];
+
+/// Parse config.yaml text, coercing mistyped scalars and clamping values into
+/// range. Returns the usable config and every correction made; only text that
+/// isn't a YAML mapping at all is an error.
+pub fn parse_and_validate(text: &str) -> Result<(BackendConfig, Vec<ConfigIssue>), String> {
+    let mut doc: serde_yaml::Value = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+    if doc.is_null() {
+        doc = serde_yaml::Value::Mapping(Default::default());
+    }
+    if !doc.is_mapping() {
+        return Err("config.yaml must be a mapping of settings".into());
+    }
+
+    let mut issues = Vec::new();
+    coerce_types(&mut doc, &mut issues);
+    let mut cfg: BackendConfig = serde_yaml::from_value(doc).map_err(|e| e.to_string())?;
+    clamp_values(&mut cfg, &mut issues);
+    Ok((cfg, issues))
+}
+
+/// Diagnostics for config.yaml text (or the file on disk) without applying anything.
+pub fn validate_config_text(text: Option<&str>) -> Result<Vec<ConfigIssue>, String> {
+    let text = match text {
+        Some(t) => t.to_string(),
+        None => std::fs::read_to_string(config_path()).map_err(|e| format!("Failed to read config.yaml: {e}"))?,
+    };
+    parse_and_validate(&text).map(|(_, issues)| issues)
+}
+
+fn coerce_types(doc: &mut serde_yaml::Value, issues: &mut Vec<ConfigIssue>) {
+    use serde_yaml::Value as Y;
+
+    for &(path, kind) in TYPED_FIELDS {
+        let (parent_key, leaf) = match path.split_once('.') {
+            Some((p, l)) => (Some(p), l),
+            None => (None, path),
+        };
+        let parent = match parent_key {
+            Some(p) => doc.get_mut(p),
+            None => Some(&mut *doc),
+        };
+        let Some(Y::Mapping(map)) = parent else { continue };
+        let Some(slot) = map.get_mut(leaf) else { continue };
+
+        let coerced = match kind {
+            FieldKind::U64 => coerce_u64(slot),
+            FieldKind::Bool => coerce_bool(slot),
+            FieldKind::Theme => coerce_theme(slot),
+            FieldKind::LogLevel => coerce_log_level(slot),
+        };
+        match coerced {
+            Ok(None) => {}
+            Ok(Some(fixed)) => {
+                issue(issues, path, format!("{} read as {}", yaml_brief(slot), yaml_brief(&fixed)));
+                *slot = fixed;
+            }
+            Err(()) => {
+                let kind = match kind {
+                    FieldKind::U64 => "number",
+                    FieldKind::Bool => "true/false",
+                    FieldKind::Theme => "theme (dark/light)",
+                    FieldKind::LogLevel => "log level (error/warn/info/debug)",
+                };
+                issue(issues, path, format!("{} is not a {}, using the default", yaml_brief(slot), kind));
+                map.remove(leaf);
+            }
+        }
+    }
+
+    // Pull rate overrides: same coercion as the global rates, unusable entries are dropped.
+    if let Some(Y::Mapping(map)) = doc.get_mut("pull_rate_overrides") {
+        let mut dropped = Vec::new();
+        for (key, value) in map.iter_mut() {
+            let field = format!("pull_rate_overrides.{}", key.as_str().unwrap_or_default());
+            match coerce_u64(value) {
+                Ok(None) => {}
+                Ok(Some(fixed)) => {
+                    issue(issues, &field, format!("{} read as {}", yaml_brief(value), yaml_brief(&fixed)));
+                    *value = fixed;
+                }
+                Err(()) => {
+                    issue(issues, &field, format!("{} is not a number, removed", yaml_brief(value)));
+                    dropped.push(key.clone());
+                }
+            }
+        }
+        for key in dropped {
+            map.remove(&key);
+        }
+    }
+
+    // Smoothing factors: numeric strings are read as numbers, anything else is dropped.
+    if let Some(Y::Mapping(map)) = doc.get_mut("smoothing") {
+        let mut dropped = Vec::new();
+        for (key, value) in map.iter_mut() {
+            let metric = key.as_str().unwrap_or_default().to_string();
+            if value.is_number() {
+                continue;
+            }
+            match value.as_str().and_then(|s| s.trim().parse::<f64>().ok()) {
+                Some(f) => {
+                    issue(issues, &format!("smoothing.{}", metric), format!("{} read as {}", yaml_brief(value), f));
+                    *value = Y::from(f);
+                }
+                None => {
+                    issue(issues, &format!("smoothing.{}", metric), format!("{} is not a number, removed", yaml_brief(value)));
+                    dropped.push(key.clone());
+                }
+            }
+        }
+        for key in dropped {
+            map.remove(&key);
+        }
+    }
+}
+
+/// `Ok(None)` when already valid, `Ok(Some(v))` when converted, `Err` when unusable.
+fn coerce_u64(v: &serde_yaml::Value) -> Result<Option<serde_yaml::Value>, ()> {
+    use serde_yaml::Value as Y;
+    let number = match v {
+        Y::Number(n) if n.as_u64().is_some() => return Ok(None),
+        Y::Number(n) => n.as_f64(),
+        Y::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+    match number {
+        Some(f) if f.is_finite() => Ok(Some(Y::from(f.max(0.0).round() as u64))),
+        _ => Err(()),
+    }
+}
+
+fn coerce_bool(v: &serde_yaml::Value) -> Result<Option<serde_yaml::Value>, ()> {
+    use serde_yaml::Value as Y;
+    let parsed = match v {
+        Y::Bool(_) => return Ok(None),
+        Y::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Some(true),
+            "false" | "no" | "off" | "0" => Some(false),
+            _ => None,
+        },
+        Y::Number(n) => match n.as_u64() {
+            Some(0) => Some(false),
+            Some(1) => Some(true),
+            _ => None,
+        },
+        _ => None,
+    };
+    parsed.map(|b| Some(Y::Bool(b))).ok_or(())
+}
+
+fn coerce_theme(v: &serde_yaml::Value) -> Result<Option<serde_yaml::Value>, ()> {
+    let raw = v.as_str().ok_or(())?;
+    let theme = UiTheme::parse(raw).ok_or(())?;
+    if raw == theme.as_str() {
+        Ok(None)
+    } else {
+        Ok(Some(serde_yaml::Value::from(theme.as_str())))
+    }
+}
+
+fn coerce_log_level(v: &serde_yaml::Value) -> Result<Option<serde_yaml::Value>, ()> {
+    let raw = v.as_str().ok_or(())?;
+    let level = LogLevel::parse(raw).ok_or(())?;
+    if raw == level.as_str() {
+        Ok(None)
+    } else {
+        Ok(Some(serde_yaml::Value::from(level.as_str())))
+    }
+}
+
+fn yaml_brief(v: &serde_yaml::Value) -> String {
+    match v {
+        serde_yaml::Value::Null => "empty value".to_string(),
+        serde_yaml::Value::String(s) => format!("'{}'", s),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|_| "value".to_string()),
+    }
+}
+
+fn clamp_values(cfg: &mut BackendConfig, issues: &mut Vec<ConfigIssue>) {
+    fn clamp(field: &str, value: &mut u64, range: RangeInclusive<u64>, issues: &mut Vec<ConfigIssue>) {
+        let clamped = (*value).clamp(*range.start(), *range.end());
+        if clamped != *value {
+            issue(issues, field, format!("{} is outside {}–{}, clamped to {}", value, range.start(), range.end(), clamped));
+            *value = clamped;
+        }
+    }
+    clamp("fast_pull_rate_ms", &mut cfg.fast_pull_rate_ms, FAST_RATE_RANGE, issues);
+    clamp("slow_pull_rate_ms", &mut cfg.slow_pull_rate_ms, SLOW_RATE_RANGE, issues);
+    clamp("data_cap_warn_percent", &mut cfg.data_cap_warn_percent, 1..=100, issues);
+    clamp("wifi_scan_cache_secs", &mut cfg.wifi_scan_cache_secs, 0..=3600, issues);
+    clamp("addon_stop_grace_ms", &mut cfg.addon_stop_grace_ms, ADDON_STOP_GRACE_RANGE, issues);
+    clamp("ipc_timeout_ms", &mut cfg.ipc_timeout_ms, IPC_TIMEOUT_RANGE, issues);
+
+    if let Err(e) = crate::hotkey::parse(&cfg.ui_hotkey) {
+        issue(issues, "ui_hotkey", format!("{}, using the default", e));
+        cfg.ui_hotkey = default_ui_hotkey();
+    }
+
+    let t = cfg.idle_thresholds;
+    if t.idle_secs == 0 || t.away_secs <= t.idle_secs {
+        issue(
+            issues,
+            "idle_thresholds",
+            format!("away_secs ({}) must be greater than a non-zero idle_secs ({}), using the defaults", t.away_secs, t.idle_secs),
+        );
+        cfg.idle_thresholds = IdleThresholds::default();
+    }
+
+    let overrides = std::mem::take(&mut cfg.pull_rate_overrides);
+    for (key, mut ms) in overrides {
+        let field = format!("pull_rate_overrides.{}", key);
+        let Some(section) = crate::ipc::data_updater::normalize_section(&key) else {
+            issue(issues, &field, "unknown section, ignored".into());
+            continue;
+        };
+        clamp(&field, &mut ms, section_rate_range(section), issues);
+        cfg.pull_rate_overrides.insert(section.to_string(), ms);
+    }
+
+    for (field, list) in [("processes.ignore", &mut cfg.processes.ignore), ("processes.include_only", &mut cfg.processes.include_only)] {
+        let before = list.len();
+        list.retain(|entry| !entry.trim().is_empty() && entry.trim() != "*");
+        if list.len() != before {
+            issue(issues, field, format!("removed {} empty or bare '*' pattern(s)", before - list.len()));
+        }
+    }
+
+    cfg.smoothing.retain(|metric, factor| {
+        if factor.is_finite() {
+            return true;
+        }
+        issues.push(ConfigIssue { field: format!("smoothing.{}", metric), message: "not a finite number, removed".into() });
+        false
+    });
+    for (metric, factor) in cfg.smoothing.iter_mut() {
+        let clamped = factor.clamp(0.01, 1.0);
+        if clamped != *factor {
+            issue(issues, &format!("smoothing.{}", metric), format!("{} is outside 0.01–1, clamped to {}", factor, clamped));
+            *factor = clamped;
+        }
+    }
+}
@@ -0,0 +1,231 @@
+// ~/veil/veil-backend/src/config/migrations.rs
+//
+// Versioned addon configs. An addon's schema.yaml declares the
+// `config_version` its current keys belong to and the `migrations` that lead
+// there from older ones:
+//
+//   config_version: 3
+//   migrations:
+//     - to: 2
+//       steps:
+//         - { op: rename, path: clock.fmt, to: format }
+//         - { op: set_default, path: clock.show_seconds, value: false }
+//     - to: 3
+//       steps:
+//         - { op: move, from: colors, to: appearance.colors }
+//
+// A config.yaml records the version it was written for under VERSION_KEY;
+// one without it is at version 1. Migrations run in order from the file's
+// version up to `config_version`, and a file already at or past it is left
+// alone.
+
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::path::Path;
+
+use super::yaml_path;
+
+/// Top-level key in config.yaml holding the version it is at.
+pub const VERSION_KEY: &str = "config_version";
+
+/// The migration part of a schema.yaml.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MigrationPlan {
+    #[serde(default)]
+    pub config_version: Option<u32>,
+    #[serde(default)]
+    pub migrations: Vec<Migration>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Migration {
+    /// Version the config is at once this migration has run.
+    pub to: u32,
+    #[serde(default)]
+    pub steps: Vec<Step>,
+}
+
+/// One transform. Paths are dotted (`clock.format`). A step whose source
+/// isn't in the file does nothing.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Step {
+    /// Give the key at `path` the name `to`, keeping it where it is.
+    Rename { path: String, to: String },
+    /// Move the value at `from` to `to`, replacing what is there.
+    Move { from: String, to: String },
+    /// Write `value` at `path` unless the key already exists.
+    SetDefault { path: String, value: Value },
+    /// Delete the key at `path`.
+    Remove { path: String },
+}
+
+impl Step {
+    fn apply(&self, root: &mut Value) {
+        match self {
+            Step::Rename { path, to } => {
+                let from = yaml_path::split(path);
+                if let Some(value) = yaml_path::remove(root, &from) {
+                    let mut target = from[..from.len() - 1].to_vec();
+                    target.push(to.clone());
+                    yaml_path::set(root, &target, value);
+                }
+            }
+            Step::Move { from, to } => {
+                if let Some(value) = yaml_path::remove(root, &yaml_path::split(from)) {
+                    yaml_path::set(root, &yaml_path::split(to), value);
+                }
+            }
+            Step::SetDefault { path, value } => {
+                yaml_path::ensure(root, &yaml_path::split(path), value.clone());
+            }
+            Step::Remove { path } => {
+                yaml_path::remove(root, &yaml_path::split(path));
+            }
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Step::Rename { path, to } => format!("rename {} to {}", path, to),
+            Step::Move { from, to } => format!("move {} to {}", from, to),
+            Step::SetDefault { path, .. } => format!("default {}", path),
+            Step::Remove { path } => format!("remove {}", path),
+        }
+    }
+}
+
+/// Read the migration plan from a schema.yaml. `None` when the file is
+/// missing or declares no `config_version`.
+pub fn load_plan(schema_path: &Path) -> Result<Option<MigrationPlan>, String> {
+    let Ok(text) = std::fs::read_to_string(schema_path) else { return Ok(None) };
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+    let plan: MigrationPlan = serde_yaml::from_str(&text)
+        .map_err(|e| format!("Invalid migrations in '{}': {}", schema_path.display(), e))?;
+    Ok(plan.config_version.is_some().then_some(plan))
+}
+
+/// Version `root` was written for.
+pub fn config_version(root: &Value) -> u32 {
+    root.get(VERSION_KEY)
+        .and_then(Value::as_u64)
+        .map(|v| v as u32)
+        .unwrap_or(1)
+}
+
+/// Bring `root` up to the plan's `config_version`, stamping it with the new
+/// version. Returns one line per migration applied, empty when there was
+/// nothing to do.
+pub fn migrate(root: &mut Value, plan: &MigrationPlan) -> Result<Vec<String>, String> {
+    let Some(target) = plan.config_version else { return Ok(Vec::new()) };
+    let from = config_version(root);
+    if from >= target {
+        return Ok(Vec::new());
+    }
+    if !root.is_mapping() {
+        return Err("config.yaml is not a mapping".to_string());
+    }
+
+    let mut pending: Vec<&Migration> = plan.migrations.iter().filter(|m| m.to > from && m.to <= target).collect();
+    pending.sort_by_key(|m| m.to);
+    if let Some(pair) = pending.windows(2).find(|pair| pair[0].to == pair[1].to) {
+        return Err(format!("More than one migration to version {}", pair[0].to));
+    }
+
+    let mut applied = Vec::new();
+    let mut version = from;
+    for migration in pending {
+        for step in &migration.steps {
+            step.apply(root);
+        }
+        let steps = migration.steps.iter().map(Step::describe).collect::<Vec<_>>().join(", ");
+        applied.push(format!("{} → {}: {}", version, migration.to, if steps.is_empty() { "no changes" } else { steps.as_str() }));
+        version = migration.to;
+    }
+    yaml_path::set(root, &[VERSION_KEY.to_string()], Value::Number(target.into()));
+    if applied.is_empty() {
+        applied.push(format!("{} → {}: no changes", from, target));
+    }
+    Ok(applied)
+}
+
+/// Run `plan` over each `<name>.before.yaml` in `dir` and compare the result
+/// with `<name>.after.yaml`. Returns `(fixture, problem)` for each failure,
+/// and how many fixtures were checked.
+pub fn check_fixtures(plan: &MigrationPlan, dir: &Path) -> Result<(usize, Vec<(String, String)>), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read '{}': {}", dir.display(), e))?;
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| e.file_name().to_str()?.strip_suffix(".before.yaml").map(str::to_string))
+        .collect();
+    names.sort();
+
+    let read = |path: &Path| -> Result<Value, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        serde_yaml::from_str(&text).map_err(|e| format!("invalid YAML in {}: {}", path.display(), e))
+    };
+    let mut failures = Vec::new();
+    for name in &names {
+        let result = read(&dir.join(format!("{}.before.yaml", name))).and_then(|mut before| {
+            let expected = read(&dir.join(format!("{}.after.yaml", name)))?;
+            migrate(&mut before, plan)?;
+            if before == expected {
+                return Ok(());
+            }
+            let got = serde_yaml::to_string(&before).unwrap_or_default();
+            Err(format!("result differs from {}.after.yaml:\n{}", name, got))
+        });
+        if let Err(problem) = result {
+            failures.push((name.clone(), problem));
+        }
+    }
+    Ok((names.len(), failures))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixtures() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/migrations")
+    }
+
+    fn fixture_plan() -> MigrationPlan {
+        load_plan(&fixtures().join("schema.yaml")).unwrap().expect("fixture schema declares config_version")
+    }
+
+    #[test]
+    fn fixtures_migrate_as_expected() {
+        let (checked, failures) = check_fixtures(&fixture_plan(), &fixtures().join("migrations")).unwrap();
+        assert!(checked >= 4, "only {} fixtures found", checked);
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
+
+    #[test]
+    fn applied_migrations_are_listed_in_order() {
+        let mut root: Value = serde_yaml::from_str("clock: { fmt: x }").unwrap();
+        let applied = migrate(&mut root, &fixture_plan()).unwrap();
+        assert_eq!(applied.len(), 2);
+        assert!(applied[0].starts_with("1 → 2: rename clock.fmt to format"), "{}", applied[0]);
+        assert!(applied[1].starts_with("2 → 3: move colors"), "{}", applied[1]);
+        assert_eq!(config_version(&root), 3);
+    }
+
+    #[test]
+    fn duplicate_migration_target_is_an_error() {
+        let plan: MigrationPlan = serde_yaml::from_str(
+            "config_version: 2\nmigrations:\n  - { to: 2, steps: [] }\n  - { to: 2, steps: [] }\n",
+        )
+        .unwrap();
+        let mut root: Value = serde_yaml::from_str("a: 1").unwrap();
+        assert!(migrate(&mut root, &plan).is_err());
+    }
+
+    #[test]
+    fn non_mapping_config_is_an_error() {
+        let mut root: Value = serde_yaml::from_str("[1, 2]").unwrap();
+        assert!(migrate(&mut root, &fixture_plan()).is_err());
+    }
+}
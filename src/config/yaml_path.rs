@@ -0,0 +1,231 @@
+// ~/veil/veil-backend/src/config/yaml_path.rs
+//
+// Dotted-path access into YAML trees ("wallpaper.assignments.*"), shared by
+// the config UI and the `config` IPC namespace.
+//
+// A numeric segment indexes into a sequence; any other segment is a mapping
+// key. Writers create missing parents as mappings and replace whatever else is
+// in the way (a scalar, or a sequence the segment can't index), so a write
+// always lands. Index `len` on a sequence appends.
+
+use serde_yaml::{Mapping, Value};
+
+/// `"a.b. c"` → `["a", "b", "c"]`; empty segments are dropped.
+pub fn split(path: &str) -> Vec<String> {
+    path.split('.')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn child<'a>(node: &'a Value, segment: &str) -> Option<&'a Value> {
+    match node {
+        Value::Mapping(map) => map.get(Value::String(segment.to_string())),
+        Value::Sequence(seq) => seq.get(segment.parse::<usize>().ok()?),
+        _ => None,
+    }
+}
+
+fn child_mut<'a>(node: &'a mut Value, segment: &str) -> Option<&'a mut Value> {
+    match node {
+        Value::Mapping(map) => map.get_mut(Value::String(segment.to_string())),
+        Value::Sequence(seq) => seq.get_mut(segment.parse::<usize>().ok()?),
+        _ => None,
+    }
+}
+
+pub fn get<'a>(root: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(root, |node, segment| child(node, segment))
+}
+
+pub fn get_mut<'a>(root: &'a mut Value, path: &[String]) -> Option<&'a mut Value> {
+    path.iter().try_fold(root, |node, segment| child_mut(node, segment))
+}
+
+/// Child `segment` of `node`, inserting `missing` if absent.
+fn entry<'a>(node: &'a mut Value, segment: &str, missing: Value) -> &'a mut Value {
+    let index = match &*node {
+        Value::Sequence(seq) => segment.parse::<usize>().ok().filter(|&i| i <= seq.len()),
+        _ => None,
+    };
+    if index.is_none() && !matches!(node, Value::Mapping(_)) {
+        *node = Value::Mapping(Mapping::new());
+    }
+
+    match (node, index) {
+        (Value::Sequence(seq), Some(i)) => {
+            if i == seq.len() {
+                seq.push(missing);
+            }
+            &mut seq[i]
+        }
+        (Value::Mapping(map), _) => map
+            .entry(Value::String(segment.to_string()))
+            .or_insert(missing),
+        _ => unreachable!("non-container nodes are replaced with a mapping"),
+    }
+}
+
+/// Node at `path`, created with `default` if it doesn't exist yet. An existing
+/// node is returned untouched.
+pub fn ensure<'a>(root: &'a mut Value, path: &[String], default: Value) -> &'a mut Value {
+    let Some((last, parents)) = path.split_last() else {
+        return root;
+    };
+    let mut current = root;
+    for segment in parents {
+        current = entry(current, segment, Value::Mapping(Mapping::new()));
+    }
+    entry(current, last, default)
+}
+
+/// `ensure` for a mapping, replacing a non-mapping value found at `path`.
+pub fn ensure_mapping<'a>(root: &'a mut Value, path: &[String]) -> &'a mut Mapping {
+    let node = ensure(root, path, Value::Mapping(Mapping::new()));
+    if !matches!(node, Value::Mapping(_)) {
+        *node = Value::Mapping(Mapping::new());
+    }
+    match node {
+        Value::Mapping(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+/// Write `value` at `path`; an empty path replaces the root.
+pub fn set(root: &mut Value, path: &[String], value: Value) {
+    *ensure(root, path, Value::Null) = value;
+}
+
+/// Take the node at `path` out of its parent, returning it.
+pub fn remove(root: &mut Value, path: &[String]) -> Option<Value> {
+    let (last, parents) = path.split_last()?;
+    match get_mut(root, parents)? {
+        Value::Mapping(map) => map.remove(Value::String(last.clone())),
+        Value::Sequence(seq) => {
+            let i = last.parse::<usize>().ok().filter(|&i| i < seq.len())?;
+            Some(seq.remove(i))
+        }
+        _ => None,
+    }
+}
+
+pub fn from_json(value: &serde_json::Value) -> Value {
+    serde_yaml::to_value(value).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(text: &str) -> Value {
+        serde_yaml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn split_trims_and_drops_empty_segments() {
+        assert_eq!(split("a.b. c"), ["a", "b", "c"]);
+        assert_eq!(split(".a..b."), ["a", "b"]);
+        assert!(split("").is_empty());
+        assert!(split(" . ").is_empty());
+    }
+
+    #[test]
+    fn get_walks_mappings_and_sequences() {
+        let root = yaml("a:\n  list: [x, y]\n  n: 1\n");
+        assert_eq!(get(&root, &split("a.list.1")), Some(&yaml("y")));
+        assert_eq!(get(&root, &split("a.n")), Some(&yaml("1")));
+        assert_eq!(get(&root, &[]), Some(&root));
+        assert_eq!(get(&root, &split("a.list.2")), None);
+        assert_eq!(get(&root, &split("a.list.x")), None);
+        assert_eq!(get(&root, &split("a.n.deeper")), None);
+        assert_eq!(get(&root, &split("missing")), None);
+    }
+
+    #[test]
+    fn get_mut_edits_in_place() {
+        let mut root = yaml("a: [1, 2]");
+        *get_mut(&mut root, &split("a.0")).unwrap() = yaml("9");
+        assert_eq!(root, yaml("a: [9, 2]"));
+        assert!(get_mut(&mut root, &split("a.5")).is_none());
+        assert!(get_mut(&mut root, &split("b.c")).is_none());
+    }
+
+    #[test]
+    fn ensure_keeps_an_existing_node() {
+        let mut root = yaml("a: {b: 1}");
+        assert_eq!(*ensure(&mut root, &split("a.b"), yaml("2")), yaml("1"));
+        assert_eq!(root, yaml("a: {b: 1}"));
+    }
+
+    #[test]
+    fn ensure_creates_missing_parents_as_mappings() {
+        let mut root = Value::Null;
+        assert_eq!(*ensure(&mut root, &split("a.b.c"), yaml("7")), yaml("7"));
+        assert_eq!(root, yaml("a: {b: {c: 7}}"));
+    }
+
+    #[test]
+    fn ensure_mapping_replaces_a_non_mapping() {
+        let mut root = yaml("a: 3");
+        ensure_mapping(&mut root, &split("a")).insert(yaml("k"), yaml("v"));
+        assert_eq!(root, yaml("a: {k: v}"));
+    }
+
+    #[test]
+    fn set_replaces_non_mapping_intermediates() {
+        let mut root = yaml("a: 1");
+        set(&mut root, &split("a.b"), yaml("2"));
+        assert_eq!(root, yaml("a: {b: 2}"));
+
+        let mut root = yaml("a: [x]");
+        set(&mut root, &split("a.key"), yaml("v"));
+        assert_eq!(root, yaml("a: {key: v}"));
+    }
+
+    #[test]
+    fn set_indexes_and_appends_to_sequences() {
+        let mut root = yaml("a: [x, y]");
+        set(&mut root, &split("a.0"), yaml("z"));
+        set(&mut root, &split("a.2"), yaml("w"));
+        assert_eq!(root, yaml("a: [z, y, w]"));
+
+        // Past the end is not an index, so the sequence gives way to a mapping.
+        set(&mut root, &split("a.5"), yaml("v"));
+        assert_eq!(root, yaml("a: {'5': v}"));
+    }
+
+    #[test]
+    fn set_with_an_empty_path_replaces_the_root() {
+        let mut root = yaml("a: 1");
+        set(&mut root, &[], yaml("[1]"));
+        assert_eq!(root, yaml("[1]"));
+    }
+
+    #[test]
+    fn remove_takes_the_node_out() {
+        let mut root = yaml("a: {b: 1, c: [x, y]}");
+        assert_eq!(remove(&mut root, &split("a.b")), Some(yaml("1")));
+        assert_eq!(remove(&mut root, &split("a.c.0")), Some(yaml("x")));
+        assert_eq!(root, yaml("a: {c: [y]}"));
+    }
+
+    #[test]
+    fn remove_leaves_the_tree_alone_when_nothing_is_there() {
+        let mut root = yaml("a: {b: 1, c: [x]}");
+        let before = root.clone();
+        assert_eq!(remove(&mut root, &split("missing.b")), None);
+        assert_eq!(remove(&mut root, &split("a.b.deeper")), None);
+        assert_eq!(remove(&mut root, &split("a.c.1")), None);
+        assert_eq!(remove(&mut root, &split("a.c.x")), None);
+        assert_eq!(remove(&mut root, &split("a.zzz")), None);
+        assert_eq!(remove(&mut root, &[]), None);
+        assert_eq!(root, before);
+    }
+
+    #[test]
+    fn from_json_converts_every_kind() {
+        let json = serde_json::json!({ "s": "x", "n": 1, "f": 1.5, "b": true, "null": null, "list": [1, "a"] });
+        assert_eq!(from_json(&json), yaml("{s: x, n: 1, f: 1.5, b: true, 'null': null, list: [1, a]}"));
+    }
+}
@@ -0,0 +1,216 @@
+// ~/veil/veil-backend/src/config_archive.rs
+//
+// `VEIL export` / `VEIL import`: move a VEIL setup between machines as one
+// zip. The archive holds the backend `config.yaml`, `tray_settings.json` and
+// every addon's `config.yaml`; asset files only with `--with-assets`.
+//
+// Import checks every config in the archive before writing any of them, and
+// copies each file it is about to overwrite to `<root>/backups/import-<time>/`.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::autostart::TraySettings;
+use crate::paths::{addons_dir, assets_dir, backups_dir, config_yaml_path, tray_settings_json_path, veil_root_dir};
+
+/// Marks a zip as a VEIL export; import refuses archives without it.
+const MANIFEST_NAME: &str = "veil-export.json";
+const BACKEND_CONFIG: &str = "config.yaml";
+const TRAY_SETTINGS: &str = "tray_settings.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    version: String,
+    created: String,
+    with_assets: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ExportSummary {
+    pub backend_config: bool,
+    pub tray_settings: bool,
+    pub addon_configs: Vec<String>,
+    pub asset_files: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    /// Corrections the config validator made to the imported `config.yaml`.
+    pub backend_config: Option<Vec<String>>,
+    pub tray_settings: bool,
+    pub addon_configs: Vec<String>,
+    /// Addon folders in the archive that aren't installed here.
+    pub skipped_addons: Vec<String>,
+    pub asset_files: usize,
+    pub ignored: Vec<String>,
+    /// Set when at least one existing file was backed up.
+    pub backup_dir: Option<PathBuf>,
+}
+
+/// A file from the archive and where it goes under the VEIL root.
+struct Restore {
+    target: PathBuf,
+    bytes: Vec<u8>,
+}
+
+pub fn export(path: &Path, with_assets: bool) -> Result<ExportSummary, String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create '{}': {}", path.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut summary = ExportSummary::default();
+
+    let manifest = Manifest {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        created: chrono::Local::now().to_rfc3339(),
+        with_assets,
+    };
+    let manifest = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    add_bytes(&mut zip, MANIFEST_NAME, &manifest, options)?;
+
+    summary.backend_config = add_file(&mut zip, BACKEND_CONFIG, &config_yaml_path(), options)?;
+    summary.tray_settings = add_file(&mut zip, TRAY_SETTINGS, &tray_settings_json_path(), options)?;
+
+    for folder in addon_folders() {
+        let source = addons_dir().join(&folder).join("config.yaml");
+        if add_file(&mut zip, &format!("Addons/{}/config.yaml", folder), &source, options)? {
+            summary.addon_configs.push(folder);
+        }
+    }
+
+    if with_assets {
+        let root = veil_root_dir();
+        for entry in WalkDir::new(assets_dir()).into_iter().flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(rel) = entry.path().strip_prefix(&root) else { continue };
+            let name = rel.to_string_lossy().replace('\\', "/");
+            if add_file(&mut zip, &name, entry.path(), options)? {
+                summary.asset_files += 1;
+            }
+        }
+    }
+
+    zip.finish().map_err(|e| format!("Failed to finish '{}': {}", path.display(), e))?;
+    Ok(summary)
+}
+
+/// Folder names under `Addons/`, sorted.
+pub(crate) fn addon_folders() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(addons_dir()) else { return Vec::new() };
+    let mut folders: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    folders.sort();
+    folders
+}
+
+pub(crate) fn add_bytes(zip: &mut ZipWriter<File>, name: &str, bytes: &[u8], options: SimpleFileOptions) -> Result<(), String> {
+    zip.start_file(name, options).map_err(|e| format!("Failed to add '{}': {}", name, e))?;
+    zip.write_all(bytes).map_err(|e| format!("Failed to add '{}': {}", name, e))
+}
+
+/// Add `source` as `name`; `false` when the file doesn't exist.
+fn add_file(zip: &mut ZipWriter<File>, name: &str, source: &Path, options: SimpleFileOptions) -> Result<bool, String> {
+    if !source.is_file() {
+        return Ok(false);
+    }
+    let bytes = fs::read(source).map_err(|e| format!("Failed to read '{}': {}", source.display(), e))?;
+    add_bytes(zip, name, &bytes, options)?;
+    Ok(true)
+}
+
+pub fn import(path: &Path) -> Result<ImportSummary, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("'{}' is not a zip archive: {}", path.display(), e))?;
+    if archive.by_name(MANIFEST_NAME).is_err() {
+        return Err(format!("'{}' is not a VEIL export (no {})", path.display(), MANIFEST_NAME));
+    }
+
+    let root = veil_root_dir();
+    let mut summary = ImportSummary::default();
+    let mut restores = Vec::new();
+
+    // Validate everything first so a bad entry leaves the current setup untouched.
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let raw_name = entry.name().to_string();
+        let Some(rel) = entry.enclosed_name() else {
+            summary.ignored.push(raw_name);
+            continue;
+        };
+        let name = rel.to_string_lossy().replace('\\', "/");
+        if name == MANIFEST_NAME {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).map_err(|e| format!("Failed to read '{}': {}", name, e))?;
+
+        let parts: Vec<&str> = name.split('/').collect();
+        match parts.as_slice() {
+            [BACKEND_CONFIG] => {
+                let text = std::str::from_utf8(&bytes).map_err(|_| "config.yaml is not valid UTF-8".to_string())?;
+                let (_, issues) = crate::config::parse_and_validate(text)
+                    .map_err(|e| format!("config.yaml is invalid: {}", e))?;
+                summary.backend_config = Some(issues.into_iter().map(|i| format!("{}: {}", i.field, i.message)).collect());
+            }
+            [TRAY_SETTINGS] => {
+                serde_json::from_slice::<TraySettings>(&bytes)
+                    .map_err(|e| format!("tray_settings.json is invalid: {}", e))?;
+                summary.tray_settings = true;
+            }
+            ["Addons", folder, "config.yaml"] => {
+                serde_yaml::from_slice::<serde_yaml::Value>(&bytes)
+                    .ok()
+                    .filter(|doc| doc.is_mapping() || doc.is_null())
+                    .ok_or_else(|| format!("Addons/{}/config.yaml is not a YAML mapping", folder))?;
+                if !addons_dir().join(folder).is_dir() {
+                    summary.skipped_addons.push(folder.to_string());
+                    continue;
+                }
+                summary.addon_configs.push(folder.to_string());
+            }
+            ["Assets", ..] => summary.asset_files += 1,
+            _ => {
+                summary.ignored.push(name.clone());
+                continue;
+            }
+        }
+        restores.push(Restore { target: root.join(rel), bytes });
+    }
+
+    let backup_root = backups_dir().join(format!("import-{}", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+    for restore in &restores {
+        if restore.target.is_file() {
+            let rel = restore.target.strip_prefix(&root).unwrap_or(&restore.target);
+            let backup = backup_root.join(rel);
+            if let Some(parent) = backup.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+            }
+            fs::copy(&restore.target, &backup)
+                .map_err(|e| format!("Failed to back up '{}': {}", restore.target.display(), e))?;
+            summary.backup_dir = Some(backup_root.clone());
+        }
+    }
+
+    for restore in &restores {
+        if let Some(parent) = restore.target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+        }
+        fs::write(&restore.target, &restore.bytes)
+            .map_err(|e| format!("Failed to write '{}': {}", restore.target.display(), e))?;
+    }
+
+    Ok(summary)
+}
@@ -1,3 +1,18 @@
+mod search;
+mod preview_capture;
+mod playlist;
+mod schedule;
+mod addon_config;
+mod ui_state;
+mod url_install;
+mod workshop;
+mod z_index;
+
+pub use preview_capture::run_wallpaper_preview_capture;
+pub use playlist::start_playlist_rotation;
+pub use schedule::start_wallpaper_scheduler;
+pub use addon_config::{addon_config_value, addon_schema_json, set_addon_config_value};
+
 use std::{borrow::Cow, collections::{HashMap, HashSet}, path::{Path, PathBuf}, sync::{Arc, Mutex}};
 
 use eframe::{App, NativeOptions, egui};
@@ -13,8 +28,11 @@ use tao::{
 use wry::WebViewBuilder;
 
 use crate::{error, info, warn};
+use crate::ipc::assets::{asset_key, fix_asset_dir, note_asset_used, verify_asset_dir, AssetIssue, AssetPrefs};
 use crate::ipc::sysdata::display::{MonitorInfo, MonitorManager};
-use crate::paths::veil_root_dir;
+use crate::paths::{addons_dir, assets_dir, backups_dir, cache_dir, veil_root_dir};
+use crate::config::yaml_path;
+use search::{SearchIndex, SearchTarget};
 
 #[derive(Clone)]
 struct AddonMeta {
@@ -26,6 +44,23 @@ struct AddonMeta {
     schema_path: PathBuf,
     accepts_assets: bool,
     asset_categories: Vec<String>,
+    /// Steam app whose Workshop items can be installed as this addon's assets.
+    workshop_app_id: Option<u32>,
+    capabilities: crate::ipc::addon::capabilities::AddonCapabilities,
+    /// Set when addon.json's `min_backend_version` is newer than this backend.
+    incompatible: Option<String>,
+}
+
+impl AddonMeta {
+    /// Category folder new assets are installed into: the existing folder
+    /// discovery would pick for the first declared category (so "wallpaper"
+    /// lands in `Wallpapers/`), or the category name itself.
+    fn primary_asset_category(&self) -> String {
+        let wanted = self.asset_categories.first().unwrap_or(&self.package);
+        find_category_dir_case_insensitive(&assets_dir(), wanted)
+            .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| wanted.clone())
+    }
 }
 
 struct AddonConfigState {
@@ -36,6 +71,36 @@ struct AddonConfigState {
     assets: Vec<AssetOption>,
     asset_selector_paths: Vec<Vec<String>>,
     custom_tabs: Vec<CustomTabPage>,
+    /// When the last edit settles and `root` gets written; `None` when saved.
+    save_due: Option<std::time::Instant>,
+    /// `root` as of the last committed save, and the states around it.
+    committed: Value,
+    undo: Vec<Value>,
+    redo: Vec<Value>,
+}
+
+impl AddonConfigState {
+    /// Make `root` the committed state. A fresh edit invalidates redo.
+    fn commit_history(&mut self) {
+        if self.root == self.committed {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.committed, self.root.clone());
+        self.undo.push(previous);
+        if self.undo.len() > CONFIG_HISTORY_DEPTH {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Step back (`undo`) or forward through the history; false at either end.
+    fn step_history(&mut self, undo: bool) -> bool {
+        let (from, to) = if undo { (&mut self.undo, &mut self.redo) } else { (&mut self.redo, &mut self.undo) };
+        let Some(target) = from.pop() else { return false };
+        to.push(std::mem::replace(&mut self.committed, target));
+        self.root = self.committed.clone();
+        true
+    }
 }
 
 #[derive(Clone)]
@@ -73,6 +138,10 @@ struct WallpaperShellAsset {
     html_url: Option<String>,
     editable: serde_json::Value,
     manifest_path: String,
+    issues: Vec<String>,
+    favorite: bool,
+    /// 0 = most recently assigned; absent if not in the recents list.
+    recent_rank: Option<usize>,
 }
 
 #[derive(Clone, Serialize)]
@@ -81,6 +150,8 @@ struct WallpaperShellData {
     wallpaper_id: Option<String>,
     mode: Option<String>,
     z_index: Option<String>,
+    /// Accepted `z_index` values, for the layer dropdown.
+    z_index_options: &'static [&'static str],
     monitor_index: Vec<String>,
     assignments: HashMap<String, String>,
     monitors: Vec<WallpaperShellMonitor>,
@@ -129,15 +200,22 @@ struct WallpaperShellData {
 #[derive(Clone, Serialize)]
 struct WallpaperShellProfile {
     monitor_index: String,
+    monitor_id: Option<String>,
     enabled: bool,
     wallpaper_id: String,
     mode: Option<String>,
     z_index: Option<String>,
+    playlist: Vec<String>,
+    rotate_interval_minutes: Option<u64>,
+    playlist_index: Option<usize>,
+    schedule: Vec<WallpaperScheduleEntry>,
 }
 
 #[derive(Clone, Serialize)]
 struct WallpaperShellMonitor {
     id: String,
+    /// `MonitorInfo::stable_id`; what a profile's `monitor_id` refers to.
+    stable_id: String,
     x: i32,
     y: i32,
     width: i32,
@@ -146,14 +224,60 @@ struct WallpaperShellMonitor {
     primary: bool,
 }
 
+impl From<MonitorInfo> for WallpaperShellMonitor {
+    fn from(m: MonitorInfo) -> Self {
+        Self {
+            id: m.id,
+            stable_id: m.stable_id,
+            x: m.x,
+            y: m.y,
+            width: m.width,
+            height: m.height,
+            scale: m.scale,
+            primary: m.primary,
+        }
+    }
+}
+
 #[derive(Clone)]
 struct WallpaperProfileEntry {
     section: String,
     enabled: bool,
     monitor_index: Vec<String>,
+    /// Stable monitor identity the profile is pinned to; takes precedence over `monitor_index`.
+    monitor_id: Option<String>,
+    /// Active wallpaper; the playlist's first entry when the section only has a playlist.
     wallpaper_id: String,
     mode: Option<String>,
     z_index: Option<String>,
+    playlist: Vec<String>,
+    rotate_interval_minutes: Option<u64>,
+    /// Time-of-day windows; the first one containing the current time wins.
+    schedule: Vec<WallpaperScheduleEntry>,
+}
+
+/// `{ from: "06:00", to: "18:00", wallpaper_id: "day" }`. `to` before `from`
+/// wraps past midnight; equal times cover the whole day.
+#[derive(Clone, Serialize)]
+struct WallpaperScheduleEntry {
+    from: String,
+    to: String,
+    wallpaper_id: String,
+    #[serde(skip)]
+    from_minute: u32,
+    #[serde(skip)]
+    to_minute: u32,
+}
+
+impl WallpaperScheduleEntry {
+    /// Whether `minute` (minutes since local midnight) falls in this window.
+    fn contains(&self, minute: u32) -> bool {
+        match self.from_minute.cmp(&self.to_minute) {
+            std::cmp::Ordering::Less => minute >= self.from_minute && minute < self.to_minute,
+            std::cmp::Ordering::Greater => minute >= self.from_minute || minute < self.to_minute,
+            std::cmp::Ordering::Equal => true,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -172,7 +296,7 @@ struct ShellIpcMessage {
     property: Option<String>,
     // For backend_setting
     key: Option<String>,
-    // For wallpaper_save_editable / wallpaper_capture_preview
+    // For wallpaper_save_editable / wallpaper_capture_preview / open_folder
     manifest_path: Option<String>,
     // For ui_view_mode
     view_mode: Option<String>,
@@ -181,6 +305,13 @@ struct ShellIpcMessage {
     renderer_mode: Option<String>,
     // For open_external_url
     url: Option<String>,
+    // For wallpaper_set_playlist
+    playlist: Option<Vec<String>>,
+    #[serde(alias = "rotate_interval_minutes")]
+    rotate_interval_minutes: Option<u64>,
+    // For logs_filter
+    level: Option<String>,
+    contains: Option<String>,
 }
 
 fn parse_shell_ipc_message(body: &str) -> Option<ShellIpcMessage> {
@@ -204,6 +335,37 @@ enum UiSection {
     Settings,
 }
 
+impl UiSection {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Home => "home",
+            Self::Addons => "addons",
+            Self::Integrations => "integrations",
+            Self::Settings => "settings",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "home" => Some(Self::Home),
+            "addons" => Some(Self::Addons),
+            "integrations" => Some(Self::Integrations),
+            "settings" => Some(Self::Settings),
+            _ => None,
+        }
+    }
+}
+
+/// Quiet period after the last addon config edit before config.yaml is written,
+/// so a slider drag ends in one save.
+const CONFIG_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// Committed addon config states kept for undo.
+const CONFIG_HISTORY_DEPTH: usize = 50;
+
+/// How long a UI selection has to stay put before it is written to disk.
+const UI_STATE_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(750);
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum AddonHubTab {
     Library,
@@ -212,7 +374,35 @@ enum AddonHubTab {
     Settings,
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+impl AddonHubTab {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Library => "library",
+            Self::Editor => "editor",
+            Self::Discover => "discover",
+            Self::Settings => "settings",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "library" => Some(Self::Library),
+            "editor" => Some(Self::Editor),
+            "discover" => Some(Self::Discover),
+            "settings" => Some(Self::Settings),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AssetFilter {
+    All,
+    Favorites,
+    Recent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct AddonSchema {
     #[serde(default)]
     version: Option<String>,
@@ -220,13 +410,13 @@ struct AddonSchema {
     ui: SchemaUi,
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct SchemaUi {
     #[serde(default)]
     sections: Vec<SchemaSection>,
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct SchemaSection {
     title: String,
     #[serde(default)]
@@ -241,7 +431,7 @@ struct SchemaSection {
     sections: Vec<SchemaSection>,
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct SchemaField {
     path: String,
     #[serde(default)]
@@ -267,6 +457,8 @@ struct SchemaField {
 #[derive(Clone)]
 struct AssetOption {
     id: String,
+    /// Name of the category folder the asset was found in.
+    category: String,
     name: String,
     version: Option<String>,
     tags: Vec<String>,
@@ -277,10 +469,23 @@ struct AssetOption {
     preview_paths: Vec<PathBuf>,
     manifest_path: PathBuf,
     editable: JsonValue,
+    /// Integrity problems found by `ipc::assets::verify_asset_dir`.
+    issues: Vec<AssetIssue>,
+}
+
+/// Preview textures kept on the GPU; least recently drawn ones go first.
+const PREVIEW_TEXTURE_CAP: usize = 64;
+
+struct CachedPreview {
+    texture: TextureHandle,
+    /// File mtime when loaded; a different one means the image was edited.
+    modified: Option<std::time::SystemTime>,
+    /// `cumulative_pass_nr` of the last pass that drew it.
+    last_used: u64,
 }
 
 struct UiCaches {
-    preview_textures: HashMap<String, TextureHandle>,
+    preview_textures: HashMap<PathBuf, CachedPreview>,
     preview_index: HashMap<String, usize>,
 }
 
@@ -291,13 +496,34 @@ impl UiCaches {
             preview_index: HashMap::new(),
         }
     }
+
+    /// Drop least recently used textures past `PREVIEW_TEXTURE_CAP`, but
+    /// never one drawn in the current pass.
+    fn evict_previews(&mut self, pass: u64) {
+        while self.preview_textures.len() > PREVIEW_TEXTURE_CAP {
+            let Some((oldest, last_used)) = self
+                .preview_textures
+                .iter()
+                .min_by_key(|(_, p)| p.last_used)
+                .map(|(k, p)| (k.clone(), p.last_used))
+            else {
+                break;
+            };
+            if last_used >= pass {
+                break;
+            }
+            self.preview_textures.remove(&oldest);
+        }
+    }
 }
 
-pub fn run_addon_config_ui(addon_ref: &str) -> Result<(), Box<dyn std::error::Error>> {
-    run_veil_ui(Some(addon_ref))
+pub fn run_addon_config_ui(addon_ref: &str, tab: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    run_veil_ui(Some(addon_ref), tab)
 }
 
-pub fn run_veil_ui(addon_focus: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+/// Open the UI, optionally on `addon_focus` and one of its tabs (matched by
+/// id or title).
+pub fn run_veil_ui(addon_focus: Option<&str>, tab_focus: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
     let addon_catalog = discover_addon_configs();
     if addon_catalog.is_empty() {
         warn!("No addon config.yaml files were discovered for VEIL UI");
@@ -310,11 +536,14 @@ pub fn run_veil_ui(addon_focus: Option<&str>) -> Result<(), Box<dyn std::error::
     let custom_tab_addons = collect_custom_tab_shell_addons(&addon_catalog);
     if !custom_tab_addons.is_empty() {
         info!("Launching VEIL WebView shell for custom addon tabs");
-        return run_veil_custom_tabs_shell(custom_tab_addons, addon_focus);
+        return run_veil_custom_tabs_shell(custom_tab_addons, addon_focus, tab_focus);
     }
 
+    // Without an explicit focus, pick up where the last session left off.
+    let saved = if addon_focus.is_none() { ui_state::UiState::load().native } else { Default::default() };
+
     let mut selected = 0usize;
-    if let Some(focus) = addon_focus {
+    if let Some(focus) = addon_focus.or(saved.addon_id.as_deref()) {
         if let Some(idx) = addon_catalog
             .iter()
             .position(|a| a.id.eq_ignore_ascii_case(focus) || a.name.eq_ignore_ascii_case(focus))
@@ -329,16 +558,21 @@ pub fn run_veil_ui(addon_focus: Option<&str>) -> Result<(), Box<dyn std::error::
         section: if addon_focus.is_some() {
             UiSection::Addons
         } else {
-            UiSection::Home
+            saved.section.as_deref().and_then(UiSection::from_name).unwrap_or(UiSection::Home)
         },
         addon_catalog,
         selected_addon_idx: selected,
         addon_state,
         global_status: "Ready".to_string(),
         caches: UiCaches::new(),
-        addon_hub_tab: AddonHubTab::Settings,
+        addon_hub_tab: tab_focus
+            .or(saved.hub_tab.as_deref())
+            .and_then(AddonHubTab::from_name)
+            .unwrap_or(AddonHubTab::Settings),
         editor_selected_asset: None,
         library_selected_monitor: None,
+        asset_prefs: AssetPrefs::load(),
+        asset_filter: AssetFilter::All,
         selected_custom_tab: None,
         last_opened_custom_tab: None,
         settings_fast_rate: 50,
@@ -346,6 +580,17 @@ pub fn run_veil_ui(addon_focus: Option<&str>) -> Result<(), Box<dyn std::error::
         settings_pull_paused: false,
         settings_refresh_on_request: true,
         settings_loaded: false,
+        theme: crate::config::UiTheme::default(),
+        theme_checked_at: None,
+        search_open: false,
+        search_query: String::new(),
+        search_selected: 0,
+        search_index: None,
+        addon_filter: String::new(),
+        saved_ui_state: saved,
+        ui_state_changed_at: None,
+        workshop: workshop::WorkshopBrowser::default(),
+        url_installer: url_install::UrlInstaller::default(),
     };
 
     let options = NativeOptions {
@@ -362,6 +607,7 @@ pub fn run_veil_ui(addon_focus: Option<&str>) -> Result<(), Box<dyn std::error::
 fn run_veil_custom_tabs_shell(
         addons: Vec<CustomTabShellAddon>,
         addon_focus: Option<&str>,
+        tab_focus: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
         if addons.is_empty() {
                 return Ok(());
@@ -372,7 +618,17 @@ fn run_veil_custom_tabs_shell(
         // crate::config accessors used for the data-tab poll rate.
         crate::config::load_config();
 
+        // Without an explicit focus, reopen the last view and addon.
+        let saved = if addon_focus.is_none() { ui_state::UiState::load().shell } else { Default::default() };
+        let initial_view_mode = saved
+                .view_mode
+                .as_deref()
+                .filter(|mode| SHELL_VIEW_MODES.contains(mode))
+                .unwrap_or("addon")
+                .to_string();
+
         let selected_addon_id = addon_focus
+                .or(saved.addon_id.as_deref())
                 .and_then(|focus| {
                         addons
                                 .iter()
@@ -381,7 +637,7 @@ fn run_veil_custom_tabs_shell(
                 })
                 .unwrap_or_else(|| addons[0].id.clone());
 
-        let html = build_veil_custom_tabs_shell_html(&addons, &selected_addon_id)?;
+        let html = build_veil_custom_tabs_shell_html(&addons, &selected_addon_id, &initial_view_mode, tab_focus)?;
         let shell_path = veil_shell_html_path()?;
         if let Some(parent) = shell_path.parent() {
                 std::fs::create_dir_all(parent)?;
@@ -406,10 +662,14 @@ fn run_veil_custom_tabs_shell(
                 .map_err(|e| format!("Failed to create VEIL shell window: {}", e))?;
 
         let protocol_root = veil_home.clone();
-        let ui_view_mode = Arc::new(Mutex::new("addon".to_string()));
+        let ui_view_mode = Arc::new(Mutex::new(initial_view_mode));
+        let saved_shell_state = Arc::new(Mutex::new(saved));
         let ui_view_mode_ipc = Arc::clone(&ui_view_mode);
         let ui_renderer_mode = Arc::new(Mutex::new("webview2".to_string()));
         let ui_renderer_mode_ipc = Arc::clone(&ui_renderer_mode);
+        // `logs.tail` args for the Logs page, set from its filter bar.
+        let logs_filter = Arc::new(Mutex::new(JsonValue::Null));
+        let logs_filter_ipc = Arc::clone(&logs_filter);
 
         let webview = WebViewBuilder::new()
                 .with_custom_protocol("veil".to_string(), move |_webview_id, request| {
@@ -500,6 +760,7 @@ fn run_veil_custom_tabs_shell(
                     let payload = request.body().to_string();
                     let ui_view_mode_ipc = Arc::clone(&ui_view_mode_ipc);
                     let ui_renderer_mode_ipc = Arc::clone(&ui_renderer_mode_ipc);
+                    let saved_shell_state = Arc::clone(&saved_shell_state);
                     warn!("[ui] IPC handler invoked, payload length={}", payload.len());
                     let result = std::panic::catch_unwind(move || {
                         let Some(message) = parse_shell_ipc_message(&payload) else {
@@ -528,15 +789,30 @@ fn run_veil_custom_tabs_shell(
                                     &monitor_ids,
                                     &monitor_indexes,
                                 ) {
-                                    Ok(_) => warn!(
-                                        "[ui] Saved wallpaper assignment: addon='{}' wallpaper='{}' indexes={:?}",
-                                        addon_id, wallpaper_id, monitor_indexes
-                                    ),
+                                    Ok(_) => {
+                                        note_asset_used(&shell_asset_key(&addon_id, &wallpaper_id));
+                                        warn!(
+                                            "[ui] Saved wallpaper assignment: addon='{}' wallpaper='{}' indexes={:?}",
+                                            addon_id, wallpaper_id, monitor_indexes
+                                        )
+                                    }
                                     Err(e) => warn!(
                                         "[ui] Failed saving wallpaper assignment: error={}", e
                                     ),
                                 }
                             }
+                            "wallpaper_apply_all" => {
+                                let Some(wallpaper_id) = message.wallpaper_id.filter(|v| !v.trim().is_empty()) else {
+                                    return;
+                                };
+                                match apply_wallpaper_to_all_monitors(&addon_id, &wallpaper_id) {
+                                    Ok(_) => {
+                                        note_asset_used(&shell_asset_key(&addon_id, &wallpaper_id));
+                                        warn!("[ui] Applied wallpaper '{}' to all monitors for '{}'", wallpaper_id, addon_id)
+                                    }
+                                    Err(e) => warn!("[ui] Failed applying wallpaper to all monitors: error={}", e),
+                                }
+                            }
                             "config_update" => {
                                 let path = message.path.unwrap_or_default();
                                 let value = message.value.unwrap_or(serde_json::Value::Null);
@@ -554,6 +830,17 @@ fn run_veil_custom_tabs_shell(
                                     Err(e) => warn!("[ui] Wallpaper property update failed: {}", e),
                                 }
                             }
+                            "asset_set_favorite" => {
+                                let Some(asset_id) = message.wallpaper_id.filter(|v| !v.trim().is_empty()) else {
+                                    return;
+                                };
+                                let favorite = message.value.and_then(|v| v.as_bool()).unwrap_or(true);
+                                let key = shell_asset_key(&addon_id, &asset_id);
+                                match AssetPrefs::update(|prefs| prefs.set_favorite(&key, favorite)) {
+                                    Ok(_) => warn!("[ui] Asset '{}' favorite={}", asset_id, favorite),
+                                    Err(e) => warn!("[ui] Favorite update failed: {}", e),
+                                }
+                            }
                             "clear_cache" => {
                                 match clear_addon_cache(&addon_id) {
                                     Ok(_) => warn!("[ui] Cache cleared for '{}'", addon_id),
@@ -595,6 +882,11 @@ fn run_veil_custom_tabs_shell(
                                             ("set_ui_data_exception_enabled", serde_json::json!({"enabled": enabled}))
                                         } else { return; }
                                     }
+                                    "theme" => {
+                                        if let Some(theme) = value.as_str() {
+                                            ("set_theme", serde_json::json!({"theme": theme}))
+                                        } else { return; }
+                                    }
                                     _ => {
                                         warn!("[ui] Unknown backend setting key: {}", key);
                                         return;
@@ -605,6 +897,8 @@ fn run_veil_custom_tabs_shell(
                                     ns: "backend".to_string(),
                                     cmd: cmd.to_string(),
                                     args: Some(args),
+                                    addon_id: None,
+                                    token: None,
                                 };
                                 match crate::ipc::request::send_ipc_request(req) {
                                     Ok(resp) if resp.ok => {
@@ -628,21 +922,58 @@ fn run_veil_custom_tabs_shell(
                                     Err(e) => warn!("[ui] Editable save failed: {}", e),
                                 }
                             }
+                            "wallpaper_set_playlist" => {
+                                let monitor_indexes = message.monitor_indexes.unwrap_or_default();
+                                let items = message.playlist.unwrap_or_default();
+                                match playlist::apply_playlist_from_shell(
+                                    &addon_id,
+                                    &monitor_indexes,
+                                    &items,
+                                    message.rotate_interval_minutes,
+                                ) {
+                                    Ok(_) => warn!(
+                                        "[ui] Saved wallpaper playlist: addon='{}' items={} indexes={:?}",
+                                        addon_id, items.len(), monitor_indexes
+                                    ),
+                                    Err(e) => warn!("[ui] Failed saving wallpaper playlist: {}", e),
+                                }
+                            }
                             "wallpaper_capture_preview" => {
                                 let wallpaper_id = message.wallpaper_id.unwrap_or_default();
                                 let manifest_path_str = message.manifest_path.unwrap_or_default();
                                 match capture_wallpaper_preview(&manifest_path_str) {
-                                    Ok(_) => warn!("[ui] Preview captured for '{}'", wallpaper_id),
+                                    Ok(_) => warn!("[ui] Preview capture started for '{}'", wallpaper_id),
                                     Err(e) => warn!("[ui] Preview capture failed: {}", e),
                                 }
                             }
                             "ui_view_mode" => {
                                 if let Some(mode) = message.view_mode {
+                                    let mode = mode.to_lowercase();
                                     if let Ok(mut guard) = ui_view_mode_ipc.lock() {
-                                        *guard = mode.to_lowercase();
+                                        *guard = mode.clone();
+                                    }
+                                    // Remember the view for the next launch; only changes hit the disk.
+                                    let state = ui_state::ShellUiState {
+                                        view_mode: Some(mode),
+                                        addon_id: message.addon_id,
+                                    };
+                                    if let Ok(mut saved) = saved_shell_state.lock() {
+                                        if *saved != state {
+                                            ui_state::save_shell(&state);
+                                            *saved = state;
+                                        }
                                     }
                                 }
                             }
+                            "logs_filter" => {
+                                if let Ok(mut guard) = logs_filter_ipc.lock() {
+                                    *guard = serde_json::json!({
+                                        "lines": 500,
+                                        "level": message.level.filter(|l| !l.is_empty()),
+                                        "contains": message.contains.filter(|c| !c.is_empty()),
+                                    });
+                                }
+                            }
                             "ui_renderer_mode" => {
                                 if let Some(mode) = message.renderer_mode.or_else(|| message.value.and_then(|v| v.as_str().map(|s| s.to_string()))) {
                                     let normalized = mode.to_lowercase();
@@ -652,6 +983,33 @@ fn run_veil_custom_tabs_shell(
                                     warn!("[ui] Renderer mode set to '{}'", normalized);
                                 }
                             }
+                            "open_folder" => {
+                                let result = match message.manifest_path.filter(|p| !p.trim().is_empty()) {
+                                    Some(manifest) => {
+                                        let dir = Path::new(&manifest).parent().unwrap_or(Path::new("")).to_path_buf();
+                                        open_folder(&dir).map(|_| dir)
+                                    }
+                                    None => open_addon_folder(&addon_id),
+                                };
+                                match result {
+                                    Ok(dir) => warn!("[ui] Opened folder {}", dir.display()),
+                                    Err(e) => warn!("[ui] Failed to open folder: {}", e),
+                                }
+                            }
+                            "media_control" => {
+                                let req = crate::ipc::request::IpcRequest {
+                                    ns: "media".to_string(),
+                                    cmd: "control".to_string(),
+                                    args: message.value,
+                                    addon_id: None,
+                                    token: None,
+                                };
+                                match crate::ipc::request::send_ipc_request(req) {
+                                    Ok(resp) if resp.ok => {}
+                                    Ok(resp) => warn!("[ui] Media control failed: {:?}", resp.error),
+                                    Err(e) => warn!("[ui] Failed to send media control to daemon: {}", e),
+                                }
+                            }
                             "open_external_url" => {
                                 let Some(url) = message.url else {
                                     warn!("[ui] open_external_url missing url");
@@ -688,12 +1046,14 @@ fn run_veil_custom_tabs_shell(
 
         let mut last_monitor_poll = std::time::Instant::now();
         let mut cached_monitor_json = String::new();
-        let mut cached_registry_json = String::new();
+        let mut cached_registry: Option<JsonValue> = None;
+        let mut last_full_registry_push = std::time::Instant::now();
         let mut cached_config_json = String::new();
         let mut last_registry_push = std::time::Instant::now();
         let mut last_config_push = std::time::Instant::now();
         let mut last_ui_heartbeat = std::time::Instant::now();
-        let snapshot_home = veil_home.clone();
+        let mut last_logs_poll = std::time::Instant::now();
+        let mut cached_logs_json = String::new();
 
         event_loop.run(move |event, _, control_flow| {
                 const UI_POLL_MS_ACTIVE_DATA_WEBVIEW: u64 = 80;
@@ -702,6 +1062,7 @@ fn run_veil_custom_tabs_shell(
                 const UI_POLL_MS_ACTIVE_DATA_OPENRENDER: u64 = 125;
             const UI_POLL_MS_ACTIVE_ADDON_OPENRENDER: u64 = 1200;
                 const UI_POLL_MS_IDLE_OPENRENDER: u64 = 950;
+                const REGISTRY_RESYNC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
                 let current_view_mode = ui_view_mode
                     .lock()
                     .map(|mode| mode.clone())
@@ -713,6 +1074,7 @@ fn run_veil_custom_tabs_shell(
                 let openrender_renderer = current_renderer_mode == "openrender";
                 let data_view_active = current_view_mode == "data";
                 let addon_view_active = current_view_mode == "addon";
+                let logs_view_active = current_view_mode == "logs";
                 let ui_poll_ms = if data_view_active {
                     if openrender_renderer { UI_POLL_MS_ACTIVE_DATA_OPENRENDER } else { UI_POLL_MS_ACTIVE_DATA_WEBVIEW }
                 } else {
@@ -731,8 +1093,37 @@ fn run_veil_custom_tabs_shell(
                         ns: "backend".to_string(),
                         cmd: "ui_heartbeat".to_string(),
                         args: None,
+                        addon_id: None,
+                        token: None,
                     };
                     let _ = crate::ipc::request::send_ipc_request(req);
+
+                    // Pick up `ui.open` requests made while the shell is running.
+                    let req = crate::ipc::request::IpcRequest {
+                        ns: "ui".to_string(),
+                        cmd: "poll".to_string(),
+                        args: None,
+                        addon_id: None,
+                        token: None,
+                    };
+                    let open = crate::ipc::request::send_ipc_request(req)
+                        .ok()
+                        .filter(|resp| resp.ok)
+                        .and_then(|resp| resp.data)
+                        .and_then(|data| data.get("open").cloned())
+                        .filter(|open| !open.is_null());
+                    if let Some(open) = open {
+                        let addon_id = open.get("addon_id").cloned().unwrap_or(JsonValue::Null);
+                        let tab = open.get("tab").cloned().unwrap_or(JsonValue::Null);
+                        info!("[ui] Opening {} (tab {}) on request", addon_id, tab);
+                        let _ = webview.evaluate_script(&format!(
+                            "if(typeof __odOpenTab==='function')__odOpenTab({},{});",
+                            addon_id, tab
+                        ));
+                        window.set_minimized(false);
+                        window.set_visible(true);
+                        window.set_focus();
+                    }
                 }
 
                 // Periodic monitor polling for live UI updates (every 2s)
@@ -742,15 +1133,7 @@ fn run_veil_custom_tabs_shell(
                     last_monitor_poll = std::time::Instant::now();
                     let fresh_monitors: Vec<WallpaperShellMonitor> = MonitorManager::enumerate_monitors()
                         .into_iter()
-                        .map(|m| WallpaperShellMonitor {
-                            id: m.id,
-                            x: m.x,
-                            y: m.y,
-                            width: m.width,
-                            height: m.height,
-                            scale: m.scale,
-                            primary: m.primary,
-                        })
+                        .map(WallpaperShellMonitor::from)
                         .collect();
                     if let Ok(json) = serde_json::to_string(&fresh_monitors) {
                         if json != cached_monitor_json {
@@ -768,17 +1151,14 @@ fn run_veil_custom_tabs_shell(
                 // is the one updating config.yaml — our in-memory config is stale.
                 if last_config_push.elapsed() >= std::time::Duration::from_secs(2) {
                     last_config_push = std::time::Instant::now();
-                    let config_path = snapshot_home.join("config.yaml");
-                    if let Ok(yaml_text) = std::fs::read_to_string(&config_path) {
-                        if let Ok(cfg) = serde_yaml::from_str::<crate::config::BackendConfig>(&yaml_text) {
-                            if let Ok(cfg_json) = serde_json::to_string(&cfg) {
-                                if cfg_json != cached_config_json {
-                                    cached_config_json = cfg_json.clone();
-                                    let _ = webview.evaluate_script(&format!(
-                                        "window.__odConfig={};if(typeof __odOnConfigPush==='function')__odOnConfigPush(window.__odConfig);",
-                                        cfg_json
-                                    ));
-                                }
+                    if let Some(cfg) = crate::config::read_config_from_disk() {
+                        if let Ok(cfg_json) = serde_json::to_string(&cfg) {
+                            if cfg_json != cached_config_json {
+                                cached_config_json = cfg_json.clone();
+                                let _ = webview.evaluate_script(&format!(
+                                    "window.__odConfig={};if(typeof __odOnConfigPush==='function')__odOnConfigPush(window.__odConfig);",
+                                    cfg_json
+                                ));
                             }
                         }
                     }
@@ -805,23 +1185,71 @@ fn run_veil_custom_tabs_shell(
                         ns: "registry".to_string(),
                         cmd: "full".to_string(),
                         args: None,
+                        addon_id: None,
+                        token: None,
                     };
                     if let Ok(resp) = crate::ipc::request::send_ipc_request(req) {
                         if resp.ok {
                             if let Some(data) = resp.data {
-                                let json_str = data.to_string();
-                                if json_str != cached_registry_json {
-                                    cached_registry_json = json_str.clone();
-                                    let _ = webview.evaluate_script(&format!(
-                                        "if(typeof __odPushRegistry==='function')__odPushRegistry({});",
-                                        json_str
-                                    ));
+                                if cached_registry.as_ref() != Some(&data) {
+                                    // Send only the changes while they are smaller than the
+                                    // snapshot, with a full push now and then to resync.
+                                    let patch = cached_registry
+                                        .as_ref()
+                                        .filter(|_| last_full_registry_push.elapsed() < REGISTRY_RESYNC_INTERVAL)
+                                        .and_then(|last| crate::ipc::delta::smaller_patch(last, &data));
+                                    let script = match patch {
+                                        Some(ops) => format!(
+                                            "if(typeof __odPatchRegistry==='function')__odPatchRegistry({});",
+                                            JsonValue::Array(ops)
+                                        ),
+                                        None => {
+                                            last_full_registry_push = std::time::Instant::now();
+                                            format!(
+                                                "if(typeof __odPushRegistry==='function')__odPushRegistry({});",
+                                                data
+                                            )
+                                        }
+                                    };
+                                    let _ = webview.evaluate_script(&script);
+                                    cached_registry = Some(data);
                                 }
                             }
                         }
                     }
                 }
 
+                // Tail the backend log while the Logs page is open.
+                if logs_view_active
+                    && last_logs_poll.elapsed() >= std::time::Duration::from_millis(1000)
+                {
+                    last_logs_poll = std::time::Instant::now();
+                    let args = logs_filter
+                        .lock()
+                        .map(|filter| filter.clone())
+                        .unwrap_or(JsonValue::Null);
+                    let req = crate::ipc::request::IpcRequest {
+                        ns: "logs".to_string(),
+                        cmd: "tail".to_string(),
+                        args: Some(args),
+                        addon_id: None,
+                        token: None,
+                    };
+                    if let Ok(resp) = crate::ipc::request::send_ipc_request(req) {
+                        let json_str = match (resp.ok, resp.data) {
+                            (true, Some(data)) => data.to_string(),
+                            _ => serde_json::json!({ "error": resp.error }).to_string(),
+                        };
+                        if json_str != cached_logs_json {
+                            cached_logs_json = json_str.clone();
+                            let _ = webview.evaluate_script(&format!(
+                                "if(typeof __odPushLogs==='function')__odPushLogs({});",
+                                json_str
+                            ));
+                        }
+                    }
+                }
+
                 match &event {
                     Event::WindowEvent { event: win_event, .. } => {
                         match win_event {
@@ -847,10 +1275,11 @@ fn veil_home_dir() -> Result<PathBuf, String> {
     Ok(veil_root_dir())
 }
 
+/// `viewMode` values the shell's `render()` knows how to show.
+const SHELL_VIEW_MODES: [&str; 7] = ["addon", "home", "settings", "data", "store", "updater", "logs"];
+
 fn veil_shell_html_path() -> Result<PathBuf, String> {
-        Ok(veil_home_dir()?
-                .join("cache")
-                .join("veil_custom_tabs_shell.html"))
+        Ok(cache_dir().join("veil_custom_tabs_shell.html"))
 }
 
 /// Convert a filesystem path under VEIL/Core to a veil:// custom protocol URL.
@@ -974,11 +1403,14 @@ fn append_veil_data_query(
     format!("{}{}odData={}", base_url, sep, encoded)
 }
 
-fn build_wallpaper_shell_data(addon: &AddonMeta, veil_home: &Path) -> Option<WallpaperShellData> {
-    let is_wallpaper = addon.package.eq_ignore_ascii_case("wallpaper")
+fn is_wallpaper_addon(addon: &AddonMeta) -> bool {
+    addon.package.eq_ignore_ascii_case("wallpaper")
         || addon.id.to_lowercase().contains("wallpaper")
-        || addon.name.to_lowercase().contains("wallpaper");
-    if !is_wallpaper {
+        || addon.name.to_lowercase().contains("wallpaper")
+}
+
+fn build_wallpaper_shell_data(addon: &AddonMeta, veil_home: &Path) -> Option<WallpaperShellData> {
+    if !is_wallpaper_addon(addon) {
         return None;
     }
 
@@ -986,19 +1418,7 @@ fn build_wallpaper_shell_data(addon: &AddonMeta, veil_home: &Path) -> Option<Wal
     let config_root = serde_yaml::from_str::<Value>(&config_content).ok()?;
 
     let schema = load_schema(&addon.schema_path);
-    let mut monitors = MonitorManager::enumerate_monitors()
-        .into_iter()
-        .map(|m| WallpaperShellMonitor {
-            id: m.id,
-            x: m.x,
-            y: m.y,
-            width: m.width,
-            height: m.height,
-            scale: m.scale,
-            primary: m.primary,
-        })
-        .collect::<Vec<_>>();
-    sort_monitors_for_wallpaper_indexes(&mut monitors);
+    let monitors = sorted_shell_monitors(&config_root);
 
     let profiles = parse_wallpaper_profiles(&config_root);
     let enabled_profiles: Vec<&WallpaperProfileEntry> = profiles.iter().filter(|p| p.enabled).collect();
@@ -1013,9 +1433,11 @@ fn build_wallpaper_shell_data(addon: &AddonMeta, veil_home: &Path) -> Option<Wal
         .copied()
         .or_else(|| profiles.first());
 
+    let prefs = AssetPrefs::load();
     let assets = discover_assets_for_meta(addon, schema.as_ref())
         .into_iter()
         .map(|asset| {
+            let key = asset.prefs_key();
             let (author_name, author_url) = asset
                 .authors
                 .first()
@@ -1049,6 +1471,9 @@ fn build_wallpaper_shell_data(addon: &AddonMeta, veil_home: &Path) -> Option<Wal
                 html_url,
                 editable: asset.editable.clone(),
                 manifest_path: asset.manifest_path.to_string_lossy().to_string(),
+                issues: asset.issues.iter().map(|i| i.message.clone()).collect(),
+                favorite: prefs.is_favorite(&key),
+                recent_rank: prefs.recent_rank(&key),
             }
         })
         .collect::<Vec<_>>();
@@ -1056,10 +1481,15 @@ fn build_wallpaper_shell_data(addon: &AddonMeta, veil_home: &Path) -> Option<Wal
     let shell_profiles: Vec<WallpaperShellProfile> = profiles.iter().map(|p| {
         WallpaperShellProfile {
             monitor_index: p.monitor_index.first().cloned().unwrap_or_else(|| "*".to_string()),
+            monitor_id: p.monitor_id.clone(),
             enabled: p.enabled,
             wallpaper_id: p.wallpaper_id.clone(),
             mode: p.mode.clone(),
             z_index: p.z_index.clone(),
+            playlist: p.playlist.clone(),
+            rotate_interval_minutes: p.rotate_interval_minutes,
+            playlist_index: playlist::playlist_index(&addon.id, &p.section),
+            schedule: p.schedule.clone(),
         }
     }).collect();
 
@@ -1087,6 +1517,7 @@ fn build_wallpaper_shell_data(addon: &AddonMeta, veil_home: &Path) -> Option<Wal
         z_index: primary_profile
             .and_then(|p| p.z_index.clone())
             .or_else(|| yaml_string(&config_root, "wallpaper.z_index")),
+        z_index_options: z_index::Z_INDEXES,
         monitor_index: primary_profile
             .map(|p| p.monitor_index.clone())
             .unwrap_or_else(|| yaml_string_list(&config_root, "wallpaper.monitor_index")),
@@ -1177,14 +1608,27 @@ fn parse_wallpaper_profiles(root: &Value) -> Vec<WallpaperProfileEntry> {
 }
 
 fn parse_wallpaper_profile_section(section: &str, map: &Mapping) -> Option<WallpaperProfileEntry> {
+    let playlist: Vec<String> = match map.get(Value::String("playlist".to_string())) {
+        Some(Value::Sequence(seq)) => seq
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    };
+    let schedule = parse_wallpaper_schedule(map);
     let wallpaper_id = map
         .get(Value::String("wallpaper_id".to_string()))
-        .and_then(|v| v.as_str())?
-        .trim()
-        .to_string();
-    if wallpaper_id.is_empty() {
-        return None;
-    }
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| playlist.first().cloned())
+        .or_else(|| schedule.first().map(|e| e.wallpaper_id.clone()))?;
+
+    let rotate_interval_minutes = map
+        .get(Value::String("rotate_interval_minutes".to_string()))
+        .and_then(|v| v.as_u64());
 
     let enabled = map
         .get(Value::String("enabled".to_string()))
@@ -1207,6 +1651,12 @@ fn parse_wallpaper_profile_section(section: &str, map: &Mapping) -> Option<Wallp
         _ => vec!["*".to_string()],
     };
 
+    let monitor_id = map
+        .get(Value::String("monitor_id".to_string()))
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
     let mode = map
         .get(Value::String("mode".to_string()))
         .and_then(|v| v.as_str())
@@ -1214,18 +1664,59 @@ fn parse_wallpaper_profile_section(section: &str, map: &Mapping) -> Option<Wallp
     let z_index = map
         .get(Value::String("z_index".to_string()))
         .and_then(|v| v.as_str())
-        .map(|s| s.to_lowercase());
+        .map(|s| z_index::normalize(s).map(str::to_string).unwrap_or_else(|_| s.to_lowercase()));
 
     Some(WallpaperProfileEntry {
         section: section.to_string(),
         enabled,
         monitor_index,
+        monitor_id,
         wallpaper_id,
         mode,
         z_index,
+        playlist,
+        rotate_interval_minutes,
+        schedule,
     })
 }
 
+/// Read a profile's `schedule` list, skipping entries with a malformed time
+/// or no wallpaper.
+fn parse_wallpaper_schedule(map: &Mapping) -> Vec<WallpaperScheduleEntry> {
+    let Some(Value::Sequence(items)) = map.get(Value::String("schedule".to_string())) else {
+        return Vec::new();
+    };
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let field = |key: &str| item.get(key).and_then(|v| v.as_str()).map(|s| s.trim().to_string());
+            let (from, to, wallpaper_id) = (field("from")?, field("to")?, field("wallpaper_id")?);
+            if wallpaper_id.is_empty() {
+                return None;
+            }
+            Some(WallpaperScheduleEntry {
+                from_minute: parse_clock_minute(&from)?,
+                to_minute: parse_clock_minute(&to)?,
+                from,
+                to,
+                wallpaper_id,
+            })
+        })
+        .collect()
+}
+
+/// `"HH:MM"` → minutes since midnight. `24:00` is accepted as the end of the day.
+fn parse_clock_minute(text: &str) -> Option<u32> {
+    let (h, m) = text.split_once(':')?;
+    let (h, m) = (h.trim().parse::<u32>().ok()?, m.trim().parse::<u32>().ok()?);
+    match (h, m) {
+        (24, 0) => Some(24 * 60),
+        (0..=23, 0..=59) => Some(h * 60 + m),
+        _ => None,
+    }
+}
+
 fn wallpaper_section_order_key(section: &str) -> (u8, u32, String) {
     if section == "wallpaper" {
         return (0, 0, section.to_string());
@@ -1240,7 +1731,24 @@ fn wallpaper_section_order_key(section: &str) -> (u8, u32, String) {
     (2, u32::MAX, section.to_string())
 }
 
-fn sort_monitors_for_wallpaper_indexes(monitors: &mut [WallpaperShellMonitor]) {
+/// Stable ids from the addon config's `monitor_index_order`, the user's manual
+/// wallpaper index order. Empty when the automatic order is used.
+fn monitor_index_order(root: &Value) -> Vec<String> {
+    yaml_string_list(root, "monitor_index_order")
+}
+
+/// Wallpaper index order: rows top to bottom, left to right within a row.
+/// Monitors named in `order` (by stable id) then move to the front in that
+/// order; ids that aren't connected are skipped, and monitors the override
+/// doesn't mention keep their automatic order after the listed ones.
+fn sort_monitors_for_wallpaper_indexes(monitors: &mut [WallpaperShellMonitor], order: &[String]) {
+    sort_monitors_by_position(monitors);
+    if !order.is_empty() {
+        monitors.sort_by_key(|m| order.iter().position(|id| *id == m.stable_id).unwrap_or(usize::MAX));
+    }
+}
+
+fn sort_monitors_by_position(monitors: &mut [WallpaperShellMonitor]) {
     if monitors.len() <= 1 {
         return;
     }
@@ -1286,9 +1794,23 @@ fn build_monitor_assignments(
     let mut assignments = HashMap::<String, String>::new();
     let mut assigned = HashSet::<usize>::new();
 
+    // Pinned profiles claim their monitor first, wherever it now sits. One whose
+    // monitor is disconnected applies nowhere rather than to whichever monitor
+    // took over its index.
+    for profile in profiles {
+        let Some(stable_id) = profile.monitor_id.as_deref() else {
+            continue;
+        };
+        if let Some(index) = monitors.iter().position(|m| m.stable_id == stable_id) {
+            if assigned.insert(index) {
+                assignments.insert(monitors[index].id.clone(), profile.wallpaper_id.clone());
+            }
+        }
+    }
+
     for priority in [0u8, 1u8, 2u8] {
         for profile in profiles {
-            if profile_priority(profile) != priority {
+            if profile.monitor_id.is_some() || profile_priority(profile) != priority {
                 continue;
             }
 
@@ -1387,21 +1909,11 @@ fn apply_wallpaper_assignment_from_shell(
         .cloned()
         .collect::<Vec<_>>();
 
-    if target_indexes.is_empty() {
-        let mut monitors = MonitorManager::enumerate_monitors()
-            .into_iter()
-            .map(|m| WallpaperShellMonitor {
-                id: m.id,
-                x: m.x,
-                y: m.y,
-                width: m.width,
-                height: m.height,
-                scale: m.scale,
-                primary: m.primary,
-            })
-            .collect::<Vec<_>>();
-        sort_monitors_for_wallpaper_indexes(&mut monitors);
+    let content = std::fs::read_to_string(&addon.config_path).unwrap_or_else(|_| "{}".to_string());
+    let mut root = serde_yaml::from_str::<Value>(&content).unwrap_or_else(|_| Value::Mapping(Mapping::new()));
 
+    let monitors = sorted_shell_monitors(&root);
+    if target_indexes.is_empty() {
         for monitor_id in monitor_ids {
             if let Some(idx) = monitors.iter().position(|m| m.id == *monitor_id) {
                 target_indexes.push(idx.to_string());
@@ -1415,30 +1927,75 @@ fn apply_wallpaper_assignment_from_shell(
         return Err("No monitor indexes resolved from monitor IDs".to_string());
     }
 
+    let wallpapers_map = yaml_path::ensure_mapping(&mut root, &yaml_path::split("wallpapers"));
+
+    for target_idx in &target_indexes {
+        let stable_id = stable_id_at_index(&monitors, target_idx);
+        upsert_wallpaper_profile_for_index(wallpapers_map, target_idx, stable_id, wallpaper_id);
+    }
+
+    let serialized = serde_yaml::to_string(&root)
+        .map_err(|e| format!("Failed to serialize YAML: {}", e))?;
+    std::fs::write(&addon.config_path, serialized)
+        .map_err(|e| format!("Failed to write '{}': {}", addon.config_path.display(), e))?;
+
+    Ok(())
+}
+
+/// Show `wallpaper_id` on every monitor: `wallpapers` is reduced to one
+/// unpinned `monitor_index: ["*"]` profile, and per-monitor profiles (including
+/// legacy top-level `wallpaper*` sections) are removed. The surviving profile
+/// keeps the `mode`/`z_index` of the previous all-monitors profile, or the first
+/// profile, so running it twice gives the same file.
+fn apply_wallpaper_to_all_monitors(addon_id: &str, wallpaper_id: &str) -> Result<(), String> {
+    let addon = discover_addon_configs()
+        .into_iter()
+        .find(|a| a.id.eq_ignore_ascii_case(addon_id))
+        .ok_or_else(|| format!("Addon '{}' not found", addon_id))?;
+
     let content = std::fs::read_to_string(&addon.config_path).unwrap_or_else(|_| "{}".to_string());
     let mut root = serde_yaml::from_str::<Value>(&content).unwrap_or_else(|_| Value::Mapping(Mapping::new()));
-    if !matches!(root, Value::Mapping(_)) {
-        root = Value::Mapping(Mapping::new());
-    }
 
-    let root_map = root
-        .as_mapping_mut()
-        .ok_or_else(|| "Config root is not a mapping".to_string())?;
+    let profiles = parse_wallpaper_profiles(&root);
+    let template = profiles
+        .iter()
+        .find(|p| p.monitor_id.is_none() && p.monitor_index == ["*"])
+        .or_else(|| profiles.first());
+    let mode = template.and_then(|p| p.mode.clone()).unwrap_or_else(|| "fill".to_string());
+    let z_index = template
+        .and_then(|p| p.z_index.as_deref())
+        .and_then(|z| z_index::normalize(z).ok())
+        .unwrap_or(z_index::DEFAULT_Z_INDEX)
+        .to_string();
 
-    let wallpapers_value = root_map
-        .entry(Value::String("wallpapers".to_string()))
-        .or_insert_with(|| Value::Mapping(Mapping::new()));
-    if !matches!(wallpapers_value, Value::Mapping(_)) {
-        *wallpapers_value = Value::Mapping(Mapping::new());
+    if let Some(root_map) = root.as_mapping_mut() {
+        root_map.retain(|key, value| {
+            let Some(section) = key.as_str().filter(|k| k.starts_with("wallpaper") && *k != "wallpapers") else {
+                return true;
+            };
+            value
+                .as_mapping()
+                .and_then(|m| parse_wallpaper_profile_section(section, m))
+                .is_none()
+        });
     }
 
-    let wallpapers_map = wallpapers_value
-        .as_mapping_mut()
-        .ok_or_else(|| "'wallpapers' is not a mapping".to_string())?;
+    let mut section = Mapping::new();
+    section.insert(Value::String("enabled".to_string()), Value::Bool(true));
+    section.insert(
+        Value::String("monitor_index".to_string()),
+        Value::Sequence(vec![Value::String("*".to_string())]),
+    );
+    section.insert(
+        Value::String("wallpaper_id".to_string()),
+        Value::String(wallpaper_id.to_string()),
+    );
+    section.insert(Value::String("mode".to_string()), Value::String(mode));
+    section.insert(Value::String("z_index".to_string()), Value::String(z_index));
 
-    for target_idx in &target_indexes {
-        upsert_wallpaper_profile_for_index(wallpapers_map, target_idx, wallpaper_id);
-    }
+    let wallpapers_map = yaml_path::ensure_mapping(&mut root, &yaml_path::split("wallpapers"));
+    wallpapers_map.clear();
+    wallpapers_map.insert(Value::String("wallpaper0".to_string()), Value::Mapping(section));
 
     let serialized = serde_yaml::to_string(&root)
         .map_err(|e| format!("Failed to serialize YAML: {}", e))?;
@@ -1448,49 +2005,104 @@ fn apply_wallpaper_assignment_from_shell(
     Ok(())
 }
 
+/// Current monitors in wallpaper index order, honouring the
+/// `monitor_index_order` override in the addon config `root`.
+fn sorted_shell_monitors(root: &Value) -> Vec<WallpaperShellMonitor> {
+    let mut monitors = MonitorManager::enumerate_monitors()
+        .into_iter()
+        .map(WallpaperShellMonitor::from)
+        .collect::<Vec<_>>();
+    sort_monitors_for_wallpaper_indexes(&mut monitors, &monitor_index_order(root));
+    monitors
+}
+
+/// Stable identity of the monitor at wallpaper index `index`, when known.
+fn stable_id_at_index<'a>(monitors: &'a [WallpaperShellMonitor], index: &str) -> Option<&'a str> {
+    let monitor = monitors.get(index.parse::<usize>().ok()?)?;
+    (!monitor.stable_id.is_empty()).then_some(monitor.stable_id.as_str())
+}
+
+fn section_monitor_indexes(section_map: &Mapping) -> Vec<String> {
+    match section_map.get(Value::String("monitor_index".to_string())) {
+        Some(Value::Sequence(seq)) => seq
+            .iter()
+            .filter_map(|item| item.as_str().map(|s| s.to_string()))
+            .collect(),
+        Some(Value::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// Section for a monitor: the one pinned to `stable_id` if any, otherwise an
+/// unpinned section targeting exactly `monitor_index`. Sections pinned to
+/// another (possibly disconnected) monitor are left alone.
+fn find_profile_section(wallpapers_map: &Mapping, monitor_index: &str, stable_id: Option<&str>) -> Option<String> {
+    let pinned_to = |m: &Mapping| {
+        m.get(Value::String("monitor_id".to_string()))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+    let sections = wallpapers_map
+        .iter()
+        .filter_map(|(k, v)| Some((k.as_str()?, v.as_mapping()?)))
+        .collect::<Vec<_>>();
+
+    if let Some(stable_id) = stable_id {
+        if let Some((key, _)) = sections.iter().find(|(_, m)| pinned_to(m).as_deref() == Some(stable_id)) {
+            return Some(key.to_string());
+        }
+    }
+    sections
+        .iter()
+        .find(|(_, m)| pinned_to(m).is_none() && section_monitor_indexes(m) == [monitor_index])
+        .map(|(key, _)| key.to_string())
+}
+
+/// Point the profile for a monitor at `wallpaper_id`, creating it if needed,
+/// and return its section key. The section is pinned to `stable_id` when
+/// known, and any playlist on it is dropped.
 fn upsert_wallpaper_profile_for_index(
     wallpapers_map: &mut Mapping,
     monitor_index: &str,
+    stable_id: Option<&str>,
     wallpaper_id: &str,
-) {
-    for (_section_key, section_value) in wallpapers_map.iter_mut() {
-        let Some(section_map) = section_value.as_mapping_mut() else {
-            continue;
-        };
-
-        let current_indexes = section_map
-            .get(Value::String("monitor_index".to_string()))
-            .and_then(|v| match v {
-                Value::Sequence(seq) => Some(
-                    seq.iter()
-                        .filter_map(|item| item.as_str().map(|s| s.to_string()))
-                        .collect::<Vec<_>>(),
-                ),
-                Value::String(s) => Some(vec![s.clone()]),
-                _ => None,
-            })
-            .unwrap_or_default();
-
-        if current_indexes.len() == 1 && current_indexes[0] == monitor_index {
+) -> String {
+    if let Some(section_key) = find_profile_section(wallpapers_map, monitor_index, stable_id) {
+        if let Some(Value::Mapping(section_map)) = wallpapers_map.get_mut(Value::String(section_key.clone())) {
             section_map.insert(
                 Value::String("wallpaper_id".to_string()),
                 Value::String(wallpaper_id.to_string()),
             );
+            section_map.remove(Value::String("playlist".to_string()));
+            section_map.remove(Value::String("rotate_interval_minutes".to_string()));
             section_map.insert(Value::String("enabled".to_string()), Value::Bool(true));
+            // The pinned monitor may have moved; keep the index the addon reads in step.
+            section_map.insert(
+                Value::String("monitor_index".to_string()),
+                Value::Sequence(vec![Value::String(monitor_index.to_string())]),
+            );
+            if let Some(stable_id) = stable_id {
+                section_map.insert(
+                    Value::String("monitor_id".to_string()),
+                    Value::String(stable_id.to_string()),
+                );
+            }
             if !section_map.contains_key(Value::String("mode".to_string())) {
                 section_map.insert(
                     Value::String("mode".to_string()),
                     Value::String("fill".to_string()),
                 );
             }
-            if !section_map.contains_key(Value::String("z_index".to_string())) {
+            if section_map.contains_key(Value::String("z_index".to_string())) {
+                z_index::normalize_section(section_map);
+            } else {
                 section_map.insert(
                     Value::String("z_index".to_string()),
-                    Value::String("desktop".to_string()),
+                    Value::String(z_index::DEFAULT_Z_INDEX.to_string()),
                 );
             }
-            return;
         }
+        return section_key;
     }
 
     let mut max_suffix = 0u32;
@@ -1512,6 +2124,12 @@ fn upsert_wallpaper_profile_for_index(
         Value::String("monitor_index".to_string()),
         Value::Sequence(vec![Value::String(monitor_index.to_string())]),
     );
+    if let Some(stable_id) = stable_id {
+        new_section.insert(
+            Value::String("monitor_id".to_string()),
+            Value::String(stable_id.to_string()),
+        );
+    }
     new_section.insert(
         Value::String("wallpaper_id".to_string()),
         Value::String(wallpaper_id.to_string()),
@@ -1522,31 +2140,32 @@ fn upsert_wallpaper_profile_for_index(
     );
     new_section.insert(
         Value::String("z_index".to_string()),
-        Value::String("desktop".to_string()),
+        Value::String(z_index::DEFAULT_Z_INDEX.to_string()),
     );
 
-    wallpapers_map.insert(Value::String(new_key), Value::Mapping(new_section));
+    wallpapers_map.insert(Value::String(new_key.clone()), Value::Mapping(new_section));
+    new_key
 }
 
 fn yaml_string(root: &Value, dotted_path: &str) -> Option<String> {
-    get_node(root, &split_path(dotted_path))
+    yaml_path::get(root, &yaml_path::split(dotted_path))
         .and_then(|v| v.as_str().map(|s| s.to_string()))
 }
 
 fn yaml_bool(root: &Value, dotted_path: &str) -> Option<bool> {
-    get_node(root, &split_path(dotted_path)).and_then(|v| v.as_bool())
+    yaml_path::get(root, &yaml_path::split(dotted_path)).and_then(|v| v.as_bool())
 }
 
 fn yaml_i64(root: &Value, dotted_path: &str) -> Option<i64> {
-    get_node(root, &split_path(dotted_path)).and_then(|v| v.as_i64())
+    yaml_path::get(root, &yaml_path::split(dotted_path)).and_then(|v| v.as_i64())
 }
 
 fn yaml_f64(root: &Value, dotted_path: &str) -> Option<f64> {
-    get_node(root, &split_path(dotted_path)).and_then(|v| v.as_f64())
+    yaml_path::get(root, &yaml_path::split(dotted_path)).and_then(|v| v.as_f64())
 }
 
 fn yaml_string_list(root: &Value, dotted_path: &str) -> Vec<String> {
-    match get_node(root, &split_path(dotted_path)) {
+    match yaml_path::get(root, &yaml_path::split(dotted_path)) {
         Some(Value::Sequence(seq)) => seq
             .iter()
             .filter_map(|v| v.as_str().map(|s| s.to_string()))
@@ -1558,7 +2177,7 @@ fn yaml_string_list(root: &Value, dotted_path: &str) -> Vec<String> {
 
 fn yaml_string_map(root: &Value, dotted_path: &str) -> HashMap<String, String> {
     let mut out = HashMap::new();
-    let Some(Value::Mapping(map)) = get_node(root, &split_path(dotted_path)) else {
+    let Some(Value::Mapping(map)) = yaml_path::get(root, &yaml_path::split(dotted_path)) else {
         return out;
     };
 
@@ -1584,34 +2203,6 @@ fn dir_size(path: &Path) -> u64 {
         .sum()
 }
 
-fn json_to_yaml(value: &serde_json::Value) -> Value {
-    serde_yaml::to_value(value).unwrap_or(Value::Null)
-}
-
-fn set_yaml_value(root: &mut Value, path: &str, value: Value) {
-    let parts: Vec<&str> = path.split('.').collect();
-    let mut current = root;
-
-    for (i, part) in parts.iter().enumerate() {
-        if i == parts.len() - 1 {
-            if let Value::Mapping(map) = current {
-                map.insert(Value::String(part.to_string()), value);
-                return;
-            }
-        } else {
-            if !matches!(current, Value::Mapping(_)) {
-                *current = Value::Mapping(Mapping::new());
-            }
-            let map = current.as_mapping_mut().unwrap();
-            let key = Value::String(part.to_string());
-            if !map.contains_key(&key) {
-                map.insert(key.clone(), Value::Mapping(Mapping::new()));
-            }
-            current = map.get_mut(&key).unwrap();
-        }
-    }
-}
-
 fn apply_config_update(addon_id: &str, path: &str, value: &serde_json::Value) -> Result<(), String> {
     if path.is_empty() {
         return Err("Empty config path".to_string());
@@ -1622,10 +2213,16 @@ fn apply_config_update(addon_id: &str, path: &str, value: &serde_json::Value) ->
         .find(|a| a.id.eq_ignore_ascii_case(addon_id))
         .ok_or_else(|| format!("Addon '{}' not found", addon_id))?;
 
+    let value = if is_wallpaper_addon(&addon) && z_index::is_z_index_path(path) {
+        checked_z_index(value)?
+    } else {
+        value.clone()
+    };
+
     let content = std::fs::read_to_string(&addon.config_path).unwrap_or_else(|_| "{}".to_string());
     let mut root = serde_yaml::from_str::<Value>(&content).unwrap_or_else(|_| Value::Mapping(Mapping::new()));
 
-    set_yaml_value(&mut root, path, json_to_yaml(value));
+    yaml_path::set(&mut root, &yaml_path::split(path), yaml_path::from_json(&value));
 
     let serialized = serde_yaml::to_string(&root)
         .map_err(|e| format!("Failed to serialize YAML: {}", e))?;
@@ -1635,6 +2232,12 @@ fn apply_config_update(addon_id: &str, path: &str, value: &serde_json::Value) ->
     Ok(())
 }
 
+/// A `z_index` about to be saved, as its canonical name.
+fn checked_z_index(value: &serde_json::Value) -> Result<serde_json::Value, String> {
+    let raw = value.as_str().ok_or_else(|| format!("z_index must be a string, got {}", value))?;
+    z_index::normalize(raw).map(serde_json::Value::from)
+}
+
 fn apply_wallpaper_property_update(
     addon_id: &str,
     monitor_indexes: &[String],
@@ -1650,43 +2253,24 @@ fn apply_wallpaper_property_update(
         .find(|a| a.id.eq_ignore_ascii_case(addon_id))
         .ok_or_else(|| format!("Addon '{}' not found", addon_id))?;
 
+    let value = if property == "z_index" { checked_z_index(value)? } else { value.clone() };
+
     let content = std::fs::read_to_string(&addon.config_path).unwrap_or_else(|_| "{}".to_string());
     let mut root = serde_yaml::from_str::<Value>(&content).unwrap_or_else(|_| Value::Mapping(Mapping::new()));
-    if !matches!(root, Value::Mapping(_)) {
-        root = Value::Mapping(Mapping::new());
-    }
-
-    let root_map = root.as_mapping_mut().ok_or("Root is not a mapping")?;
-    let wallpapers_value = root_map
-        .entry(Value::String("wallpapers".to_string()))
-        .or_insert_with(|| Value::Mapping(Mapping::new()));
-    if !matches!(wallpapers_value, Value::Mapping(_)) {
-        *wallpapers_value = Value::Mapping(Mapping::new());
-    }
-    let wallpapers_map = wallpapers_value.as_mapping_mut().ok_or("wallpapers not a mapping")?;
+    let wallpapers_map = yaml_path::ensure_mapping(&mut root, &yaml_path::split("wallpapers"));
 
-    let yaml_value = json_to_yaml(value);
+    let yaml_value = yaml_path::from_json(&value);
 
     for (_section_key, section_value) in wallpapers_map.iter_mut() {
         let Some(section_map) = section_value.as_mapping_mut() else { continue };
 
-        let current_indexes = section_map
-            .get(Value::String("monitor_index".to_string()))
-            .and_then(|v| match v {
-                Value::Sequence(seq) => Some(
-                    seq.iter()
-                        .filter_map(|item| item.as_str().map(|s| s.to_string()))
-                        .collect::<Vec<_>>(),
-                ),
-                Value::String(s) => Some(vec![s.clone()]),
-                _ => None,
-            })
-            .unwrap_or_default();
+        let current_indexes = section_monitor_indexes(section_map);
 
         let matches = current_indexes.iter().any(|idx| monitor_indexes.contains(idx));
         if !matches { continue; }
 
         section_map.insert(Value::String(property.to_string()), yaml_value.clone());
+        z_index::normalize_section(section_map);
     }
 
     let serialized = serde_yaml::to_string(&root)
@@ -1764,51 +2348,32 @@ fn save_editable_to_manifest(manifest_path_str: &str, key: &str, value: &serde_j
     Err(format!("Key '{}' not found in editable section", key))
 }
 
-/// Capture a screenshot of the wallpaper for the preview image.
-/// Uses the wallpaper's index.html and captures via a headless approach.
-/// Since WebView screenshot APIs are limited, we copy the existing first preview
-/// or create a placeholder. In a full implementation, this would use a headless
-/// browser or wry's screenshot capability.
+/// Start an off-screen capture of the wallpaper into `preview/capture.png`.
+/// Returns once the capture process is running; see `preview_capture`.
 fn capture_wallpaper_preview(manifest_path_str: &str) -> Result<(), String> {
     if manifest_path_str.is_empty() {
         return Err("Missing manifest path".to_string());
     }
-    let manifest_path = PathBuf::from(manifest_path_str);
-    let manifest_dir = manifest_path.parent()
-        .ok_or("Cannot determine manifest directory")?;
-
-    let preview_dir = manifest_dir.join("preview");
-    if !preview_dir.exists() {
-        std::fs::create_dir_all(&preview_dir)
-            .map_err(|e| format!("Failed to create preview dir: {}", e))?;
-    }
-
-    // Use wry's print_to_pdf-like approach or an offscreen capture.
-    // For now we mark that a new preview is needed by writing a veil timestamp
-    // so the wallpaper addon's watcher knows to regenerate.
-    let marker_path = preview_dir.join(".preview_capture_pending");
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    std::fs::write(&marker_path, format!("{}", timestamp))
-        .map_err(|e| format!("Failed to write capture marker: {}", e))?;
-
-    warn!("[ui] Preview capture requested for {} — marker written", manifest_path_str);
-    Ok(())
+    preview_capture::spawn_capture(Path::new(manifest_path_str))
 }
 
 fn build_veil_custom_tabs_shell_html(
         addons: &[CustomTabShellAddon],
         selected_addon_id: &str,
+        initial_view_mode: &str,
+        initial_tab: Option<&str>,
 ) -> Result<String, Box<dyn std::error::Error>> {
         let addons_json = serde_json::to_string(addons)?;
         let selected_json = serde_json::to_string(selected_addon_id)?;
+        let view_mode_json = serde_json::to_string(initial_view_mode)?;
+        let initial_tab_json = serde_json::to_string(&initial_tab)?;
     let backend_version_json = serde_json::to_string(env!("CARGO_PKG_VERSION"))?;
+    let metric_labels_json = serde_json::to_string(&crate::ipc::metrics::metric_labels_json())?;
+    let theme = crate::config::read_config_from_disk().map(|c| c.theme).unwrap_or_default().as_str();
 
         Ok(format!(
                 r#"<!doctype html>
-<html lang="en">
+<html lang="en" data-theme="{theme}">
 <head>
     <meta charset="utf-8" />
     <meta name="viewport" content="width=device-width, initial-scale=1" />
@@ -1837,6 +2402,21 @@ fn build_veil_custom_tabs_shell_html(
             --transition-fast: 150ms cubic-bezier(0.4,0,0.2,1);
             --sidebar-width: 180px;
         }}
+        :root[data-theme="light"] {{
+            --bg-base: #f4f4f7;
+            --bg-surface: #ffffff;
+            --bg-elevated: #ececf1;
+            --bg-hover: #e2e2e9;
+            --bg-active: #d6d6df;
+            --border-subtle: rgba(0,0,0,0.06);
+            --border-default: rgba(0,0,0,0.12);
+            --border-strong: rgba(0,0,0,0.2);
+            --text-primary: #16161d;
+            --text-secondary: #55556a;
+            --text-tertiary: #8a8a9c;
+            --accent-subtle: rgba(220,38,38,0.1);
+            --shadow-md: 0 4px 12px rgba(0,0,0,0.12);
+        }}
         *, *::before, *::after {{ margin: 0; padding: 0; box-sizing: border-box; }}
         html, body {{ height: 100%; overflow: hidden; }}
         body {{
@@ -2247,6 +2827,21 @@ fn build_veil_custom_tabs_shell_html(
             font-family: inherit;
         }}
         .data-filter-chip:hover {{ background: var(--bg-hover); }}
+        .data-filter-search {{
+            margin-left: auto;
+            min-width: 180px;
+            padding: 6px 10px;
+            border-radius: var(--radius-sm);
+            border: 1px solid var(--border-subtle);
+            background: var(--bg-elevated);
+            color: var(--text-primary);
+            font-size: 12px;
+            font-family: inherit;
+            outline: none;
+        }}
+        .data-filter-search:focus {{ border-color: var(--accent-border); }}
+        .log-line.warn {{ color: #f59e0b; }}
+        .log-line.error {{ color: #ef4444; }}
         .data-filter-chip.active {{
             background: var(--accent-subtle);
             color: var(--accent);
@@ -2362,6 +2957,18 @@ fn build_veil_custom_tabs_shell_html(
         }}
         .data-bar-fill.warn {{ background: #f59e0b; }}
         .data-bar-fill.danger {{ background: #ef4444; }}
+        .core-grid {{ display: flex; gap: 2px; margin: 4px 0 8px; }}
+        .core-bar {{
+            flex: 1;
+            min-width: 3px;
+            height: 28px;
+            background: var(--bg-hover);
+            border-radius: 2px;
+            display: flex;
+            align-items: flex-end;
+            overflow: hidden;
+        }}
+        .core-bar .data-bar-fill {{ width: 100%; border-radius: 0; transition: height 0.3s ease; }}
         .data-big-value {{
             font-size: 24px;
             font-weight: 700;
@@ -2387,6 +2994,7 @@ fn build_veil_custom_tabs_shell_html(
         .data-tag.online {{ background: rgba(34,197,94,0.15); color: #22c55e; }}
         .data-tag.offline {{ background: rgba(239,68,68,0.15); color: #ef4444; }}
         .data-tag.charging {{ background: rgba(59,130,246,0.15); color: #3b82f6; }}
+        .data-tag.warning {{ background: rgba(245,158,11,0.15); color: #f59e0b; }}
         .data-panel-footer {{
             padding: 0 16px 12px;
             font-size: 11px;
@@ -2402,6 +3010,19 @@ fn build_veil_custom_tabs_shell_html(
         .data-connection-dot.live {{ background: #22c55e; box-shadow: 0 0 6px rgba(34,197,94,0.4); }}
         .data-connection-dot.stale {{ background: #f59e0b; }}
         .data-connection-dot.dead {{ background: #ef4444; }}
+        .data-paused-banner {{
+            display: flex;
+            align-items: center;
+            gap: 12px;
+            padding: 10px 14px;
+            margin-bottom: 12px;
+            border: 1px solid rgba(245,158,11,0.5);
+            border-radius: 8px;
+            background: rgba(245,158,11,0.1);
+            color: #f59e0b;
+            font-size: 13px;
+        }}
+        .data-paused-banner button {{ margin-left: auto; }}
         .data-stat-row {{
             display: flex;
             gap: 16px;
@@ -2575,6 +3196,29 @@ fn build_veil_custom_tabs_shell_html(
             color: var(--text-tertiary);
             margin-top: 4px;
         }}
+        .media-controls {{
+            display: flex;
+            justify-content: center;
+            gap: 8px;
+            margin-top: 8px;
+        }}
+        .media-ctrl-btn {{
+            display: inline-flex;
+            align-items: center;
+            justify-content: center;
+            width: 30px;
+            height: 30px;
+            border: none;
+            border-radius: 50%;
+            background: var(--bg-hover);
+            color: var(--text-secondary);
+            cursor: pointer;
+        }}
+        .media-ctrl-btn:hover {{ background: var(--bg-active); color: var(--text-primary); }}
+        .media-ctrl-btn.primary {{ background: var(--accent); color: #fff; }}
+        .media-ctrl-btn:disabled {{ opacity: 0.35; cursor: default; }}
+        .media-ctrl-btn svg {{ width: 14px; height: 14px; }}
+        .media-progress-bar.seekable {{ cursor: pointer; }}
         .media-meta {{
             display: flex;
             flex-wrap: wrap;
@@ -2711,6 +3355,14 @@ fn build_veil_custom_tabs_shell_html(
                         <path d="M1 1h4l2.68 13.39a2 2 0 0 0 2 1.61h8.72a2 2 0 0 0 2-1.61L23 6H6"/>
                     </svg>
                 </button>
+                <button class="quick-action-btn" data-tooltip="Logs">
+                    <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round">
+                        <path d="M14 2H6a2 2 0 0 0-2 2v16a2 2 0 0 0 2 2h12a2 2 0 0 0 2-2V8z"/>
+                        <polyline points="14 2 14 8 20 8"/>
+                        <line x1="8" y1="13" x2="16" y2="13"/>
+                        <line x1="8" y1="17" x2="16" y2="17"/>
+                    </svg>
+                </button>
                 <button class="quick-action-btn" data-tooltip="Updater">
                     <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round">
                         <polyline points="23 4 23 10 17 10"/>
@@ -2737,7 +3389,13 @@ fn build_veil_custom_tabs_shell_html(
         const ADDONS = {addons_json};
         let currentAddonId = {selected_json};
         const BACKEND_CURRENT_VERSION = {backend_version_json};
+        // Labels from the backend metric registry (ipc/metrics.rs).
+        const METRIC_LABELS = {metric_labels_json};
+        function mlabel(path, fallback) {{
+            return METRIC_LABELS['sysdata.' + path] || fallback || path;
+        }}
         let currentTabId = null;
+        const INITIAL_TAB = {initial_tab_json};
 
         const ADDON_ICONS = {{
             'wallpaper': '<svg width="18" height="18" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><rect x="3" y="3" width="18" height="18" rx="2" ry="2"/><circle cx="8.5" cy="8.5" r="1.5"/><polyline points="21 15 16 10 5 21"/></svg>',
@@ -2891,7 +3549,7 @@ fn build_veil_custom_tabs_shell_html(
             return ADDONS.find(a => a.id === currentAddonId) || ADDONS[0];
         }}
 
-        let viewMode = 'addon';
+        let viewMode = {view_mode_json};
 
         function renderAddons() {{
             const host = document.getElementById('nav-menu');
@@ -2968,8 +3626,16 @@ fn build_veil_custom_tabs_shell_html(
             }});
         }}
 
+        function applyTheme(theme) {{
+            var next = theme === 'light' ? 'light' : 'dark';
+            if (document.documentElement.getAttribute('data-theme') !== next) {{
+                document.documentElement.setAttribute('data-theme', next);
+            }}
+        }}
+
         function renderSettingsPage() {{
             var cfg = window.__odConfig || {{}};
+            var theme = cfg.theme === 'light' ? 'light' : 'dark';
             var fastRate = cfg.fast_pull_rate_ms || 50;
             var slowRate = cfg.slow_pull_rate_ms || 500;
             var rorChecked = cfg.refresh_on_request !== false;
@@ -2989,7 +3655,7 @@ fn build_veil_custom_tabs_shell_html(
                     '<h3>Data Collection — Slow Tier</h3>' +
                     '<p style="color:var(--text-dim);font-size:12px;margin:2px 0 8px;">Heavyweight data: CPU, GPU, RAM, storage, network, bluetooth, wifi, system, processes</p>' +
                     '<div class="setting-row"><span class="s-label">Slow Pull Rate (ms)</span>' +
-                        '<input type="number" id="cfg-slow-rate" class="s-input" value="' + slowRate + '" min="100" max="10000" step="100">' +
+                        '<input type="number" id="cfg-slow-rate" class="s-input" value="' + slowRate + '" min="100" max="60000" step="100">' +
                     '</div>' +
                 '</div>' +
                 '<div class="page-settings-group">' +
@@ -3008,7 +3674,10 @@ fn build_veil_custom_tabs_shell_html(
                 '<div class="page-settings-group">' +
                     '<h3>Interface</h3>' +
                     '<div class="setting-row"><span class="s-label">Theme</span>' +
-                        '<select id="cfg-theme" class="s-input"><option value="dark" selected>Dark</option><option value="light">Light</option></select>' +
+                        '<select id="cfg-theme" class="s-input">' +
+                            '<option value="dark"' + (theme === 'dark' ? ' selected' : '') + '>Dark</option>' +
+                            '<option value="light"' + (theme === 'light' ? ' selected' : '') + '>Light</option>' +
+                        '</select>' +
                     '</div>' +
                     '<div class="setting-row"><span class="s-label">Renderer</span>' +
                         '<select id="cfg-renderer" class="s-input">' +
@@ -3021,6 +3690,7 @@ fn build_veil_custom_tabs_shell_html(
             var slowEl = document.getElementById('cfg-slow-rate');
             var rorEl = document.getElementById('cfg-refresh-on-req');
             var pauseEl = document.getElementById('cfg-pull-paused');
+            var themeEl = document.getElementById('cfg-theme');
             var rendererEl = document.getElementById('cfg-renderer');
             var fastTimer = null;
             var slowTimer = null;
@@ -3052,6 +3722,13 @@ fn build_veil_custom_tabs_shell_html(
                 window.__odConfig.data_pull_paused = pauseEl.checked;
                 window.__odBridgePost({{ type: 'backend_setting', key: 'pull_paused', value: pauseEl.checked }});
             }});
+            if (themeEl) themeEl.addEventListener('change', function() {{
+                var next = themeEl.value === 'light' ? 'light' : 'dark';
+                if (!window.__odConfig) window.__odConfig = {{}};
+                window.__odConfig.theme = next;
+                applyTheme(next);
+                window.__odBridgePost({{ type: 'backend_setting', key: 'theme', value: next }});
+            }});
             if (rendererEl) rendererEl.addEventListener('change', function() {{
                 var mode = (rendererEl.value || 'webview2').toLowerCase();
                 window.__odBridgePost({{ type: 'ui_renderer_mode', renderer_mode: mode }});
@@ -3193,12 +3870,14 @@ fn build_veil_custom_tabs_shell_html(
 
         window.__odOnConfigPush = function(cfg) {{
             window.__odConfig = cfg || {{}};
+            applyTheme(window.__odConfig.theme);
 
             if (viewMode === 'settings') {{
                 var fastEl = document.getElementById('cfg-fast-rate');
                 var slowEl = document.getElementById('cfg-slow-rate');
                 var rorEl = document.getElementById('cfg-refresh-on-req');
                 var pauseEl = document.getElementById('cfg-pull-paused');
+                var themeEl = document.getElementById('cfg-theme');
 
                 var nextFast = Number((window.__odConfig && window.__odConfig.fast_pull_rate_ms) || 50);
                 var nextSlow = Number((window.__odConfig && window.__odConfig.slow_pull_rate_ms) || 500);
@@ -3209,6 +3888,8 @@ fn build_veil_custom_tabs_shell_html(
                 if (slowEl && Number(slowEl.value) !== nextSlow) slowEl.value = String(nextSlow);
                 if (rorEl && rorEl.checked !== nextRor) rorEl.checked = nextRor;
                 if (pauseEl && pauseEl.checked !== nextPaused) pauseEl.checked = nextPaused;
+                var nextTheme = window.__odConfig.theme === 'light' ? 'light' : 'dark';
+                if (themeEl && themeEl.value !== nextTheme) themeEl.value = nextTheme;
             }}
 
             if (viewMode === 'data') {{
@@ -3222,7 +3903,7 @@ fn build_veil_custom_tabs_shell_html(
             const header = document.getElementById('page-header');
             const content = document.getElementById('page-content');
             var dataPollRate = (window.__odConfig && window.__odConfig.fast_pull_rate_ms) || 80;
-            header.innerHTML = '<h2>Data</h2><p style="color:var(--text-dim);margin:4px 0 0;"><span class="data-connection-dot live"></span>Live registry via IPC — fast tier ' + dataPollRate + 'ms</p>';
+            header.innerHTML = '<h2>Data</h2><p style="color:var(--text-dim);margin:4px 0 0;"><span id="data-health-dot" class="data-connection-dot live"></span>Live registry via IPC — fast tier ' + dataPollRate + 'ms</p>';
             var uiDataExceptionEnabled = !!(window.__odConfig && window.__odConfig.ui_data_exception_enabled !== false);
             var chips = ['All','Hardware','Network','Input','System','App','JSON'];
             window.__dataActiveChip = window.__dataActiveChip || 'All';
@@ -3234,8 +3915,10 @@ fn build_veil_custom_tabs_shell_html(
                     '</div>' +
                     '<p style="color:var(--text-dim);font-size:12px;margin:4px 0 0;">When enabled, opening VEIL UI keeps all data updates active via UI heartbeat.</p>' +
                 '</div>' +
+                '<div id="data-paused-banner" class="data-paused-banner" style="display:none;"></div>' +
                 '<div class="data-filter">' +
                     chips.map(function(c) {{ return '<button class="data-filter-chip' + (c === window.__dataActiveChip ? ' active' : '') + '">' + c + '</button>'; }}).join('') +
+                    '<input type="search" id="data-search" class="data-filter-search" placeholder="Search panels and fields" spellcheck="false">' +
                 '</div>' +
                 '<div id="data-panels-container" class="data-panels-grid"></div>' +
                 '<div id="data-json-fallback" class="data-json-wrap" style="display:none;"><pre id="data-json-pre">Loading\u2026</pre></div>';
@@ -3254,6 +3937,17 @@ fn build_veil_custom_tabs_shell_html(
                     scheduleDataPanelsRender(true);
                 }};
             }});
+            var searchEl = document.getElementById('data-search');
+            if (searchEl) {{
+                searchEl.value = window.__dataSearch || '';
+                searchEl.addEventListener('input', function() {{
+                    clearTimeout(window.__dataSearchTimer);
+                    window.__dataSearchTimer = setTimeout(function() {{
+                        window.__dataSearch = searchEl.value;
+                        scheduleDataPanelsRender(true);
+                    }}, DATA_SEARCH_DEBOUNCE_MS);
+                }});
+            }}
             // Render immediately if we already have data
             if (window.__lastRegistryData) {{
                 scheduleDataPanelsRender(true);
@@ -3263,6 +3957,7 @@ fn build_veil_custom_tabs_shell_html(
         }}
 
         const DATA_RENDER_MIN_INTERVAL_MS = 100;
+        const DATA_SEARCH_DEBOUNCE_MS = 200;
         window.__dataRenderTimer = null;
         window.__dataRenderScheduled = false;
         window.__lastDataRenderTs = 0;
@@ -3355,15 +4050,101 @@ fn build_veil_custom_tabs_shell_html(
             return false;
         }}
 
+        // Connection health. A panel is stale once its section has missed
+        // DATA_STALE_INTERVALS of its own collection intervals; the whole page
+        // is dead when the newest snapshot is older than DATA_DEAD_INTERVALS
+        // of the slower pull rate. Both have a floor so jitter on very short
+        // rates doesn't flicker the dots.
+        const DATA_STALE_INTERVALS = 3;
+        const DATA_STALE_MIN_MS = 1000;
+        const DATA_DEAD_INTERVALS = 3;
+        const DATA_DEAD_MIN_MS = 5000;
+
+        function computePanelHealth(data) {{
+            var meta = data.__meta || {{}};
+            var sections = meta.sections || {{}};
+            var generated = meta.generated_at_ms || meta.written_ms || 0;
+            var health = {{}};
+            // Paused sections are expected to age; the banner says so instead.
+            if (meta.paused) return health;
+            Object.keys(sections).forEach(function(key) {{
+                var s = sections[key];
+                if (!s || s.tracked === false || !s.last_updated_ms) return;
+                var limit = Math.max(DATA_STALE_MIN_MS, (s.interval_ms || 0) * DATA_STALE_INTERVALS);
+                health[key] = (generated - s.last_updated_ms) > limit ? 'stale' : 'live';
+            }});
+            return health;
+        }}
+
+        // "Collection paused" banner with a Resume button, shown while the
+        // snapshot says pulling is paused.
+        function updatePausedBanner(data) {{
+            var banner = document.getElementById('data-paused-banner');
+            if (!banner) return;
+            var meta = (data && data.__meta) || {{}};
+            if (!meta.paused) {{
+                banner.style.display = 'none';
+                return;
+            }}
+            var when = meta.last_collected_ms ? new Date(meta.last_collected_ms).toLocaleTimeString() : 'before the pause';
+            var text = 'Collection paused — showing last snapshot from ' + when;
+            if (banner.style.display === 'none' || banner.getAttribute('data-text') !== text) {{
+                banner.setAttribute('data-text', text);
+                banner.innerHTML = '<span>' + escapeHtml(text) + '</span><button class="data-filter-chip" id="data-resume-btn">Resume</button>';
+                banner.style.display = '';
+                document.getElementById('data-resume-btn').onclick = function() {{
+                    if (!window.__odConfig) window.__odConfig = {{}};
+                    window.__odConfig.data_pull_paused = false;
+                    window.__odBridgePost({{ type: 'backend_setting', key: 'pull_paused', value: false }});
+                }};
+            }}
+        }}
+
+        function snapshotIsDead() {{
+            var data = window.__lastRegistryData;
+            var meta = (data && data.__meta) || {{}};
+            var generated = meta.generated_at_ms || meta.written_ms;
+            if (!generated) return true;
+            var cfg = window.__odConfig || {{}};
+            var rate = Math.max(cfg.fast_pull_rate_ms || 0, cfg.slow_pull_rate_ms || 0);
+            return (Date.now() - generated) > Math.max(DATA_DEAD_MIN_MS, rate * DATA_DEAD_INTERVALS);
+        }}
+
+        // Update the dots in place; runs after each render and on a timer,
+        // since a dead backend stops the pushes that would re-render.
+        function refreshDataHealth() {{
+            if (viewMode !== 'data') return;
+            var dead = snapshotIsDead();
+            var health = window.__panelHealth || {{}};
+            var anyStale = false;
+            document.querySelectorAll('.data-connection-dot[data-health-key]').forEach(function(dot) {{
+                var state = dead ? 'dead' : (health[dot.getAttribute('data-health-key')] || 'live');
+                if (state === 'stale') anyStale = true;
+                dot.className = 'data-connection-dot ' + state;
+                dot.title = state === 'live' ? 'Updating' : state === 'stale' ? 'Not refreshed recently' : 'Backend not responding';
+            }});
+            var header = document.getElementById('data-health-dot');
+            if (header) {{
+                var data = window.__lastRegistryData;
+                var paused = !!(data && data.__meta && data.__meta.paused);
+                var overall = dead ? 'dead' : (anyStale || paused) ? 'stale' : 'live';
+                header.className = 'data-connection-dot ' + overall;
+                header.title = dead ? 'No data from the backend' : paused ? 'Collection paused' : anyStale ? 'Some sections are not refreshing' : 'Registry is updating';
+            }}
+        }}
+        setInterval(refreshDataHealth, 1000);
+
         function panelCard(key, title, subtitle, bodyHtml) {{
             var icon = PANEL_ICONS[key] || PANEL_ICONS.system;
             var untracked = panelIsUntracked(key);
             var statePill = untracked ? '<div class="data-panel-state-pill">Untracked</div>' : '';
+            var health = window.__panelHealth && window.__panelHealth[key];
+            var dot = health ? '<span class="data-connection-dot ' + health + '" data-health-key="' + key + '"></span>' : '';
             return '<div class="data-panel' + (untracked ? ' untracked' : '') + '" data-panel-key="' + key + '">' +
                 statePill +
                 '<div class="data-panel-header">' +
                     '<div class="data-panel-icon">' + icon + '</div>' +
-                    '<div><div class="data-panel-title">' + title + '</div>' +
+                    '<div><div class="data-panel-title">' + dot + title + '</div>' +
                         (subtitle ? '<div class="data-panel-subtitle">' + subtitle + '</div>' : '') +
                     '</div>' +
                 '</div>' +
@@ -3371,29 +4152,78 @@ fn build_veil_custom_tabs_shell_html(
             '</div>';
         }}
 
+        // One thin bar per logical processor, in OS order.
+        function coreGrid(usages) {{
+            var bars = usages.map(function(u, i) {{
+                var pct = Math.max(0, Math.min(100, u || 0));
+                var cls = pct > 90 ? 'danger' : pct > 70 ? 'warn' : '';
+                return '<div class="core-bar" title="Core ' + i + ': ' + pct.toFixed(0) + '%"><div class="data-bar-fill ' + cls + '" style="height:' + pct + '%"></div></div>';
+            }}).join('');
+            return dataRow('Per Core', usages.length + ' logical') + '<div class="core-grid">' + bars + '</div>';
+        }}
+
         function buildCpuPanel(d) {{
             if (!d || d === null) return '';
             var body = '';
-            if (d.usage_percent != null) body += pctBar(d.usage_percent, 'Usage');
+            if (d.usage_percent != null) body += pctBar(d.usage_percent, mlabel('cpu.usage_percent'));
+            if (Array.isArray(d.per_core_usage) && d.per_core_usage.length > 0) body += coreGrid(d.per_core_usage);
             body += dataRow('Name', d.brand || '\u2014');
-            if (d.base_frequency_mhz != null) body += dataRow('Base Speed', (d.base_frequency_mhz/1000).toFixed(2) + ' GHz');
-            if (d.frequency_mhz != null) body += dataRow('Speed', (d.frequency_mhz/1000).toFixed(2) + ' GHz');
+            if (d.base_frequency_mhz != null) body += dataRow(mlabel('cpu.base_frequency_mhz'), (d.base_frequency_mhz/1000).toFixed(2) + ' GHz');
+            if (d.frequency_mhz != null) body += dataRow(mlabel('cpu.frequency_mhz'), (d.frequency_mhz/1000).toFixed(2) + ' GHz');
             if (d.sockets != null) body += dataRow('Sockets', d.sockets);
-            if (d.physical_cores != null) body += dataRow('Cores', d.physical_cores);
-            if (d.logical_cores != null) body += dataRow('Logical Processors', d.logical_cores);
+            if (d.physical_cores != null) body += dataRow(mlabel('cpu.physical_cores'), d.physical_cores);
+            if (d.logical_cores != null) body += dataRow(mlabel('cpu.logical_cores'), d.logical_cores);
             if (d.virtualization != null) body += dataRow('Virtualization', d.virtualization ? '<span class="data-tag online">Enabled</span>' : '<span class="data-tag offline">Disabled</span>');
             if (d.l1_cache_kb != null) body += dataRow('L1 Cache', d.l1_cache_kb >= 1024 ? (d.l1_cache_kb/1024).toFixed(1) + ' MB' : d.l1_cache_kb + ' KB');
             if (d.l2_cache_kb != null) body += dataRow('L2 Cache', d.l2_cache_kb >= 1024 ? (d.l2_cache_kb/1024).toFixed(1) + ' MB' : d.l2_cache_kb + ' KB');
             if (d.l3_cache_kb != null) body += dataRow('L3 Cache', d.l3_cache_kb >= 1024 ? (d.l3_cache_kb/1024).toFixed(1) + ' MB' : d.l3_cache_kb + ' KB');
-            if (d.process_count != null) body += dataRow('Processes', d.process_count);
-            if (d.thread_count != null) body += dataRow('Threads', d.thread_count);
-            if (d.handle_count != null) body += dataRow('Handles', d.handle_count);
-            if (d.temperature && d.temperature.average_c) body += dataRow('Temperature', d.temperature.average_c.toFixed(1) + ' \u00b0C');
+            if (d.process_count != null) body += dataRow(mlabel('cpu.process_count'), d.process_count);
+            if (d.thread_count != null) body += dataRow(mlabel('cpu.thread_count'), d.thread_count);
+            if (d.handle_count != null) body += dataRow(mlabel('cpu.handle_count'), d.handle_count);
+            if (d.temperature && d.temperature.average_c) body += dataRow(mlabel('cpu.temperature.average_c'), d.temperature.average_c.toFixed(1) + ' \u00b0C');
+            if (d.thermal_throttling != null) body += dataRow('Thermal Throttling', d.thermal_throttling ? '<span class="data-tag warning">Yes</span>' : 'No');
+            if (d.temperature && Array.isArray(d.temperature.sensors) && d.temperature.sensors.length > 1) {{
+                d.temperature.sensors.forEach(function(s) {{
+                    if (s.temperature_c != null) body += dataRow('&nbsp;&nbsp;' + (s.label || 'Sensor'), s.temperature_c.toFixed(1) + ' \u00b0C');
+                }});
+            }}
             if (d.uptime_seconds != null) {{
                 var s = d.uptime_seconds; var dd = Math.floor(s/86400); var hh = Math.floor((s%86400)/3600); var mm = Math.floor((s%3600)/60); var ss = s%60;
-                body += dataRow('Up Time', (dd > 0 ? dd + ':' : '') + (hh<10?'0':'') + hh + ':' + (mm<10?'0':'') + mm + ':' + (ss<10?'0':'') + ss);
+                body += dataRow(mlabel('cpu.uptime_seconds'), (dd > 0 ? dd + ':' : '') + (hh<10?'0':'') + hh + ':' + (mm<10?'0':'') + mm + ':' + (ss<10?'0':'') + ss);
             }}
-            return panelCard('cpu', 'CPU', d.brand || null, body);
+            var subtitle = d.brand || null;
+            if (d.thermal_throttling) subtitle = '<span class="data-tag warning">Throttling</span> ' + (subtitle || '');
+            return panelCard('cpu', 'CPU', subtitle, body);
+        }}
+
+        // Top VRAM consumers reported for one adapter.
+        function gpuProcessRows(procs) {{
+            if (!Array.isArray(procs) || procs.length === 0) return '';
+            var rows = dataRow('Top VRAM Users', '');
+            procs.forEach(function(p) {{
+                rows += dataRow('&nbsp;&nbsp;' + (p.name || 'PID ' + p.pid) + ' <span style="opacity:0.6">(' + p.pid + ')</span>', fmtBytes(p.vram_bytes || 0));
+            }});
+            return rows;
+        }}
+
+        function videoEngineRows(engines) {{
+            if (!Array.isArray(engines) || engines.length === 0) return '';
+            var rows = dataRow('Video Engines', '');
+            engines.forEach(function(e) {{
+                rows += dataRow('&nbsp;&nbsp;' + escapeHtml(e.engine_name || '?'), (e.usage_percent || 0).toFixed(0) + '%');
+            }});
+            return rows;
+        }}
+
+        // performance_limit as a row value; limits that cost speed get a warning tag.
+        function gpuLimitText(limit) {{
+            var names = {{ thermal: 'Thermal', power: 'Power', hardware: 'Hardware Slowdown', sync_boost: 'Sync Boost', clock_setting: 'Clock Setting', none: 'None' }};
+            var text = names[limit] || limit;
+            return gpuLimitWarns(limit) ? '<span class="data-tag warning">' + text + '</span>' : text;
+        }}
+
+        function gpuLimitWarns(limit) {{
+            return limit === 'thermal' || limit === 'power' || limit === 'hardware';
         }}
 
         function buildGpuPanel(d) {{
@@ -3401,6 +4231,9 @@ fn build_veil_custom_tabs_shell_html(
             var body = '';
             var adapters = d.adapters || [];
             if (adapters.length > 1) {{
+                var sum = d.summary || {{}};
+                if (sum.max_usage_percent != null) body += pctBar(sum.max_usage_percent, 'Busiest GPU' + (sum.busiest_adapter_index != null ? ' (GPU ' + sum.busiest_adapter_index + ')' : ''));
+                if (sum.total_vram_total_mb != null) body += dataRow('Total Dedicated Memory', (sum.total_vram_used_mb/1024).toFixed(1) + ' / ' + (sum.total_vram_total_mb/1024).toFixed(1) + ' GB');
                 // Multi-GPU: show each adapter as a section
                 adapters.forEach(function(a, i) {{
                     body += '<div style="margin-bottom:8px;padding-bottom:8px;' + (i < adapters.length-1 ? 'border-bottom:1px solid var(--border-color,#333);' : '') + '">';
@@ -3418,13 +4251,16 @@ fn build_veil_custom_tabs_shell_html(
                     }}
                     if (a.temperature_c != null) body += dataRow('Temperature', a.temperature_c.toFixed(1) + ' \u00b0C');
                     if (a.power_draw_w != null) body += dataRow('Power Draw', a.power_draw_w.toFixed(1) + ' W');
+                    if (a.performance_limit != null) body += dataRow('Performance Limit', gpuLimitText(a.performance_limit));
                     if (a.encoder_usage_percent != null) body += dataRow('Video Encode', a.encoder_usage_percent.toFixed(0) + '%');
                     if (a.decoder_usage_percent != null) body += dataRow('Video Decode', a.decoder_usage_percent.toFixed(0) + '%');
+                    body += videoEngineRows(a.video_engines);
+                    body += gpuProcessRows(a.processes);
                     body += '</div>';
                 }});
             }} else {{
                 // Single GPU — flat layout
-                if (d.usage_percent != null) body += pctBar(d.usage_percent, 'GPU Load');
+                if (d.usage_percent != null) body += pctBar(d.usage_percent, mlabel('gpu.usage_percent'));
                 body += dataRow('Name', d.name || '\u2014');
                 if (d.vram_total_mb != null && d.vram_used_mb != null) {{
                     body += dataRow('Dedicated Memory', (d.vram_used_mb/1024).toFixed(1) + ' / ' + (d.vram_total_mb/1024).toFixed(1) + ' GB');
@@ -3436,21 +4272,28 @@ fn build_veil_custom_tabs_shell_html(
                 if (d.physical_location && typeof d.physical_location === 'object') {{
                     body += dataRow('Physical Location', 'PCI bus ' + (d.physical_location.bus!=null?d.physical_location.bus:'?') + ', device ' + (d.physical_location.device!=null?d.physical_location.device:'?') + ', function ' + (d.physical_location.function!=null?d.physical_location.function:'?'));
                 }}
-                if (d.temperature_c != null) body += dataRow('Temperature', d.temperature_c.toFixed(1) + ' \u00b0C');
-                if (d.power_draw_w != null) body += dataRow('Power Draw', d.power_draw_w.toFixed(1) + ' W');
-                if (d.fan_speed_percent != null) body += dataRow('Fan Speed', d.fan_speed_percent + '%');
-                if (d.clock_graphics_mhz != null) body += dataRow('GPU Clock', d.clock_graphics_mhz + ' MHz');
-                if (d.clock_memory_mhz != null) body += dataRow('Mem Clock', d.clock_memory_mhz + ' MHz');
-                if (d.encoder_usage_percent != null) body += dataRow('Video Encode', d.encoder_usage_percent.toFixed(0) + '%');
-                if (d.decoder_usage_percent != null) body += dataRow('Video Decode', d.decoder_usage_percent.toFixed(0) + '%');
+                if (d.temperature_c != null) body += dataRow(mlabel('gpu.temperature_c'), d.temperature_c.toFixed(1) + ' \u00b0C');
+                if (d.power_draw_w != null) body += dataRow(mlabel('gpu.power_draw_w'), d.power_draw_w.toFixed(1) + ' W');
+                if (d.fan_speed_percent != null) body += dataRow(mlabel('gpu.fan_speed_percent'), d.fan_speed_percent + '%');
+                if (d.clock_graphics_mhz != null) body += dataRow(mlabel('gpu.clock_graphics_mhz'), d.clock_graphics_mhz + ' MHz');
+                if (d.clock_memory_mhz != null) body += dataRow(mlabel('gpu.clock_memory_mhz'), d.clock_memory_mhz + ' MHz');
+                if (d.performance_limit != null) body += dataRow('Performance Limit', gpuLimitText(d.performance_limit));
+                if (d.encoder_usage_percent != null) body += dataRow(mlabel('gpu.encoder_usage_percent'), d.encoder_usage_percent.toFixed(0) + '%');
+                if (d.decoder_usage_percent != null) body += dataRow(mlabel('gpu.decoder_usage_percent'), d.decoder_usage_percent.toFixed(0) + '%');
+                body += videoEngineRows(d.video_engines);
+                if (adapters.length === 1) body += gpuProcessRows(adapters[0].processes);
             }}
-            return panelCard('gpu', 'GPU', d.name || null, body);
+            var subtitle = d.name || null;
+            if (adapters.some(function(a) {{ return gpuLimitWarns(a.performance_limit); }})) {{
+                subtitle = '<span class="data-tag warning">Throttling</span> ' + (subtitle || '');
+            }}
+            return panelCard('gpu', 'GPU', subtitle, body);
         }}
 
         function buildRamPanel(d) {{
             if (!d || d === null) return '';
             var body = '';
-            if (d.usage_percent != null) body += pctBar(d.usage_percent, 'Usage');
+            if (d.usage_percent != null) body += pctBar(d.usage_percent, mlabel('ram.usage_percent'));
             if (d.total_bytes != null) {{
                 body += '<div class="data-stat-row">' +
                     '<div class="data-stat-item"><div class="data-stat-label">In Use</div><div class="data-stat-value">' + fmtBytes(d.used_bytes) + '</div></div>' +
@@ -3458,21 +4301,37 @@ fn build_veil_custom_tabs_shell_html(
                     '<div class="data-stat-item"><div class="data-stat-label">Total</div><div class="data-stat-value">' + fmtBytes(d.total_bytes) + '</div></div>' +
                 '</div>';
             }}
-            if (d.free_bytes != null) body += dataRow('Free', fmtBytes(d.free_bytes));
+            if (d.standby_bytes != null && d.modified_bytes != null && d.free_bytes != null && d.total_bytes) {{
+                // Windows' page lists: in use + modified + standby + free = total.
+                var lists = [
+                    ['In Use', Math.max(0, d.total_bytes - d.modified_bytes - d.standby_bytes - d.free_bytes), 'var(--accent-color,#4a9eff)', 'Held by processes, drivers and the system'],
+                    ['Modified', d.modified_bytes, '#e8a33d', 'Changed pages waiting to be written to disk before reuse'],
+                    ['Standby', d.standby_bytes, '#8a8fa3', 'Cached data not in active use; handed out at once when needed'],
+                    ['Free', d.free_bytes, '#3a3f4b', 'Holds nothing at all']
+                ];
+                body += '<div style="display:flex;height:8px;border-radius:4px;overflow:hidden;margin:6px 0 4px;">' +
+                    lists.map(function(l) {{ return '<div title="' + l[0] + '" style="width:' + (l[1] / d.total_bytes * 100) + '%;background:' + l[2] + '"></div>'; }}).join('') +
+                    '</div>';
+                lists.forEach(function(l) {{
+                    body += dataRow('<span title="' + l[3] + '">' + l[0] + '</span>', fmtBytes(l[1]));
+                }});
+            }} else if (d.free_bytes != null) {{
+                body += dataRow(mlabel('ram.free_bytes'), fmtBytes(d.free_bytes));
+            }}
             if (d.speed_mhz) body += dataRow('Speed', d.speed_mhz + ' MT/s');
             if (d.slots_used != null && d.slots_total != null) body += dataRow('Slots Used', d.slots_used + ' of ' + d.slots_total);
             if (d.form_factor) body += dataRow('Form Factor', d.form_factor);
             if (d.memory_type) body += dataRow('Type', d.memory_type);
             if (d.hardware_reserved_bytes != null) body += dataRow('Hardware Reserved', fmtBytes(d.hardware_reserved_bytes));
             if (d.committed_bytes != null && d.commit_limit_bytes != null) body += dataRow('Committed', fmtBytes(d.committed_bytes) + ' / ' + fmtBytes(d.commit_limit_bytes));
-            if (d.cached_bytes != null) body += dataRow('Cached', fmtBytes(d.cached_bytes));
+            if (d.cached_bytes != null) body += dataRow(mlabel('ram.cached_bytes'), fmtBytes(d.cached_bytes));
             if (d.paged_pool_bytes != null) body += dataRow('Paged Pool', fmtBytes(d.paged_pool_bytes));
             if (d.non_paged_pool_bytes != null) body += dataRow('Non-paged Pool', fmtBytes(d.non_paged_pool_bytes));
             if (d.compressed_bytes != null && d.compressed_bytes > 0) body += dataRow('Compressed', fmtBytes(d.compressed_bytes));
             // Swap
             if (d.swap_total_bytes != null && d.swap_total_bytes > 0) {{
                 body += '<div style="margin-top:8px;padding-top:8px;border-top:1px solid var(--border-color,#333);">';
-                if (d.swap_usage_percent != null) body += pctBar(d.swap_usage_percent, 'Swap Usage');
+                if (d.swap_usage_percent != null) body += pctBar(d.swap_usage_percent, mlabel('ram.swap_usage_percent'));
                 body += dataRow('Swap Used', fmtBytes(d.swap_used_bytes) + ' / ' + fmtBytes(d.swap_total_bytes));
                 if (d.swap_free_bytes != null) body += dataRow('Swap Free', fmtBytes(d.swap_free_bytes));
                 body += '</div>';
@@ -3522,6 +4381,11 @@ fn build_veil_custom_tabs_shell_html(
                     if (pd.system_disk) body += dataRow('System Disk', '<span class="data-tag online">Yes</span>');
                     if (pd.page_file_disk) body += dataRow('Page File', '<span class="data-tag online">Yes</span>');
                     if (pd.health_status) body += dataRow('Health', pd.health_status);
+                    var sm = pd.smart || {{}};
+                    if (sm.temperature_c != null) body += dataRow('Drive Temperature', sm.temperature_c + ' \u00b0C');
+                    if (sm.power_on_hours != null) body += dataRow('Power-On Hours', sm.power_on_hours.toLocaleString());
+                    if (sm.reallocated_sectors != null) body += dataRow('Reallocated Sectors', sm.reallocated_sectors);
+                    if (sm.wear_leveling_percent != null) body += dataRow('Life Remaining', sm.wear_leveling_percent + '%');
                     if (pd.firmware_version) body += dataRow('Firmware', pd.firmware_version);
                     // Show logical volumes for this physical disk
                     var pdVolumes = pd.volumes || [];
@@ -3554,17 +4418,31 @@ fn build_veil_custom_tabs_shell_html(
             if (!d || d === null) return '';
             var body = '';
             // Top-level aggregates
-            if (d.interface_count != null) body += dataRow('Interfaces', d.interface_count);
+            if (d.interface_count != null) body += dataRow(mlabel('network.interface_count'), d.interface_count);
             if (d.received_bytes_per_second != null || d.transmitted_bytes_per_second != null) {{
-                body += dataRow('Total Down', fmtBytes(Math.round(d.received_bytes_per_second || 0)) + '/s');
-                body += dataRow('Total Up', fmtBytes(Math.round(d.transmitted_bytes_per_second || 0)) + '/s');
+                body += dataRow(mlabel('network.received_bytes_per_second'), fmtBytes(Math.round(d.received_bytes_per_second || 0)) + '/s');
+                body += dataRow(mlabel('network.transmitted_bytes_per_second'), fmtBytes(Math.round(d.transmitted_bytes_per_second || 0)) + '/s');
+            }}
+            if (d.total_received_bytes != null) body += dataRow(mlabel('network.total_received_bytes'), fmtBytes(d.total_received_bytes));
+            if (d.total_transmitted_bytes != null) body += dataRow(mlabel('network.total_transmitted_bytes'), fmtBytes(d.total_transmitted_bytes));
+            if (d.session_received_bytes != null) body += dataRow(mlabel('network.session_received_bytes'), fmtBytes(d.session_received_bytes));
+            if (d.session_transmitted_bytes != null) body += dataRow(mlabel('network.session_transmitted_bytes'), fmtBytes(d.session_transmitted_bytes));
+            if (d.total_packets_received != null) body += dataRow(mlabel('network.total_packets_received'), d.total_packets_received);
+            if (d.total_packets_transmitted != null) body += dataRow(mlabel('network.total_packets_transmitted'), d.total_packets_transmitted);
+            if (d.total_errors_received != null && d.total_errors_received > 0) body += dataRow(mlabel('network.total_errors_received'), d.total_errors_received);
+            if (d.total_errors_transmitted != null && d.total_errors_transmitted > 0) body += dataRow(mlabel('network.total_errors_transmitted'), d.total_errors_transmitted);
+            if (d.default_route_interface) body += dataRow('Default Route', d.default_route_interface);
+            if (d.link_change_seq != null) body += dataRow('Link Changes', d.link_change_seq);
+            if (Array.isArray(d.top_talkers) && d.top_talkers.length > 0) {{
+                body += dataRow('Top Apps', '\u2193 / \u2191');
+                d.top_talkers.slice(0, 5).forEach(function(t) {{
+                    body += dataRow('&nbsp;&nbsp;' + (t.name || 'PID ' + t.pid), fmtBytes(Math.round(t.recv_bps || 0)) + '/s \u00b7 ' + fmtBytes(Math.round(t.sent_bps || 0)) + '/s');
+                }});
+            }}
+            if (d.data_cap && d.data_cap.cap_bytes != null) {{
+                var capText = fmtBytes(d.data_cap.used_bytes || 0) + ' / ' + fmtBytes(d.data_cap.cap_bytes) + ' (' + Math.round(d.data_cap.used_percent || 0) + '%)';
+                body += dataRow(mlabel('network.data_cap.used_percent'), d.data_cap.warning ? '<span class="data-tag offline">' + capText + '</span>' : capText);
             }}
-            if (d.total_received_bytes != null) body += dataRow('Total Received', fmtBytes(d.total_received_bytes));
-            if (d.total_transmitted_bytes != null) body += dataRow('Total Sent', fmtBytes(d.total_transmitted_bytes));
-            if (d.total_packets_received != null) body += dataRow('Packets In', d.total_packets_received);
-            if (d.total_packets_transmitted != null) body += dataRow('Packets Out', d.total_packets_transmitted);
-            if (d.total_errors_received != null && d.total_errors_received > 0) body += dataRow('Errors In', d.total_errors_received);
-            if (d.total_errors_transmitted != null && d.total_errors_transmitted > 0) body += dataRow('Errors Out', d.total_errors_transmitted);
             // Per-interface
             var ifaces = d.interfaces || [];
             if (Array.isArray(ifaces) && ifaces.length > 0) {{
@@ -3580,6 +4458,8 @@ fn build_veil_custom_tabs_shell_html(
                     if (iface.link_speed) body += dataRow('Link Speed', iface.link_speed);
                     if (iface.media_type) body += dataRow('Type', iface.media_type);
                     if (iface.media_connection_state) body += dataRow('Media State', iface.media_connection_state);
+                    if (iface.metered) body += dataRow('Metered', 'Yes');
+                    if (iface.link_up && iface.up_since_ms) body += dataRow('Up Since', new Date(iface.up_since_ms).toLocaleString());
                     var ipv4 = null; var ipv6 = null;
                     if (iface.ip_addresses && Array.isArray(iface.ip_addresses)) {{
                         for (var i = 0; i < iface.ip_addresses.length; i++) {{
@@ -3595,6 +4475,8 @@ fn build_veil_custom_tabs_shell_html(
                     if (iface.transmitted_bytes_per_second != null) body += dataRow('Up', fmtBytes(Math.round(iface.transmitted_bytes_per_second)) + '/s');
                     if (iface.total_received_bytes != null) body += dataRow('Total Rx', fmtBytes(iface.total_received_bytes));
                     if (iface.total_transmitted_bytes != null) body += dataRow('Total Tx', fmtBytes(iface.total_transmitted_bytes));
+                    if (iface.session_received_bytes != null) body += dataRow('Session Rx', fmtBytes(iface.session_received_bytes));
+                    if (iface.session_transmitted_bytes != null) body += dataRow('Session Tx', fmtBytes(iface.session_transmitted_bytes));
                     var pkt = iface.packets || {{}};
                     if (pkt.total_received != null) body += dataRow('Packets Rx', pkt.total_received);
                     if (pkt.total_transmitted != null) body += dataRow('Packets Tx', pkt.total_transmitted);
@@ -3608,7 +4490,11 @@ fn build_veil_custom_tabs_shell_html(
             }} else {{
                 body += dataRow('Status', 'No interfaces detected');
             }}
-            return panelCard('network', 'Network', (ifaces.length || 0) + ' interface(s)', body);
+            var subtitle = (ifaces.length || 0) + ' interface(s)';
+            if (d.connectivity) {{
+                subtitle = (d.connectivity.internet ? '<span class="data-tag online">Online</span>' : '<span class="data-tag offline">No internet</span>') + ' ' + subtitle;
+            }}
+            return panelCard('network', 'Network', subtitle, body);
         }}
 
         function buildAudioPanel(d) {{
@@ -3642,6 +4528,17 @@ fn build_veil_custom_tabs_shell_html(
             body += dataRow('Input Muted', id.muted != null ? (id.muted ? '<span class="data-tag offline">Yes</span>' : '<span class="data-tag online">No</span>') : '\u2014');
             if (id.name) body += dataRow('Input Device', id.name);
             body += '</div>';
+            // Per-app sessions
+            if (Array.isArray(d.sessions) && d.sessions.length > 0) {{
+                body += '<div style="margin-top:8px;padding-top:8px;border-top:1px solid var(--border-color,#333);">';
+                body += dataRow('Active Apps', d.sessions.length);
+                d.sessions.forEach(function(s) {{
+                    var label = s.system_sounds ? 'System Sounds' : (s.name || 'PID ' + s.pid);
+                    var value = (s.muted ? 'Muted' : Math.round(s.volume_percent || 0) + '%') + ' \u00b7 level ' + Math.round((s.peak_level || 0) * 100) + '%';
+                    body += dataRow('&nbsp;&nbsp;' + label, value);
+                }});
+                body += '</div>';
+            }}
             // Spectrum
             var spec = d.spectrum_32;
             if (spec && Array.isArray(spec) && spec.length > 0) {{
@@ -3710,8 +4607,9 @@ fn build_veil_custom_tabs_shell_html(
                     var s = totalSec % 60;
                     return m + ':' + (s < 10 ? '0' : '') + s;
                 }}
+                var seekable = !!(d.controls && d.controls.seek);
                 body += '<div class="media-progress">';
-                body += '<div class="media-progress-bar"><div class="media-progress-fill" style="width:' + pct + '%"></div></div>';
+                body += '<div class="media-progress-bar' + (seekable ? ' seekable' : '') + '"' + (seekable ? ' data-media-seek="' + dur + '" data-media-app="' + escapeHtml(d.source_app_id || '') + '"' : '') + '><div class="media-progress-fill" style="width:' + pct + '%"></div></div>';
                 body += '<div class="media-time"><span>' + fmtTime(pos) + '</span><span>' + fmtTime(dur) + '</span></div>';
                 body += '</div>';
             }}
@@ -3738,10 +4636,37 @@ fn build_veil_custom_tabs_shell_html(
                 chips.push('<span class="media-chip">' + trackStr + '</span>');
             }}
 
+            // Transport controls, enabled per what the app accepts
+            var ctl = d.controls || {{}};
+            var app = escapeHtml(d.source_app_id || '');
+            function ctrlBtn(action, enabled, icon, extra) {{
+                return '<button class="media-ctrl-btn' + (extra || '') + '" data-media-action="' + action + '" data-media-app="' + app + '"' + (enabled ? '' : ' disabled') + '><svg viewBox="0 0 24 24" fill="currentColor">' + icon + '</svg></button>';
+            }}
+            body += '<div class="media-controls">';
+            body += ctrlBtn('previous', ctl.previous, '<path d="M6 5h2v14H6zM20 5v14L9 12z"/>');
+            body += isPlaying
+                ? ctrlBtn('pause', ctl.pause, '<path d="M7 5h4v14H7zM13 5h4v14h-4z"/>', ' primary')
+                : ctrlBtn('play', ctl.play, '<path d="M7 5v14l12-7z"/>', ' primary');
+            body += ctrlBtn('next', ctl.next, '<path d="M16 5h2v14h-2zM4 5v14l11-7z"/>');
+            body += '</div>';
+
             if (chips.length > 0) {{
                 body += '<div class="media-meta">' + chips.join('') + '</div>';
             }}
 
+            // Other sessions, each with its own play/pause toggle
+            var others = (d.sessions || []).filter(function(s) {{ return !s.active; }});
+            if (others.length > 0) {{
+                body += '<div style="margin-top:8px;padding-top:8px;border-top:1px solid var(--border-subtle);">';
+                others.forEach(function(s) {{
+                    var label = [s.title, s.artist].filter(Boolean).join(' — ') || s.source_app_id || 'Unknown';
+                    var sApp = escapeHtml(s.source_app_id || '');
+                    body += dataRow(escapeHtml(label), escapeHtml(s.playback_status || '') +
+                        ' <button class="media-ctrl-btn" style="width:22px;height:22px;vertical-align:middle;" data-media-action="toggle" data-media-app="' + sApp + '"><svg viewBox="0 0 24 24" fill="currentColor"><path d="M7 5v14l12-7z"/></svg></button>');
+                }});
+                body += '</div>';
+            }}
+
             body += '</div>';
             return panelCard('media', 'Media', d.title || null, body);
         }}
@@ -3772,6 +4697,12 @@ fn build_veil_custom_tabs_shell_html(
                 body += dataRow('Uptime', (dd > 0 ? dd + 'd ' : '') + h + 'h ' + m + 'm');
             }}
             if (d.boot_time_unix != null) body += dataRow('Boot Time', new Date(d.boot_time_unix * 1000).toLocaleString());
+            if (d.ntp_synchronized != null) {{
+                var sync = d.ntp_synchronized ? '<span class="data-tag online">Synced</span>' : '<span class="data-tag offline">Not Synced</span>';
+                if (d.source) sync += ' <span style="font-size:11px;color:var(--text-dim);">' + d.source + '</span>';
+                body += dataRow('Clock Sync', sync);
+            }}
+            if (d.last_sync_time) body += dataRow('Last Sync', d.last_sync_time);
             return panelCard('time', 'Time', null, body);
         }}
 
@@ -3783,7 +4714,12 @@ fn build_veil_custom_tabs_shell_html(
             body += dataRow('Num Lock', ts.num_lock ? '<span class="data-tag online">ON</span>' : '<span class="data-tag offline">OFF</span>');
             body += dataRow('Scroll Lock', ts.scroll_lock ? '<span class="data-tag online">ON</span>' : '<span class="data-tag offline">OFF</span>');
             if (ts.insert != null) body += dataRow('Insert', ts.insert ? '<span class="data-tag online">ON</span>' : '<span class="data-tag offline">OFF</span>');
-            if (d.layout_id) body += dataRow('Layout', d.layout_id);
+            if (d.layout_id) {{
+                var active = (d.installed_layouts || []).find(function(l) {{ return l.id === d.layout_id; }});
+                body += dataRow('Layout', active && active.name ? active.name + ' <span style="font-size:11px;color:var(--text-dim);">' + d.layout_id + '</span>' : d.layout_id);
+            }}
+            if (d.installed_layouts && d.installed_layouts.length > 1) body += dataRow('Installed Layouts', d.installed_layouts.map(function(l) {{ return l.name || l.id; }}).join(', '));
+            if (d.layout_change_seq != null) body += dataRow('Layout Switches', d.layout_change_seq);
             if (d.type_name) body += dataRow('Type', d.type_name);
             if (d.type_id != null) body += dataRow('Type ID', d.type_id);
             if (d.subtype != null) body += dataRow('Subtype', d.subtype);
@@ -3816,6 +4752,9 @@ fn build_veil_custom_tabs_shell_html(
             if (ev.clicked && ev.clicked.length > 0) body += dataRow('Clicked', ev.clicked.join(', '));
             if (d.speed != null) body += dataRow('Speed', d.speed);
             if (d.wheel_present != null) body += dataRow('Wheel', d.wheel_present ? 'Present' : 'None');
+            if (d.velocity_px_per_sec != null) body += dataRow('Velocity', Math.round(d.velocity_px_per_sec) + ' px/s');
+            if (d.movement_px_since_boot != null) body += dataRow('Distance Moved', d.movement_px_since_boot.toLocaleString() + ' px');
+            if (d.wheel_ticks_since_boot != null) body += dataRow('Wheel Ticks', d.wheel_ticks_since_boot.toLocaleString());
             if (scr.primary_width != null && scr.primary_height != null) body += dataRow('Primary Screen', scr.primary_width + '\u00d7' + scr.primary_height);
             if (scr.virtual_width != null && scr.virtual_height != null) body += dataRow('Virtual Screen', scr.virtual_width + '\u00d7' + scr.virtual_height);
             if (scr.monitor_count != null) body += dataRow('Monitors', scr.monitor_count);
@@ -3848,11 +4787,17 @@ fn build_veil_custom_tabs_shell_html(
             if (bat.saver_active != null) body += dataRow('Battery Saver', bat.saver_active ? 'Active' : 'Off');
             if (d.power_plan) body += dataRow('Power Plan', d.power_plan);
             var det = bat.details || {{}};
+            var health = bat.health_percent != null ? bat.health_percent : det.health_percent;
+            if (health != null) {{
+                var cycles = bat.cycle_count != null ? ' <span style="font-size:11px;color:var(--text-dim);">' + bat.cycle_count + ' cycles</span>' : '';
+                body += dataRow('Battery Health', health.toFixed(1) + '%' + cycles);
+            }}
             if (det.name) body += dataRow('Battery Name', det.name);
             if (det.chemistry) body += dataRow('Chemistry', det.chemistry);
-            if (det.design_capacity_mwh != null) body += dataRow('Design Capacity', (det.design_capacity_mwh / 1000).toFixed(1) + ' Wh');
-            if (det.full_charge_capacity_mwh != null) body += dataRow('Full Charge', (det.full_charge_capacity_mwh / 1000).toFixed(1) + ' Wh');
-            if (det.health_percent != null) body += dataRow('Health', det.health_percent.toFixed(1) + '%');
+            var designMwh = bat.design_capacity_mwh != null ? bat.design_capacity_mwh : det.design_capacity_mwh;
+            var fullMwh = bat.full_charge_capacity_mwh != null ? bat.full_charge_capacity_mwh : det.full_charge_capacity_mwh;
+            if (designMwh != null) body += dataRow('Design Capacity', (designMwh / 1000).toFixed(1) + ' Wh');
+            if (fullMwh != null) body += dataRow('Full Charge', (fullMwh / 1000).toFixed(1) + ' Wh');
             if (det.design_voltage_mv != null) body += dataRow('Design Voltage', (det.design_voltage_mv / 1000).toFixed(2) + ' V');
             if (det.status) body += dataRow('Battery Status', det.status);
             if (det.estimated_charge_percent != null) body += dataRow('Est. Charge', det.estimated_charge_percent + '%');
@@ -3906,6 +4851,7 @@ fn build_veil_custom_tabs_shell_html(
                 devices.slice(0, 8).forEach(function(dev) {{
                     var devName = typeof dev === 'string' ? dev : (dev.name || dev.address || '?');
                     var conn = dev.connected ? ' <span class="data-tag online">Connected</span>' : '';
+                    if (dev.battery_percent != null) conn += ' <span class="data-tag">' + dev.battery_percent + '%</span>';
                     var devType = dev.type ? ' <span style="font-size:11px;color:var(--text-dim);">' + dev.type + '</span>' : '';
                     var devClass = dev.class ? ' <span style="font-size:11px;color:var(--text-dim);">(' + dev.class + ')</span>' : '';
                     body += dataRow('', devName + conn + devType + devClass);
@@ -3950,10 +4896,12 @@ fn build_veil_custom_tabs_shell_html(
             if (theme.accent_color_hex) body += dataRow('Accent Color', '<span style="display:inline-block;width:12px;height:12px;border-radius:2px;background:' + theme.accent_color_hex + ';vertical-align:middle;margin-right:4px;"></span>' + theme.accent_color_hex);
             if (theme.transparency_enabled != null) body += dataRow('Transparency', theme.transparency_enabled ? 'Enabled' : 'Disabled');
             // Motherboard & BIOS
-            if (d.motherboard && d.motherboard.manufacturer && d.motherboard.product) body += dataRow('Board', d.motherboard.manufacturer + ' ' + d.motherboard.product);
-            if (d.motherboard && d.motherboard.serial_number) body += dataRow('Board S/N', d.motherboard.serial_number);
+            var board = d.baseboard || d.motherboard || {{}};
+            if (board.manufacturer || board.product) body += dataRow('Board', [board.manufacturer, board.product].filter(Boolean).join(' '));
+            if (board.version) body += dataRow('Board Version', board.version);
+            if (board.serial) body += dataRow('Board S/N', board.serial);
             var bios = d.bios || {{}};
-            if (bios.name || bios.manufacturer) body += dataRow('BIOS', (bios.manufacturer || '') + (bios.name ? ' ' + bios.name : ''));
+            if (bios.name || bios.vendor) body += dataRow('BIOS', (bios.vendor || '') + (bios.name ? ' ' + bios.name : ''));
             if (bios.version) body += dataRow('BIOS Version', bios.version);
             if (bios.release_date) body += dataRow('BIOS Date', bios.release_date);
             return panelCard('system', 'System', d.hostname || d.computer_name || null, body);
@@ -3979,6 +4927,9 @@ fn build_veil_custom_tabs_shell_html(
                 if (meta.orientation && meta.orientation !== 'landscape') body += dataRow('Orientation', meta.orientation);
                 if (meta.connection_type) body += dataRow('Connection', meta.connection_type);
                 if (meta.hdr_supported) body += dataRow('HDR', '<span class="data-tag online">Supported</span>');
+                if (meta.icc_profile_name) body += dataRow('Color Profile', meta.icc_profile_name);
+                if (meta.gamma_ramp_identity === false) body += dataRow('Gamma Ramp', '<span class="data-tag charging">Modified</span>');
+                if (meta.brightness_percent != null) body += dataRow('Brightness', meta.brightness_percent + '%');
                 if (meta.manufacturer) body += dataRow('Manufacturer', meta.manufacturer);
                 if (meta.physical_width_mm && meta.physical_height_mm) {{
                     var diag = Math.sqrt(meta.physical_width_mm*meta.physical_width_mm + meta.physical_height_mm*meta.physical_height_mm) / 25.4;
@@ -4000,6 +4951,8 @@ fn build_veil_custom_tabs_shell_html(
                 body += '<div class="data-big-value">' + m + '<span class="data-big-unit">m</span> ' + s + '<span class="data-big-unit">s</span></div>';
             }}
             if (d.idle_state) body += dataRow('State', d.idle_state);
+            if (d.last_keyboard_activity_ms != null) body += dataRow('Last Keyboard', Math.floor(d.last_keyboard_activity_ms / 1000) + 's ago');
+            if (d.last_mouse_activity_ms != null) body += dataRow('Last Mouse', Math.floor(d.last_mouse_activity_ms / 1000) + 's ago');
             if (d.is_idle != null) body += dataRow('Idle', d.is_idle ? '<span class="data-tag offline">Yes</span>' : '<span class="data-tag online">No</span>');
             if (d.screensaver_active != null) body += dataRow('Screensaver', d.screensaver_active ? 'Active' : 'Inactive');
             if (d.screen_locked != null) body += dataRow('Screen Locked', d.screen_locked ? 'Yes' : 'No');
@@ -4092,6 +5045,16 @@ fn build_veil_custom_tabs_shell_html(
                 var id = addon && addon.id ? addon.id : '?';
                 var version = addon && addon.metadata && addon.metadata.version ? ' v' + addon.metadata.version : '';
                 var display = addon && addon.metadata && addon.metadata.name ? addon.metadata.name + version : (version.trim() || '—');
+                var caps = addon && addon.metadata && addon.metadata.capabilities ? addon.metadata.capabilities : null;
+                if (caps) {{
+                    var perms = [].concat(
+                        (caps.collectors || []).map(function(c) {{ return 'collector:' + c; }}),
+                        caps.commands || [],
+                        (caps.hotkeys || []).map(function(h) {{ return 'hotkey:' + h; }})
+                    );
+                    if (perms.length) display += ' <span class="data-tag" title="' + perms.join(', ') + '">' + perms.length + ' perm' + (perms.length === 1 ? '' : 's') + '</span>';
+                }}
+                if (addon && addon.metadata && addon.metadata.incompatible) display += ' <span class="data-tag" title="' + escapeHtml(addon.metadata.incompatible) + '">incompatible</span>';
                 body += dataRow(id, display);
             }});
             if (addons.length > 12) body += dataRow('More', '+' + (addons.length - 12));
@@ -4161,6 +5124,7 @@ fn build_veil_custom_tabs_shell_html(
             var jsonFallback = document.getElementById('data-json-fallback');
             if (!container) return;
             if (!data) {{ container.innerHTML = '<div style="color:var(--text-dim);padding:20px;">No data available</div>'; return; }}
+            updatePausedBanner(data);
 
             var filter = window.__dataActiveChip || 'All';
 
@@ -4181,6 +5145,7 @@ fn build_veil_custom_tabs_shell_html(
             var allowed = FILTER_MAP[filter];
             var sys = data.sysdata || {{}};
             var sectionMeta = (data.__meta && data.__meta.sections) || {{}};
+            window.__panelHealth = computePanelHealth(data);
             var html = '';
 
             function shouldShow(key) {{ return !allowed || allowed.indexOf(key) !== -1; }}
@@ -4245,8 +5210,103 @@ fn build_veil_custom_tabs_shell_html(
             if (!allowed)                 html += buildUnknownSysdataPanels(sys);
 
             container.innerHTML = html || '<div style="color:var(--text-dim);padding:20px;">No data for this filter</div>';
+            applyDataSearch(container);
+            refreshDataHealth();
+        }}
+
+        // Narrow the rendered panels to the search box: a panel whose title
+        // matches stays whole, otherwise only its matching rows are kept and
+        // it is hidden when none do.
+        function applyDataSearch(container) {{
+            var query = (window.__dataSearch || '').trim().toLowerCase();
+            if (!query) return;
+            var visible = 0;
+            container.querySelectorAll('.data-panel').forEach(function(panel) {{
+                var titleEl = panel.querySelector('.data-panel-title');
+                var title = titleEl ? titleEl.textContent.toLowerCase() : '';
+                if (title.indexOf(query) !== -1) {{ visible++; return; }}
+                var hits = 0;
+                panel.querySelectorAll('.data-row').forEach(function(row) {{
+                    var label = row.querySelector('.data-row-label');
+                    var match = !!label && label.textContent.toLowerCase().indexOf(query) !== -1;
+                    row.style.display = match ? '' : 'none';
+                    if (match) hits++;
+                }});
+                if (hits) visible++;
+                else panel.style.display = 'none';
+            }});
+            if (!visible) {{
+                container.insertAdjacentHTML('beforeend', '<div style="color:var(--text-dim);padding:20px;">Nothing matches “' + escapeHtml(query) + '”</div>');
+            }}
+        }}
+
+        // ── Logs page ──
+        // The Rust event loop polls `logs.tail` about once a second while this
+        // page is open and pushes the result through __odPushLogs.
+        window.__logsFilter = window.__logsFilter || {{ level: '', contains: '' }};
+
+        function renderLogsPage() {{
+            const header = document.getElementById('page-header');
+            const content = document.getElementById('page-content');
+            header.innerHTML = '<h2>Logs</h2><p style="color:var(--text-dim);margin:4px 0 0;">Tail of the backend log, refreshed every second</p>';
+            var levels = [['', 'All'], ['info', 'Info+'], ['warn', 'Warnings+'], ['error', 'Errors']];
+            content.innerHTML =
+                '<div class="data-filter">' +
+                    levels.map(function(l) {{ return '<button class="data-filter-chip' + (l[0] === window.__logsFilter.level ? ' active' : '') + '" data-level="' + l[0] + '">' + l[1] + '</button>'; }}).join('') +
+                    '<input type="search" id="logs-search" class="data-filter-search" placeholder="Filter lines" spellcheck="false">' +
+                '</div>' +
+                '<div id="logs-wrap" class="data-json-wrap"><pre id="logs-pre">Loading\u2026</pre></div>';
+
+            content.querySelectorAll('.data-filter-chip').forEach(function(chip) {{
+                chip.onclick = function() {{
+                    window.__logsFilter.level = chip.getAttribute('data-level');
+                    content.querySelectorAll('.data-filter-chip').forEach(function(c) {{ c.classList.toggle('active', c === chip); }});
+                    postLogsFilter();
+                }};
+            }});
+            var searchEl = document.getElementById('logs-search');
+            searchEl.value = window.__logsFilter.contains;
+            searchEl.addEventListener('input', function() {{
+                clearTimeout(window.__logsSearchTimer);
+                window.__logsSearchTimer = setTimeout(function() {{
+                    window.__logsFilter.contains = searchEl.value;
+                    postLogsFilter();
+                }}, DATA_SEARCH_DEBOUNCE_MS);
+            }});
+            postLogsFilter();
+            if (window.__lastLogs) renderLogLines(window.__lastLogs);
         }}
 
+        function postLogsFilter() {{
+            window.__odBridgePost({{ type: 'logs_filter', level: window.__logsFilter.level, contains: window.__logsFilter.contains }});
+        }}
+
+        function renderLogLines(data) {{
+            var pre = document.getElementById('logs-pre');
+            var wrap = document.getElementById('logs-wrap');
+            if (!pre || !wrap) return;
+            if (data.error) {{
+                pre.textContent = 'Could not read the log: ' + data.error;
+                return;
+            }}
+            var lines = data.lines || [];
+            // Follow new lines only when the view is already at the bottom.
+            var atBottom = wrap.scrollTop + wrap.clientHeight >= wrap.scrollHeight - 8;
+            pre.innerHTML = lines.length
+                ? lines.map(function(line) {{
+                    var cls = line.indexOf('[ERROR]') !== -1 ? ' error' : line.indexOf('[WARN]') !== -1 ? ' warn' : '';
+                    return '<div class="log-line' + cls + '">' + escapeHtml(line) + '</div>';
+                }}).join('')
+                : 'No matching lines' + (data.path ? ' in ' + escapeHtml(data.path) : '');
+            if (atBottom) wrap.scrollTop = wrap.scrollHeight;
+        }}
+
+        window.__odPushLogs = function(data) {{
+            if (!data || typeof data !== 'object') return;
+            window.__lastLogs = data;
+            if (viewMode === 'logs') renderLogLines(data);
+        }};
+
         window.__odPushMonitors = function(monitors) {{
             var frame = document.getElementById('tabFrame');
             if (frame && frame.contentWindow) {{
@@ -4256,6 +5316,8 @@ fn build_veil_custom_tabs_shell_html(
 
         // Live registry data push from Rust event loop
         window.__odPushRegistry = function(data) {{
+            // Keep showing the last good snapshot rather than blanking the panels.
+            if (!data || typeof data !== 'object' || !data.sysdata) return;
             window.__lastRegistryData = data;
             // Only update if the Data page is currently active
             if (viewMode === 'data') {{
@@ -4263,6 +5325,30 @@ fn build_veil_custom_tabs_shell_html(
             }}
         }};
 
+        // Apply a JSON Patch from the Rust event loop to the last pushed snapshot.
+        // Without one (e.g. after a reload) it waits for the next full push.
+        window.__odPatchRegistry = function(ops) {{
+            var data = window.__lastRegistryData;
+            if (!data || !Array.isArray(ops)) return;
+            for (var i = 0; i < ops.length; i++) {{
+                var op = ops[i];
+                var tokens = op.path.split('/').slice(1).map(function(t) {{
+                    return t.replace(/~1/g, '/').replace(/~0/g, '~');
+                }});
+                if (!tokens.length) {{
+                    if (op.op === 'replace') data = op.value;
+                    continue;
+                }}
+                var parent = data;
+                for (var j = 0; j < tokens.length - 1 && parent; j++) parent = parent[tokens[j]];
+                if (!parent || typeof parent !== 'object') return;
+                var last = tokens[tokens.length - 1];
+                if (op.op === 'remove') delete parent[last];
+                else parent[last] = op.value;
+            }}
+            window.__odPushRegistry(data);
+        }};
+
         // Track user scroll activity so we can defer DOM updates
         (function() {{
             var timer = null;
@@ -4281,10 +5367,26 @@ fn build_veil_custom_tabs_shell_html(
             }}, {{ passive: true }});
         }})();
 
+        // Media card buttons and seeking, delegated because panels re-render.
+        document.addEventListener('click', function(e) {{
+            var btn = e.target.closest('[data-media-action]');
+            if (btn && !btn.disabled) {{
+                window.__odBridgePost({{ type: 'media_control', value: {{ action: btn.getAttribute('data-media-action'), source_app_id: btn.getAttribute('data-media-app') || null }} }});
+                return;
+            }}
+            var bar = e.target.closest('[data-media-seek]');
+            if (bar) {{
+                var rect = bar.getBoundingClientRect();
+                var frac = Math.min(Math.max((e.clientX - rect.left) / rect.width, 0), 1);
+                var dur = parseInt(bar.getAttribute('data-media-seek'), 10) || 0;
+                window.__odBridgePost({{ type: 'media_control', value: {{ action: 'seek', position_ms: Math.round(frac * dur), source_app_id: bar.getAttribute('data-media-app') || null }} }});
+            }}
+        }});
+
         document.querySelectorAll('.quick-action-btn').forEach(function(btn) {{
             btn.addEventListener('click', function() {{
                 var tip = (btn.getAttribute('data-tooltip') || '').toLowerCase();
-                if (tip === 'home' || tip === 'settings' || tip === 'data' || tip === 'store' || tip === 'updater') {{
+                if (tip === 'home' || tip === 'settings' || tip === 'data' || tip === 'store' || tip === 'updater' || tip === 'logs') {{
                     viewMode = tip;
                     render();
                 }}
@@ -4313,11 +5415,29 @@ fn build_veil_custom_tabs_shell_html(
                     scheduleDataPanelsRender(true);
                 }} else if (viewMode === 'store') renderStorePage();
                 else if (viewMode === 'updater') renderUpdaterPage();
+                else if (viewMode === 'logs') renderLogsPage();
             }}
-            window.__odBridgePost({{ type: 'ui_view_mode', viewMode: viewMode }});
-        }}
+            window.__odBridgePost({{ type: 'ui_view_mode', viewMode: viewMode, addonId: currentAddonId }});
+        }}
+
+        // Switch to an addon and one of its tabs (id or title, any case).
+        // Used for the launch-time tab and for `ui.open` while the shell is up.
+        window.__odOpenTab = function(addonId, tab) {{
+            var want = String(addonId || '').toLowerCase();
+            var addon = ADDONS.find(a => a.id.toLowerCase() === want || String(a.name || '').toLowerCase() === want);
+            if (addon) {{
+                viewMode = 'addon';
+                currentAddonId = addon.id;
+                currentTabId = null;
+                var wantTab = String(tab || '').toLowerCase();
+                var match = wantTab && (addon.tabs || []).find(t => t.id.toLowerCase() === wantTab || String(t.title || '').toLowerCase() === wantTab);
+                if (match) currentTabId = match.id;
+            }}
+            render();
+        }};
 
-        render();
+        if (INITIAL_TAB) window.__odOpenTab(currentAddonId, INITIAL_TAB);
+        else render();
     </script>
 </body>
 </html>
@@ -4335,6 +5455,8 @@ struct ODApp {
     addon_hub_tab: AddonHubTab,
     editor_selected_asset: Option<String>,
     library_selected_monitor: Option<String>,
+    asset_prefs: AssetPrefs,
+    asset_filter: AssetFilter,
     selected_custom_tab: Option<String>,
     last_opened_custom_tab: Option<String>,
     // Backend settings state
@@ -4343,10 +5465,29 @@ struct ODApp {
     settings_pull_paused: bool,
     settings_refresh_on_request: bool,
     settings_loaded: bool,
+    // Mirrors `theme` in config.yaml; re-read periodically so edits from the
+    // shell or the file apply without a relaunch
+    theme: crate::config::UiTheme,
+    theme_checked_at: Option<std::time::Instant>,
+    // Ctrl+K palette; the index is rebuilt each time it opens
+    search_open: bool,
+    search_query: String,
+    search_selected: usize,
+    search_index: Option<SearchIndex>,
+    // Addon hub filter, matched against addon name and id
+    addon_filter: String,
+    // Last state written to ui_state.json, and when the current one first differed
+    saved_ui_state: ui_state::NativeUiState,
+    ui_state_changed_at: Option<std::time::Instant>,
+    workshop: workshop::WorkshopBrowser,
+    url_installer: url_install::UrlInstaller,
 }
 
 impl ODApp {
     fn load_selected_addon(&mut self) {
+        if let Some(state) = self.addon_state.as_mut() {
+            flush_addon_save(state, &mut self.global_status);
+        }
         if self.addon_catalog.is_empty() {
             self.addon_state = None;
             self.global_status = "No addons available".to_string();
@@ -4376,6 +5517,116 @@ impl ODApp {
         }
     }
 
+    fn toggle_search(&mut self) {
+        self.search_open = !self.search_open;
+        if self.search_open {
+            self.search_query.clear();
+            self.search_selected = 0;
+            self.search_index = Some(SearchIndex::build(&self.addon_catalog));
+        }
+    }
+
+    fn search_palette(&mut self, ctx: &egui::Context) {
+        if !self.search_open {
+            return;
+        }
+        let Some(index) = self.search_index.take() else {
+            self.search_open = false;
+            return;
+        };
+
+        let (escape, enter, down, up) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+            )
+        });
+
+        let mut chosen: Option<SearchTarget> = None;
+        egui::Window::new("Search")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .fixed_size([520.0, 0.0])
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+            .show(ctx, |ui| {
+                let edit = ui.add(
+                    egui::TextEdit::singleline(&mut self.search_query)
+                        .hint_text("Search addons, assets, settings, metrics…")
+                        .desired_width(f32::INFINITY),
+                );
+                edit.request_focus();
+                if edit.changed() {
+                    self.search_selected = 0;
+                }
+
+                let results = index.search(&self.search_query);
+                if down {
+                    self.search_selected = (self.search_selected + 1).min(results.len().saturating_sub(1));
+                }
+                if up {
+                    self.search_selected = self.search_selected.saturating_sub(1);
+                }
+
+                ui.add_space(6.0);
+                if results.is_empty() {
+                    ui.label(RichText::new("No matches").color(Color32::GRAY));
+                }
+                for (idx, entry) in results.iter().enumerate() {
+                    let row = ui.horizontal(|ui| {
+                        ui.label(RichText::new(entry.kind.label()).small().color(Color32::LIGHT_BLUE));
+                        let clicked = ui.selectable_label(idx == self.search_selected, &entry.title).clicked();
+                        ui.label(RichText::new(&entry.detail).small().color(Color32::GRAY));
+                        clicked
+                    });
+                    if row.inner || (enter && idx == self.search_selected) {
+                        chosen = Some(entry.target.clone());
+                    }
+                }
+            });
+
+        self.search_index = Some(index);
+        if escape {
+            self.search_open = false;
+        }
+        if let Some(target) = chosen {
+            self.search_open = false;
+            self.jump_to(ctx, target);
+        }
+    }
+
+    fn jump_to(&mut self, ctx: &egui::Context, target: SearchTarget) {
+        match target {
+            SearchTarget::Addon { addon_idx } => {
+                self.section = UiSection::Addons;
+                self.selected_addon_idx = addon_idx;
+                self.load_selected_addon();
+            }
+            SearchTarget::Asset { addon_idx, asset_id } => {
+                self.section = UiSection::Addons;
+                self.selected_addon_idx = addon_idx;
+                self.load_selected_addon();
+                self.addon_hub_tab = AddonHubTab::Editor;
+                self.editor_selected_asset = Some(asset_id);
+            }
+            SearchTarget::Setting { addon_idx, path } => {
+                self.section = UiSection::Addons;
+                self.selected_addon_idx = addon_idx;
+                self.load_selected_addon();
+                self.addon_hub_tab = AddonHubTab::Settings;
+                self.selected_custom_tab = Some("settings".to_string());
+                self.global_status = format!("Setting: {}", path);
+            }
+            SearchTarget::Metric { path } => {
+                // No native data page yet; hand the path over for use in a config or widget.
+                ctx.copy_text(path.clone());
+                self.global_status = format!("Copied metric path '{}'", path);
+            }
+        }
+    }
+
     fn sidebar(&mut self, ctx: &egui::Context) {
         egui::SidePanel::left("sidebar")
             .resizable(false)
@@ -4392,6 +5643,9 @@ impl ODApp {
                 ui.selectable_value(&mut self.section, UiSection::Settings, "Settings");
 
                 ui.separator();
+                if ui.button("Search  (Ctrl+K)").clicked() {
+                    self.toggle_search();
+                }
                 ui.label(RichText::new("Schema + asset hub").italics());
                 ui.label(RichText::new("Scope: local native UI (non-web)").italics());
             });
@@ -4399,7 +5653,7 @@ impl ODApp {
 
     fn section_card(ui: &mut egui::Ui, title: &str, add_contents: impl FnOnce(&mut egui::Ui)) {
         egui::Frame::default()
-            .fill(Color32::from_rgb(21, 24, 30))
+            .fill(panel_fill(ui, Color32::from_rgb(21, 24, 30)))
             .stroke(Stroke::new(1.0, Color32::from_rgb(55, 66, 82)))
             .corner_radius(6.0)
             .inner_margin(egui::Margin::same(10))
@@ -4422,12 +5676,60 @@ impl ODApp {
         Self::section_card(ui, "Integrations", |ui| {
             ui.group(|ui| {
                 ui.strong("Steam Workshop");
-                ui.label("Planned provider for browsing/installing/updating addon assets.");
-                ui.label(RichText::new("Status: scaffolded").color(Color32::LIGHT_BLUE));
+                ui.label("Installs items you subscribe to in Steam as addon assets.");
+                ui.label("Addons opt in with `assets.workshop_app_id` in addon.json; use Browse Workshop on their Discover tab.");
+                ui.label(RichText::new("Status: read-only (subscribed items)").color(Color32::LIGHT_BLUE));
             });
         });
     }
 
+    /// Apply the configured theme, re-reading config.yaml every couple of seconds.
+    fn sync_theme(&mut self, ctx: &egui::Context) {
+        let first = self.theme_checked_at.is_none();
+        let due = first
+            || self
+                .theme_checked_at
+                .is_some_and(|t| t.elapsed() >= std::time::Duration::from_secs(2));
+        if due {
+            self.theme_checked_at = Some(std::time::Instant::now());
+            let theme = crate::config::read_config_from_disk().map(|c| c.theme).unwrap_or_default();
+            if first || theme != self.theme {
+                self.set_theme(ctx, theme);
+            }
+        }
+        ctx.request_repaint_after(std::time::Duration::from_secs(2));
+    }
+
+    /// Write the section, addon and hub tab to ui_state.json once they have
+    /// stayed unchanged for `UI_STATE_SAVE_DELAY`.
+    fn persist_ui_state(&mut self, ctx: &egui::Context) {
+        let current = ui_state::NativeUiState {
+            section: Some(self.section.name().to_string()),
+            addon_id: self.addon_catalog.get(self.selected_addon_idx).map(|a| a.id.clone()),
+            hub_tab: Some(self.addon_hub_tab.name().to_string()),
+        };
+        if current == self.saved_ui_state {
+            self.ui_state_changed_at = None;
+            return;
+        }
+        let changed_at = *self.ui_state_changed_at.get_or_insert_with(std::time::Instant::now);
+        if changed_at.elapsed() >= UI_STATE_SAVE_DELAY {
+            ui_state::save_native(&current);
+            self.saved_ui_state = current;
+            self.ui_state_changed_at = None;
+        } else {
+            ctx.request_repaint_after(UI_STATE_SAVE_DELAY);
+        }
+    }
+
+    fn set_theme(&mut self, ctx: &egui::Context, theme: crate::config::UiTheme) {
+        self.theme = theme;
+        ctx.set_visuals(match theme {
+            crate::config::UiTheme::Light => egui::Visuals::light(),
+            crate::config::UiTheme::Dark => egui::Visuals::dark(),
+        });
+    }
+
     fn show_settings(&mut self, ui: &mut egui::Ui) {
         // Load current values from the backend config on first visit
         if !self.settings_loaded {
@@ -4439,6 +5741,22 @@ impl ODApp {
             self.settings_loaded = true;
         }
 
+        Self::section_card(ui, "Interface", |ui| {
+            ui.label(RichText::new("Theme").strong());
+            let mut theme = self.theme;
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut theme, crate::config::UiTheme::Dark, "Dark");
+                ui.selectable_value(&mut theme, crate::config::UiTheme::Light, "Light");
+            });
+            if theme != self.theme {
+                crate::config::set_ui_theme(theme);
+                self.set_theme(ui.ctx(), theme);
+                self.global_status = format!("Theme → {}", theme.as_str());
+            }
+        });
+
+        ui.add_space(10.0);
+
         Self::section_card(ui, "Backend Settings", |ui| {
             ui.label("Control the VEIL backend data engine.");
             ui.add_space(10.0);
@@ -4446,7 +5764,7 @@ impl ODApp {
             // ── Fast-tier pull rate slider ──
             ui.label(RichText::new("Fast Pull Rate").strong());
             ui.label(
-                RichText::new("How often lightweight data is collected (audio, time, keyboard, mouse, idle, power, display). 0–5000 ms.")
+                RichText::new("How often lightweight data is collected (audio, time, keyboard, mouse, idle, power, display). 10–5000 ms.")
                     .small()
                     .color(Color32::GRAY),
             );
@@ -4455,7 +5773,7 @@ impl ODApp {
             let fast_before = self.settings_fast_rate;
             ui.horizontal(|ui| {
                 ui.add(
-                    egui::Slider::new(&mut self.settings_fast_rate, 0..=5000)
+                    egui::Slider::new(&mut self.settings_fast_rate, crate::config::FAST_RATE_RANGE)
                         .suffix(" ms")
                         .clamping(egui::SliderClamping::Always),
                 );
@@ -4474,7 +5792,7 @@ impl ODApp {
             // ── Slow-tier pull rate slider ──
             ui.label(RichText::new("Slow Pull Rate").strong());
             ui.label(
-                RichText::new("How often heavyweight data is collected (CPU, GPU, RAM, storage, network, processes, etc.). 0–10000 ms.")
+                RichText::new("How often heavyweight data is collected (CPU, GPU, RAM, storage, network, processes, etc.). 100–60000 ms.")
                     .small()
                     .color(Color32::GRAY),
             );
@@ -4483,7 +5801,7 @@ impl ODApp {
             let slow_before = self.settings_slow_rate;
             ui.horizontal(|ui| {
                 ui.add(
-                    egui::Slider::new(&mut self.settings_slow_rate, 0..=10000)
+                    egui::Slider::new(&mut self.settings_slow_rate, crate::config::SLOW_RATE_RANGE)
                         .suffix(" ms")
                         .clamping(egui::SliderClamping::Always),
                 );
@@ -4558,6 +5876,34 @@ impl ODApp {
                 self.settings_refresh_on_request = cfg.refresh_on_request;
                 self.global_status = "Reloaded config.yaml".to_string();
             }
+
+            ui.add_space(4.0);
+            if ui.button("Restart backend").clicked() {
+                let req = crate::ipc::request::IpcRequest {
+                    ns: "backend".to_string(),
+                    cmd: "restart".to_string(),
+                    args: None,
+                    addon_id: None,
+                    token: None,
+                };
+                self.global_status = match crate::ipc::request::send_ipc_request(req) {
+                    Ok(resp) if resp.ok => "Backend restarting; running addons are stopped first".to_string(),
+                    Ok(resp) => format!("Restart refused: {}", resp.error.unwrap_or_default()),
+                    Err(e) => format!("Restart failed: {}", e),
+                };
+            }
+
+            if ui.button("Save diagnostics bundle").clicked() {
+                self.global_status = match crate::diagnostics::create(None) {
+                    Ok(summary) if summary.notes.is_empty() => format!("Saved {}", summary.path.display()),
+                    Ok(summary) => format!(
+                        "Saved {} with {} note(s), see diagnostics.json",
+                        summary.path.display(),
+                        summary.notes.len()
+                    ),
+                    Err(e) => format!("Diagnostics bundle failed: {}", e),
+                };
+            }
         });
     }
 
@@ -4566,19 +5912,47 @@ impl ODApp {
             return false;
         }
 
+        if self.addon_catalog.len() > 1 {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.addon_filter)
+                        .hint_text("Filter addons")
+                        .desired_width(220.0),
+                );
+                if !self.addon_filter.is_empty() && ui.small_button("✕").clicked() {
+                    self.addon_filter.clear();
+                }
+            });
+            ui.add_space(4.0);
+        }
+
+        let query = self.addon_filter.trim().to_lowercase();
         let mut changed = false;
+        let mut shown = 0;
         ui.horizontal_wrapped(|ui| {
             for (idx, addon) in self.addon_catalog.iter().enumerate() {
                 let selected = idx == self.selected_addon_idx;
+                // The selected addon stays visible so its settings below keep a tab.
+                if !selected
+                    && !query.is_empty()
+                    && !addon.name.to_lowercase().contains(&query)
+                    && !addon.id.to_lowercase().contains(&query)
+                {
+                    continue;
+                }
+                shown += 1;
                 let text = RichText::new(&addon.name)
                     .strong()
-                    .color(if selected { Color32::WHITE } else { Color32::from_rgb(210, 215, 225) });
+                    .color(if selected { ui.visuals().strong_text_color() } else { ui.visuals().text_color() });
 
                 if ui.selectable_label(selected, text).clicked() {
                     self.selected_addon_idx = idx;
                     changed = true;
                 }
             }
+            if shown <= 1 && !query.is_empty() {
+                ui.label(RichText::new("No other addons match").small().color(Color32::GRAY));
+            }
         });
 
         changed
@@ -4622,6 +5996,25 @@ impl ODApp {
                             ui.label(RichText::new(format!("schema {}", ver)).small().color(Color32::LIGHT_BLUE));
                         }
                     }
+                    if ui.small_button("Open addon folder").clicked() {
+                        self.global_status = match open_folder(&state.meta.addon_root) {
+                            Ok(_) => format!("Opened {}", state.meta.addon_root.display()),
+                            Err(e) => e,
+                        };
+                    }
+                });
+                if let Some(reason) = &state.meta.incompatible {
+                    ui.label(RichText::new(format!("⚠ {}. The addon won't start until VEIL is updated.", reason)).color(Color32::from_rgb(230, 120, 100)));
+                }
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(RichText::new("Permissions:").small().color(Color32::GRAY));
+                    if state.meta.capabilities.is_empty() {
+                        ui.label(RichText::new("none declared").small().color(Color32::GRAY));
+                    } else {
+                        for cap in state.meta.capabilities.summary() {
+                            ui.label(RichText::new(cap).small().color(Color32::LIGHT_YELLOW));
+                        }
+                    }
                 });
                 ui.add_space(6.0);
 
@@ -4658,21 +6051,57 @@ impl ODApp {
 
                 let after_render = serde_yaml::to_string(&state.root).ok();
                 if before_render != after_render {
-                    match save_addon_state(&mut state) {
-                        Ok(_) => {
-                            state.status = "Live saved config.yaml".to_string();
-                            self.global_status = "Live saved addon config".to_string();
+                    // Each edit pushes the write back; `update` saves once it's due.
+                    state.save_due = Some(std::time::Instant::now() + CONFIG_SAVE_DELAY);
+                    state.status = "Saving…".to_string();
+                    ui.ctx().request_repaint_after(CONFIG_SAVE_DELAY);
+                }
+
+                ui.add_space(10.0);
+                let mut step = None;
+                ui.horizontal(|ui| {
+                    // Commit a pending edit first so it can be undone too.
+                    let can_undo = !state.undo.is_empty() || state.save_due.is_some();
+                    if ui.add_enabled(can_undo, egui::Button::new("Undo")).on_hover_text("Ctrl+Z").clicked() {
+                        step = Some(true);
+                    }
+                    if ui.add_enabled(!state.redo.is_empty(), egui::Button::new("Redo")).on_hover_text("Ctrl+Y").clicked() {
+                        step = Some(false);
+                    }
+                });
+                // Text fields keep Ctrl+Z for their own undo while focused.
+                if !ui.ctx().wants_keyboard_input() {
+                    ui.input_mut(|i| {
+                        if i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z)
+                            || i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y)
+                        {
+                            step = Some(false);
+                        } else if i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z) {
+                            step = Some(true);
                         }
-                        Err(e) => {
-                            state.status = format!("Live save failed: {}", e);
-                            self.global_status = "Live save failed".to_string();
-                            error!("Config UI live save failed: {}", e);
+                    });
+                }
+                if let Some(undo) = step {
+                    flush_addon_save(&mut state, &mut self.global_status);
+                    if state.step_history(undo) {
+                        let verb = if undo { "Undid" } else { "Redid" };
+                        match save_addon_state(&mut state) {
+                            Ok(_) => {
+                                state.status = format!("{} last change", verb);
+                                self.global_status = format!("{} addon config change", verb);
+                            }
+                            Err(e) => {
+                                state.status = format!("Live save failed: {}", e);
+                                self.global_status = "Live save failed".to_string();
+                                error!("Config UI live save failed: {}", e);
+                            }
                         }
                     }
                 }
 
-                ui.add_space(10.0);
+                // A reload starts from the file on disk with an empty history.
                 if ui.button("Reload").clicked() {
+                    flush_addon_save(&mut state, &mut self.global_status);
                     match load_addon_state(state.meta.clone()) {
                         Ok(new_state) => {
                             state = new_state;
@@ -4708,7 +6137,10 @@ impl ODApp {
             }
         }
 
-        let monitors = MonitorManager::enumerate_monitors();
+        let mut monitors = MonitorManager::enumerate_monitors();
+        let mut indexed = monitors.iter().cloned().map(WallpaperShellMonitor::from).collect::<Vec<_>>();
+        sort_monitors_for_wallpaper_indexes(&mut indexed, &monitor_index_order(&state.root));
+        monitors.sort_by_key(|m| indexed.iter().position(|s| s.id == m.id).unwrap_or(usize::MAX));
         let selected_monitor = self.library_selected_monitor.clone().unwrap_or_else(|| {
             monitors
                 .iter()
@@ -4745,15 +6177,22 @@ impl ODApp {
 
         ui.add_space(6.0);
         render_monitor_layout_preview(ui, &monitors, &state.root, &state.assets, self.library_selected_monitor.as_deref());
+        render_monitor_index_order_controls(ui, &monitors, &mut state.root, self.library_selected_monitor.as_deref());
 
         ui.add_space(8.0);
-        if let Some(chosen_id) = render_asset_cards(ui, &state.assets, &mut self.caches, &self.editor_selected_asset, true) {
+        render_asset_filter_chips(ui, &mut self.asset_filter);
+        ui.add_space(4.0);
+        let visible = filter_assets(&state.assets, &self.asset_prefs, self.asset_filter);
+        if let Some(chosen_id) = render_asset_cards(ui, &visible, &mut self.caches, &mut self.asset_prefs, &self.editor_selected_asset, true) {
             self.editor_selected_asset = Some(chosen_id.clone());
             let monitor_key = self
                 .library_selected_monitor
                 .clone()
                 .unwrap_or_else(|| "*".to_string());
             apply_asset_assignment_to_monitor(&mut state.root, &monitor_key, &chosen_id);
+            if let Some(asset) = state.assets.iter().find(|a| a.id == chosen_id) {
+                record_recent_asset(&mut self.asset_prefs, asset);
+            }
         }
     }
 
@@ -4764,6 +6203,7 @@ impl ODApp {
 
         ui.label(RichText::new("Editor").strong());
         ui.add_space(6.0);
+        render_manifest_problems(ui, &state.assets);
 
         if state.assets.is_empty() {
             ui.label("No assets discovered for this addon.");
@@ -4794,17 +6234,19 @@ impl ODApp {
             None => return,
         };
 
+        let mut rescan = false;
         if let Some(asset) = state.assets.iter().find(|a| a.id == selected_id) {
-            render_asset_detail(ui, asset, &mut self.caches);
+            rescan = render_asset_detail(ui, asset, &mut self.caches, &mut self.global_status);
 
             ui.add_space(10.0);
             ui.label(RichText::new("Apply asset").strong());
             if ui.button("Set as active").clicked() {
                 for selector_path in &state.asset_selector_paths {
-                    if let Some(v) = get_node_mut(&mut state.root, selector_path) {
+                    if let Some(v) = yaml_path::get_mut(&mut state.root, selector_path) {
                         *v = Value::String(asset.id.clone());
                     }
                 }
+                record_recent_asset(&mut self.asset_prefs, asset);
             }
 
             if state.meta.id.to_lowercase().contains("wallpaper") {
@@ -4813,6 +6255,9 @@ impl ODApp {
                 render_editable_values(ui, &asset.id, &asset.editable, &mut state.root);
             }
         }
+        if rescan {
+            state.assets = discover_assets_for_meta(&state.meta, state.schema.as_ref());
+        }
     }
 
     fn render_discover_tab(&mut self, ui: &mut egui::Ui, state: &mut AddonConfigState) {
@@ -4820,15 +6265,34 @@ impl ODApp {
             return;
         }
 
-        ui.label(RichText::new("Discover").strong());
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Discover").strong());
+            if state.meta.workshop_app_id.is_some() {
+                ui.toggle_value(&mut self.workshop.open, "Browse Workshop");
+            }
+        });
         ui.add_space(6.0);
+        if state.meta.accepts_assets {
+            self.url_installer.show(ui, &state.meta.primary_asset_category());
+            ui.add_space(6.0);
+        }
+        if let (Some(app_id), true) = (state.meta.workshop_app_id, self.workshop.open) {
+            Self::section_card(ui, "Steam Workshop", |ui| {
+                self.workshop.show(ui, app_id, &state.meta.primary_asset_category());
+            });
+            ui.add_space(6.0);
+        }
+        render_manifest_problems(ui, &state.assets);
 
         if state.assets.is_empty() {
             ui.label("No assets discovered for this addon.");
             return;
         }
 
-        if let Some(chosen_id) = render_asset_cards(ui, &state.assets, &mut self.caches, &self.editor_selected_asset, true) {
+        render_asset_filter_chips(ui, &mut self.asset_filter);
+        ui.add_space(4.0);
+        let visible = filter_assets(&state.assets, &self.asset_prefs, self.asset_filter);
+        if let Some(chosen_id) = render_asset_cards(ui, &visible, &mut self.caches, &mut self.asset_prefs, &self.editor_selected_asset, true) {
             self.editor_selected_asset = Some(chosen_id);
             self.addon_hub_tab = AddonHubTab::Editor;
         }
@@ -4880,6 +6344,49 @@ impl ODApp {
 
 impl App for ODApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.sync_theme(ctx);
+        self.persist_ui_state(ctx);
+        if let Some(state) = self.addon_state.as_mut() {
+            let closing = ctx.input(|i| i.viewport().close_requested());
+            match state.save_due {
+                Some(due) if closing || std::time::Instant::now() >= due => {
+                    flush_addon_save(state, &mut self.global_status);
+                }
+                Some(due) => ctx.request_repaint_after(due.saturating_duration_since(std::time::Instant::now())),
+                None => {}
+            }
+        }
+        if let Some(done) = self.workshop.poll() {
+            if done.installed {
+                if let Some(state) = self.addon_state.as_mut() {
+                    state.assets = discover_assets_for_meta(&state.meta, state.schema.as_ref());
+                }
+            }
+            self.global_status = done.status;
+        }
+        if let Some(result) = self.url_installer.poll() {
+            self.global_status = match result {
+                Ok(installed) => {
+                    if let Some(state) = self.addon_state.as_mut() {
+                        state.assets = discover_assets_for_meta(&state.meta, state.schema.as_ref());
+                    }
+                    self.editor_selected_asset = Some(installed.id.clone());
+                    let verb = if installed.replaced { "Updated" } else { "Installed" };
+                    match installed.issues.len() {
+                        0 => format!("{} '{}'", verb, installed.id),
+                        n => format!("{} '{}' ({} problem(s), see Editor)", verb, installed.id, n),
+                    }
+                }
+                Err(e) => format!("Install from URL failed: {}", e),
+            };
+        }
+        if self.workshop.busy() || self.url_installer.busy() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::K)) {
+            self.toggle_search();
+        }
+        self.search_palette(ctx);
         self.sidebar(ctx);
         egui::CentralPanel::default().show(ctx, |ui| match self.section {
             UiSection::Home => self.show_home(ui),
@@ -4890,6 +6397,11 @@ impl App for ODApp {
     }
 }
 
+/// Card background: the given shade in dark mode, egui's faint fill in light mode.
+fn panel_fill(ui: &egui::Ui, dark: Color32) -> Color32 {
+    if ui.visuals().dark_mode { dark } else { ui.visuals().faint_bg_color }
+}
+
 fn render_schema_section(
     ui: &mut egui::Ui,
     root: &mut Value,
@@ -4900,7 +6412,7 @@ fn render_schema_section(
     depth: usize,
     open_library_requested: &mut bool,
 ) {
-    let path_segments = split_path(section.path.as_deref().unwrap_or_default());
+    let path_segments = yaml_path::split(section.path.as_deref().unwrap_or_default());
     let stroke_color = match depth % 3 {
         0 => Color32::from_rgb(70, 122, 194),
         1 => Color32::from_rgb(84, 160, 120),
@@ -4909,7 +6421,7 @@ fn render_schema_section(
 
     egui::Frame::default()
         .stroke(Stroke::new(1.0, stroke_color))
-        .fill(Color32::from_rgb(18, 20, 26))
+        .fill(panel_fill(ui, Color32::from_rgb(18, 20, 26)))
         .corner_radius(5.0)
         .inner_margin(egui::Margin::same(8))
         .show(ui, |ui| {
@@ -4946,7 +6458,7 @@ fn render_normal_section(
     depth: usize,
     open_library_requested: &mut bool,
 ) {
-    let Some(target) = get_node_mut(root, section_path) else {
+    let Some(target) = yaml_path::get_mut(root, section_path) else {
         ui.label(RichText::new("Section path not found in config").color(Color32::RED));
         return;
     };
@@ -4971,7 +6483,7 @@ fn render_nested_section(
     depth: usize,
     open_library_requested: &mut bool,
 ) {
-    let nested_path = split_path(section.path.as_deref().unwrap_or_default());
+    let nested_path = yaml_path::split(section.path.as_deref().unwrap_or_default());
     let stroke_color = match depth % 3 {
         0 => Color32::from_rgb(70, 122, 194),
         1 => Color32::from_rgb(84, 160, 120),
@@ -4980,7 +6492,7 @@ fn render_nested_section(
 
     egui::Frame::default()
         .stroke(Stroke::new(1.0, stroke_color))
-        .fill(Color32::from_rgb(18, 20, 26))
+        .fill(panel_fill(ui, Color32::from_rgb(18, 20, 26)))
         .corner_radius(5.0)
         .inner_margin(egui::Margin::same(8))
         .show(ui, |ui| {
@@ -5000,7 +6512,7 @@ fn render_nested_section(
                     {
                         render_map_cards_on_node(ui, current_node, &nested_path, section, meta, assets, caches, depth + 1, open_library_requested);
                     } else {
-                        let Some(target) = get_node_mut(current_node, &nested_path) else {
+                        let Some(target) = yaml_path::get_mut(current_node, &nested_path) else {
                             ui.label(RichText::new("Section path not found in config").color(Color32::RED));
                             return;
                         };
@@ -5029,7 +6541,7 @@ fn render_map_cards(
     depth: usize,
     open_library_requested: &mut bool,
 ) {
-    let Some(target) = get_node_mut(root, map_path) else {
+    let Some(target) = yaml_path::get_mut(root, map_path) else {
         ui.label(RichText::new("Map section path not found").color(Color32::RED));
         return;
     };
@@ -5048,7 +6560,7 @@ fn render_map_cards_on_node(
     depth: usize,
     open_library_requested: &mut bool,
 ) {
-    let Some(target) = get_node_mut(current_node, map_path) else {
+    let Some(target) = yaml_path::get_mut(current_node, map_path) else {
         ui.label(RichText::new("Map section path not found").color(Color32::RED));
         return;
     };
@@ -5085,7 +6597,7 @@ fn render_map_cards_target(
 
         egui::Frame::default()
             .stroke(Stroke::new(1.0, stroke_color))
-            .fill(Color32::from_rgb(16, 18, 24))
+            .fill(panel_fill(ui, Color32::from_rgb(16, 18, 24)))
             .corner_radius(5.0)
             .inner_margin(egui::Margin::same(8))
             .show(ui, |ui| {
@@ -5108,7 +6620,7 @@ fn render_schema_field(
     caches: &mut UiCaches,
     open_library_requested: &mut bool,
 ) {
-    let path_segments = split_path(&field.path);
+    let path_segments = yaml_path::split(&field.path);
     if path_segments.is_empty() {
         return;
     }
@@ -5118,7 +6630,7 @@ fn render_schema_field(
         .clone()
         .unwrap_or_else(|| pretty_label(path_segments.last().map(|s| s.as_str()).unwrap_or_default()));
 
-    let Some(value) = get_node_mut(target_node, &path_segments) else {
+    let Some(value) = yaml_path::get_mut(target_node, &path_segments) else {
         ui.horizontal(|ui| {
             ui.label(RichText::new(field_label).strong());
             ui.label(RichText::new("(missing path)").color(Color32::RED));
@@ -5307,10 +6819,50 @@ fn render_text_value(ui: &mut egui::Ui, value: &mut Value) {
     }
 }
 
+fn render_asset_filter_chips(ui: &mut egui::Ui, filter: &mut AssetFilter) {
+    ui.horizontal(|ui| {
+        for (value, label) in [
+            (AssetFilter::All, "All"),
+            (AssetFilter::Favorites, "★ Favorites"),
+            (AssetFilter::Recent, "Recent"),
+        ] {
+            if ui.selectable_label(*filter == value, label).clicked() {
+                *filter = value;
+            }
+        }
+    });
+}
+
+/// Move `asset` to the front of the recents on disk and refresh `prefs`.
+fn record_recent_asset(prefs: &mut AssetPrefs, asset: &AssetOption) {
+    let key = asset.prefs_key();
+    match AssetPrefs::update(|p| p.record_recent(&key)) {
+        Ok(updated) => *prefs = updated,
+        Err(e) => warn!("Failed to record recent asset '{}': {}", key, e),
+    }
+}
+
+/// Assets visible under `filter`; the recent view is ordered most recent first.
+fn filter_assets(assets: &[AssetOption], prefs: &AssetPrefs, filter: AssetFilter) -> Vec<AssetOption> {
+    match filter {
+        AssetFilter::All => assets.to_vec(),
+        AssetFilter::Favorites => assets.iter().filter(|a| prefs.is_favorite(&a.prefs_key())).cloned().collect(),
+        AssetFilter::Recent => {
+            let mut recent: Vec<(usize, AssetOption)> = assets
+                .iter()
+                .filter_map(|a| prefs.recent_rank(&a.prefs_key()).map(|rank| (rank, a.clone())))
+                .collect();
+            recent.sort_by_key(|(rank, _)| *rank);
+            recent.into_iter().map(|(_, a)| a).collect()
+        }
+    }
+}
+
 fn render_asset_cards(
     ui: &mut egui::Ui,
     assets: &[AssetOption],
     caches: &mut UiCaches,
+    prefs: &mut AssetPrefs,
     selected_asset: &Option<String>,
     allow_click_select: bool,
 ) -> Option<String> {
@@ -5347,7 +6899,37 @@ fn render_asset_cards(
         let response = frame.show(ui, |ui| {
                 ui.horizontal(|ui| {
                     ui.vertical(|ui| {
-                        ui.label(RichText::new(&asset.name).strong());
+                        ui.horizontal(|ui| {
+                            let key = asset.prefs_key();
+                            let favorite = prefs.is_favorite(&key);
+                            let star = RichText::new(if favorite { "★" } else { "☆" })
+                                .color(if favorite { Color32::from_rgb(250, 200, 70) } else { Color32::GRAY });
+                            if ui
+                                .add(egui::Button::new(star).frame(false))
+                                .on_hover_text(if favorite { "Remove from favorites" } else { "Add to favorites" })
+                                .clicked()
+                            {
+                                match AssetPrefs::update(|p| p.set_favorite(&key, !favorite)) {
+                                    Ok(updated) => *prefs = updated,
+                                    Err(e) => warn!("Failed to update favorite '{}': {}", key, e),
+                                }
+                            }
+                            ui.label(RichText::new(&asset.name).strong());
+                            if !asset.issues.is_empty() {
+                                let tooltip = asset
+                                    .issues
+                                    .iter()
+                                    .map(|i| i.message.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join("\n");
+                                ui.label(
+                                    RichText::new(format!("⚠ {}", asset.issues.len()))
+                                        .small()
+                                        .color(Color32::from_rgb(235, 170, 60)),
+                                )
+                                .on_hover_text(tooltip);
+                            }
+                        });
                         ui.label(RichText::new(&asset.id).small().color(Color32::GRAY));
                         if let Some(v) = &asset.version {
                             ui.label(RichText::new(format!("v{}", v)).small());
@@ -5394,7 +6976,43 @@ fn render_asset_cards(
     clicked
 }
 
-fn render_asset_detail(ui: &mut egui::Ui, asset: &AssetOption, caches: &mut UiCaches) {
+/// Returns true when the user repaired the asset and the list should be rescanned.
+/// Warning card listing manifests that are missing, don't parse, or lack
+/// `id`/`name`. Nothing is drawn when every manifest is fine.
+fn render_manifest_problems(ui: &mut egui::Ui, assets: &[AssetOption]) {
+    let broken: Vec<(&AssetOption, Vec<&AssetIssue>)> = assets
+        .iter()
+        .map(|a| (a, a.issues.iter().filter(|i| i.is_manifest_problem()).collect::<Vec<_>>()))
+        .filter(|(_, issues)| !issues.is_empty())
+        .collect();
+    if broken.is_empty() {
+        return;
+    }
+
+    let warn_color = Color32::from_rgb(235, 170, 60);
+    egui::Frame::group(ui.style()).stroke(egui::Stroke::new(1.0, warn_color)).show(ui, |ui| {
+        ui.set_width(ui.available_width());
+        let title = if broken.len() == 1 {
+            "1 asset manifest has problems".to_string()
+        } else {
+            format!("{} asset manifests have problems", broken.len())
+        };
+        ui.label(RichText::new(format!("⚠ {}", title)).strong().color(warn_color));
+        egui::CollapsingHeader::new("Details").id_salt("manifest_problems").show(ui, |ui| {
+            for (asset, issues) in &broken {
+                ui.label(RichText::new(asset.manifest_path.display().to_string()).small().monospace());
+                for issue in issues {
+                    ui.label(RichText::new(format!("  • {}", issue.message)).small());
+                }
+                ui.add_space(4.0);
+            }
+        });
+    });
+    ui.add_space(6.0);
+}
+
+fn render_asset_detail(ui: &mut egui::Ui, asset: &AssetOption, caches: &mut UiCaches, global_status: &mut String) -> bool {
+    let mut repaired = false;
     ui.label(RichText::new(&asset.name).strong().size(18.0));
     ui.label(RichText::new(&asset.id).small().color(Color32::GRAY));
 
@@ -5420,7 +7038,31 @@ fn render_asset_detail(ui: &mut egui::Ui, asset: &AssetOption, caches: &mut UiCa
         ui.label(ld);
     }
 
-    ui.label(RichText::new(format!("Manifest: {}", asset.manifest_path.display())).small().color(Color32::GRAY));
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(format!("Manifest: {}", asset.manifest_path.display())).small().color(Color32::GRAY));
+        if ui.small_button("Open asset folder").clicked() {
+            let dir = asset.manifest_path.parent().unwrap_or(Path::new(""));
+            *global_status = match open_folder(dir) {
+                Ok(_) => format!("Opened {}", dir.display()),
+                Err(e) => e,
+            };
+        }
+    });
+
+    if !asset.issues.is_empty() {
+        ui.add_space(6.0);
+        ui.label(RichText::new("Problems").strong().color(Color32::from_rgb(235, 170, 60)));
+        for issue in &asset.issues {
+            ui.label(RichText::new(format!("• {}", issue.message)).small());
+        }
+        if asset.issues.iter().any(|i| i.fixable) && ui.button("Repair").clicked() {
+            if let Some(dir) = asset.manifest_path.parent() {
+                repaired = !fix_asset_dir(dir, &asset.issues).is_empty();
+            }
+        }
+    }
+
+    repaired
 }
 
 fn render_editable_values(ui: &mut egui::Ui, asset_id: &str, editable: &JsonValue, root: &mut Value) {
@@ -5432,11 +7074,9 @@ fn render_editable_values(ui: &mut egui::Ui, asset_id: &str, editable: &JsonValu
     for (key, val) in obj {
         ui.horizontal(|ui| {
             ui.label(RichText::new(pretty_label(key)).strong());
-            let store_path = split_path(&format!("wallpaper.asset_props.{}.{}", asset_id, key));
-            ensure_node_path(root, &store_path, json_to_yaml_scalar(val));
-            if let Some(current) = get_node_mut(root, &store_path) {
-                render_text_value(ui, current);
-            }
+            let store_path = yaml_path::split(&format!("wallpaper.asset_props.{}.{}", asset_id, key));
+            let current = yaml_path::ensure(root, &store_path, json_to_yaml_scalar(val));
+            render_text_value(ui, current);
         });
     }
 }
@@ -5458,35 +7098,6 @@ fn json_to_yaml_scalar(v: &JsonValue) -> Value {
     }
 }
 
-fn ensure_node_path(root: &mut Value, path: &[String], default_value: Value) {
-    if path.is_empty() {
-        return;
-    }
-
-    let mut current = root;
-    for (idx, segment) in path.iter().enumerate() {
-        let is_last = idx == path.len() - 1;
-        let key = Value::String(segment.clone());
-
-        let needs_mapping = !matches!(current, Value::Mapping(_));
-        if needs_mapping {
-            *current = Value::Mapping(Mapping::new());
-        }
-
-        let map = match current {
-            Value::Mapping(m) => m,
-            _ => unreachable!(),
-        };
-
-        if is_last {
-            map.entry(key).or_insert(default_value.clone());
-            return;
-        } else {
-            current = map.entry(key).or_insert_with(|| Value::Mapping(Mapping::new()));
-        }
-    }
-}
-
 fn cycle_preview(asset: &AssetOption, caches: &mut UiCaches, forward: bool) {
     if asset.preview_paths.is_empty() {
         return;
@@ -5510,22 +7121,42 @@ fn pick_preview_path(asset: &AssetOption, caches: &UiCaches) -> Option<PathBuf>
     Some(asset.preview_paths[idx].clone())
 }
 
+/// Cached by path and reloaded when the file's mtime changes. A preview
+/// that no longer exists is dropped from the cache.
 fn load_preview_texture(ctx: &egui::Context, path: &Path, caches: &mut UiCaches) -> Option<TextureHandle> {
-    let key = path.to_string_lossy().to_string();
-    if !caches.preview_textures.contains_key(&key) {
-        let image = image::open(path).ok()?.into_rgba8();
-        let size = [image.width() as usize, image.height() as usize];
-        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &image.into_raw());
-        let texture = ctx.load_texture(key.clone(), color_image, TextureOptions::LINEAR);
-        caches.preview_textures.insert(key.clone(), texture);
+    let pass = ctx.cumulative_pass_nr();
+    let Ok(meta) = std::fs::metadata(path) else {
+        caches.preview_textures.remove(path);
+        return None;
+    };
+    let modified = meta.modified().ok();
+
+    if let Some(cached) = caches.preview_textures.get_mut(path) {
+        if cached.modified == modified {
+            cached.last_used = pass;
+            return Some(cached.texture.clone());
+        }
     }
-    caches.preview_textures.get(&key).cloned()
+
+    let Some(image) = image::open(path).ok().map(|i| i.into_rgba8()) else {
+        caches.preview_textures.remove(path);
+        return None;
+    };
+    let size = [image.width() as usize, image.height() as usize];
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &image.into_raw());
+    let texture = ctx.load_texture(path.to_string_lossy(), color_image, TextureOptions::LINEAR);
+    caches.preview_textures.insert(
+        path.to_path_buf(),
+        CachedPreview { texture: texture.clone(), modified, last_used: pass },
+    );
+    caches.evict_previews(pass);
+    Some(texture)
 }
 
 fn read_asset_selector_values(root: &Value, selector_paths: &[Vec<String>]) -> Vec<(String, String)> {
     let mut out = Vec::new();
     for path in selector_paths {
-        if let Some(v) = get_node(root, path) {
+        if let Some(v) = yaml_path::get(root, path) {
             if let Value::String(s) = v {
                 out.push((path.join("."), s.clone()));
             }
@@ -5535,22 +7166,15 @@ fn read_asset_selector_values(root: &Value, selector_paths: &[Vec<String>]) -> V
 }
 
 fn apply_asset_assignment_to_monitor(root: &mut Value, monitor_key: &str, asset_id: &str) {
-    let assignments_path = split_path("wallpaper.assignments");
-    ensure_node_path(root, &assignments_path, Value::Mapping(Mapping::new()));
-
-    let assignment_entry_path = split_path(&format!("wallpaper.assignments.{}", monitor_key));
-    ensure_node_path(root, &assignment_entry_path, Value::String(asset_id.to_string()));
-    if let Some(v) = get_node_mut(root, &assignment_entry_path) {
-        *v = Value::String(asset_id.to_string());
-    }
+    let assignment_entry_path = yaml_path::split(&format!("wallpaper.assignments.{}", monitor_key));
+    yaml_path::set(root, &assignment_entry_path, Value::String(asset_id.to_string()));
 
-    if let Some(v) = get_node_mut(root, &split_path("wallpaper.wallpaper_id")) {
+    if let Some(v) = yaml_path::get_mut(root, &yaml_path::split("wallpaper.wallpaper_id")) {
         *v = Value::String(asset_id.to_string());
     }
 
-    let monitor_index_path = split_path("wallpaper.monitor_index");
-    ensure_node_path(root, &monitor_index_path, Value::Sequence(vec![]));
-    if let Some(Value::Sequence(seq)) = get_node_mut(root, &monitor_index_path) {
+    let monitor_index_path = yaml_path::split("wallpaper.monitor_index");
+    if let Value::Sequence(seq) = yaml_path::ensure(root, &monitor_index_path, Value::Sequence(vec![])) {
         let exists = seq.iter().any(|v| v.as_str().map(|s| s == monitor_key).unwrap_or(false));
         if !exists {
             seq.push(Value::String(monitor_key.to_string()));
@@ -5559,22 +7183,96 @@ fn apply_asset_assignment_to_monitor(root: &mut Value, monitor_key: &str, asset_
 }
 
 fn get_assigned_asset_for_monitor(root: &Value, monitor_key: &str) -> Option<String> {
-    let assignment_entry_path = split_path(&format!("wallpaper.assignments.{}", monitor_key));
-    if let Some(Value::String(v)) = get_node(root, &assignment_entry_path) {
+    let assignment_entry_path = yaml_path::split(&format!("wallpaper.assignments.{}", monitor_key));
+    if let Some(Value::String(v)) = yaml_path::get(root, &assignment_entry_path) {
         return Some(v.clone());
     }
 
-    if let Some(Value::String(v)) = get_node(root, &split_path("wallpaper.assignments.*")) {
+    if let Some(Value::String(v)) = yaml_path::get(root, &yaml_path::split("wallpaper.assignments.*")) {
         return Some(v.clone());
     }
 
-    if let Some(Value::String(v)) = get_node(root, &split_path("wallpaper.wallpaper_id")) {
+    if let Some(Value::String(v)) = yaml_path::get(root, &yaml_path::split("wallpaper.wallpaper_id")) {
         return Some(v.clone());
     }
 
     None
 }
 
+/// Move the selected monitor within the wallpaper index order. The result is
+/// stored as `monitor_index_order` (stable ids) in the addon config; `monitors`
+/// must already be in index order.
+fn render_monitor_index_order_controls(
+    ui: &mut egui::Ui,
+    monitors: &[MonitorInfo],
+    root: &mut Value,
+    selected_monitor: Option<&str>,
+) {
+    if monitors.len() < 2 {
+        return;
+    }
+
+    let saved = monitor_index_order(root);
+    let position = selected_monitor.and_then(|id| monitors.iter().position(|m| m.id == id));
+    let reorderable = monitors.iter().all(|m| !m.stable_id.is_empty());
+    let mut new_order: Option<Vec<String>> = None;
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label(RichText::new("Wallpaper index:").strong());
+        match position {
+            Some(pos) if reorderable => {
+                ui.label(format!("#{}", pos));
+                let mut swap_with = None;
+                if ui.add_enabled(pos > 0, egui::Button::new("◀ Earlier")).clicked() {
+                    swap_with = Some(pos - 1);
+                }
+                if ui.add_enabled(pos + 1 < monitors.len(), egui::Button::new("Later ▶")).clicked() {
+                    swap_with = Some(pos + 1);
+                }
+                if let Some(other) = swap_with {
+                    let mut ids = monitors.iter().map(|m| m.stable_id.clone()).collect::<Vec<_>>();
+                    ids.swap(pos, other);
+                    new_order = Some(ids);
+                }
+            }
+            Some(_) => {
+                ui.label(RichText::new("Monitor identities are unavailable, so the order can't be changed").small().color(Color32::GRAY));
+            }
+            None => {
+                ui.label(RichText::new("Select a monitor to change its index").small().color(Color32::GRAY));
+            }
+        }
+        if !saved.is_empty() && ui.button("Use automatic order").clicked() {
+            new_order = Some(Vec::new());
+        }
+    });
+
+    // A saved order for a different monitor set still applies to the monitors
+    // it names; say so, since the indexes may not be what the user arranged.
+    let connected = monitors.iter().filter(|m| saved.contains(&m.stable_id)).count();
+    if !saved.is_empty() && (connected != saved.len() || connected != monitors.len()) {
+        ui.label(
+            RichText::new("The saved order was made for a different set of monitors. Monitors it doesn't list follow in automatic order.")
+                .small()
+                .color(Color32::from_rgb(230, 180, 90)),
+        );
+    }
+
+    match new_order {
+        Some(ids) if ids.is_empty() => {
+            if let Some(map) = root.as_mapping_mut() {
+                map.remove(Value::String("monitor_index_order".to_string()));
+            }
+        }
+        Some(ids) => yaml_path::set(
+            root,
+            &yaml_path::split("monitor_index_order"),
+            Value::Sequence(ids.into_iter().map(Value::String).collect()),
+        ),
+        None => {}
+    }
+}
+
 fn render_monitor_layout_preview(
     ui: &mut egui::Ui,
     monitors: &[MonitorInfo],
@@ -5605,7 +7303,7 @@ fn render_monitor_layout_preview(
     let pad = 10.0;
     let scale = ((rect.width() - pad * 2.0) / total_w).min((rect.height() - pad * 2.0) / total_h);
 
-    for monitor in monitors {
+    for (index, monitor) in monitors.iter().enumerate() {
         let left = rect.left() + pad + ((monitor.x as f32 - min_x) * scale);
         let top = rect.top() + pad + ((monitor.y as f32 - min_y) * scale);
         let w = (monitor.width as f32 * scale).max(40.0);
@@ -5644,7 +7342,7 @@ fn render_monitor_layout_preview(
         painter.text(
             mrect.left_top() + egui::vec2(6.0, 6.0),
             egui::Align2::LEFT_TOP,
-            format!("{} {}x{}", monitor_label, monitor.width, monitor.height),
+            format!("#{} {} {}x{}", index, monitor_label, monitor.width, monitor.height),
             egui::FontId::proportional(11.0),
             Color32::WHITE,
         );
@@ -5668,21 +7366,6 @@ fn render_monitor_layout_preview(
     }
 }
 
-fn get_node<'a>(root: &'a Value, path: &[String]) -> Option<&'a Value> {
-    if path.is_empty() {
-        return Some(root);
-    }
-
-    let mut current = root;
-    for segment in path {
-        let Value::Mapping(map) = current else {
-            return None;
-        };
-        current = map.get(Value::String(segment.clone()))?;
-    }
-    Some(current)
-}
-
 fn render_raw_fallback(ui: &mut egui::Ui, root: &mut Value) {
     ui.label(RichText::new("No schema.yaml found. Showing fallback editor.").small().color(Color32::GRAY));
     ui.add_space(6.0);
@@ -5712,7 +7395,7 @@ fn render_yaml_node_fallback(ui: &mut egui::Ui, node: &mut Value, path: &mut Vec
 
                         egui::Frame::default()
                             .stroke(Stroke::new(1.0, stroke_color))
-                            .fill(Color32::from_rgb(18, 20, 26))
+                            .fill(panel_fill(ui, Color32::from_rgb(18, 20, 26)))
                             .corner_radius(5.0)
                             .inner_margin(egui::Margin::same(8))
                             .show(ui, |ui| {
@@ -5743,7 +7426,7 @@ fn render_yaml_node_fallback(ui: &mut egui::Ui, node: &mut Value, path: &mut Vec
                 path.push(idx.to_string());
                 egui::Frame::default()
                     .stroke(Stroke::new(1.0, Color32::from_rgb(80, 80, 95)))
-                    .fill(Color32::from_rgb(18, 20, 26))
+                    .fill(panel_fill(ui, Color32::from_rgb(18, 20, 26)))
                     .corner_radius(5.0)
                     .inner_margin(egui::Margin::same(8))
                     .show(ui, |ui| {
@@ -5764,29 +7447,6 @@ fn render_yaml_node_fallback(ui: &mut egui::Ui, node: &mut Value, path: &mut Vec
     }
 }
 
-fn split_path(path: &str) -> Vec<String> {
-    path.split('.')
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .map(|s| s.to_string())
-        .collect()
-}
-
-fn get_node_mut<'a>(root: &'a mut Value, path: &[String]) -> Option<&'a mut Value> {
-    if path.is_empty() {
-        return Some(root);
-    }
-
-    let mut current = root;
-    for segment in path {
-        let Value::Mapping(map) = current else {
-            return None;
-        };
-        current = map.get_mut(Value::String(segment.clone()))?;
-    }
-    Some(current)
-}
-
 fn pretty_label(raw: &str) -> String {
     raw.replace(['-', '_'], " ")
         .split_whitespace()
@@ -5823,6 +7483,36 @@ fn open_local_html(path: &Path) -> Result<(), String> {
     }
 }
 
+/// Show `dir` in Explorer. Only existing folders are accepted, so a path
+/// can't be used to launch a file.
+fn open_folder(dir: &Path) -> Result<(), String> {
+    if !dir.is_dir() {
+        return Err(format!("Folder not found: {}", dir.display()));
+    }
+
+    if cfg!(target_os = "windows") {
+        // Explorer ignores paths with forward slashes and opens Documents instead.
+        let native = dir.to_string_lossy().replace('/', "\\");
+        std::process::Command::new("explorer")
+            .arg(&native)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder '{}': {}", dir.display(), e))?;
+        Ok(())
+    } else {
+        Err("Opening folders is currently implemented for Windows only".to_string())
+    }
+}
+
+/// `open_folder` for an addon's root, looked up by id.
+fn open_addon_folder(addon_id: &str) -> Result<PathBuf, String> {
+    let addon = discover_addon_configs()
+        .into_iter()
+        .find(|a| a.id.eq_ignore_ascii_case(addon_id))
+        .ok_or_else(|| format!("Addon '{}' not found", addon_id))?;
+    open_folder(&addon.addon_root)?;
+    Ok(addon.addon_root)
+}
+
 fn file_path_to_url(path: &Path) -> Result<String, String> {
     let canonical = std::fs::canonicalize(path)
         .map_err(|e| format!("Failed to resolve path '{}': {}", path.display(), e))?;
@@ -6049,6 +7739,25 @@ fn render_selected_custom_tab(
     }
 }
 
+/// Write a pending debounced edit now, if there is one.
+fn flush_addon_save(state: &mut AddonConfigState, global_status: &mut String) {
+    if state.save_due.take().is_none() {
+        return;
+    }
+    state.commit_history();
+    match save_addon_state(state) {
+        Ok(_) => {
+            state.status = "Live saved config.yaml".to_string();
+            *global_status = "Live saved addon config".to_string();
+        }
+        Err(e) => {
+            state.status = format!("Live save failed: {}", e);
+            *global_status = "Live save failed".to_string();
+            error!("Config UI live save failed: {}", e);
+        }
+    }
+}
+
 fn save_addon_state(state: &mut AddonConfigState) -> Result<(), String> {
     let serialized = serde_yaml::to_string(&state.root)
         .map_err(|e| format!("Failed to serialize YAML: {}", e))?;
@@ -6060,8 +7769,16 @@ fn save_addon_state(state: &mut AddonConfigState) -> Result<(), String> {
 fn load_addon_state(meta: AddonMeta) -> Result<AddonConfigState, Box<dyn std::error::Error>> {
     ensure_config_file_exists(&meta.config_path)?;
 
-    let content = std::fs::read_to_string(&meta.config_path).unwrap_or_else(|_| "{}".to_string());
-    let root = serde_yaml::from_str::<Value>(&content).unwrap_or_else(|_| Value::Mapping(Mapping::new()));
+    // A file that can't be read or parsed is reported, not loaded as empty:
+    // migrating or live-saving that would overwrite the user's settings.
+    let content = std::fs::read_to_string(&meta.config_path)
+        .map_err(|e| format!("Failed to read {}: {}", meta.config_path.display(), e))?;
+    let mut root = serde_yaml::from_str::<Value>(&content)
+        .map_err(|e| format!("{} is not valid YAML, fix it by hand: {}", meta.config_path.display(), e))?;
+    if root.is_null() {
+        root = Value::Mapping(Mapping::new());
+    }
+    migrate_addon_config(&meta, &mut root);
 
     let schema = load_schema(&meta.schema_path);
     let asset_selector_paths = collect_asset_selector_paths(schema.as_ref());
@@ -6070,15 +7787,66 @@ fn load_addon_state(meta: AddonMeta) -> Result<AddonConfigState, Box<dyn std::er
 
     Ok(AddonConfigState {
         meta,
+        committed: root.clone(),
+        undo: Vec::new(),
+        redo: Vec::new(),
         root,
         schema,
         status: "Live save enabled".to_string(),
         assets,
         asset_selector_paths,
         custom_tabs,
+        save_due: None,
     })
 }
 
+/// Bring `root` up to the `config_version` in the addon's schema.yaml. The
+/// file as it was is copied to `backups/migrate-<time>/` before the migrated
+/// one is written. On failure `root` is left as read.
+fn migrate_addon_config(meta: &AddonMeta, root: &mut Value) {
+    let plan = match crate::config::migrations::load_plan(&meta.schema_path) {
+        Ok(Some(plan)) => plan,
+        Ok(None) => return,
+        Err(e) => {
+            warn!("[{}] {}", meta.id, e);
+            return;
+        }
+    };
+    let mut migrated = root.clone();
+    let applied = match crate::config::migrations::migrate(&mut migrated, &plan) {
+        Ok(applied) if applied.is_empty() => return,
+        Ok(applied) => applied,
+        Err(e) => {
+            warn!("[{}] Config migration failed, loading config.yaml unchanged: {}", meta.id, e);
+            return;
+        }
+    };
+
+    let rel = meta.config_path.strip_prefix(veil_root_dir()).unwrap_or(Path::new("config.yaml"));
+    let backup = backups_dir().join(format!("migrate-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"))).join(rel);
+    let backed_up = backup
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::copy(&meta.config_path, &backup));
+    if let Err(e) = backed_up {
+        warn!("[{}] Not migrating config.yaml; backup to {} failed: {}", meta.id, backup.display(), e);
+        return;
+    }
+    let written = serde_yaml::to_string(&migrated)
+        .map_err(|e| e.to_string())
+        .and_then(|text| std::fs::write(&meta.config_path, text).map_err(|e| e.to_string()));
+    if let Err(e) = written {
+        warn!("[{}] Failed to write migrated config.yaml: {}", meta.id, e);
+        return;
+    }
+
+    for line in &applied {
+        info!("[{}] Migrated config.yaml {}", meta.id, line);
+    }
+    info!("[{}] Previous config.yaml saved to {}", meta.id, backup.display());
+    *root = migrated;
+}
+
 fn collect_asset_selector_paths(schema: Option<&AddonSchema>) -> Vec<Vec<String>> {
     let mut out = Vec::new();
     let Some(schema) = schema else { return out; };
@@ -6091,7 +7859,7 @@ fn collect_asset_selector_paths(schema: Option<&AddonSchema>) -> Vec<Vec<String>
         for field in &section.fields {
             if field.control.eq_ignore_ascii_case("asset_selector") {
                 let mut full = full_base.clone();
-                full.extend(split_path(&field.path));
+                full.extend(yaml_path::split(&field.path));
                 out.push(full);
             }
         }
@@ -6107,6 +7875,18 @@ fn collect_asset_selector_paths(schema: Option<&AddonSchema>) -> Vec<Vec<String>
     out
 }
 
+/// Give every `z_index` field the accepted values as dropdown options, so
+/// the editor can't offer one the save path would reject.
+fn fill_z_index_options(sections: &mut [SchemaSection]) {
+    for section in sections {
+        for field in section.fields.iter_mut().filter(|f| z_index::is_z_index_path(&f.path)) {
+            field.control = "dropdown".to_string();
+            field.options = z_index::Z_INDEXES.iter().map(|z| z.to_string()).collect();
+        }
+        fill_z_index_options(&mut section.sections);
+    }
+}
+
 fn load_schema(path: &Path) -> Option<AddonSchema> {
     if !path.exists() {
         return None;
@@ -6118,7 +7898,10 @@ fn load_schema(path: &Path) -> Option<AddonSchema> {
     }
 
     match serde_yaml::from_str::<AddonSchema>(&content) {
-        Ok(schema) => Some(schema),
+        Ok(mut schema) => {
+            fill_z_index_options(&mut schema.ui.sections);
+            Some(schema)
+        }
         Err(e) => {
             warn!("Failed to parse schema '{}': {}", path.display(), e);
             None
@@ -6137,11 +7920,15 @@ fn ensure_config_file_exists(path: &PathBuf) -> Result<(), Box<dyn std::error::E
 fn discover_addon_configs() -> Vec<AddonMeta> {
     let mut result = Vec::new();
 
-    let addons_root = veil_root_dir().join("Addons");
+    let addons_root = addons_dir();
     let entries = match std::fs::read_dir(&addons_root) {
         Ok(v) => v,
         Err(_) => return result,
     };
+    // The daemon owns config.yaml; read the filter straight from disk.
+    let filter = crate::config::read_config_from_disk()
+        .map(|cfg| cfg.addons)
+        .unwrap_or_default();
 
     for entry in entries.flatten() {
         let addon_dir = entry.path();
@@ -6162,6 +7949,11 @@ fn discover_addon_configs() -> Vec<AddonMeta> {
             .or_else(|| addon_dir.file_name().and_then(|s| s.to_str()).map(|s| s.to_string()))
             .unwrap_or_else(|| "unknown".to_string());
 
+        let folder = addon_dir.file_name().and_then(|s| s.to_str()).unwrap_or_default();
+        if !filter.allows(&id, folder) {
+            continue;
+        }
+
         let name = parsed
             .get("name")
             .and_then(|v| v.as_str())
@@ -6187,6 +7979,13 @@ fn discover_addon_configs() -> Vec<AddonMeta> {
             .map(|arr| arr.iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect::<Vec<_>>())
             .unwrap_or_default();
 
+        let workshop_app_id = parsed
+            .get("assets")
+            .and_then(|a| a.get("workshop_app_id"))
+            .or_else(|| parsed.get("workshop_app_id"))
+            .and_then(|v| v.as_u64())
+            .and_then(|v| u32::try_from(v).ok());
+
         result.push(AddonMeta {
             id,
             name,
@@ -6196,6 +7995,9 @@ fn discover_addon_configs() -> Vec<AddonMeta> {
             schema_path: addon_dir.join("schema.yaml"),
             accepts_assets,
             asset_categories,
+            workshop_app_id,
+            capabilities: crate::ipc::addon::capabilities::parse_capabilities(&parsed),
+            incompatible: crate::ipc::addon::compat::incompatibility(&parsed),
         });
     }
 
@@ -6234,12 +8036,58 @@ fn discover_assets_for_meta(meta: &AddonMeta, schema: Option<&AddonSchema>) -> V
         }
     }
 
-    let mut merged = Vec::new();
-    let mut seen = HashSet::new();
+    // The daemon owns config.yaml; read the setting straight from disk.
+    let disambiguate = crate::config::read_config_from_disk()
+        .map(|cfg| cfg.disambiguate_asset_ids)
+        .unwrap_or(false);
+
+    let mut merged: Vec<AssetOption> = Vec::new();
+    let mut seen_manifests = HashSet::new();
+    let mut index_by_id: HashMap<String, usize> = HashMap::new();
     for category in categories {
-        for asset in discover_assets_for_category(&category) {
-            if seen.insert(asset.id.clone()) {
+        for mut asset in discover_assets_for_category(&category) {
+            // Category aliases ("wallpaper"/"wallpapers") can resolve to the same folder.
+            if !seen_manifests.insert(asset.manifest_path.clone()) {
+                continue;
+            }
+
+            let Some(&kept_idx) = index_by_id.get(&asset.id.to_ascii_lowercase()) else {
+                index_by_id.insert(asset.id.to_ascii_lowercase(), merged.len());
+                merged.push(asset);
+                continue;
+            };
+
+            let kept_path = merged[kept_idx].manifest_path.clone();
+            warn!(
+                "Duplicate asset id '{}': '{}' conflicts with '{}'",
+                asset.id,
+                asset.manifest_path.display(),
+                kept_path.display()
+            );
+
+            if disambiguate {
+                let original_id = asset.id.clone();
+                asset.id = disambiguated_asset_id(&asset, &index_by_id);
+                asset.issues.push(duplicate_id_issue(format!(
+                    "id '{}' is also used by '{}'; shown here as '{}'. Rename one of them.",
+                    original_id,
+                    kept_path.display(),
+                    asset.id
+                )));
+                merged[kept_idx].issues.push(duplicate_id_issue(format!(
+                    "id '{}' is also used by '{}' (shown as '{}'). Rename one of them.",
+                    original_id,
+                    asset.manifest_path.display(),
+                    asset.id
+                )));
+                index_by_id.insert(asset.id.to_ascii_lowercase(), merged.len());
                 merged.push(asset);
+            } else {
+                merged[kept_idx].issues.push(duplicate_id_issue(format!(
+                    "id '{}' is also used by '{}', which is hidden. Rename one of them.",
+                    asset.id,
+                    asset.manifest_path.display()
+                )));
             }
         }
     }
@@ -6248,14 +8096,60 @@ fn discover_assets_for_meta(meta: &AddonMeta, schema: Option<&AddonSchema>) -> V
     merged
 }
 
+impl AssetOption {
+    fn prefs_key(&self) -> String {
+        asset_key(&self.category, &self.id)
+    }
+}
+
+/// Prefs key for an asset the wallpaper shell names only by id: the
+/// category it was found in for `addon_id`, else the addon's primary one.
+/// Without the addon there is no category, and the bare id is used.
+fn shell_asset_key(addon_id: &str, asset_id: &str) -> String {
+    let Some(addon) = discover_addon_configs().into_iter().find(|a| a.id.eq_ignore_ascii_case(addon_id)) else {
+        return asset_id.to_string();
+    };
+    discover_assets_for_meta(&addon, None)
+        .into_iter()
+        .find(|a| a.id == asset_id)
+        .map(|a| a.prefs_key())
+        .unwrap_or_else(|| asset_key(&addon.primary_asset_category(), asset_id))
+}
+
+fn duplicate_id_issue(message: String) -> AssetIssue {
+    AssetIssue { code: "duplicate_id", message, fixable: false }
+}
+
+/// `<id>-<folder>`, with a numeric suffix if that is taken too.
+fn disambiguated_asset_id(asset: &AssetOption, taken: &HashMap<String, usize>) -> String {
+    let folder = asset
+        .manifest_path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|s| s.to_str())
+        .unwrap_or("copy");
+    let base = format!("{}-{}", asset.id, folder);
+    let mut candidate = base.clone();
+    let mut n = 2;
+    while taken.contains_key(&candidate.to_ascii_lowercase()) {
+        candidate = format!("{}-{}", base, n);
+        n += 1;
+    }
+    candidate
+}
+
 fn discover_assets_for_category(category: &str) -> Vec<AssetOption> {
     let mut result = Vec::new();
 
-    let assets_root = veil_root_dir().join("Assets");
+    let assets_root = assets_dir();
     let category_root = match find_category_dir_case_insensitive(&assets_root, category) {
         Some(p) => p,
         None => return result,
     };
+    let category_name = category_root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| category.to_string());
 
     for entry in walkdir::WalkDir::new(&category_root)
         .min_depth(1)
@@ -6273,11 +8167,9 @@ fn discover_assets_for_category(category: &str) -> Vec<AssetOption> {
             None => continue,
         };
 
-        let manifest_text = match std::fs::read_to_string(&manifest_path) {
-            Ok(t) => t,
-            Err(_) => continue,
-        };
-
+        // Unreadable or broken manifests are still listed under the folder
+        // name so the manifest problems card can point at them.
+        let manifest_text = std::fs::read_to_string(&manifest_path).unwrap_or_default();
         let manifest = parse_json_relaxed(&manifest_text).unwrap_or(JsonValue::Null);
 
         let id = manifest
@@ -6329,9 +8221,11 @@ fn discover_assets_for_category(category: &str) -> Vec<AssetOption> {
 
         let preview_paths = collect_preview_paths(&metadata, manifest_dir);
         let editable = manifest.get("editable").cloned().unwrap_or(JsonValue::Null);
+        let (_, issues) = verify_asset_dir(manifest_dir, category);
 
         result.push(AssetOption {
             id,
+            category: category_name.clone(),
             name,
             version,
             tags,
@@ -6342,6 +8236,7 @@ fn discover_assets_for_category(category: &str) -> Vec<AssetOption> {
             preview_paths,
             manifest_path,
             editable,
+            issues,
         });
     }
 
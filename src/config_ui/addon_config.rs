@@ -0,0 +1,47 @@
+// ~/veil/veil-backend/src/config_ui/addon_config.rs
+//
+// Headless access to addon config for the `config` IPC namespace, using the
+// same discovery, schema parsing and YAML traversal as the UI.
+
+use serde_json::Value as JsonValue;
+use serde_yaml::{Mapping, Value};
+
+use super::{apply_config_update, discover_addon_configs, load_schema, AddonMeta};
+use crate::config::yaml_path;
+
+fn find_addon(addon_id: &str) -> Result<AddonMeta, String> {
+    discover_addon_configs()
+        .into_iter()
+        .find(|a| a.id.eq_ignore_ascii_case(addon_id))
+        .ok_or_else(|| format!("Addon '{}' not found", addon_id))
+}
+
+/// Parsed `schema.yaml` of `addon_id`, or `null` when it ships none.
+pub fn addon_schema_json(addon_id: &str) -> Result<JsonValue, String> {
+    let addon = find_addon(addon_id)?;
+    match load_schema(&addon.schema_path) {
+        Some(schema) => serde_json::to_value(schema).map_err(|e| format!("Failed to serialize schema: {}", e)),
+        None => Ok(JsonValue::Null),
+    }
+}
+
+/// Value at dotted `path` in the addon's `config.yaml`; the whole file for an
+/// empty path, `null` when the path is unset.
+pub fn addon_config_value(addon_id: &str, path: &str) -> Result<JsonValue, String> {
+    let addon = find_addon(addon_id)?;
+    let root = match std::fs::read_to_string(&addon.config_path) {
+        Ok(content) => serde_yaml::from_str::<Value>(&content)
+            .map_err(|e| format!("Failed to parse '{}': {}", addon.config_path.display(), e))?,
+        Err(_) => Value::Mapping(Mapping::new()),
+    };
+
+    match yaml_path::get(&root, &yaml_path::split(path)) {
+        Some(node) => serde_json::to_value(node).map_err(|e| format!("Failed to convert '{}': {}", path, e)),
+        None => Ok(JsonValue::Null),
+    }
+}
+
+/// Write `value` at dotted `path` in the addon's `config.yaml`.
+pub fn set_addon_config_value(addon_id: &str, path: &str, value: &JsonValue) -> Result<(), String> {
+    apply_config_update(addon_id, path, value)
+}
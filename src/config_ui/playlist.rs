@@ -0,0 +1,256 @@
+// ~/veil/veil-backend/src/config_ui/playlist.rs
+//
+// Wallpaper playlists. A profile section may list `playlist: [id, ...]` and
+// `rotate_interval_minutes`; the daemon's rotation thread then moves the
+// section's `wallpaper_id` to the next entry on that interval, and the
+// wallpaper addon picks the change up through its config watcher. Each
+// playlist's position is kept in `<root>/playlist_state.json` so rotation
+// carries on across restarts. A playlist with a single entry is static.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use super::{
+    discover_addon_configs, is_wallpaper_addon, parse_wallpaper_profiles, sorted_shell_monitors,
+    stable_id_at_index, upsert_wallpaper_profile_for_index,
+};
+use crate::config::yaml_path;
+use crate::paths::playlist_state_path;
+use crate::{error, info, warn};
+
+const TICK: Duration = Duration::from_secs(30);
+/// Used when the shell saves a playlist without an interval.
+const DEFAULT_INTERVAL_MINUTES: u64 = 30;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PlaylistState {
+    /// Keyed by `<addon id>/<profile section>`.
+    #[serde(default)]
+    sections: BTreeMap<String, PlaylistPosition>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct PlaylistPosition {
+    index: usize,
+    /// Unix seconds of the last switch.
+    rotated_at: u64,
+}
+
+impl PlaylistState {
+    fn load() -> Self {
+        std::fs::read_to_string(playlist_state_path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize playlist state: {}", e))?;
+        std::fs::write(playlist_state_path(), text)
+            .map_err(|e| format!("Failed to write playlist state: {}", e))
+    }
+}
+
+fn state_key(addon_id: &str, section: &str) -> String {
+    format!("{}/{}", addon_id, section)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Current playlist position of a profile section, if it has rotated before.
+pub(super) fn playlist_index(addon_id: &str, section: &str) -> Option<usize> {
+    PlaylistState::load()
+        .sections
+        .get(&state_key(addon_id, section))
+        .map(|p| p.index)
+}
+
+/// Spawn the daemon thread that advances due playlists.
+pub fn start_playlist_rotation() {
+    let spawned = std::thread::Builder::new()
+        .name("veil-playlist".into())
+        .spawn(|| loop {
+            rotate_due_playlists();
+            std::thread::sleep(TICK);
+        });
+    if let Err(e) = spawned {
+        error!("[playlist] Failed to start rotation thread: {}", e);
+    }
+}
+
+fn rotate_due_playlists() {
+    let mut state = PlaylistState::load();
+    let now = unix_now();
+    let mut state_changed = false;
+
+    for addon in discover_addon_configs().iter().filter(|a| is_wallpaper_addon(a)) {
+        let Some(root) = std::fs::read_to_string(&addon.config_path)
+            .ok()
+            .and_then(|text| serde_yaml::from_str::<Value>(&text).ok())
+        else {
+            continue;
+        };
+
+        let mut switches = Vec::<(String, String)>::new();
+        for profile in parse_wallpaper_profiles(&root) {
+            let Some(interval) = profile.rotate_interval_minutes.filter(|m| *m > 0) else {
+                continue;
+            };
+            // A schedule decides the wallpaper on its own.
+            if !profile.enabled || profile.playlist.len() < 2 || !profile.schedule.is_empty() {
+                continue;
+            }
+
+            let key = state_key(&addon.id, &profile.section);
+            let pos = state.sections.entry(key).or_insert_with(|| {
+                state_changed = true;
+                PlaylistPosition {
+                    index: profile.playlist.iter().position(|id| *id == profile.wallpaper_id).unwrap_or(0),
+                    rotated_at: now,
+                }
+            });
+            if now.saturating_sub(pos.rotated_at) < interval * 60 {
+                continue;
+            }
+
+            pos.index = (pos.index + 1) % profile.playlist.len();
+            pos.rotated_at = now;
+            state_changed = true;
+            switches.push((profile.section.clone(), profile.playlist[pos.index].clone()));
+        }
+
+        if switches.is_empty() {
+            continue;
+        }
+        match write_active_wallpapers(&addon.config_path, &switches) {
+            Ok(()) => {
+                for (section, wallpaper_id) in &switches {
+                    info!("[playlist] {} {} -> '{}'", addon.id, section, wallpaper_id);
+                }
+            }
+            Err(e) => warn!("[playlist] Failed to rotate wallpapers for '{}': {}", addon.id, e),
+        }
+    }
+
+    if state_changed {
+        if let Err(e) = state.save() {
+            warn!("[playlist] {}", e);
+        }
+    }
+}
+
+/// Profile sections live either under `wallpapers:` or at the top level.
+fn profile_section_mut<'a>(root: &'a mut Value, section: &str) -> Option<&'a mut Mapping> {
+    let nested = root
+        .get("wallpapers")
+        .and_then(|w| w.get(section))
+        .is_some_and(|v| v.is_mapping());
+    if nested {
+        root.get_mut("wallpapers")?.get_mut(section)?.as_mapping_mut()
+    } else {
+        root.get_mut(section)?.as_mapping_mut()
+    }
+}
+
+pub(super) fn write_active_wallpapers(config_path: &Path, switches: &[(String, String)]) -> Result<(), String> {
+    let content = std::fs::read_to_string(config_path)
+        .map_err(|e| format!("Failed to read '{}': {}", config_path.display(), e))?;
+    let mut root = serde_yaml::from_str::<Value>(&content)
+        .map_err(|e| format!("Failed to parse '{}': {}", config_path.display(), e))?;
+
+    for (section, wallpaper_id) in switches {
+        if let Some(map) = profile_section_mut(&mut root, section) {
+            map.insert(
+                Value::String("wallpaper_id".to_string()),
+                Value::String(wallpaper_id.clone()),
+            );
+        }
+    }
+
+    let serialized = serde_yaml::to_string(&root)
+        .map_err(|e| format!("Failed to serialize YAML: {}", e))?;
+    std::fs::write(config_path, serialized)
+        .map_err(|e| format!("Failed to write '{}': {}", config_path.display(), e))
+}
+
+/// Save a playlist from the shell Library for the given monitor indexes. The
+/// first entry becomes active now; a single entry is saved as a plain
+/// `wallpaper_id` with no rotation.
+pub(super) fn apply_playlist_from_shell(
+    addon_id: &str,
+    monitor_indexes: &[String],
+    playlist: &[String],
+    rotate_interval_minutes: Option<u64>,
+) -> Result<(), String> {
+    let monitor_indexes = monitor_indexes
+        .iter()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>();
+    if monitor_indexes.is_empty() {
+        return Err("No monitor indexes supplied".to_string());
+    }
+    let playlist = playlist
+        .iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect::<Vec<_>>();
+    let Some(first) = playlist.first() else {
+        return Err("Playlist is empty".to_string());
+    };
+    let interval = rotate_interval_minutes.unwrap_or(DEFAULT_INTERVAL_MINUTES).max(1);
+
+    let addon = discover_addon_configs()
+        .into_iter()
+        .find(|a| a.id.eq_ignore_ascii_case(addon_id))
+        .ok_or_else(|| format!("Addon '{}' not found", addon_id))?;
+
+    let content = std::fs::read_to_string(&addon.config_path).unwrap_or_else(|_| "{}".to_string());
+    let mut root = serde_yaml::from_str::<Value>(&content).unwrap_or_else(|_| Value::Mapping(Mapping::new()));
+    let monitors = sorted_shell_monitors(&root);
+    let wallpapers_map = yaml_path::ensure_mapping(&mut root, &yaml_path::split("wallpapers"));
+
+    let mut sections = Vec::new();
+    for monitor_index in &monitor_indexes {
+        let stable_id = stable_id_at_index(&monitors, monitor_index);
+        let section = upsert_wallpaper_profile_for_index(wallpapers_map, monitor_index, stable_id, first);
+        if playlist.len() > 1 {
+            if let Some(Value::Mapping(map)) = wallpapers_map.get_mut(Value::String(section.clone())) {
+                map.insert(
+                    Value::String("playlist".to_string()),
+                    Value::Sequence(playlist.iter().cloned().map(Value::String).collect()),
+                );
+                map.insert(
+                    Value::String("rotate_interval_minutes".to_string()),
+                    Value::Number(interval.into()),
+                );
+            }
+        }
+        sections.push(section);
+    }
+
+    let serialized = serde_yaml::to_string(&root)
+        .map_err(|e| format!("Failed to serialize YAML: {}", e))?;
+    std::fs::write(&addon.config_path, serialized)
+        .map_err(|e| format!("Failed to write '{}': {}", addon.config_path.display(), e))?;
+
+    // Start each edited playlist from the top.
+    let mut state = PlaylistState::load();
+    let now = unix_now();
+    for section in &sections {
+        let key = state_key(&addon.id, section);
+        if playlist.len() > 1 {
+            state.sections.insert(key, PlaylistPosition { index: 0, rotated_at: now });
+        } else {
+            state.sections.remove(&key);
+        }
+    }
+    state.save()
+}
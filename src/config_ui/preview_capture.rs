@@ -0,0 +1,259 @@
+// ~/veil/veil-backend/src/config_ui/preview_capture.rs
+//
+// Wallpaper preview screenshots. The UI spawns `VEIL --capture-wallpaper-preview
+// <manifest>`, which loads the wallpaper's index.html in an off-screen webview,
+// waits for the page to finish loading, grabs the window with PrintWindow and
+// writes `preview/capture.png`. Running it in its own process keeps the
+// capture's event loop away from the UI's. If anything fails, the previous
+// preview is left alone.
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+use tao::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{Event, StartCause},
+    event_loop::{ControlFlow, EventLoopBuilder},
+    window::WindowBuilder,
+};
+use wry::{PageLoadEvent, WebViewBuilder};
+
+use super::file_path_to_url;
+use crate::{error, info, warn};
+
+/// Output file inside the asset's `preview/` directory.
+const CAPTURE_FILE_NAME: &str = "capture.png";
+
+const CAPTURE_WIDTH: u32 = 1280;
+const CAPTURE_HEIGHT: u32 = 720;
+/// Give up if the page hasn't finished loading by then.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(15);
+/// Time after load for scripts to draw their first frames.
+const SETTLE_DELAY: Duration = Duration::from_millis(750);
+/// The UI kills a capture process that runs longer than this.
+const PROCESS_TIMEOUT: Duration = Duration::from_secs(30);
+
+enum CaptureEvent {
+    Loaded,
+}
+
+fn capture_paths(manifest_path: &Path) -> Result<(PathBuf, PathBuf), String> {
+    let dir = manifest_path.parent().ok_or("Cannot determine manifest directory")?;
+    let index = dir.join("index.html");
+    if !index.is_file() {
+        return Err(format!("Wallpaper has no index.html: {}", index.display()));
+    }
+    Ok((index, dir.join("preview")))
+}
+
+/// Start a capture process for `manifest_path` and return at once. Failures
+/// and timeouts are logged from a watcher thread.
+pub(super) fn spawn_capture(manifest_path: &Path) -> Result<(), String> {
+    capture_paths(manifest_path)?;
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to resolve VEIL executable: {}", e))?;
+    let mut child = Command::new(exe)
+        .arg("--capture-wallpaper-preview")
+        .arg(manifest_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn preview capture: {}", e))?;
+
+    let label = manifest_path.display().to_string();
+    std::thread::Builder::new()
+        .name("veil-preview-capture".into())
+        .spawn(move || {
+            let started = Instant::now();
+            let status = loop {
+                match child.try_wait() {
+                    Ok(Some(status)) => break Some(status),
+                    Ok(None) if started.elapsed() >= PROCESS_TIMEOUT => {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        break None;
+                    }
+                    Ok(None) => std::thread::sleep(Duration::from_millis(200)),
+                    Err(e) => {
+                        warn!("[ui] Preview capture for {} could not be awaited: {}", label, e);
+                        return;
+                    }
+                }
+            };
+            match status {
+                Some(s) if s.success() => info!("[ui] Preview captured for {}", label),
+                Some(s) => {
+                    let mut stderr = String::new();
+                    if let Some(mut pipe) = child.stderr.take() {
+                        let _ = pipe.read_to_string(&mut stderr);
+                    }
+                    warn!("[ui] Preview capture failed for {} ({}): {}", label, s, stderr.trim());
+                }
+                None => warn!("[ui] Preview capture for {} timed out; previous preview kept", label),
+            }
+        })
+        .map_err(|e| format!("Failed to watch preview capture: {}", e))?;
+    Ok(())
+}
+
+/// Body of the `--capture-wallpaper-preview` process. Exits the process when done.
+pub fn run_wallpaper_preview_capture(manifest_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (index, preview_dir) = capture_paths(Path::new(manifest_path))?;
+    let url = file_path_to_url(&index)?;
+    info!("[capture] Rendering {} at {}x{}", index.display(), CAPTURE_WIDTH, CAPTURE_HEIGHT);
+
+    let event_loop = EventLoopBuilder::<CaptureEvent>::with_user_event().build();
+    let proxy = event_loop.create_proxy();
+    // Off-screen rather than hidden: a hidden WebView2 stops rendering.
+    let window = WindowBuilder::new()
+        .with_title("VEIL preview capture")
+        .with_inner_size(PhysicalSize::new(CAPTURE_WIDTH, CAPTURE_HEIGHT))
+        .with_position(PhysicalPosition::new(-32000, -32000))
+        .with_decorations(false)
+        .with_resizable(false)
+        .with_focused(false)
+        .build(&event_loop)
+        .map_err(|e| format!("Failed to create capture window: {}", e))?;
+
+    let webview = WebViewBuilder::new()
+        .with_url(&url)
+        .with_on_page_load_handler(move |event, _| {
+            if let PageLoadEvent::Finished = event {
+                let _ = proxy.send_event(CaptureEvent::Loaded);
+            }
+        })
+        .build(&window)
+        .map_err(|e| format!("Failed to create capture webview: {}", e))?;
+
+    let load_deadline = Instant::now() + LOAD_TIMEOUT;
+    let mut capture_at: Option<Instant> = None;
+
+    event_loop.run(move |event, _, control_flow| {
+        let _keep_alive = &webview;
+        *control_flow = ControlFlow::WaitUntil(capture_at.unwrap_or(load_deadline));
+
+        match event {
+            Event::UserEvent(CaptureEvent::Loaded) if capture_at.is_none() => {
+                let at = Instant::now() + SETTLE_DELAY;
+                capture_at = Some(at);
+                *control_flow = ControlFlow::WaitUntil(at);
+            }
+            Event::NewEvents(StartCause::ResumeTimeReached { .. }) => {
+                let result = match capture_at {
+                    Some(at) if Instant::now() >= at => {
+                        capture_window(&window).and_then(|img| save_capture(&img, &preview_dir))
+                    }
+                    None if Instant::now() >= load_deadline => {
+                        Err(format!("Page did not finish loading within {}s", LOAD_TIMEOUT.as_secs()))
+                    }
+                    _ => return,
+                };
+                match result {
+                    Ok(path) => {
+                        info!("[capture] Wrote {}", path.display());
+                        *control_flow = ControlFlow::Exit;
+                    }
+                    Err(e) => {
+                        error!("[capture] {}", e);
+                        eprintln!("{}", e);
+                        *control_flow = ControlFlow::ExitWithCode(1);
+                    }
+                }
+            }
+            _ => {}
+        }
+    });
+}
+
+/// Write through a temp file so a failed encode never clobbers the old preview.
+fn save_capture(img: &image::RgbaImage, preview_dir: &Path) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(preview_dir).map_err(|e| format!("Failed to create preview dir: {}", e))?;
+    let path = preview_dir.join(CAPTURE_FILE_NAME);
+    let tmp = preview_dir.join(format!("{}.tmp", CAPTURE_FILE_NAME));
+    img.save_with_format(&tmp, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode preview: {}", e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        format!("Failed to replace preview: {}", e)
+    })?;
+    Ok(path)
+}
+
+#[cfg(target_os = "windows")]
+fn capture_window(window: &tao::window::Window) -> Result<image::RgbaImage, String> {
+    use tao::platform::windows::WindowExtWindows;
+    use windows::Win32::{
+        Foundation::HWND,
+        Graphics::Gdi::{
+            CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC, GetDIBits,
+            ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+        },
+        Storage::Xps::{PrintWindow, PRINT_WINDOW_FLAGS},
+    };
+    // Includes DirectComposition content such as the WebView2 surface.
+    const PW_RENDERFULLCONTENT: PRINT_WINDOW_FLAGS = PRINT_WINDOW_FLAGS(2);
+
+    let hwnd = HWND(window.hwnd() as *mut _);
+    let (w, h) = (CAPTURE_WIDTH as i32, CAPTURE_HEIGHT as i32);
+    let mut pixels = vec![0u8; (CAPTURE_WIDTH * CAPTURE_HEIGHT * 4) as usize];
+
+    let (printed, lines) = unsafe {
+        let window_dc = GetDC(Some(hwnd));
+        let mem_dc = CreateCompatibleDC(Some(window_dc));
+        let bitmap = CreateCompatibleBitmap(window_dc, w, h);
+        let previous = SelectObject(mem_dc, bitmap.into());
+
+        let printed = PrintWindow(hwnd, mem_dc, PW_RENDERFULLCONTENT).as_bool();
+        SelectObject(mem_dc, previous);
+
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: w,
+                biHeight: -h, // top-down rows
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let lines = GetDIBits(
+            mem_dc,
+            bitmap,
+            0,
+            CAPTURE_HEIGHT,
+            Some(pixels.as_mut_ptr() as *mut _),
+            &mut info,
+            DIB_RGB_COLORS,
+        );
+
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(mem_dc);
+        ReleaseDC(Some(hwnd), window_dc);
+        (printed, lines)
+    };
+
+    if !printed || lines != h {
+        return Err("PrintWindow could not capture the wallpaper window".into());
+    }
+    if pixels.iter().all(|&b| b == 0) {
+        return Err("Captured a blank frame".into());
+    }
+
+    // GDI hands back BGRX; PNG wants opaque RGBA.
+    for px in pixels.chunks_exact_mut(4) {
+        px.swap(0, 2);
+        px[3] = 255;
+    }
+    image::RgbaImage::from_raw(CAPTURE_WIDTH, CAPTURE_HEIGHT, pixels)
+        .ok_or_else(|| "Capture buffer has the wrong size".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn capture_window(_window: &tao::window::Window) -> Result<image::RgbaImage, String> {
+    Err("Wallpaper preview capture is only supported on Windows".into())
+}
@@ -0,0 +1,137 @@
+// ~/veil/veil-backend/src/config_ui/schedule.rs
+//
+// Time-of-day wallpaper schedules. A profile section with a `schedule` list
+// gets its `wallpaper_id` rewritten whenever a different entry starts to
+// match the local time, and the wallpaper addon applies it through its config
+// watcher. Only changes of the matching entry are written, so a wallpaper
+// picked by hand stays until the next window begins.
+//
+// When the clock falls back for DST, the repeated hour is held on the entry
+// that was already active instead of switching back and forth.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json::Value as JsonValue;
+use serde_yaml::Value;
+
+use super::playlist::write_active_wallpapers;
+use super::{discover_addon_configs, is_wallpaper_addon, parse_wallpaper_profiles};
+use crate::ipc::registry::global_registry;
+use crate::{error, info, warn};
+
+const TICK: Duration = Duration::from_secs(20);
+/// A registry `time` entry older than this is ignored (fast tier paused or idle).
+const MAX_TIME_AGE_SECS: i64 = 120;
+
+struct LocalClock {
+    /// Minutes since local midnight.
+    minute: u32,
+    /// Seconds since the Unix epoch, shifted by the UTC offset.
+    local_unix: i64,
+}
+
+/// Local time from the `time` sysdata: the registry entry when it is fresh,
+/// otherwise a direct sample.
+fn local_clock() -> Option<LocalClock> {
+    let cached = global_registry()
+        .read()
+        .unwrap()
+        .sysdata
+        .iter()
+        .find(|e| e.category == "time")
+        .map(|e| e.metadata.clone());
+    let now = chrono::Utc::now().timestamp();
+    let fresh = |t: &JsonValue| t.get("unix").and_then(|v| v.as_i64()).is_some_and(|u| (now - u).abs() <= MAX_TIME_AGE_SECS);
+    let time = cached
+        .filter(fresh)
+        .unwrap_or_else(crate::ipc::sysdata::time::get_time_json);
+
+    let field = |key: &str| time.get(key).and_then(|v| v.as_i64());
+    let (hour, minute, unix) = (field("hour")?, field("minute")?, field("unix")?);
+    Some(LocalClock {
+        minute: (hour * 60 + minute) as u32,
+        local_unix: unix + field("utc_offset_seconds").unwrap_or(0),
+    })
+}
+
+/// Spawn the daemon thread that applies scheduled wallpapers.
+pub fn start_wallpaper_scheduler() {
+    let spawned = std::thread::Builder::new()
+        .name("veil-wallpaper-schedule".into())
+        .spawn(|| {
+            let mut scheduler = Scheduler::default();
+            loop {
+                scheduler.tick();
+                std::thread::sleep(TICK);
+            }
+        });
+    if let Err(e) = spawned {
+        error!("[schedule] Failed to start wallpaper scheduler: {}", e);
+    }
+}
+
+#[derive(Default)]
+struct Scheduler {
+    /// Index of the entry last applied, keyed by `<addon id>/<section>`.
+    active: HashMap<String, usize>,
+    /// Latest local time seen; a smaller value means the clock fell back.
+    latest_local_unix: i64,
+}
+
+impl Scheduler {
+    fn tick(&mut self) {
+        let Some(clock) = local_clock() else {
+            return;
+        };
+        if clock.local_unix < self.latest_local_unix {
+            // Inside a repeated hour; keep what is showing until it has passed.
+            return;
+        }
+        self.latest_local_unix = clock.local_unix;
+
+        for addon in discover_addon_configs().iter().filter(|a| is_wallpaper_addon(a)) {
+            let Some(root) = std::fs::read_to_string(&addon.config_path)
+                .ok()
+                .and_then(|text| serde_yaml::from_str::<Value>(&text).ok())
+            else {
+                continue;
+            };
+
+            let mut switches = Vec::<(String, String)>::new();
+            for profile in parse_wallpaper_profiles(&root) {
+                if !profile.enabled || profile.schedule.is_empty() {
+                    continue;
+                }
+                let key = format!("{}/{}", addon.id, profile.section);
+                // Overlapping windows: the first listed wins.
+                let Some(idx) = profile.schedule.iter().position(|e| e.contains(clock.minute)) else {
+                    self.active.remove(&key);
+                    continue;
+                };
+                let entry = &profile.schedule[idx];
+                let first_seen = !self.active.contains_key(&key);
+                if self.active.insert(key, idx) == Some(idx) {
+                    continue;
+                }
+                // On startup only write when the config disagrees.
+                if first_seen && profile.wallpaper_id == entry.wallpaper_id {
+                    continue;
+                }
+                switches.push((profile.section.clone(), entry.wallpaper_id.clone()));
+            }
+
+            if switches.is_empty() {
+                continue;
+            }
+            match write_active_wallpapers(&addon.config_path, &switches) {
+                Ok(()) => {
+                    for (section, wallpaper_id) in &switches {
+                        info!("[schedule] {} {} -> '{}'", addon.id, section, wallpaper_id);
+                    }
+                }
+                Err(e) => warn!("[schedule] Failed to apply scheduled wallpapers for '{}': {}", addon.id, e),
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// ~/veil/veil-backend/src/config_ui/search.rs
+//
+// Ctrl+K command palette: one index over addons, assets, schema settings and
+// metric paths, queried with a small fuzzy matcher.
+
+use serde_json::Value as JsonValue;
+
+use super::{discover_assets_for_meta, load_schema, AddonMeta, SchemaSection};
+use crate::ipc::metrics::metric_info;
+use crate::ipc::snapshot::read_registry_snapshot;
+
+/// Results shown in the palette.
+const MAX_RESULTS: usize = 12;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum SearchKind {
+    Addon,
+    Asset,
+    Setting,
+    Metric,
+}
+
+impl SearchKind {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            SearchKind::Addon => "Addon",
+            SearchKind::Asset => "Asset",
+            SearchKind::Setting => "Setting",
+            SearchKind::Metric => "Metric",
+        }
+    }
+}
+
+/// Where a result leads when chosen.
+#[derive(Clone)]
+pub(super) enum SearchTarget {
+    Addon { addon_idx: usize },
+    Asset { addon_idx: usize, asset_id: String },
+    Setting { addon_idx: usize, path: String },
+    Metric { path: String },
+}
+
+#[derive(Clone)]
+pub(super) struct SearchEntry {
+    pub(super) kind: SearchKind,
+    pub(super) title: String,
+    /// Secondary line: owning addon, tags or full path.
+    pub(super) detail: String,
+    /// Lower-cased text the matcher runs against.
+    haystack: String,
+    pub(super) target: SearchTarget,
+}
+
+impl SearchEntry {
+    fn new(kind: SearchKind, title: String, detail: String, extra: &str, target: SearchTarget) -> Self {
+        let haystack = format!("{} {} {}", title, detail, extra).to_lowercase();
+        Self { kind, title, detail, haystack, target }
+    }
+}
+
+pub(super) struct SearchIndex {
+    entries: Vec<SearchEntry>,
+}
+
+impl SearchIndex {
+    /// Build from the addon catalog, each addon's schema and assets, and the
+    /// metric paths in the last registry snapshot.
+    pub(super) fn build(addons: &[AddonMeta]) -> Self {
+        let mut entries = Vec::new();
+
+        for (addon_idx, addon) in addons.iter().enumerate() {
+            entries.push(SearchEntry::new(
+                SearchKind::Addon,
+                addon.name.clone(),
+                addon.id.clone(),
+                &addon.package,
+                SearchTarget::Addon { addon_idx },
+            ));
+
+            let schema = load_schema(&addon.schema_path);
+            if let Some(schema) = &schema {
+                for section in &schema.ui.sections {
+                    collect_settings(section, addon_idx, &addon.name, &mut entries);
+                }
+            }
+
+            for asset in discover_assets_for_meta(addon, schema.as_ref()) {
+                let tags = asset.tags.join(" ");
+                entries.push(SearchEntry::new(
+                    SearchKind::Asset,
+                    asset.name.clone(),
+                    format!("{} · {}", addon.name, asset.id),
+                    &tags,
+                    SearchTarget::Asset { addon_idx, asset_id: asset.id },
+                ));
+            }
+        }
+
+        for path in metric_paths() {
+            let (title, extra) = match metric_info(&path) {
+                Some(info) => (info.label.to_string(), info.category),
+                None => (path.clone(), ""),
+            };
+            entries.push(SearchEntry::new(
+                SearchKind::Metric,
+                title,
+                path.clone(),
+                extra,
+                SearchTarget::Metric { path },
+            ));
+        }
+
+        Self { entries }
+    }
+
+    /// Best matches for `query`, highest score first. An empty query lists addons.
+    pub(super) fn search(&self, query: &str) -> Vec<&SearchEntry> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return self
+                .entries
+                .iter()
+                .filter(|e| e.kind == SearchKind::Addon)
+                .take(MAX_RESULTS)
+                .collect();
+        }
+
+        let mut scored: Vec<(i32, &SearchEntry)> = self
+            .entries
+            .iter()
+            .filter_map(|e| fuzzy_score(&query, &e.haystack).map(|s| (s, e)))
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.title.len().cmp(&b.1.title.len())));
+        scored.into_iter().take(MAX_RESULTS).map(|(_, e)| e).collect()
+    }
+}
+
+fn collect_settings(section: &SchemaSection, addon_idx: usize, addon_name: &str, out: &mut Vec<SearchEntry>) {
+    for field in &section.fields {
+        let label = field.label.clone().unwrap_or_else(|| field.path.clone());
+        out.push(SearchEntry::new(
+            SearchKind::Setting,
+            label,
+            format!("{} · {}", addon_name, section.title),
+            &field.path,
+            SearchTarget::Setting { addon_idx, path: field.path.clone() },
+        ));
+    }
+    for nested in &section.sections {
+        collect_settings(nested, addon_idx, addon_name, out);
+    }
+}
+
+/// Dotted leaf paths under `sysdata` in `registry.json`, e.g. `sysdata.cpu.usage`.
+/// Arrays are indexed by their first element only to keep the list short.
+fn metric_paths() -> Vec<String> {
+    fn walk(value: &JsonValue, prefix: &str, out: &mut Vec<String>) {
+        match value {
+            JsonValue::Object(map) => {
+                for (key, child) in map {
+                    walk(child, &format!("{}.{}", prefix, key), out);
+                }
+            }
+            JsonValue::Array(items) => {
+                if let Some(first) = items.first() {
+                    walk(first, &format!("{}[0]", prefix), out);
+                }
+            }
+            _ => out.push(prefix.to_string()),
+        }
+    }
+
+    let mut out = Vec::new();
+    let snapshot = read_registry_snapshot();
+    if let Some(sysdata) = snapshot.as_ref().and_then(|v| v.get("sysdata")) {
+        walk(sysdata, "sysdata", &mut out);
+    }
+    out
+}
+
+/// Subsequence match: every query char must appear in order. Consecutive
+/// runs and matches at word starts score higher; `None` means no match.
+fn fuzzy_score(query: &str, haystack: &str) -> Option<i32> {
+    if haystack.contains(query) {
+        return Some(1000 - haystack.find(query).unwrap_or(0) as i32);
+    }
+
+    let hay: Vec<char> = haystack.chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+    for qc in query.chars().filter(|c| !c.is_whitespace()) {
+        let found = hay[pos..].iter().position(|&c| c == qc)? + pos;
+        score += 1;
+        if prev_match.map(|p| p + 1) == Some(found) {
+            score += 5;
+        }
+        if found == 0 || matches!(hay[found - 1], ' ' | '.' | '_' | '-') {
+            score += 3;
+        }
+        prev_match = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
@@ -0,0 +1,70 @@
+// ~/veil/veil-backend/src/config_ui/ui_state.rs
+//
+// Where the UI was left, restored on the next launch unless the launch names
+// an addon to focus. The native UI and the WebView shell keep separate
+// entries in the same file; each save re-reads it so neither overwrites the
+// other's part.
+
+use serde::{Deserialize, Serialize};
+
+use crate::paths::ui_state_path;
+use crate::warn;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NativeUiState {
+    /// `home`, `addons`, `integrations` or `settings`.
+    #[serde(default)]
+    pub section: Option<String>,
+    /// Selected addon by id, so a removed addon falls back to the first one.
+    #[serde(default)]
+    pub addon_id: Option<String>,
+    #[serde(default)]
+    pub hub_tab: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShellUiState {
+    /// The shell's `viewMode`: `addon`, `home`, `settings`, `data`, ...
+    #[serde(default)]
+    pub view_mode: Option<String>,
+    #[serde(default)]
+    pub addon_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UiState {
+    #[serde(default)]
+    pub native: NativeUiState,
+    #[serde(default)]
+    pub shell: ShellUiState,
+}
+
+impl UiState {
+    pub fn load() -> Self {
+        std::fs::read_to_string(ui_state_path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let written = serde_json::to_string_pretty(self)
+            .map_err(|e| e.to_string())
+            .and_then(|text| std::fs::write(ui_state_path(), text).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            warn!("[ui] Failed to save UI state: {}", e);
+        }
+    }
+}
+
+pub fn save_native(state: &NativeUiState) {
+    let mut all = UiState::load();
+    all.native = state.clone();
+    all.save();
+}
+
+pub fn save_shell(state: &ShellUiState) {
+    let mut all = UiState::load();
+    all.shell = state.clone();
+    all.save();
+}
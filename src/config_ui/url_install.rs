@@ -0,0 +1,69 @@
+// ~/veil/veil-backend/src/config_ui/url_install.rs
+//
+// "Install from URL" row on the Discover tab. The download runs on a worker
+// thread; `poll` hands the result back so the UI can report it and rescan.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use crate::ipc::assets::{install_from_url, UrlInstall};
+use crate::warn;
+
+#[derive(Default)]
+pub(super) struct UrlInstaller {
+    url: String,
+    pending: Option<Receiver<Result<UrlInstall, String>>>,
+}
+
+impl UrlInstaller {
+    pub fn busy(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    fn start(&mut self, category: &str) {
+        let (tx, rx) = mpsc::channel();
+        let url = self.url.trim().to_string();
+        let category = category.to_string();
+        let spawned = std::thread::Builder::new()
+            .name("asset-download".into())
+            .spawn(move || {
+                let _ = tx.send(install_from_url(&url, &category));
+            });
+        match spawned {
+            Ok(_) => self.pending = Some(rx),
+            Err(e) => warn!("[assets] Failed to start download: {}", e),
+        }
+    }
+
+    /// Collect a finished download, if any. Clears the URL on success.
+    pub fn poll(&mut self) -> Option<Result<UrlInstall, String>> {
+        let result = match self.pending.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err("Download stopped unexpectedly".to_string()),
+        };
+        self.pending = None;
+        if result.is_ok() {
+            self.url.clear();
+        }
+        Some(result)
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, category: &str) {
+        ui.horizontal(|ui| {
+            let input = ui.add_enabled(
+                !self.busy(),
+                egui::TextEdit::singleline(&mut self.url)
+                    .hint_text("https://…/wallpaper.zip")
+                    .desired_width(360.0),
+            );
+            let enter = input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let ready = !self.busy() && !self.url.trim().is_empty();
+            if (ui.add_enabled(ready, egui::Button::new("Install from URL")).clicked() || enter) && ready {
+                self.start(category);
+            }
+            if self.busy() {
+                ui.spinner();
+            }
+        });
+    }
+}
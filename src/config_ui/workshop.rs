@@ -0,0 +1,163 @@
+// ~/veil/veil-backend/src/config_ui/workshop.rs
+//
+// "Browse Workshop" panel on the Discover tab, for addons whose addon.json
+// sets `assets.workshop_app_id`. Listing and installing run on a worker
+// thread; `poll` hands the result back to the UI, which reports it through
+// the status bar and rescans the addon's assets after an install.
+
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use egui::{Color32, RichText};
+
+use crate::ipc::assets::{AssetProvider, RemoteAsset, SteamWorkshop};
+use crate::warn;
+
+enum Reply {
+    Listed(Result<Vec<RemoteAsset>, String>),
+    Installed(String, Result<PathBuf, String>),
+}
+
+/// Outcome of a finished job, for the status bar.
+pub(super) struct JobDone {
+    pub status: String,
+    /// An item was installed, so the addon's asset list is stale.
+    pub installed: bool,
+}
+
+#[derive(Default)]
+pub(super) struct WorkshopBrowser {
+    pub open: bool,
+    query: String,
+    items: Vec<RemoteAsset>,
+    /// `(app id, category)` the items were listed for.
+    source: Option<(u32, String)>,
+    pending: Option<Receiver<Reply>>,
+}
+
+impl WorkshopBrowser {
+    pub fn busy(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    fn run(&mut self, app_id: u32, category: &str, job: impl FnOnce(SteamWorkshop) -> Reply + Send + 'static) {
+        let (tx, rx) = mpsc::channel();
+        let provider = SteamWorkshop::new(app_id, category);
+        let spawned = std::thread::Builder::new()
+            .name("workshop".into())
+            .spawn(move || {
+                let _ = tx.send(job(provider));
+            });
+        match spawned {
+            Ok(_) => self.pending = Some(rx),
+            Err(e) => warn!("[workshop] Failed to start worker: {}", e),
+        }
+    }
+
+    /// List subscribed items for `app_id`, replacing the current list.
+    pub fn refresh(&mut self, app_id: u32, category: &str) {
+        if self.busy() {
+            return;
+        }
+        self.source = Some((app_id, category.to_string()));
+        let query = self.query.clone();
+        self.run(app_id, category, move |p| Reply::Listed(p.search(&query)));
+    }
+
+    fn install(&mut self, app_id: u32, category: &str, id: String) {
+        if self.busy() {
+            return;
+        }
+        self.run(app_id, category, move |p| {
+            let result = p.install(&id);
+            Reply::Installed(id, result)
+        });
+    }
+
+    /// Collect a finished job, if any.
+    pub fn poll(&mut self) -> Option<JobDone> {
+        let reply = match self.pending.as_ref()?.try_recv() {
+            Ok(reply) => reply,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => {
+                self.pending = None;
+                return Some(JobDone { status: "Workshop job stopped unexpectedly".to_string(), installed: false });
+            }
+        };
+        self.pending = None;
+        Some(match reply {
+            Reply::Listed(Ok(items)) => {
+                let status = format!("Workshop: {} subscribed item(s)", items.len());
+                self.items = items;
+                JobDone { status, installed: false }
+            }
+            Reply::Listed(Err(e)) => {
+                self.items.clear();
+                JobDone { status: format!("Workshop: {}", e), installed: false }
+            }
+            Reply::Installed(id, Ok(path)) => {
+                if let Some(item) = self.items.iter_mut().find(|i| i.id == id) {
+                    item.installed = true;
+                }
+                JobDone { status: format!("Installed Workshop item {} to {}", id, path.display()), installed: true }
+            }
+            Reply::Installed(id, Err(e)) => {
+                JobDone { status: format!("Workshop item {} failed: {}", id, e), installed: false }
+            }
+        })
+    }
+
+    /// The panel body. The list is refreshed when the addon's app id or
+    /// category differs from the one it was built for.
+    pub fn show(&mut self, ui: &mut egui::Ui, app_id: u32, category: &str) {
+        if self.source.as_ref().map(|(a, c)| (*a, c.as_str())) != Some((app_id, category)) {
+            self.items.clear();
+            self.refresh(app_id, category);
+        }
+
+        ui.horizontal(|ui| {
+            let search = ui.add(egui::TextEdit::singleline(&mut self.query).hint_text("Search subscribed items"));
+            let enter = search.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.add_enabled(!self.busy(), egui::Button::new("Refresh")).clicked() || enter {
+                self.refresh(app_id, category);
+            }
+            if self.busy() {
+                ui.spinner();
+            }
+        });
+        ui.label(
+            RichText::new(format!("Items you subscribe to in Steam (app {}) install into Assets/{}.", app_id, category))
+                .small()
+                .color(Color32::GRAY),
+        );
+        ui.add_space(4.0);
+
+        if self.items.is_empty() && !self.busy() {
+            ui.label("No downloaded Workshop items found.");
+            return;
+        }
+
+        let mut install = None;
+        egui::ScrollArea::vertical().id_salt("workshop_items").max_height(260.0).show(ui, |ui| {
+            for item in &self.items {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&item.title).strong());
+                    ui.label(RichText::new(&item.id).small().color(Color32::GRAY));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let label = if item.installed { "Reinstall" } else { "Install" };
+                        if ui.add_enabled(!self.busy(), egui::Button::new(label)).clicked() {
+                            install = Some(item.id.clone());
+                        }
+                    });
+                });
+                if let Some(desc) = &item.description {
+                    ui.label(RichText::new(desc).small());
+                }
+                ui.separator();
+            }
+        });
+        if let Some(id) = install {
+            self.install(app_id, category, id);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// ~/veil/veil-backend/src/config_ui/z_index.rs
+//
+// The layer a wallpaper profile's window is placed on, from `z_index` in the
+// wallpaper addon's config.yaml. Z_INDEXES is the one list of accepted values:
+// the save paths validate against it and `schema.yaml` dropdowns for a
+// `z_index` field are filled from it, so the two can't disagree.
+//
+//   desktop       Above the desktop icons, below every window (default).
+//   behind_icons  In the WorkerW behind the desktop icons.
+//   overlay       Topmost, above other windows.
+//
+// Older configs used other names for the same layers; `normalize` maps them
+// onto this set.
+
+use serde_yaml::{Mapping, Value};
+
+pub const Z_INDEXES: &[&str] = &["desktop", "behind_icons", "overlay"];
+pub const DEFAULT_Z_INDEX: &str = "desktop";
+
+/// Canonical name for `raw`, accepting any case, `-` or spaces for `_`, and
+/// the legacy names. Unknown values are an error listing the accepted set.
+pub fn normalize(raw: &str) -> Result<&'static str, String> {
+    let key = raw.trim().to_ascii_lowercase().replace(['-', ' '], "_");
+    if let Some(known) = Z_INDEXES.iter().find(|z| **z == key) {
+        return Ok(*known);
+    }
+    match key.as_str() {
+        "normal" | "default" | "over_icons" => Ok("desktop"),
+        "workerw" | "background" | "bottom" | "wallpaper" => Ok("behind_icons"),
+        "top" | "topmost" | "above" => Ok("overlay"),
+        _ => Err(format!("Unknown z_index '{}'; expected one of: {}", raw.trim(), Z_INDEXES.join(", "))),
+    }
+}
+
+/// Rewrite a legacy `z_index` in a profile section to its canonical name.
+/// Unknown values are left for the addon to report.
+pub fn normalize_section(section: &mut Mapping) {
+    let key = Value::String("z_index".to_string());
+    let canonical = section.get(&key).and_then(|v| v.as_str()).and_then(|z| normalize(z).ok());
+    if let Some(canonical) = canonical {
+        section.insert(key, Value::String(canonical.to_string()));
+    }
+}
+
+/// Whether dotted `path` in the wallpaper config is a profile's `z_index`.
+pub fn is_z_index_path(path: &str) -> bool {
+    path.rsplit('.').next() == Some("z_index")
+}
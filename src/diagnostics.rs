@@ -0,0 +1,182 @@
+// ~/veil/veil-backend/src/diagnostics.rs
+//
+// `VEIL diag`: one zip to attach to a bug report, with the backend and UI
+// logs, the backend `config.yaml`, `tray_settings.json`, every addon's
+// `config.yaml` and a sysdata snapshot. Values under keys that look sensitive
+// (REDACTED_KEYS) are replaced in everything but the logs.
+//
+// Each part is collected on its own. A backend that doesn't answer or a file
+// that can't be read leaves a note in `diagnostics.json` instead of failing
+// the whole bundle.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::config_archive::{add_bytes, addon_folders};
+use crate::ipc::request::{send_ipc_request, IpcRequest};
+use crate::paths::{
+    addons_dir, backend_log_path, config_yaml_path, downloads_dir, tray_settings_json_path, ui_log_path,
+    veil_root_dir,
+};
+
+/// Substrings of key names (compared lowercase) whose values are redacted.
+const REDACTED_KEYS: &[&str] = &[
+    "serial", "token", "password", "passwd", "secret", "api_key", "apikey", "credential", "private_key",
+];
+const REDACTED: &str = "[redacted]";
+
+pub struct DiagSummary {
+    pub path: PathBuf,
+    pub included: Vec<String>,
+    /// Parts that are missing or came from a fallback, with the reason.
+    pub notes: Vec<String>,
+}
+
+struct Bundle {
+    zip: ZipWriter<File>,
+    options: SimpleFileOptions,
+    included: Vec<String>,
+    notes: Vec<String>,
+}
+
+impl Bundle {
+    /// Add `part` as `name`, or note why it couldn't be collected. Only a
+    /// failure to write the zip itself is an error.
+    fn add(&mut self, name: &str, part: Result<Vec<u8>, String>) -> Result<(), String> {
+        match part {
+            Ok(bytes) => {
+                add_bytes(&mut self.zip, name, &bytes, self.options)?;
+                self.included.push(name.to_string());
+            }
+            Err(e) => self.notes.push(format!("{}: {}", name, e)),
+        }
+        Ok(())
+    }
+}
+
+/// Write the bundle to `out_dir`, by default the user's Downloads folder
+/// (or the VEIL root when there is none).
+pub fn create(out_dir: Option<&Path>) -> Result<DiagSummary, String> {
+    let dir = match out_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => downloads_dir().filter(|d| d.is_dir()).unwrap_or_else(veil_root_dir),
+    };
+    let now = chrono::Local::now();
+    let path = dir.join(format!("VEIL-diagnostics-{}.zip", now.format("%Y%m%d-%H%M%S")));
+    let file = File::create(&path).map_err(|e| format!("Failed to create '{}': {}", path.display(), e))?;
+    let mut bundle = Bundle {
+        zip: ZipWriter::new(file),
+        options: SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+        included: Vec::new(),
+        notes: Vec::new(),
+    };
+
+    bundle.add("logs/veil.log", read(&backend_log_path()))?;
+    bundle.add("logs/veil-ui.log", read(&ui_log_path()))?;
+    bundle.add("config.yaml", read(&config_yaml_path()).and_then(|b| redact_yaml(&b)))?;
+    bundle.add("tray_settings.json", read(&tray_settings_json_path()).and_then(|b| redact_json(&b)))?;
+    for folder in addon_folders() {
+        let source = addons_dir().join(&folder).join("config.yaml");
+        if source.is_file() {
+            bundle.add(&format!("Addons/{}/config.yaml", folder), read(&source).and_then(|b| redact_yaml(&b)))?;
+        }
+    }
+
+    let (sysdata, source) = match sysdata_snapshot() {
+        Ok((mut value, source, fallback_reason)) => {
+            if let Some(reason) = fallback_reason {
+                bundle.notes.push(format!("sysdata.json: from the registry snapshot on disk; {}", reason));
+            }
+            redact(&mut value);
+            (serde_json::to_vec_pretty(&value).map_err(|e| e.to_string()), source)
+        }
+        Err(e) => (Err(e), "none"),
+    };
+    bundle.add("sysdata.json", sysdata)?;
+
+    let info = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "created": now.to_rfc3339(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "veil_root": veil_root_dir().display().to_string(),
+        "sysdata_source": source,
+        "included": bundle.included,
+        "notes": bundle.notes,
+    });
+    let info = serde_json::to_vec_pretty(&info).map_err(|e| e.to_string())?;
+    add_bytes(&mut bundle.zip, "diagnostics.json", &info, bundle.options)?;
+
+    bundle.zip.finish().map_err(|e| format!("Failed to finish '{}': {}", path.display(), e))?;
+    Ok(DiagSummary { path, included: bundle.included, notes: bundle.notes })
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("'{}' not found", path.display()),
+        _ => format!("failed to read '{}': {}", path.display(), e),
+    })
+}
+
+/// Sysdata from the running backend, else from the last registry snapshot
+/// it wrote. Returns the value, where it came from and, for the snapshot,
+/// why the backend wasn't used.
+fn sysdata_snapshot() -> Result<(Value, &'static str, Option<String>), String> {
+    let req = IpcRequest {
+        ns: "registry".to_string(),
+        cmd: "list_sysdata".to_string(),
+        args: None,
+        addon_id: None,
+        token: None,
+    };
+    let problem = match send_ipc_request(req) {
+        Ok(resp) if resp.ok => return Ok((resp.data.unwrap_or(Value::Null), "backend", None)),
+        Ok(resp) => resp.error.unwrap_or_else(|| "request failed".to_string()),
+        Err(e) => e,
+    };
+    let reason = format!("the backend didn't answer ({})", problem);
+    match crate::ipc::snapshot::read_registry_snapshot().and_then(|s| s.get("sysdata").cloned()) {
+        Some(sysdata) => Ok((sysdata, "registry snapshot", Some(reason))),
+        None => Err(format!("{} and there is no registry snapshot on disk", reason)),
+    }
+}
+
+/// Replace the value under every sensitive key, at any depth. Nulls stay,
+/// so the bundle still shows which of them were unset.
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if REDACTED_KEYS.iter().any(|k| key.contains(k)) {
+                    if !v.is_null() {
+                        *v = Value::String(REDACTED.to_string());
+                    }
+                } else {
+                    redact(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// A file that can't be parsed is left out rather than copied unredacted.
+fn redact_yaml(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let mut value: Value =
+        serde_yaml::from_slice(bytes).map_err(|e| format!("left out, not parseable for redaction: {}", e))?;
+    redact(&mut value);
+    serde_yaml::to_string(&value).map(String::into_bytes).map_err(|e| e.to_string())
+}
+
+fn redact_json(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let mut value: Value =
+        serde_json::from_slice(bytes).map_err(|e| format!("left out, not parseable for redaction: {}", e))?;
+    redact(&mut value);
+    serde_json::to_vec_pretty(&value).map_err(|e| e.to_string())
+}
@@ -0,0 +1,188 @@
+// ~/veil/veil-backend/src/hotkey.rs
+//
+// Global hotkey that opens or focuses the VEIL UI, read from `ui_hotkey` in
+// config.yaml ("Ctrl+Alt+V" by default, empty to disable). The daemon owns
+// it on a dedicated thread, because WM_HOTKEY is delivered to the message
+// queue of the thread that registered the key.
+
+pub const MOD_ALT: u32 = 0x1;
+pub const MOD_CONTROL: u32 = 0x2;
+pub const MOD_SHIFT: u32 = 0x4;
+pub const MOD_WIN: u32 = 0x8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub struct Hotkey {
+    pub modifiers: u32,
+    /// Windows virtual-key code.
+    pub vk: u32,
+}
+
+/// `"Ctrl+Alt+V"` → modifiers and key. Case and spaces don't matter.
+/// `Ok(None)` for an empty string, which disables the hotkey.
+pub fn parse(text: &str) -> Result<Option<Hotkey>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    let mut modifiers = 0;
+    let mut vk = None;
+    for part in text.split('+').map(str::trim) {
+        let modifier = match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => Some(MOD_CONTROL),
+            "alt" => Some(MOD_ALT),
+            "shift" => Some(MOD_SHIFT),
+            "win" | "super" | "meta" => Some(MOD_WIN),
+            _ => None,
+        };
+        if let Some(m) = modifier {
+            modifiers |= m;
+            continue;
+        }
+        if vk.is_some() {
+            return Err(format!("more than one key in '{}'", text));
+        }
+        vk = Some(key_code(part).ok_or_else(|| format!("unknown key '{}'", part))?);
+    }
+
+    let vk = vk.ok_or_else(|| format!("'{}' has no key, only modifiers", text))?;
+    if modifiers == 0 {
+        return Err(format!("'{}' needs at least one of Ctrl, Alt, Shift or Win", text));
+    }
+    Ok(Some(Hotkey { modifiers, vk }))
+}
+
+/// Virtual-key code for a key name: letters, digits, F1–F24 and a few
+/// named keys.
+fn key_code(name: &str) -> Option<u32> {
+    let upper = name.to_ascii_uppercase();
+    let mut chars = upper.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphanumeric() {
+            return Some(c as u32);
+        }
+    }
+    if let Some(n) = upper.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()) {
+        return (1..=24).contains(&n).then(|| 0x6F + n);
+    }
+    Some(match upper.as_str() {
+        "SPACE" => 0x20,
+        "PAGEUP" | "PGUP" => 0x21,
+        "PAGEDOWN" | "PGDN" => 0x22,
+        "END" => 0x23,
+        "HOME" => 0x24,
+        "LEFT" => 0x25,
+        "UP" => 0x26,
+        "RIGHT" => 0x27,
+        "DOWN" => 0x28,
+        "INSERT" | "INS" => 0x2D,
+        "DELETE" | "DEL" => 0x2E,
+        "PAUSE" => 0x13,
+        _ => return None,
+    })
+}
+
+#[cfg(target_os = "windows")]
+mod win {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_NOREPEAT};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetMessageW, PeekMessageW, PostThreadMessageW, MSG, PM_NOREMOVE, WM_APP, WM_HOTKEY,
+    };
+
+    use crate::{info, warn};
+
+    const HOTKEY_ID: i32 = 1;
+    /// Posted to the hotkey thread when `ui_hotkey` changes.
+    const WM_RELOAD_HOTKEY: u32 = WM_APP + 1;
+
+    /// Id of the hotkey thread, 0 until it has a message queue.
+    static HOTKEY_THREAD: AtomicU32 = AtomicU32::new(0);
+
+    /// Register `ui_hotkey` and serve it until the process exits.
+    pub fn start() {
+        let spawned = std::thread::Builder::new()
+            .name("veil-hotkey".into())
+            .spawn(|| unsafe {
+                // Create the message queue before publishing the thread id,
+                // so a reload posted right away isn't lost.
+                let mut msg = MSG::default();
+                let _ = PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE);
+                HOTKEY_THREAD.store(GetCurrentThreadId(), Ordering::Relaxed);
+
+                let mut registered = register(&crate::config::ui_hotkey());
+                while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                    match msg.message {
+                        WM_HOTKEY if msg.wParam.0 == HOTKEY_ID as usize => {
+                            match crate::ipc::dispatch::open_or_focus_ui() {
+                                Ok(status) => info!("[hotkey] UI {}", status),
+                                Err(e) => warn!("[hotkey] Failed to open the UI: {}", e),
+                            }
+                        }
+                        WM_RELOAD_HOTKEY => {
+                            if registered {
+                                let _ = UnregisterHotKey(None, HOTKEY_ID);
+                            }
+                            registered = register(&crate::config::ui_hotkey());
+                        }
+                        _ => {}
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            warn!("[hotkey] Failed to start hotkey thread: {}", e);
+        }
+    }
+
+    /// Register `text` on the calling thread. A bad or already taken hotkey
+    /// is logged and left unregistered.
+    unsafe fn register(text: &str) -> bool {
+        let hotkey = match super::parse(text) {
+            Ok(Some(hotkey)) => hotkey,
+            Ok(None) => {
+                info!("[hotkey] UI hotkey disabled");
+                return false;
+            }
+            Err(e) => {
+                warn!("[hotkey] Ignoring ui_hotkey: {}", e);
+                return false;
+            }
+        };
+        match RegisterHotKey(None, HOTKEY_ID, HOT_KEY_MODIFIERS(hotkey.modifiers) | MOD_NOREPEAT, hotkey.vk) {
+            Ok(()) => {
+                info!("[hotkey] {} opens the UI", text.trim());
+                true
+            }
+            Err(e) => {
+                warn!("[hotkey] Couldn't register {}; another program may already use it: {}", text.trim(), e);
+                false
+            }
+        }
+    }
+
+    /// Re-register after `ui_hotkey` changed. Does nothing in processes that
+    /// don't own the hotkey.
+    pub fn reload() {
+        let thread = HOTKEY_THREAD.load(Ordering::Relaxed);
+        if thread != 0 {
+            unsafe {
+                let _ = PostThreadMessageW(thread, WM_RELOAD_HOTKEY, WPARAM(0), LPARAM(0));
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub use win::{reload, start};
+
+#[cfg(not(target_os = "windows"))]
+pub fn start() {
+    crate::info!("[hotkey] Global hotkeys are only supported on Windows");
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn reload() {}
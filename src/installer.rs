@@ -165,35 +165,19 @@ pub type LogFn = fn(LogLevel, &str);
 // Path resolution
 // ---------------------------------------------------------------------------
 
-/// Resolve the user's home directory (Windows-first).
-pub fn user_home_dir() -> Option<PathBuf> {
-    if let Ok(profile) = std::env::var("USERPROFILE") {
-        return Some(PathBuf::from(profile));
-    }
-    let drive = std::env::var("HOMEDRIVE").ok();
-    let path = std::env::var("HOMEPATH").ok();
-    match (drive, path) {
-        (Some(d), Some(p)) => Some(PathBuf::from(format!("{d}{p}"))),
-        _ => None,
-    }
-}
-
 /// Resolve the canonical app root.
 ///
 /// For VEIL:
-/// - Core root: `~/VEIL/Core/`
+/// - Core root: `paths::veil_root_dir` (`~/VEIL/Core/`, or `$VEIL_HOME` when set)
 /// - Addons root: `~/VEIL/Core/Addons/`
 ///
 /// For non-VEIL apps we keep the legacy layout:
 /// - `~/ProjectOpen/<app_name>/`
 pub fn app_root(app_name: &str) -> Option<PathBuf> {
-    user_home_dir().map(|home| {
-        if app_name.eq_ignore_ascii_case("VEIL") {
-            home.join("VEIL").join("Core")
-        } else {
-            home.join("ProjectOpen").join(app_name)
-        }
-    })
+    if app_name.eq_ignore_ascii_case("VEIL") {
+        return Some(crate::paths::veil_root_dir());
+    }
+    crate::paths::user_home_dir().map(|home| home.join("ProjectOpen").join(app_name))
 }
 
 /// Resolve the install directory for a given config.
@@ -232,13 +216,10 @@ pub fn is_installed(config: &InstallerConfig) -> bool {
 
 /// Resolve the logs directory.
 pub fn logs_dir(app_name: &str) -> Option<PathBuf> {
-    user_home_dir().map(|home| {
-        if app_name.eq_ignore_ascii_case("VEIL") {
-            home.join("VEIL").join("Core").join("logs")
-        } else {
-            home.join("ProjectOpen").join(".Logs").join(app_name)
-        }
-    })
+    if app_name.eq_ignore_ascii_case("VEIL") {
+        return Some(crate::paths::logs_dir());
+    }
+    crate::paths::user_home_dir().map(|home| home.join("ProjectOpen").join(".Logs").join(app_name))
 }
 
 // ---------------------------------------------------------------------------
@@ -0,0 +1,277 @@
+// ~/veil/veil-backend/src/ipc/addon/capabilities.rs
+//
+// Addon capability declarations from addon.json.
+//
+// Two manifest shapes are accepted:
+//
+//   "capabilities": {
+//       "collectors": ["cpu", "gpu"],
+//       "commands":   ["addon.start", "processes.kill"],
+//       "hotkeys":    ["Ctrl+Alt+W"]
+//   }
+//
+//   "permissions": ["sysdata.cpu", "processes.kill"]   // flat list → commands
+//
+// Sysdata is checked by section rather than by command: `sysdata.get`,
+// `sysdata.subscribe`, `sysdata.describe` and the `get_<section>` commands
+// are allowed when every section they read is declared, either in
+// `collectors` or as a `sysdata.<section>` command.
+//
+// Declarations are always parsed and shown in the UI. Enforcement of
+// `commands`/`collectors` against IPC requests is opt-in via
+// `enforce_addon_permissions` in config.yaml. An addon is recognized by the
+// token it was launched with (see `ipc::token`). Enforcement denies by
+// default: a caller without any token is checked as an addon that declared
+// nothing.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::ipc::data_updater::normalize_section;
+use crate::ipc::registry::global_registry;
+use crate::ipc::token::Caller;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AddonCapabilities {
+    /// Sysdata sections the addon reads (e.g. "cpu", "network").
+    #[serde(default)]
+    pub collectors: Vec<String>,
+    /// IPC commands the addon may call, as "ns.cmd" (wildcards: "ns.*", "*").
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Global hotkeys the addon intends to register.
+    #[serde(default)]
+    pub hotkeys: Vec<String>,
+}
+
+impl AddonCapabilities {
+    pub fn is_empty(&self) -> bool {
+        self.collectors.is_empty() && self.commands.is_empty() && self.hotkeys.is_empty()
+    }
+
+    /// Flat, human-readable list for display ("collector:cpu", "processes.kill", "hotkey:Ctrl+K").
+    pub fn summary(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        out.extend(self.collectors.iter().map(|c| format!("collector:{}", c)));
+        out.extend(self.commands.iter().cloned());
+        out.extend(self.hotkeys.iter().map(|h| format!("hotkey:{}", h)));
+        out
+    }
+
+    /// Whether a request for `ns.cmd` with `args` is covered by this declaration.
+    pub fn permits(&self, ns: &str, cmd: &str, args: Option<&Value>) -> bool {
+        // A batch is only a wrapper; each entry is checked on its own. `kv`
+        // only reaches the caller's own store.
+        if ns.eq_ignore_ascii_case("batch") || ns.eq_ignore_ascii_case("kv") {
+            return true;
+        }
+        if ns.eq_ignore_ascii_case("sysdata") {
+            return match sysdata_reads(cmd, args) {
+                SysdataRead::Nothing => true,
+                SysdataRead::Sections(sections) => sections.iter().all(|s| self.reads_section(s)),
+                SysdataRead::Everything => self.reads_section("*"),
+            };
+        }
+        self.has_command(ns, cmd)
+    }
+
+    fn has_command(&self, ns: &str, cmd: &str) -> bool {
+        self.commands.iter().any(|p| {
+            let p = p.trim();
+            p == "*"
+                || p.eq_ignore_ascii_case(&format!("{}.*", ns))
+                || p.eq_ignore_ascii_case(&format!("{}.{}", ns, cmd))
+        })
+    }
+
+    /// Whether sysdata `section` is declared; `"*"` asks for all of them.
+    fn reads_section(&self, section: &str) -> bool {
+        let collector_ok = self.collectors.iter().any(|c| {
+            let c = c.trim();
+            c == "*" || (section != "*" && normalize_section(c).unwrap_or(c) == section)
+        });
+        collector_ok || self.has_command("sysdata", section)
+    }
+}
+
+/// What a `sysdata` request reads.
+enum SysdataRead {
+    Nothing,
+    /// These sections, normalized where known.
+    Sections(Vec<String>),
+    /// Every section, or more than the arguments say.
+    Everything,
+}
+
+/// Section a sysdata path or key belongs to: `"sysdata.cpu.usage_percent"` → `"cpu"`.
+fn section_of(path: &str) -> String {
+    let path = path.trim();
+    let path = path.strip_prefix("sysdata.").unwrap_or(path);
+    let section = path.split('.').next().unwrap_or_default();
+    normalize_section(section).map_or_else(|| section.to_ascii_lowercase(), str::to_string)
+}
+
+/// The sections `sysdata.<cmd>` reads with these `args`. Arguments that don't
+/// resolve count as reading everything, so they need a `*` declaration.
+fn sysdata_reads(cmd: &str, args: Option<&Value>) -> SysdataRead {
+    let strings = |key: &str| -> Option<Vec<String>> {
+        args?.get(key)?.as_array()?.iter().map(|v| v.as_str().map(section_of)).collect()
+    };
+    let sections = match cmd {
+        "unsubscribe" => return SysdataRead::Nothing,
+        "get" => match args.and_then(|a| a.get("path")).and_then(|v| v.as_str()) {
+            Some(path) => Some(vec![section_of(path)]),
+            None => strings("paths"),
+        },
+        "subscribe" => strings("keys"),
+        "describe" => match args.and_then(|a| a.get("section")) {
+            Some(section) => section.as_str().map(|s| vec![section_of(s)]),
+            None => return SysdataRead::Everything,
+        },
+        "get_temp" => Some(vec!["cpu".to_string(), "gpu".to_string()]),
+        "get_notifications" | "get_tray_icons" => Some(vec!["appdata".to_string()]),
+        _ => cmd.strip_prefix("get_").map(|section| vec![section_of(section)]),
+    };
+    match sections {
+        Some(sections) if !sections.is_empty() => SysdataRead::Sections(sections),
+        _ => SysdataRead::Everything,
+    }
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse the capability block from a raw addon.json value.
+pub fn parse_capabilities(manifest: &Value) -> AddonCapabilities {
+    let block = manifest.get("capabilities");
+    let mut caps = AddonCapabilities {
+        collectors: string_list(block.and_then(|b| b.get("collectors"))),
+        commands: string_list(block.and_then(|b| b.get("commands"))),
+        hotkeys: string_list(block.and_then(|b| b.get("hotkeys"))),
+    };
+
+    for perm in string_list(manifest.get("permissions")) {
+        if !caps.commands.iter().any(|c| c.eq_ignore_ascii_case(&perm)) {
+            caps.commands.push(perm);
+        }
+    }
+
+    caps
+}
+
+/// Check an IPC request made on behalf of `addon_id` against its declared capabilities.
+/// Only consulted when `enforce_addon_permissions` is enabled.
+pub fn check_addon_permission(addon_id: &str, ns: &str, cmd: &str, args: Option<&Value>) -> Result<(), String> {
+    let reg = global_registry().read().unwrap();
+    let addon = reg
+        .addons
+        .iter()
+        .find(|a| a.id.eq_ignore_ascii_case(addon_id))
+        .ok_or_else(|| format!("Unknown addon '{}'", addon_id))?;
+
+    let caps = addon
+        .metadata
+        .get("capabilities")
+        .and_then(|v| serde_json::from_value::<AddonCapabilities>(v.clone()).ok())
+        .unwrap_or_default();
+
+    if caps.permits(ns, cmd, args) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Addon '{}' lacks permission '{}.{}'",
+            addon_id, ns, cmd
+        ))
+    }
+}
+
+/// Check a request from `caller`. Only consulted when
+/// `enforce_addon_permissions` is enabled. Session-token holders are the
+/// user's own tools and pass.
+pub fn check_caller_permission(caller: &Caller, ns: &str, cmd: &str, args: Option<&Value>) -> Result<(), String> {
+    match caller {
+        Caller::Session => Ok(()),
+        Caller::Addon(addon_id) => check_addon_permission(addon_id, ns, cmd, args),
+        Caller::Anonymous if AddonCapabilities::default().permits(ns, cmd, args) => Ok(()),
+        Caller::Anonymous => Err(format!(
+            "Unidentified callers lack permission '{}.{}'; send an IPC token",
+            ns, cmd
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn caps(collectors: &[&str], commands: &[&str]) -> AddonCapabilities {
+        AddonCapabilities {
+            collectors: collectors.iter().map(|s| s.to_string()).collect(),
+            commands: commands.iter().map(|s| s.to_string()).collect(),
+            hotkeys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn collectors_allow_get_and_subscribe_on_their_sections() {
+        let cpu = caps(&["cpu", "display"], &[]);
+        assert!(cpu.permits("sysdata", "get", Some(&json!({ "path": "cpu.usage_percent" }))));
+        assert!(cpu.permits("sysdata", "get", Some(&json!({ "paths": ["sysdata.cpu.frequency_mhz", "displays.monitors"] }))));
+        assert!(cpu.permits("sysdata", "subscribe", Some(&json!({ "keys": ["cpu.usage_percent"] }))));
+        assert!(cpu.permits("sysdata", "describe", Some(&json!({ "section": "cpu" }))));
+        assert!(cpu.permits("sysdata", "get_cpu", None));
+        assert!(cpu.permits("sysdata", "get_displays", None));
+        assert!(cpu.permits("sysdata", "unsubscribe", Some(&json!({ "subscription_id": 1 }))));
+    }
+
+    #[test]
+    fn collectors_limit_get_and_subscribe_to_their_sections() {
+        let cpu = caps(&["cpu"], &[]);
+        assert!(!cpu.permits("sysdata", "get", Some(&json!({ "path": "network.received_bytes" }))));
+        assert!(!cpu.permits("sysdata", "get", Some(&json!({ "paths": ["cpu.usage_percent", "ram.used_bytes"] }))));
+        assert!(!cpu.permits("sysdata", "subscribe", Some(&json!({ "keys": ["cpu.usage_percent", "processes"] }))));
+        assert!(!cpu.permits("sysdata", "describe", None));
+        assert!(!cpu.permits("sysdata", "get_temp", None));
+        assert!(!cpu.permits("sysdata", "get", None));
+    }
+
+    #[test]
+    fn declaring_the_command_does_not_open_every_section() {
+        let getter = caps(&[], &["sysdata.get", "sysdata.subscribe"]);
+        assert!(!getter.permits("sysdata", "get", Some(&json!({ "path": "cpu.usage_percent" }))));
+        assert!(!getter.permits("sysdata", "subscribe", Some(&json!({ "keys": ["ram"] }))));
+
+        // A section named as a command counts like a collector.
+        let flat = caps(&[], &["sysdata.cpu"]);
+        assert!(flat.permits("sysdata", "get", Some(&json!({ "path": "cpu.usage_percent" }))));
+        assert!(!flat.permits("sysdata", "get", Some(&json!({ "path": "gpu.usage_percent" }))));
+    }
+
+    #[test]
+    fn wildcards_read_everything() {
+        for all in [caps(&["*"], &[]), caps(&[], &["sysdata.*"]), caps(&[], &["*"])] {
+            assert!(all.permits("sysdata", "describe", None));
+            assert!(all.permits("sysdata", "get", Some(&json!({ "paths": ["cpu", "ram", "network"] }))));
+        }
+    }
+
+    #[test]
+    fn other_namespaces_are_checked_by_command() {
+        let c = caps(&["*"], &["addon.start"]);
+        assert!(c.permits("addon", "start", None));
+        assert!(!c.permits("addon", "stop", None));
+        assert!(c.permits("batch", "run", None));
+        assert!(AddonCapabilities::default().permits("kv", "get", None));
+    }
+}
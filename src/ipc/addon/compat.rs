@@ -0,0 +1,58 @@
+// ~/veil/veil-backend/src/ipc/addon/compat.rs
+//
+// Opt-in backend version requirement from addon.json:
+//
+//   "min_backend_version": "0.3.0"
+//
+// Partial versions ("0.3", "1") and a leading "v" are accepted. Addons that
+// don't declare it start on any backend.
+
+use semver::{BuildMetadata, Version};
+use serde_json::Value;
+
+use crate::ipc::response::{codes, IpcError};
+
+pub const BACKEND_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// `1` → 1.0.0, `v1.2` → 1.2.0; pre-release tags are kept, build metadata dropped.
+pub fn parse_version(text: &str) -> Option<Version> {
+    let text = text.trim();
+    let text = text.strip_prefix(['v', 'V']).unwrap_or(text);
+    let core_end = text.find(['-', '+']).unwrap_or(text.len());
+    let (core, suffix) = text.split_at(core_end);
+    let parts = core.split('.').count();
+    let padded = match parts {
+        1 => format!("{}.0.0{}", core, suffix),
+        2 => format!("{}.0{}", core, suffix),
+        _ => text.to_string(),
+    };
+    let mut version = Version::parse(&padded).ok()?;
+    version.build = BuildMetadata::EMPTY;
+    Some(version)
+}
+
+/// Whether a backend at `backend` satisfies `min`. Pre-releases sort before
+/// their release, so 0.3.0-beta does not satisfy 0.3.0.
+pub fn satisfies(backend: &Version, min: &Version) -> bool {
+    backend >= min
+}
+
+/// Why the addon described by `manifest` can't run on this backend, or `None`
+/// when it can (including when it declares no requirement).
+pub fn incompatibility(manifest: &Value) -> Option<String> {
+    let declared = manifest.get("min_backend_version").filter(|v| !v.is_null())?;
+    let Some(min) = declared.as_str().and_then(parse_version) else {
+        return Some(format!("min_backend_version {} is not a valid version", declared));
+    };
+    let backend = parse_version(BACKEND_VERSION)?;
+    (!satisfies(&backend, &min))
+        .then(|| format!("Requires VEIL backend {} or newer (running {})", min, backend))
+}
+
+/// `incompatibility` as an `INCOMPATIBLE_VERSION` error, for the start paths.
+pub fn check(manifest: &Value) -> Result<(), IpcError> {
+    match incompatibility(manifest) {
+        Some(reason) => Err(IpcError::new(codes::INCOMPATIBLE_VERSION, reason)),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,51 @@
+use serde_json::{Value, json};
+use sysinfo::{System, ProcessesToUpdate};
+use crate::autostart::load_tray_settings;
+use crate::ipc::registry::global_registry;
+use crate::ipc::response::IpcError;
+use super::compat;
+use super::start::find_addon_pid_in;
+use super::supervisor::tracked_pid;
+use super::utils::registry_entry_to_addon;
+
+/// Every discovered addon with its live run state, sorted by name.
+pub fn list(_args: Option<Value>) -> Result<Value, IpcError> {
+    let entries = global_registry().read().unwrap().addons.clone();
+    let autostart = load_tray_settings().addon_autostart;
+
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+
+    let mut addons: Vec<(String, String, Value)> = entries
+        .iter()
+        .map(|entry| {
+            let name = entry
+                .metadata
+                .get("name")
+                .and_then(|v| v.as_str())
+                .unwrap_or(entry.id.as_str())
+                .to_string();
+            let package = entry.metadata.get("package").and_then(|v| v.as_str());
+            let running = registry_entry_to_addon(entry)
+                .map(|addon| tracked_pid(&addon.name).or_else(|| find_addon_pid_in(&sys, &addon)).is_some())
+                .unwrap_or(false);
+            // Tray settings key autostart by whichever name was used to start it.
+            let autostart = autostart.iter().any(|(key, enabled)| {
+                *enabled && (key.eq_ignore_ascii_case(&entry.id) || key.eq_ignore_ascii_case(&name))
+            });
+            let item = json!({
+                "id": entry.id,
+                "name": name,
+                "package": package,
+                "running": running,
+                "autostart": autostart,
+                "min_backend_version": entry.metadata.get("min_backend_version"),
+                "incompatible": compat::incompatibility(&entry.metadata),
+            });
+            (name.to_lowercase(), entry.id.clone(), item)
+        })
+        .collect();
+
+    addons.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+    Ok(Value::Array(addons.into_iter().map(|(_, _, item)| item).collect()))
+}
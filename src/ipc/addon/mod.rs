@@ -1,10 +1,18 @@
 // ~/veil/veil-backend/src/ipc/addon/mod.rs
 
 pub mod utils;
+pub mod capabilities;
+pub mod compat;
 pub mod start;
 pub mod stop;
 pub mod reload;
+pub mod status;
+pub mod list;
+pub mod supervisor;
+pub mod shutdown;
 
 pub use start::start;
-pub use stop::stop;
-pub use reload::reload;
\ No newline at end of file
+pub use stop::{stop, stop_all};
+pub use reload::reload;
+pub use status::status;
+pub use list::list;
\ No newline at end of file
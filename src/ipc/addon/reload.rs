@@ -1,29 +1,23 @@
 use serde_json::{Value, json};
 use crate::{info, error};
-use crate::ipc::registry::global_registry;
+use crate::ipc::response::IpcError;
+use super::utils::{addon_name_arg, dry_run_arg, entry_to_addon, find_addon_entry};
+use super::compat;
 use super::stop::stop;
-use super::start::start;
+use super::start::{dry_run, start};
 
-pub fn reload(args: Option<Value>) -> Result<Value, String> {
-    let addon_name = args
-        .as_ref()
-        .and_then(|v| v.get("addon_name"))
-        .and_then(|v| v.as_str())
-        .ok_or("Missing addon_name in args")?
-        .to_string();
+pub fn reload(args: Option<Value>) -> Result<Value, IpcError> {
+    let addon_name = addon_name_arg(args.as_ref())?;
 
     // Verify addon exists first
-    let reg = global_registry().read().unwrap();
-    let _entry = reg.addons.iter().find(|a| {
-        a.id == addon_name ||
-        a.metadata.get("name")
-            .and_then(|n| n.as_str())
-            .map(|n| n.eq_ignore_ascii_case(&addon_name))
-            .unwrap_or(false)
-    })
-        .ok_or(format!("Addon not found: {}", addon_name))?
-        .clone();
-    drop(reg);
+    let entry = find_addon_entry(&addon_name)?;
+
+    if dry_run_arg(args.as_ref()) {
+        return Ok(dry_run(&entry_to_addon(&entry)?, &entry.metadata, &addon_name, true));
+    }
+
+    // Refuse before stopping, so an incompatible addon isn't left stopped.
+    compat::check(&entry.metadata)?;
 
     // Stop the addon
     let _ = stop(args.clone());
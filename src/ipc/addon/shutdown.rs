@@ -0,0 +1,67 @@
+// ~/veil/veil-backend/src/ipc/addon/shutdown.rs
+//
+// Graceful stop requests. An addon can only be told to shut down while it
+// holds a stream connection (`sysdata.subscribe` with its `addon_id`); each
+// such connection registers a listener here and, once a shutdown is
+// requested, pushes a single `{ "event": "shutdown" }` frame to the addon.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+
+fn listeners() -> &'static Mutex<HashMap<String, usize>> {
+    static LISTENERS: OnceLock<Mutex<HashMap<String, usize>>> = OnceLock::new();
+    LISTENERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn requested() -> &'static Mutex<HashSet<String>> {
+    static REQUESTED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    REQUESTED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Marks an addon as reachable for as long as its stream connection lives.
+pub struct ShutdownListener {
+    addon_id: String,
+}
+
+impl ShutdownListener {
+    pub fn register(addon_id: &str) -> Self {
+        *listeners().lock().unwrap().entry(addon_id.to_string()).or_insert(0) += 1;
+        Self { addon_id: addon_id.to_string() }
+    }
+
+    pub fn addon_id(&self) -> &str {
+        &self.addon_id
+    }
+}
+
+impl Drop for ShutdownListener {
+    fn drop(&mut self) {
+        let mut map = listeners().lock().unwrap();
+        if let Some(count) = map.get_mut(&self.addon_id) {
+            *count -= 1;
+            if *count == 0 {
+                map.remove(&self.addon_id);
+            }
+        }
+    }
+}
+
+/// Whether the addon has a connection a shutdown message can be sent on.
+pub fn has_listener(addon_id: &str) -> bool {
+    listeners().lock().unwrap().contains_key(addon_id)
+}
+
+/// Ask the addon's stream connections to send it a shutdown message.
+pub fn request_shutdown(addon_id: &str) {
+    requested().lock().unwrap().insert(addon_id.to_string());
+}
+
+pub fn shutdown_requested(addon_id: &str) -> bool {
+    requested().lock().unwrap().contains(addon_id)
+}
+
+/// Forget a shutdown request once the stop has finished, so a later start of
+/// the same addon is not told to exit.
+pub fn clear(addon_id: &str) {
+    requested().lock().unwrap().remove(addon_id);
+}
@@ -1,48 +1,172 @@
 use serde_json::{Value, json};
-use std::process::{Command, Stdio};
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
 use sysinfo::{System, ProcessesToUpdate};
-use crate::{info, error};
-use crate::ipc::registry::global_registry;
-use super::utils::registry_entry_to_addon;
+use crate::{info, warn, error};
+use crate::ipc::response::{codes, IpcError};
+use super::compat;
+use super::supervisor::{track, tracked_pid};
+use super::utils::{addon_name_arg, dry_run_arg, entry_to_addon, find_addon_entry};
 
-/// Check if an addon is already running by matching exe path or process name.
-fn is_addon_running(addon: &crate::Addon) -> bool {
+/// PID of a running addon process, matched by exe path or process name.
+pub(super) fn find_addon_pid(addon: &crate::Addon) -> Option<u32> {
     let mut sys = System::new();
     sys.refresh_processes(ProcessesToUpdate::All, true);
-    for (_pid, proc_) in sys.processes() {
+    find_addon_pid_in(&sys, addon)
+}
+
+/// `find_addon_pid` against an already refreshed process list.
+pub(super) fn find_addon_pid_in(sys: &System, addon: &crate::Addon) -> Option<u32> {
+    for (pid, proc_) in sys.processes() {
         if let Some(exe) = proc_.exe() {
             if exe == addon.exe_path.as_path() {
-                return true;
+                return Some(pid.as_u32());
             }
         }
         if proc_.name().eq_ignore_ascii_case(&format!("{}.exe", addon.package)) {
-            return true;
+            return Some(pid.as_u32());
         }
     }
-    false
+    None
+}
+
+/// Check if an addon is already running, tracked or not.
+fn is_addon_running(addon: &crate::Addon) -> bool {
+    tracked_pid(&addon.name).is_some() || find_addon_pid(addon).is_some()
+}
+
+/// The command `spawn_addon` runs, also rendered by dry runs so they can't
+/// drift from a real start.
+fn launch_command(addon: &crate::Addon) -> Command {
+    let mut cmd = Command::new(&addon.exe_path);
+    cmd.current_dir(&addon.dir)
+        .args(&addon.args)
+        .envs(addon.env.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    cmd
+}
+
+/// Launch the addon executable detached from our stdio.
+pub(super) fn spawn_addon(addon: &crate::Addon) -> Result<Child, IpcError> {
+    // Ensure binary exists
+    if !addon.exe_path.exists() {
+        error!("Addon executable not found: {}", addon.exe_path.display());
+        return Err(IpcError::new(
+            codes::EXECUTABLE_NOT_FOUND,
+            format!("Addon executable not found: {}", addon.exe_path.display()),
+        ));
+    }
+
+    for warning in &addon.launch_warnings {
+        warn!("[IPC] Addon '{}' addon.json: {}", addon.name, warning);
+    }
+
+    let mut cmd = launch_command(addon);
+    if let Some(token) = crate::ipc::token::issue_addon_token(&addon.name) {
+        cmd.env(crate::ipc::token::ADDON_TOKEN_ENV, token);
+    }
+    cmd.spawn()
+        .map_err(|e| {
+            error!("[IPC] Failed to start addon '{}': {}", addon.name, e);
+            IpcError::new(codes::START_FAILED, format!("Failed to start addon: {}", e))
+        })
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| ["exe", "bat", "cmd", "com"].iter().any(|x| e.eq_ignore_ascii_case(x)))
+        .unwrap_or(false)
 }
 
-pub fn start(args: Option<Value>) -> Result<Value, String> {
-    let addon_name = args
-        .as_ref()
-        .and_then(|v| v.get("addon_name"))
-        .and_then(|v| v.as_str())
-        .ok_or("Missing addon_name in args")?
-        .to_string();
-
-    let reg = global_registry().read().unwrap();
-    let entry = reg.addons.iter().find(|a| {
-        a.id == addon_name ||
-        a.metadata.get("name")
-            .and_then(|n| n.as_str())
-            .map(|n| n.eq_ignore_ascii_case(&addon_name))
-            .unwrap_or(false)
+#[cfg(not(windows))]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata().map(|m| m.permissions().mode() & 0o111 != 0).unwrap_or(false)
+}
+
+fn quote_arg(arg: &OsStr) -> String {
+    let arg = arg.to_string_lossy();
+    if arg.is_empty() || arg.contains(char::is_whitespace) {
+        format!("\"{}\"", arg)
+    } else {
+        arg.to_string()
+    }
+}
+
+/// What `start` would launch for `addon`, and anything that would stop it.
+/// A reload stops the addon first, so a running copy isn't a problem there.
+pub(super) fn dry_run(addon: &crate::Addon, manifest: &Value, addon_name: &str, reload: bool) -> Value {
+    let cmd = launch_command(addon);
+    let command_line = std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(quote_arg)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let working_dir = cmd.get_current_dir().unwrap_or(&addon.dir);
+
+    let exe_exists = addon.exe_path.is_file();
+    let exe_executable = exe_exists && is_executable(&addon.exe_path);
+    let working_dir_exists = working_dir.is_dir();
+    let running = is_addon_running(addon);
+    let incompatible = compat::incompatibility(manifest);
+
+    let mut problems = Vec::new();
+    if let Some(reason) = &incompatible {
+        problems.push(reason.clone());
+    }
+    if !exe_exists {
+        problems.push(format!("Executable not found: {}", addon.exe_path.display()));
+    } else if !exe_executable {
+        problems.push(format!("Not an executable file: {}", addon.exe_path.display()));
+    }
+    if !working_dir_exists {
+        problems.push(format!("Working directory not found: {}", working_dir.display()));
+    }
+    if running && !reload {
+        problems.push("Already running; start would do nothing".to_string());
+    }
+
+    json!({
+        "status": "dry_run",
+        "addon": addon_name,
+        "reload": reload,
+        "id": addon.name,
+        "package": addon.package,
+        "exe_path": addon.exe_path.display().to_string(),
+        "working_dir": working_dir.display().to_string(),
+        "command_line": command_line,
+        "env": addon.env.iter().map(|(k, v)| (k.clone(), json!(v))).collect::<serde_json::Map<_, _>>(),
+        "launch_warnings": addon.launch_warnings,
+        "checks": {
+            "exe_exists": exe_exists,
+            "exe_executable": exe_executable,
+            "working_dir_exists": working_dir_exists,
+            "already_running": running,
+            "backend_compatible": incompatible.is_none(),
+        },
+        "would_start": problems.is_empty(),
+        "problems": problems,
     })
-        .ok_or(format!("Addon not found: {}", addon_name))?
-        .clone();
-    drop(reg);
+}
 
-    let addon = registry_entry_to_addon(&entry)?;
+pub fn start(args: Option<Value>) -> Result<Value, IpcError> {
+    let addon_name = addon_name_arg(args.as_ref())?;
+    let entry = find_addon_entry(&addon_name)?;
+    let addon = entry_to_addon(&entry)?;
+
+    if dry_run_arg(args.as_ref()) {
+        return Ok(dry_run(&addon, &entry.metadata, &addon_name, false));
+    }
+
+    if let Err(e) = compat::check(&entry.metadata) {
+        error!("[IPC] Not starting addon '{}': {}", addon.name, e.message);
+        return Err(e);
+    }
 
     // Check if addon is already running
     if is_addon_running(&addon) {
@@ -52,27 +176,9 @@ pub fn start(args: Option<Value>) -> Result<Value, String> {
 
     info!("Starting addon '{}'", addon.name);
 
-    // Ensure binary exists
-    if !addon.exe_path.exists() {
-        error!("Addon executable not found: {}", addon.exe_path.display());
-        return Err(format!("Addon executable not found: {}", addon.exe_path.display()));
-    }
-
-    match Command::new(&addon.exe_path)
-        .current_dir(&addon.dir)
-        .stdin(Stdio::null())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()
-    {
-        Ok(child) => {
-            info!("[IPC] Started addon '{}' with PID {}", addon.name, child.id());
-            Ok(json!({"status": "started", "addon": addon_name}))
-        }
-        Err(e) => {
-            error!("[IPC] Failed to start addon '{}': {}", addon.name, e);
-            Err(format!("Failed to start addon: {}", e))
-        }
-    }
+    let child = spawn_addon(&addon)?;
+    info!("[IPC] Started addon '{}' with PID {}", addon.name, child.id());
+    track(&addon.name, child);
+    Ok(json!({"status": "started", "addon": addon_name}))
 }
 
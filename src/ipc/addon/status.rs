@@ -0,0 +1,21 @@
+use serde_json::{Value, json};
+use crate::ipc::response::IpcError;
+use super::start::find_addon_pid;
+use super::supervisor::{restart_count, tracked_pid};
+use super::utils::{addon_name_arg, entry_to_addon, find_addon_entry};
+
+/// Run state of one addon. Processes the backend didn't spawn are still
+/// reported as running, found by exe path or name.
+pub fn status(args: Option<Value>) -> Result<Value, IpcError> {
+    let addon_name = addon_name_arg(args.as_ref())?;
+    let entry = find_addon_entry(&addon_name)?;
+    let addon = entry_to_addon(&entry)?;
+
+    let pid = tracked_pid(&addon.name).or_else(|| find_addon_pid(&addon));
+    Ok(json!({
+        "name": addon.name,
+        "running": pid.is_some(),
+        "pid": pid,
+        "restarts": restart_count(&addon.name),
+    }))
+}
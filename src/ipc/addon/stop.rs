@@ -1,76 +1,161 @@
-
-
 use serde_json::{Value, json};
 use std::path::Path;
-use sysinfo::{System, ProcessesToUpdate};
+use std::process::Child;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessesToUpdate, System};
 use crate::{info, error, warn};
+use crate::Addon;
+use crate::config::addon_stop_grace_ms;
 use crate::ipc::registry::global_registry;
-use super::utils::registry_entry_to_addon;
+use crate::ipc::response::{codes, IpcError};
+use super::shutdown;
+use super::supervisor::{release, tracked_ids};
+use super::utils::{addon_name_arg, entry_to_addon, find_addon_entry, registry_entry_to_addon};
+
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub fn stop(args: Option<Value>) -> Result<Value, IpcError> {
+    let addon_name = addon_name_arg(args.as_ref())?;
+    let entry = find_addon_entry(&addon_name)?;
+    let addon = entry_to_addon(&entry)?;
+
+    info!("Stopping addon '{}'", addon.name);
+
+    match stop_addon(&addon, Duration::from_millis(addon_stop_grace_ms())) {
+        Some(graceful) => {
+            info!("[IPC] Stopped addon '{}'", addon_name);
+            Ok(json!({"status": "stopped", "addon": addon_name, "graceful": graceful}))
+        }
+        None => {
+            error!("[IPC] Failed to stop addon '{}'", addon_name);
+            Err(IpcError::new(codes::NOT_RUNNING, format!("Failed to stop addon: {}", addon_name)))
+        }
+    }
+}
 
 /// Stop ALL running addon processes. Called during backend exit.
+///
+/// Each addon gets its own thread, so the grace periods overlap instead of
+/// adding up.
+pub fn stop_all() -> Vec<String> {
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    let tracked = tracked_ids();
 
-pub fn stop(args: Option<Value>) -> Result<Value, String> {
-    let addon_name = args
-        .as_ref()
-        .and_then(|v| v.get("addon_name"))
-        .and_then(|v| v.as_str())
-        .ok_or("Missing addon_name in args")?
-        .to_string();
+    let addons: Vec<Addon> = global_registry()
+        .read()
+        .unwrap()
+        .addons
+        .iter()
+        .filter_map(|entry| registry_entry_to_addon(entry).ok())
+        .filter(|addon| tracked.contains(&addon.name) || !matching_pids(&sys, addon).is_empty())
+        .collect();
+    if addons.is_empty() {
+        return Vec::new();
+    }
 
-    let reg = global_registry().read().unwrap();
-    let entry = reg.addons.iter().find(|a| {
-        a.id == addon_name ||
-        a.metadata.get("name")
-            .and_then(|n| n.as_str())
-            .map(|n| n.eq_ignore_ascii_case(&addon_name))
-            .unwrap_or(false)
-    })
-        .ok_or(format!("Addon not found: {}", addon_name))?
-        .clone();
-    drop(reg);
+    let grace = Duration::from_millis(addon_stop_grace_ms());
+    info!("Stopping {} addon(s) (grace {}ms)", addons.len(), grace.as_millis());
 
-    let addon = registry_entry_to_addon(&entry)?;
+    let handles: Vec<_> = addons
+        .into_iter()
+        .map(|addon| {
+            std::thread::spawn(move || stop_addon(&addon, grace).map(|_| addon.name))
+        })
+        .collect();
+    handles
+        .into_iter()
+        .filter_map(|h| h.join().ok().flatten())
+        .collect()
+}
 
-    info!("Stopping addon '{}'", addon.name);
+/// Ask the addon to shut down, wait up to `grace` for it to exit, then kill
+/// whatever is left. Returns `None` when nothing was running, otherwise
+/// whether every process exited on its own.
+fn stop_addon(addon: &Addon, grace: Duration) -> Option<bool> {
+    // Taking the child off the supervisor first means neither the wait nor
+    // the kill below is mistaken for a crash.
+    let mut child = release(&addon.name);
+
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    let pids = matching_pids(&sys, addon);
+    if child.is_none() && pids.is_empty() {
+        return None;
+    }
+
+    let mut graceful = false;
+    if !grace.is_zero() && shutdown::has_listener(&addon.name) {
+        shutdown::request_shutdown(&addon.name);
+        let deadline = Instant::now() + grace;
+        loop {
+            if all_exited(&mut child, &mut sys, &pids) {
+                graceful = true;
+                info!("Addon '{}' shut down on request", addon.name);
+                break;
+            }
+            if Instant::now() >= deadline {
+                warn!("Addon '{}' did not exit within {}ms; terminating", addon.name, grace.as_millis());
+                break;
+            }
+            std::thread::sleep(EXIT_POLL_INTERVAL);
+        }
+        shutdown::clear(&addon.name);
+    }
+
+    if !graceful {
+        terminate(addon, child, &mut sys, &pids);
+    }
+    Some(graceful)
+}
 
+/// OS processes for the addon, matched by exe path or file name. Catches
+/// copies the backend didn't spawn as well as the tracked one.
+fn matching_pids(sys: &System, addon: &Addon) -> Vec<Pid> {
     let exe_filename = addon.exe_path
         .file_name()
         .unwrap_or_default()
         .to_string_lossy()
         .to_string();
 
-    // Try OS-level process kill by exe path/name
-    let mut sys = System::new();
-    sys.refresh_processes(ProcessesToUpdate::All, true);
-    let mut stopped = false;
+    sys.processes()
+        .iter()
+        .filter(|(_, proc_)| {
+            proc_.exe() == Some(Path::new(&addon.exe_path))
+                || proc_.name().eq_ignore_ascii_case(&exe_filename)
+        })
+        .map(|(pid, _)| *pid)
+        .collect()
+}
 
-    for (_pid, proc_) in sys.processes() {
-        let mut matches = false;
-
-        if proc_.exe() == Some(Path::new(&addon.exe_path)) {
-            matches = true;
-            info!("Process matched by exe path: {}", addon.exe_path.display());
+fn all_exited(child: &mut Option<Child>, sys: &mut System, pids: &[Pid]) -> bool {
+    if let Some(c) = child.as_mut() {
+        if let Ok(None) = c.try_wait() {
+            return false;
         }
+    }
+    sys.refresh_processes(ProcessesToUpdate::Some(pids), true);
+    pids.iter().all(|pid| sys.process(*pid).is_none())
+}
 
-        if !matches && proc_.name().eq_ignore_ascii_case(&exe_filename) {
-            matches = true;
-            info!("Process matched by name: {}", exe_filename);
+fn terminate(addon: &Addon, child: Option<Child>, sys: &mut System, pids: &[Pid]) {
+    if let Some(mut c) = child {
+        match c.kill() {
+            Ok(()) => {
+                let _ = c.wait();
+                info!("Killed tracked process for '{}'", addon.name);
+            }
+            Err(e) => warn!("Failed to kill '{}' (PID {}): {}", addon.name, c.id(), e),
         }
+    }
 
-        if matches {
+    sys.refresh_processes(ProcessesToUpdate::Some(pids), true);
+    for pid in pids {
+        if let Some(proc_) = sys.process(*pid) {
             match proc_.kill() {
                 true => info!("Successfully killed OS process for '{}'", addon.name),
                 false => warn!("Failed to kill OS process for '{}'", addon.name),
             }
-            stopped = true;
         }
     }
-
-    if stopped {
-        info!("[IPC] Stopped addon '{}'", addon_name);
-        Ok(json!({"status": "stopped", "addon": addon_name}))
-    } else {
-        error!("[IPC] Failed to stop addon '{}'", addon_name);
-        Err(format!("Failed to stop addon: {}", addon_name))
-    }
-}
\ No newline at end of file
+}
@@ -0,0 +1,226 @@
+// ~/veil/veil-backend/src/ipc/addon/supervisor.rs
+//
+// Tracks the addon processes the backend spawns. A background thread polls
+// each child; when one exits with a failure status it is logged and, if the
+// addon is listed in `addon_auto_restart` in tray settings, relaunched after
+// an exponential backoff. An addon that keeps crashing is given up on after
+// MAX_CRASH_STREAK restarts in a row. Crashes and failed restarts are also
+// passed to `notify`.
+
+use std::collections::HashMap;
+use std::process::Child;
+use std::sync::{Mutex, Once, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::autostart::load_tray_settings;
+use crate::{error, info, warn};
+use super::start::spawn_addon;
+use super::utils::{entry_to_addon, find_addon_entry};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const BACKOFF_BASE: Duration = Duration::from_secs(2);
+const BACKOFF_MAX: Duration = Duration::from_secs(60);
+/// An addon that ran at least this long before crashing starts a new streak.
+const STABLE_RUN: Duration = Duration::from_secs(60);
+const MAX_CRASH_STREAK: u32 = 5;
+
+struct TrackedAddon {
+    /// `None` once the process has exited.
+    child: Option<Child>,
+    started_at: Instant,
+    /// Automatic restarts since the addon was last started by hand.
+    restarts: u32,
+    crash_streak: u32,
+    restart_at: Option<Instant>,
+}
+
+fn tracked() -> &'static Mutex<HashMap<String, TrackedAddon>> {
+    static TRACKED: OnceLock<Mutex<HashMap<String, TrackedAddon>>> = OnceLock::new();
+    TRACKED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Take ownership of a freshly started addon process (keyed by addon id).
+pub fn track(addon_id: &str, child: Child) {
+    insert(addon_id, child, false);
+}
+
+fn insert(addon_id: &str, child: Child, restarted: bool) {
+    let now = Instant::now();
+    {
+        let mut map = tracked().lock().unwrap();
+        let t = map.entry(addon_id.to_string()).or_insert_with(|| TrackedAddon {
+            child: None,
+            started_at: now,
+            restarts: 0,
+            crash_streak: 0,
+            restart_at: None,
+        });
+        if restarted {
+            t.restarts += 1;
+        } else {
+            t.restarts = 0;
+            t.crash_streak = 0;
+        }
+        t.child = Some(child);
+        t.started_at = now;
+        t.restart_at = None;
+    }
+    ensure_watcher();
+}
+
+/// Stop supervising an addon without touching its process. Returns the child
+/// if it is still running, so the caller decides how it ends.
+pub fn release(addon_id: &str) -> Option<Child> {
+    let mut child = tracked().lock().unwrap().remove(addon_id)?.child?;
+    match child.try_wait() {
+        Ok(Some(_)) => None,
+        _ => Some(child),
+    }
+}
+
+/// Addons that currently have a tracked, running process.
+pub fn tracked_ids() -> Vec<String> {
+    tracked()
+        .lock()
+        .unwrap()
+        .iter_mut()
+        .filter(|(_, t)| t.child.as_mut().is_some_and(|c| matches!(c.try_wait(), Ok(None))))
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+/// PID of the tracked process, if it is still alive.
+pub fn tracked_pid(addon_id: &str) -> Option<u32> {
+    let mut map = tracked().lock().unwrap();
+    let child = map.get_mut(addon_id)?.child.as_mut()?;
+    match child.try_wait() {
+        Ok(None) => Some(child.id()),
+        _ => None,
+    }
+}
+
+/// Automatic restarts since the addon was last started by hand.
+pub fn restart_count(addon_id: &str) -> u32 {
+    tracked().lock().unwrap().get(addon_id).map(|t| t.restarts).unwrap_or(0)
+}
+
+fn auto_restart_enabled(addon_id: &str) -> bool {
+    load_tray_settings()
+        .addon_auto_restart
+        .iter()
+        .any(|(name, enabled)| *enabled && name.eq_ignore_ascii_case(addon_id))
+}
+
+fn backoff(crash_streak: u32) -> Duration {
+    BACKOFF_BASE.saturating_mul(1 << crash_streak.min(16)).min(BACKOFF_MAX)
+}
+
+fn ensure_watcher() {
+    static WATCHER: Once = Once::new();
+    WATCHER.call_once(|| {
+        let spawned = std::thread::Builder::new()
+            .name("veil-addon-supervisor".into())
+            .spawn(|| loop {
+                std::thread::sleep(POLL_INTERVAL);
+                for addon_id in poll_children() {
+                    restart(&addon_id);
+                }
+            });
+        if let Err(e) = spawned {
+            error!("[addons] Failed to start addon supervisor: {}", e);
+        }
+    });
+}
+
+/// Reap exited children and schedule restarts. Returns the addons whose
+/// restart is due now.
+fn poll_children() -> Vec<String> {
+    let now = Instant::now();
+    let mut due = Vec::new();
+    let mut crashes = Vec::new();
+    let mut map = tracked().lock().unwrap();
+
+    for (addon_id, t) in map.iter_mut() {
+        let Some(child) = t.child.as_mut() else {
+            if t.restart_at.is_some_and(|at| now >= at) {
+                t.restart_at = None;
+                due.push(addon_id.clone());
+            }
+            continue;
+        };
+        let status = match child.try_wait() {
+            Ok(Some(status)) => status,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!("[addons] Failed to poll '{}' (PID {}): {}", addon_id, child.id(), e);
+                continue;
+            }
+        };
+        t.child = None;
+
+        let ran_for = t.started_at.elapsed();
+        if status.success() {
+            info!("[addons] '{}' exited normally after {}s", addon_id, ran_for.as_secs());
+            continue;
+        }
+        error!("[addons] '{}' exited unexpectedly ({}) after {}s", addon_id, status, ran_for.as_secs());
+        crashes.push((addon_id.clone(), format!("Exited with {} after {}s", status, ran_for.as_secs())));
+
+        if !auto_restart_enabled(addon_id) {
+            continue;
+        }
+        if ran_for >= STABLE_RUN {
+            t.crash_streak = 0;
+        }
+        schedule_restart(addon_id, t, now);
+    }
+    // Notifying reads the registry, so not while holding the tracked map.
+    drop(map);
+    for (addon_id, reason) in crashes {
+        crate::notify::addon_crashed(&addon_id, &reason);
+    }
+    due
+}
+
+fn schedule_restart(addon_id: &str, t: &mut TrackedAddon, now: Instant) {
+    if t.crash_streak >= MAX_CRASH_STREAK {
+        error!(
+            "[addons] '{}' failed {} times in a row; giving up on auto-restart",
+            addon_id, t.crash_streak
+        );
+        return;
+    }
+    let delay = backoff(t.crash_streak);
+    t.crash_streak += 1;
+    t.restart_at = Some(now + delay);
+    warn!("[addons] Restarting '{}' in {}s", addon_id, delay.as_secs());
+}
+
+fn restart(addon_id: &str) {
+    // Skip if the addon was stopped or started by hand since it was scheduled.
+    let still_wanted = tracked()
+        .lock()
+        .unwrap()
+        .get(addon_id)
+        .is_some_and(|t| t.child.is_none());
+    if !still_wanted {
+        return;
+    }
+
+    let spawned = find_addon_entry(addon_id)
+        .and_then(|entry| entry_to_addon(&entry))
+        .and_then(|addon| spawn_addon(&addon));
+    match spawned {
+        Ok(child) => {
+            info!("[addons] Restarted '{}' with PID {}", addon_id, child.id());
+            insert(addon_id, child, true);
+        }
+        Err(e) => {
+            error!("[addons] Failed to restart '{}': {}", addon_id, e);
+            crate::notify::addon_start_failed(addon_id, &e.message);
+            if let Some(t) = tracked().lock().unwrap().get_mut(addon_id) {
+                schedule_restart(addon_id, t, Instant::now());
+            }
+        }
+    }
+}
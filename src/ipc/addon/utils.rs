@@ -1,7 +1,47 @@
 // ~/veil/veil-backend/src/ipc/addon/utils.rs
 
+use serde_json::Value;
 use std::path::PathBuf;
 use crate::Addon;
+use crate::ipc::registry::{global_registry, RegistryEntry};
+use crate::ipc::response::{codes, IpcError};
+use crate::paths::veil_root_dir;
+
+/// `addon_name` from the request args.
+pub fn addon_name_arg(args: Option<&Value>) -> Result<String, IpcError> {
+    args.and_then(|v| v.get("addon_name"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| IpcError::new(codes::BAD_ARGS, "Missing addon_name in args"))
+}
+
+/// `dry_run` from the request args; absent means a real run.
+pub fn dry_run_arg(args: Option<&Value>) -> bool {
+    args.and_then(|v| v.get("dry_run"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Registry entry whose id, or display name ignoring case, is `addon_name`.
+pub fn find_addon_entry(addon_name: &str) -> Result<RegistryEntry, IpcError> {
+    let reg = global_registry().read().unwrap();
+    reg.addons
+        .iter()
+        .find(|a| {
+            a.id == addon_name ||
+            a.metadata.get("name")
+                .and_then(|n| n.as_str())
+                .map(|n| n.eq_ignore_ascii_case(addon_name))
+                .unwrap_or(false)
+        })
+        .cloned()
+        .ok_or_else(|| IpcError::new(codes::ADDON_NOT_FOUND, format!("Addon not found: {}", addon_name)))
+}
+
+/// `registry_entry_to_addon` for the handlers, tagging failures as `ADDON_INVALID`.
+pub fn entry_to_addon(entry: &RegistryEntry) -> Result<Addon, IpcError> {
+    registry_entry_to_addon(entry).map_err(|e| IpcError::new(codes::ADDON_INVALID, e))
+}
 
 pub fn registry_entry_to_addon(entry: &crate::ipc::registry::RegistryEntry) -> Result<Addon, String> {
     let name = entry.id.clone();
@@ -11,11 +51,79 @@ pub fn registry_entry_to_addon(entry: &crate::ipc::registry::RegistryEntry) -> R
         .and_then(|v| v.as_str())
         .ok_or("Missing package in metadata")?
         .to_string();
-    
+    let (args, env, launch_warnings) = launch_options(entry);
+
     Ok(Addon {
         name,
         exe_path,
         dir,
         package,
+        args,
+        env,
+        launch_warnings,
     })
 }
+
+/// `${ADDON_ROOT}`, `${ADDON_ID}`, `${ADDON_CONFIG}` and `${VEIL_HOME}`.
+/// Anything else is left as written.
+fn expand_placeholders(text: &str, entry: &RegistryEntry) -> String {
+    text.replace("${ADDON_ROOT}", &entry.path.display().to_string())
+        .replace("${ADDON_ID}", &entry.id)
+        .replace("${ADDON_CONFIG}", &entry.path.join("config.yaml").display().to_string())
+        .replace("${VEIL_HOME}", &veil_root_dir().display().to_string())
+}
+
+/// Strings as-is; numbers and booleans as written in the JSON.
+fn scalar_text(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Extra launch arguments and environment from addon.json:
+///
+///   "args": ["--config", "${ADDON_CONFIG}"],
+///   "env": { "RUST_LOG": "info" }
+///
+/// Malformed entries are skipped and described in the returned warnings
+/// rather than failing the launch.
+fn launch_options(entry: &RegistryEntry) -> (Vec<String>, Vec<(String, String)>, Vec<String>) {
+    let mut warnings = Vec::new();
+
+    let mut args = Vec::new();
+    match entry.metadata.get("args") {
+        None | Some(Value::Null) => {}
+        Some(Value::Array(items)) => {
+            for (i, item) in items.iter().enumerate() {
+                match scalar_text(item) {
+                    Some(text) => args.push(expand_placeholders(&text, entry)),
+                    None => warnings.push(format!("args[{}] is not a string, skipped", i)),
+                }
+            }
+        }
+        Some(_) => warnings.push("args is not a list, ignored".to_string()),
+    }
+
+    let mut env = Vec::new();
+    match entry.metadata.get("env") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(vars)) => {
+            for (key, value) in vars {
+                if key.is_empty() || key.contains(['=', '\0']) {
+                    warnings.push(format!("env key '{}' is not a valid variable name, skipped", key));
+                    continue;
+                }
+                match scalar_text(value) {
+                    Some(text) => env.push((key.clone(), expand_placeholders(&text, entry))),
+                    None => warnings.push(format!("env.{} is not a string, skipped", key)),
+                }
+            }
+        }
+        Some(_) => warnings.push("env is not an object, ignored".to_string()),
+    }
+
+    (args, env, warnings)
+}
@@ -1,5 +1,10 @@
 // ~/veil/veil-backend/src/ipc/appdata/mod.rs
 
 pub mod window;
+#[cfg(target_os = "windows")]
 pub mod notifications;
+#[cfg(target_os = "windows")]
 pub mod trayicons;
+
+#[cfg(not(target_os = "windows"))]
+pub use super::unsupported::{notifications, trayicons};
@@ -1,13 +1,16 @@
 // ~/veil/veil-backend/src/ipc/appdata/window.rs
 
 use serde::Serialize;
+#[cfg(target_os = "windows")]
 use std::{
     cell::RefCell,
     collections::HashMap,
     path::PathBuf,
     sync::Mutex,
 };
+#[cfg(target_os = "windows")]
 use sha2::{Digest, Sha256};
+#[cfg(target_os = "windows")]
 use windows::{
     core::BOOL,
     Win32::{
@@ -22,10 +25,9 @@ use windows::{
     },
 };
 
-use crate::{
-    ipc::registry::RegistryEntry,
-    info, warn, error,
-};
+use crate::ipc::registry::RegistryEntry;
+#[cfg(target_os = "windows")]
+use crate::{info, warn, error};
 
 #[derive(Serialize, Debug, Clone)]
 pub struct ActiveWindowInfo {
@@ -55,6 +57,7 @@ pub struct WindowPosition {
 
 pub struct ActiveWindowManager;
 
+#[cfg(target_os = "windows")]
 impl ActiveWindowManager {
     /// Enumerate all visible, non-minimized windows and map each to its nearest monitor.
     /// Focused window is tagged through metadata.focused.
@@ -301,4 +304,11 @@ impl ActiveWindowManager {
             exe_path,
         })
     }
-}
\ No newline at end of file
+}
+
+#[cfg(not(target_os = "windows"))]
+impl ActiveWindowManager {
+    pub fn enumerate_active_windows() -> Vec<RegistryEntry> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,49 @@
+// ~/veil/veil-backend/src/ipc/assets/mod.rs
+
+pub mod verify;
+pub mod prefs;
+pub mod provider;
+pub mod url_install;
+pub mod workshop;
+
+pub use prefs::{asset_key, note_asset_used, AssetPrefs};
+pub use provider::{AssetProvider, RemoteAsset};
+pub use url_install::{install_from_url, UrlInstall};
+pub use workshop::SteamWorkshop;
+pub use verify::{fix_asset_dir, verify_asset_dir, verify_assets, AssetIssue, AssetReport, MANIFEST_ISSUE_CODES};
+
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// How often the background integrity scan re-checks `Assets/`.
+const SCAN_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+static LAST_REPORT: OnceLock<Mutex<Vec<AssetReport>>> = OnceLock::new();
+
+fn last_report_slot() -> &'static Mutex<Vec<AssetReport>> {
+    LAST_REPORT.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// Run a scan (optionally repairing trivial issues) and cache the result.
+pub fn run_scan(category: Option<&str>, fix: bool) -> Vec<AssetReport> {
+    let reports = verify_assets(category, fix);
+    if category.is_none() {
+        if let Ok(mut slot) = last_report_slot().lock() {
+            *slot = reports.clone();
+        }
+    }
+    reports
+}
+
+/// Result of the most recent full scan.
+pub fn last_report() -> Vec<AssetReport> {
+    last_report_slot().lock().map(|r| r.clone()).unwrap_or_default()
+}
+
+/// Spawn the periodic integrity scan. Read-only: repairs only happen on request.
+pub fn start_integrity_scan() {
+    std::thread::spawn(|| loop {
+        run_scan(None, false);
+        std::thread::sleep(SCAN_INTERVAL);
+    });
+}
@@ -0,0 +1,137 @@
+// ~/veil/veil-backend/src/ipc/assets/prefs.rs
+//
+// Per-user asset favorites and recently-used list, stored in
+// `<root>/asset_prefs.json`. Assets are keyed `<category>/<id>` (see
+// `asset_key`), since ids are only unique within a category; bare ids written
+// by older versions still match their asset until it is next changed.
+//
+// The mutators only touch the value in memory. Both the daemon and the UI
+// process write the file, so changes meant to persist go through `update`,
+// which applies them to a fresh read.
+
+use serde::{Deserialize, Serialize};
+
+use crate::paths::asset_prefs_path;
+use crate::warn;
+
+/// Entries kept in the recently-used list.
+const MAX_RECENTS: usize = 20;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetPrefs {
+    #[serde(default)]
+    pub favorites: Vec<String>,
+    /// Most recent first.
+    #[serde(default)]
+    pub recents: Vec<String>,
+}
+
+/// Prefs key for the asset `id` in `category`.
+pub fn asset_key(category: &str, id: &str) -> String {
+    format!("{}/{}", category, id)
+}
+
+/// Whether a stored entry refers to `key`. A bare id from before keys had a
+/// category matches the id part.
+fn same_asset(entry: &str, key: &str) -> bool {
+    if entry.eq_ignore_ascii_case(key) {
+        return true;
+    }
+    !entry.contains('/') && key.rsplit_once('/').is_some_and(|(_, id)| id.eq_ignore_ascii_case(entry))
+}
+
+impl AssetPrefs {
+    pub fn load() -> Self {
+        std::fs::read_to_string(asset_prefs_path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize asset prefs: {}", e))?;
+        std::fs::write(asset_prefs_path(), text)
+            .map_err(|e| format!("Failed to write asset prefs: {}", e))
+    }
+
+    /// Load the prefs, apply `change`, save them and return the result.
+    pub fn update(change: impl FnOnce(&mut Self)) -> Result<Self, String> {
+        let mut prefs = Self::load();
+        change(&mut prefs);
+        prefs.save()?;
+        Ok(prefs)
+    }
+
+    pub fn is_favorite(&self, key: &str) -> bool {
+        self.favorites.iter().any(|f| same_asset(f, key))
+    }
+
+    /// Position in the recents list (0 = most recent).
+    pub fn recent_rank(&self, key: &str) -> Option<usize> {
+        self.recents.iter().position(|r| same_asset(r, key))
+    }
+
+    /// Mark or unmark `key` as a favorite.
+    pub fn set_favorite(&mut self, key: &str, favorite: bool) {
+        self.favorites.retain(|f| !same_asset(f, key));
+        if favorite {
+            self.favorites.push(key.to_string());
+        }
+    }
+
+    /// Move `key` to the front of the recents list.
+    pub fn record_recent(&mut self, key: &str) {
+        self.recents.retain(|r| !same_asset(r, key));
+        self.recents.insert(0, key.to_string());
+        self.recents.truncate(MAX_RECENTS);
+    }
+}
+
+/// Record an asset assignment; failures are logged, not propagated.
+pub fn note_asset_used(key: &str) {
+    if let Err(e) = AssetPrefs::update(|prefs| prefs.record_recent(key)) {
+        warn!("Failed to record recent asset '{}': {}", key, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_id_in_two_categories_is_two_assets() {
+        let mut prefs = AssetPrefs::default();
+        prefs.set_favorite(&asset_key("Wallpapers", "aurora"), true);
+
+        assert!(prefs.is_favorite("Wallpapers/aurora"));
+        assert!(!prefs.is_favorite("Themes/aurora"));
+
+        prefs.record_recent("Themes/aurora");
+        prefs.record_recent("Wallpapers/aurora");
+        assert_eq!(prefs.recent_rank("Wallpapers/aurora"), Some(0));
+        assert_eq!(prefs.recent_rank("Themes/aurora"), Some(1));
+    }
+
+    #[test]
+    fn bare_ids_match_until_rewritten() {
+        let mut prefs = AssetPrefs { favorites: vec!["aurora".into()], recents: vec!["aurora".into()] };
+        assert!(prefs.is_favorite("Wallpapers/aurora"));
+        assert_eq!(prefs.recent_rank("wallpapers/AURORA"), Some(0));
+
+        prefs.record_recent("Wallpapers/aurora");
+        assert_eq!(prefs.recents, vec!["Wallpapers/aurora".to_string()]);
+        prefs.set_favorite("Wallpapers/aurora", false);
+        assert!(prefs.favorites.is_empty());
+    }
+
+    #[test]
+    fn recents_are_capped() {
+        let mut prefs = AssetPrefs::default();
+        for i in 0..MAX_RECENTS + 5 {
+            prefs.record_recent(&asset_key("Wallpapers", &i.to_string()));
+        }
+        assert_eq!(prefs.recents.len(), MAX_RECENTS);
+        assert_eq!(prefs.recent_rank(&asset_key("Wallpapers", &(MAX_RECENTS + 4).to_string())), Some(0));
+    }
+}
@@ -0,0 +1,32 @@
+// ~/veil/veil-backend/src/ipc/assets/provider.rs
+//
+// Sources assets can be installed from. A provider lists what it offers and
+// copies a chosen item into `Assets/<category>/`; both calls may touch disk
+// or the network, so the UI runs them off its own thread.
+
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteAsset {
+    /// Provider-specific id, passed back to `install`.
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub preview: Option<PathBuf>,
+    /// Whether `install` has already put this item under `Assets/`.
+    pub installed: bool,
+}
+
+pub trait AssetProvider: Send {
+    /// Shown in status messages, e.g. "Steam Workshop".
+    fn name(&self) -> &'static str;
+
+    /// Items whose title, id or tags contain `query` (case-insensitive); an
+    /// empty query lists everything.
+    fn search(&self, query: &str) -> Result<Vec<RemoteAsset>, String>;
+
+    /// Install or refresh an item and return the asset folder it now lives in.
+    fn install(&self, id: &str) -> Result<PathBuf, String>;
+}
@@ -0,0 +1,285 @@
+// ~/veil/veil-backend/src/ipc/assets/url_install.rs
+//
+// Install an asset from a `.zip` bundle on the web. The archive must contain
+// a `manifest.json` with an `id`, either at its root or inside a single
+// top-level folder; that folder's contents become
+// `Assets/<category>/<id>/`. Downloads and extracted sizes are capped, and
+// entries that would land outside the asset folder are rejected. An existing
+// copy of the asset is kept until the new one is in place.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use zip::ZipArchive;
+
+use super::verify::{parse_manifest, verify_asset_dir, AssetIssue};
+use crate::paths::{assets_dir, ADDON_ASSETS_CATEGORY};
+use crate::{info, warn};
+
+/// Largest bundle accepted, compressed.
+pub const MAX_DOWNLOAD_BYTES: u64 = 200 * 1024 * 1024;
+/// Largest bundle accepted once unpacked; stops zip bombs.
+const MAX_EXTRACTED_BYTES: u64 = 1024 * 1024 * 1024;
+pub(crate) const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UrlInstall {
+    pub id: String,
+    pub category: String,
+    pub path: PathBuf,
+    /// An asset with the same id was already installed and has been replaced.
+    pub replaced: bool,
+    /// What `verify_asset_dir` found in the installed folder.
+    pub issues: Vec<AssetIssue>,
+}
+
+/// Folder-safe name: no separators, no `..`, nothing hidden.
+fn safe_component(s: &str) -> bool {
+    !s.is_empty()
+        && !s.starts_with('.')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ' '))
+}
+
+/// The existing category folder under `assets_root` that `category` names,
+/// matched case-insensitively. Categories come into being when an addon
+/// declares them (see `ensure_user_config_dirs`); `Addons` holds addons'
+/// private data and is not one.
+fn known_category(assets_root: &Path, category: &str) -> Result<String, String> {
+    let unknown = || format!("Unknown asset category '{}'", category);
+    if !safe_component(category) || category.eq_ignore_ascii_case(ADDON_ASSETS_CATEGORY) {
+        return Err(unknown());
+    }
+    fs::read_dir(assets_root)
+        .map_err(|_| unknown())?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .find(|name| name.eq_ignore_ascii_case(category))
+        .ok_or_else(unknown)
+}
+
+/// Download `url` (http or https) and install the bundle into `category`.
+/// Blocks until done; callers run it off the UI thread.
+pub fn install_from_url(url: &str, category: &str) -> Result<UrlInstall, String> {
+    let url = url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err("Only http:// and https:// URLs are supported".to_string());
+    }
+    let assets_root = assets_dir();
+    let category = known_category(&assets_root, category)?;
+
+    let bytes = download(url)?;
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Download is not a zip archive: {}", e))?;
+
+    let (prefix, manifest) = find_manifest(&mut archive)?;
+    let id = manifest
+        .get("id")
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .ok_or("manifest.json has no \"id\"")?;
+    if !safe_component(&id) {
+        return Err(format!("manifest.json id '{}' can't be used as a folder name", id));
+    }
+
+    let category_dir = assets_root.join(&category);
+    let target = category_dir.join(&id);
+    let staging = category_dir.join(format!(".{}.partial", id));
+    let _ = fs::remove_dir_all(&staging);
+    if let Err(e) = extract(&mut archive, &prefix, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    let backup = category_dir.join(format!(".{}.old", id));
+    let replaced = move_into_place(&staging, &target, &backup)?;
+
+    let (_, issues) = verify_asset_dir(&target, &category);
+    info!("[assets] Installed '{}' from {} to {} ({} issue(s))", id, url, target.display(), issues.len());
+    Ok(UrlInstall { id, category, path: target, replaced, issues })
+}
+
+/// Rename `staging` to `target`. An existing `target` is first renamed to
+/// `backup` and put back if the install fails, so a failed update leaves
+/// the old copy as it was. Returns whether a copy was replaced.
+fn move_into_place(staging: &Path, target: &Path, backup: &Path) -> Result<bool, String> {
+    let replaced = target.exists();
+    if replaced {
+        let _ = fs::remove_dir_all(backup);
+        if let Err(e) = fs::rename(target, backup) {
+            let _ = fs::remove_dir_all(staging);
+            return Err(format!("Failed to replace '{}': {}", target.display(), e));
+        }
+    }
+
+    if let Err(e) = fs::rename(staging, target) {
+        let _ = fs::remove_dir_all(staging);
+        if replaced {
+            if let Err(restore) = fs::rename(backup, target) {
+                warn!("[assets] Failed to restore '{}' from '{}': {}", target.display(), backup.display(), restore);
+            }
+        }
+        return Err(format!("Failed to install '{}': {}", target.display(), e));
+    }
+
+    if replaced {
+        if let Err(e) = fs::remove_dir_all(backup) {
+            warn!("[assets] Failed to remove the previous copy '{}': {}", backup.display(), e);
+        }
+    }
+    Ok(replaced)
+}
+
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let agent = ureq::AgentBuilder::new().timeout(DOWNLOAD_TIMEOUT).build();
+    let response = agent.get(url).call().map_err(|e| match e {
+        ureq::Error::Status(code, _) => format!("Download failed: HTTP {}", code),
+        other => format!("Download failed: {}", other),
+    })?;
+
+    let too_big = || format!("Download is larger than {} MB", MAX_DOWNLOAD_BYTES / (1024 * 1024));
+    if let Some(len) = response.header("Content-Length").and_then(|v| v.parse::<u64>().ok()) {
+        if len > MAX_DOWNLOAD_BYTES {
+            return Err(too_big());
+        }
+    }
+    // The header can be missing or wrong, so the read is capped as well.
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_DOWNLOAD_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Download failed: {}", e))?;
+    if bytes.len() as u64 > MAX_DOWNLOAD_BYTES {
+        return Err(too_big());
+    }
+    Ok(bytes)
+}
+
+/// The shallowest `manifest.json` and the folder it sits in ("" for the
+/// archive root). Deeper manifests belong to nested content and are ignored.
+fn find_manifest(archive: &mut ZipArchive<Cursor<Vec<u8>>>) -> Result<(String, Value), String> {
+    let mut best: Option<(usize, String)> = None;
+    for name in archive.file_names() {
+        let parts: Vec<&str> = name.split('/').collect();
+        if parts.last() != Some(&"manifest.json") || parts.len() > 2 {
+            continue;
+        }
+        if !matches!(&best, Some((depth, _)) if *depth <= parts.len()) {
+            best = Some((parts.len(), name.to_string()));
+        }
+    }
+    let (_, name) = best.ok_or("Archive has no manifest.json at its root or in its top folder")?;
+
+    let mut text = String::new();
+    archive
+        .by_name(&name)
+        .map_err(|e| e.to_string())?
+        .read_to_string(&mut text)
+        .map_err(|e| format!("Failed to read manifest.json: {}", e))?;
+    let manifest = parse_manifest(&text).map_err(|e| format!("manifest.json is not valid JSON: {}", e))?;
+    let prefix = name.strip_suffix("manifest.json").unwrap_or_default().to_string();
+    Ok((prefix, manifest))
+}
+
+/// Unpack the entries under `prefix` into `dest`. `enclosed_name` drops
+/// absolute paths and `..`, which is what keeps a crafted archive inside `dest`.
+fn extract(archive: &mut ZipArchive<Cursor<Vec<u8>>>, prefix: &str, dest: &Path) -> Result<(), String> {
+    fs::create_dir_all(dest).map_err(|e| format!("Failed to create '{}': {}", dest.display(), e))?;
+    let mut total: u64 = 0;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        let Some(rel) = entry.enclosed_name() else {
+            return Err(format!("Archive entry '{}' points outside the asset folder", entry.name()));
+        };
+        let Ok(rel) = rel.strip_prefix(prefix) else { continue };
+        if rel.as_os_str().is_empty() {
+            continue;
+        }
+        let out = dest.join(rel);
+        if entry.is_dir() {
+            fs::create_dir_all(&out).map_err(|e| format!("Failed to create '{}': {}", out.display(), e))?;
+            continue;
+        }
+
+        let size = entry.size();
+        total += size;
+        if total > MAX_EXTRACTED_BYTES {
+            return Err(format!("Archive unpacks to more than {} MB", MAX_EXTRACTED_BYTES / (1024 * 1024)));
+        }
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+        }
+        let mut file = fs::File::create(&out).map_err(|e| format!("Failed to write '{}': {}", out.display(), e))?;
+        // `size()` comes from the archive's own header, so the copy is capped too.
+        std::io::copy(&mut (&mut entry).take(size), &mut file)
+            .map_err(|e| format!("Failed to write '{}': {}", out.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("veil-urlinstall-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_asset(dir: &Path, marker: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("manifest.json"), format!(r#"{{"id":"a","name":"{}"}}"#, marker)).unwrap();
+    }
+
+    fn marker(dir: &Path) -> String {
+        fs::read_to_string(dir.join("manifest.json")).unwrap()
+    }
+
+    #[test]
+    fn only_existing_asset_categories_are_accepted() {
+        let root = temp_root("category");
+        fs::create_dir_all(root.join("Wallpapers")).unwrap();
+        fs::create_dir_all(root.join(ADDON_ASSETS_CATEGORY)).unwrap();
+
+        assert_eq!(known_category(&root, "wallpapers").unwrap(), "Wallpapers");
+        assert!(known_category(&root, "Addons").is_err());
+        assert!(known_category(&root, "addons").is_err());
+        assert!(known_category(&root, "Themes").is_err());
+        assert!(known_category(&root, "..").is_err());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn replacing_keeps_nothing_but_the_new_copy() {
+        let root = temp_root("replace");
+        let (staging, target, backup) = (root.join(".a.partial"), root.join("a"), root.join(".a.old"));
+        write_asset(&target, "old");
+        write_asset(&staging, "new");
+
+        assert!(move_into_place(&staging, &target, &backup).unwrap());
+        assert!(marker(&target).contains("new"));
+        assert!(!staging.exists() && !backup.exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn failed_install_restores_the_old_copy() {
+        let root = temp_root("restore");
+        let (staging, target, backup) = (root.join(".a.partial"), root.join("a"), root.join(".a.old"));
+        write_asset(&target, "old");
+        // No staging folder, so the final rename fails.
+
+        assert!(move_into_place(&staging, &target, &backup).is_err());
+        assert!(marker(&target).contains("old"));
+        assert!(!backup.exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,334 @@
+// ~/veil/veil-backend/src/ipc/assets/verify.rs
+//
+// Asset integrity checks: the manifest parses, files it references exist,
+// and its `id` is unique within the category. Problems are reported per
+// asset; a missing preview folder can be repaired in place.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::paths::{assets_dir, ADDON_ASSETS_CATEGORY};
+use crate::{info, warn};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetIssue {
+    /// Stable machine-readable code, e.g. "missing_preview".
+    pub code: &'static str,
+    pub message: String,
+    /// Whether `fix` can repair it without user input.
+    pub fixable: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetReport {
+    pub category: String,
+    pub id: Option<String>,
+    pub path: PathBuf,
+    pub issues: Vec<AssetIssue>,
+    /// Issue codes repaired during this run.
+    pub fixed: Vec<&'static str>,
+}
+
+/// Codes for a manifest that is missing, unparsable or lacks required fields;
+/// `assets.problems` and the UI's warning card list only these.
+pub const MANIFEST_ISSUE_CODES: &[&str] = &["missing_manifest", "invalid_manifest", "missing_id", "missing_name"];
+
+impl AssetIssue {
+    pub fn is_manifest_problem(&self) -> bool {
+        MANIFEST_ISSUE_CODES.contains(&self.code)
+    }
+
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), fixable: false }
+    }
+
+    fn fixable(code: &'static str, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), fixable: true }
+    }
+}
+
+/// Strict JSON first, then JSON5. The error is JSON5's, since that is the
+/// most lenient reading the manifest gets.
+pub(super) fn parse_manifest(text: &str) -> Result<Value, String> {
+    serde_json::from_str::<Value>(text)
+        .or_else(|_| json5::from_str::<Value>(text))
+        .map_err(|e| e.to_string())
+}
+
+/// Manifest file names, in the order `registry::discover_assets` tries them.
+const MANIFEST_NAMES: &[&str] = &["manifest.json", "meta.json"];
+
+/// The first manifest in `asset_dir` that can be read, with its file name.
+fn read_manifest(asset_dir: &Path) -> Option<(&'static str, String)> {
+    MANIFEST_NAMES
+        .iter()
+        .find_map(|name| std::fs::read_to_string(asset_dir.join(name)).ok().map(|text| (*name, text)))
+}
+
+fn is_wallpaper_category(category: &str) -> bool {
+    matches!(category.to_ascii_lowercase().as_str(), "wallpaper" | "wallpapers")
+}
+
+/// Check a single asset folder. `category` decides category-specific rules
+/// (wallpapers need an `index.html`). Duplicate ids are checked by `verify_assets`.
+pub fn verify_asset_dir(asset_dir: &Path, category: &str) -> (Option<String>, Vec<AssetIssue>) {
+    let mut issues = Vec::new();
+
+    let Some((file_name, text)) = read_manifest(asset_dir) else {
+        issues.push(AssetIssue::new("missing_manifest", "manifest.json (or meta.json) is missing or unreadable"));
+        return (None, issues);
+    };
+
+    let manifest = match parse_manifest(&text) {
+        Ok(m) => m,
+        Err(e) => {
+            issues.push(AssetIssue::new("invalid_manifest", format!("{} is not valid JSON: {}", file_name, e)));
+            return (None, issues);
+        }
+    };
+
+    let id = manifest.get("id").and_then(|v| v.as_str()).map(|s| s.to_string());
+    if id.is_none() {
+        issues.push(AssetIssue::new("missing_id", format!("{} has no \"id\"; the folder name is used instead", file_name)));
+    }
+    if manifest.get("name").and_then(|v| v.as_str()).is_none() {
+        issues.push(AssetIssue::new("missing_name", format!("{} has no \"name\"; the id is shown instead", file_name)));
+    }
+
+    let preview = manifest.get("metadata").and_then(|m| m.get("preview"));
+    let mut preview_refs: Vec<&str> = Vec::new();
+    match preview {
+        Some(Value::String(s)) => preview_refs.push(s),
+        Some(Value::Array(arr)) => preview_refs.extend(arr.iter().filter_map(|v| v.as_str())),
+        _ => {}
+    }
+
+    for rel in &preview_refs {
+        if let Some(dir) = rel.strip_suffix("/*") {
+            if !asset_dir.join(dir).is_dir() {
+                issues.push(AssetIssue::fixable("missing_preview_dir", format!("Preview folder '{}' does not exist", dir)));
+            }
+        } else if !asset_dir.join(rel).is_file() {
+            issues.push(AssetIssue::new("missing_preview", format!("Preview file '{}' does not exist", rel)));
+        }
+    }
+    if preview_refs.is_empty() && !asset_dir.join("preview").is_dir() {
+        issues.push(AssetIssue::fixable("missing_preview_dir", "No preview declared and no 'preview' folder"));
+    }
+
+    for key in ["index", "entry", "exe_path"] {
+        if let Some(rel) = manifest.get(key).and_then(|v| v.as_str()) {
+            if !asset_dir.join(rel).is_file() {
+                issues.push(AssetIssue::new("missing_file", format!("'{}' file '{}' does not exist", key, rel)));
+            }
+        }
+    }
+    if is_wallpaper_category(category)
+        && manifest.get("index").is_none()
+        && manifest.get("entry").is_none()
+        && !asset_dir.join("index.html").is_file()
+    {
+        issues.push(AssetIssue::new("missing_index", "Wallpaper has no index.html"));
+    }
+
+    (id, issues)
+}
+
+/// Create the preview folder for issues marked `missing_preview_dir`.
+pub fn fix_asset_dir(asset_dir: &Path, issues: &[AssetIssue]) -> Vec<&'static str> {
+    let mut fixed = Vec::new();
+    if issues.iter().any(|i| i.code == "missing_preview_dir") {
+        let manifest = read_manifest(asset_dir).and_then(|(_, text)| parse_manifest(&text).ok());
+        let declared = manifest
+            .as_ref()
+            .and_then(|m| m.get("metadata"))
+            .and_then(|m| m.get("preview"))
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.strip_suffix("/*"))
+            .unwrap_or("preview")
+            .to_string();
+        let dir = asset_dir.join(&declared);
+        match std::fs::create_dir_all(&dir) {
+            Ok(()) => {
+                info!("Created missing preview folder '{}'", dir.display());
+                fixed.push("missing_preview_dir");
+            }
+            Err(e) => warn!("Failed to create preview folder '{}': {}", dir.display(), e),
+        }
+    }
+    fixed
+}
+
+/// Asset folders in a category. Manifests may sit up to a few levels deep
+/// (same depth the library scans); a top-level folder with no manifest
+/// anywhere below it is returned as-is so it gets reported.
+fn asset_dirs_in_category(category_path: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let Ok(entries) = std::fs::read_dir(category_path) else { return out };
+
+    for entry in entries.flatten() {
+        let top = entry.path();
+        if !top.is_dir() {
+            continue;
+        }
+        let nested: Vec<PathBuf> = walkdir::WalkDir::new(&top)
+            .min_depth(1)
+            .max_depth(3)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy();
+                MANIFEST_NAMES.iter().any(|m| name.eq_ignore_ascii_case(m))
+            })
+            .filter_map(|e| e.path().parent().map(|p| p.to_path_buf()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if nested.is_empty() {
+            out.push(top);
+        } else {
+            out.extend(nested);
+        }
+    }
+    out
+}
+
+/// Verify every asset under `Assets/` (or one category). With `fix`, trivially
+/// reparable issues are repaired and dropped from the report.
+pub fn verify_assets(category_filter: Option<&str>, fix: bool) -> Vec<AssetReport> {
+    verify_assets_in(&assets_dir(), category_filter, fix)
+}
+
+/// `verify_assets` over the categories in `root`. `Assets/Addons` holds
+/// addons' private data rather than assets, and is skipped.
+fn verify_assets_in(root: &Path, category_filter: Option<&str>, fix: bool) -> Vec<AssetReport> {
+    let mut reports = Vec::new();
+    let Ok(categories) = std::fs::read_dir(root) else {
+        return reports;
+    };
+
+    for category in categories.flatten() {
+        let category_path = category.path();
+        if !category_path.is_dir() {
+            continue;
+        }
+        let category_name = category.file_name().to_string_lossy().to_string();
+        if category_name.eq_ignore_ascii_case(ADDON_ASSETS_CATEGORY) {
+            continue;
+        }
+        if let Some(filter) = category_filter {
+            if !category_name.eq_ignore_ascii_case(filter) {
+                continue;
+            }
+        }
+
+        let mut category_reports = Vec::new();
+        for asset_dir in asset_dirs_in_category(&category_path) {
+            let (id, mut issues) = verify_asset_dir(&asset_dir, &category_name);
+            let fixed = if fix { fix_asset_dir(&asset_dir, &issues) } else { Vec::new() };
+            issues.retain(|i| !fixed.contains(&i.code));
+
+            category_reports.push(AssetReport {
+                category: category_name.clone(),
+                id,
+                path: asset_dir,
+                issues,
+                fixed,
+            });
+        }
+
+        let mut by_id: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for report in &category_reports {
+            if let Some(id) = &report.id {
+                by_id.entry(id.to_ascii_lowercase()).or_default().push(report.path.clone());
+            }
+        }
+        for report in &mut category_reports {
+            let Some(id) = &report.id else { continue };
+            let paths = &by_id[&id.to_ascii_lowercase()];
+            if paths.len() > 1 {
+                let others: Vec<String> = paths
+                    .iter()
+                    .filter(|p| **p != report.path)
+                    .map(|p| p.display().to_string())
+                    .collect();
+                report.issues.push(AssetIssue::new(
+                    "duplicate_id",
+                    format!("id '{}' is also used by {}", id, others.join(", ")),
+                ));
+            }
+        }
+
+        reports.extend(category_reports);
+    }
+
+    let problems = reports.iter().filter(|r| !r.issues.is_empty()).count();
+    if problems > 0 {
+        warn!("Asset verification: {} of {} assets have problems", problems, reports.len());
+    } else {
+        info!("Asset verification: {} assets OK", reports.len());
+    }
+
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("veil-verify-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(path: &Path, text: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, text).unwrap();
+    }
+
+    #[test]
+    fn meta_json_only_asset_passes() {
+        let root = temp_dir("meta");
+        let asset = root.join("Widgets").join("clock");
+        write(&asset.join("meta.json"), r#"{ "id": "clock", "name": "Clock" }"#);
+        std::fs::create_dir_all(asset.join("preview")).unwrap();
+
+        let (id, issues) = verify_asset_dir(&asset, "Widgets");
+        assert_eq!(id.as_deref(), Some("clock"));
+        assert!(issues.is_empty(), "{:?}", issues);
+
+        let reports = verify_assets_in(&root, None, false);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].path, asset);
+        assert!(reports[0].issues.is_empty(), "{:?}", reports[0].issues);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn manifest_json_wins_over_meta_json() {
+        let root = temp_dir("both");
+        let asset = root.join("Widgets").join("clock");
+        write(&asset.join("manifest.json"), r#"{ "id": "from-manifest", "name": "Clock" }"#);
+        write(&asset.join("meta.json"), r#"{ "id": "from-meta", "name": "Clock" }"#);
+
+        let (id, _) = verify_asset_dir(&asset, "Widgets");
+        assert_eq!(id.as_deref(), Some("from-manifest"));
+        assert_eq!(verify_assets_in(&root, None, false).len(), 1);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn addon_private_data_is_not_verified() {
+        let root = temp_dir("addons");
+        write(&root.join("Addons").join("clock").join("state.bin"), "data");
+
+        let reports = verify_assets_in(&root, None, true);
+        assert!(reports.is_empty(), "{:?}", reports);
+        assert!(!root.join("Addons").join("clock").join("preview").exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,250 @@
+// ~/veil/veil-backend/src/ipc/assets/workshop.rs
+//
+// Steam Workshop as an asset provider, read-only for now: it lists items the
+// user is already subscribed to (whatever Steam has downloaded into
+// `steamapps/workshop/content/<app id>/` in any library folder) and installs
+// one by copying it to `Assets/<category>/workshop-<item id>/`. Items without
+// a VEIL `manifest.json` get one generated from their `project.json`.
+//
+// Nothing here talks to Steam itself, so subscribing and updates still go
+// through the Steam client.
+
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::provider::{AssetProvider, RemoteAsset};
+use crate::info;
+use crate::paths::asset_category_dir;
+
+pub struct SteamWorkshop {
+    app_id: u32,
+    category: String,
+}
+
+impl SteamWorkshop {
+    pub fn new(app_id: u32, category: &str) -> Self {
+        Self { app_id, category: category.to_string() }
+    }
+
+    fn install_dir(&self, item_id: &str) -> PathBuf {
+        asset_category_dir(&self.category).join(format!("workshop-{}", item_id))
+    }
+
+    /// Downloaded item folders across every Steam library, keyed by item id.
+    /// The first library that has an item wins.
+    fn item_dirs(&self) -> Result<Vec<(String, PathBuf)>, String> {
+        let root = steam_root().ok_or("Steam installation not found")?;
+        let mut items: Vec<(String, PathBuf)> = Vec::new();
+        for library in library_folders(&root) {
+            let content = library
+                .join("steamapps")
+                .join("workshop")
+                .join("content")
+                .join(self.app_id.to_string());
+            let Ok(entries) = fs::read_dir(&content) else { continue };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let id = entry.file_name().to_string_lossy().to_string();
+                if path.is_dir() && is_item_id(&id) && !items.iter().any(|(i, _)| *i == id) {
+                    items.push((id, path));
+                }
+            }
+        }
+        items.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(items)
+    }
+
+    fn describe(&self, id: &str, dir: &Path) -> RemoteAsset {
+        let manifest = read_json(&dir.join("manifest.json"));
+        let project = read_json(&dir.join("project.json"));
+        let meta = manifest.get("metadata").cloned().unwrap_or(Value::Null);
+
+        let str_of = |v: &Value, key: &str| v.get(key).and_then(|s| s.as_str()).map(|s| s.to_string());
+        let title = str_of(&manifest, "name")
+            .or_else(|| str_of(&project, "title"))
+            .unwrap_or_else(|| id.to_string());
+        let description = str_of(&meta, "short_description").or_else(|| str_of(&project, "description"));
+        let tags = meta
+            .get("tags")
+            .or_else(|| project.get("tags"))
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect())
+            .unwrap_or_default();
+        let preview = str_of(&meta, "preview")
+            .or_else(|| str_of(&project, "preview"))
+            .map(|p| dir.join(p))
+            .filter(|p| p.is_file());
+
+        RemoteAsset {
+            id: id.to_string(),
+            title,
+            description,
+            tags,
+            preview,
+            installed: self.install_dir(id).join("manifest.json").is_file(),
+        }
+    }
+}
+
+impl AssetProvider for SteamWorkshop {
+    fn name(&self) -> &'static str {
+        "Steam Workshop"
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<RemoteAsset>, String> {
+        let query = query.trim().to_lowercase();
+        Ok(self
+            .item_dirs()?
+            .iter()
+            .map(|(id, dir)| self.describe(id, dir))
+            .filter(|item| {
+                query.is_empty()
+                    || item.id.contains(&query)
+                    || item.title.to_lowercase().contains(&query)
+                    || item.tags.iter().any(|t| t.to_lowercase().contains(&query))
+            })
+            .collect())
+    }
+
+    fn install(&self, id: &str) -> Result<PathBuf, String> {
+        if !is_item_id(id) {
+            return Err(format!("Invalid Workshop item id '{}'", id));
+        }
+        let (_, source) = self
+            .item_dirs()?
+            .into_iter()
+            .find(|(i, _)| i == id)
+            .ok_or_else(|| format!("Workshop item {} is not downloaded; subscribe to it in Steam first", id))?;
+
+        // Copy next to the target first so a failed copy leaves the installed
+        // version alone.
+        let target = self.install_dir(id);
+        let staging = target.with_file_name(format!(".workshop-{}.partial", id));
+        let _ = fs::remove_dir_all(&staging);
+        copy_dir(&source, &staging)?;
+
+        let manifest_path = staging.join("manifest.json");
+        if !manifest_path.is_file() {
+            let manifest = generated_manifest(self.app_id, id, &read_json(&source.join("project.json")));
+            let text = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+            fs::write(&manifest_path, text).map_err(|e| format!("Failed to write '{}': {}", manifest_path.display(), e))?;
+        }
+
+        if target.exists() {
+            fs::remove_dir_all(&target).map_err(|e| format!("Failed to replace '{}': {}", target.display(), e))?;
+        }
+        fs::rename(&staging, &target).map_err(|e| format!("Failed to install '{}': {}", target.display(), e))?;
+        info!("[workshop] Installed item {} (app {}) to {}", id, self.app_id, target.display());
+        Ok(target)
+    }
+}
+
+fn is_item_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_digit())
+}
+
+fn read_json(path: &Path) -> Value {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|t| serde_json::from_str(&t).ok().or_else(|| json5::from_str(&t).ok()))
+        .unwrap_or(Value::Null)
+}
+
+/// VEIL manifest for an item that only ships a Wallpaper Engine style
+/// `project.json`.
+fn generated_manifest(app_id: u32, item_id: &str, project: &Value) -> Value {
+    let title = project.get("title").and_then(|v| v.as_str()).unwrap_or(item_id);
+    let mut metadata = json!({
+        "tags": project.get("tags").cloned().unwrap_or(json!([])),
+        "source": format!("steam_workshop:{}/{}", app_id, item_id),
+    });
+    if let Some(desc) = project.get("description").and_then(|v| v.as_str()) {
+        metadata["short_description"] = json!(desc);
+    }
+    if let Some(preview) = project.get("preview").and_then(|v| v.as_str()) {
+        metadata["preview"] = json!(preview);
+    }
+    json!({
+        "id": format!("workshop-{}", item_id),
+        "name": title,
+        "metadata": metadata,
+    })
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    for entry in WalkDir::new(from).into_iter().flatten() {
+        let Ok(rel) = entry.path().strip_prefix(from) else { continue };
+        let dest = to.join(rel);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest).map_err(|e| format!("Failed to create '{}': {}", dest.display(), e))?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &dest).map_err(|e| format!("Failed to copy '{}': {}", entry.path().display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// The Steam install folder, which is also the first library.
+fn steam_root() -> Option<PathBuf> {
+    registry_steam_path()
+        .or_else(|| {
+            std::env::var_os("ProgramFiles(x86)").map(|p| PathBuf::from(p).join("Steam"))
+        })
+        .or_else(|| crate::paths::user_home_dir().map(|h| h.join(".local").join("share").join("Steam")))
+        .filter(|p| p.join("steamapps").is_dir())
+}
+
+#[cfg(windows)]
+fn registry_steam_path() -> Option<PathBuf> {
+    use windows::core::PCWSTR;
+    use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_SZ};
+
+    let wide = |s: &str| s.encode_utf16().chain(std::iter::once(0)).collect::<Vec<u16>>();
+    let subkey = wide("Software\\Valve\\Steam");
+    let value = wide("SteamPath");
+    let mut buf = [0u16; 512];
+    let mut size = (buf.len() * 2) as u32;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            PCWSTR(subkey.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            RRF_RT_REG_SZ,
+            None,
+            Some(buf.as_mut_ptr() as *mut _),
+            Some(&mut size),
+        )
+    };
+    if status.is_err() {
+        return None;
+    }
+    let len = (size as usize / 2).min(buf.len());
+    let text = String::from_utf16_lossy(&buf[..len]).trim_end_matches('\0').to_string();
+    (!text.is_empty()).then(|| PathBuf::from(text))
+}
+
+#[cfg(not(windows))]
+fn registry_steam_path() -> Option<PathBuf> {
+    None
+}
+
+/// Library folders from `steamapps/libraryfolders.vdf`, starting with `root`.
+/// Only the `"path"` entries matter, so the KeyValues file is read line by line.
+fn library_folders(root: &Path) -> Vec<PathBuf> {
+    let mut folders = vec![root.to_path_buf()];
+    let Ok(text) = fs::read_to_string(root.join("steamapps").join("libraryfolders.vdf")) else {
+        return folders;
+    };
+    for line in text.lines() {
+        let tokens: Vec<&str> = line.split('"').skip(1).step_by(2).collect();
+        if let ["path", path] = tokens.as_slice() {
+            let path = PathBuf::from(path.replace("\\\\", "\\"));
+            if !folders.contains(&path) {
+                folders.push(path);
+            }
+        }
+    }
+    folders
+}
@@ -6,23 +6,33 @@
 // *maximum* intervals between collections — not polling sleeps.
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Condvar, Mutex, OnceLock, RwLock,
     },
     thread,
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use crate::{
     ipc::registry::{
         global_registry, pull_sysdata_cpu,
         merge_sysdata_tier, RegistryEntry,
     },
-    config::{fast_pull_rate_ms, slow_pull_rate_ms, pull_paused, ui_data_exception_enabled},
+    config::{
+        fast_pull_rate_ms, slow_pull_rate_ms, pull_paused, ui_data_exception_enabled,
+        restart_stalled_updaters, pull_rate_override,
+    },
 };
+use crate::{error, info, warn};
 use crate::ipc::{
     appdata::window::ActiveWindowManager,
+    smoothing,
+    sysdata::{
+        display::{MonitorInfo, MonitorManager},
+        mock::{mock_active_windows, mock_enabled, mock_monitors, mock_sysdata},
+        process_snapshot::ProcessSnapshot,
+    },
 };
 use serde_json::json;
 
@@ -31,6 +41,8 @@ const IDLE_SLEEP_MS: u64 = 250;
 
 static LAST_UI_HEARTBEAT_MS: AtomicU64 = AtomicU64::new(0);
 static EXPLICIT_TRACKED_SECTIONS: OnceLock<RwLock<HashSet<String>>> = OnceLock::new();
+/// Sections held open by `sysdata.subscribe` streams, counted per subscription.
+static SUBSCRIBED_SECTIONS: OnceLock<Mutex<HashMap<&'static str, usize>>> = OnceLock::new();
 
 /// Global wake signal.  Any code that changes tracking demands or config
 /// should call `wake_updaters()` so sleeping threads re-evaluate immediately.
@@ -65,11 +77,15 @@ const TRACKABLE_SECTIONS: &[&str] = &[
     "processes", "appdata",
 ];
 
+/// Sections still collected in safe mode. The rest go through drivers,
+/// WinRT or hooks, and may be what kept the last launches from staying up.
+const SAFE_MODE_SECTIONS: &[&str] = &["time", "cpu", "ram", "storage", "system"];
+
 fn tracked_sections() -> &'static RwLock<HashSet<String>> {
     EXPLICIT_TRACKED_SECTIONS.get_or_init(|| RwLock::new(HashSet::new()))
 }
 
-fn normalize_section(section: &str) -> Option<&'static str> {
+pub fn normalize_section(section: &str) -> Option<&'static str> {
     match section.to_ascii_lowercase().as_str() {
         "display" | "displays" => Some("displays"),
         "time" => Some("time"),
@@ -93,7 +109,7 @@ fn normalize_section(section: &str) -> Option<&'static str> {
     }
 }
 
-fn section_to_internal_category(section: &str) -> Option<&'static str> {
+pub fn section_to_internal_category(section: &str) -> Option<&'static str> {
     match normalize_section(section)? {
         "displays" => Some("display"),
         other => Some(other),
@@ -123,15 +139,51 @@ pub fn set_explicit_tracking_demands(sections: &[String]) {
     }
 }
 
+fn subscribed_sections() -> &'static Mutex<HashMap<&'static str, usize>> {
+    SUBSCRIBED_SECTIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Keep `sections` collected while a subscription needs them. Pair with
+/// `unsubscribe_sections`; overlapping subscriptions are counted.
+pub fn subscribe_sections(sections: &[&'static str]) {
+    {
+        let mut counts = subscribed_sections().lock().unwrap();
+        for section in sections {
+            *counts.entry(section).or_insert(0) += 1;
+        }
+    }
+    wake_updaters();
+}
+
+pub fn unsubscribe_sections(sections: &[&'static str]) {
+    let mut counts = subscribed_sections().lock().unwrap();
+    for section in sections {
+        if let Some(count) = counts.get_mut(section) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(section);
+            }
+        }
+    }
+}
+
 pub fn section_tracking_enabled(section: &str) -> bool {
     let Some(normalized) = normalize_section(section) else {
         return false;
     };
 
+    if crate::safe_mode::safe_mode() && !SAFE_MODE_SECTIONS.contains(&normalized) {
+        return false;
+    }
+
     if tracked_sections().read().unwrap().contains(normalized) {
         return true;
     }
 
+    if subscribed_sections().lock().unwrap().contains_key(normalized) {
+        return true;
+    }
+
     if !ui_data_exception_enabled() {
         return false;
     }
@@ -157,17 +209,90 @@ pub fn demand_tracking_active() -> bool {
     !pull_paused() && TRACKABLE_SECTIONS.iter().any(|section| section_tracking_enabled(section))
 }
 
-fn single_sys_entry(category: &str) -> Option<RegistryEntry> {
+fn current_monitors() -> Vec<MonitorInfo> {
+    if mock_enabled() {
+        mock_monitors()
+    } else {
+        MonitorManager::enumerate_monitors()
+    }
+}
+
+/// The `display` section's metadata.
+pub(crate) fn displays_json(monitors: Vec<MonitorInfo>) -> serde_json::Value {
+    json!({
+        "monitors": monitors.into_iter().map(|m| json!({
+            "id": m.id,
+            "primary": m.primary,
+            "x": m.x,
+            "y": m.y,
+            "width": m.width,
+            "height": m.height,
+            "scale": m.scale,
+            "dpi": m.dpi,
+            "refresh_rate_hz": m.refresh_rate_hz,
+            "color_depth_bits": m.color_depth_bits,
+            "bits_per_channel": m.bits_per_channel,
+            "orientation": m.orientation,
+            "aspect_ratio": m.aspect_ratio,
+            "device_name": m.device_name,
+            "monitor_name": m.monitor_name,
+            "connection_type": m.connection_type,
+            "hdr_supported": m.hdr_supported,
+            "physical_width_mm": m.physical_width_mm,
+            "physical_height_mm": m.physical_height_mm,
+            "manufacturer": m.manufacturer,
+            "product_code": m.product_code,
+            "serial_number": m.serial_number,
+            "year_of_manufacture": m.year_of_manufacture,
+            "icc_profile_name": m.icc_profile_name,
+            "gamma_ramp_identity": m.gamma_ramp_identity,
+            "brightness_percent": m.brightness_percent,
+            "device_id": m.device_id,
+            "stable_id": m.stable_id,
+        })).collect::<Vec<_>>()
+    })
+}
+
+/// Sections whose collectors read the process table.
+const PROCESS_SNAPSHOT_SECTIONS: [&str; 4] = ["gpu", "ram", "network", "processes"];
+
+/// Collect one section. `procs` is the tier's shared process snapshot; a
+/// section that needs one and isn't given one takes its own.
+fn single_sys_entry(category: &str, procs: Option<&ProcessSnapshot>) -> Option<RegistryEntry> {
+    if mock_enabled() && category != "display" {
+        return mock_sysdata(category).map(|metadata| RegistryEntry {
+            id: category.into(),
+            category: category.into(),
+            subtype: "system".into(),
+            metadata,
+            path: std::path::PathBuf::new(),
+            exe_path: "".into(),
+        });
+    }
+
+    let captured;
+    let procs = match procs {
+        Some(procs) => procs,
+        None => {
+            captured = if PROCESS_SNAPSHOT_SECTIONS.contains(&category) {
+                ProcessSnapshot::capture()
+            } else {
+                ProcessSnapshot::default()
+            };
+            &captured
+        }
+    };
+
     match category {
         "cpu" => Some(pull_sysdata_cpu()),
-        "gpu" => Some(RegistryEntry { id: "gpu".into(), category: "gpu".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::gpu::get_gpu_json(), path: std::path::PathBuf::new(), exe_path: "".into() }),
-        "ram" => Some(RegistryEntry { id: "ram".into(), category: "ram".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::ram::get_ram_json(), path: std::path::PathBuf::new(), exe_path: "".into() }),
+        "gpu" => Some(RegistryEntry { id: "gpu".into(), category: "gpu".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::gpu::get_gpu_json(procs), path: std::path::PathBuf::new(), exe_path: "".into() }),
+        "ram" => Some(RegistryEntry { id: "ram".into(), category: "ram".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::ram::get_ram_json(procs), path: std::path::PathBuf::new(), exe_path: "".into() }),
         "storage" => Some(RegistryEntry { id: "storage".into(), category: "storage".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::storage::get_storage_json(), path: std::path::PathBuf::new(), exe_path: "".into() }),
-        "network" => Some(RegistryEntry { id: "network".into(), category: "network".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::network::get_network_json(), path: std::path::PathBuf::new(), exe_path: "".into() }),
+        "network" => Some(RegistryEntry { id: "network".into(), category: "network".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::network::get_network_json(procs), path: std::path::PathBuf::new(), exe_path: "".into() }),
         "bluetooth" => Some(RegistryEntry { id: "bluetooth".into(), category: "bluetooth".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::bluetooth::get_bluetooth_json(), path: std::path::PathBuf::new(), exe_path: "".into() }),
         "wifi" => Some(RegistryEntry { id: "wifi".into(), category: "wifi".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::wifi::get_wifi_json(), path: std::path::PathBuf::new(), exe_path: "".into() }),
         "system" => Some(RegistryEntry { id: "system".into(), category: "system".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::system::get_system_json(), path: std::path::PathBuf::new(), exe_path: "".into() }),
-        "processes" => Some(RegistryEntry { id: "processes".into(), category: "processes".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::processes::get_processes_json(), path: std::path::PathBuf::new(), exe_path: "".into() }),
+        "processes" => Some(RegistryEntry { id: "processes".into(), category: "processes".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::processes::get_processes_json(procs), path: std::path::PathBuf::new(), exe_path: "".into() }),
         "audio" => Some(RegistryEntry { id: "audio".into(), category: "audio".into(), subtype: "system".into(), metadata: crate::ipc::sysdata::audio::get_audio_json(), path: std::path::PathBuf::new(), exe_path: "".into() }),
         "media" => {
             crate::ipc::sysdata::media::refresh_media_session_cache_if_due();
@@ -182,33 +307,7 @@ fn single_sys_entry(category: &str) -> Option<RegistryEntry> {
             id: "display_group".into(),
             category: "display".into(),
             subtype: "system".into(),
-            metadata: json!({
-                "monitors": crate::ipc::sysdata::display::MonitorManager::enumerate_monitors().into_iter().map(|m| json!({
-                    "id": m.id,
-                    "primary": m.primary,
-                    "x": m.x,
-                    "y": m.y,
-                    "width": m.width,
-                    "height": m.height,
-                    "scale": m.scale,
-                    "dpi": m.dpi,
-                    "refresh_rate_hz": m.refresh_rate_hz,
-                    "color_depth_bits": m.color_depth_bits,
-                    "bits_per_channel": m.bits_per_channel,
-                    "orientation": m.orientation,
-                    "aspect_ratio": m.aspect_ratio,
-                    "device_name": m.device_name,
-                    "monitor_name": m.monitor_name,
-                    "connection_type": m.connection_type,
-                    "hdr_supported": m.hdr_supported,
-                    "physical_width_mm": m.physical_width_mm,
-                    "physical_height_mm": m.physical_height_mm,
-                    "manufacturer": m.manufacturer,
-                    "product_code": m.product_code,
-                    "serial_number": m.serial_number,
-                    "year_of_manufacture": m.year_of_manufacture,
-                })).collect::<Vec<_>>()
-            }),
+            metadata: displays_json(current_monitors()),
             path: std::path::PathBuf::new(),
             exe_path: "".into(),
         }),
@@ -223,155 +322,414 @@ fn single_sys_entry(category: &str) -> Option<RegistryEntry> {
 //   2. Briefly acquires a write lock to merge results.
 //   3. Sleeps via `interruptible_sleep()` (Condvar) so it wakes
 //      instantly when demands or config change.
+//
+// Every loop iteration stamps the tier's last-tick time; the watchdog
+// below uses it to spot a tier stuck inside a collector call.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tier {
+    Fast,
+    Appdata,
+    Cpu,
+    Slow,
+}
 
-/// Start registry updater threads — fast, appdata, cpu, and slow tiers.
-pub fn start_registry_updater() {
+const TIERS: [Tier; 4] = [Tier::Fast, Tier::Appdata, Tier::Cpu, Tier::Slow];
 
-    // ── Fast-tier (time, audio, keyboard, mouse, idle, power, display) ──
-    thread::spawn(move || {
-        loop {
-            if pull_paused() {
-                interruptible_sleep(Duration::from_millis(50));
-                continue;
-            }
+impl Tier {
+    fn name(self) -> &'static str {
+        match self {
+            Tier::Fast => "fast",
+            Tier::Appdata => "appdata",
+            Tier::Cpu => "cpu",
+            Tier::Slow => "slow",
+        }
+    }
 
-            let mut fast_requested = Vec::<&str>::new();
-            for section in ["time", "keyboard", "mouse", "audio", "media", "idle"] {
-                if section_tracking_enabled(section) {
-                    if let Some(cat) = section_to_internal_category(section) {
-                        fast_requested.push(cat);
-                    }
-                }
-            }
+    fn start_delay(self) -> Duration {
+        match self {
+            Tier::Fast => Duration::ZERO,
+            Tier::Appdata => Duration::from_millis(5),
+            Tier::Cpu => Duration::from_millis(10),
+            Tier::Slow => Duration::from_millis(15),
+        }
+    }
 
-            if fast_requested.is_empty() {
-                interruptible_sleep(Duration::from_millis(IDLE_SLEEP_MS));
-                continue;
-            }
+    /// Longest a healthy iteration of this tier should take.
+    fn stall_threshold_ms(self) -> u64 {
+        let rate = match self {
+            Tier::Fast | Tier::Appdata => fast_pull_rate_ms(),
+            Tier::Cpu | Tier::Slow => slow_pull_rate_ms(),
+        };
+        STALL_THRESHOLD_MIN_MS.max(rate.saturating_mul(3))
+    }
 
-            let rate = fast_pull_rate_ms().max(1);
+    /// Run one iteration and return how long to sleep before the next.
+    fn step(self) -> Duration {
+        match self {
+            Tier::Fast => fast_tier_step(),
+            Tier::Appdata => appdata_tier_step(),
+            Tier::Cpu => cpu_tier_step(),
+            Tier::Slow => slow_tier_step(),
+        }
+    }
+}
 
-            // Collect outside the lock
-            let fast_data: Vec<RegistryEntry> = fast_requested
-                .iter()
-                .filter_map(|cat| single_sys_entry(cat))
-                .collect();
+/// Slow tier steps longer than this are logged with a per-section breakdown.
+const SLOW_STEP_LOG_THRESHOLD: Duration = Duration::from_secs(2);
 
-            // Merge under write lock (brief)
-            {
-                let mut reg = global_registry().write().unwrap();
-                let merged = merge_sysdata_tier(&reg.sysdata, fast_data, &fast_requested);
-                if reg.sysdata != merged {
-                    reg.sysdata = merged;
-                }
-            }
+/// Sections collected by the fast tier; appdata has its own thread at the same rate.
+const FAST_TIER_SECTIONS: [&str; 6] = ["time", "keyboard", "mouse", "audio", "media", "idle"];
+const SLOW_TIER_SECTIONS: [&str; 10] = [
+    "gpu", "ram", "storage", "network",
+    "bluetooth", "wifi", "system", "processes",
+    "power", "displays",
+];
+
+/// Whether `section` (normalized) runs at the fast rate, and so takes
+/// `FAST_RATE_RANGE` for its `pull_rate_overrides` entry.
+pub fn is_fast_rate_section(section: &str) -> bool {
+    section == "appdata" || FAST_TIER_SECTIONS.contains(&section)
+}
+
+/// Unix ms of each section's last collection, whether or not its value changed.
+static SECTION_UPDATED_MS: OnceLock<Mutex<HashMap<&'static str, u64>>> = OnceLock::new();
+
+fn stamp_sections(sections: &[&'static str]) {
+    let now = now_ms();
+    let mut updated = SECTION_UPDATED_MS.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+    for &section in sections {
+        updated.insert(section, now);
+    }
+}
+
+/// When `section` (normalized) was last collected, if ever.
+pub fn section_last_updated_ms(section: &str) -> Option<u64> {
+    SECTION_UPDATED_MS.get()?.lock().unwrap().get(section).copied()
+}
+
+/// Configured collection interval for `section` (normalized).
+pub fn section_interval_ms(section: &str) -> u64 {
+    pull_rate_override(section).unwrap_or_else(|| {
+        if is_fast_rate_section(section) { fast_pull_rate_ms() } else { slow_pull_rate_ms() }
+    })
+}
 
-            interruptible_sleep(Duration::from_millis(rate));
+/// Next collection time per section. Sections sharing a tier thread each keep
+/// their own timer, so a `pull_rate_overrides` entry needs no extra thread.
+static NEXT_DUE: OnceLock<Mutex<HashMap<&'static str, Instant>>> = OnceLock::new();
+
+/// Split `sections` into those due now (rescheduling each) and the time until
+/// the next one comes due. A section without an override runs at `tier_rate_ms`.
+fn due_sections(sections: &[&'static str], tier_rate_ms: u64) -> (Vec<&'static str>, Duration) {
+    let now = Instant::now();
+    let mut next_due = NEXT_DUE.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+    let mut due = Vec::new();
+    let mut sleep = Duration::from_millis(tier_rate_ms);
+    for &section in sections {
+        let rate = Duration::from_millis(pull_rate_override(section).unwrap_or(tier_rate_ms).max(1));
+        let at = next_due.entry(section).or_insert(now);
+        // A shortened interval takes effect now rather than after the old one runs out.
+        if *at > now + rate {
+            *at = now + rate;
         }
-    });
-
-    // ── Appdata (active windows) ──
-    thread::spawn(move || {
-        thread::sleep(Duration::from_millis(5));
-        loop {
-            if pull_paused() {
-                interruptible_sleep(Duration::from_millis(100));
-                continue;
-            }
+        if *at <= now {
+            due.push(section);
+            *at = now + rate;
+        }
+        sleep = sleep.min(at.saturating_duration_since(now));
+    }
+    (due, sleep)
+}
 
-            if !section_tracking_enabled("appdata") {
-                interruptible_sleep(Duration::from_millis(IDLE_SLEEP_MS));
-                continue;
-            }
+/// Collect `section` (normalized) on its tier's next pass instead of waiting
+/// out its interval, e.g. after a command changed what it reports.
+pub fn refresh_section_now(section: &'static str) {
+    if let Some(next_due) = NEXT_DUE.get() {
+        next_due.lock().unwrap().insert(section, Instant::now());
+    }
+    wake_updaters();
+}
 
-            let appdata_rate = fast_pull_rate_ms().max(25);
-            let appdata = ActiveWindowManager::enumerate_active_windows();
+const STALL_THRESHOLD_MIN_MS: u64 = 30_000;
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+/// Restarts per tier before the watchdog gives up and only reports.
+const MAX_TIER_RESTARTS: u64 = 3;
+
+static TIER_LAST_TICK_MS: [AtomicU64; 4] = [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+static TIER_GENERATION: [AtomicU64; 4] = [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+static TIER_RESTARTS: [AtomicU64; 4] = [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+/// Duration of each tier's most recent iteration.
+static TIER_LAST_STEP_MS: [AtomicU64; 4] = [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+static TIER_STALLED: [AtomicBool; 4] = [AtomicBool::new(false), AtomicBool::new(false), AtomicBool::new(false), AtomicBool::new(false)];
+
+fn fast_tier_step() -> Duration {
+    if pull_paused() {
+        return Duration::from_millis(50);
+    }
 
-            {
-                let mut reg = global_registry().write().unwrap();
-                if reg.appdata != appdata {
-                    reg.appdata = appdata;
-                }
-            }
+    let tracked: Vec<&'static str> = FAST_TIER_SECTIONS
+        .into_iter()
+        .filter(|section| section_tracking_enabled(section))
+        .collect();
+    if tracked.is_empty() {
+        return Duration::from_millis(IDLE_SLEEP_MS);
+    }
 
-            interruptible_sleep(Duration::from_millis(appdata_rate));
+    let (due, sleep) = due_sections(&tracked, fast_pull_rate_ms().max(1));
+    let fast_requested: Vec<&str> = due
+        .iter()
+        .filter_map(|section| section_to_internal_category(section))
+        .collect();
+    if fast_requested.is_empty() {
+        return sleep;
+    }
+
+    // Collect outside the lock
+    let mut fast_data: Vec<RegistryEntry> = fast_requested
+        .iter()
+        .filter_map(|cat| single_sys_entry(cat, None))
+        .collect();
+    smoothing::apply(&mut fast_data);
+
+    // Merge under write lock (brief)
+    {
+        let mut reg = global_registry().write().unwrap();
+        let merged = merge_sysdata_tier(&reg.sysdata, fast_data, &fast_requested);
+        if reg.sysdata != merged {
+            reg.sysdata = merged;
         }
-    });
-
-    // ── CPU (slow, isolated) ──
-    thread::spawn(move || {
-        thread::sleep(Duration::from_millis(10));
-        loop {
-            if pull_paused() {
-                interruptible_sleep(Duration::from_millis(100));
-                continue;
-            }
+    }
+    stamp_sections(&due);
 
-            if !section_tracking_enabled("cpu") {
-                interruptible_sleep(Duration::from_millis(IDLE_SLEEP_MS));
-                continue;
-            }
+    sleep
+}
 
-            let rate = slow_pull_rate_ms().max(50);
-            let cpu_entry = pull_sysdata_cpu();
+fn appdata_tier_step() -> Duration {
+    if pull_paused() {
+        return Duration::from_millis(100);
+    }
 
-            {
-                let mut reg = global_registry().write().unwrap();
-                let merged = merge_sysdata_tier(&reg.sysdata, vec![cpu_entry], &["cpu"]);
-                if reg.sysdata != merged {
-                    reg.sysdata = merged;
-                }
-            }
+    if !section_tracking_enabled("appdata") {
+        return Duration::from_millis(IDLE_SLEEP_MS);
+    }
+
+    let (due, sleep) = due_sections(&["appdata"], fast_pull_rate_ms().max(25));
+    if due.is_empty() {
+        return sleep;
+    }
+    let appdata = if mock_enabled() {
+        mock_active_windows()
+    } else {
+        ActiveWindowManager::enumerate_active_windows()
+    };
 
-            interruptible_sleep(Duration::from_millis(rate));
+    {
+        let mut reg = global_registry().write().unwrap();
+        if reg.appdata != appdata {
+            reg.appdata = appdata;
         }
-    });
-
-    // ── Slow-tier (gpu, ram, storage, network, bluetooth, wifi, system, processes) ──
-    thread::spawn(move || {
-        thread::sleep(Duration::from_millis(15));
-        loop {
-            if pull_paused() {
-                interruptible_sleep(Duration::from_millis(100));
-                continue;
-            }
+    }
+    stamp_sections(&due);
 
-            let slow_sections: &[&str] = &[
-                "gpu", "ram", "storage", "network",
-                "bluetooth", "wifi", "system", "processes",
-                "power", "displays",
-            ];
-
-            let mut requested_slow = Vec::<&str>::new();
-            for section in slow_sections {
-                if section_tracking_enabled(section) {
-                    if let Some(cat) = section_to_internal_category(section) {
-                        requested_slow.push(cat);
-                    }
-                }
-            }
+    sleep
+}
 
-            if requested_slow.is_empty() {
-                interruptible_sleep(Duration::from_millis(IDLE_SLEEP_MS));
-                continue;
+fn cpu_tier_step() -> Duration {
+    if pull_paused() {
+        return Duration::from_millis(100);
+    }
+
+    if !section_tracking_enabled("cpu") {
+        return Duration::from_millis(IDLE_SLEEP_MS);
+    }
+
+    let (due, sleep) = due_sections(&["cpu"], slow_pull_rate_ms().max(50));
+    if due.is_empty() {
+        return sleep;
+    }
+    let mut cpu_data = vec![single_sys_entry("cpu", None).unwrap_or_else(pull_sysdata_cpu)];
+    smoothing::apply(&mut cpu_data);
+
+    {
+        let mut reg = global_registry().write().unwrap();
+        let merged = merge_sysdata_tier(&reg.sysdata, cpu_data, &["cpu"]);
+        if reg.sysdata != merged {
+            reg.sysdata = merged;
+        }
+    }
+    stamp_sections(&due);
+
+    sleep
+}
+
+fn slow_tier_step() -> Duration {
+    if pull_paused() {
+        return Duration::from_millis(100);
+    }
+
+    let tracked: Vec<&'static str> = SLOW_TIER_SECTIONS
+        .into_iter()
+        .filter(|section| section_tracking_enabled(section))
+        .collect();
+    if tracked.is_empty() {
+        return Duration::from_millis(IDLE_SLEEP_MS);
+    }
+
+    let (due, sleep) = due_sections(&tracked, slow_pull_rate_ms().max(50));
+    let requested_slow: Vec<&str> = due
+        .iter()
+        .filter_map(|section| section_to_internal_category(section))
+        .collect();
+    if requested_slow.is_empty() {
+        return sleep;
+    }
+
+    // One walk of the process table for every collector that needs it.
+    let procs = requested_slow
+        .iter()
+        .any(|cat| PROCESS_SNAPSHOT_SECTIONS.contains(cat))
+        .then(ProcessSnapshot::capture);
+    let started = Instant::now();
+    let mut timings = Vec::with_capacity(requested_slow.len());
+    let mut slow_data: Vec<RegistryEntry> = requested_slow
+        .iter()
+        .filter_map(|cat| {
+            let t = Instant::now();
+            let entry = single_sys_entry(cat, procs.as_ref());
+            timings.push(format!("{} {}ms", cat, t.elapsed().as_millis()));
+            entry
+        })
+        .collect();
+    if started.elapsed() >= SLOW_STEP_LOG_THRESHOLD {
+        info!("Slow tier step took {}ms ({})", started.elapsed().as_millis(), timings.join(", "));
+    }
+    smoothing::apply(&mut slow_data);
+
+    {
+        let mut reg = global_registry().write().unwrap();
+        let merged = merge_sysdata_tier(&reg.sysdata, slow_data, &requested_slow);
+        if reg.sysdata != merged {
+            reg.sysdata = merged;
+        }
+    }
+    stamp_sections(&due);
+
+    sleep
+}
+
+/// Spawn (or replace) the thread for `tier`. A replaced thread that later
+/// unblocks sees a newer generation and exits instead of running twice.
+fn spawn_tier(tier: Tier) {
+    let idx = tier as usize;
+    let generation = TIER_GENERATION[idx].fetch_add(1, Ordering::SeqCst) + 1;
+    TIER_LAST_TICK_MS[idx].store(now_ms(), Ordering::Relaxed);
+
+    let spawned = thread::Builder::new()
+        .name(format!("veil-updater-{}", tier.name()))
+        .spawn(move || {
+            thread::sleep(tier.start_delay());
+            while TIER_GENERATION[idx].load(Ordering::SeqCst) == generation {
+                TIER_LAST_TICK_MS[idx].store(now_ms(), Ordering::Relaxed);
+                let started = Instant::now();
+                let sleep = tier.step();
+                TIER_LAST_STEP_MS[idx].store(started.elapsed().as_millis() as u64, Ordering::Relaxed);
+                interruptible_sleep(sleep);
             }
+            warn!("Retired replaced {} updater thread", tier.name());
+        });
+
+    if let Err(e) = spawned {
+        error!("Failed to spawn {} updater thread: {}", tier.name(), e);
+    }
+}
+
+/// Start registry updater threads — fast, appdata, cpu, and slow tiers —
+/// plus the watchdog that monitors them and the on-disk snapshot writer.
+pub fn start_registry_updater() {
+    if mock_enabled() {
+        warn!("Mock collectors enabled — sysdata and appdata are synthetic");
+    }
+
+    #[cfg(target_os = "windows")]
+    if !mock_enabled() {
+        crate::ipc::sysdata::static_hw::prefetch();
+    }
+
+    for tier in TIERS {
+        spawn_tier(tier);
+    }
+
+    thread::spawn(updater_watchdog);
+    crate::ipc::snapshot::start_snapshot_writer();
+}
 
-            let rate = slow_pull_rate_ms().max(50);
+// ── Watchdog ────────────────────────────────────────────────────────
 
-            let slow_data: Vec<RegistryEntry> = requested_slow
-                .iter()
-                .filter_map(|cat| single_sys_entry(cat))
-                .collect();
+fn updater_watchdog() {
+    loop {
+        thread::sleep(WATCHDOG_INTERVAL);
+        let now = now_ms();
 
-            {
-                let mut reg = global_registry().write().unwrap();
-                let merged = merge_sysdata_tier(&reg.sysdata, slow_data, &requested_slow);
-                if reg.sysdata != merged {
-                    reg.sysdata = merged;
+        for tier in TIERS {
+            let idx = tier as usize;
+            let age = now.saturating_sub(TIER_LAST_TICK_MS[idx].load(Ordering::Relaxed));
+            let stalled = age > tier.stall_threshold_ms();
+            let was_stalled = TIER_STALLED[idx].swap(stalled, Ordering::Relaxed);
+
+            if !stalled {
+                if was_stalled {
+                    info!("{} updater recovered", tier.name());
                 }
+                continue;
             }
 
-            interruptible_sleep(Duration::from_millis(rate));
+            if !was_stalled {
+                error!("{} updater has not ticked for {}ms — data engine degraded", tier.name(), age);
+            }
+
+            if !restart_stalled_updaters() {
+                continue;
+            }
+            let restarts = TIER_RESTARTS[idx].load(Ordering::Relaxed);
+            if restarts >= MAX_TIER_RESTARTS {
+                continue;
+            }
+            TIER_RESTARTS[idx].store(restarts + 1, Ordering::Relaxed);
+            warn!("Restarting stalled {} updater (restart {}/{})", tier.name(), restarts + 1, MAX_TIER_RESTARTS);
+            spawn_tier(tier);
+            TIER_STALLED[idx].store(false, Ordering::Relaxed);
         }
-    });
+    }
+}
+
+/// Whether any updater tier is currently stalled.
+pub fn updater_degraded() -> bool {
+    TIER_STALLED.iter().any(|s| s.load(Ordering::Relaxed))
+}
+
+/// Per-tier watchdog view for `backend.updater_status`.
+pub fn updater_status_json() -> serde_json::Value {
+    let now = now_ms();
+    let tiers: serde_json::Map<String, serde_json::Value> = TIERS
+        .iter()
+        .map(|&tier| {
+            let idx = tier as usize;
+            let last = TIER_LAST_TICK_MS[idx].load(Ordering::Relaxed);
+            (tier.name().to_string(), json!({
+                "last_tick_ms": last,
+                "age_ms": now.saturating_sub(last),
+                "last_step_ms": TIER_LAST_STEP_MS[idx].load(Ordering::Relaxed),
+                "stall_threshold_ms": tier.stall_threshold_ms(),
+                "stalled": TIER_STALLED[idx].load(Ordering::Relaxed),
+                "restarts": TIER_RESTARTS[idx].load(Ordering::Relaxed),
+            }))
+        })
+        .collect();
+
+    json!({
+        "status": if updater_degraded() { "degraded" } else { "ok" },
+        "restart_stalled_updaters": restart_stalled_updaters(),
+        "tiers": tiers,
+    })
 }
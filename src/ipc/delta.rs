@@ -0,0 +1,65 @@
+// ~/veil/veil-backend/src/ipc/delta.rs
+//
+// JSON Patch (RFC 6902) style differences between two snapshots, so streams
+// and the shell can send only what changed. Only `add`, `remove` and
+// `replace` are produced. Objects are compared key by key. Arrays are compared
+// element by element when their length is unchanged and replaced whole
+// otherwise, which keeps list reorders from turning into long patches.
+
+use serde_json::{json, Value};
+
+/// Operations that turn `old` into `new`, in order.
+pub fn diff(old: &Value, new: &Value) -> Vec<Value> {
+    let mut ops = Vec::new();
+    diff_into(old, new, &mut String::new(), &mut ops);
+    ops
+}
+
+/// `diff`, unless the patch would serialize larger than `new` itself, in
+/// which case the caller should send the full document.
+pub fn smaller_patch(old: &Value, new: &Value) -> Option<Vec<Value>> {
+    let ops = diff(old, new);
+    let patch_len = serde_json::to_vec(&ops).map(|v| v.len()).ok()?;
+    let full_len = serde_json::to_vec(new).map(|v| v.len()).ok()?;
+    (patch_len < full_len).then_some(ops)
+}
+
+fn diff_into(old: &Value, new: &Value, path: &mut String, ops: &mut Vec<Value>) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, old_value) in a {
+                let len = push(path, key);
+                match b.get(key) {
+                    Some(new_value) => diff_into(old_value, new_value, path, ops),
+                    None => ops.push(json!({ "op": "remove", "path": path })),
+                }
+                path.truncate(len);
+            }
+            for (key, new_value) in b.iter().filter(|(k, _)| !a.contains_key(*k)) {
+                let len = push(path, key);
+                ops.push(json!({ "op": "add", "path": path, "value": new_value }));
+                path.truncate(len);
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (i, (old_item, new_item)) in a.iter().zip(b).enumerate() {
+                let len = push(path, &i.to_string());
+                diff_into(old_item, new_item, path, ops);
+                path.truncate(len);
+            }
+        }
+        _ => ops.push(json!({ "op": "replace", "path": path, "value": new })),
+    }
+}
+
+/// Append `/key` (escaped as a JSON Pointer token) and return the length
+/// to truncate back to.
+fn push(path: &mut String, key: &str) -> usize {
+    let len = path.len();
+    path.push('/');
+    path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+    len
+}
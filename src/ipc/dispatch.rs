@@ -1,4 +1,7 @@
 use serde_json::Value;
+use crate::ipc::addon::capabilities::check_caller_permission;
+use crate::ipc::response::{codes, IpcError, IpcResponse};
+use crate::ipc::token::Caller;
 use crate::warn;
 
 mod registryd;
@@ -7,22 +10,109 @@ mod addond;
 mod backendd;
 mod trackingd;
 mod controld;
+mod assetsd;
+mod schemad;
+mod configd;
+mod networkd;
+mod wifid;
+mod processesd;
+mod uid;
+mod logsd;
+mod displayd;
+mod audiod;
+mod mediad;
+mod kvd;
+
+pub use uid::open_or_focus_ui;
 
 pub fn dispatch(
     ns: &str,
     cmd: &str,
     args: Option<Value>,
-) -> Result<Value, String> {
-    match ns {
+) -> Result<Value, IpcError> {
+    let result = match ns {
         "registry" => registryd::dispatch_registry(cmd, args),
-        "sysdata" => sysdatad::dispatch_sysdata(cmd),
-        "addon" => addond::dispatch_addon(cmd, args),
+        "sysdata" => return sysdatad::dispatch_sysdata(cmd, args),
+        "addon" => return addond::dispatch_addon(cmd, args),
         "backend" => backendd::dispatch_backend(cmd, args),
         "tracking" => trackingd::dispatch_tracking(cmd, args),
         "control" => controld::dispatch_control(cmd, args),
+        "assets" => assetsd::dispatch_assets(cmd, args),
+        "schema" => schemad::dispatch_schema(cmd, args),
+        "config" => configd::dispatch_config(cmd, args),
+        "network" => networkd::dispatch_network(cmd, args),
+        "batch" => return dispatch_batch(cmd, args, &Caller::Anonymous),
+        "kv" => return kvd::dispatch_kv(cmd, args, None),
+        "wifi" => wifid::dispatch_wifi(cmd, args),
+        "processes" => processesd::dispatch_processes(cmd, args),
+        "ui" => uid::dispatch_ui(cmd, args),
+        "logs" => logsd::dispatch_logs(cmd, args),
+        "display" => displayd::dispatch_display(cmd, args),
+        "audio" => audiod::dispatch_audio(cmd, args),
+        "media" => mediad::dispatch_media(cmd, args),
         _ => {
             warn!("[IPC] Unknown namespace requested: '{}'", ns);
-            Err(format!("Unknown namespace: {}", ns))
+            return Err(IpcError::new(codes::UNKNOWN_NAMESPACE, format!("Unknown namespace: {}", ns)));
         }
+    };
+    result.map_err(IpcError::from)
+}
+
+/// `dispatch` for a request from `caller`. `batch` and `kv` need to know the
+/// caller; everything else is routed as usual.
+pub fn dispatch_as(ns: &str, cmd: &str, args: Option<Value>, caller: &Caller) -> Result<Value, IpcError> {
+    match ns {
+        "batch" => dispatch_batch(cmd, args, caller),
+        "kv" => kvd::dispatch_kv(cmd, args, caller.addon_id()),
+        _ => dispatch(ns, cmd, args),
+    }
+}
+
+/// Most sub-requests a single `batch.run` may carry.
+pub const MAX_BATCH_SIZE: usize = 32;
+
+/// `batch.run`: dispatch each `{ns, cmd, args}` in `args.requests` in order and
+/// return one `IpcResponse` per entry. A failing entry only fills its own
+/// `error`. Every entry is checked against the caller's capabilities exactly
+/// as a standalone request would be.
+pub fn dispatch_batch(cmd: &str, args: Option<Value>, caller: &Caller) -> Result<Value, IpcError> {
+    if cmd != "run" {
+        return Err(IpcError::new(codes::UNKNOWN_COMMAND, format!("Unknown batch command: {}", cmd)));
     }
+    let requests = args
+        .as_ref()
+        .and_then(|a| a.get("requests"))
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| IpcError::new(codes::BAD_ARGS, "Missing 'requests' in args"))?;
+    if requests.len() > MAX_BATCH_SIZE {
+        return Err(IpcError::new(
+            codes::BATCH_TOO_LARGE,
+            format!("Batch too large: {} requests (max {})", requests.len(), MAX_BATCH_SIZE),
+        ));
+    }
+
+    let responses: Vec<IpcResponse> = requests
+        .iter()
+        .map(|req| {
+            let ns = req.get("ns").and_then(|v| v.as_str()).unwrap_or_default();
+            let cmd = req.get("cmd").and_then(|v| v.as_str()).unwrap_or_default();
+            if ns.is_empty() || cmd.is_empty() {
+                return IpcResponse::err_code(codes::BAD_ARGS, "Batch entry needs 'ns' and 'cmd'");
+            }
+            if ns == "batch" {
+                return IpcResponse::err_code(codes::BAD_ARGS, "Batches cannot be nested");
+            }
+            if crate::config::enforce_addon_permissions() {
+                if let Err(e) = check_caller_permission(caller, ns, cmd, req.get("args")) {
+                    return IpcResponse::err_code(codes::PERMISSION_DENIED, e);
+                }
+            }
+            match dispatch_as(ns, cmd, req.get("args").cloned(), caller) {
+                Ok(value) => IpcResponse::ok(value),
+                Err(err) => IpcResponse::from(err),
+            }
+        })
+        .collect();
+
+    serde_json::to_value(responses).map_err(|e| IpcError::from(format!("Failed to serialize batch: {}", e)))
 }
@@ -1,13 +1,17 @@
 // ~/veil/veil-backend/src/ipc/dispatch/addond.rs
 
-use serde_json::Value;
-use crate::ipc::addon::{start, stop, reload};
+use serde_json::{json, Value};
+use crate::ipc::addon::{start, stop, stop_all, reload, status, list};
+use crate::ipc::response::{codes, IpcError};
 
-pub fn dispatch_addon(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+pub fn dispatch_addon(cmd: &str, args: Option<Value>) -> Result<Value, IpcError> {
     match cmd {
         "start" => start(args),
         "stop" => stop(args),
+        "stop_all" => Ok(json!({ "stopped": stop_all() })),
         "reload" => reload(args),
-        _ => Err(format!("Unknown addon command: {}", cmd)),
+        "status" => status(args),
+        "list" => list(args),
+        _ => Err(IpcError::new(codes::UNKNOWN_COMMAND, format!("Unknown addon command: {}", cmd))),
     }
 }
@@ -0,0 +1,115 @@
+// ~/veil/veil-backend/src/ipc/dispatch/assetsd.rs
+
+use serde_json::Value;
+use crate::ipc::assets::{asset_key, install_from_url, last_report, run_scan, AssetPrefs, AssetReport};
+
+fn reports_json(reports: &[AssetReport], only_problems: bool) -> Value {
+    let list: Vec<&AssetReport> = reports
+        .iter()
+        .filter(|r| !only_problems || !r.issues.is_empty() || !r.fixed.is_empty())
+        .collect();
+    let problems = reports.iter().filter(|r| !r.issues.is_empty()).count();
+    serde_json::json!({
+        "checked": reports.len(),
+        "problems": problems,
+        "assets": list,
+    })
+}
+
+/// Prefs key from the `category` and `id` args.
+fn prefs_key_arg(args: Option<&Value>) -> Result<String, String> {
+    let arg = |key: &str| {
+        args.and_then(|a| a.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| format!("Missing '{}' in args", key))
+    };
+    Ok(asset_key(arg("category")?, arg("id")?))
+}
+
+pub fn dispatch_assets(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    let all = args
+        .as_ref()
+        .and_then(|a| a.get("all"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    match cmd {
+        // Re-scan now. Args: `category` (optional), `fix` (repair trivial
+        // issues), `all` (include assets with no problems).
+        "verify" => {
+            let category = args
+                .as_ref()
+                .and_then(|a| a.get("category"))
+                .and_then(|v| v.as_str());
+            let fix = args
+                .as_ref()
+                .and_then(|a| a.get("fix"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            Ok(reports_json(&run_scan(category, fix), !all))
+        }
+
+        // Manifests that are missing, don't parse, or lack `id`/`name`, one
+        // entry per problem. Args: `category` (optional).
+        "problems" => {
+            let category = args
+                .as_ref()
+                .and_then(|a| a.get("category"))
+                .and_then(|v| v.as_str());
+            let problems: Vec<Value> = run_scan(category, false)
+                .iter()
+                .flat_map(|r| {
+                    r.issues.iter().filter(|i| i.is_manifest_problem()).map(move |i| {
+                        serde_json::json!({
+                            "category": r.category,
+                            "path": r.path.join("manifest.json"),
+                            "code": i.code,
+                            "message": i.message,
+                        })
+                    })
+                })
+                .collect();
+            Ok(serde_json::json!({ "count": problems.len(), "problems": problems }))
+        }
+
+        // Download a .zip bundle and install it. Args: `url`, `category`.
+        // Blocks this connection until the download finishes.
+        "install_url" => {
+            let arg = |key: &str| {
+                args.as_ref()
+                    .and_then(|a| a.get(key))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .ok_or_else(|| format!("Missing '{}' in args", key))
+            };
+            let installed = install_from_url(arg("url")?, arg("category")?)?;
+            serde_json::to_value(installed).map_err(|e| e.to_string())
+        }
+
+        // Result of the last background scan, without touching disk.
+        "last_verify" => Ok(reports_json(&last_report(), !all)),
+
+        "get_prefs" => serde_json::to_value(AssetPrefs::load()).map_err(|e| e.to_string()),
+
+        "set_favorite" => {
+            let key = prefs_key_arg(args.as_ref())?;
+            let favorite = args
+                .as_ref()
+                .and_then(|a| a.get("favorite"))
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing 'favorite' in args")?;
+            let prefs = AssetPrefs::update(|prefs| prefs.set_favorite(&key, favorite))?;
+            serde_json::to_value(prefs).map_err(|e| e.to_string())
+        }
+
+        "add_recent" => {
+            let key = prefs_key_arg(args.as_ref())?;
+            let prefs = AssetPrefs::update(|prefs| prefs.record_recent(&key))?;
+            serde_json::to_value(prefs).map_err(|e| e.to_string())
+        }
+
+        _ => Err(format!("Unknown assets command: {}", cmd)),
+    }
+}
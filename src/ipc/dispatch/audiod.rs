@@ -0,0 +1,61 @@
+// ~/veil/veil-backend/src/ipc/dispatch/audiod.rs
+
+use serde_json::{json, Value};
+use crate::ipc::sysdata::mock;
+#[cfg(target_os = "windows")]
+use crate::ipc::sysdata::audio::{list_devices_json, set_default_device};
+
+pub fn dispatch_audio(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    match cmd {
+        "list_devices" => {
+            if mock::mock_enabled() {
+                return Ok(mock::mock_audio_devices());
+            }
+            list_devices_json()
+        }
+
+        // Args: `device_id` from list_devices and `role` (output, input or
+        // communications).
+        "set_default" => {
+            let args = args.as_ref();
+            let device_id = args
+                .and_then(|a| a.get("device_id"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'device_id' in args")?;
+            let role = args
+                .and_then(|a| a.get("role"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'role' in args")?;
+
+            if mock::mock_enabled() {
+                if !["output", "input", "communications"].contains(&role) {
+                    return Err(format!("Unknown role '{}': expected output, input or communications", role));
+                }
+                let devices = mock::mock_audio_devices();
+                let device = devices["devices"]
+                    .as_array()
+                    .and_then(|list| list.iter().find(|d| d["id"] == device_id))
+                    .ok_or_else(|| format!("No active audio device with id {}", device_id))?;
+                return Ok(json!({
+                    "device_id": device_id,
+                    "name": device["name"],
+                    "role": role,
+                    "direction": device["direction"],
+                }));
+            }
+            set_default_device(device_id, role)
+        }
+
+        _ => Err(format!("Unknown audio command: {}", cmd)),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn list_devices_json() -> Result<Value, String> {
+    Err("Audio devices are only listed on Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_default_device(_device_id: &str, _role: &str) -> Result<Value, String> {
+    Err("Switching audio devices is only supported on Windows".to_string())
+}
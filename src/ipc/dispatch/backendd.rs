@@ -2,7 +2,11 @@
 
 use serde_json::{json, Value};
 use crate::config;
-use crate::ipc::data_updater::{set_explicit_tracking_demands, touch_ui_heartbeat};
+use crate::ipc::data_updater::{
+    normalize_section, set_explicit_tracking_demands, touch_ui_heartbeat, updater_degraded,
+    updater_status_json,
+};
+use crate::ipc::registry::reload_registry;
 
 pub fn dispatch_backend(cmd: &str, args: Option<Value>) -> Result<Value, String> {
     match cmd {
@@ -14,9 +18,35 @@ pub fn dispatch_backend(cmd: &str, args: Option<Value>) -> Result<Value, String>
                 "data_pull_paused": cfg.data_pull_paused,
                 "refresh_on_request": cfg.refresh_on_request,
                 "ui_data_exception_enabled": cfg.ui_data_exception_enabled,
+                "enforce_addon_permissions": cfg.enforce_addon_permissions,
+                "addons": cfg.addons,
+                "restart_stalled_updaters": cfg.restart_stalled_updaters,
+                "disambiguate_asset_ids": cfg.disambiguate_asset_ids,
+                "compress_snapshot": cfg.compress_snapshot,
+                "connectivity_check": cfg.connectivity_check,
+                "pull_rate_overrides": cfg.pull_rate_overrides,
+                "data_cap_mb": cfg.data_cap_mb,
+                "data_cap_warn_percent": cfg.data_cap_warn_percent,
+                "smoothing": cfg.smoothing,
+                "wifi_scan_cache_secs": cfg.wifi_scan_cache_secs,
+                "addon_stop_grace_ms": cfg.addon_stop_grace_ms,
+                "ipc_timeout_ms": cfg.ipc_timeout_ms,
+                "http_bridge_origins": cfg.http_bridge_origins,
+                "ui_hotkey": cfg.ui_hotkey,
+                "idle_thresholds": cfg.idle_thresholds,
+                "theme": cfg.theme,
+                "log_level": cfg.log_level,
+                "safe_mode": crate::safe_mode::safe_mode(),
+                "degraded": updater_degraded(),
             }))
         }
 
+        "validate_config" => {
+            let yaml = args.as_ref().and_then(|a| a.get("yaml")).and_then(|v| v.as_str());
+            let issues = config::validate_config_text(yaml)?;
+            Ok(json!({ "valid": issues.is_empty(), "issues": issues }))
+        }
+
         "set_fast_pull_rate" => {
             let ms = args
                 .as_ref()
@@ -67,6 +97,257 @@ pub fn dispatch_backend(cmd: &str, args: Option<Value>) -> Result<Value, String>
             Ok(json!({ "ui_data_exception_enabled": config::ui_data_exception_enabled() }))
         }
 
+        "set_enforce_addon_permissions" => {
+            let enabled = args
+                .as_ref()
+                .and_then(|a| a.get("enabled"))
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing 'enabled' in args")?;
+            config::set_enforce_addon_permissions(enabled);
+            Ok(json!({ "enforce_addon_permissions": config::enforce_addon_permissions() }))
+        }
+
+        "set_restart_stalled_updaters" => {
+            let enabled = args
+                .as_ref()
+                .and_then(|a| a.get("enabled"))
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing 'enabled' in args")?;
+            config::set_restart_stalled_updaters(enabled);
+            Ok(json!({ "restart_stalled_updaters": config::restart_stalled_updaters() }))
+        }
+
+        "updater_status" => Ok(updater_status_json()),
+
+        "set_disambiguate_asset_ids" => {
+            let enabled = args
+                .as_ref()
+                .and_then(|a| a.get("enabled"))
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing 'enabled' in args")?;
+            config::set_disambiguate_asset_ids(enabled);
+            Ok(json!({ "disambiguate_asset_ids": config::disambiguate_asset_ids() }))
+        }
+
+        "set_compress_snapshot" => {
+            let enabled = args
+                .as_ref()
+                .and_then(|a| a.get("enabled"))
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing 'enabled' in args")?;
+            config::set_compress_snapshot(enabled);
+            Ok(json!({ "compress_snapshot": config::compress_snapshot() }))
+        }
+
+        "set_connectivity_check" => {
+            let enabled = args
+                .as_ref()
+                .and_then(|a| a.get("enabled"))
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing 'enabled' in args")?;
+            config::set_connectivity_check(enabled);
+            crate::ipc::data_updater::refresh_section_now("network");
+            Ok(json!({ "connectivity_check": config::connectivity_check() }))
+        }
+
+        // Args: `cap_mb` (0 turns the cap off), `warn_percent` (optional, keeps current).
+        "set_data_cap" => {
+            let cap_mb = args
+                .as_ref()
+                .and_then(|a| a.get("cap_mb"))
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing 'cap_mb' in args")?;
+            let warn_percent = args
+                .as_ref()
+                .and_then(|a| a.get("warn_percent"))
+                .and_then(|v| v.as_u64())
+                .unwrap_or_else(config::data_cap_warn_percent);
+            config::set_data_cap(cap_mb, warn_percent);
+            Ok(json!({
+                "data_cap_mb": config::data_cap_mb(),
+                "data_cap_warn_percent": config::data_cap_warn_percent(),
+            }))
+        }
+
+        "set_wifi_scan_cache_secs" => {
+            let secs = args
+                .as_ref()
+                .and_then(|a| a.get("secs"))
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing 'secs' in args")?;
+            config::set_wifi_scan_cache_secs(secs);
+            Ok(json!({ "wifi_scan_cache_secs": config::wifi_scan_cache_secs() }))
+        }
+
+        "set_addon_stop_grace_ms" => {
+            let ms = args
+                .as_ref()
+                .and_then(|a| a.get("grace_ms"))
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing 'grace_ms' in args")?;
+            config::set_addon_stop_grace_ms(ms);
+            Ok(json!({ "addon_stop_grace_ms": config::addon_stop_grace_ms() }))
+        }
+
+        "set_ipc_timeout_ms" => {
+            let ms = args
+                .as_ref()
+                .and_then(|a| a.get("timeout_ms"))
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing 'timeout_ms' in args")?;
+            config::set_ipc_timeout_ms(ms);
+            Ok(json!({ "ipc_timeout_ms": config::ipc_timeout_ms() }))
+        }
+
+        "restart" => {
+            crate::restart_backend();
+            Ok(json!({ "status": "restarting" }))
+        }
+
+        "shutdown" => {
+            crate::shutdown_backend();
+            Ok(json!({ "status": "shutting_down" }))
+        }
+
+        "set_ui_hotkey" => {
+            let hotkey = args
+                .as_ref()
+                .and_then(|a| a.get("hotkey"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'hotkey' in args")?;
+            config::set_ui_hotkey(hotkey)?;
+            Ok(json!({ "ui_hotkey": config::ui_hotkey() }))
+        }
+
+        "set_theme" => {
+            let theme = args
+                .as_ref()
+                .and_then(|a| a.get("theme"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'theme' in args")?;
+            let theme = config::UiTheme::parse(theme)
+                .ok_or_else(|| format!("Unknown theme '{}' (expected 'dark' or 'light')", theme))?;
+            config::set_ui_theme(theme);
+            Ok(json!({ "theme": config::ui_theme() }))
+        }
+
+        "set_log_level" => {
+            let level = args
+                .as_ref()
+                .and_then(|a| a.get("level"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'level' in args")?;
+            let level = config::LogLevel::parse(level)
+                .ok_or_else(|| format!("Unknown log level '{}' (expected error, warn, info or debug)", level))?;
+            config::set_log_level(level);
+            Ok(json!({ "log_level": config::log_level() }))
+        }
+
+        "set_idle_thresholds" => {
+            let secs = |key: &str| {
+                args.as_ref()
+                    .and_then(|a| a.get(key))
+                    .and_then(|v| v.as_u64())
+                    .ok_or(format!("Missing '{}' in args", key))
+            };
+            let idle_secs = secs("idle_secs")?;
+            let away_secs = secs("away_secs")?;
+            if away_secs <= idle_secs {
+                return Err("'away_secs' must be greater than 'idle_secs'".to_string());
+            }
+            config::set_idle_thresholds(idle_secs, away_secs);
+            Ok(json!({ "idle_thresholds": config::idle_thresholds() }))
+        }
+
+        // Args: `section` (e.g. "cpu"), `rate_ms` (null or omitted returns it to its tier's rate).
+        "set_pull_rate_override" => {
+            let section = args
+                .as_ref()
+                .and_then(|a| a.get("section"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'section' in args")?;
+            let section = normalize_section(section).ok_or(format!("Unknown section: {}", section))?;
+            let rate_ms = args
+                .as_ref()
+                .and_then(|a| a.get("rate_ms"))
+                .and_then(|v| v.as_u64());
+            config::set_pull_rate_override(section, rate_ms);
+            Ok(json!({ "pull_rate_overrides": config::pull_rate_overrides() }))
+        }
+
+        // Args: `metric` (e.g. "cpu.usage_percent"), `factor` (0–1; null or 0 turns it off).
+        "set_smoothing" => {
+            let metric = args
+                .as_ref()
+                .and_then(|a| a.get("metric"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'metric' in args")?;
+            let metric = metric.strip_prefix("sysdata.").unwrap_or(metric);
+            if !metric.contains('.') {
+                return Err(format!("Metric path must be <category>.<field>: {}", metric));
+            }
+            let factor = args
+                .as_ref()
+                .and_then(|a| a.get("factor"))
+                .and_then(|v| v.as_f64())
+                .filter(|f| *f > 0.0);
+            config::set_smoothing(metric, factor);
+            Ok(json!({ "smoothing": config::smoothing() }))
+        }
+
+        "set_addon_enabled" => {
+            let addon_id = args
+                .as_ref()
+                .and_then(|a| a.get("addon_id"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'addon_id' in args")?;
+            let enabled = args
+                .as_ref()
+                .and_then(|a| a.get("enabled"))
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing 'enabled' in args")?;
+            config::set_addon_enabled(addon_id, enabled);
+            reload_registry();
+            Ok(json!({ "addons": config::addon_filter() }))
+        }
+
+        "set_addons_enabled_only" => {
+            let enabled_only = args
+                .as_ref()
+                .and_then(|a| a.get("enabled_only"))
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing 'enabled_only' in args")?;
+            config::set_addons_enabled_only(enabled_only);
+            reload_registry();
+            Ok(json!({ "addons": config::addon_filter() }))
+        }
+
+        // Args: `ignore` and/or `include_only`, arrays of process names; an
+        // omitted list is left as it is.
+        "set_process_filter" => {
+            let mut filter = config::process_filter();
+            let names = |key: &str| -> Result<Option<Vec<String>>, String> {
+                match args.as_ref().and_then(|a| a.get(key)) {
+                    None => Ok(None),
+                    Some(v) => v
+                        .as_array()
+                        .ok_or(format!("'{}' must be an array of process names", key))?
+                        .iter()
+                        .map(|n| n.as_str().map(|s| s.trim().to_string()).ok_or(format!("'{}' must contain strings", key)))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(|list| Some(list.into_iter().filter(|n| !n.is_empty()).collect())),
+                }
+            };
+            if let Some(ignore) = names("ignore")? {
+                filter.ignore = ignore;
+            }
+            if let Some(include_only) = names("include_only")? {
+                filter.include_only = include_only;
+            }
+            config::set_process_filter(filter);
+            Ok(json!({ "processes": config::process_filter() }))
+        }
+
         "ui_heartbeat" => {
             touch_ui_heartbeat();
             Ok(json!({ "ok": true }))
@@ -0,0 +1,35 @@
+// ~/veil/veil-backend/src/ipc/dispatch/configd.rs
+
+use serde_json::{json, Value};
+use crate::config_ui::{addon_config_value, addon_schema_json, set_addon_config_value};
+
+fn str_arg<'a>(args: &'a Option<Value>, key: &str) -> Option<&'a str> {
+    args.as_ref().and_then(|a| a.get(key)).and_then(|v| v.as_str())
+}
+
+pub fn dispatch_config(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    let addon_id = str_arg(&args, "addon_id").ok_or("Missing 'addon_id' in args")?;
+
+    match cmd {
+        // Parsed schema.yaml, `null` if the addon has none.
+        "schema" => addon_schema_json(addon_id),
+
+        // Dotted `path` into config.yaml; omit it for the whole file.
+        "get" => {
+            let path = str_arg(&args, "path").unwrap_or_default();
+            Ok(json!({ "path": path, "value": addon_config_value(addon_id, path)? }))
+        }
+
+        "set" => {
+            let path = str_arg(&args, "path").ok_or("Missing 'path' in args")?;
+            let value = args
+                .as_ref()
+                .and_then(|a| a.get("value"))
+                .ok_or("Missing 'value' in args")?;
+            set_addon_config_value(addon_id, path, value)?;
+            Ok(json!({ "path": path, "value": value }))
+        }
+
+        _ => Err(format!("Unknown config command: {}", cmd)),
+    }
+}
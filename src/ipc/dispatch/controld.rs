@@ -9,7 +9,7 @@
 
 use serde_json::{json, Value};
 use std::fs;
-use crate::paths::veil_root_dir;
+use crate::paths::logs_dir;
 
 pub fn dispatch_control(cmd: &str, args: Option<Value>) -> Result<Value, String> {
     match cmd {
@@ -29,7 +29,7 @@ pub fn dispatch_control(cmd: &str, args: Option<Value>) -> Result<Value, String>
                 return Err(format!("Invalid log filename: {:?}", name));
             }
 
-            let log_dir = veil_root_dir().join("logs");
+            let log_dir = logs_dir();
             fs::create_dir_all(&log_dir)
                 .map_err(|e| format!("Could not create logs dir: {}", e))?;
 
@@ -0,0 +1,46 @@
+// ~/veil/veil-backend/src/ipc/dispatch/displayd.rs
+
+use serde_json::{json, Value};
+use crate::ipc::sysdata::mock;
+#[cfg(target_os = "windows")]
+use crate::ipc::sysdata::brightness::set_brightness;
+
+pub fn dispatch_display(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    match cmd {
+        // Args: `monitor_id` (`id`, `stable_id` or `device_name` from
+        // get_displays) and `percent` (0–100).
+        "set_brightness" => {
+            let args = args.as_ref();
+            let monitor_id = args
+                .and_then(|a| a.get("monitor_id"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'monitor_id' in args")?;
+            let percent = args
+                .and_then(|a| a.get("percent"))
+                .and_then(|v| v.as_u64())
+                .filter(|p| *p <= 100)
+                .ok_or("'percent' must be a whole number from 0 to 100")? as u8;
+
+            if mock::mock_enabled() {
+                let monitor = mock::mock_monitors()
+                    .into_iter()
+                    .find(|m| [&m.id, &m.stable_id, &m.device_name].iter().any(|id| id.as_str() == monitor_id))
+                    .ok_or_else(|| format!("Unknown monitor: {}", monitor_id))?;
+                return Ok(json!({
+                    "monitor_id": monitor_id,
+                    "device_name": monitor.device_name,
+                    "percent": percent,
+                    "method": "mock",
+                }));
+            }
+            set_brightness(monitor_id, percent)
+        }
+
+        _ => Err(format!("Unknown display command: {}", cmd)),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_brightness(_monitor_id: &str, _percent: u8) -> Result<Value, String> {
+    Err("Brightness control is only supported on Windows".to_string())
+}
@@ -0,0 +1,261 @@
+// ~/veil/veil-backend/src/ipc/dispatch/kvd.rs
+//
+// "kv" IPC namespace — a small key/value store per addon, kept in
+// `<root>/kv/<addon_id>.json`. The store is picked by the addon the request's
+// token was issued to (see `ipc::token`), so an addon only ever sees its own
+// keys. The session token and a bare `addon_id` can't reach any store.
+//
+// Commands:
+//   get    { key }         { key, value }; `value` is null for an unset key.
+//                          Without `key`: { keys, bytes } for the whole store.
+//   set    { key, value }  Store any JSON value under `key`.
+//   delete { key }         { key, deleted }.
+//
+// Writes go to a temp file that is renamed over the old one, and the lock on
+// STORES is held across each read-modify-write, so concurrent sets from the
+// pipe and HTTP threads can't drop each other's keys.
+
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::ipc::addon::utils::find_addon_entry;
+use crate::ipc::response::{codes, IpcError};
+use crate::ipc::snapshot::write_atomic;
+use crate::paths::kv_dir;
+
+const MAX_KEY_BYTES: usize = 256;
+/// Largest value, measured as serialized JSON.
+const MAX_VALUE_BYTES: usize = 64 * 1024;
+/// All of one addon's keys and values together.
+const MAX_ADDON_BYTES: usize = 1024 * 1024;
+
+type Store = Map<String, Value>;
+
+/// Stores read so far, by file.
+fn stores() -> &'static Mutex<HashMap<PathBuf, Store>> {
+    static STORES: OnceLock<Mutex<HashMap<PathBuf, Store>>> = OnceLock::new();
+    STORES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// `addon_id` is the addon the request's token was issued to.
+pub fn dispatch_kv(cmd: &str, args: Option<Value>, addon_id: Option<&str>) -> Result<Value, IpcError> {
+    let addon_id = addon_id.ok_or_else(|| {
+        IpcError::new(
+            codes::UNAUTHORIZED,
+            format!("'kv' needs the token the addon was launched with ({})", crate::ipc::token::ADDON_TOKEN_ENV),
+        )
+    })?;
+    let addon_id = store_id(addon_id)?;
+    run(&kv_dir(), &addon_id, cmd, args.as_ref())
+}
+
+/// Run `cmd` against `addon_id`'s store in `dir`.
+fn run(dir: &Path, addon_id: &str, cmd: &str, args: Option<&Value>) -> Result<Value, IpcError> {
+    let key = args.and_then(|a| a.get("key")).and_then(|v| v.as_str());
+    let path = dir.join(format!("{}.json", addon_id));
+
+    let mut stores = stores().lock().unwrap();
+    if !stores.contains_key(&path) {
+        stores.insert(path.clone(), load(&path)?);
+    }
+    let store = stores.get_mut(&path).expect("store was just loaded");
+
+    match cmd {
+        "get" => Ok(match key {
+            Some(key) => json!({ "key": key, "value": store.get(key).cloned().unwrap_or(Value::Null) }),
+            None => json!({ "keys": store.keys().collect::<Vec<_>>(), "bytes": used_bytes(store) }),
+        }),
+
+        "set" => {
+            let key = require_key(key)?;
+            let value = args
+                .and_then(|a| a.get("value"))
+                .cloned()
+                .ok_or_else(|| IpcError::new(codes::BAD_ARGS, "Missing 'value' in args"))?;
+            let size = entry_bytes(key, &value);
+            if size - key.len() > MAX_VALUE_BYTES {
+                return Err(IpcError::new(
+                    codes::TOO_LARGE,
+                    format!("Value for '{}' is {} bytes (max {})", key, size - key.len(), MAX_VALUE_BYTES),
+                ));
+            }
+            let replaced = store.get(key).map_or(0, |old| entry_bytes(key, old));
+            let total = used_bytes(store) - replaced + size;
+            if total > MAX_ADDON_BYTES {
+                return Err(IpcError::new(
+                    codes::TOO_LARGE,
+                    format!("Store for '{}' would grow to {} bytes (max {})", addon_id, total, MAX_ADDON_BYTES),
+                ));
+            }
+
+            let previous = store.insert(key.to_string(), value);
+            if let Err(e) = save(&path, store) {
+                match previous {
+                    Some(old) => store.insert(key.to_string(), old),
+                    None => store.remove(key),
+                };
+                return Err(e.into());
+            }
+            Ok(json!({ "key": key, "bytes": total }))
+        }
+
+        "delete" => {
+            let key = require_key(key)?;
+            let Some(old) = store.remove(key) else {
+                return Ok(json!({ "key": key, "deleted": false }));
+            };
+            if let Err(e) = save(&path, store) {
+                store.insert(key.to_string(), old);
+                return Err(e.into());
+            }
+            Ok(json!({ "key": key, "deleted": true }))
+        }
+
+        _ => Err(IpcError::new(codes::UNKNOWN_COMMAND, format!("Unknown kv command: {}", cmd))),
+    }
+}
+
+/// Registry id of the calling addon, checked to be usable as a file name.
+fn store_id(addon_id: &str) -> Result<String, IpcError> {
+    let id = find_addon_entry(addon_id)?.id;
+    let safe = !id.is_empty()
+        && !id.starts_with('.')
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !safe {
+        return Err(IpcError::new(codes::ADDON_INVALID, format!("Addon id '{}' can't name a kv store", id)));
+    }
+    Ok(id)
+}
+
+fn require_key(key: Option<&str>) -> Result<&str, IpcError> {
+    match key {
+        None | Some("") => Err(IpcError::new(codes::BAD_ARGS, "Missing 'key' in args")),
+        Some(key) if key.len() > MAX_KEY_BYTES => Err(IpcError::new(
+            codes::TOO_LARGE,
+            format!("Key is {} bytes (max {})", key.len(), MAX_KEY_BYTES),
+        )),
+        Some(key) => Ok(key),
+    }
+}
+
+fn entry_bytes(key: &str, value: &Value) -> usize {
+    key.len() + serde_json::to_vec(value).map_or(0, |v| v.len())
+}
+
+fn used_bytes(store: &Store) -> usize {
+    store.iter().map(|(k, v)| entry_bytes(k, v)).sum()
+}
+
+/// The addon's store on disk; empty when it has never written one. A file
+/// that doesn't parse is an error rather than an empty store, so the next
+/// `set` can't overwrite it.
+fn load(path: &Path) -> Result<Store, IpcError> {
+    match std::fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|e| IpcError::from(format!("Failed to parse '{}': {}", path.display(), e))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Store::new()),
+        Err(e) => Err(IpcError::from(format!("Failed to read '{}': {}", path.display(), e))),
+    }
+}
+
+fn save(path: &Path, store: &Store) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create '{}': {}", dir.display(), e))?;
+    }
+    let json = serde_json::to_vec_pretty(store).map_err(|e| format!("Failed to serialize kv store: {}", e))?;
+    write_atomic(path, &json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("veil-kv-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn set(dir: &Path, addon_id: &str, key: &str, value: Value) -> Result<Value, IpcError> {
+        run(dir, addon_id, "set", Some(&json!({ "key": key, "value": value })))
+    }
+
+    fn on_disk(dir: &Path, addon_id: &str) -> Store {
+        let bytes = std::fs::read(dir.join(format!("{}.json", addon_id))).unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn concurrent_sets_from_two_callers_keep_every_key() {
+        let dir = temp_dir("concurrent");
+        let writers: Vec<_> = ["a", "b"]
+            .into_iter()
+            .map(|caller| {
+                let dir = dir.clone();
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        set(&dir, "shared", &format!("{}-{}", caller, i), json!(i)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let store = on_disk(&dir, "shared");
+        assert_eq!(store.len(), 100);
+        for caller in ["a", "b"] {
+            for i in 0..50 {
+                assert_eq!(store.get(&format!("{}-{}", caller, i)), Some(&json!(i)));
+            }
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn addons_only_see_their_own_keys() {
+        let dir = temp_dir("isolation");
+        set(&dir, "clock", "pos", json!([10, 20])).unwrap();
+        set(&dir, "weather", "city", json!("Oslo")).unwrap();
+
+        let get = |addon_id: &str, key: &str| {
+            run(&dir, addon_id, "get", Some(&json!({ "key": key }))).unwrap()["value"].clone()
+        };
+        assert_eq!(get("clock", "pos"), json!([10, 20]));
+        assert_eq!(get("weather", "pos"), Value::Null);
+        assert_eq!(get("clock", "city"), Value::Null);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn limits_are_enforced() {
+        let dir = temp_dir("limits");
+        let long_key = "k".repeat(MAX_KEY_BYTES + 1);
+        let err = set(&dir, "clock", &long_key, json!(1)).unwrap_err();
+        assert_eq!(err.code, Some(codes::TOO_LARGE));
+
+        let big = json!("x".repeat(MAX_VALUE_BYTES));
+        let err = set(&dir, "clock", "big", big).unwrap_err();
+        assert_eq!(err.code, Some(codes::TOO_LARGE));
+
+        let chunk = "x".repeat(MAX_VALUE_BYTES - 16);
+        let mut total_err = None;
+        for i in 0..(MAX_ADDON_BYTES / MAX_VALUE_BYTES + 2) {
+            if let Err(e) = set(&dir, "clock", &format!("chunk-{}", i), json!(chunk)) {
+                total_err = Some(e);
+                break;
+            }
+        }
+        assert_eq!(total_err.map(|e| e.code), Some(Some(codes::TOO_LARGE)));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn request_without_an_addon_token_is_rejected() {
+        let err = dispatch_kv("get", None, None).unwrap_err();
+        assert_eq!(err.code, Some(codes::UNAUTHORIZED));
+    }
+}
@@ -0,0 +1,107 @@
+// ~/veil/veil-backend/src/ipc/dispatch/logsd.rs
+//
+// "logs" IPC namespace — read the backend log without hunting for the file.
+//
+// Commands:
+//   tail  { lines: 200, level: "warn", contains: "wallpaper" }
+//         The last `lines` lines of `logs/veil.log` (at most 2000), oldest
+//         first. `level` keeps that level and anything more severe;
+//         `contains` is a case-insensitive substring match. Only the last
+//         1 MB of the file is searched.
+
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::config::LogLevel;
+use crate::paths::backend_log_path;
+
+const DEFAULT_LINES: usize = 200;
+const MAX_LINES: usize = 2000;
+/// Bytes read from the end of the file; bounds both the work and the reply.
+const MAX_SCAN_BYTES: u64 = 1024 * 1024;
+
+/// Severity rank of a `<timestamp> [LEVEL] message` line; higher is more severe.
+fn line_rank(line: &str) -> Option<u8> {
+    let start = line.find(" [")? + 2;
+    let end = start + line[start..].find(']')?;
+    rank(&line[start..end])
+}
+
+fn rank(level: &str) -> Option<u8> {
+    match LogLevel::parse(level)? {
+        LogLevel::Debug => Some(0),
+        LogLevel::Info => Some(1),
+        LogLevel::Warn => Some(2),
+        LogLevel::Error => Some(3),
+    }
+}
+
+fn tail(args: Option<&Value>) -> Result<Value, String> {
+    let lines = args
+        .and_then(|a| a.get("lines"))
+        .and_then(|v| v.as_u64())
+        .map(|n| (n as usize).clamp(1, MAX_LINES))
+        .unwrap_or(DEFAULT_LINES);
+    let min_rank = match args.and_then(|a| a.get("level")).and_then(|v| v.as_str()) {
+        Some(level) => Some(rank(level).ok_or_else(|| format!("Unknown log level '{}'", level))?),
+        None => None,
+    };
+    let contains = args
+        .and_then(|a| a.get("contains"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty());
+
+    let path = backend_log_path();
+    // A separate read-only handle; the logger's writer thread keeps its own.
+    let mut file = match File::open(&path) {
+        Ok(f) => f,
+        Err(_) => return Ok(json!({ "path": path.display().to_string(), "lines": [], "truncated": false })),
+    };
+    let len = file.metadata().map(|m| m.len()).map_err(|e| format!("Failed to read log: {}", e))?;
+    let start = len.saturating_sub(MAX_SCAN_BYTES);
+    file.seek(SeekFrom::Start(start)).map_err(|e| format!("Failed to read log: {}", e))?;
+    let mut bytes = Vec::with_capacity((len - start) as usize);
+    file.take(MAX_SCAN_BYTES).read_to_end(&mut bytes).map_err(|e| format!("Failed to read log: {}", e))?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    let mut body: &str = &text;
+    // Drop a line cut by the scan window, and one the writer hasn't finished.
+    if start > 0 {
+        body = body.split_once('\n').map(|(_, rest)| rest).unwrap_or("");
+    }
+    if !body.ends_with('\n') {
+        body = body.rsplit_once('\n').map(|(done, _)| done).unwrap_or("");
+    }
+
+    // Continuation lines (no level of their own) take the level of the line above.
+    let mut current_rank = 1;
+    let mut matched: Vec<&str> = body
+        .lines()
+        .filter(|line| {
+            if let Some(r) = line_rank(line) {
+                current_rank = r;
+            }
+            min_rank.map(|min| current_rank >= min).unwrap_or(true)
+                && contains.as_ref().map(|q| line.to_lowercase().contains(q)).unwrap_or(true)
+        })
+        .collect();
+    let truncated = matched.len() > lines || start > 0;
+    if matched.len() > lines {
+        matched.drain(..matched.len() - lines);
+    }
+
+    Ok(json!({
+        "path": path.display().to_string(),
+        "lines": matched,
+        "truncated": truncated,
+    }))
+}
+
+pub fn dispatch_logs(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    match cmd {
+        "tail" => tail(args.as_ref()),
+        _ => Err(format!("Unknown logs command: {}", cmd)),
+    }
+}
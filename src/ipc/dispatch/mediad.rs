@@ -0,0 +1,30 @@
+// ~/veil/veil-backend/src/ipc/dispatch/mediad.rs
+
+use serde_json::Value;
+use crate::ipc::sysdata::{media, mock};
+
+pub fn dispatch_media(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    match cmd {
+        // Args: `action` (play, pause, toggle, next, previous, stop, seek),
+        // optional `source_app_id` to pick a session, `position_ms` for seek.
+        "control" => {
+            let args = args.as_ref();
+            let action = args
+                .and_then(|a| a.get("action"))
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'action' in args")?;
+            let source_app_id = args
+                .and_then(|a| a.get("source_app_id"))
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty());
+            let position_ms = args.and_then(|a| a.get("position_ms")).and_then(|v| v.as_i64());
+
+            if mock::mock_enabled() {
+                return mock::mock_media_control(action, source_app_id, position_ms);
+            }
+            media::control(action, source_app_id, position_ms)
+        }
+
+        _ => Err(format!("Unknown media command: {}", cmd)),
+    }
+}
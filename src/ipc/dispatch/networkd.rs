@@ -0,0 +1,21 @@
+// ~/veil/veil-backend/src/ipc/dispatch/networkd.rs
+
+use serde_json::{json, Value};
+use crate::info;
+use crate::ipc::sysdata::{mock, network};
+
+pub fn dispatch_network(cmd: &str, _args: Option<Value>) -> Result<Value, String> {
+    match cmd {
+        // Zero `session_received_bytes` / `session_transmitted_bytes`.
+        "reset_counters" => {
+            network::reset_session_counters();
+            if mock::mock_enabled() {
+                mock::reset_network_session();
+            }
+            info!("Network session counters reset");
+            Ok(json!({ "reset": true }))
+        }
+
+        _ => Err(format!("Unknown network command: {}", cmd)),
+    }
+}
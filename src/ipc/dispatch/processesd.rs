@@ -0,0 +1,24 @@
+// ~/veil/veil-backend/src/ipc/dispatch/processesd.rs
+
+use serde_json::Value;
+use crate::ipc::sysdata::{mock, processes};
+
+pub fn dispatch_processes(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    match cmd {
+        // Spawn hierarchy. Args: `pid` (optional subtree root).
+        "tree" => {
+            let pid = args
+                .as_ref()
+                .and_then(|a| a.get("pid"))
+                .and_then(|v| v.as_u64())
+                .map(|v| u32::try_from(v).map_err(|_| format!("Invalid pid: {}", v)))
+                .transpose()?;
+            if mock::mock_enabled() {
+                return mock::mock_process_tree(pid);
+            }
+            processes::process_tree_json(pid)
+        }
+
+        _ => Err(format!("Unknown processes command: {}", cmd)),
+    }
+}
@@ -0,0 +1,19 @@
+// ~/veil/veil-backend/src/ipc/dispatch/schemad.rs
+
+use serde_json::Value;
+use crate::ipc::metrics::metrics_json;
+
+pub fn dispatch_schema(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    match cmd {
+        // Metric labels, units and categories. Optional `category` filter.
+        "metrics" => {
+            let category = args
+                .as_ref()
+                .and_then(|a| a.get("category"))
+                .and_then(|v| v.as_str());
+            Ok(metrics_json(category))
+        }
+
+        _ => Err(format!("Unknown schema command: {}", cmd)),
+    }
+}
@@ -1,8 +1,12 @@
 // ~/veil/veil-backend/src/ipc/dispatch/sysdatad.rs
 
-use serde_json::Value;
+use serde_json::{Map, Value};
 use crate::ipc::sysdata::display::MonitorManager;
-use crate::ipc::registry::global_registry;
+use crate::ipc::sysdata::mock::{mock_enabled, mock_monitors};
+use crate::ipc::data_updater::section_to_internal_category;
+use crate::ipc::metrics::describe_fields;
+use crate::ipc::registry::{global_registry, Registry};
+use crate::ipc::response::{codes, IpcError};
 
 fn metadata_for_category(reg: &crate::ipc::registry::Registry, category: &str) -> Value {
     reg.sysdata
@@ -12,13 +16,70 @@ fn metadata_for_category(reg: &crate::ipc::registry::Registry, category: &str) -
         .unwrap_or(Value::Null)
 }
 
-pub fn dispatch_sysdata(cmd: &str) -> Result<Value, String> {
+/// `sysdata.get`: `{ path }` returns that value alone, `{ paths: [...] }` an
+/// object keyed by path. Paths are dotted (`cpu.temperature.average_c`); the
+/// `sysdata.` prefix is optional.
+fn get_paths(reg: &Registry, args: Option<&Value>) -> Result<Value, IpcError> {
+    let lookup = |path: &str| {
+        let key = path.trim();
+        let key = key.strip_prefix("sysdata.").unwrap_or(key);
+        reg.sysdata_value(key)
+            .ok_or_else(|| IpcError::new(codes::NOT_FOUND, format!("No sysdata at '{}'", path)))
+    };
+
+    if let Some(path) = args.and_then(|a| a.get("path")).and_then(|v| v.as_str()) {
+        return lookup(path);
+    }
+    let paths = args
+        .and_then(|a| a.get("paths"))
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| IpcError::new(codes::BAD_ARGS, "Missing 'path' or 'paths' in args"))?;
+
+    let mut out = Map::new();
+    for path in paths {
+        let path = path
+            .as_str()
+            .ok_or_else(|| IpcError::new(codes::BAD_ARGS, "'paths' must be strings"))?;
+        out.insert(path.to_string(), lookup(path)?);
+    }
+    Ok(Value::Object(out))
+}
+
+pub fn dispatch_sysdata(cmd: &str, args: Option<Value>) -> Result<Value, IpcError> {
     
     let reg = global_registry().read().unwrap();
 
     match cmd {
+        // Streams are served by the named-pipe server itself (see server.rs);
+        // one-shot transports such as the HTTP bridge end up here.
+        "subscribe" | "unsubscribe" => Err(IpcError::from(format!(
+            "'{}' is only available on a named-pipe connection",
+            cmd
+        ))),
+        "get" => get_paths(&reg, args.as_ref()),
+        // `{ unit, display_name, kind }` per field path, for the fields in the
+        // current data. Optional `section` filter.
+        "describe" => {
+            let section = args.as_ref().and_then(|a| a.get("section")).and_then(|v| v.as_str());
+            let category = match section {
+                Some(s) => Some(section_to_internal_category(s).ok_or_else(|| {
+                    IpcError::new(codes::BAD_ARGS, format!("Unknown sysdata section: {}", s))
+                })?),
+                None => None,
+            };
+            let sections = reg
+                .sysdata
+                .iter()
+                .filter(|e| category.map_or(true, |c| e.category == c))
+                .map(|e| (if e.category == "display" { "displays" } else { e.category.as_str() }, &e.metadata));
+            Ok(describe_fields(sections))
+        }
         "get_displays" => {
-            let monitors = MonitorManager::enumerate_monitors();
+            let monitors = if mock_enabled() {
+                mock_monitors()
+            } else {
+                MonitorManager::enumerate_monitors()
+            };
             let displays: Vec<Value> = monitors.into_iter().map(|m| {
                 serde_json::json!({
                     "id": m.id,
@@ -44,6 +105,11 @@ pub fn dispatch_sysdata(cmd: &str) -> Result<Value, String> {
                     "product_code": m.product_code,
                     "serial_number": m.serial_number,
                     "year_of_manufacture": m.year_of_manufacture,
+                    "icc_profile_name": m.icc_profile_name,
+                    "gamma_ramp_identity": m.gamma_ramp_identity,
+                    "brightness_percent": m.brightness_percent,
+                    "device_id": m.device_id,
+                    "stable_id": m.stable_id,
                 })
             }).collect();
 
@@ -81,6 +147,6 @@ pub fn dispatch_sysdata(cmd: &str) -> Result<Value, String> {
         "get_tray_icons" => {
             Ok(crate::ipc::appdata::trayicons::get_tray_icons_json())
         }
-        _ => Err(format!("Unknown sysdata command: {}", cmd)),
+        _ => Err(IpcError::new(codes::UNKNOWN_COMMAND, format!("Unknown sysdata command: {}", cmd))),
     }
 }
\ No newline at end of file
@@ -0,0 +1,117 @@
+// ~/veil/veil-backend/src/ipc/dispatch/uid.rs
+//
+// "ui" IPC namespace — lets addons deep-link into the VEIL shell.
+//
+// Commands:
+//   open  { addon_id: "<id>", tab: "<tab id or title>" }
+//         Show the shell on an addon, optionally on one of its tabs. A shell
+//         that is already running picks the request up on its next `poll`
+//         and brings its window forward; otherwise a new shell is launched
+//         with `--addon-config-ui <id> --tab <tab>`.
+//   poll  Called by the shell about twice a second. Marks it as running and
+//         returns the pending `open` request, or null.
+//   focus Like `open` without picking an addon: bring the shell forward as
+//         it is, or launch it on the last view. Used by the global hotkey
+//         and by a second VEIL launch.
+
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::info;
+
+/// A shell that hasn't polled for this long is treated as closed.
+const SHELL_POLL_TTL_MS: u64 = 2500;
+/// How long a freshly launched shell may take to start polling before
+/// another `open` launches a second one.
+const SHELL_LAUNCH_GRACE_MS: u64 = 15_000;
+
+static SHELL_LAST_POLL_MS: AtomicU64 = AtomicU64::new(0);
+static SHELL_LAUNCHED_MS: AtomicU64 = AtomicU64::new(0);
+static PENDING_OPEN: Mutex<Option<Value>> = Mutex::new(None);
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn shell_running() -> bool {
+    let now = now_ms();
+    now.saturating_sub(SHELL_LAST_POLL_MS.load(Ordering::Relaxed)) <= SHELL_POLL_TTL_MS
+        || now.saturating_sub(SHELL_LAUNCHED_MS.load(Ordering::Relaxed)) <= SHELL_LAUNCH_GRACE_MS
+}
+
+/// Launch a shell on `addon_id`, or on the view it was last left on.
+fn launch_shell(addon_id: Option<&str>, tab: Option<&str>) -> Result<(), String> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to resolve executable: {}", e))?;
+    let mut cmd = std::process::Command::new(exe);
+    cmd.arg("--addon-config-ui");
+    if let Some(addon_id) = addon_id {
+        cmd.arg(addon_id);
+    }
+    if let Some(tab) = tab {
+        cmd.arg("--tab").arg(tab);
+    }
+    let child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to launch VEIL UI: {}", e))?;
+    SHELL_LAUNCHED_MS.store(now_ms(), Ordering::Relaxed);
+    info!("[ui] Launched shell for {:?} (PID {})", addon_id, child.id());
+    Ok(())
+}
+
+/// Bring a running shell forward as it is, or launch one. Returns what
+/// happened: `"routed"` or `"launched"`.
+pub fn open_or_focus_ui() -> Result<&'static str, String> {
+    if shell_running() {
+        *PENDING_OPEN.lock().unwrap() = Some(json!({ "addon_id": null, "tab": null }));
+        Ok("routed")
+    } else {
+        launch_shell(None, None)?;
+        Ok("launched")
+    }
+}
+
+pub fn dispatch_ui(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    match cmd {
+        "open" => {
+            let addon_id = args
+                .as_ref()
+                .and_then(|a| a.get("addon_id"))
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.trim().is_empty())
+                .ok_or("Missing 'addon_id' in args")?;
+            let tab = args
+                .as_ref()
+                .and_then(|a| a.get("tab"))
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.trim().is_empty());
+
+            if shell_running() {
+                *PENDING_OPEN.lock().unwrap() = Some(json!({ "addon_id": addon_id, "tab": tab }));
+                info!("[ui] Routing open '{}' (tab {:?}) to the running shell", addon_id, tab);
+                Ok(json!({ "status": "routed", "addon_id": addon_id, "tab": tab }))
+            } else {
+                launch_shell(Some(addon_id), tab)?;
+                Ok(json!({ "status": "launched", "addon_id": addon_id, "tab": tab }))
+            }
+        }
+
+        "focus" => {
+            let status = open_or_focus_ui()?;
+            Ok(json!({ "status": status }))
+        }
+
+        "poll" => {
+            SHELL_LAST_POLL_MS.store(now_ms(), Ordering::Relaxed);
+            SHELL_LAUNCHED_MS.store(0, Ordering::Relaxed);
+            let open = PENDING_OPEN.lock().unwrap().take();
+            Ok(json!({ "open": open }))
+        }
+
+        _ => Err(format!("Unknown ui command: {}", cmd)),
+    }
+}
@@ -0,0 +1,23 @@
+// ~/veil/veil-backend/src/ipc/dispatch/wifid.rs
+
+use serde_json::Value;
+use crate::ipc::sysdata::{mock, wifi};
+
+pub fn dispatch_wifi(cmd: &str, args: Option<Value>) -> Result<Value, String> {
+    match cmd {
+        // Nearby networks. Args: `refresh` (bypass the cache).
+        "scan" => {
+            if mock::mock_enabled() {
+                return Ok(mock::mock_wifi_scan());
+            }
+            let refresh = args
+                .as_ref()
+                .and_then(|a| a.get("refresh"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            Ok(wifi::scan_networks_json(refresh))
+        }
+
+        _ => Err(format!("Unknown wifi command: {}", cmd)),
+    }
+}
@@ -10,7 +10,10 @@
 //   POST /api/{ns}/{cmd}   (JSON body = args)
 //   OPTIONS *               (CORS preflight)
 //
-// Binds to 127.0.0.1:9851 (localhost only — no remote exposure).
+// Binds to 127.0.0.1:9851 (localhost only — no remote exposure). Any page
+// the user opens can still reach that address, so browser requests are only
+// answered for local origins and those listed in `http_bridge_origins`, and
+// POST bodies must be `application/json` (which a plain form can't send).
 
 use std::io::{BufRead, BufReader, Read, Write};
 use std::net::{TcpListener, TcpStream};
@@ -60,8 +63,16 @@ fn handle_connection(stream: TcpStream) -> Result<(), Box<dyn std::error::Error>
     let method = parts[0];
     let raw_path = parts[1];
 
-    // Read headers — we need Content-Length for POST bodies
+    // Read headers — we need Content-Length and Content-Type for POST bodies,
+    // Origin and Sec-Fetch-Site to tell browser requests apart, the optional
+    // X-VEIL-Addon caller id for capability checks, and X-VEIL-Token for
+    // privileged namespaces.
     let mut content_length: usize = 0;
+    let mut content_type: Option<String> = None;
+    let mut origin: Option<String> = None;
+    let mut fetch_site: Option<String> = None;
+    let mut addon_id: Option<String> = None;
+    let mut token: Option<String> = None;
     loop {
         let mut line = String::new();
         reader.read_line(&mut line)?;
@@ -73,12 +84,39 @@ fn handle_connection(stream: TcpStream) -> Result<(), Box<dyn std::error::Error>
             content_length = val.trim().parse().unwrap_or(0);
         } else if let Some(val) = trimmed.strip_prefix("content-length:") {
             content_length = val.trim().parse().unwrap_or(0);
+        } else if let Some((name, val)) = trimmed.split_once(':') {
+            let (name, val) = (name.trim(), val.trim());
+            if val.is_empty() {
+                continue;
+            }
+            if name.eq_ignore_ascii_case("X-VEIL-Addon") {
+                addon_id = Some(val.to_string());
+            } else if name.eq_ignore_ascii_case("X-VEIL-Token") {
+                token = Some(val.to_string());
+            } else if name.eq_ignore_ascii_case("Content-Type") {
+                content_type = Some(val.to_string());
+            } else if name.eq_ignore_ascii_case("Origin") {
+                origin = Some(val.to_string());
+            } else if name.eq_ignore_ascii_case("Sec-Fetch-Site") {
+                fetch_site = Some(val.to_string());
+            }
         }
     }
 
+    if !origin_allowed(origin.as_deref(), fetch_site.as_deref()) {
+        crate::warn!("[HTTP] Rejected {} {} from origin {}", method, raw_path, origin.as_deref().unwrap_or("(none)"));
+        let body = serde_json::json!({
+            "ok": false,
+            "error": "Origin not allowed; add it to http_bridge_origins in config.yaml",
+            "error_code": crate::ipc::response::codes::PERMISSION_DENIED,
+        }).to_string();
+        return write_response(&stream, 403, &body, Some("application/json"), None);
+    }
+    let origin = origin.as_deref();
+
     // Handle CORS preflight
     if method == "OPTIONS" {
-        return write_response(&stream, 204, "", None);
+        return write_response(&stream, 204, "", None, origin);
     }
 
     // Parse path and query string
@@ -96,57 +134,141 @@ fn handle_connection(stream: TcpStream) -> Result<(), Box<dyn std::error::Error>
 
         // Build args from query string (GET) or body (POST)
         let args = if method == "POST" && content_length > 0 {
+            if !is_json(content_type.as_deref()) {
+                let body = serde_json::json!({
+                    "ok": false,
+                    "error": "POST bodies must be sent as Content-Type: application/json",
+                    "error_code": crate::ipc::response::codes::BAD_ARGS,
+                }).to_string();
+                return write_response(&stream, 415, &body, Some("application/json"), origin);
+            }
             let mut body = vec![0u8; content_length.min(1_048_576)]; // 1MB cap
             reader.read_exact(&mut body)?;
-            serde_json::from_slice(&body).ok()
+            match serde_json::from_slice(&body) {
+                Ok(args) => Some(args),
+                Err(e) => {
+                    let body = serde_json::json!({
+                        "ok": false,
+                        "error": format!("Invalid JSON body: {}", e),
+                        "error_code": crate::ipc::response::codes::BAD_ARGS,
+                    }).to_string();
+                    return write_response(&stream, 400, &body, Some("application/json"), origin);
+                }
+            }
         } else {
             parse_query_to_args(query)
         };
 
-        let body = match crate::ipc::dispatch::dispatch(ns, cmd, args) {
+        let caller = match crate::ipc::token::authorize(ns, args.as_ref(), token.as_deref(), addon_id.as_deref()) {
+            Ok(caller) => caller,
+            Err(e) => {
+                let body = serde_json::json!({ "ok": false, "error": e.message, "error_code": e.code }).to_string();
+                return write_response(&stream, 401, &body, Some("application/json"), origin);
+            }
+        };
+
+        if crate::config::enforce_addon_permissions() {
+            if let Err(e) = crate::ipc::addon::capabilities::check_caller_permission(&caller, ns, cmd, args.as_ref()) {
+                let body = serde_json::json!({
+                    "ok": false,
+                    "error": e,
+                    "error_code": crate::ipc::response::codes::PERMISSION_DENIED,
+                }).to_string();
+                return write_response(&stream, 403, &body, Some("application/json"), origin);
+            }
+        }
+
+        let result = crate::ipc::dispatch::dispatch_as(ns, cmd, args, &caller);
+        let body = match result {
             Ok(data) => {
                 serde_json::json!({ "ok": true, "data": data }).to_string()
             }
             Err(e) => {
-                serde_json::json!({ "ok": false, "error": e }).to_string()
+                serde_json::json!({ "ok": false, "error": e.message, "error_code": e.code }).to_string()
             }
         };
 
-        write_response(&stream, 200, &body, Some("application/json"))
+        write_response(&stream, 200, &body, Some("application/json"), origin)
     } else {
         let body = serde_json::json!({
             "ok": false,
             "error": "Unknown endpoint. Use /api/{ns}/{cmd}"
         }).to_string();
-        write_response(&stream, 404, &body, Some("application/json"))
+        write_response(&stream, 404, &body, Some("application/json"), origin)
     }
 }
 
 // ── Helpers ───────────────────────────────────────────────────────────
 
-/// Write an HTTP response with CORS headers.
+/// Whether to answer a request with this `Origin` / `Sec-Fetch-Site`.
+///
+/// Browsers send `Origin` with every cross-origin fetch, so one that isn't
+/// local or listed is refused. Without an `Origin` the caller is either a
+/// native client or a page's plain `<img>`/link request; browsers tag the
+/// latter with a `Sec-Fetch-Site` other than `none`.
+fn origin_allowed(origin: Option<&str>, fetch_site: Option<&str>) -> bool {
+    match origin {
+        Some(origin) => {
+            is_local_origin(origin)
+                || crate::config::http_bridge_origins()
+                    .iter()
+                    .any(|o| o.trim_end_matches('/').eq_ignore_ascii_case(origin))
+        }
+        None => fetch_site.is_none_or(|site| site.eq_ignore_ascii_case("none")),
+    }
+}
+
+/// `http(s)://localhost`, `127.0.0.1` or `[::1]`, on any port. `null` (file://
+/// pages, sandboxed frames) is not local; list it to allow it.
+fn is_local_origin(origin: &str) -> bool {
+    let Some(rest) = origin.strip_prefix("http://").or_else(|| origin.strip_prefix("https://")) else {
+        return false;
+    };
+    let host = match rest.strip_prefix('[') {
+        Some(v6) => v6.split_once(']').map_or("", |(h, _)| h),
+        None => rest.split(':').next().unwrap_or_default(),
+    };
+    host.eq_ignore_ascii_case("localhost") || host == "127.0.0.1" || host == "::1"
+}
+
+/// `application/json`, with or without parameters such as `charset`.
+fn is_json(content_type: Option<&str>) -> bool {
+    content_type
+        .and_then(|ct| ct.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+}
+
+/// Write an HTTP response, with CORS headers for an allowed `origin`.
 fn write_response(
     mut stream: &TcpStream,
     status: u16,
     body: &str,
     content_type: Option<&str>,
+    origin: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let status_text = match status {
         200 => "OK",
         204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
         404 => "Not Found",
+        415 => "Unsupported Media Type",
         _ => "OK",
     };
 
-    let mut headers = format!(
-        "HTTP/1.1 {} {}\r\n\
-         Access-Control-Allow-Origin: *\r\n\
-         Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
-         Access-Control-Allow-Headers: Content-Type\r\n\
-         Access-Control-Max-Age: 86400\r\n\
-         Connection: close\r\n",
-        status, status_text,
-    );
+    let mut headers = format!("HTTP/1.1 {} {}\r\n", status, status_text);
+    if let Some(origin) = origin {
+        headers.push_str(&format!(
+            "Access-Control-Allow-Origin: {}\r\n\
+             Vary: Origin\r\n\
+             Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type, X-VEIL-Addon, X-VEIL-Token\r\n\
+             Access-Control-Max-Age: 86400\r\n",
+            origin,
+        ));
+    }
+    headers.push_str("Connection: close\r\n");
 
     if let Some(ct) = content_type {
         headers.push_str(&format!("Content-Type: {}\r\n", ct));
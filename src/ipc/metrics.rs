@@ -0,0 +1,707 @@
+// ~/veil/veil-backend/src/ipc/metrics.rs
+//
+// Human-facing names for metric paths. One table shared by the Data page,
+// the search palette and the `schema.metrics` command, so a label or unit
+// only has to change here.
+//
+// `sysdata.describe` covers every field rather than just these: it walks the
+// live sysdata and looks each field up by its full path in FIELD_GROUPS, so
+// the descriptors list exactly what the collectors emit and no unit is
+// guessed from a name.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::OnceLock;
+
+use crate::ipc::smoothing::SMOOTHED_SUFFIX;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MetricInfo {
+    /// Full dotted path, e.g. `sysdata.cpu.usage_percent`.
+    pub path: &'static str,
+    pub label: &'static str,
+    /// Display unit: `%`, `B`, `B/s`, `MHz`, `MB`, `°C`, `W`, `s`, `ms`, `px`, `px/s`, or empty for counts/text.
+    pub unit: &'static str,
+    /// Section the metric belongs to (matches the `sysdata` key).
+    pub category: &'static str,
+}
+
+const fn m(path: &'static str, label: &'static str, unit: &'static str, category: &'static str) -> MetricInfo {
+    MetricInfo { path, label, unit, category }
+}
+
+pub static METRICS: &[MetricInfo] = &[
+    // cpu
+    m("sysdata.cpu.usage_percent", "CPU Usage", "%", "cpu"),
+    m("sysdata.cpu.frequency_mhz", "CPU Speed", "MHz", "cpu"),
+    m("sysdata.cpu.base_frequency_mhz", "CPU Base Speed", "MHz", "cpu"),
+    m("sysdata.cpu.max_frequency_mhz", "CPU Max Speed", "MHz", "cpu"),
+    m("sysdata.cpu.logical_cores", "Logical Processors", "", "cpu"),
+    m("sysdata.cpu.physical_cores", "Cores", "", "cpu"),
+    m("sysdata.cpu.process_count", "Processes", "", "cpu"),
+    m("sysdata.cpu.thread_count", "Threads", "", "cpu"),
+    m("sysdata.cpu.handle_count", "Handles", "", "cpu"),
+    m("sysdata.cpu.temperature.average_c", "CPU Temperature", "°C", "cpu"),
+    m("sysdata.cpu.uptime_seconds", "Up Time", "s", "cpu"),
+    // gpu
+    m("sysdata.gpu.usage_percent", "GPU Load", "%", "gpu"),
+    m("sysdata.gpu.vram_used_mb", "Dedicated Memory Used", "MB", "gpu"),
+    m("sysdata.gpu.vram_total_mb", "Dedicated Memory", "MB", "gpu"),
+    m("sysdata.gpu.memory_usage_percent", "GPU Memory Usage", "%", "gpu"),
+    m("sysdata.gpu.temperature_c", "GPU Temperature", "°C", "gpu"),
+    m("sysdata.gpu.power_draw_w", "GPU Power Draw", "W", "gpu"),
+    m("sysdata.gpu.fan_speed_percent", "GPU Fan Speed", "%", "gpu"),
+    m("sysdata.gpu.clock_graphics_mhz", "GPU Clock", "MHz", "gpu"),
+    m("sysdata.gpu.clock_memory_mhz", "GPU Memory Clock", "MHz", "gpu"),
+    m("sysdata.gpu.encoder_usage_percent", "Video Encode", "%", "gpu"),
+    m("sysdata.gpu.decoder_usage_percent", "Video Decode", "%", "gpu"),
+    // ram
+    m("sysdata.ram.usage_percent", "Memory Usage", "%", "ram"),
+    m("sysdata.ram.used_bytes", "Memory In Use", "B", "ram"),
+    m("sysdata.ram.available_bytes", "Memory Available", "B", "ram"),
+    m("sysdata.ram.total_bytes", "Total Memory", "B", "ram"),
+    m("sysdata.ram.free_bytes", "Memory Free", "B", "ram"),
+    m("sysdata.ram.standby_bytes", "Standby", "B", "ram"),
+    m("sysdata.ram.modified_bytes", "Modified", "B", "ram"),
+    m("sysdata.ram.cached_bytes", "Cached", "B", "ram"),
+    m("sysdata.ram.committed_bytes", "Committed", "B", "ram"),
+    m("sysdata.ram.swap_usage_percent", "Swap Usage", "%", "ram"),
+    m("sysdata.ram.swap_used_bytes", "Swap Used", "B", "ram"),
+    // storage
+    m("sysdata.storage.usage_percent", "Disk Usage", "%", "storage"),
+    m("sysdata.storage.used_bytes", "Disk Used", "B", "storage"),
+    m("sysdata.storage.available_bytes", "Disk Available", "B", "storage"),
+    m("sysdata.storage.total_bytes", "Disk Capacity", "B", "storage"),
+    m("sysdata.storage.disk_count", "Disks", "", "storage"),
+    // network
+    m("sysdata.network.received_bytes_per_second", "Download Rate", "B/s", "network"),
+    m("sysdata.network.transmitted_bytes_per_second", "Upload Rate", "B/s", "network"),
+    m("sysdata.network.total_received_bytes", "Total Received", "B", "network"),
+    m("sysdata.network.total_transmitted_bytes", "Total Sent", "B", "network"),
+    m("sysdata.network.session_received_bytes", "Received This Session", "B", "network"),
+    m("sysdata.network.session_transmitted_bytes", "Sent This Session", "B", "network"),
+    m("sysdata.network.total_packets_received", "Packets In", "", "network"),
+    m("sysdata.network.total_packets_transmitted", "Packets Out", "", "network"),
+    m("sysdata.network.total_errors_received", "Errors In", "", "network"),
+    m("sysdata.network.total_errors_transmitted", "Errors Out", "", "network"),
+    m("sysdata.network.interface_count", "Interfaces", "", "network"),
+    m("sysdata.network.data_cap.used_bytes", "Metered Data This Month", "B", "network"),
+    m("sysdata.network.data_cap.used_percent", "Data Cap Used", "%", "network"),
+    // wifi
+    m("sysdata.wifi.signal_quality", "Wi-Fi Signal", "%", "wifi"),
+    m("sysdata.wifi.ssid", "Wi-Fi Network", "", "wifi"),
+    // mouse
+    m("sysdata.mouse.velocity_px_per_sec", "Mouse Velocity", "px/s", "mouse"),
+    m("sysdata.mouse.movement_px_since_boot", "Mouse Distance", "px", "mouse"),
+    m("sysdata.mouse.wheel_ticks_since_boot", "Wheel Ticks", "", "mouse"),
+    // audio
+    m("sysdata.audio.output_device.volume_percent", "Output Volume", "%", "audio"),
+    m("sysdata.audio.output_device.muted", "Output Muted", "", "audio"),
+    m("sysdata.audio.output_device.audio_level", "Output Level", "", "audio"),
+    m("sysdata.audio.input_device.volume_percent", "Input Volume", "%", "audio"),
+    // media
+    m("sysdata.media.title", "Now Playing", "", "media"),
+    m("sysdata.media.artist", "Artist", "", "media"),
+    m("sysdata.media.playback_status", "Playback Status", "", "media"),
+    m("sysdata.media.timeline.position_ms", "Track Position", "ms", "media"),
+    // power
+    m("sysdata.power.battery.percent", "Battery", "%", "power"),
+    m("sysdata.power.battery.charging", "Charging", "", "power"),
+    m("sysdata.power.battery.lifetime_seconds", "Battery Time Left", "s", "power"),
+    m("sysdata.power.battery.health_percent", "Battery Health", "%", "power"),
+    m("sysdata.power.battery.cycle_count", "Battery Cycles", "", "power"),
+    m("sysdata.power.ac_status", "AC Power", "", "power"),
+    // idle
+    m("sysdata.idle.idle_seconds", "Idle Time", "s", "idle"),
+    // processes
+    m("sysdata.processes.total_count", "Process Count", "", "processes"),
+    // time
+    m("sysdata.time.uptime_seconds", "System Up Time", "s", "time"),
+    m("sysdata.time.human_time", "Local Time", "", "time"),
+    m("sysdata.time.human_date", "Local Date", "", "time"),
+];
+
+/// Look up a metric; the `sysdata.` prefix is optional.
+pub fn metric_info(path: &str) -> Option<&'static MetricInfo> {
+    METRICS.iter().find(|m| {
+        m.path == path || m.path.strip_prefix("sysdata.") == Some(path)
+    })
+}
+
+/// `{ path: label }` for every known metric, for UIs that only need labels.
+pub fn metric_labels_json() -> Value {
+    let map: serde_json::Map<String, Value> = METRICS
+        .iter()
+        .map(|m| (m.path.to_string(), Value::String(m.label.to_string())))
+        .collect();
+    Value::Object(map)
+}
+
+/// Full table, optionally limited to one category.
+pub fn metrics_json(category: Option<&str>) -> Value {
+    let list: Vec<&MetricInfo> = METRICS
+        .iter()
+        .filter(|m| category.map(|c| m.category.eq_ignore_ascii_case(c)).unwrap_or(true))
+        .collect();
+    json!({ "metrics": list })
+}
+
+/// What a sysdata field measures.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    Percent,
+    Bytes,
+    BytesPerSec,
+    Celsius,
+    Hz,
+    Count,
+    /// ISO 8601 date/time string.
+    Iso8601,
+    Duration,
+    /// Unix time, in `unit`.
+    Timestamp,
+    /// Any other quantity; `unit` says which, if any.
+    Number,
+}
+
+/// Unit of one sysdata field. `name` is the field's path below the group
+/// prefix it is listed under.
+struct FieldUnit {
+    name: &'static str,
+    kind: FieldKind,
+    unit: &'static str,
+}
+
+const fn f(name: &'static str, kind: FieldKind, unit: &'static str) -> FieldUnit {
+    FieldUnit { name, kind, unit }
+}
+
+/// Fields with one shape, placed under each of `prefixes`.
+struct FieldGroup {
+    prefixes: &'static [&'static str],
+    fields: &'static [FieldUnit],
+}
+
+use FieldKind::{Bytes, BytesPerSec, Celsius, Count, Duration, Hz, Iso8601, Number, Percent, Timestamp};
+
+/// Every numeric and date field the collectors emit, keyed by full path
+/// (`<prefix>.<name>`, `*` for array elements). Only ISO 8601 strings are
+/// listed; other strings are not described. A collector that adds a field
+/// adds it here too, and `sysdata.describe` lists it under `undescribed`
+/// until then.
+static FIELD_GROUPS: &[FieldGroup] = &[
+    FieldGroup { prefixes: &["cpu"], fields: &[
+        f("usage_percent", Percent, "%"),
+        f("per_core_usage.*", Percent, "%"),
+        f("per_core.*.core_id", Number, ""),
+        f("per_core.*.usage_percent", Percent, "%"),
+        f("per_core.*.frequency_mhz", Hz, "MHz"),
+        f("frequency_mhz", Hz, "MHz"),
+        f("base_frequency_mhz", Hz, "MHz"),
+        f("max_frequency_mhz", Hz, "MHz"),
+        f("logical_cores", Count, ""),
+        f("physical_cores", Count, ""),
+        f("sockets", Count, ""),
+        f("l1_cache_kb", Bytes, "KB"),
+        f("l2_cache_kb", Bytes, "KB"),
+        f("l3_cache_kb", Bytes, "KB"),
+        f("process_count", Count, ""),
+        f("thread_count", Count, ""),
+        f("handle_count", Count, ""),
+        f("uptime_seconds", Duration, "s"),
+        f("boot_time_unix", Timestamp, "s"),
+    ] },
+    FieldGroup { prefixes: &["cpu.temperature", "gpu.temperature"], fields: &[
+        f("average_c", Celsius, "°C"),
+    ] },
+    FieldGroup { prefixes: &["cpu.temperature.sensors.*", "gpu.temperature.sensors.*", "gpu.sensors.*"], fields: &[
+        f("temperature_c", Celsius, "°C"),
+        f("max_c", Celsius, "°C"),
+        f("critical_c", Celsius, "°C"),
+    ] },
+    // The top-level gpu fields repeat the primary adapter's.
+    FieldGroup { prefixes: &["gpu", "gpu.adapters.*"], fields: &[
+        f("usage_percent", Percent, "%"),
+        f("memory_usage_percent", Percent, "%"),
+        f("encoder_usage_percent", Percent, "%"),
+        f("decoder_usage_percent", Percent, "%"),
+        f("fan_speed_percent", Percent, "%"),
+        f("vram_total_mb", Bytes, "MB"),
+        f("vram_used_mb", Bytes, "MB"),
+        f("vram_free_mb", Bytes, "MB"),
+        f("shared_gpu_memory_bytes", Bytes, "B"),
+        f("temperature_c", Celsius, "°C"),
+        f("power_draw_w", Number, "W"),
+        f("clock_graphics_mhz", Hz, "MHz"),
+        f("clock_memory_mhz", Hz, "MHz"),
+        f("physical_location.bus", Number, ""),
+        f("physical_location.device", Number, ""),
+        f("physical_location.function", Number, ""),
+        f("video_engines.*.usage_percent", Percent, "%"),
+    ] },
+    FieldGroup { prefixes: &["gpu.adapters.*"], fields: &[
+        f("adapter_ram_bytes", Bytes, "B"),
+        f("current_refresh_rate_hz", Hz, "Hz"),
+        f("processes.*.pid", Number, ""),
+        f("processes.*.vram_bytes", Bytes, "B"),
+    ] },
+    FieldGroup { prefixes: &["gpu.summary"], fields: &[
+        f("adapter_count", Count, ""),
+        f("busiest_adapter_index", Number, ""),
+        f("max_usage_percent", Percent, "%"),
+        f("total_vram_used_mb", Bytes, "MB"),
+        f("total_vram_total_mb", Bytes, "MB"),
+        f("vram_usage_percent", Percent, "%"),
+    ] },
+    FieldGroup { prefixes: &["ram"], fields: &[
+        f("usage_percent", Percent, "%"),
+        f("total_bytes", Bytes, "B"),
+        f("used_bytes", Bytes, "B"),
+        f("available_bytes", Bytes, "B"),
+        f("free_bytes", Bytes, "B"),
+        f("standby_bytes", Bytes, "B"),
+        f("modified_bytes", Bytes, "B"),
+        f("cached_bytes", Bytes, "B"),
+        f("committed_bytes", Bytes, "B"),
+        f("commit_limit_bytes", Bytes, "B"),
+        f("compressed_bytes", Bytes, "B"),
+        f("hardware_reserved_bytes", Bytes, "B"),
+        f("paged_pool_bytes", Bytes, "B"),
+        f("non_paged_pool_bytes", Bytes, "B"),
+        f("swap_usage_percent", Percent, "%"),
+        f("swap_total_bytes", Bytes, "B"),
+        f("swap_used_bytes", Bytes, "B"),
+        f("swap_free_bytes", Bytes, "B"),
+        f("speed_mhz", Hz, "MHz"),
+        f("slots_used", Count, ""),
+        f("slots_total", Count, ""),
+        f("sticks.*.capacity_bytes", Bytes, "B"),
+        f("sticks.*.speed_mhz", Hz, "MHz"),
+        f("sticks.*.configured_speed_mhz", Hz, "MHz"),
+        f("sticks.*.data_width_bits", Number, "bit"),
+        f("sticks.*.total_width_bits", Number, "bit"),
+        f("top_processes.*.pid", Number, ""),
+        f("top_processes.*.memory_bytes", Bytes, "B"),
+        f("top_processes.*.virtual_memory_bytes", Bytes, "B"),
+    ] },
+    FieldGroup { prefixes: &["storage", "storage.disks.*"], fields: &[
+        f("usage_percent", Percent, "%"),
+        f("total_bytes", Bytes, "B"),
+        f("used_bytes", Bytes, "B"),
+        f("available_bytes", Bytes, "B"),
+    ] },
+    FieldGroup { prefixes: &["storage"], fields: &[
+        f("disk_count", Count, ""),
+    ] },
+    FieldGroup { prefixes: &["storage.disks.*"], fields: &[
+        f("disk_number", Number, ""),
+        f("physical_capacity_bytes", Bytes, "B"),
+    ] },
+    FieldGroup { prefixes: &["storage.physical_disks.*"], fields: &[
+        f("disk_number", Number, ""),
+        f("capacity_bytes", Bytes, "B"),
+    ] },
+    FieldGroup { prefixes: &["storage.disks.*.smart", "storage.physical_disks.*.smart"], fields: &[
+        f("temperature_c", Celsius, "°C"),
+        f("power_on_hours", Duration, "h"),
+        f("reallocated_sectors", Count, ""),
+        f("media_errors", Count, ""),
+        f("wear_leveling_percent", Percent, "%"),
+    ] },
+    // The top-level network fields add up the interfaces'.
+    FieldGroup { prefixes: &["network", "network.interfaces.*"], fields: &[
+        f("received_bytes", Bytes, "B"),
+        f("transmitted_bytes", Bytes, "B"),
+        f("total_received_bytes", Bytes, "B"),
+        f("total_transmitted_bytes", Bytes, "B"),
+        f("session_received_bytes", Bytes, "B"),
+        f("session_transmitted_bytes", Bytes, "B"),
+        f("received_bytes_per_second", BytesPerSec, "B/s"),
+        f("transmitted_bytes_per_second", BytesPerSec, "B/s"),
+    ] },
+    FieldGroup { prefixes: &["network"], fields: &[
+        f("total_packets_received", Count, ""),
+        f("total_packets_transmitted", Count, ""),
+        f("total_errors_received", Count, ""),
+        f("total_errors_transmitted", Count, ""),
+        f("interface_count", Count, ""),
+        f("link_change_seq", Count, ""),
+        f("connectivity.checked_at_ms", Timestamp, "ms"),
+        f("top_talkers.*.pid", Number, ""),
+        f("top_talkers.*.sent_bps", BytesPerSec, "B/s"),
+        f("top_talkers.*.recv_bps", BytesPerSec, "B/s"),
+        f("data_cap.used_bytes", Bytes, "B"),
+        f("data_cap.cap_bytes", Bytes, "B"),
+        f("data_cap.used_percent", Percent, "%"),
+        f("data_cap.warn_percent", Percent, "%"),
+        f("data_cap.warned_at_ms", Timestamp, "ms"),
+    ] },
+    FieldGroup { prefixes: &["network.interfaces.*"], fields: &[
+        f("up_since_ms", Timestamp, "ms"),
+        f("ip_addresses.*.prefix", Number, "bit"),
+        f("packets.received", Count, ""),
+        f("packets.transmitted", Count, ""),
+        f("packets.total_received", Count, ""),
+        f("packets.total_transmitted", Count, ""),
+        f("errors.received", Count, ""),
+        f("errors.transmitted", Count, ""),
+        f("errors.total_received", Count, ""),
+        f("errors.total_transmitted", Count, ""),
+    ] },
+    FieldGroup { prefixes: &["wifi.connected"], fields: &[
+        f("signal_percent", Percent, "%"),
+        f("channel", Number, ""),
+        f("receive_rate_mbps", Number, "Mbit/s"),
+        f("transmit_rate_mbps", Number, "Mbit/s"),
+    ] },
+    FieldGroup { prefixes: &["audio"], fields: &[
+        f("output_device.volume_percent", Percent, "%"),
+        f("output_device.audio_level", Number, ""),
+        f("output_device.levels.peak", Number, ""),
+        f("output_device.levels.peak_db", Number, "dB"),
+        f("output_device.levels.rms", Number, ""),
+        f("output_device.levels.rms_db", Number, "dB"),
+        f("output_device.levels.smoothed_peak", Number, ""),
+        f("output_device.levels.smoothed_peak_db", Number, "dB"),
+        f("output_device.levels.smoothed_rms", Number, ""),
+        f("output_device.levels.smoothed_rms_db", Number, "dB"),
+        f("output_device.history.peak_32.*", Number, ""),
+        f("output_device.history.sample_count", Count, ""),
+        f("input_device.volume_percent", Percent, "%"),
+        f("sessions.*.pid", Number, ""),
+        f("sessions.*.volume_percent", Percent, "%"),
+        f("sessions.*.peak_level", Number, ""),
+        f("spectrum_32.*", Number, ""),
+    ] },
+    FieldGroup { prefixes: &["media", "audio.media_session"], fields: &[
+        f("track_number", Number, ""),
+        f("album_track_count", Count, ""),
+        f("playback_rate", Number, ""),
+        f("timeline.position_ms", Duration, "ms"),
+        f("timeline.start_ms", Duration, "ms"),
+        f("timeline.end_ms", Duration, "ms"),
+        f("timeline.duration_ms", Duration, "ms"),
+    ] },
+    FieldGroup { prefixes: &["time"], fields: &[
+        f("iso", Iso8601, ""),
+        f("utc_iso", Iso8601, ""),
+        f("unix", Timestamp, "s"),
+        f("unix_ms", Timestamp, "ms"),
+        f("year", Number, ""),
+        f("quarter", Number, ""),
+        f("month", Number, ""),
+        f("iso_week", Number, ""),
+        f("day", Number, ""),
+        f("day_of_year", Number, ""),
+        f("hour", Number, ""),
+        f("hour_12", Number, ""),
+        f("minute", Number, ""),
+        f("second", Number, ""),
+        f("millisecond", Number, ""),
+        f("utc_offset_seconds", Duration, "s"),
+        f("utc_offset_hours", Duration, "h"),
+        f("uptime_seconds", Duration, "s"),
+        f("boot_time_unix", Timestamp, "s"),
+    ] },
+    FieldGroup { prefixes: &["keyboard"], fields: &[
+        f("layout_change_seq", Count, ""),
+        f("type_id", Number, ""),
+        f("subtype", Number, ""),
+        f("function_key_count", Count, ""),
+        f("pressed_count", Count, ""),
+    ] },
+    FieldGroup { prefixes: &["mouse"], fields: &[
+        f("cursor.x", Number, "px"),
+        f("cursor.y", Number, "px"),
+        f("buttons.count", Count, ""),
+        f("buttons.left_clicks", Count, ""),
+        f("buttons.right_clicks", Count, ""),
+        f("buttons.middle_clicks", Count, ""),
+        f("movement_px_since_boot", Number, "px"),
+        f("wheel_ticks_since_boot", Count, ""),
+        f("velocity_px_per_sec", Number, "px/s"),
+        f("speed", Number, ""),
+        f("screen.primary_width", Number, "px"),
+        f("screen.primary_height", Number, "px"),
+        f("screen.virtual_width", Number, "px"),
+        f("screen.virtual_height", Number, "px"),
+        f("screen.virtual_x", Number, "px"),
+        f("screen.virtual_y", Number, "px"),
+        f("screen.monitor_count", Count, ""),
+    ] },
+    FieldGroup { prefixes: &["power.battery", "power.battery.details"], fields: &[
+        f("design_capacity_mwh", Number, "mWh"),
+        f("full_charge_capacity_mwh", Number, "mWh"),
+        f("health_percent", Percent, "%"),
+    ] },
+    FieldGroup { prefixes: &["power.battery"], fields: &[
+        f("percent", Percent, "%"),
+        f("lifetime_seconds", Duration, "s"),
+        f("fulllife_seconds", Duration, "s"),
+        f("cycle_count", Count, ""),
+    ] },
+    FieldGroup { prefixes: &["power.battery.details"], fields: &[
+        f("design_voltage_mv", Number, "mV"),
+        f("estimated_charge_percent", Percent, "%"),
+        f("estimated_runtime_minutes", Duration, "min"),
+        f("battery_status_code", Number, ""),
+    ] },
+    FieldGroup { prefixes: &["idle"], fields: &[
+        f("idle_ms", Duration, "ms"),
+        f("idle_time_ms", Duration, "ms"),
+        f("idle_seconds", Duration, "s"),
+        f("idle_minutes", Duration, "min"),
+        f("last_keyboard_activity_ms", Duration, "ms"),
+        f("last_mouse_activity_ms", Duration, "ms"),
+    ] },
+    FieldGroup { prefixes: &["bluetooth"], fields: &[
+        f("devices.*.battery_percent", Percent, "%"),
+    ] },
+    FieldGroup { prefixes: &["system"], fields: &[
+        f("uptime_seconds", Duration, "s"),
+        f("uptime_breakdown.days", Number, ""),
+        f("uptime_breakdown.hours", Number, ""),
+        f("uptime_breakdown.minutes", Number, ""),
+        f("uptime_breakdown.seconds", Number, ""),
+        f("boot_time_unix", Timestamp, "s"),
+        f("boot_time_iso", Iso8601, ""),
+        f("last_wake_unix", Timestamp, "s"),
+        f("last_wake_time", Iso8601, ""),
+        f("locale.timezone.utc_offset_hours", Duration, "h"),
+        f("theme.accent_color_raw", Number, ""),
+    ] },
+    FieldGroup { prefixes: &["processes"], fields: &[
+        f("total_count", Count, ""),
+        f("filtered_count", Count, ""),
+        f("total_cpu_usage", Percent, "%"),
+        f("total_memory_bytes", Bytes, "B"),
+        f("status_counts.running", Count, ""),
+        f("status_counts.sleeping", Count, ""),
+        f("status_counts.stopped", Count, ""),
+        f("status_counts.zombie", Count, ""),
+        f("status_counts.other", Count, ""),
+    ] },
+    FieldGroup { prefixes: &["processes.top_cpu.*", "processes.top_memory.*"], fields: &[
+        f("pid", Number, ""),
+        f("parent_pid", Number, ""),
+        f("cpu_percent", Percent, "%"),
+        f("memory_bytes", Bytes, "B"),
+        f("virtual_memory_bytes", Bytes, "B"),
+    ] },
+    FieldGroup { prefixes: &["displays.monitors.*"], fields: &[
+        f("x", Number, "px"),
+        f("y", Number, "px"),
+        f("width", Number, "px"),
+        f("height", Number, "px"),
+        f("scale", Number, ""),
+        f("dpi", Number, ""),
+        f("refresh_rate_hz", Hz, "Hz"),
+        f("color_depth_bits", Number, "bit"),
+        f("bits_per_channel", Number, "bit"),
+        f("physical_width_mm", Number, "mm"),
+        f("physical_height_mm", Number, "mm"),
+        f("year_of_manufacture", Number, ""),
+        f("brightness_percent", Percent, "%"),
+    ] },
+];
+
+/// `FIELD_GROUPS` by full path.
+fn field_units() -> &'static HashMap<String, &'static FieldUnit> {
+    static UNITS: OnceLock<HashMap<String, &'static FieldUnit>> = OnceLock::new();
+    UNITS.get_or_init(|| {
+        FIELD_GROUPS
+            .iter()
+            .flat_map(|group| {
+                group.prefixes.iter().flat_map(move |prefix| {
+                    group.fields.iter().map(move |field| (format!("{}.{}", prefix, field.name), field))
+                })
+            })
+            .collect()
+    })
+}
+
+/// Name endings that only spell out the unit, dropped from display names.
+const UNIT_SUFFIXES: &[&str] = &[
+    "_bytes_per_second", "_px_per_sec", "_px_since_boot", "_bytes", "_percent", "_mhz", "_hz",
+    "_kb", "_mb", "_mbps", "_bps", "_ms", "_seconds", "_minutes", "_hours", "_unix", "_iso",
+    "_c", "_w", "_mwh", "_mv", "_mm", "_bits", "_db",
+];
+
+/// `"base_frequency_mhz"` → `"Base Frequency"`: a unit suffix goes, the
+/// words are capitalised.
+fn humanize(name: &str, field: &FieldUnit) -> String {
+    // A unitless count keeps its whole name: `reallocated_sectors`.
+    let has_unit = !field.unit.is_empty() || field.kind == FieldKind::Iso8601;
+    let stem = if has_unit {
+        UNIT_SUFFIXES
+            .iter()
+            .find_map(|suffix| name.strip_suffix(suffix).filter(|s| !s.is_empty()))
+            .unwrap_or(name)
+    } else {
+        name
+    };
+    stem.split('_')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Default)]
+struct Descriptors {
+    fields: BTreeMap<String, Value>,
+    undescribed: BTreeSet<String>,
+}
+
+impl Descriptors {
+    /// Walk `value` below `path`. Array elements share one `*` path.
+    fn walk(&mut self, value: &Value, path: &mut String, name: &str) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let len = path.len();
+                    path.push('.');
+                    path.push_str(key);
+                    self.walk(child, path, key);
+                    path.truncate(len);
+                }
+            }
+            Value::Array(items) => {
+                let len = path.len();
+                path.push_str(".*");
+                for item in items {
+                    self.walk(item, path, name);
+                }
+                path.truncate(len);
+            }
+            Value::Number(_) | Value::String(_) => {
+                if self.fields.contains_key(path.as_str()) {
+                    return;
+                }
+                // A `_smoothed` sibling from `smoothing::apply` is described
+                // by the field it smooths.
+                let (base_path, base_name, smoothed) =
+                    match (field_units().contains_key(path.as_str()), path.strip_suffix(SMOOTHED_SUFFIX)) {
+                        (false, Some(base)) => (base, name.strip_suffix(SMOOTHED_SUFFIX).unwrap_or(name), true),
+                        _ => (path.as_str(), name, false),
+                    };
+                let field = field_units().get(base_path).copied();
+                if value.is_string() && field.map(|f| f.kind) != Some(FieldKind::Iso8601) {
+                    return;
+                }
+                let Some(field) = field else {
+                    self.undescribed.insert(path.clone());
+                    return;
+                };
+                let mut display_name = metric_info(base_path)
+                    .map(|m| m.label.to_string())
+                    .unwrap_or_else(|| humanize(base_name, field));
+                if smoothed {
+                    display_name.push_str(" (Smoothed)");
+                }
+                self.fields.insert(
+                    path.clone(),
+                    json!({ "unit": field.unit, "display_name": display_name, "kind": field.kind }),
+                );
+            }
+            Value::Bool(_) | Value::Null => {}
+        }
+    }
+}
+
+/// `{ fields: { path: { unit, display_name, kind } }, undescribed: [path] }`
+/// for the numeric and ISO 8601 fields in `sections` (`(sysdata key, value)`).
+/// Array elements appear once, as `*`. `undescribed` lists numeric fields
+/// missing from FIELD_GROUPS, and should stay empty.
+pub fn describe_fields<'a>(sections: impl IntoIterator<Item = (&'a str, &'a Value)>) -> Value {
+    let mut out = Descriptors::default();
+    for (section, value) in sections {
+        out.walk(value, &mut section.to_string(), section);
+    }
+    let fields: Map<String, Value> = out.fields.into_iter().collect();
+    json!({ "fields": fields, "undescribed": out.undescribed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::data_updater::displays_json;
+    use crate::ipc::sysdata::mock::{mock_monitors, mock_sysdata};
+
+    const MOCK_SECTIONS: &[&str] = &[
+        "cpu", "gpu", "ram", "storage", "network", "audio", "media", "time", "keyboard", "mouse",
+        "power", "idle", "bluetooth", "wifi", "system", "processes",
+    ];
+
+    #[test]
+    fn every_mock_sysdata_field_is_described() {
+        let mut sections: Vec<(&str, Value)> = MOCK_SECTIONS
+            .iter()
+            .map(|s| (*s, mock_sysdata(s).unwrap_or_else(|| panic!("no mock data for {}", s))))
+            .collect();
+        sections.push(("displays", displays_json(mock_monitors())));
+
+        let described = describe_fields(sections.iter().map(|(s, v)| (*s, v)));
+        assert_eq!(described["undescribed"], json!([]));
+        assert_eq!(described["fields"]["cpu.l3_cache_kb"]["unit"], "KB");
+        assert_eq!(described["fields"]["time.iso"]["kind"], "iso8601");
+    }
+
+    /// Dotted paths of the numbers in `value` whose parent is an object,
+    /// with array elements by index.
+    fn smoothable_paths(value: &Value, path: &str, out: &mut Vec<String>) {
+        let join = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    if child.is_number() {
+                        out.push(join(key));
+                    } else {
+                        smoothable_paths(child, &join(key), out);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    smoothable_paths(item, &join(&i.to_string()), out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn every_smoothed_field_is_described() {
+        let mut sections: Vec<(&str, Value)> = MOCK_SECTIONS
+            .iter()
+            .map(|s| (*s, mock_sysdata(s).unwrap_or_else(|| panic!("no mock data for {}", s))))
+            .collect();
+        sections.push(("displays", displays_json(mock_monitors())));
+        for (_, value) in &mut sections {
+            let mut paths = Vec::new();
+            smoothable_paths(value, "", &mut paths);
+            for path in paths {
+                crate::ipc::smoothing::smooth_field(value, &path, 0.5, None);
+            }
+        }
+
+        let described = describe_fields(sections.iter().map(|(s, v)| (*s, v)));
+        assert_eq!(described["undescribed"], json!([]));
+        let smoothed = &described["fields"]["cpu.usage_percent_smoothed"];
+        assert_eq!(smoothed["unit"], "%");
+        assert_eq!(smoothed["kind"], "percent");
+        assert!(smoothed["display_name"].as_str().unwrap().ends_with("(Smoothed)"));
+        assert_eq!(described["fields"]["cpu.per_core.*.frequency_mhz_smoothed"]["unit"], "MHz");
+    }
+
+    #[test]
+    fn no_field_is_listed_twice() {
+        let listed: usize = FIELD_GROUPS.iter().map(|g| g.prefixes.len() * g.fields.len()).sum();
+        assert_eq!(field_units().len(), listed);
+    }
+}
@@ -8,5 +8,15 @@ pub mod registry;
 pub mod sysdata;
 pub mod appdata;
 pub mod data_updater;
+pub mod delta;
 pub mod addon;
-pub mod http_bridge;
\ No newline at end of file
+pub mod http_bridge;
+pub mod assets;
+pub mod metrics;
+pub mod smoothing;
+pub mod snapshot;
+pub mod token;
+#[cfg(target_os = "windows")]
+pub mod subscription;
+#[cfg(not(target_os = "windows"))]
+mod unsupported;
\ No newline at end of file
@@ -12,9 +12,12 @@ use std::{
 
 use crate::{
     info, warn, error,
-    paths::veil_root_dir,
+    paths::{addons_dir, assets_dir, veil_root_dir},
+};
+use crate::ipc::data_updater::{
+    demand_tracking_active, section_interval_ms, section_last_updated_ms, section_to_internal_category,
+    section_tracking_enabled,
 };
-use crate::ipc::data_updater::{demand_tracking_active, section_tracking_enabled};
 
 /// Single registry entry (addon, widget, etc)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -36,6 +39,24 @@ pub struct Registry {
     pub appdata: Vec<RegistryEntry>,
 }
 
+impl Registry {
+    /// Value for a sysdata key: a section (`cpu`) or a dotted path inside one
+    /// (`cpu.temperature.average_c`, array elements by index). `None` when the
+    /// section has no entry or the path doesn't resolve.
+    pub fn sysdata_value(&self, key: &str) -> Option<Value> {
+        let (section, path) = match key.split_once('.') {
+            Some((section, path)) => (section, Some(path)),
+            None => (key, None),
+        };
+        let category = section_to_internal_category(section)?;
+        let entry = self.sysdata.iter().find(|e| e.category == category)?;
+        match path {
+            Some(p) => entry.metadata.pointer(&format!("/{}", p.replace('.', "/"))).cloned(),
+            None => Some(entry.metadata.clone()),
+        }
+    }
+}
+
 static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
 
 pub fn global_registry() -> &'static RwLock<Registry> {
@@ -49,6 +70,7 @@ pub fn global_registry() -> &'static RwLock<Registry> {
 pub fn discover_addons(addons_root: &Path) -> Vec<RegistryEntry> {
     info!("Discovering addons in '{}'", addons_root.display());
     let mut entries = Vec::new();
+    let filter = crate::config::addon_filter();
 
     if let Ok(read_dir) = std::fs::read_dir(addons_root) {
         for entry in read_dir.flatten() {
@@ -58,6 +80,12 @@ pub fn discover_addons(addons_root: &Path) -> Vec<RegistryEntry> {
             if let Ok(data) = std::fs::read_to_string(&manifest_path) {
                 match serde_json::from_str::<Value>(&data) {
                     Ok(mut meta) => {
+                        let folder = addon_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+                        if !filter.allows(meta["id"].as_str().unwrap_or(""), &folder) {
+                            info!("Skipping disabled addon: {}", folder);
+                            continue;
+                        }
+
                         info!("Discovered addon: {}", meta["name"].as_str().unwrap_or("unknown"));
 
                         // Convert exe_path to absolute path
@@ -78,6 +106,15 @@ pub fn discover_addons(addons_root: &Path) -> Vec<RegistryEntry> {
                             warn!("Addon '{}' has no 'exe_path' in manifest", addon_dir.display());
                         }
 
+                        // Normalise the capabilities/permissions declaration into one block.
+                        let caps = crate::ipc::addon::capabilities::parse_capabilities(&meta);
+                        meta["capabilities"] = serde_json::to_value(&caps).unwrap_or(Value::Null);
+
+                        if let Some(reason) = crate::ipc::addon::compat::incompatibility(&meta) {
+                            warn!("Addon '{}' can't be started: {}", meta["name"].as_str().unwrap_or("unknown"), reason);
+                            meta["incompatible"] = Value::String(reason);
+                        }
+
 
                         entries.push(RegistryEntry {
                             id: meta["id"].as_str().unwrap_or("").to_string(),
@@ -230,6 +267,11 @@ pub fn pull_sysdata_fast() -> Vec<RegistryEntry> {
                 "product_code": m.product_code,
                 "serial_number": m.serial_number,
                 "year_of_manufacture": m.year_of_manufacture,
+                "icc_profile_name": m.icc_profile_name,
+                "gamma_ramp_identity": m.gamma_ramp_identity,
+                "brightness_percent": m.brightness_percent,
+                "device_id": m.device_id,
+                "stable_id": m.stable_id,
             }),
             path: std::path::PathBuf::new(),
             exe_path: "".into(),
@@ -285,8 +327,8 @@ pub fn registry_manager() {
     // start immediately after, so the IPC server & tray come up fast.
     {
         let mut reg = global_registry().write().unwrap();
-        let addons = discover_addons(&root.join("Addons"));
-        let assets = discover_assets(&root.join("Assets"));
+        let addons = discover_addons(&addons_dir());
+        let assets = discover_assets(&assets_dir());
         *reg = Registry { addons, assets, sysdata: Vec::new(), appdata: Vec::new() };
         info!(
             "Registry initialized: {} addons, {} assets",
@@ -312,8 +354,8 @@ pub fn registry_watcher() -> Result<(), Box<dyn std::error::Error>> {
     info!("Starting registry watcher");
     let (tx, rx) = channel();
     let root = veil_root_dir();
-    let addons_root = root.join("Addons");
-    let assets_root = root.join("Assets");
+    let addons_root = addons_dir();
+    let assets_root = assets_dir();
 
     let mut watcher: RecommendedWatcher =
         Watcher::new(tx, Config::default().with_poll_interval(Duration::from_millis(250)))?;
@@ -345,7 +387,7 @@ pub fn registry_watcher() -> Result<(), Box<dyn std::error::Error>> {
 
                     if touches_content_tree {
                         info!("Detected Addons/Assets change, reloading registry");
-                        reload_registry(&root);
+                        reload_registry();
                     }
 
                 }
@@ -356,10 +398,10 @@ pub fn registry_watcher() -> Result<(), Box<dyn std::error::Error>> {
     }
 }
 
-fn reload_registry(root: &Path) {
+pub fn reload_registry() {
     info!("Reloading registry...");
-    let addons = discover_addons(&root.join("Addons"));
-    let assets = discover_assets(&root.join("Assets"));
+    let addons = discover_addons(&addons_dir());
+    let assets = discover_assets(&assets_dir());
 
     {
         let mut reg = global_registry().write().unwrap();
@@ -403,12 +445,16 @@ pub fn registry_to_output_json(reg: &Registry) -> Value {
     ];
 
     let mut sections_meta = serde_json::Map::new();
+    let mut last_collected_ms: Option<u64> = None;
 
     for (section, _value) in section_values {
+        last_collected_ms = last_collected_ms.max(section_last_updated_ms(section));
         sections_meta.insert(
             section.to_string(),
             serde_json::json!({
-                "tracked": section_tracking_enabled(section)
+                "tracked": section_tracking_enabled(section),
+                "last_updated_ms": section_last_updated_ms(section),
+                "interval_ms": section_interval_ms(section),
             }),
         );
     }
@@ -420,6 +466,11 @@ pub fn registry_to_output_json(reg: &Registry) -> Value {
         "appdata": appdata_out,
         "__meta": {
             "written_ms": now_ms,
+            "generated_at_ms": now_ms,
+            // While paused the sections keep their last values; `last_collected_ms`
+            // is when the newest of them was taken.
+            "paused": crate::config::pull_paused(),
+            "last_collected_ms": last_collected_ms,
             "tracking_active": tracking_active,
             "sections": sections_meta,
         }
@@ -1,136 +1,474 @@
 use serde::{Deserialize, Serialize};
-use serde_json::{Value, to_vec, from_slice};
+use serde_json::Value;
+#[cfg(target_os = "windows")]
+use serde_json::{to_vec, from_slice};
+#[cfg(target_os = "windows")]
+use std::sync::mpsc::{self, RecvTimeoutError};
+#[cfg(target_os = "windows")]
+use std::thread;
+#[cfg(target_os = "windows")]
+use std::time::{Duration, Instant};
+#[cfg(target_os = "windows")]
 use windows::core::PCWSTR;
+#[cfg(target_os = "windows")]
 use windows::Win32::{
-    Foundation::{CloseHandle, HANDLE, ERROR_PIPE_BUSY, ERROR_MORE_DATA, ERROR_BROKEN_PIPE, ERROR_NO_DATA},
+    Foundation::{
+        CloseHandle, HANDLE, ERROR_PIPE_BUSY, ERROR_MORE_DATA, ERROR_BROKEN_PIPE, ERROR_NO_DATA,
+        ERROR_FILE_NOT_FOUND, ERROR_OPERATION_ABORTED,
+    },
     Storage::FileSystem::{
         CreateFileW, ReadFile, WriteFile, FILE_GENERIC_READ, FILE_GENERIC_WRITE,
         FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING, FILE_FLAGS_AND_ATTRIBUTES,
     },
     System::Pipes::{WaitNamedPipeW, SetNamedPipeHandleState, PIPE_READMODE_MESSAGE},
+    System::IO::CancelIoEx,
 };
 use crate::ipc::response::IpcResponse;
-use crate::error;
+#[cfg(target_os = "windows")]
+use crate::ipc::response::codes;
+#[cfg(target_os = "windows")]
+use crate::{error, warn};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IpcRequest {
     pub ns: String,
     pub cmd: String,
     pub args: Option<Value>,
+    /// Addon issuing the request, if any. Only checked against the addon the
+    /// token was issued to; the token is what identifies an addon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addon_id: Option<String>,
+    /// Session token from `ipc_token`, required for the privileged namespaces.
+    /// Filled in by `send_ipc_request` when left empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
 }
 
+#[cfg(target_os = "windows")]
 const PIPE_NAME: &str = r"\\.\pipe\veil";
+#[cfg(target_os = "windows")]
 const READ_CHUNK: usize = 64 * 1024;
+/// Pause before the one retry after a transient connect failure.
+#[cfg(target_os = "windows")]
+const CONNECT_RETRY_DELAY: Duration = Duration::from_millis(100);
+#[cfg(target_os = "windows")]
+const CANCEL_REPEAT: Duration = Duration::from_millis(50);
+/// Extra time for `assets.verify`, which reads every asset and may rewrite
+/// manifests when `fix` is set.
+#[cfg(target_os = "windows")]
+const ASSET_VERIFY_ALLOWANCE: Duration = Duration::from_secs(60);
 
+#[cfg(target_os = "windows")]
 fn to_wide(s: &str) -> Vec<u16> {
     s.encode_utf16().chain(Some(0)).collect()
 }
 
+#[cfg(target_os = "windows")]
 fn is_win32_error(err: &windows::core::Error, win32_code: u32) -> bool {
     err.code() == windows::core::HRESULT::from_win32(win32_code)
 }
 
+/// How a connect or exchange attempt failed.
+#[cfg(target_os = "windows")]
+enum PipeError {
+    TimedOut,
+    /// The pipe is missing or went away mid-connect, e.g. between server
+    /// instances. Worth one more try.
+    Transient(String),
+    Failed(String),
+}
+
+/// Send one request over the pipe. The session token is loaded from disk
+/// when the request doesn't carry one, so callers never handle it.
+///
+/// Connecting and waiting for the reply share one budget (see
+/// `request_timeout`). Running out of it yields a `TIMEOUT` error response
+/// rather than blocking the caller. A transient connect failure is retried
+/// once.
+#[cfg(target_os = "windows")]
 pub fn send_ipc_request(request: IpcRequest) -> Result<IpcResponse, String> {
-    unsafe {
-        // --- Connect to pipe ---
-        let handle: HANDLE = loop {
-            let result = CreateFileW(
-                PCWSTR(to_wide(PIPE_NAME).as_ptr()),
-                FILE_GENERIC_READ.0 | FILE_GENERIC_WRITE.0,
-                FILE_SHARE_READ | FILE_SHARE_WRITE,
-                None,
-                OPEN_EXISTING,
-                FILE_FLAGS_AND_ATTRIBUTES(0),
-                None,
-            );
+    let timeout = request_timeout(&request);
+    send_request_to(PIPE_NAME, request, timeout)
+}
 
-            match result {
-                Ok(h) => break h,
-                Err(err) => {
-                    let code = err.code().0 as u32;
-                    if code == ERROR_PIPE_BUSY.0 {
-                        let _ = WaitNamedPipeW(PCWSTR(to_wide(PIPE_NAME).as_ptr()), 2000);
-                        continue;
-                    }
-                    return Err(format!("IPC connect failed: {:?}", err));
-                }
+/// `ipc_timeout_ms`, plus the time the backend may legitimately spend on
+/// the command before it replies: stopping addons waits out their grace
+/// period, `assets.install_url` downloads an archive.
+#[cfg(target_os = "windows")]
+fn request_timeout(request: &IpcRequest) -> Duration {
+    let allowance = match (request.ns.as_str(), request.cmd.as_str()) {
+        ("addon", "stop" | "stop_all") => Duration::from_millis(crate::config::client_addon_stop_grace_ms()),
+        ("assets", "install_url") => crate::ipc::assets::url_install::DOWNLOAD_TIMEOUT,
+        ("assets", "verify") => ASSET_VERIFY_ALLOWANCE,
+        _ => Duration::ZERO,
+    };
+    Duration::from_millis(crate::config::client_ipc_timeout_ms()) + allowance
+}
+
+#[cfg(target_os = "windows")]
+fn send_request_to(pipe: &str, mut request: IpcRequest, timeout: Duration) -> Result<IpcResponse, String> {
+    if request.token.is_none() {
+        request.token = crate::ipc::token::load();
+    }
+    let payload = to_vec(&request).map_err(|e| format!("IPC serialize failed: {e}"))?;
+    let deadline = Instant::now() + timeout;
+
+    let mut retried = false;
+    let handle = loop {
+        match unsafe { connect(pipe, deadline) } {
+            Ok(h) => break h,
+            Err(PipeError::Transient(_)) if !retried => {
+                retried = true;
+                thread::sleep(CONNECT_RETRY_DELAY.min(deadline.saturating_duration_since(Instant::now())));
             }
-        };
+            Err(PipeError::TimedOut) => return Ok(timed_out(&request, timeout)),
+            Err(PipeError::Transient(msg)) | Err(PipeError::Failed(msg)) => {
+                return Err(format!("IPC connect failed: {msg}"));
+            }
+        }
+    };
 
-        // --- Send request ---
+    let result = unsafe { exchange(handle, &payload, deadline) };
+    unsafe {
+        let _ = CloseHandle(handle);
+    }
+    let response = match result {
+        Ok(bytes) => bytes,
+        Err(PipeError::TimedOut) => return Ok(timed_out(&request, timeout)),
+        Err(PipeError::Transient(msg)) | Err(PipeError::Failed(msg)) => return Err(msg),
+    };
 
-        // Switch the client handle to message-read mode so ReadFile
-        // returns ERROR_MORE_DATA when a message exceeds the read
-        // buffer, instead of silently truncating.
-        {
-            let mut mode = PIPE_READMODE_MESSAGE;
-            let _ = SetNamedPipeHandleState(handle, Some(&mut mode), None, None);
+    match from_slice::<IpcResponse>(&response) {
+        Ok(resp) => Ok(resp),
+        Err(e) => {
+            error!("[IPC] decode failed ({} bytes): {e}", response.len());
+            Err(format!("[IPC] decode failed: {e}"))
         }
+    }
+}
 
-        let payload = match to_vec(&request) {
-            Ok(p) => p,
-            Err(e) => {
-                let _ = CloseHandle(handle);
-                return Err(format!("IPC serialize failed: {e}"));
-            }
+#[cfg(target_os = "windows")]
+fn timed_out(request: &IpcRequest, timeout: Duration) -> IpcResponse {
+    warn!("[IPC] {}.{} timed out after {}ms", request.ns, request.cmd, timeout.as_millis());
+    IpcResponse::err_code(
+        codes::TIMEOUT,
+        format!("No response from the backend within {}ms", timeout.as_millis()),
+    )
+}
+
+/// Open `pipe`, waiting for a free instance until `deadline`.
+#[cfg(target_os = "windows")]
+unsafe fn connect(pipe: &str, deadline: Instant) -> Result<HANDLE, PipeError> {
+    let name = to_wide(pipe);
+    loop {
+        let result = CreateFileW(
+            PCWSTR(name.as_ptr()),
+            FILE_GENERIC_READ.0 | FILE_GENERIC_WRITE.0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            None,
+        );
+        let err = match result {
+            Ok(h) => return Ok(h),
+            Err(err) => err,
         };
+        if is_win32_error(&err, ERROR_PIPE_BUSY.0) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(PipeError::TimedOut);
+            }
+            let _ = WaitNamedPipeW(PCWSTR(name.as_ptr()), remaining.as_millis().max(1) as u32);
+            continue;
+        }
+        if is_win32_error(&err, ERROR_FILE_NOT_FOUND.0) {
+            return Err(PipeError::Transient(format!("{:?}", err)));
+        }
+        return Err(PipeError::Failed(format!("{:?}", err)));
+    }
+}
 
-        let mut written = 0u32;
-        if WriteFile(handle, Some(&payload), Some(&mut written), None).is_err() {
-            let _ = CloseHandle(handle);
-            return Err("IPC write failed".into());
+/// Cancels whatever pipe I/O is blocked on `handle` once `deadline` passes,
+/// until the returned sender is dropped. Cancelling repeats so a read that
+/// starts just after the first cancel is caught too. The thread reports
+/// whether it cancelled anything.
+#[cfg(target_os = "windows")]
+fn watchdog(handle: HANDLE, deadline: Instant) -> (mpsc::Sender<()>, thread::JoinHandle<bool>) {
+    let (tx, rx) = mpsc::channel::<()>();
+    let raw = handle.0 as usize; // pointer → integer (Send)
+    let thread = thread::spawn(move || {
+        let mut wait = deadline.saturating_duration_since(Instant::now());
+        let mut cancelled = false;
+        while matches!(rx.recv_timeout(wait), Err(RecvTimeoutError::Timeout)) {
+            unsafe {
+                let _ = CancelIoEx(HANDLE(raw as *mut _), None);
+            }
+            cancelled = true;
+            wait = CANCEL_REPEAT;
         }
+        cancelled
+    });
+    (tx, thread)
+}
 
-        // --- Read response (multi-chunk loop for messages > READ_CHUNK) ---
-        let mut response = Vec::<u8>::new();
-        loop {
-            let mut chunk = vec![0u8; READ_CHUNK];
-            let mut read = 0u32;
+/// Write `payload` and read the whole reply, giving up at `deadline`.
+#[cfg(target_os = "windows")]
+unsafe fn exchange(handle: HANDLE, payload: &[u8], deadline: Instant) -> Result<Vec<u8>, PipeError> {
+    // Switch the client handle to message-read mode so ReadFile
+    // returns ERROR_MORE_DATA when a message exceeds the read
+    // buffer, instead of silently truncating.
+    {
+        let mut mode = PIPE_READMODE_MESSAGE;
+        let _ = SetNamedPipeHandleState(handle, Some(&mut mode), None, None);
+    }
 
-            match ReadFile(handle, Some(&mut chunk), Some(&mut read), None) {
-                Ok(_) => {
-                    if read == 0 {
-                        break;
-                    }
+    let (done, guard) = watchdog(handle, deadline);
+    let result = read_write(handle, payload, deadline);
+    drop(done);
+    let cancelled = guard.join().unwrap_or(false);
+
+    // A reply that finished just as the watchdog fired still counts.
+    match result {
+        Err(_) if cancelled => Err(PipeError::TimedOut),
+        other => other,
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn read_write(handle: HANDLE, payload: &[u8], deadline: Instant) -> Result<Vec<u8>, PipeError> {
+    let mut written = 0u32;
+    if WriteFile(handle, Some(payload), Some(&mut written), None).is_err() {
+        return Err(PipeError::Failed("IPC write failed".into()));
+    }
+
+    // --- Read response (multi-chunk loop for messages > READ_CHUNK) ---
+    let mut response = Vec::<u8>::new();
+    loop {
+        if Instant::now() >= deadline {
+            return Err(PipeError::TimedOut);
+        }
+        let mut chunk = vec![0u8; READ_CHUNK];
+        let mut read = 0u32;
+
+        match ReadFile(handle, Some(&mut chunk), Some(&mut read), None) {
+            Ok(_) => {
+                if read == 0 {
+                    break;
+                }
+                response.extend_from_slice(&chunk[..read as usize]);
+                // In byte-mode reads a successful ReadFile means we got
+                // all available data for now.  For message-mode pipes the
+                // OS would signal ERROR_MORE_DATA if more is pending.
+                break;
+            }
+            Err(e) => {
+                if read > 0 {
                     response.extend_from_slice(&chunk[..read as usize]);
-                    // In byte-mode reads a successful ReadFile means we got
-                    // all available data for now.  For message-mode pipes the
-                    // OS would signal ERROR_MORE_DATA if more is pending.
+                }
+
+                if is_win32_error(&e, ERROR_MORE_DATA.0) {
+                    // More data available — keep reading
+                    continue;
+                }
+
+                // Broken pipe / no data after accumulating bytes means
+                // the server closed its end — treat what we have as complete.
+                if is_win32_error(&e, ERROR_BROKEN_PIPE.0)
+                    || is_win32_error(&e, ERROR_NO_DATA.0)
+                {
                     break;
                 }
-                Err(e) => {
-                    if read > 0 {
-                        response.extend_from_slice(&chunk[..read as usize]);
-                    }
-
-                    if is_win32_error(&e, ERROR_MORE_DATA.0) {
-                        // More data available — keep reading
-                        continue;
-                    }
-
-                    // Broken pipe / no data after accumulating bytes means
-                    // the server closed its end — treat what we have as complete.
-                    if is_win32_error(&e, ERROR_BROKEN_PIPE.0)
-                        || is_win32_error(&e, ERROR_NO_DATA.0)
-                    {
-                        break;
-                    }
-
-                    let _ = CloseHandle(handle);
-                    error!("[IPC] [Response] read failed: {:?}", e);
-                    return Err("[IPC] [Response] read failed".into());
+
+                if is_win32_error(&e, ERROR_OPERATION_ABORTED.0) {
+                    return Err(PipeError::TimedOut);
                 }
+
+                error!("[IPC] [Response] read failed: {:?}", e);
+                return Err(PipeError::Failed("[IPC] [Response] read failed".into()));
             }
         }
+    }
+    Ok(response)
+}
 
-        let _ = CloseHandle(handle);
+/// A framed stream connection (see `sysdata.subscribe`), read one frame at
+/// a time. Dropping it closes the pipe, which ends the stream on the server.
+#[cfg(target_os = "windows")]
+pub struct IpcStream {
+    handle: HANDLE,
+}
 
-        match from_slice::<IpcResponse>(&response) {
-            Ok(resp) => Ok(resp),
-            Err(e) => {
-                error!("[IPC] decode failed ({} bytes): {e}", response.len());
-                Err(format!("[IPC] decode failed: {e}"))
+#[cfg(target_os = "windows")]
+impl IpcStream {
+    /// Connect and send `request`. Connecting is bounded by `ipc_timeout_ms`;
+    /// the server's reply arrives as the first frame.
+    pub fn open(mut request: IpcRequest) -> Result<Self, String> {
+        if request.token.is_none() {
+            request.token = crate::ipc::token::load();
+        }
+        let payload = to_vec(&request).map_err(|e| format!("IPC serialize failed: {e}"))?;
+        let deadline = Instant::now() + Duration::from_millis(crate::config::client_ipc_timeout_ms());
+        let handle = match unsafe { connect(PIPE_NAME, deadline) } {
+            Ok(h) => h,
+            Err(PipeError::TimedOut) => return Err("Timed out connecting to the backend".into()),
+            Err(PipeError::Transient(msg)) | Err(PipeError::Failed(msg)) => {
+                return Err(format!("IPC connect failed: {msg}"));
             }
+        };
+        let stream = Self { handle };
+        let mut written = 0u32;
+        unsafe { WriteFile(handle, Some(&payload), Some(&mut written), None) }
+            .map_err(|e| format!("IPC write failed: {e}"))?;
+        Ok(stream)
+    }
+
+    /// Block until the next frame arrives. Fails once the server closes the
+    /// stream or the read is cancelled through `raw_handle`.
+    pub fn next_frame(&mut self) -> Result<IpcResponse, String> {
+        let mut len = [0u8; 4];
+        self.read_exact(&mut len)?;
+        let mut body = vec![0u8; u32::from_le_bytes(len) as usize];
+        self.read_exact(&mut body)?;
+        from_slice::<IpcResponse>(&body).map_err(|e| format!("[IPC] decode failed: {e}"))
+    }
+
+    /// The pipe handle as an integer, so another thread can `CancelIoEx` a
+    /// blocked `next_frame`.
+    pub fn raw_handle(&self) -> usize {
+        self.handle.0 as usize
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), String> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let mut read = 0u32;
+            let result = unsafe { ReadFile(self.handle, Some(&mut buf[filled..]), Some(&mut read), None) };
+            match result {
+                Ok(()) if read == 0 => return Err("The backend closed the stream".into()),
+                Ok(()) => filled += read as usize,
+                Err(e) if is_win32_error(&e, ERROR_BROKEN_PIPE.0) || is_win32_error(&e, ERROR_NO_DATA.0) => {
+                    return Err("The backend closed the stream".into());
+                }
+                Err(e) if is_win32_error(&e, ERROR_OPERATION_ABORTED.0) => return Err("Read cancelled".into()),
+                Err(e) => return Err(format!("IPC read failed: {e}")),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for IpcStream {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseHandle(self.handle);
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn send_ipc_request(_request: IpcRequest) -> Result<IpcResponse, String> {
+    Err("Named-pipe IPC is only supported on Windows".into())
+}
+
+#[cfg(all(test, target_os = "windows"))]
+mod tests {
+    use super::*;
+    use windows::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows::Win32::Storage::FileSystem::FlushFileBuffers;
+    use windows::Win32::System::Pipes::{
+        ConnectNamedPipe, CreateNamedPipeW, PIPE_TYPE_MESSAGE, PIPE_WAIT,
+    };
+
+    const PIPE_ACCESS_DUPLEX: u32 = 0x00000003;
+
+    /// A server that accepts one connection, reads the request and then sits
+    /// on it for `stall` without replying.
+    fn slow_server(name: &str, stall: Duration) -> thread::JoinHandle<()> {
+        slow_server_replying(name, stall, None)
+    }
+
+    /// Like `slow_server`, but writes `reply` once `stall` has passed.
+    fn slow_server_replying(name: &str, stall: Duration, reply: Option<Vec<u8>>) -> thread::JoinHandle<()> {
+        let wide = to_wide(name);
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let server = thread::spawn(move || unsafe {
+            let pipe = CreateNamedPipeW(
+                PCWSTR(wide.as_ptr()),
+                FILE_FLAGS_AND_ATTRIBUTES(PIPE_ACCESS_DUPLEX),
+                PIPE_TYPE_MESSAGE | PIPE_READMODE_MESSAGE | PIPE_WAIT,
+                1,
+                4096,
+                4096,
+                0,
+                None,
+            );
+            assert!(pipe != INVALID_HANDLE_VALUE, "failed to create test pipe");
+            ready_tx.send(()).unwrap();
+            let _ = ConnectNamedPipe(pipe, None);
+            let mut buf = [0u8; 4096];
+            let mut read = 0u32;
+            let _ = ReadFile(pipe, Some(&mut buf), Some(&mut read), None);
+            thread::sleep(stall);
+            if let Some(reply) = reply {
+                let mut written = 0u32;
+                let _ = WriteFile(pipe, Some(&reply), Some(&mut written), None);
+                let _ = FlushFileBuffers(pipe);
+            }
+            let _ = CloseHandle(pipe);
+        });
+        ready_rx.recv().unwrap();
+        server
+    }
+
+    #[test]
+    fn slow_server_times_out_instead_of_blocking() {
+        let name = format!(r"\\.\pipe\veil-test-slow-{}", std::process::id());
+        let server = slow_server(&name, Duration::from_secs(3));
+        let request = IpcRequest {
+            ns: "sysdata".into(),
+            cmd: "cpu".into(),
+            args: None,
+            addon_id: None,
+            token: Some("unused".into()),
+        };
+
+        let started = Instant::now();
+        let resp = send_request_to(&name, request, Duration::from_millis(200)).unwrap();
+        let elapsed = started.elapsed();
+
+        assert!(!resp.ok);
+        assert_eq!(resp.error_code.as_deref(), Some(codes::TIMEOUT));
+        assert!(elapsed < Duration::from_secs(2), "took {:?}", elapsed);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn addon_stop_waits_out_the_grace_period() {
+        let stop = |cmd: &str| IpcRequest {
+            ns: "addon".into(),
+            cmd: cmd.into(),
+            args: Some(serde_json::json!({ "addon_name": "slow" })),
+            addon_id: None,
+            token: Some("unused".into()),
+        };
+        let base = Duration::from_millis(crate::config::client_ipc_timeout_ms());
+        let grace = Duration::from_millis(crate::config::client_addon_stop_grace_ms());
+        assert_eq!(request_timeout(&stop("stop")), base + grace);
+        assert_eq!(request_timeout(&stop("stop_all")), base + grace);
+        assert_eq!(request_timeout(&stop("status")), base);
+
+        // An addon that uses its whole grace period: the backend replies
+        // after `grace`, which must not be reported as a timeout.
+        let grace = Duration::from_millis(600);
+        let name = format!(r"\\.\pipe\veil-test-stop-{}", std::process::id());
+        let reply = serde_json::to_vec(&IpcResponse::ok(serde_json::json!({ "status": "stopped" }))).unwrap();
+        let server = slow_server_replying(&name, grace, Some(reply));
+
+        let resp = send_request_to(&name, stop("stop"), Duration::from_millis(200) + grace).unwrap();
+
+        assert!(resp.ok, "{:?}", resp.error);
+        server.join().unwrap();
+    }
+}
@@ -1,11 +1,60 @@
 use serde::{Serialize, Deserialize};
 use serde_json::Value;
+use std::fmt;
+
+/// Stable identifiers for `IpcResponse::error_code`. Clients match on these;
+/// the `error` text next to them is for people and may change.
+pub mod codes {
+    pub const BAD_ARGS: &str = "BAD_ARGS";
+    pub const UNKNOWN_NAMESPACE: &str = "UNKNOWN_NAMESPACE";
+    pub const UNKNOWN_COMMAND: &str = "UNKNOWN_COMMAND";
+    pub const PERMISSION_DENIED: &str = "PERMISSION_DENIED";
+    pub const UNAUTHORIZED: &str = "UNAUTHORIZED";
+    pub const BATCH_TOO_LARGE: &str = "BATCH_TOO_LARGE";
+    pub const ADDON_NOT_FOUND: &str = "ADDON_NOT_FOUND";
+    pub const ADDON_INVALID: &str = "ADDON_INVALID";
+    pub const EXECUTABLE_NOT_FOUND: &str = "EXECUTABLE_NOT_FOUND";
+    pub const START_FAILED: &str = "START_FAILED";
+    pub const INCOMPATIBLE_VERSION: &str = "INCOMPATIBLE_VERSION";
+    pub const NOT_RUNNING: &str = "NOT_RUNNING";
+    pub const NOT_FOUND: &str = "NOT_FOUND";
+    pub const TIMEOUT: &str = "TIMEOUT";
+    pub const TOO_LARGE: &str = "TOO_LARGE";
+}
+
+/// An error with an optional machine-readable code. Plain `String` errors from
+/// the namespace handlers convert into it without a code.
+#[derive(Debug)]
+pub struct IpcError {
+    pub code: Option<&'static str>,
+    pub message: String,
+}
+
+impl IpcError {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self { code: Some(code), message: message.into() }
+    }
+}
+
+impl From<String> for IpcError {
+    fn from(message: String) -> Self {
+        Self { code: None, message }
+    }
+}
+
+impl fmt::Display for IpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IpcResponse {
     pub ok: bool,
     pub data: Option<Value>,
     pub error: Option<String>,
+    #[serde(default)]
+    pub error_code: Option<String>,
 }
 
 impl IpcResponse {
@@ -14,6 +63,7 @@ impl IpcResponse {
             ok: true,
             data: Some(data),
             error: None,
+            error_code: None,
         }
     }
 
@@ -23,6 +73,23 @@ impl IpcResponse {
             ok: false,
             data: None,
             error: Some(msg_str),
+            error_code: None,
+        }
+    }
+
+    pub fn err_code(code: &'static str, msg: impl Into<String>) -> Self {
+        Self {
+            error_code: Some(code.to_string()),
+            ..Self::err(msg)
         }
     }
-}
\ No newline at end of file
+}
+
+impl From<IpcError> for IpcResponse {
+    fn from(e: IpcError) -> Self {
+        Self {
+            error_code: e.code.map(str::to_string),
+            ..Self::err(e.message)
+        }
+    }
+}
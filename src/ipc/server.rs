@@ -1,26 +1,45 @@
-use std::{thread, time::Duration};
+#[cfg(target_os = "windows")]
+use std::{thread, time::{Duration, Instant}};
+#[cfg(target_os = "windows")]
 use serde_json::{from_slice, to_vec};
+#[cfg(target_os = "windows")]
 use windows::core::PCWSTR;
+#[cfg(target_os = "windows")]
 use windows::Win32::{
     Foundation::{HANDLE, INVALID_HANDLE_VALUE, CloseHandle, GetLastError, ERROR_PIPE_CONNECTED},
     System::Pipes::*,
     Storage::FileSystem::{FlushFileBuffers, ReadFile, WriteFile, FILE_FLAGS_AND_ATTRIBUTES},
 };
 
+#[cfg(target_os = "windows")]
 use crate::{
     ipc::{
         request::IpcRequest,
-        response::IpcResponse,
-        dispatch::dispatch,
+        response::{codes, IpcError, IpcResponse},
+        dispatch::dispatch_as,
+        addon::capabilities::check_caller_permission,
+        addon::shutdown::{shutdown_requested, ShutdownListener},
+        token::{self, Caller},
+        subscription::{Subscription, MAX_SUBSCRIPTIONS_PER_CONNECTION},
     },
 };
-use crate::{info, warn, error};
+use crate::warn;
+#[cfg(target_os = "windows")]
+use crate::{info, error};
 
 const PIPE_NAME: &str = r"\\.\pipe\veil";
+#[cfg(target_os = "windows")]
 const PIPE_ACCESS_DUPLEX: u32 = 0x00000003;
 
+#[cfg(target_os = "windows")]
 const BUFFER_SIZE: u32 = 1024 * 1024;
 
+/// Longest a stream connection sleeps before checking for new requests or a
+/// disconnected client.
+#[cfg(target_os = "windows")]
+const STREAM_IDLE_TICK: Duration = Duration::from_millis(50);
+
+#[cfg(target_os = "windows")]
 fn to_wide(s: &str) -> Vec<u16> {
     s.encode_utf16().chain(Some(0)).collect()
 }
@@ -32,8 +51,10 @@ fn to_wide(s: &str) -> Vec<u16> {
 /// connections, eliminating the "pipe busy" race that occurs with a single
 /// loop (the gap between `ConnectNamedPipe` returning and the next
 /// `CreateNamedPipeW` call).
+#[cfg(target_os = "windows")]
 const LISTENER_POOL_SIZE: usize = 4;
 
+#[cfg(target_os = "windows")]
 pub fn start_ipc_server() {
     info!("Starting IPC server on pipe '{}' ({} listeners)",
           PIPE_NAME, LISTENER_POOL_SIZE);
@@ -48,6 +69,12 @@ pub fn start_ipc_server() {
     ipc_accept_loop();
 }
 
+#[cfg(not(target_os = "windows"))]
+pub fn start_ipc_server() {
+    warn!("Named-pipe IPC ('{}') is only supported on Windows; use the HTTP bridge", PIPE_NAME);
+}
+
+#[cfg(target_os = "windows")]
 fn ipc_accept_loop() {
     let pipe_name_wide = to_wide(PIPE_NAME);
 
@@ -94,6 +121,7 @@ fn ipc_accept_loop() {
     }
 }
 
+#[cfg(target_os = "windows")]
 unsafe fn handle_client(pipe: HANDLE) {
     let mut buffer_vec = vec![0u8; BUFFER_SIZE as usize];
     let mut read = 0u32;
@@ -112,17 +140,210 @@ unsafe fn handle_client(pipe: HANDLE) {
         }
     };
 
-    let response = match dispatch(&req.ns, &req.cmd, req.args) {
+    let caller = match permit(&req) {
+        Ok(caller) => caller,
+        Err(e) => {
+            send(pipe, IpcResponse::from(e));
+            return;
+        }
+    };
+
+    if req.ns == "sysdata" && req.cmd == "subscribe" {
+        stream_subscriptions(pipe, req, caller);
+        return;
+    }
+
+    let result = dispatch_as(&req.ns, &req.cmd, req.args, &caller);
+    let response = match result {
         Ok(value) => IpcResponse::ok(value),
         Err(err) => {
             warn!("IPC dispatch error: {}", err);
-            IpcResponse::err(err)
+            IpcResponse::from(err)
         }
     };
 
     send(pipe, response);
 }
 
+/// Authenticate `req` and check it against its caller's capabilities.
+#[cfg(target_os = "windows")]
+fn permit(req: &IpcRequest) -> Result<Caller, IpcError> {
+    let caller = token::authorize(&req.ns, req.args.as_ref(), req.token.as_deref(), req.addon_id.as_deref())?;
+    if crate::config::enforce_addon_permissions() {
+        if let Err(e) = check_caller_permission(&caller, &req.ns, &req.cmd, req.args.as_ref()) {
+            warn!("IPC permission denied: {}", e);
+            return Err(IpcError::new(codes::PERMISSION_DENIED, e));
+        }
+    }
+    Ok(caller)
+}
+
+/// Serve `sysdata.subscribe` on this connection until the client leaves.
+///
+/// Once a connection subscribes it switches to framed mode: every message the
+/// server writes, the acknowledgement included, is a 4-byte little-endian
+/// length followed by that many bytes of `IpcResponse` JSON. The client may
+/// keep sending plain JSON requests on the same pipe (`sysdata.subscribe`,
+/// `sysdata.unsubscribe { subscription_id }`); the stream ends when the
+/// client disconnects or its last subscription is removed.
+///
+/// When the first request comes from an addon, the connection is also how the
+/// addon is asked to exit: `addon.stop` pushes one `{ "event": "shutdown" }`
+/// frame before its grace period starts.
+#[cfg(target_os = "windows")]
+unsafe fn stream_subscriptions(pipe: HANDLE, first: IpcRequest, caller: Caller) {
+    let mut subs: Vec<Subscription> = Vec::new();
+    let mut next_id = 1u32;
+    let mut buffer = vec![0u8; BUFFER_SIZE as usize];
+    let listener = caller.addon_id().map(ShutdownListener::register);
+    let mut shutdown_sent = false;
+
+    // The first request was already permitted by `handle_client`.
+    let mut pending = Some((first, Some(caller)));
+    loop {
+        if let Some((req, caller)) = pending.take() {
+            let reply = stream_request(req, caller, &mut subs, &mut next_id);
+            if !send_frame(pipe, reply) {
+                break;
+            }
+            if subs.is_empty() {
+                break;
+            }
+        }
+
+        let now = Instant::now();
+        let mut alive = true;
+        for sub in subs.iter_mut() {
+            if let Some(payload) = sub.poll(now) {
+                if !send_frame(pipe, IpcResponse::ok(payload)) {
+                    alive = false;
+                    break;
+                }
+            }
+        }
+        if !alive {
+            break;
+        }
+
+        if let Some(listener) = listener.as_ref() {
+            if !shutdown_sent && shutdown_requested(listener.addon_id()) {
+                shutdown_sent = true;
+                info!("Sending shutdown to addon '{}'", listener.addon_id());
+                if !send_frame(pipe, IpcResponse::ok(serde_json::json!({ "event": "shutdown" }))) {
+                    break;
+                }
+            }
+        }
+
+        // PeekNamedPipe doubles as the disconnect check: it fails once the
+        // client end is closed, and never blocks the pushes above.
+        let mut available = 0u32;
+        if PeekNamedPipe(pipe, None, 0, None, Some(&mut available), None).is_err() {
+            break;
+        }
+        if available > 0 {
+            let mut read = 0u32;
+            if ReadFile(pipe, Some(&mut buffer), Some(&mut read), None).is_err() {
+                break;
+            }
+            match from_slice::<IpcRequest>(&buffer[..read as usize]) {
+                Ok(req) => pending = Some((req, None)),
+                Err(e) => {
+                    if !send_frame(pipe, IpcResponse::err(format!("invalid request: {e}"))) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+
+        let now = Instant::now();
+        let wait = subs
+            .iter()
+            .map(|s| s.until_due(now))
+            .min()
+            .unwrap_or(STREAM_IDLE_TICK)
+            .min(STREAM_IDLE_TICK);
+        thread::sleep(wait);
+    }
+
+    if !subs.is_empty() {
+        info!("IPC stream closed; dropped {} subscription(s)", subs.len());
+    }
+}
+
+/// Handle a request received on a stream connection. `caller` is set when
+/// the request has already been permitted.
+#[cfg(target_os = "windows")]
+fn stream_request(
+    req: IpcRequest,
+    caller: Option<Caller>,
+    subs: &mut Vec<Subscription>,
+    next_id: &mut u32,
+) -> IpcResponse {
+    let caller = match caller.map_or_else(|| permit(&req), Ok) {
+        Ok(caller) => caller,
+        Err(e) => return IpcResponse::from(e),
+    };
+    match (req.ns.as_str(), req.cmd.as_str()) {
+        ("sysdata", "subscribe") => {
+            if subs.len() >= MAX_SUBSCRIPTIONS_PER_CONNECTION {
+                return IpcResponse::err(format!(
+                    "At most {} subscriptions per connection",
+                    MAX_SUBSCRIPTIONS_PER_CONNECTION
+                ));
+            }
+            match Subscription::from_args(*next_id, req.args.as_ref()) {
+                Ok(sub) => {
+                    *next_id += 1;
+                    let ack = sub.describe();
+                    subs.push(sub);
+                    IpcResponse::ok(ack)
+                }
+                Err(e) => IpcResponse::err_code(codes::BAD_ARGS, e),
+            }
+        }
+        ("sysdata", "unsubscribe") => {
+            let id = req
+                .args
+                .as_ref()
+                .and_then(|a| a.get("subscription_id"))
+                .and_then(|v| v.as_u64());
+            match id.and_then(|id| subs.iter().position(|s| s.id as u64 == id)) {
+                Some(idx) => {
+                    let sub = subs.remove(idx);
+                    IpcResponse::ok(serde_json::json!({ "subscription_id": sub.id, "removed": true }))
+                }
+                None => IpcResponse::err_code(codes::BAD_ARGS, "Missing or unknown 'subscription_id' in args"),
+            }
+        }
+        // Anything else is answered once, inside the stream's framing.
+        (ns, cmd) => match dispatch_as(ns, cmd, req.args, &caller) {
+            Ok(value) => IpcResponse::ok(value),
+            Err(err) => IpcResponse::from(err),
+        },
+    }
+}
+
+/// Write one length-prefixed frame. Returns `false` once the client is gone.
+#[cfg(target_os = "windows")]
+unsafe fn send_frame(pipe: HANDLE, resp: IpcResponse) -> bool {
+    let body = match to_vec(&resp) {
+        Ok(b) => b,
+        Err(e) => {
+            error!("Failed to serialize IPC frame: {e}");
+            return true;
+        }
+    };
+    let mut frame = Vec::with_capacity(4 + body.len());
+    frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&body);
+
+    let mut written = 0u32;
+    WriteFile(pipe, Some(&frame), Some(&mut written), None).is_ok()
+}
+
+#[cfg(target_os = "windows")]
 unsafe fn send(pipe: HANDLE, resp: IpcResponse) {
     let bytes = match to_vec(&resp) {
         Ok(b) if !b.is_empty() => b,
@@ -0,0 +1,70 @@
+// ~/veil/veil-backend/src/ipc/smoothing.rs
+//
+// Optional exponential smoothing for jumpy metrics. Each path listed under
+// `smoothing` in config.yaml gets a `<name>_smoothed` sibling next to the raw
+// value; nothing is added when the map is empty (the default).
+
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+use crate::config;
+use crate::ipc::registry::RegistryEntry;
+
+/// Appended to a field's name for its smoothed sibling.
+pub const SMOOTHED_SUFFIX: &str = "_smoothed";
+
+/// Last smoothed value per metric path.
+static STATE: OnceLock<Mutex<HashMap<String, f64>>> = OnceLock::new();
+
+fn state() -> &'static Mutex<HashMap<String, f64>> {
+    STATE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Add `*_smoothed` fields to freshly collected entries, in place.
+/// Paths are `<category>.<field>[.<field>...]`, e.g. `cpu.usage_percent`.
+pub fn apply(entries: &mut [RegistryEntry]) {
+    let settings = config::smoothing();
+    if settings.is_empty() {
+        return;
+    }
+
+    let mut last = state().lock().unwrap();
+    for (path, factor) in &settings {
+        let Some((category, field_path)) = path.split_once('.') else { continue };
+        let Some(entry) = entries.iter_mut().find(|e| e.category == category) else { continue };
+
+        let alpha = factor.clamp(0.01, 1.0);
+        if let Some(smoothed) = smooth_field(&mut entry.metadata, field_path, alpha, last.get(path).copied()) {
+            last.insert(path.clone(), smoothed);
+        }
+    }
+
+    // Forget metrics that were removed from the config so re-adding starts fresh.
+    last.retain(|path, _| settings.contains_key(path));
+}
+
+/// Blend the number at `field_path` (dot-separated, below `metadata`) into
+/// `prev` with weight `alpha` and store it as the field's smoothed sibling.
+/// Returns the smoothed value, or `None` when there is no such number.
+pub(crate) fn smooth_field(metadata: &mut Value, field_path: &str, alpha: f64, prev: Option<f64>) -> Option<f64> {
+    let (parent_path, leaf) = match field_path.rsplit_once('.') {
+        Some((parent, leaf)) => (Some(parent), leaf),
+        None => (None, field_path),
+    };
+    let parent = match parent_path {
+        Some(p) => metadata.pointer_mut(&format!("/{}", p.replace('.', "/"))),
+        None => Some(metadata),
+    };
+    let Some(Value::Object(parent)) = parent else { return None };
+    let raw = parent.get(leaf).and_then(|v| v.as_f64())?;
+
+    let smoothed = match prev {
+        Some(prev) => alpha * raw + (1.0 - alpha) * prev,
+        None => raw,
+    };
+    parent.insert(format!("{}{}", leaf, SMOOTHED_SUFFIX), serde_json::json!(smoothed));
+    Some(smoothed)
+}
@@ -0,0 +1,210 @@
+// ~/veil/veil-backend/src/ipc/snapshot.rs
+//
+// On-disk copy of the registry for readers in other processes, such as the
+// config UI's search palette. It is written as `registry.json`, or gzipped to
+// `registry.json.gz` when `compress_snapshot` is set. Each write goes to a temp
+// file in the same directory and is renamed over the old one, so readers see
+// either the previous snapshot or the new one.
+
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde_json::Value;
+
+use crate::config::compress_snapshot;
+use crate::ipc::registry::{global_registry, registry_to_output_json, Registry};
+use crate::paths::{registry_json_gz_path, registry_json_path};
+use crate::{error, warn};
+
+const INTERVAL: Duration = Duration::from_millis(500);
+
+/// Spawn the thread that keeps the snapshot current. Unchanged registries are
+/// not rewritten.
+pub fn start_snapshot_writer() {
+    let spawned = std::thread::Builder::new()
+        .name("veil-registry-snapshot".into())
+        .spawn(|| {
+            let mut last: Option<(bool, Registry)> = None;
+            loop {
+                std::thread::sleep(INTERVAL);
+                let compressed = compress_snapshot();
+                let reg = global_registry().read().unwrap().clone();
+                if last.as_ref().is_some_and(|(mode, prev)| *mode == compressed && *prev == reg) {
+                    continue;
+                }
+
+                let mode_changed = last.as_ref().map(|(mode, _)| *mode) != Some(compressed);
+                match write_snapshot(&registry_to_output_json(&reg), compressed) {
+                    Ok(()) => {
+                        if mode_changed {
+                            // Don't leave a stale copy in the other format behind.
+                            let stale = if compressed { registry_json_path() } else { registry_json_gz_path() };
+                            let _ = std::fs::remove_file(stale);
+                        }
+                        last = Some((compressed, reg));
+                    }
+                    Err(e) => warn!("[snapshot] Failed to write registry snapshot: {}", e),
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        error!("[snapshot] Failed to start registry snapshot writer: {}", e);
+    }
+}
+
+fn write_snapshot(value: &Value, compressed: bool) -> Result<(), String> {
+    let json = serde_json::to_vec(value).map_err(|e| format!("serialize: {}", e))?;
+    if !compressed {
+        return write_atomic(&registry_json_path(), &json);
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| format!("gzip: {}", e))
+        .and_then(|bytes| write_atomic(&registry_json_gz_path(), &bytes))
+}
+
+/// Write `bytes` to `<path>.tmp` and rename it over `path`.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    std::fs::write(&tmp, bytes).map_err(|e| format!("write '{}': {}", Path::new(&tmp).display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("rename to '{}': {}", path.display(), e))
+}
+
+/// Last snapshot that parsed, handed out when a read fails.
+static LAST_GOOD: Mutex<Option<Value>> = Mutex::new(None);
+
+/// The newest snapshot on disk, plain or gzipped. A read or parse failure
+/// (a file swapped mid-read, a truncated copy left by an older build) returns
+/// the last snapshot that did parse; `None` only before any has.
+pub fn read_registry_snapshot() -> Option<Value> {
+    read_snapshot_or_last_good(&registry_json_path(), &registry_json_gz_path())
+}
+
+fn read_snapshot_or_last_good(path: &Path, gz_path: &Path) -> Option<Value> {
+    let mut last_good = LAST_GOOD.lock().unwrap();
+    match read_newest_snapshot(path, gz_path) {
+        Some(snapshot) => {
+            *last_good = Some(snapshot.clone());
+            Some(snapshot)
+        }
+        None => last_good.clone(),
+    }
+}
+
+/// Whichever of `path` and `gz_path` was written last, parsed.
+fn read_newest_snapshot(path: &Path, gz_path: &Path) -> Option<Value> {
+    let (_, path) = [path, gz_path]
+        .into_iter()
+        .filter_map(|p| Some((std::fs::metadata(p).ok()?.modified().ok()?, p)))
+        .max_by_key(|(modified, _)| *modified)?;
+
+    let bytes = std::fs::read(path).ok()?;
+    if path != gz_path {
+        return serde_json::from_slice(&bytes).ok();
+    }
+    let mut json = Vec::new();
+    GzDecoder::new(bytes.as_slice()).read_to_end(&mut json).ok()?;
+    serde_json::from_slice(&json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    /// Both tests go through the one `LAST_GOOD`.
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("veil-snapshot-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A snapshot big enough that a torn read would cut it short. Every
+    /// entry repeats `seq`, so one mixed from two writes shows up too.
+    fn snapshot(seq: u64) -> Value {
+        json!({ "seq": seq, "entries": vec![seq; 2_000] })
+    }
+
+    fn assert_whole(value: &Value) {
+        let seq = value["seq"].as_u64().expect("snapshot without seq");
+        let entries = value["entries"].as_array().expect("snapshot without entries");
+        assert_eq!(entries.len(), 2_000);
+        assert!(entries.iter().all(|e| e.as_u64() == Some(seq)), "snapshot {} mixes writes", seq);
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn readers_never_see_a_partial_snapshot() {
+        let _serial = SERIAL.lock().unwrap();
+        let dir = temp_dir("hammer");
+        let (path, gz_path) = (dir.join("registry.json"), dir.join("registry.json.gz"));
+        write_atomic(&path, &serde_json::to_vec(&snapshot(0)).unwrap()).unwrap();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let (path, gz_path, done) = (path.clone(), gz_path.clone(), done.clone());
+                std::thread::spawn(move || {
+                    let mut reads = 0;
+                    while !done.load(Ordering::Relaxed) {
+                        // Straight from disk: a rename swaps whole files, so
+                        // every read parses and is one complete snapshot.
+                        assert_whole(&read_newest_snapshot(&path, &gz_path).expect("torn snapshot read"));
+                        // With the fallback there is always one to hand out.
+                        assert_whole(&read_snapshot_or_last_good(&path, &gz_path).unwrap());
+                        reads += 1;
+                    }
+                    reads
+                })
+            })
+            .collect();
+
+        // Alternate formats so the readers also switch between files.
+        for seq in 1..=500 {
+            let json = serde_json::to_vec(&snapshot(seq)).unwrap();
+            if seq % 2 == 0 {
+                write_atomic(&gz_path, &gzip(&json)).unwrap();
+            } else {
+                write_atomic(&path, &json).unwrap();
+            }
+        }
+        done.store(true, Ordering::Relaxed);
+        for reader in readers {
+            assert!(reader.join().unwrap() > 0);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_truncated_snapshot_falls_back_to_the_last_good_one() {
+        let _serial = SERIAL.lock().unwrap();
+        let dir = temp_dir("truncated");
+        let (path, gz_path) = (dir.join("registry.json"), dir.join("registry.json.gz"));
+        let json = serde_json::to_vec(&snapshot(7)).unwrap();
+        write_atomic(&path, &json).unwrap();
+        assert_eq!(read_snapshot_or_last_good(&path, &gz_path), Some(snapshot(7)));
+
+        std::fs::write(&path, &json[..json.len() / 2]).unwrap();
+        assert_eq!(read_newest_snapshot(&path, &gz_path), None);
+        assert_eq!(read_snapshot_or_last_good(&path, &gz_path), Some(snapshot(7)));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
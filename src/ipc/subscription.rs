@@ -0,0 +1,133 @@
+// ~/veil/veil-backend/src/ipc/subscription.rs
+//
+// `sysdata.subscribe` streams. A pipe client names sysdata keys and a rate,
+// and the server pushes a frame for a subscription only when the collected
+// values changed since the last one it sent. With `diff: true` those frames
+// carry a patch against the previous frame instead of the full values, when
+// the patch is smaller. Wire format lives in server.rs.
+
+use serde_json::{json, Map, Value};
+use std::time::{Duration, Instant};
+
+use crate::ipc::data_updater::{normalize_section, subscribe_sections, unsubscribe_sections};
+use crate::ipc::delta;
+use crate::ipc::registry::global_registry;
+
+/// Subscriptions a single pipe connection may hold at once.
+pub const MAX_SUBSCRIPTIONS_PER_CONNECTION: usize = 8;
+const MAX_KEYS: usize = 32;
+const MIN_RATE_MS: u64 = 50;
+const MAX_RATE_MS: u64 = 60_000;
+const DEFAULT_RATE_MS: u64 = 1000;
+
+pub struct Subscription {
+    pub id: u32,
+    /// Requested keys: a section (`cpu`) or a dotted path inside one (`cpu.usage_percent`).
+    keys: Vec<String>,
+    /// Distinct sections behind `keys`, held open in the updater while subscribed.
+    sections: Vec<&'static str>,
+    rate: Duration,
+    next_due: Instant,
+    last_sent: Option<Value>,
+    /// Send `patch` frames against `last_sent` when they are smaller.
+    diff: bool,
+}
+
+impl Subscription {
+    /// Parse `{ keys: [...], rate_ms, diff }` from a `subscribe` request.
+    pub fn from_args(id: u32, args: Option<&Value>) -> Result<Self, String> {
+        let raw_keys = args
+            .and_then(|a| a.get("keys"))
+            .and_then(|v| v.as_array())
+            .ok_or("Missing 'keys' in args")?;
+        if raw_keys.is_empty() || raw_keys.len() > MAX_KEYS {
+            return Err(format!("'keys' must list 1 to {} entries", MAX_KEYS));
+        }
+
+        let mut keys = Vec::new();
+        let mut sections = Vec::new();
+        for key in raw_keys {
+            let key = key.as_str().ok_or("'keys' must be strings")?.trim();
+            let key = key.strip_prefix("sysdata.").unwrap_or(key).to_string();
+            let section = key.split('.').next().unwrap_or_default();
+            let section = normalize_section(section)
+                .filter(|s| *s != "appdata")
+                .ok_or(format!("Unknown sysdata key: {}", key))?;
+            if !sections.contains(&section) {
+                sections.push(section);
+            }
+            keys.push(key);
+        }
+
+        let rate_ms = args
+            .and_then(|a| a.get("rate_ms"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_RATE_MS)
+            .clamp(MIN_RATE_MS, MAX_RATE_MS);
+        let diff = args.and_then(|a| a.get("diff")).and_then(|v| v.as_bool()).unwrap_or(false);
+
+        subscribe_sections(&sections);
+        Ok(Self {
+            id,
+            keys,
+            sections,
+            rate: Duration::from_millis(rate_ms),
+            next_due: Instant::now(),
+            last_sent: None,
+            diff,
+        })
+    }
+
+    /// Acknowledgement payload for a new subscription.
+    pub fn describe(&self) -> Value {
+        json!({
+            "subscription_id": self.id,
+            "keys": self.keys,
+            "rate_ms": self.rate.as_millis() as u64,
+            "diff": self.diff,
+        })
+    }
+
+    /// Frame payload if the subscription is due and its values changed.
+    pub fn poll(&mut self, now: Instant) -> Option<Value> {
+        if now < self.next_due {
+            return None;
+        }
+        self.next_due = now + self.rate;
+
+        let snapshot = self.snapshot();
+        if self.last_sent.as_ref() == Some(&snapshot) {
+            return None;
+        }
+        let patch = match &self.last_sent {
+            Some(last) if self.diff => delta::smaller_patch(last, &snapshot),
+            _ => None,
+        };
+        let frame = match patch {
+            Some(ops) => json!({ "subscription_id": self.id, "patch": ops }),
+            None => json!({ "subscription_id": self.id, "data": snapshot }),
+        };
+        self.last_sent = Some(snapshot);
+        Some(frame)
+    }
+
+    /// Time until the next poll is due.
+    pub fn until_due(&self, now: Instant) -> Duration {
+        self.next_due.saturating_duration_since(now)
+    }
+
+    fn snapshot(&self) -> Value {
+        let reg = global_registry().read().unwrap();
+        let mut out = Map::new();
+        for key in &self.keys {
+            out.insert(key.clone(), reg.sysdata_value(key).unwrap_or(Value::Null));
+        }
+        Value::Object(out)
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        unsubscribe_sections(&self.sections);
+    }
+}
@@ -3,7 +3,7 @@
 use serde_json::{json, Value};
 use std::{
 	cell::RefCell,
-	collections::VecDeque,
+	collections::{HashMap, VecDeque},
 	sync::{
 		atomic::{AtomicBool, Ordering},
 		OnceLock, RwLock,
@@ -11,11 +11,14 @@ use std::{
 	time::Duration,
 };
 use rustfft::{FftPlanner, num_complex::Complex};
+use windows::core::{Interface, PCWSTR};
 use windows::Win32::{
 	Devices::FunctionDiscovery::PKEY_Device_FriendlyName,
+	Foundation::S_OK,
 	Media::Audio::{
-		eCapture, eConsole, eMultimedia, eRender, IMMDevice, IMMDeviceEnumerator,
-		MMDeviceEnumerator,
+		eAll, eCapture, eCommunications, eConsole, eMultimedia, eRender, EDataFlow, ERole, IMMDevice,
+		IMMDeviceEnumerator, IMMEndpoint, MMDeviceEnumerator, DEVICE_STATE_ACTIVE,
+		AudioSessionStateActive, IAudioSessionControl2, IAudioSessionManager2, ISimpleAudioVolume,
 		IAudioClient, IAudioCaptureClient,
 		AUDCLNT_SHAREMODE_SHARED, AUDCLNT_STREAMFLAGS_LOOPBACK,
 		WAVEFORMATEX,
@@ -28,6 +31,8 @@ use windows::Win32::{
 	},
 };
 
+use crate::info;
+
 unsafe fn endpoint_display_name(device: &IMMDevice) -> Option<String> {
 	if let Ok(store) = device.OpenPropertyStore(STGM_READ) {
 		if let Ok(mut value) = store.GetValue(&PKEY_Device_FriendlyName) {
@@ -64,6 +69,9 @@ const FFT_SIZE: usize = 2048;
 
 static SPECTRUM_CACHE: OnceLock<RwLock<[f32; SPECTRUM_BINS]>> = OnceLock::new();
 static SPECTRUM_STARTED: AtomicBool = AtomicBool::new(false);
+/// Set by `set_default_device` so the collector re-queries the default
+/// endpoints on its next call instead of at the periodic refresh.
+static DEVICES_CHANGED: AtomicBool = AtomicBool::new(false);
 
 fn spectrum_cache() -> &'static RwLock<[f32; SPECTRUM_BINS]> {
 	SPECTRUM_CACHE.get_or_init(|| RwLock::new([0.0; SPECTRUM_BINS]))
@@ -74,6 +82,9 @@ struct BackendAudioState {
 	output_meter: Option<IAudioMeterInformation>,
 	output_volume: Option<IAudioEndpointVolume>,
 	input_volume: Option<IAudioEndpointVolume>,
+	session_manager: Option<IAudioSessionManager2>,
+	/// Process names by PID for the session list; cleared with the device refresh.
+	session_names: HashMap<u32, String>,
 	output_name: String,
 	input_name: String,
 	peak_ema: f32,
@@ -94,6 +105,8 @@ impl BackendAudioState {
 				output_meter: None,
 				output_volume: None,
 				input_volume: None,
+				session_manager: None,
+				session_names: HashMap::new(),
 				output_name: "default-output".to_string(),
 				input_name: "default-input".to_string(),
 				peak_ema: 0.0,
@@ -132,6 +145,8 @@ impl BackendAudioState {
 			self.output_meter = None;
 			self.output_volume = None;
 			self.input_volume = None;
+			self.session_manager = None;
+			self.session_names.clear();
 
 			if let Ok(output) = self
 				.enumerator
@@ -143,6 +158,7 @@ impl BackendAudioState {
 				}
 				self.output_meter = output.Activate::<IAudioMeterInformation>(CLSCTX_ALL, None).ok();
 				self.output_volume = output.Activate::<IAudioEndpointVolume>(CLSCTX_ALL, None).ok();
+				self.session_manager = output.Activate::<IAudioSessionManager2>(CLSCTX_ALL, None).ok();
 			}
 
 			if let Ok(input) = self
@@ -159,6 +175,72 @@ impl BackendAudioState {
 	}
 }
 
+impl BackendAudioState {
+	fn session_name(&mut self, pid: u32) -> String {
+		if let Some(name) = self.session_names.get(&pid) {
+			return name.clone();
+		}
+		let mut sys = sysinfo::System::new();
+		let spid = sysinfo::Pid::from_u32(pid);
+		sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[spid]), false);
+		let name = sys
+			.process(spid)
+			.map(|p| p.name().to_string_lossy().to_string())
+			.unwrap_or_else(|| format!("PID {}", pid));
+		self.session_names.insert(pid, name.clone());
+		name
+	}
+
+	/// Active render sessions on the default output device. Re-enumerated on
+	/// every call, so expired sessions drop out on the next sample.
+	unsafe fn sessions(&mut self) -> Vec<Value> {
+		let Some(manager) = self.session_manager.as_ref() else { return Vec::new() };
+		let Ok(sessions) = manager.GetSessionEnumerator() else { return Vec::new() };
+		let count = sessions.GetCount().unwrap_or(0);
+
+		let mut found = Vec::new();
+		for i in 0..count {
+			let Ok(control) = sessions.GetSession(i) else { continue };
+			if control.GetState().ok() != Some(AudioSessionStateActive) {
+				continue;
+			}
+			let Ok(control2) = control.cast::<IAudioSessionControl2>() else { continue };
+			let pid = control2.GetProcessId().unwrap_or(0);
+			let system_sounds = control2.IsSystemSoundsSession() == S_OK;
+
+			let (volume, muted) = match control.cast::<ISimpleAudioVolume>() {
+				Ok(v) => (
+					v.GetMasterVolume().unwrap_or(0.0).clamp(0.0, 1.0),
+					v.GetMute().map(|m| m.as_bool()).unwrap_or(false),
+				),
+				Err(_) => (0.0, false),
+			};
+			let peak = control
+				.cast::<IAudioMeterInformation>()
+				.ok()
+				.and_then(|m| m.GetPeakValue().ok())
+				.unwrap_or(0.0)
+				.clamp(0.0, 1.0);
+			found.push((pid, system_sounds, volume, muted, peak));
+		}
+
+		found
+			.into_iter()
+			.map(|(pid, system_sounds, volume, muted, peak)| {
+				let name = if system_sounds { "System Sounds".to_string() } else { self.session_name(pid) };
+				json!({
+					"pid": pid,
+					"name": name,
+					"system_sounds": system_sounds,
+					"volume_percent": (volume * 100.0).round(),
+					"muted": muted,
+					"peak_level": peak,
+				})
+			})
+			.collect()
+	}
+}
+
 pub fn get_audio_json() -> Value {
 	unsafe {
 		let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
@@ -185,6 +267,13 @@ pub fn get_audio_json() -> Value {
 			*state_opt = BackendAudioState::new().ok();
 		}
 
+		if DEVICES_CHANGED.swap(false, Ordering::Relaxed) {
+			if let Some(state) = state_opt.as_mut() {
+				state.calls_since_refresh = 0;
+				state.refresh();
+			}
+		}
+
 		let Some(state) = state_opt.as_mut() else {
 			return json!({
 				"output_device": {
@@ -197,7 +286,8 @@ pub fn get_audio_json() -> Value {
 					"name": "default-input",
 					"volume_percent": 0,
 					"muted": false
-				}
+				},
+				"sessions": []
 			});
 		};
 
@@ -206,6 +296,7 @@ pub fn get_audio_json() -> Value {
 		let mut output_peak = 0.0f32;
 		let mut input_volume = 0.0f32;
 		let mut input_muted = false;
+		let sessions = unsafe { state.sessions() };
 
 		unsafe {
 			if let Some(vol) = state.output_volume.as_ref() {
@@ -296,12 +387,141 @@ pub fn get_audio_json() -> Value {
 				"volume_percent": (input_volume * 100.0).round(),
 				"muted": input_muted,
 			},
+			"sessions": sessions,
 			"media_session": super::media::get_media_session_json(),
 			"spectrum_32": spectrum_cache().read().map(|s| s.to_vec()).unwrap_or_default(),
 		})
 	})
 }
 
+// ── Default device switching ──
+//
+// Windows has no public API for changing the default endpoint. The Sound
+// control panel uses the undocumented IPolicyConfig, whose layout has been
+// stable since Windows 7; only SetDefaultEndpoint is called here.
+
+mod policy_config {
+	#![allow(dead_code, non_snake_case)]
+
+	use std::ffi::c_void;
+	use windows::core::{IUnknown, IUnknown_Vtbl, GUID, HRESULT, PCWSTR};
+	use windows::Win32::Media::Audio::{ERole, WAVEFORMATEX};
+
+	pub const CLSID_POLICY_CONFIG_CLIENT: GUID = GUID::from_u128(0x870af99c_171d_4f9e_af0d_e63df40c2bc9);
+
+	#[windows::core::interface("f8679f50-850a-41cf-9c72-430f290290c8")]
+	pub unsafe trait IPolicyConfig: IUnknown {
+		fn GetMixFormat(&self, device: PCWSTR, format: *mut *mut WAVEFORMATEX) -> HRESULT;
+		fn GetDeviceFormat(&self, device: PCWSTR, default: i32, format: *mut *mut WAVEFORMATEX) -> HRESULT;
+		fn ResetDeviceFormat(&self, device: PCWSTR) -> HRESULT;
+		fn SetDeviceFormat(&self, device: PCWSTR, endpoint: *mut WAVEFORMATEX, mix: *mut WAVEFORMATEX) -> HRESULT;
+		fn GetProcessingPeriod(&self, device: PCWSTR, default: i32, period: *mut i64, min_period: *mut i64) -> HRESULT;
+		fn SetProcessingPeriod(&self, device: PCWSTR, period: *mut i64) -> HRESULT;
+		fn GetShareMode(&self, device: PCWSTR, mode: *mut c_void) -> HRESULT;
+		fn SetShareMode(&self, device: PCWSTR, mode: *mut c_void) -> HRESULT;
+		fn GetPropertyValue(&self, device: PCWSTR, key: *const c_void, value: *mut c_void) -> HRESULT;
+		fn SetPropertyValue(&self, device: PCWSTR, key: *const c_void, value: *mut c_void) -> HRESULT;
+		fn SetDefaultEndpoint(&self, device: PCWSTR, role: ERole) -> HRESULT;
+		fn SetEndpointVisibility(&self, device: PCWSTR, visible: i32) -> HRESULT;
+	}
+}
+
+unsafe fn endpoint_id(device: &IMMDevice) -> Option<String> {
+	let id = device.GetId().ok()?;
+	let text = id.to_string().ok();
+	CoTaskMemFree(Some(id.0 as _));
+	text
+}
+
+unsafe fn endpoint_flow(device: &IMMDevice) -> Option<EDataFlow> {
+	device.cast::<IMMEndpoint>().ok()?.GetDataFlow().ok()
+}
+
+fn direction(flow: EDataFlow) -> &'static str {
+	if flow == eCapture { "input" } else { "output" }
+}
+
+fn device_enumerator() -> Result<IMMDeviceEnumerator, String> {
+	unsafe {
+		let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+		CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)
+			.map_err(|e| format!("CoCreateInstance(MMDeviceEnumerator) failed: {e:?}"))
+	}
+}
+
+/// Active playback and recording endpoints, marked with the ones that are
+/// currently default for each role.
+pub fn list_devices_json() -> Result<Value, String> {
+	let enumerator = device_enumerator()?;
+	unsafe {
+		let default_id = |flow: EDataFlow, role: ERole| {
+			enumerator.GetDefaultAudioEndpoint(flow, role).ok().and_then(|d| endpoint_id(&d))
+		};
+		let output = (default_id(eRender, eConsole), default_id(eRender, eCommunications));
+		let input = (default_id(eCapture, eConsole), default_id(eCapture, eCommunications));
+
+		let collection = enumerator
+			.EnumAudioEndpoints(eAll, DEVICE_STATE_ACTIVE)
+			.map_err(|e| format!("Failed to enumerate audio devices: {e:?}"))?;
+		let mut devices = Vec::new();
+		for i in 0..collection.GetCount().unwrap_or(0) {
+			let Ok(device) = collection.Item(i) else { continue };
+			let Some(id) = endpoint_id(&device) else { continue };
+			let flow = endpoint_flow(&device).unwrap_or(eRender);
+			let (default, communications) = if flow == eCapture { &input } else { &output };
+			devices.push(json!({
+				"id": id,
+				"name": endpoint_display_name(&device).unwrap_or_else(|| id.clone()),
+				"direction": direction(flow),
+				"default": default.as_deref() == Some(id.as_str()),
+				"default_communications": communications.as_deref() == Some(id.as_str()),
+			}));
+		}
+		Ok(json!({ "devices": devices }))
+	}
+}
+
+/// Make `device_id` the default endpoint for `role`: `output` or `input`
+/// (console and multimedia) or `communications`.
+pub fn set_default_device(device_id: &str, role: &str) -> Result<Value, String> {
+	let (expected_flow, roles): (Option<EDataFlow>, &[ERole]) = match role {
+		"output" => (Some(eRender), &[eConsole, eMultimedia]),
+		"input" => (Some(eCapture), &[eConsole, eMultimedia]),
+		"communications" => (None, &[eCommunications]),
+		_ => return Err(format!("Unknown role '{}': expected output, input or communications", role)),
+	};
+
+	let enumerator = device_enumerator()?;
+	let wide: Vec<u16> = device_id.encode_utf16().chain(Some(0)).collect();
+	unsafe {
+		let device = enumerator
+			.GetDevice(PCWSTR(wide.as_ptr()))
+			.ok()
+			.filter(|d| d.GetState().map(|s| s == DEVICE_STATE_ACTIVE).unwrap_or(false))
+			.ok_or_else(|| format!("No active audio device with id {}", device_id))?;
+		let flow = endpoint_flow(&device).ok_or("Couldn't tell whether the device plays or records")?;
+		if expected_flow.is_some_and(|expected| expected != flow) {
+			return Err(format!("{} is an {} device, not an {} device", device_id, direction(flow), role));
+		}
+
+		let policy: policy_config::IPolicyConfig =
+			CoCreateInstance(&policy_config::CLSID_POLICY_CONFIG_CLIENT, None, CLSCTX_ALL)
+				.map_err(|e| format!("CoCreateInstance(PolicyConfigClient) failed: {e:?}"))?;
+		for &r in roles {
+			policy
+				.SetDefaultEndpoint(PCWSTR(wide.as_ptr()), r)
+				.ok()
+				.map_err(|e| format!("Failed to set the default {} device: {e:?}", role))?;
+		}
+
+		let name = endpoint_display_name(&device).unwrap_or_else(|| device_id.to_string());
+		info!("[audio] Default {} device is now '{}'", role, name);
+		DEVICES_CHANGED.store(true, Ordering::Relaxed);
+		crate::ipc::data_updater::refresh_section_now("audio");
+		Ok(json!({ "device_id": device_id, "name": name, "role": role, "direction": direction(flow) }))
+	}
+}
+
 fn start_spectrum_capture_once() {
 	if SPECTRUM_STARTED
 		.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
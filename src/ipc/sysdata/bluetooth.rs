@@ -84,15 +84,42 @@ Add-Type -AssemblyName System.Runtime.WindowsRuntime 2>$null;
 [void][Windows.Devices.Bluetooth.BluetoothDevice,Windows.Devices.Bluetooth,ContentType=WindowsRuntime];
 [void][Windows.Devices.Bluetooth.BluetoothLEDevice,Windows.Devices.Bluetooth,ContentType=WindowsRuntime];
 [void][Windows.Devices.Enumeration.DeviceInformation,Windows.Devices.Enumeration,ContentType=WindowsRuntime];
+[void][Windows.Devices.Bluetooth.GenericAttributeProfile.GattDeviceService,Windows.Devices.Bluetooth,ContentType=WindowsRuntime];
+[void][Windows.Storage.Streams.DataReader,Windows.Storage.Streams,ContentType=WindowsRuntime];
 $asTask = ([System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object {
     $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and
     $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1'
 })[0];
-function WA($op, $type) {
+function WA($op, $type, $ms = 5000) {
     $t = $asTask.MakeGenericMethod($type).Invoke($null, @($op));
-    $t.Wait(5000) | Out-Null;
+    if (-not $t.Wait($ms)) { return $null }
     return $t.Result;
 }
+# Battery level Windows reports for hands-free / HID devices (DEVPKEY_Bluetooth_Battery),
+# keyed by the 12-digit address in the device instance ID.
+$battery = @{};
+try {
+    Get-PnpDevice -PresentOnly | Where-Object { $_.InstanceId -match '^BTH(ENUM|LE)\\' } | ForEach-Object {
+        $v = (Get-PnpDeviceProperty -InstanceId $_.InstanceId -KeyName '{104EA319-6EE2-4701-BD47-8DDBF425BBE5} 2').Data;
+        if ($v -ne $null -and $_.InstanceId -match '([0-9A-F]{12})') { $battery[$matches[1]] = [int]$v }
+    }
+} catch {}
+# GATT Battery Level (0x180F / 0x2A19). Only called for connected LE devices and
+# from the cached service list, so out-of-range devices are never paged.
+function GattBattery($ble) {
+    try {
+        $svcUuid = [Guid]'0000180f-0000-1000-8000-00805f9b34fb';
+        $chrUuid = [Guid]'00002a19-0000-1000-8000-00805f9b34fb';
+        $cached = [Windows.Devices.Bluetooth.BluetoothCacheMode]::Cached;
+        $svcs = WA ($ble.GetGattServicesForUuidAsync($svcUuid, $cached)) ([Windows.Devices.Bluetooth.GenericAttributeProfile.GattDeviceServicesResult]) 2000;
+        if (-not $svcs -or $svcs.Services.Count -eq 0) { return $null }
+        $chrs = WA ($svcs.Services[0].GetCharacteristicsForUuidAsync($chrUuid, $cached)) ([Windows.Devices.Bluetooth.GenericAttributeProfile.GattCharacteristicsResult]) 2000;
+        if (-not $chrs -or $chrs.Characteristics.Count -eq 0) { return $null }
+        $read = WA ($chrs.Characteristics[0].ReadValueAsync()) ([Windows.Devices.Bluetooth.GenericAttributeProfile.GattReadResult]) 2000;
+        if (-not $read -or $read.Status -ne [Windows.Devices.Bluetooth.GenericAttributeProfile.GattCommunicationStatus]::Success) { return $null }
+        return [int][Windows.Storage.Streams.DataReader]::FromBuffer($read.Value).ReadByte();
+    } catch { return $null }
+}
 $seen = @{};
 # Classic Bluetooth
 try {
@@ -111,6 +138,7 @@ try {
                 "Address=$addr";
                 "Class=$($bt.ClassOfDevice.MajorClass)";
                 "Type=Classic";
+                if ($battery.ContainsKey($addr)) { "Battery=$($battery[$addr])" }
                 "END_DEVICE";
             }
         }
@@ -133,6 +161,9 @@ try {
                 "Address=$addr";
                 "Class=BLE";
                 "Type=LE";
+                $level = if ($isConn) { GattBattery $ble } else { $null };
+                if ($level -eq $null -and $battery.ContainsKey($addr)) { $level = $battery[$addr] }
+                if ($level -ne $null) { "Battery=$level" }
                 "END_DEVICE";
             }
         }
@@ -169,6 +200,7 @@ fn parse_bluetooth_output(text: &str) -> Vec<Value> {
 	let mut address = String::new();
 	let mut class = String::new();
 	let mut dev_type = String::new();
+	let mut battery: Option<u8> = None;
 	let mut in_device = false;
 
 	for raw in text.lines() {
@@ -180,6 +212,7 @@ fn parse_bluetooth_output(text: &str) -> Vec<Value> {
 			address.clear();
 			class.clear();
 			dev_type.clear();
+			battery = None;
 			continue;
 		}
 		if line == "END_DEVICE" {
@@ -189,13 +222,18 @@ fn parse_bluetooth_output(text: &str) -> Vec<Value> {
 					in_device = false;
 					continue;
 				}
-				devices.push(json!({
+				let mut device = json!({
 					"name": name,
 					"connected": connected,
 					"address": if address.is_empty() { Value::Null } else { json!(address) },
 					"class": if class.is_empty() { Value::Null } else { json!(class) },
 					"type": if dev_type.is_empty() { Value::Null } else { json!(dev_type) },
-				}));
+				});
+				// Only devices that expose a battery level get the field at all.
+				if let Some(level) = battery {
+					device["battery_percent"] = json!(level);
+				}
+				devices.push(device);
 			}
 			in_device = false;
 			continue;
@@ -213,6 +251,8 @@ fn parse_bluetooth_output(text: &str) -> Vec<Value> {
 			class = v.trim().to_string();
 		} else if let Some(v) = line.strip_prefix("Type=") {
 			dev_type = v.trim().to_string();
+		} else if let Some(v) = line.strip_prefix("Battery=") {
+			battery = v.trim().parse::<u8>().ok().filter(|b| *b <= 100);
 		}
 	}
 
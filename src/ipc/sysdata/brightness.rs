@@ -0,0 +1,177 @@
+// ~/veil/veil-backend/src/ipc/sysdata/brightness.rs
+//
+// Monitor brightness over DDC/CI (external monitors) and WMI (internal
+// panels). Internal panels are read with the EDID query in display.rs. DDC/CI
+// calls go through the monitor's I2C bus and can hang on monitors that
+// implement it badly, so each one runs on its own thread and is abandoned
+// after DDC_TIMEOUT.
+
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use windows::Win32::Devices::Display::{
+	DestroyPhysicalMonitors, GetMonitorBrightness, GetNumberOfPhysicalMonitorsFromHMONITOR,
+	GetPhysicalMonitorsFromHMONITOR, SetMonitorBrightness, PHYSICAL_MONITOR,
+};
+use windows::Win32::Graphics::Gdi::HMONITOR;
+
+use super::display;
+use crate::info;
+use crate::ipc::registry::global_registry;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+const DDC_TIMEOUT: Duration = Duration::from_millis(750);
+
+/// GDI device names with a DDC/CI call still running.
+fn busy() -> &'static Mutex<HashSet<String>> {
+	static BUSY: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+	BUSY.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Run `f` on its own thread and wait up to DDC_TIMEOUT for it. A monitor
+/// whose previous call hasn't returned is refused, so a hung monitor holds
+/// at most one thread.
+fn bounded<T: Send + 'static>(device_name: &str, f: impl FnOnce() -> T + Send + 'static) -> Result<T, String> {
+	if !busy().lock().unwrap().insert(device_name.to_string()) {
+		return Err(format!("{} is still busy with an earlier DDC/CI call", device_name));
+	}
+	let (tx, rx) = mpsc::channel();
+	let key = device_name.to_string();
+	let spawned = thread::Builder::new().name("veil-ddc".into()).spawn(move || {
+		let result = f();
+		busy().lock().unwrap().remove(&key);
+		let _ = tx.send(result);
+	});
+	if let Err(e) = spawned {
+		busy().lock().unwrap().remove(device_name);
+		return Err(format!("Failed to start DDC/CI thread: {}", e));
+	}
+	rx.recv_timeout(DDC_TIMEOUT)
+		.map_err(|_| format!("{} did not answer within {} ms", device_name, DDC_TIMEOUT.as_millis()))
+}
+
+/// Call `f` with the physical monitors behind `hmonitor`; `None` when there
+/// are none (e.g. most internal panels).
+unsafe fn with_physical<T>(hmonitor: HMONITOR, f: impl FnOnce(&[PHYSICAL_MONITOR]) -> T) -> Option<T> {
+	let mut count = 0u32;
+	GetNumberOfPhysicalMonitorsFromHMONITOR(hmonitor, &mut count).ok()?;
+	if count == 0 {
+		return None;
+	}
+	let mut monitors = vec![PHYSICAL_MONITOR::default(); count as usize];
+	GetPhysicalMonitorsFromHMONITOR(hmonitor, &mut monitors).ok()?;
+	let result = f(&monitors);
+	let _ = DestroyPhysicalMonitors(&monitors);
+	Some(result)
+}
+
+/// `(min, current, max)` as reported over DDC/CI.
+unsafe fn ddc_range(monitor: &PHYSICAL_MONITOR) -> Option<(u32, u32, u32)> {
+	let (mut min, mut current, mut max) = (0u32, 0u32, 0u32);
+	let ok = GetMonitorBrightness(monitor.hPhysicalMonitor, &mut min, &mut current, &mut max) != 0;
+	(ok && max > min).then_some((min, current.clamp(min, max), max))
+}
+
+/// DDC/CI brightness of the monitor on `hmonitor`, or `None` when it doesn't
+/// answer in time or doesn't support it.
+pub fn read_ddc(hmonitor: HMONITOR, device_name: &str) -> Option<u8> {
+	let raw = hmonitor.0 as usize;
+	bounded(device_name, move || unsafe {
+		with_physical(HMONITOR(raw as *mut _), |monitors| {
+			monitors.iter().find_map(|m| ddc_range(m)).map(|(min, current, max)| {
+				(((current - min) * 100 + (max - min) / 2) / (max - min)) as u8
+			})
+		})
+		.flatten()
+	})
+	.ok()
+	.flatten()
+}
+
+unsafe fn ddc_set(hmonitor: HMONITOR, percent: u8) -> Result<(), String> {
+	let set = with_physical(hmonitor, |monitors| {
+		monitors
+			.iter()
+			.filter(|m| match ddc_range(m) {
+				Some((min, _, max)) => {
+					let value = min + ((max - min) * percent as u32 + 50) / 100;
+					SetMonitorBrightness(m.hPhysicalMonitor, value) != 0
+				}
+				None => false,
+			})
+			.count()
+	});
+	match set {
+		Some(n) if n > 0 => Ok(()),
+		_ => Err("not supported by the monitor".to_string()),
+	}
+}
+
+/// Set an internal panel's backlight through `WmiMonitorBrightnessMethods`.
+fn wmi_set(device_name: &str, percent: u8) -> Result<(), String> {
+	let hw_id = display::monitor_hw_id(device_name)
+		.filter(|id| id.chars().all(|c| c.is_ascii_alphanumeric()))
+		.ok_or("no hardware id for the monitor")?;
+	let script = format!(
+		r#"$ErrorActionPreference='Stop';
+$m = Get-CimInstance -Namespace root\wmi -ClassName WmiMonitorBrightnessMethods -EA SilentlyContinue |
+    Where-Object {{ ($_.InstanceName -split '\\')[1] -eq '{}' }} | Select-Object -First 1;
+if (-not $m) {{ 'NONE'; exit }}
+Invoke-CimMethod -InputObject $m -MethodName WmiSetBrightness -Arguments @{{ Timeout = [uint32]0; Brightness = [byte]{} }} | Out-Null;
+'OK'"#,
+		hw_id, percent
+	);
+	let output = Command::new("powershell")
+		.creation_flags(CREATE_NO_WINDOW)
+		.args(["-NoProfile", "-NonInteractive", "-Command", &script])
+		.output()
+		.map_err(|e| format!("failed to run PowerShell: {}", e))?;
+	match String::from_utf8_lossy(&output.stdout).trim() {
+		"OK" => Ok(()),
+		"NONE" => Err("not an internal panel".to_string()),
+		_ => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+	}
+}
+
+/// GDI device name for a `stable_id` from the last display snapshot;
+/// anything else is passed through for `display::find_monitor`.
+fn resolve_monitor_id(monitor_id: &str) -> String {
+	let reg = global_registry().read().unwrap();
+	reg.sysdata_value("displays.monitors")
+		.and_then(|monitors| {
+			monitors.as_array()?.iter().find_map(|m| {
+				(m.get("stable_id")?.as_str()? == monitor_id)
+					.then(|| m.get("device_name")?.as_str().map(str::to_string))
+					.flatten()
+			})
+		})
+		.unwrap_or_else(|| monitor_id.to_string())
+}
+
+/// Set a monitor's brightness, trying DDC/CI first and WMI second.
+/// `monitor_id` is the `id`, `stable_id` or `device_name` from `get_displays`.
+pub fn set_brightness(monitor_id: &str, percent: u8) -> Result<Value, String> {
+	let (hmonitor, device_name) = display::find_monitor(&resolve_monitor_id(monitor_id))
+		.ok_or_else(|| format!("Unknown monitor: {}", monitor_id))?;
+	let raw = hmonitor.0 as usize;
+	let ddc = bounded(&device_name, move || unsafe { ddc_set(HMONITOR(raw as *mut _), percent) }).and_then(|r| r);
+	let method = match ddc {
+		Ok(()) => "ddc",
+		Err(ddc_err) => match wmi_set(&device_name, percent) {
+			Ok(()) => "wmi",
+			Err(wmi_err) => {
+				return Err(format!(
+					"Couldn't set brightness on {}: DDC/CI {}; WMI {}",
+					device_name, ddc_err, wmi_err
+				))
+			}
+		},
+	};
+	info!("[display] Set brightness of {} to {}% via {}", device_name, percent, method);
+	Ok(json!({ "monitor_id": monitor_id, "device_name": device_name, "percent": percent, "method": method }))
+}
@@ -4,13 +4,19 @@ use serde_json::{json, Value};
 use std::cell::RefCell;
 use std::os::windows::process::CommandExt;
 use std::process::Command;
+use std::time::{Duration, Instant};
 use sysinfo::Components;
 use sysinfo::ProcessesToUpdate;
 use sysinfo::System;
 use windows::Win32::Foundation::FILETIME;
+use windows::Win32::System::ProcessStatus::{GetPerformanceInfo, PERFORMANCE_INFORMATION};
 use windows::Win32::System::Threading::GetSystemTimes;
 
+use super::static_hw::static_hardware;
+
 const CREATE_NO_WINDOW: u32 = 0x08000000;
+/// How long a throttling reading is reused; each one starts PowerShell.
+const THROTTLE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
 
 thread_local! {
     static CPU_SYS: RefCell<System> = RefCell::new({
@@ -20,10 +26,11 @@ thread_local! {
         sys
     });
 	static CPU_TIMES: RefCell<Option<(u64, u64, u64)>> = const { RefCell::new(None) };
+	static THROTTLE: RefCell<Option<(Instant, Option<bool>)>> = const { RefCell::new(None) };
 }
 
 pub fn get_cpu_json() -> Value {
-	let (logical_cores, avg_usage, avg_frequency_mhz, brand, vendor_id, per_core, process_count) =
+	let (logical_cores, avg_usage, avg_frequency_mhz, brand, vendor_id, per_core, per_core_usage, process_count) =
 		CPU_SYS.with(|cell| {
 			let mut sys = cell.borrow_mut();
 
@@ -69,6 +76,16 @@ pub fn get_cpu_json() -> Value {
 				})
 				.collect();
 
+			// sysinfo reads these from the per-processor PDH counters, in the
+			// OS's logical processor order (P-cores before E-cores on hybrid parts).
+			let per_core_usage: Vec<f32> = cpus
+				.iter()
+				.map(|c| {
+					let usage = c.cpu_usage();
+					if usage.is_finite() { usage.clamp(0.0, 100.0) } else { 0.0 }
+				})
+				.collect();
+
 			let process_count = sys.processes().len();
 
 			(
@@ -78,25 +95,27 @@ pub fn get_cpu_json() -> Value {
 				brand,
 				vendor_id,
 				per_core,
+				per_core_usage,
 				process_count,
 			)
 		});
 
-	let physical_cores = System::physical_core_count().unwrap_or(0);
 
 	let usage_percent = query_system_cpu_usage_percent()
 		.or_else(query_perf_cpu_usage_percent)
 		.unwrap_or(avg_usage);
 
 	let cpu_temp = get_cpu_temperature_json();
+	let thermal_throttling = thermal_throttling();
 
-	let uptime_seconds = System::uptime();
-	let boot_time_unix = System::boot_time();
+	let (uptime_seconds, boot_time_unix) = super::time::boot_clock();
 
 	let arch = std::env::consts::ARCH;
 
-	// Query additional CPU details from WMI (base speed, sockets, virtualization, caches, handles, threads)
-	let cpu_details = query_cpu_details();
+	let hw = static_hardware();
+	let physical_cores = hw.physical_cores.unwrap_or(0);
+	let cpu_details = &hw.cpu;
+	let (handle_count, thread_count) = query_handle_thread_counts();
 
 	json!({
 		"brand": brand,
@@ -113,16 +132,33 @@ pub fn get_cpu_json() -> Value {
 		"l1_cache_kb": cpu_details.get("l1_cache_kb").cloned().unwrap_or(Value::Null),
 		"l2_cache_kb": cpu_details.get("l2_cache_kb").cloned().unwrap_or(Value::Null),
 		"l3_cache_kb": cpu_details.get("l3_cache_kb").cloned().unwrap_or(Value::Null),
-		"thread_count": cpu_details.get("thread_count").cloned().unwrap_or(Value::Null),
-		"handle_count": cpu_details.get("handle_count").cloned().unwrap_or(Value::Null),
+		"thread_count": thread_count,
+		"handle_count": handle_count,
 		"temperature": cpu_temp,
+		"thermal_throttling": thermal_throttling,
 		"per_core": per_core,
+		"per_core_usage": per_core_usage,
 		"uptime_seconds": uptime_seconds,
 		"boot_time_unix": boot_time_unix,
 		"process_count": process_count,
 	})
 }
 
+/// Readings outside this range (°C) are treated as sensor glitches and dropped.
+const PLAUSIBLE_TEMP_C: std::ops::RangeInclusive<f32> = -20.0..=150.0;
+
+fn plausible_temp(c: f32) -> Option<f32> {
+	(c.is_finite() && PLAUSIBLE_TEMP_C.contains(&c)).then_some(c)
+}
+
+fn sensor_json(label: &str, temperature_c: f32, source: &str) -> Value {
+	json!({
+		"label": label,
+		"temperature_c": temperature_c,
+		"source": source,
+	})
+}
+
 fn get_cpu_temperature_json() -> Value {
 	let components = Components::new_with_refreshed_list();
 	let mut sensors = Vec::<Value>::new();
@@ -138,33 +174,23 @@ fn get_cpu_temperature_json() -> Value {
 		if !is_cpu {
 			continue;
 		}
+		let Some(value_c) = component.temperature().and_then(plausible_temp) else { continue };
 
-		sensors.push(json!({
-			"label": component.label(),
-			"temperature_c": component.temperature().unwrap_or(0.0),
-			"max_c": component.max(),
-			"critical_c": component.critical(),
-			"source": "sysinfo",
-		}));
+		let mut sensor = sensor_json(component.label(), value_c, "sysinfo");
+		sensor["max_c"] = json!(component.max());
+		sensor["critical_c"] = json!(component.critical());
+		sensors.push(sensor);
 	}
 
 	if sensors.is_empty() {
-		if let Some(temp) = query_wmi_cpu_temp_c() {
-			sensors.push(json!({
-				"label": "CPU Thermal Zone",
-				"temperature_c": temp,
-				"source": "wmi",
-			}));
+		for (label, value_c) in query_wmi_cpu_temps_c() {
+			sensors.push(sensor_json(&label, value_c, "wmi"));
 		}
 	}
 
 	if sensors.is_empty() {
-		if let Some(temp) = query_perf_counter_temp_c() {
-			sensors.push(json!({
-				"label": "Thermal Zone Counter",
-				"temperature_c": temp,
-				"source": "perf-counter",
-			}));
+		for (label, value_c) in query_perf_counter_temps_c() {
+			sensors.push(sensor_json(&label, value_c, "perf-counter"));
 		}
 	}
 
@@ -195,68 +221,89 @@ fn average_temp(sensors: &[Value]) -> f32 {
 	}
 }
 
-fn query_wmi_cpu_temp_c() -> Option<f32> {
-	let output = Command::new("wmic")
+/// Short label for an ACPI thermal zone instance, e.g. `ACPI\ThermalZone\TZ00_0` → `TZ00`.
+fn thermal_zone_label(instance: &str) -> String {
+	let name = instance.rsplit(['\\', '.']).next().unwrap_or(instance).trim();
+	let name = name.strip_suffix("_0").unwrap_or(name);
+	if name.is_empty() { "Thermal Zone".to_string() } else { name.to_string() }
+}
+
+fn query_wmi_cpu_temps_c() -> Vec<(String, f32)> {
+	let Ok(output) = Command::new("wmic")
 		.creation_flags(CREATE_NO_WINDOW)
 		.args([
 			"/namespace:\\\\root\\wmi",
 			"PATH",
 			"MSAcpi_ThermalZoneTemperature",
 			"get",
-			"CurrentTemperature",
+			"CurrentTemperature,InstanceName",
 			"/value",
 		])
 		.output()
-		.ok()?;
+	else {
+		return Vec::new();
+	};
 
 	if !output.status.success() {
-		return None;
+		return Vec::new();
 	}
 
+	// `/value` prints one `Key=Value` per line with a blank line between zones.
 	let text = String::from_utf8_lossy(&output.stdout);
-	let mut values = Vec::<f32>::new();
-	for line in text.lines() {
-		if let Some(value) = line.trim().strip_prefix("CurrentTemperature=") {
-			if let Ok(raw) = value.trim().parse::<f32>() {
-				let c = (raw / 10.0) - 273.15;
-				if c > -50.0 && c < 200.0 {
-					values.push(c);
-				}
+	let mut sensors = Vec::new();
+	let mut temp: Option<f32> = None;
+	let mut instance: Option<String> = None;
+	let mut flush = |temp: &mut Option<f32>, instance: &mut Option<String>| {
+		if let Some(raw) = temp.take() {
+			if let Some(c) = plausible_temp((raw / 10.0) - 273.15) {
+				let label = instance.take().map(|i| thermal_zone_label(&i)).unwrap_or_else(|| "Thermal Zone".to_string());
+				sensors.push((label, c));
 			}
 		}
-	}
+		*instance = None;
+	};
 
-	if values.is_empty() {
-		None
-	} else {
-		Some(values.iter().sum::<f32>() / values.len() as f32)
+	for line in text.lines() {
+		let line = line.trim();
+		if let Some(value) = line.strip_prefix("CurrentTemperature=") {
+			if temp.is_some() {
+				flush(&mut temp, &mut instance);
+			}
+			temp = value.trim().parse::<f32>().ok();
+		} else if let Some(value) = line.strip_prefix("InstanceName=") {
+			instance = Some(value.trim().to_string());
+		}
 	}
+	flush(&mut temp, &mut instance);
+	sensors
 }
 
-fn query_perf_counter_temp_c() -> Option<f32> {
+fn query_perf_counter_temps_c() -> Vec<(String, f32)> {
 	let script = r#"$ErrorActionPreference='SilentlyContinue';
 $samples = Get-Counter '\Thermal Zone Information(*)\Temperature' |
-	Select-Object -ExpandProperty CounterSamples |
-	Select-Object -ExpandProperty CookedValue;
-if ($samples) {
-	$samples | ForEach-Object { $_.ToString([System.Globalization.CultureInfo]::InvariantCulture) }
+	Select-Object -ExpandProperty CounterSamples;
+foreach ($s in $samples) {
+	"$($s.InstanceName)=" + $s.CookedValue.ToString([System.Globalization.CultureInfo]::InvariantCulture)
 }"#;
 
-	let output = Command::new("powershell")
+	let Ok(output) = Command::new("powershell")
 		.creation_flags(CREATE_NO_WINDOW)
 		.args(["-NoProfile", "-NonInteractive", "-Command", script])
 		.output()
-		.ok()?;
+	else {
+		return Vec::new();
+	};
 
 	if !output.status.success() {
-		return None;
+		return Vec::new();
 	}
 
 	let text = String::from_utf8_lossy(&output.stdout);
-	let mut values = Vec::<f32>::new();
+	let mut sensors = Vec::new();
 
 	for line in text.lines() {
-		let raw = match line.trim().parse::<f32>() {
+		let Some((instance, value)) = line.trim().rsplit_once('=') else { continue };
+		let raw = match value.trim().parse::<f32>() {
 			Ok(v) => v,
 			Err(_) => continue,
 		};
@@ -269,16 +316,59 @@ if ($samples) {
 			raw
 		};
 
-		if c > -50.0 && c < 200.0 {
-			values.push(c);
+		if let Some(c) = plausible_temp(c) {
+			sensors.push((thermal_zone_label(instance), c));
 		}
 	}
 
-	if values.is_empty() {
-		None
-	} else {
-		Some(values.iter().sum::<f32>() / values.len() as f32)
+	sensors
+}
+
+/// Whether an ACPI thermal zone is passively cooling (throttling) the CPU,
+/// read at most every THROTTLE_CHECK_INTERVAL. `None` when no zone reports a
+/// passive limit, which is common on desktops.
+fn thermal_throttling() -> Option<bool> {
+	THROTTLE.with(|cell| {
+		let mut last = cell.borrow_mut();
+		if let Some((at, throttling)) = *last {
+			if at.elapsed() < THROTTLE_CHECK_INTERVAL {
+				return throttling;
+			}
+		}
+		let limits = query_passive_limits_percent();
+		let throttling = (!limits.is_empty()).then(|| limits.iter().any(|&p| p < 100.0));
+		*last = Some((Instant::now(), throttling));
+		throttling
+	})
+}
+
+/// `% Passive Limit` of each thermal zone: 100 means unthrottled, lower is the
+/// share of full speed the zone currently allows. Perf counters need no
+/// elevation, unlike MSAcpi_ThermalZoneTemperature.
+fn query_passive_limits_percent() -> Vec<f32> {
+	let script = r#"$ErrorActionPreference='SilentlyContinue';
+$samples = Get-Counter '\Thermal Zone Information(*)\% Passive Limit' |
+	Select-Object -ExpandProperty CounterSamples;
+foreach ($s in $samples) {
+	$s.CookedValue.ToString([System.Globalization.CultureInfo]::InvariantCulture)
+}"#;
+
+	let Ok(output) = Command::new("powershell")
+		.creation_flags(CREATE_NO_WINDOW)
+		.args(["-NoProfile", "-NonInteractive", "-Command", script])
+		.output()
+	else {
+		return Vec::new();
+	};
+	if !output.status.success() {
+		return Vec::new();
 	}
+
+	String::from_utf8_lossy(&output.stdout)
+		.lines()
+		.filter_map(|line| line.trim().parse::<f32>().ok())
+		.filter(|p| p.is_finite() && (0.0..=100.0).contains(p))
+		.collect()
 }
 
 fn query_system_cpu_usage_percent() -> Option<f32> {
@@ -355,8 +445,9 @@ if ($sample -ne $null) {
 	None
 }
 
-/// Query CPU details not available from sysinfo: base speed, caches, sockets, virtualization, handles.
-fn query_cpu_details() -> Value {
+/// CPU details not available from sysinfo that are fixed for the session:
+/// base speed, caches, sockets, virtualization. Read once via `static_hw`.
+pub(super) fn query_cpu_static() -> Value {
 	let script = r#"$ErrorActionPreference='SilentlyContinue';
 $cpu = Get-CimInstance Win32_Processor -ErrorAction SilentlyContinue | Select-Object -First 1;
 if ($cpu) {
@@ -378,12 +469,6 @@ $caches = Get-CimInstance Win32_CacheMemory -ErrorAction SilentlyContinue;
 $totalL1 = 0;
 foreach ($c in $caches) { if ($c.Purpose -match 'L1' -or $c.Level -eq 3) { $totalL1 += $c.MaxCacheSize } }
 if ($totalL1 -gt 0) { "L1Total=$totalL1" }
-$handles = (Get-Process -ErrorAction SilentlyContinue | Measure-Object -Property HandleCount -Sum).Sum;
-"TotalHandles=$handles";
-$threads = (Get-CimInstance Win32_OperatingSystem -ErrorAction SilentlyContinue).NumberOfProcesses;
-try { $threads = (Get-Process -ErrorAction SilentlyContinue | Measure-Object -Property Threads -Sum -ErrorAction SilentlyContinue).Sum } catch {};
-if (-not $threads) { $threads = (Get-CimInstance Win32_PerfFormattedData_PerfOS_System -ErrorAction SilentlyContinue).Threads };
-"TotalThreads=$threads";
 "#;
 
 	let output = Command::new("powershell")
@@ -403,8 +488,6 @@ if (-not $threads) { $threads = (Get-CimInstance Win32_PerfFormattedData_PerfOS_
 	let mut l3_cache_kb: Option<u64> = None;
 	let mut virt_fw: Option<bool> = None;
 	let mut vm_ext: Option<bool> = None;
-	let mut total_handles: Option<u64> = None;
-	let mut total_threads: Option<u64> = None;
 	let mut manufacturer: Option<String> = None;
 	let mut stepping: Option<String> = None;
 
@@ -418,8 +501,6 @@ if (-not $threads) { $threads = (Get-CimInstance Win32_PerfFormattedData_PerfOS_
 		else if let Some(v) = line.strip_prefix("L3CacheSize=") { l3_cache_kb = v.trim().parse().ok(); }
 		else if let Some(v) = line.strip_prefix("VirtualizationFirmwareEnabled=") { virt_fw = Some(v.trim().eq_ignore_ascii_case("true")); }
 		else if let Some(v) = line.strip_prefix("VMMonitorModeExtensions=") { vm_ext = Some(v.trim().eq_ignore_ascii_case("true")); }
-		else if let Some(v) = line.strip_prefix("TotalHandles=") { total_handles = v.trim().parse().ok(); }
-		else if let Some(v) = line.strip_prefix("TotalThreads=") { total_threads = v.trim().parse().ok(); }
 		else if let Some(v) = line.strip_prefix("Manufacturer=") { manufacturer = Some(v.trim().to_string()); }
 		else if let Some(v) = line.strip_prefix("Stepping=") { stepping = Some(v.trim().to_string()); }
 	}
@@ -439,9 +520,18 @@ if (-not $threads) { $threads = (Get-CimInstance Win32_PerfFormattedData_PerfOS_
 		"l2_cache_kb": l2_cache_kb,
 		"l3_cache_kb": l3_cache_kb,
 		"virtualization": virtualization,
-		"handle_count": total_handles,
-		"thread_count": total_threads,
 		"manufacturer": manufacturer,
 		"stepping": stepping,
 	})
 }
+
+/// System-wide handle and thread counts, as Task Manager shows them.
+fn query_handle_thread_counts() -> (Option<u32>, Option<u32>) {
+	let mut info = PERFORMANCE_INFORMATION::default();
+	let ok = unsafe { GetPerformanceInfo(&mut info, std::mem::size_of::<PERFORMANCE_INFORMATION>() as u32) }.is_ok();
+	if ok {
+		(Some(info.HandleCount), Some(info.ThreadCount))
+	} else {
+		(None, None)
+	}
+}
@@ -0,0 +1,119 @@
+// ~/veil/veil-backend/src/ipc/sysdata/data_usage.rs
+//
+// Monthly traffic on metered interfaces, checked against the `data_cap_mb`
+// setting. Kept in `<root>/data_usage.json` so the month's total, and when
+// the warning last fired, survive restarts; both start over when the calendar
+// month changes.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::config;
+use crate::paths::data_usage_path;
+use crate::warn;
+
+/// How often the running total is flushed to disk.
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Default, Serialize, Deserialize)]
+struct UsageFile {
+	/// `YYYY-MM` the total belongs to.
+	#[serde(default)]
+	month: String,
+	#[serde(default)]
+	metered_bytes: u64,
+	/// Unix ms of the latest warning this month.
+	#[serde(default)]
+	warned_at_ms: Option<u64>,
+}
+
+struct UsageState {
+	file: UsageFile,
+	last_saved: Option<Instant>,
+	/// Whether usage was already at or above the warning threshold.
+	warned: bool,
+}
+
+static STATE: OnceLock<Mutex<UsageState>> = OnceLock::new();
+
+fn current_month() -> String {
+	chrono::Local::now().format("%Y-%m").to_string()
+}
+
+fn state() -> &'static Mutex<UsageState> {
+	STATE.get_or_init(|| {
+		let file = std::fs::read_to_string(data_usage_path())
+			.ok()
+			.and_then(|text| serde_json::from_str::<UsageFile>(&text).ok())
+			.unwrap_or_default();
+		// A warning raised before a restart isn't raised again for the same crossing.
+		let warned = file.warned_at_ms.is_some();
+		Mutex::new(UsageState { file, last_saved: None, warned })
+	})
+}
+
+fn save(file: &UsageFile) {
+	let path = data_usage_path();
+	match serde_json::to_string_pretty(file) {
+		Ok(text) => {
+			if let Err(e) = std::fs::write(&path, text) {
+				warn!("Failed to write data usage '{}': {}", path.display(), e);
+			}
+		}
+		Err(e) => warn!("Failed to serialize data usage: {}", e),
+	}
+}
+
+/// Add `bytes` of metered traffic for this tick. Returns the `data_cap`
+/// status block and whether the warning threshold was crossed on this call.
+/// The block's `warned_this_period` and `warned_at_ms` keep the warning
+/// visible for the rest of the month.
+pub fn record_metered_bytes(bytes: u64) -> (Value, bool) {
+	let mut st = state().lock().unwrap();
+
+	let month = current_month();
+	if st.file.month != month {
+		st.file = UsageFile { month, metered_bytes: 0, warned_at_ms: None };
+		st.warned = false;
+		st.last_saved = None;
+	}
+	st.file.metered_bytes = st.file.metered_bytes.saturating_add(bytes);
+
+	if st.last_saved.map(|t| t.elapsed() >= SAVE_INTERVAL).unwrap_or(true) {
+		save(&st.file);
+		st.last_saved = Some(Instant::now());
+	}
+
+	let cap_bytes = config::data_cap_mb().saturating_mul(1024 * 1024);
+	let warn_percent = config::data_cap_warn_percent();
+	let used = st.file.metered_bytes;
+	let percent = if cap_bytes > 0 { used as f64 / cap_bytes as f64 * 100.0 } else { 0.0 };
+	let warning = cap_bytes > 0 && percent >= warn_percent as f64;
+
+	let crossed = warning && !st.warned;
+	if crossed {
+		warn!(
+			"Metered data usage at {:.1}% of the {} MB monthly cap",
+			percent,
+			config::data_cap_mb()
+		);
+		st.file.warned_at_ms = Some(SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0));
+		save(&st.file);
+		st.last_saved = Some(Instant::now());
+	}
+	st.warned = warning;
+
+	let status = json!({
+		"month": st.file.month,
+		"used_bytes": used,
+		"cap_bytes": if cap_bytes > 0 { json!(cap_bytes) } else { Value::Null },
+		"used_percent": if cap_bytes > 0 { json!(percent) } else { Value::Null },
+		"warn_percent": warn_percent,
+		"warning": warning,
+		"warned_this_period": st.file.warned_at_ms.is_some(),
+		"warned_at_ms": st.file.warned_at_ms,
+	});
+	(status, crossed)
+}
@@ -1,21 +1,30 @@
 // ~/veil/veil-backend/src/ipc/sysdata/display.rs
 
 use serde::Serialize;
+#[cfg(target_os = "windows")]
 use sha2::{Digest, Sha256};
+#[cfg(target_os = "windows")]
 use std::{collections::HashMap, mem::size_of, os::windows::process::CommandExt, process::Command};
+#[cfg(target_os = "windows")]
 use windows::{
-    core::{BOOL, PCWSTR},
+    core::{BOOL, PCWSTR, PWSTR},
     Win32::{
-        Foundation::LPARAM,
+        Foundation::{LPARAM, RECT},
         Graphics::Gdi::{
-            EnumDisplayDevicesW, EnumDisplayMonitors, EnumDisplaySettingsW, GetMonitorInfoW,
-            DEVMODEW, DISPLAY_DEVICEW, HDC, HMONITOR, MONITORINFOEXW,
+            CreateDCW, DeleteDC, EnumDisplayDevicesW, EnumDisplayMonitors, EnumDisplaySettingsW,
+            GetMonitorInfoW, DEVMODEW, DISPLAY_DEVICEW, HDC, HMONITOR, MONITORINFOEXW,
             ENUM_CURRENT_SETTINGS,
         },
+        UI::ColorSystem::{
+            GetDeviceGammaRamp, WcsGetDefaultColorProfile, WcsGetUsePerUserProfiles, CPST_NONE,
+            CPT_ICC, WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+            WCS_PROFILE_MANAGEMENT_SCOPE_SYSTEM_WIDE,
+        },
         UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI},
     },
 };
 
+#[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
 #[derive(Serialize, Debug, Clone)]
@@ -43,14 +52,51 @@ pub struct MonitorInfo {
     pub product_code: String,
     pub serial_number: String,
     pub year_of_manufacture: u32,
+    /// File name of the ICC profile associated with the monitor; `None` when
+    /// nothing is assigned.
+    pub icc_profile_name: Option<String>,
+    /// `false` when something (calibration loader, night light) has changed the gamma ramp.
+    pub gamma_ramp_identity: bool,
+    /// Backlight level from WMI for internal panels, DDC/CI for external
+    /// monitors; `None` when the monitor doesn't report one.
+    pub brightness_percent: Option<u8>,
+    /// Device instance path (`MONITOR\GSM5BBF\{guid}\0001`); stable per connector.
+    pub device_id: String,
+    /// Identity that survives replugging and rearranging: the EDID serial when
+    /// it is real and unique, otherwise the device instance path.
+    pub stable_id: String,
+}
+
+/// Fill in `stable_id` for a full set of monitors. Two monitors of the same
+/// model with the same (or a placeholder) serial get their device paths instead.
+pub fn assign_stable_ids(monitors: &mut [MonitorInfo]) {
+    let edid_id = |m: &MonitorInfo| {
+        let serial = m.serial_number.trim();
+        let placeholder = serial.is_empty() || serial.chars().all(|c| c == '0');
+        (!placeholder).then(|| format!("edid:{}{}:{}", m.manufacturer.trim(), m.product_code.trim(), serial))
+    };
+    let edid_ids: Vec<Option<String>> = monitors.iter().map(edid_id).collect();
+
+    for (i, monitor) in monitors.iter_mut().enumerate() {
+        let unique_edid = edid_ids[i]
+            .clone()
+            .filter(|id| edid_ids.iter().filter(|other| other.as_ref() == Some(id)).count() == 1);
+        monitor.stable_id = match unique_edid {
+            Some(id) => id,
+            None if !monitor.device_id.is_empty() => format!("dev:{}", monitor.device_id),
+            None => String::new(),
+        };
+    }
 }
 
 /// Parse EDID data from registry to extract monitor details
+#[cfg(target_os = "windows")]
 fn query_edid_monitors() -> Vec<(String, EdidInfo)> {
     let script = r#"$ErrorActionPreference='SilentlyContinue';
 $monitors = Get-CimInstance -Namespace root\wmi -ClassName WmiMonitorID;
 $conn = Get-CimInstance -Namespace root\wmi -ClassName WmiMonitorConnectionParams;
 $bp = Get-CimInstance -Namespace root\wmi -ClassName WmiMonitorBasicDisplayParams;
+$br = Get-CimInstance -Namespace root\wmi -ClassName WmiMonitorBrightness;
 foreach ($m in $monitors) {
     $inst = $m.InstanceName;
     $name = if ($m.UserFriendlyName) { ($m.UserFriendlyName | Where-Object {$_ -ne 0} | ForEach-Object {[char]$_}) -join '' } else { '' };
@@ -63,6 +109,8 @@ foreach ($m in $monitors) {
     $b = $bp | Where-Object { $_.InstanceName -eq $inst };
     $hSize = if ($b) { $b.MaxHorizontalImageSize } else { 0 };
     $vSize = if ($b) { $b.MaxVerticalImageSize } else { 0 };
+    $w = $br | Where-Object { $_.InstanceName -eq $inst };
+    $bright = if ($w) { $w.CurrentBrightness } else { '' };
     "InstanceName=$inst";
     "MonitorName=$name";
     "Manufacturer=$mfr";
@@ -72,6 +120,7 @@ foreach ($m in $monitors) {
     "VideoOutputTechnology=$connType";
     "HorizontalSizeCm=$hSize";
     "VerticalSizeCm=$vSize";
+    "Brightness=$bright";
     "";
 }
 # HDR support from WmiMonitorBrightness or AdvancedColor
@@ -129,6 +178,8 @@ if ($adv) {
                         .map(|v| v * 10).unwrap_or(0),
                     physical_height_mm: fields.get("VerticalSizeCm").and_then(|s| s.trim().parse::<u32>().ok())
                         .map(|v| v * 10).unwrap_or(0),
+                    brightness_percent: fields.get("Brightness").and_then(|s| s.trim().parse::<u8>().ok())
+                        .map(|v| v.min(100)),
                 };
                 // Match by device path: InstanceName contains the display device ID
                 result.push((inst, info));
@@ -144,6 +195,7 @@ if ($adv) {
 }
 
 /// Check HDR capability via PowerShell AdvancedColorInfo
+#[cfg(target_os = "windows")]
 fn query_hdr_support() -> HashMap<String, bool> {
     let script = r#"$ErrorActionPreference='SilentlyContinue';
 Add-Type -AssemblyName System.Runtime.WindowsRuntime 2>$null;
@@ -176,6 +228,7 @@ if (Test-Path $acKey) {
     result
 }
 
+#[cfg(target_os = "windows")]
 #[derive(Debug, Clone, Default)]
 struct EdidInfo {
     monitor_name: String,
@@ -186,8 +239,11 @@ struct EdidInfo {
     connection_type: String,
     physical_width_mm: u32,
     physical_height_mm: u32,
+    /// `WmiMonitorBrightness`, only present for internal panels.
+    brightness_percent: Option<u8>,
 }
 
+#[cfg(target_os = "windows")]
 fn compute_aspect_ratio(w: i32, h: i32) -> String {
     if w <= 0 || h <= 0 { return String::new() }
     let gcd = gcd(w as u32, h as u32);
@@ -196,6 +252,7 @@ fn compute_aspect_ratio(w: i32, h: i32) -> String {
     format!("{}:{}", rw, rh)
 }
 
+#[cfg(target_os = "windows")]
 fn gcd(mut a: u32, mut b: u32) -> u32 {
     while b != 0 {
         let t = b;
@@ -206,6 +263,7 @@ fn gcd(mut a: u32, mut b: u32) -> u32 {
 }
 
 /// Try to get the monitor device name from EnumDisplayDevices for matching with EDID
+#[cfg(target_os = "windows")]
 fn get_monitor_device_ids() -> HashMap<String, String> {
     let mut result = HashMap::<String, String>::new();
     unsafe {
@@ -241,9 +299,85 @@ fn get_monitor_device_ids() -> HashMap<String, String> {
     result
 }
 
+/// `CLASS_MONITOR` ('mntr') for WcsGetUsePerUserProfiles.
+#[cfg(target_os = "windows")]
+const CLASS_MONITOR: u32 = 0x6D6E_7472;
+
+/// Default ICC profile associated with a monitor, looked up by its
+/// `MONITOR\...` device ID. Only an explicit association counts; WCS fails
+/// for unassociated monitors instead of handing back sRGB.
+#[cfg(target_os = "windows")]
+fn query_icc_profile_name(monitor_device_id: &str) -> Option<String> {
+    if monitor_device_id.is_empty() {
+        return None;
+    }
+    let device: Vec<u16> = monitor_device_id.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut name = [0u16; 260];
+    unsafe {
+        let mut per_user = BOOL(0);
+        let _ = WcsGetUsePerUserProfiles(PCWSTR(device.as_ptr()), CLASS_MONITOR, &mut per_user);
+        let scope = if per_user.as_bool() {
+            WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER
+        } else {
+            WCS_PROFILE_MANAGEMENT_SCOPE_SYSTEM_WIDE
+        };
+        if !WcsGetDefaultColorProfile(
+            scope,
+            PCWSTR(device.as_ptr()),
+            CPT_ICC,
+            CPST_NONE,
+            0,
+            (name.len() * 2) as u32,
+            PWSTR(name.as_mut_ptr()),
+        )
+        .as_bool()
+        {
+            return None;
+        }
+    }
+    let len = name.iter().position(|c| *c == 0).unwrap_or(name.len());
+    let profile = String::from_utf16_lossy(&name[..len]);
+    (!profile.is_empty()).then_some(profile)
+}
+
+/// Whether the display's gamma ramp is the identity (i * 257 per channel).
+/// A ramp that can't be read is assumed untouched.
+#[cfg(target_os = "windows")]
+fn gamma_ramp_is_identity(device_name: &str) -> bool {
+    let device: Vec<u16> = device_name.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut ramp = [0u16; 3 * 256];
+    unsafe {
+        let hdc = CreateDCW(PCWSTR(device.as_ptr()), PCWSTR(device.as_ptr()), PCWSTR::null(), None);
+        if hdc.is_invalid() {
+            return true;
+        }
+        let ok = GetDeviceGammaRamp(hdc, ramp.as_mut_ptr() as *mut _).as_bool();
+        let _ = DeleteDC(hdc);
+        if !ok {
+            return true;
+        }
+    }
+    // Drivers round the ramp slightly; allow one 8-bit step either way.
+    ramp.chunks(256).all(|channel| {
+        channel
+            .iter()
+            .enumerate()
+            .all(|(i, v)| (*v as i32 - i as i32 * 257).abs() <= 256)
+    })
+}
+
+/// Hardware id (`GSM5BBF`) of the monitor on a GDI device such as
+/// `\\.\DISPLAY1`, as used in WMI instance names.
+#[cfg(target_os = "windows")]
+pub fn monitor_hw_id(device_name: &str) -> Option<String> {
+    let hw_id = extract_hw_id(get_monitor_device_ids().get(device_name)?);
+    (!hw_id.is_empty()).then_some(hw_id)
+}
+
 /// Extract the hardware ID portion from a monitor path.
 /// E.g. "MONITOR\\GSM5BBF\\{guid}" → "GSM5BBF"
 ///      "DISPLAY\\GSM5BBF\\5&1234..." → "GSM5BBF"
+#[cfg(target_os = "windows")]
 fn extract_hw_id(path: &str) -> String {
     let parts: Vec<&str> = path.split('\\').collect();
     if parts.len() >= 2 {
@@ -253,8 +387,49 @@ fn extract_hw_id(path: &str) -> String {
     }
 }
 
+/// `MonitorInfo::id`: a hash of the GDI device name and desktop rectangle.
+#[cfg(target_os = "windows")]
+fn monitor_hash_id(device_name: &str, rc: &RECT) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(device_name.as_bytes());
+    hasher.update(rc.left.to_le_bytes());
+    hasher.update(rc.top.to_le_bytes());
+    hasher.update(rc.right.to_le_bytes());
+    hasher.update(rc.bottom.to_le_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Handle and GDI device name of the monitor whose `id` or `device_name` is
+/// `monitor_id`, without the EDID and WMI queries of a full enumeration.
+#[cfg(target_os = "windows")]
+pub fn find_monitor(monitor_id: &str) -> Option<(HMONITOR, String)> {
+    unsafe extern "system" fn callback(hmonitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, lparam: LPARAM) -> BOOL {
+        let ctx = &mut *(lparam.0 as *mut (String, Option<(HMONITOR, String)>));
+        let mut mi_ex: MONITORINFOEXW = std::mem::zeroed();
+        mi_ex.monitorInfo.cbSize = size_of::<MONITORINFOEXW>() as u32;
+        if GetMonitorInfoW(hmonitor, &mut mi_ex.monitorInfo).as_bool() {
+            let len = mi_ex.szDevice.iter().position(|c| *c == 0).unwrap_or(mi_ex.szDevice.len());
+            let device_name = String::from_utf16_lossy(&mi_ex.szDevice[..len]);
+            if device_name.eq_ignore_ascii_case(&ctx.0)
+                || monitor_hash_id(&device_name, &mi_ex.monitorInfo.rcMonitor) == ctx.0
+            {
+                ctx.1 = Some((hmonitor, device_name));
+                return BOOL(0);
+            }
+        }
+        BOOL(1)
+    }
+
+    let mut ctx: (String, Option<(HMONITOR, String)>) = (monitor_id.to_string(), None);
+    unsafe {
+        let _ = EnumDisplayMonitors(None, None, Some(callback), LPARAM(&mut ctx as *mut _ as isize));
+    }
+    ctx.1
+}
+
 pub struct MonitorManager;
 
+#[cfg(target_os = "windows")]
 impl MonitorManager {
     pub fn enumerate_monitors() -> Vec<MonitorInfo> {
         // Query EDID info and monitor device IDs
@@ -265,7 +440,7 @@ impl MonitorManager {
         unsafe extern "system" fn callback(
             hmonitor: HMONITOR,
             _hdc: HDC,
-            _rect: *mut windows::Win32::Foundation::RECT,
+            _rect: *mut RECT,
             lparam: LPARAM,
         ) -> BOOL {
             let ctx = &mut *(lparam.0 as *mut MonitorEnumContext);
@@ -351,6 +526,7 @@ impl MonitorManager {
                 let hw_id_part = extract_hw_id(&mon_device_id);
                 let mut matched_edid: Option<&EdidInfo> = None;
                 let mut matched_idx: Option<usize> = None;
+                let mut matched_by_hw_id = false;
 
                 if !hw_id_part.is_empty() {
                     for (i, (inst, info)) in ctx.edid_data.iter().enumerate() {
@@ -359,6 +535,7 @@ impl MonitorManager {
                         if !inst_hw.is_empty() && inst_hw.eq_ignore_ascii_case(&hw_id_part) {
                             matched_edid = Some(info);
                             matched_idx = Some(i);
+                            matched_by_hw_id = true;
                             break;
                         }
                     }
@@ -380,14 +557,14 @@ impl MonitorManager {
                 }
 
                 let edid = matched_edid.cloned().unwrap_or_default();
+                let id = monitor_hash_id(&device_name, &rc);
 
-                let mut hasher = Sha256::new();
-                hasher.update(device_name.as_bytes());
-                hasher.update(rc.left.to_le_bytes());
-                hasher.update(rc.top.to_le_bytes());
-                hasher.update(rc.right.to_le_bytes());
-                hasher.update(rc.bottom.to_le_bytes());
-                let id = format!("{:x}", hasher.finalize());
+                // An order-matched EDID entry may belong to another monitor, so
+                // only trust its WMI brightness on a hardware-id match.
+                let brightness_percent = edid
+                    .brightness_percent
+                    .filter(|_| matched_by_hw_id)
+                    .or_else(|| super::brightness::read_ddc(hmonitor, &device_name));
 
                 ctx.monitors.push(MonitorInfo {
                     id,
@@ -413,6 +590,11 @@ impl MonitorManager {
                     product_code: edid.product_code,
                     serial_number: edid.serial_number,
                     year_of_manufacture: edid.year_of_manufacture,
+                    icc_profile_name: query_icc_profile_name(&mon_device_id),
+                    gamma_ramp_identity: gamma_ramp_is_identity(&device_name),
+                    brightness_percent,
+                    device_id: mon_device_id,
+                    stable_id: String::new(),
                 });
             }
             BOOL(1)
@@ -435,6 +617,14 @@ impl MonitorManager {
         unsafe {
             let _ = EnumDisplayMonitors(None, None, Some(callback), LPARAM(&mut ctx as *mut _ as isize));
         }
+        assign_stable_ids(&mut ctx.monitors);
         ctx.monitors
     }
-}
\ No newline at end of file
+}
+
+#[cfg(not(target_os = "windows"))]
+impl MonitorManager {
+    pub fn enumerate_monitors() -> Vec<MonitorInfo> {
+        Vec::new()
+    }
+}
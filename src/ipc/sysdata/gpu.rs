@@ -5,14 +5,27 @@ use std::{
 	env,
 	path::PathBuf,
 	process::Command,
-	sync::OnceLock,
+	sync::{atomic::{AtomicBool, Ordering}, OnceLock},
 };
 use std::os::windows::process::CommandExt;
 use sysinfo::{Components, System};
+use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1};
+use super::process_snapshot::ProcessSnapshot;
 
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-pub fn get_gpu_json() -> Value {
+/// Processes listed per adapter in `processes`.
+const MAX_GPU_PROCESSES: usize = 10;
+
+const NVIDIA_SMI_FIELDS: &str = "name,driver_version,utilization.gpu,utilization.memory,utilization.encoder,utilization.decoder,memory.total,memory.used,memory.free,temperature.gpu,power.draw,fan.speed,clocks.current.graphics,clocks.current.memory";
+/// Bitmask of why the clocks are held down. Drivers from 535 on also call it
+/// `clocks_event_reasons.active` but still accept this name.
+const NVIDIA_SMI_THROTTLE_FIELD: &str = "clocks_throttle_reasons.active";
+/// Cleared once nvidia-smi rejects NVIDIA_SMI_THROTTLE_FIELD, so older drivers
+/// still get the other fields.
+static THROTTLE_FIELD_SUPPORTED: AtomicBool = AtomicBool::new(true);
+
+pub fn get_gpu_json(snapshot: &ProcessSnapshot) -> Value {
 	let components = Components::new_with_refreshed_list();
 
 	// Try nvidia-smi first for comprehensive data (usage, VRAM, power, clocks, etc.)
@@ -138,6 +151,9 @@ pub fn get_gpu_json() -> Value {
 
 	let average_c = average_temp(&all_sensors);
 
+	let mut adapters = adapters;
+	attach_gpu_processes(&mut adapters, snapshot);
+
 	// Top-level summary from primary adapter (first one)
 	let primary = adapters.first();
 	let name = primary.and_then(|a| a.get("name")).cloned().unwrap_or(Value::Null);
@@ -156,8 +172,10 @@ pub fn get_gpu_json() -> Value {
 	let decoder_usage = primary.and_then(|a| a.get("decoder_usage_percent")).cloned().unwrap_or(Value::Null);
 	let clock_graphics = primary.and_then(|a| a.get("clock_graphics_mhz")).cloned().unwrap_or(Value::Null);
 	let clock_memory = primary.and_then(|a| a.get("clock_memory_mhz")).cloned().unwrap_or(Value::Null);
+	let performance_limit = primary.and_then(|a| a.get("performance_limit")).cloned().unwrap_or(Value::Null);
+	let video_engines = primary.and_then(|a| a.get("video_engines")).cloned();
 
-	json!({
+	let mut out = json!({
 		"detected": !adapters.is_empty() || !all_sensors.is_empty(),
 		"name": name,
 		"usage_percent": usage_percent,
@@ -177,12 +195,52 @@ pub fn get_gpu_json() -> Value {
 		"decoder_usage_percent": decoder_usage,
 		"clock_graphics_mhz": clock_graphics,
 		"clock_memory_mhz": clock_memory,
+		"performance_limit": performance_limit,
+		"summary": adapter_summary(&adapters),
 		"adapters": adapters,
 		"temperature": {
 			"average_c": average_c,
 			"sensors": all_sensors.clone(),
 		},
 		"sensors": all_sensors,
+	});
+	if let (Some(engines), Some(obj)) = (video_engines, out.as_object_mut()) {
+		obj.insert("video_engines".into(), engines);
+	}
+	out
+}
+
+/// One headline figure across all adapters, recomputed every tick so it
+/// follows adapters being added or removed. VRAM totals only count adapters
+/// that report both used and total memory.
+fn adapter_summary(adapters: &[Value]) -> Value {
+	let mut busiest: Option<(usize, f64)> = None;
+	for (idx, adapter) in adapters.iter().enumerate() {
+		let Some(usage) = adapter.get("usage_percent").and_then(|v| v.as_f64()) else { continue };
+		if busiest.map(|(_, max)| usage > max).unwrap_or(true) {
+			busiest = Some((idx, usage));
+		}
+	}
+
+	let mut vram_used_mb = 0u64;
+	let mut vram_total_mb = 0u64;
+	for adapter in adapters {
+		let used = adapter.get("vram_used_mb").and_then(|v| v.as_u64());
+		let total = adapter.get("vram_total_mb").and_then(|v| v.as_u64());
+		if let (Some(used), Some(total)) = (used, total) {
+			vram_used_mb = vram_used_mb.saturating_add(used);
+			vram_total_mb = vram_total_mb.saturating_add(total);
+		}
+	}
+	let reports_vram = vram_total_mb > 0;
+
+	json!({
+		"adapter_count": adapters.len(),
+		"busiest_adapter_index": busiest.map(|(idx, _)| idx),
+		"max_usage_percent": busiest.map(|(_, usage)| usage),
+		"total_vram_used_mb": reports_vram.then_some(vram_used_mb),
+		"total_vram_total_mb": reports_vram.then_some(vram_total_mb),
+		"vram_usage_percent": reports_vram.then(|| vram_used_mb as f64 / vram_total_mb as f64 * 100.0),
 	})
 }
 
@@ -201,18 +259,202 @@ fn average_temp(sensors: &[Value]) -> f32 {
 	if count == 0 { 0.0 } else { sum / count as f32 }
 }
 
+/// Loose name match used to line up adapters reported by different tools.
+fn same_adapter_name(a: &str, b: &str) -> bool {
+	let (a, b) = (a.trim().to_lowercase(), b.trim().to_lowercase());
+	!a.is_empty() && !b.is_empty() && (a.contains(&b) || b.contains(&a))
+}
+
+/// `(description, luid)` for each DXGI adapter, with the LUID formatted the way
+/// the GPU performance counters print it (`0x<high>_0x<low>`).
+fn query_dxgi_adapter_luids() -> Vec<(String, String)> {
+	let mut result = Vec::new();
+	let Ok(factory) = (unsafe { CreateDXGIFactory1::<IDXGIFactory1>() }) else { return result };
+	let mut index = 0;
+	while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+		index += 1;
+		let Ok(desc) = (unsafe { adapter.GetDesc1() }) else { continue };
+		let len = desc.Description.iter().position(|&c| c == 0).unwrap_or(desc.Description.len());
+		let name = String::from_utf16_lossy(&desc.Description[..len]);
+		let luid = format!("0x{:08x}_0x{:08x}", desc.AdapterLuid.HighPart as u32, desc.AdapterLuid.LowPart);
+		result.push((name, luid));
+	}
+	result
+}
+
+/// Per-adapter figures from the GPU performance counters, keyed by adapter LUID.
+struct GpuCounters {
+	/// Top VRAM consumers from `GPU Process Memory` (the counters Task Manager uses).
+	processes: std::collections::HashMap<String, Vec<Value>>,
+	/// `{ engine_name, usage_percent }` per video engine from `GPU Engine`.
+	video_engines: std::collections::HashMap<String, Vec<Value>>,
+}
+
+/// Read both counter sets in one `Get-Counter` call. `None` when the counters
+/// are unavailable.
+fn query_gpu_counters(snapshot: &ProcessSnapshot) -> Option<GpuCounters> {
+	let script = r#"$ErrorActionPreference='SilentlyContinue';
+$samples = (Get-Counter '\GPU Process Memory(*)\Dedicated Usage','\GPU Engine(*)\Utilization Percentage').CounterSamples;
+if (-not $samples) { exit 1 }
+foreach ($s in $samples) {
+	if ($s.Path -like '*\gpu process memory(*') {
+		if ($s.InstanceName -match '^pid_(\d+)_luid_(0x[0-9a-f]+)_(0x[0-9a-f]+)') {
+			"mem|$($Matches[1])|$($Matches[2])_$($Matches[3])|$([uint64]$s.CookedValue)"
+		}
+	} elseif ($s.InstanceName -match '_luid_(0x[0-9a-f]+)_(0x[0-9a-f]+)_phys_(\d+)_eng_(\d+)_engtype_(video\w*)$') {
+		"eng|$($Matches[1])_$($Matches[2])|$($Matches[3])|$($Matches[4])|$($Matches[5])|$($s.CookedValue.ToString([System.Globalization.CultureInfo]::InvariantCulture))"
+	}
+}
+"#;
+
+	let output = Command::new("powershell")
+		.creation_flags(CREATE_NO_WINDOW)
+		.args(["-NoProfile", "-NonInteractive", "-Command", script])
+		.output()
+		.ok()?;
+	if !output.status.success() { return None }
+
+	// Memory: one instance per (pid, adapter, physical engine); sum them per pid.
+	let mut totals = std::collections::HashMap::<(String, u32), u64>::new();
+	// Engines: one instance per (pid, engine); sum them per engine.
+	let mut engines = std::collections::HashMap::<(String, u32, u32, String), f64>::new();
+	let text = String::from_utf8_lossy(&output.stdout);
+	for line in text.lines() {
+		let parts: Vec<&str> = line.trim().split('|').collect();
+		match parts.as_slice() {
+			["mem", pid, luid, bytes] => {
+				let (Ok(pid), Ok(bytes)) = (pid.parse::<u32>(), bytes.parse::<u64>()) else { continue };
+				let entry = totals.entry((luid.to_lowercase(), pid)).or_insert(0);
+				*entry = entry.saturating_add(bytes);
+			}
+			["eng", luid, phys, eng, engtype, usage] => {
+				let (Ok(phys), Ok(eng), Ok(usage)) = (phys.parse::<u32>(), eng.parse::<u32>(), usage.parse::<f64>()) else { continue };
+				if usage.is_finite() {
+					*engines.entry((luid.to_lowercase(), phys, eng, engtype.to_lowercase())).or_insert(0.0) += usage;
+				}
+			}
+			_ => {}
+		}
+	}
+
+	let mut by_luid = std::collections::HashMap::<String, Vec<(u32, u64, String)>>::new();
+	for ((luid, pid), bytes) in totals {
+		if bytes > 0 {
+			let name = snapshot.name(pid).unwrap_or("").to_string();
+			by_luid.entry(luid).or_default().push((pid, bytes, name));
+		}
+	}
+	let processes = by_luid.into_iter().map(|(luid, mut procs)| {
+		procs.sort_by(|a, b| b.1.cmp(&a.1));
+		procs.truncate(MAX_GPU_PROCESSES);
+		let list = procs.into_iter().map(|(pid, vram_bytes, name)| json!({
+			"pid": pid,
+			"name": name,
+			"vram_bytes": vram_bytes,
+		})).collect();
+		(luid, list)
+	}).collect();
+
+	Some(GpuCounters { processes, video_engines: video_engines_by_luid(engines) })
+}
+
+/// Name engines the way Task Manager does: the type plus its index among
+/// engines of that type on the adapter (`VideoDecode0`, `VideoDecode1`, ...).
+fn video_engines_by_luid(
+	engines: std::collections::HashMap<(String, u32, u32, String), f64>,
+) -> std::collections::HashMap<String, Vec<Value>> {
+	let mut sorted: Vec<_> = engines.into_iter().collect();
+	sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+	let mut by_luid = std::collections::HashMap::<String, Vec<Value>>::new();
+	let mut ordinals = std::collections::HashMap::<(String, String), usize>::new();
+	for ((luid, _phys, _eng, engtype), usage) in sorted {
+		let n = ordinals.entry((luid.clone(), engtype.clone())).or_insert(0);
+		let engine_name = format!("{}{}", engine_type_name(&engtype), n);
+		*n += 1;
+		by_luid.entry(luid).or_default().push(json!({
+			"engine_name": engine_name,
+			"usage_percent": (usage.clamp(0.0, 100.0) * 10.0).round() / 10.0,
+		}));
+	}
+	for list in by_luid.values_mut() {
+		list.sort_by(|a, b| a["engine_name"].as_str().cmp(&b["engine_name"].as_str()));
+	}
+	by_luid
+}
+
+/// Counter instance names come back lowercased; restore the display form.
+fn engine_type_name(engtype: &str) -> String {
+	match engtype {
+		"videodecode" => "VideoDecode".to_string(),
+		"videoencode" => "VideoEncode".to_string(),
+		"videoprocessing" => "VideoProcessing".to_string(),
+		other => {
+			let rest = other.strip_prefix("video").unwrap_or(other);
+			let mut chars = rest.chars();
+			match chars.next() {
+				Some(c) => format!("Video{}{}", c.to_ascii_uppercase(), chars.as_str()),
+				None => "Video".to_string(),
+			}
+		}
+	}
+}
+
+/// Add `luid`, the top VRAM consumers (`processes`) and per-engine
+/// `video_engines` to each adapter. Adapters are matched to DXGI by name;
+/// a field is left out if its counters aren't available.
+fn attach_gpu_processes(adapters: &mut [Value], snapshot: &ProcessSnapshot) {
+	let luids = query_dxgi_adapter_luids();
+	if luids.is_empty() { return }
+	let counters = query_gpu_counters(snapshot);
+
+	// Identical cards share a name, so each LUID is handed out once, in enumeration order.
+	let mut claimed = vec![false; luids.len()];
+	for adapter in adapters.iter_mut() {
+		let name = adapter.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
+		let Some(idx) = luids
+			.iter()
+			.enumerate()
+			.position(|(i, (desc, _))| !claimed[i] && same_adapter_name(desc, &name))
+		else { continue };
+		claimed[idx] = true;
+		let luid = &luids[idx].1;
+		let Some(obj) = adapter.as_object_mut() else { continue };
+		obj.insert("luid".into(), json!(luid));
+		if let Some(counters) = &counters {
+			let list = counters.processes.get(luid).cloned().unwrap_or_default();
+			obj.insert("processes".into(), Value::Array(list));
+			if let Some(engines) = counters.video_engines.get(luid).filter(|e| !e.is_empty()) {
+				obj.insert("video_engines".into(), Value::Array(engines.clone()));
+			}
+		}
+	}
+}
+
 /// Query nvidia-smi for comprehensive GPU stats.
 fn query_nvidia_smi_detailed() -> Vec<Value> {
+	let with_throttle = THROTTLE_FIELD_SUPPORTED.load(Ordering::Relaxed);
+	let query = if with_throttle {
+		format!("--query-gpu={},{}", NVIDIA_SMI_FIELDS, NVIDIA_SMI_THROTTLE_FIELD)
+	} else {
+		format!("--query-gpu={}", NVIDIA_SMI_FIELDS)
+	};
 	let output = run_nvidia_smi()
 		.creation_flags(CREATE_NO_WINDOW)
-		.args([
-			"--query-gpu=name,driver_version,utilization.gpu,utilization.memory,utilization.encoder,utilization.decoder,memory.total,memory.used,memory.free,temperature.gpu,power.draw,fan.speed,clocks.current.graphics,clocks.current.memory",
-			"--format=csv,noheader,nounits",
-		])
+		.args([query.as_str(), "--format=csv,noheader,nounits"])
 		.output();
 
 	let Ok(output) = output else { return Vec::new() };
-	if !output.status.success() { return Vec::new() }
+	if !output.status.success() {
+		let rejected = [&output.stdout, &output.stderr]
+			.iter()
+			.any(|out| String::from_utf8_lossy(out).contains(NVIDIA_SMI_THROTTLE_FIELD));
+		if with_throttle && rejected {
+			THROTTLE_FIELD_SUPPORTED.store(false, Ordering::Relaxed);
+			return query_nvidia_smi_detailed();
+		}
+		return Vec::new();
+	}
 
 	let text = String::from_utf8_lossy(&output.stdout);
 	text.lines()
@@ -246,12 +488,47 @@ fn query_nvidia_smi_detailed() -> Vec<Value> {
 				"fan_speed_percent": parse_f(parts[11]),
 				"clock_graphics_mhz": parse_u(parts[12]),
 				"clock_memory_mhz": parse_u(parts[13]),
+				"performance_limit": parts.get(14).and_then(|s| parse_hex(s)).map(performance_limit),
 				"source": "nvidia-smi",
 			}))
 		})
 		.collect()
 }
 
+fn parse_hex(s: &str) -> Option<u64> {
+	let s = s.trim();
+	u64::from_str_radix(s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"))?, 16).ok()
+}
+
+/// The main reason in an NVML clock throttle bitmask: "thermal", "power",
+/// "hardware" (an external slowdown signal), "sync_boost", "clock_setting"
+/// (application or display clocks), or "none" when nothing but idling holds
+/// the clocks down. NVML has no voltage reason.
+fn performance_limit(mask: u64) -> &'static str {
+	const APPLICATIONS_CLOCKS: u64 = 0x2;
+	const SW_POWER_CAP: u64 = 0x4;
+	const HW_SLOWDOWN: u64 = 0x8;
+	const SYNC_BOOST: u64 = 0x10;
+	const SW_THERMAL: u64 = 0x20;
+	const HW_THERMAL: u64 = 0x40;
+	const HW_POWER_BRAKE: u64 = 0x80;
+	const DISPLAY_CLOCKS: u64 = 0x100;
+
+	if mask & (SW_THERMAL | HW_THERMAL) != 0 {
+		"thermal"
+	} else if mask & (SW_POWER_CAP | HW_POWER_BRAKE) != 0 {
+		"power"
+	} else if mask & HW_SLOWDOWN != 0 {
+		"hardware"
+	} else if mask & SYNC_BOOST != 0 {
+		"sync_boost"
+	} else if mask & (APPLICATIONS_CLOCKS | DISPLAY_CLOCKS) != 0 {
+		"clock_setting"
+	} else {
+		"none"
+	}
+}
+
 fn query_wmi_video_controllers() -> Vec<Value> {
 	let script = r#"$ErrorActionPreference='SilentlyContinue';
 $gpus = Get-CimInstance Win32_VideoController;
@@ -1,26 +1,31 @@
 // ~/veil/veil-backend/src/ipc/sysdata/idle.rs
 
 use serde_json::{json, Value};
+#[cfg(target_os = "windows")]
 use std::mem;
 
+use crate::config;
+
 #[cfg(target_os = "windows")]
 use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
 
 pub fn get_idle_json() -> Value {
 	let idle_ms = get_idle_time_ms();
+	let (last_keyboard_ms, last_mouse_ms) = device_activity_ms();
 	let screen_locked = is_screen_locked();
 	let screensaver_active = is_screensaver_running();
 
 	let idle_seconds = idle_ms / 1000;
 	let idle_minutes = idle_seconds / 60;
+	let thresholds = config::idle_thresholds();
 
 	let idle_state = if screen_locked {
 		"locked"
 	} else if screensaver_active {
 		"screensaver"
-	} else if idle_minutes >= 15 {
+	} else if idle_seconds >= thresholds.away_secs {
 		"away"
-	} else if idle_minutes >= 5 {
+	} else if idle_seconds >= thresholds.idle_secs {
 		"idle"
 	} else {
 		"active"
@@ -33,6 +38,8 @@ pub fn get_idle_json() -> Value {
 		"idle_time_ms": idle_ms,
 		"idle_seconds": idle_seconds,
 		"idle_minutes": idle_minutes,
+		"last_keyboard_activity_ms": last_keyboard_ms,
+		"last_mouse_activity_ms": last_mouse_ms,
 		"idle_state": idle_state,
 		"is_idle": is_idle,
 		"screen_locked": screen_locked,
@@ -62,8 +69,24 @@ fn get_idle_time_ms() -> u64 {
 	0
 }
 
+/// Milliseconds since the last keyboard and mouse input, from the input hooks.
+/// `None` for a device with no input since the backend started.
+#[cfg(target_os = "windows")]
+fn device_activity_ms() -> (Option<u64>, Option<u64>) {
+	use super::input_hooks;
+	input_hooks::ensure_started();
+	let now = unsafe { windows::Win32::System::SystemInformation::GetTickCount() };
+	(input_hooks::ms_since_keyboard(now), input_hooks::ms_since_mouse(now))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn device_activity_ms() -> (Option<u64>, Option<u64>) {
+	(None, None)
+}
+
 /// Check if the screen is locked by looking for the LogonUI process.
 /// Uses Win32 process enumeration instead of spawning PowerShell.
+#[cfg(target_os = "windows")]
 fn is_screen_locked() -> bool {
 	use windows::Win32::System::ProcessStatus::EnumProcesses;
 	use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
@@ -109,6 +132,7 @@ fn is_screen_locked() -> bool {
 }
 
 /// Check if a screensaver is running by querying SystemParametersInfo.
+#[cfg(target_os = "windows")]
 fn is_screensaver_running() -> bool {
 	use windows::Win32::UI::WindowsAndMessaging::{
 		SystemParametersInfoW, SPI_GETSCREENSAVERRUNNING, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
@@ -123,4 +147,14 @@ fn is_screensaver_running() -> bool {
 		);
 		ok.is_ok() && running != 0
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_screen_locked() -> bool {
+	false
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_screensaver_running() -> bool {
+	false
+}
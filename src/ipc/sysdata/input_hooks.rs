@@ -0,0 +1,86 @@
+// ~/veil/veil-backend/src/ipc/sysdata/input_hooks.rs
+//
+// Low-level mouse and keyboard hooks on one message-loop thread. They record
+// only when input happened (and wheel distance), never which key or where,
+// for the counters polling can't see: wheel ticks and per-device idle time.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
+use windows::Win32::UI::WindowsAndMessaging::{
+	CallNextHookEx, GetMessageW, SetWindowsHookExW, KBDLLHOOKSTRUCT, MSG, MSLLHOOKSTRUCT,
+	WH_KEYBOARD_LL, WH_MOUSE_LL, WM_MOUSEHWHEEL, WM_MOUSEWHEEL,
+};
+
+use crate::warn;
+
+const WHEEL_DELTA: u64 = 120;
+
+/// Wheel movement in raw delta units, so high-resolution wheels add up exactly.
+static WHEEL_UNITS: AtomicU64 = AtomicU64::new(0);
+/// GetTickCount-based event times; 0 until the first event.
+static LAST_MOUSE_TICK: AtomicU32 = AtomicU32::new(0);
+static LAST_KEYBOARD_TICK: AtomicU32 = AtomicU32::new(0);
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+unsafe extern "system" fn mouse_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+	if code >= 0 {
+		let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+		LAST_MOUSE_TICK.store(info.time.max(1), Ordering::Relaxed);
+		if wparam.0 as u32 == WM_MOUSEWHEEL || wparam.0 as u32 == WM_MOUSEHWHEEL {
+			let delta = (info.mouseData >> 16) as u16 as i16;
+			WHEEL_UNITS.fetch_add(delta.unsigned_abs() as u64, Ordering::Relaxed);
+		}
+	}
+	CallNextHookEx(None, code, wparam, lparam)
+}
+
+unsafe extern "system" fn keyboard_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+	if code >= 0 {
+		let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+		LAST_KEYBOARD_TICK.store(info.time.max(1), Ordering::Relaxed);
+	}
+	CallNextHookEx(None, code, wparam, lparam)
+}
+
+/// Install both hooks on first use. Cheap to call every sample.
+pub fn ensure_started() {
+	if STARTED.swap(true, Ordering::SeqCst) {
+		return;
+	}
+	let spawned = std::thread::Builder::new().name("veil-input-hooks".into()).spawn(|| unsafe {
+		if SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook), None, 0).is_err() {
+			warn!("Failed to install mouse hook; wheel and mouse activity stay unknown");
+		}
+		if SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook), None, 0).is_err() {
+			warn!("Failed to install keyboard hook; keyboard activity stays unknown");
+		}
+		// Low-level hooks are called through this thread's message queue.
+		let mut msg = MSG::default();
+		while GetMessageW(&mut msg, None, 0, 0).as_bool() {}
+	});
+	if spawned.is_err() {
+		STARTED.store(false, Ordering::SeqCst);
+	}
+}
+
+/// Whole wheel notches (either direction) since the hooks were installed.
+pub fn wheel_ticks() -> u64 {
+	WHEEL_UNITS.load(Ordering::Relaxed) / WHEEL_DELTA
+}
+
+/// Milliseconds since the last mouse event, `None` if none was seen yet.
+pub fn ms_since_mouse(now_tick: u32) -> Option<u64> {
+	since(&LAST_MOUSE_TICK, now_tick)
+}
+
+/// Milliseconds since the last key event, `None` if none was seen yet.
+pub fn ms_since_keyboard(now_tick: u32) -> Option<u64> {
+	since(&LAST_KEYBOARD_TICK, now_tick)
+}
+
+fn since(tick: &AtomicU32, now_tick: u32) -> Option<u64> {
+	match tick.load(Ordering::Relaxed) {
+		0 => None,
+		t => Some(now_tick.wrapping_sub(t) as u64),
+	}
+}
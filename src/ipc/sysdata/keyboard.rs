@@ -1,12 +1,21 @@
 // ~/veil/veil-backend/src/ipc/sysdata/keyboard.rs
 
 use serde_json::{json, Value};
-use std::{collections::HashSet, sync::{OnceLock, RwLock}};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Mutex, OnceLock, RwLock},
+};
+use windows::core::{PCWSTR, PWSTR};
+use windows::Win32::System::Registry::{
+	RegCloseKey, RegEnumKeyExW, RegGetValueW, RegOpenKeyExW, HKEY, HKEY_LOCAL_MACHINE, KEY_READ,
+	RRF_RT_REG_SZ,
+};
 use windows::Win32::UI::Input::KeyboardAndMouse::{
 	GetAsyncKeyState,
-	GetKeyState, GetKeyboardLayoutNameW, GetKeyboardType,
+	GetKeyState, GetKeyboardLayout, GetKeyboardLayoutList, GetKeyboardType, HKL,
 	VK_CAPITAL, VK_NUMLOCK, VK_SCROLL, VK_INSERT,
 };
+use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
 
 static KEYBOARD_PRESSED: OnceLock<RwLock<HashSet<i32>>> = OnceLock::new();
 
@@ -14,6 +23,149 @@ fn keyboard_pressed() -> &'static RwLock<HashSet<i32>> {
 	KEYBOARD_PRESSED.get_or_init(|| RwLock::new(HashSet::new()))
 }
 
+const KEYBOARD_LAYOUTS_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\Keyboard Layouts";
+
+/// Installed layouts (rebuilt only when the HKL list changes) and the
+/// active-layout switch counter.
+struct LayoutState {
+	hkls: Vec<usize>,
+	installed: Vec<Value>,
+	active: Option<String>,
+	change_seq: u64,
+}
+
+static LAYOUT_STATE: OnceLock<Mutex<LayoutState>> = OnceLock::new();
+
+/// Layout variant id (`Layout Id` value) -> KLID, read once from the registry.
+static VARIANT_KLIDS: OnceLock<HashMap<u16, String>> = OnceLock::new();
+
+fn wide(s: &str) -> Vec<u16> {
+	s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// REG_SZ value under HKLM, or `None` if the key or value is missing.
+fn hklm_string(subkey: &str, value: &str) -> Option<String> {
+	let subkey = wide(subkey);
+	let value = wide(value);
+	let mut buf = [0u16; 256];
+	let mut size = (buf.len() * 2) as u32;
+	let status = unsafe {
+		RegGetValueW(
+			HKEY_LOCAL_MACHINE,
+			PCWSTR(subkey.as_ptr()),
+			PCWSTR(value.as_ptr()),
+			RRF_RT_REG_SZ,
+			None,
+			Some(buf.as_mut_ptr() as *mut _),
+			Some(&mut size),
+		)
+	};
+	if status.is_err() {
+		return None;
+	}
+	let len = (size as usize / 2).min(buf.len());
+	let text = String::from_utf16_lossy(&buf[..len]).trim_end_matches('\0').to_string();
+	(!text.is_empty()).then_some(text)
+}
+
+fn variant_klids() -> &'static HashMap<u16, String> {
+	VARIANT_KLIDS.get_or_init(|| {
+		let mut map = HashMap::new();
+		let root = wide(KEYBOARD_LAYOUTS_KEY);
+		let mut key = HKEY::default();
+		unsafe {
+			if RegOpenKeyExW(HKEY_LOCAL_MACHINE, PCWSTR(root.as_ptr()), None, KEY_READ, &mut key).is_err() {
+				return map;
+			}
+			let mut index = 0;
+			loop {
+				let mut name = [0u16; 16];
+				let mut len = name.len() as u32;
+				let status = RegEnumKeyExW(key, index, Some(PWSTR(name.as_mut_ptr())), &mut len, None, None, None, None);
+				if status.is_err() {
+					break;
+				}
+				index += 1;
+				let klid = String::from_utf16_lossy(&name[..len as usize]);
+				let variant = hklm_string(&format!("{}\\{}", KEYBOARD_LAYOUTS_KEY, klid), "Layout Id")
+					.and_then(|v| u16::from_str_radix(v.trim(), 16).ok());
+				if let Some(variant) = variant {
+					map.insert(variant, klid.to_uppercase());
+				}
+			}
+			let _ = RegCloseKey(key);
+		}
+		map
+	})
+}
+
+/// KLID string (as `GetKeyboardLayoutNameW` reports it) for an input locale handle.
+/// The high word is the physical layout: either a language id or, with the top
+/// nibble set, a layout variant that has to be looked up.
+fn hkl_to_klid(hkl: usize) -> String {
+	let device = ((hkl >> 16) & 0xFFFF) as u16;
+	if device & 0xF000 == 0xF000 {
+		if let Some(klid) = variant_klids().get(&(device & 0x0FFF)) {
+			return klid.clone();
+		}
+		return format!("{:08X}", hkl as u32);
+	}
+	format!("0000{:04X}", device)
+}
+
+fn layout_entry(hkl: usize) -> Value {
+	let id = hkl_to_klid(hkl);
+	let name = hklm_string(&format!("{}\\{}", KEYBOARD_LAYOUTS_KEY, id), "Layout Text");
+	json!({ "id": id, "name": name })
+}
+
+/// Layout of the foreground window's thread; the backend's own thread never
+/// sees the user switch languages.
+unsafe fn active_hkl() -> usize {
+	let hwnd = GetForegroundWindow();
+	let thread = if hwnd.0.is_null() { 0 } else { GetWindowThreadProcessId(hwnd, None) };
+	GetKeyboardLayout(thread).0 as usize
+}
+
+/// `(layout_id, installed_layouts, layout_change_seq)` for this sample.
+unsafe fn sample_layouts() -> (String, Vec<Value>, u64) {
+	let count = GetKeyboardLayoutList(None).max(0) as usize;
+	let mut list = vec![HKL::default(); count];
+	let filled = GetKeyboardLayoutList(Some(&mut list)).max(0) as usize;
+	let hkls: Vec<usize> = list[..filled.min(count)].iter().map(|h| h.0 as usize).collect();
+	let active_hkl = active_hkl();
+
+	let mut st = LAYOUT_STATE
+		.get_or_init(|| {
+			Mutex::new(LayoutState { hkls: Vec::new(), installed: Vec::new(), active: None, change_seq: 0 })
+		})
+		.lock()
+		.unwrap();
+
+	if st.hkls != hkls || st.installed.is_empty() {
+		let mut installed: Vec<Value> = Vec::new();
+		for hkl in &hkls {
+			let entry = layout_entry(*hkl);
+			if !installed.iter().any(|e| e["id"] == entry["id"]) {
+				installed.push(entry);
+			}
+		}
+		st.installed = installed;
+		st.hkls = hkls;
+	}
+
+	let active = hkl_to_klid(active_hkl);
+	if !st.installed.iter().any(|e| e["id"] == active.as_str()) {
+		st.installed.push(layout_entry(active_hkl));
+	}
+	if st.active.as_deref().is_some_and(|prev| prev != active) {
+		st.change_seq = st.change_seq.wrapping_add(1);
+	}
+	st.active = Some(active.clone());
+
+	(active, st.installed.clone(), st.change_seq)
+}
+
 const TRACKED_KEYS: &[(i32, &str)] = &[
 	(0x08, "Backspace"), (0x09, "Tab"), (0x0D, "Enter"), (0x10, "Shift"),
 	(0x11, "Control"), (0x12, "Alt"), (0x14, "CapsLock"), (0x1B, "Escape"),
@@ -45,15 +197,7 @@ pub fn get_keyboard_json() -> Value {
 		let keyboard_subtype = GetKeyboardType(1); // 1 = subtype
 		let num_function_keys = GetKeyboardType(2); // 2 = number of function keys
 
-		// Keyboard layout identifier (KL_NAMELENGTH = 9)
-		let mut layout_buf = [0u16; 9];
-		let layout_name = if GetKeyboardLayoutNameW(&mut layout_buf).is_ok() {
-			String::from_utf16_lossy(&layout_buf)
-				.trim_end_matches('\0')
-				.to_string()
-		} else {
-			"unknown".to_string()
-		};
+		let (layout_name, installed_layouts, layout_change_seq) = sample_layouts();
 
 		let type_name = match keyboard_type {
 			1 => "IBM PC/XT (83-key)",
@@ -98,6 +242,8 @@ pub fn get_keyboard_json() -> Value {
 
 		json!({
 			"layout_id": layout_name,
+			"installed_layouts": installed_layouts,
+			"layout_change_seq": layout_change_seq,
 			"type_name": type_name,
 			"type_id": keyboard_type,
 			"subtype": keyboard_subtype,
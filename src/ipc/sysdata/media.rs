@@ -46,7 +46,7 @@ struct MediaTimelineTracker {
 }
 
 fn media_session_cache() -> &'static RwLock<Value> {
-	MEDIA_SESSION_CACHE.get_or_init(|| RwLock::new(json!({ "playing": false })))
+	MEDIA_SESSION_CACHE.get_or_init(|| RwLock::new(no_session()))
 }
 
 fn media_timeline_tracker() -> &'static RwLock<MediaTimelineTracker> {
@@ -65,7 +65,11 @@ pub fn get_media_session_json() -> Value {
 	media_session_cache()
 		.read()
 		.map(|v| v.clone())
-		.unwrap_or_else(|_| json!({ "playing": false }))
+		.unwrap_or_else(|_| no_session())
+}
+
+fn no_session() -> Value {
+	json!({ "playing": false, "sessions": [] })
 }
 
 // ── worker thread ────────────────────────────────────────────
@@ -82,7 +86,7 @@ fn start_media_poller_once() {
 		loop {
 			let media = query_media_session();
 			if media.is_null() {
-				*media_session_cache().write().unwrap() = json!({ "playing": false });
+				*media_session_cache().write().unwrap() = no_session();
 			} else {
 				*media_session_cache().write().unwrap() = media;
 			}
@@ -114,6 +118,40 @@ fn wait_async<T: windows::core::RuntimeType>(
 	}
 }
 
+fn status_name(status: GlobalSystemMediaTransportControlsSessionPlaybackStatus) -> &'static str {
+	if status == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing {
+		"playing"
+	} else if status == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Paused {
+		"paused"
+	} else if status == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Stopped {
+		"stopped"
+	} else if status == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Opened {
+		"opened"
+	} else if status == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Closed {
+		"closed"
+	} else if status == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Changing {
+		"changing"
+	} else {
+		"unknown"
+	}
+}
+
+/// Short entry for the `sessions` list: which app, what it plays and its state.
+fn session_summary(session: &GlobalSystemMediaTransportControlsSession) -> Value {
+	let properties = session.TryGetMediaPropertiesAsync().ok().and_then(|op| wait_async(&op, 500));
+	let text = |s: windows::core::HSTRING| Some(s.to_string()).filter(|s| !s.is_empty());
+	json!({
+		"source_app_id": session.SourceAppUserModelId().ok().map(|s| s.to_string()),
+		"title": properties.as_ref().and_then(|p| p.Title().ok()).and_then(text),
+		"artist": properties.as_ref().and_then(|p| p.Artist().ok()).and_then(text),
+		"playback_status": session
+			.GetPlaybackInfo()
+			.ok()
+			.and_then(|info| info.PlaybackStatus().ok())
+			.map(status_name),
+	})
+}
+
 // ── base64 encoder ───────────────────────────────────────────
 
 const B64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
@@ -210,6 +248,7 @@ fn query_media_session() -> Value {
 	let sessions = manager.GetSessions().ok();
 	let mut chosen_session = current_session.clone();
 	let mut best_score: i32 = i32::MIN;
+	let mut session_list = Vec::new();
 
 	if let Some(current) = current_session.as_ref() {
 		let current_status = current
@@ -232,6 +271,7 @@ fn query_media_session() -> Value {
 				let Ok(candidate) = list.GetAt(i) else {
 					continue;
 				};
+				session_list.push(session_summary(&candidate));
 
 				let status = candidate
 					.GetPlaybackInfo()
@@ -313,7 +353,7 @@ fn query_media_session() -> Value {
 	let session: GlobalSystemMediaTransportControlsSession = match chosen_session {
 		Some(s) => s,
 		None => {
-			return json!({ "playing": false });
+			return no_session();
 		}
 	};
 
@@ -330,23 +370,7 @@ fn query_media_session() -> Value {
 		.as_ref()
 		.and_then(|info| info.PlaybackStatus().ok());
 
-	let status_str = status.map(|s| {
-		if s == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing {
-			"playing"
-		} else if s == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Paused {
-			"paused"
-		} else if s == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Stopped {
-			"stopped"
-		} else if s == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Opened {
-			"opened"
-		} else if s == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Closed {
-			"closed"
-		} else if s == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Changing {
-			"changing"
-		} else {
-			"unknown"
-		}
-	});
+	let status_str = status.map(status_name);
 
 	let is_playing = status
 		.map(|s| s == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing)
@@ -413,6 +437,22 @@ fn query_media_session() -> Value {
 		.SourceAppUserModelId()
 		.ok()
 		.map(|s| s.to_string());
+	for entry in &mut session_list {
+		let active = entry["source_app_id"].as_str() == source_app_id.as_deref();
+		entry["active"] = json!(active);
+	}
+
+	// Which `media.control` actions the app accepts right now.
+	let controls = playback_info.as_ref().and_then(|info| info.Controls().ok()).map(|c| {
+		json!({
+			"play": c.IsPlayEnabled().unwrap_or(false),
+			"pause": c.IsPauseEnabled().unwrap_or(false),
+			"next": c.IsNextEnabled().unwrap_or(false),
+			"previous": c.IsPreviousEnabled().unwrap_or(false),
+			"stop": c.IsStopEnabled().unwrap_or(false),
+			"seek": c.IsPlaybackPositionEnabled().unwrap_or(false),
+		})
+	});
 
 	// Timeline (units are 100-nanosecond intervals → ms)
 	let raw_position_ms = timeline
@@ -527,11 +567,72 @@ fn query_media_session() -> Value {
 		"shuffle": is_shuffle,
 		"repeat_mode": auto_repeat,
 		"thumbnail": thumbnail_data_url,
+		"controls": controls,
 		"timeline": {
 			"position_ms": position_ms,
 			"start_ms": start_ms,
 			"end_ms": end_ms,
 			"duration_ms": duration_ms,
-		}
+		},
+		"sessions": session_list,
 	})
 }
+
+// ── transport control ────────────────────────────────────────
+
+/// `media.control`: run `action` (play, pause, toggle, next, previous, stop
+/// or seek) on the session of `source_app_id`, or on the active session when
+/// none is given. `position_ms` is the seek target from the track start.
+pub fn control(action: &str, source_app_id: Option<&str>, position_ms: Option<i64>) -> Result<Value, String> {
+	unsafe {
+		let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+	}
+	let manager = GlobalSystemMediaTransportControlsSessionManager::RequestAsync()
+		.ok()
+		.and_then(|op| wait_async(&op, 3000))
+		.ok_or("The media session manager is unavailable")?;
+
+	// Default to the session the Data page and `get_media` show as active.
+	let cached = get_media_session_json();
+	let target = source_app_id.or_else(|| cached["source_app_id"].as_str()).map(str::to_string);
+	let session = match target.as_deref() {
+		Some(app) => manager.GetSessions().ok().and_then(|list| {
+			(0..list.Size().unwrap_or(0))
+				.filter_map(|i| list.GetAt(i).ok())
+				.find(|s| s.SourceAppUserModelId().map(|id| id.to_string() == app).unwrap_or(false))
+		}),
+		None => manager.GetCurrentSession().ok(),
+	}
+	.ok_or_else(|| match target.as_deref() {
+		Some(app) => format!("No media session for {}", app),
+		None => "No active media session".to_string(),
+	})?;
+
+	let op = match action {
+		"play" => session.TryPlayAsync(),
+		"pause" => session.TryPauseAsync(),
+		"toggle" => session.TryTogglePlayPauseAsync(),
+		"next" => session.TrySkipNextAsync(),
+		"previous" => session.TrySkipPreviousAsync(),
+		"stop" => session.TryStopAsync(),
+		"seek" => {
+			let position_ms = position_ms.ok_or("seek needs 'position_ms'")?.max(0);
+			let start_ms = session
+				.GetTimelineProperties()
+				.ok()
+				.and_then(|t| t.StartTime().ok())
+				.map(|d| d.Duration / 10_000)
+				.unwrap_or(0);
+			session.TryChangePlaybackPositionAsync((start_ms + position_ms) * 10_000)
+		}
+		_ => return Err(format!("Unknown media action '{}'", action)),
+	}
+	.map_err(|e| format!("{} failed: {}", action, e))?;
+
+	let app = session.SourceAppUserModelId().map(|s| s.to_string()).unwrap_or_default();
+	match wait_async(&op, 2000) {
+		Some(true) => Ok(json!({ "action": action, "source_app_id": app })),
+		Some(false) => Err(format!("{} doesn't accept '{}' right now", app, action)),
+		None => Err(format!("{} didn't answer '{}' in time", app, action)),
+	}
+}
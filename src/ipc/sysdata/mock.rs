@@ -0,0 +1,987 @@
+// ~/veil/veil-backend/src/ipc/sysdata/mock.rs
+//
+// Deterministic synthetic collectors for CI and UI work without real hardware.
+// Enabled by building with `--features mock` or running with `VEIL_MOCK=1`.
+//
+// Every section mirrors the JSON shape of its real collector. Values are pure
+// functions of a per-section sample counter, so two runs produce the same
+// sequence while the UI still sees data moving.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use crate::ipc::appdata::window::{ActiveWindowInfo, WindowPosition, WindowSize};
+use crate::ipc::registry::RegistryEntry;
+use crate::ipc::sysdata::display::{self, MonitorInfo};
+use crate::ipc::sysdata::processes;
+
+pub const MOCK_ENV: &str = "VEIL_MOCK";
+
+/// Fixed "boot" instant used for uptime/boot-time fields (2024-01-01T00:00:00Z).
+const MOCK_BOOT_UNIX: u64 = 1_704_067_200;
+
+const GIB: u64 = 1024 * 1024 * 1024;
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+static SAMPLES: OnceLock<Mutex<HashMap<&'static str, u64>>> = OnceLock::new();
+
+/// Whether collectors should be replaced by mock data.
+pub fn mock_enabled() -> bool {
+	*ENABLED.get_or_init(|| {
+		cfg!(feature = "mock")
+			|| std::env::var(MOCK_ENV)
+				.map(|v| matches!(v.trim(), "1" | "true" | "yes"))
+				.unwrap_or(false)
+	})
+}
+
+/// Advance and return the sample index for a section.
+fn next_sample(section: &'static str) -> u64 {
+	let mut map = SAMPLES
+		.get_or_init(|| Mutex::new(HashMap::new()))
+		.lock()
+		.unwrap();
+	let n = map.entry(section).or_insert(0);
+	let current = *n;
+	*n += 1;
+	current
+}
+
+/// Restart the mock "data used this session" counters.
+pub fn reset_network_session() {
+	if let Some(samples) = SAMPLES.get() {
+		samples.lock().unwrap().remove("network_session");
+	}
+}
+
+/// Canned result for the `wifi.scan` command.
+pub fn mock_wifi_scan() -> Value {
+	let n = next_sample("wifi_scan");
+	json!({
+		"status": "ok",
+		"available": [
+			{ "ssid": "MockNet", "bssid": "02:00:00:00:00:aa", "signal_percent": wave(n, 30, 60.0, 95.0) as u64, "channel": 36, "band": "5 GHz", "security": "WPA2-Personal" },
+			{ "ssid": "MockNet", "bssid": "02:00:00:00:00:ab", "signal_percent": 54, "channel": 6, "band": "2.4 GHz", "security": "WPA2-Personal" },
+			{ "ssid": "Neighbour", "bssid": "02:00:00:00:01:10", "signal_percent": 31, "channel": 11, "band": "2.4 GHz", "security": "WPA3-Personal" },
+			{ "ssid": Value::Null, "bssid": "02:00:00:00:02:20", "signal_percent": 12, "channel": 149, "band": "5 GHz", "security": "Open" },
+		],
+		"cached": false,
+		"scanned_at_unix": MOCK_BOOT_UNIX + n,
+	})
+}
+
+/// Smooth deterministic wave in `[lo, hi]`.
+fn wave(n: u64, period: u64, lo: f64, hi: f64) -> f64 {
+	let phase = (n % period) as f64 / period as f64 * std::f64::consts::TAU;
+	let t = (phase.sin() + 1.0) / 2.0;
+	((lo + (hi - lo) * t) * 10.0).round() / 10.0
+}
+
+/// Endpoints for `audio.list_devices`, matching the mock `audio` section.
+pub fn mock_audio_devices() -> Value {
+	json!({
+		"devices": [
+			{ "id": "{0.0.0.00000000}.{mock-speakers}", "name": "Mock Speakers", "direction": "output", "default": true, "default_communications": false },
+			{ "id": "{0.0.0.00000000}.{mock-headset}", "name": "Mock Headset", "direction": "output", "default": false, "default_communications": true },
+			{ "id": "{0.0.1.00000000}.{mock-microphone}", "name": "Mock Microphone", "direction": "input", "default": true, "default_communications": true },
+		],
+	})
+}
+
+/// Mock metadata for a sysdata category (`"display"` is handled by `mock_monitors`).
+pub fn mock_sysdata(category: &str) -> Option<Value> {
+	let value = match category {
+		"cpu" => cpu(next_sample("cpu")),
+		"gpu" => gpu(next_sample("gpu")),
+		"ram" => ram(next_sample("ram")),
+		"storage" => storage(),
+		"network" => network(next_sample("network")),
+		"audio" => audio(next_sample("audio")),
+		"media" => media(next_sample("media")),
+		"time" => time(next_sample("time")),
+		"keyboard" => keyboard(),
+		"mouse" => mouse(next_sample("mouse")),
+		"power" => power(next_sample("power")),
+		"idle" => idle(next_sample("idle")),
+		"bluetooth" => bluetooth(),
+		"wifi" => wifi(next_sample("wifi")),
+		"system" => system(next_sample("system")),
+		"processes" => processes(next_sample("processes")),
+		_ => return None,
+	};
+	Some(value)
+}
+
+fn cpu(n: u64) -> Value {
+	let per_core: Vec<Value> = (0..8)
+		.map(|i| json!({
+			"core_id": i,
+			"usage_percent": wave(n + i * 3, 40, 5.0, 85.0),
+			"frequency_mhz": 3600 + (i * 50),
+		}))
+		.collect();
+	let per_core_usage: Vec<f64> = (0..8).map(|i| wave(n + i * 3, 40, 5.0, 85.0)).collect();
+
+	json!({
+		"brand": "Mock CPU 8-Core Processor",
+		"vendor_id": "MockVendor",
+		"arch": "x86_64",
+		"logical_cores": 8,
+		"physical_cores": 4,
+		"usage_percent": wave(n, 40, 10.0, 70.0),
+		"frequency_mhz": 3775,
+		"base_frequency_mhz": 3600,
+		"max_frequency_mhz": 4800,
+		"sockets": 1,
+		"virtualization": "Enabled",
+		"l1_cache_kb": 256,
+		"l2_cache_kb": 4096,
+		"l3_cache_kb": 16384,
+		"thread_count": 2400,
+		"handle_count": 98000,
+		"temperature": {
+			"average_c": wave(n, 60, 45.0, 65.0),
+			"sensors": [{
+				"label": "CPU Package",
+				"temperature_c": wave(n, 60, 45.0, 65.0),
+				"max_c": 90.0,
+				"critical_c": 100.0,
+				"source": "mock",
+			}],
+		},
+		"thermal_throttling": false,
+		"per_core": per_core,
+		"per_core_usage": per_core_usage,
+		"uptime_seconds": 3600 + n,
+		"boot_time_unix": MOCK_BOOT_UNIX,
+		"process_count": 180,
+	})
+}
+
+fn gpu(n: u64) -> Value {
+	let temp = wave(n, 60, 40.0, 70.0);
+	// Shows the throttling tag at the top of the temperature wave.
+	let performance_limit = if temp > 67.0 { "thermal" } else { "none" };
+	let sensors = json!([{
+		"label": "Mock GPU",
+		"temperature_c": temp,
+		"source": "mock",
+	}]);
+	let video_engines = json!([
+		{ "engine_name": "VideoDecode0", "usage_percent": wave(n, 45, 0.0, 40.0) },
+		{ "engine_name": "VideoEncode0", "usage_percent": 0.0 },
+		{ "engine_name": "VideoProcessing0", "usage_percent": 0.0 },
+	]);
+
+	json!({
+		"detected": true,
+		"name": "Mock GPU 8GB",
+		"usage_percent": wave(n, 30, 0.0, 95.0),
+		"vram_total_mb": 8192,
+		"vram_used_mb": 2048,
+		"vram_free_mb": 6144,
+		"memory_usage_percent": 25.0,
+		"shared_gpu_memory_bytes": 16 * GIB,
+		"driver_version": "1.0.0-mock",
+		"driver_date": "2024-01-01",
+		"manufacturer": "MockVendor",
+		"physical_location": {
+			"bus": 1,
+			"device": 0,
+			"function": 0,
+			"description": "PCI bus 1, device 0, function 0",
+		},
+		"temperature_c": temp,
+		"power_draw_w": wave(n, 30, 20.0, 180.0),
+		"fan_speed_percent": 35.0,
+		"encoder_usage_percent": 0.0,
+		"decoder_usage_percent": 0.0,
+		"clock_graphics_mhz": 1800,
+		"clock_memory_mhz": 7000,
+		"performance_limit": performance_limit,
+		"summary": {
+			"adapter_count": 1,
+			"busiest_adapter_index": 0,
+			"max_usage_percent": wave(n, 30, 0.0, 95.0),
+			"total_vram_used_mb": 2048,
+			"total_vram_total_mb": 8192,
+			"vram_usage_percent": 25.0,
+		},
+		"adapters": [{
+			"name": "Mock GPU 8GB",
+			"adapter_ram_bytes": 8 * GIB,
+			"driver_version": "1.0.0-mock",
+			"driver_date": "2024-01-01",
+			"manufacturer": "MockVendor",
+			"video_processor": "Mock GPU",
+			"current_resolution": "2560x1440",
+			"current_refresh_rate_hz": 144,
+			"status": "OK",
+			"physical_location": {
+				"bus": 1,
+				"device": 0,
+				"function": 0,
+				"description": "PCI bus 1, device 0, function 0",
+			},
+			"performance_limit": performance_limit,
+			"source": "mock",
+			"shared_gpu_memory_bytes": 16 * GIB,
+			"luid": "0x00000000_0x0000c5a2",
+			"processes": [
+				{ "pid": 4120, "name": "chrome", "vram_bytes": 900 * 1024 * 1024 },
+				{ "pid": 2216, "name": "dwm", "vram_bytes": 350 * 1024 * 1024 },
+				{ "pid": 8840, "name": "explorer", "vram_bytes": 96 * 1024 * 1024 },
+			],
+			"video_engines": video_engines.clone(),
+		}],
+		"video_engines": video_engines,
+		"temperature": {
+			"average_c": temp,
+			"sensors": sensors.clone(),
+		},
+		"sensors": sensors,
+	})
+}
+
+fn ram(n: u64) -> Value {
+	let total = 32 * GIB;
+	let used = (wave(n, 50, 35.0, 60.0) / 100.0 * total as f64) as u64;
+	let available = total - used;
+	let swap_total = 8 * GIB;
+	let swap_used = GIB;
+
+	let stick = |slot: u32| json!({
+		"capacity_bytes": 16 * GIB,
+		"speed_mhz": 3200,
+		"configured_speed_mhz": 3200,
+		"form_factor": "DIMM",
+		"memory_type": "DDR4",
+		"manufacturer": "MockMem",
+		"part_number": "MOCK-16G",
+		"serial_number": format!("0000000{}", slot),
+		"bank_label": format!("BANK {}", slot),
+		"device_locator": format!("DIMM {}", slot),
+		"data_width_bits": 64,
+		"total_width_bits": 64,
+	});
+
+	json!({
+		"total_bytes": total,
+		"used_bytes": used,
+		"available_bytes": available,
+		"standby_bytes": available / 2,
+		"modified_bytes": 256 * 1024 * 1024,
+		"free_bytes": available - available / 2,
+		"usage_percent": used as f64 / total as f64 * 100.0,
+		"swap_total_bytes": swap_total,
+		"swap_used_bytes": swap_used,
+		"swap_free_bytes": swap_total - swap_used,
+		"swap_usage_percent": swap_used as f64 / swap_total as f64 * 100.0,
+		"speed_mhz": 3200,
+		"form_factor": "DIMM",
+		"slots_used": 2,
+		"slots_total": 4,
+		"memory_type": "DDR4",
+		"sticks": [stick(0), stick(1)],
+		"hardware_reserved_bytes": 128 * 1024 * 1024,
+		"committed_bytes": used + swap_used,
+		"commit_limit_bytes": total + swap_total,
+		"cached_bytes": 4 * GIB,
+		"paged_pool_bytes": 512 * 1024 * 1024,
+		"non_paged_pool_bytes": 256 * 1024 * 1024,
+		"compressed_bytes": 300 * 1024 * 1024,
+		"top_processes": mock_memory_processes(),
+	})
+}
+
+fn storage() -> Value {
+	let total = 1024 * GIB;
+	let available = 400 * GIB;
+	let used = total - available;
+
+	let physical = json!({
+		"model": "Mock NVMe SSD 1TB",
+		"media_type": "SSD",
+		"bus_type": "NVMe",
+		"serial_number": "MOCK0001",
+		"firmware_version": "1.0",
+		"capacity_bytes": total,
+		"health_status": "Healthy",
+		"disk_number": 0,
+		"drive_letters": ["C"],
+		"system_disk": true,
+		"page_file_disk": true,
+		"smart": {
+			"temperature_c": 38,
+			"power_on_hours": 4210,
+			"reallocated_sectors": Value::Null,
+			"media_errors": 0,
+			"wear_leveling_percent": 97,
+			"source": "mock",
+		},
+	});
+	let smart = physical["smart"].clone();
+
+	json!({
+		"total_bytes": total,
+		"available_bytes": available,
+		"used_bytes": used,
+		"usage_percent": used as f64 / total as f64 * 100.0,
+		"disk_count": 1,
+		"physical_disks": [physical],
+		"disks": [{
+			"name": "System",
+			"mount": "C:\\",
+			"kind": "SSD",
+			"file_system": "NTFS",
+			"removable": false,
+			"total_bytes": total,
+			"available_bytes": available,
+			"used_bytes": used,
+			"usage_percent": used as f64 / total as f64 * 100.0,
+			"model": "Mock NVMe SSD 1TB",
+			"media_type": "SSD",
+			"bus_type": "NVMe",
+			"serial_number": "MOCK0001",
+			"firmware_version": "1.0",
+			"disk_number": 0,
+			"physical_capacity_bytes": total,
+			"system_disk": true,
+			"page_file_disk": true,
+			"health_status": "Healthy",
+			"smart": smart,
+		}],
+	})
+}
+
+fn network(n: u64) -> Value {
+	let rx = (wave(n, 20, 10.0, 500.0) * 1024.0) as u64;
+	let tx = (wave(n + 5, 20, 5.0, 120.0) * 1024.0) as u64;
+	let total_rx = 5 * GIB + n * rx;
+	let total_tx = GIB + n * tx;
+	let session = next_sample("network_session");
+	let session_rx = session * rx;
+	let session_tx = session * tx;
+
+	json!({
+		"received_bytes": rx,
+		"transmitted_bytes": tx,
+		"total_received_bytes": total_rx,
+		"total_transmitted_bytes": total_tx,
+		"session_received_bytes": session_rx,
+		"session_transmitted_bytes": session_tx,
+		"received_bytes_per_second": rx as f64,
+		"transmitted_bytes_per_second": tx as f64,
+		"total_packets_received": total_rx / 1200,
+		"total_packets_transmitted": total_tx / 1200,
+		"total_errors_received": 0,
+		"total_errors_transmitted": 0,
+		"interface_count": 1,
+		"interfaces": [{
+			"interface": "Ethernet",
+			"description": "Mock Ethernet Adapter",
+			"mac_address": "02:00:00:00:00:01",
+			"ip_addresses": [
+				{ "addr": "192.168.1.100", "prefix": 24 },
+				{ "addr": "fe80::1", "prefix": 64 },
+			],
+			"link_speed": "1 Gbps",
+			"media_type": "802.3",
+			"adapter_status": "Up",
+			"media_connection_state": "Connected",
+			"driver_version": "1.0.0",
+			"driver_provider": "MockVendor",
+			"metered": false,
+			"link_up": true,
+			"up_since_ms": MOCK_BOOT_UNIX * 1000,
+			"received_bytes": rx,
+			"transmitted_bytes": tx,
+			"total_received_bytes": total_rx,
+			"total_transmitted_bytes": total_tx,
+			"session_received_bytes": session_rx,
+			"session_transmitted_bytes": session_tx,
+			"received_bytes_per_second": rx as f64,
+			"transmitted_bytes_per_second": tx as f64,
+			"packets": {
+				"received": rx / 1200,
+				"transmitted": tx / 1200,
+				"total_received": total_rx / 1200,
+				"total_transmitted": total_tx / 1200,
+			},
+			"errors": {
+				"received": 0,
+				"transmitted": 0,
+				"total_received": 0,
+				"total_transmitted": 0,
+			},
+		}],
+		"link_change_seq": 0,
+		"default_route_interface": "Ethernet",
+		"connectivity": crate::config::connectivity_check().then(|| json!({
+			"internet": true,
+			"checked_at_ms": (MOCK_BOOT_UNIX + n) * 1000,
+		})),
+		"top_talkers": [
+			{ "pid": 4120, "name": "chrome.exe", "sent_bps": tx as f64 * 0.2, "recv_bps": rx as f64 * 0.7 },
+			{ "pid": 6012, "name": "steam.exe", "sent_bps": tx as f64 * 0.05, "recv_bps": rx as f64 * 0.2 },
+		],
+		// The mock adapter isn't metered, so the cap never fills up.
+		"data_cap": {
+			"month": chrono::Local::now().format("%Y-%m").to_string(),
+			"used_bytes": 0,
+			"cap_bytes": Value::Null,
+			"used_percent": Value::Null,
+			"warn_percent": crate::config::data_cap_warn_percent(),
+			"warning": false,
+			"warned_this_period": false,
+			"warned_at_ms": Value::Null,
+		},
+		"events": {
+			"data_cap_warning": false,
+		},
+	})
+}
+
+fn audio(n: u64) -> Value {
+	let peak = wave(n, 16, 0.0, 1.0);
+	let rms = (peak * 0.7 * 100.0).round() / 100.0;
+	let to_db = |v: f64| if v <= 0.0 { -100.0 } else { 20.0 * v.log10() };
+	let peak_history: Vec<f64> = (0..32).map(|i| wave(n + i, 16, 0.0, 1.0)).collect();
+	let spectrum: Vec<f64> = (0..32).map(|i| wave(n + i * 2, 24, 0.0, 1.0)).collect();
+
+	json!({
+		"output_device": {
+			"name": "Mock Speakers",
+			"volume_percent": 50.0,
+			"muted": false,
+			"audio_level": peak,
+			"levels": {
+				"peak": peak,
+				"peak_db": to_db(peak),
+				"rms": rms,
+				"rms_db": to_db(rms),
+				"smoothed_peak": peak,
+				"smoothed_peak_db": to_db(peak),
+				"smoothed_rms": rms,
+				"smoothed_rms_db": to_db(rms)
+			},
+			"history": {
+				"peak_32": peak_history,
+				"sample_count": 32
+			}
+		},
+		"input_device": {
+			"name": "Mock Microphone",
+			"volume_percent": 80.0,
+			"muted": false,
+		},
+		"sessions": [
+			{ "pid": 4120, "name": "chrome.exe", "system_sounds": false, "volume_percent": 100.0, "muted": false, "peak_level": peak },
+			{ "pid": 0, "name": "System Sounds", "system_sounds": true, "volume_percent": 80.0, "muted": false, "peak_level": 0.0 },
+		],
+		"media_session": media(n),
+		"spectrum_32": spectrum,
+	})
+}
+
+fn media(n: u64) -> Value {
+	let duration_ms: u64 = 180_000;
+	json!({
+		"playing": true,
+		"source_app_id": "Mock.Player",
+		"title": "Mock Track",
+		"artist": "Mock Artist",
+		"album": "Mock Album",
+		"album_artist": "Mock Artist",
+		"track_number": 1,
+		"album_track_count": 10,
+		"genres": ["Electronic"],
+		"playback_type": "Music",
+		"playback_status": "Playing",
+		"playback_rate": 1.0,
+		"shuffle": false,
+		"repeat_mode": "None",
+		"thumbnail": Value::Null,
+		"controls": { "play": false, "pause": true, "next": true, "previous": true, "stop": true, "seek": true },
+		"timeline": {
+			"position_ms": (n * 1000) % duration_ms,
+			"start_ms": 0,
+			"end_ms": duration_ms,
+			"duration_ms": duration_ms,
+		},
+		"sessions": [
+			{ "source_app_id": "Mock.Player", "title": "Mock Track", "artist": "Mock Artist", "playback_status": "playing", "active": true },
+			{ "source_app_id": "Mock.Browser", "title": "Mock Video", "artist": Value::Null, "playback_status": "paused", "active": false },
+		],
+	})
+}
+
+/// `media.control` against the mock sessions: checks the arguments, changes nothing.
+pub fn mock_media_control(action: &str, source_app_id: Option<&str>, position_ms: Option<i64>) -> Result<Value, String> {
+	let app = source_app_id.unwrap_or("Mock.Player");
+	if !["Mock.Player", "Mock.Browser"].contains(&app) {
+		return Err(format!("No media session for {}", app));
+	}
+	match action {
+		"play" | "pause" | "toggle" | "next" | "previous" | "stop" => {}
+		"seek" if position_ms.is_some() => {}
+		"seek" => return Err("seek needs 'position_ms'".to_string()),
+		_ => return Err(format!("Unknown media action '{}'", action)),
+	}
+	Ok(json!({ "action": action, "source_app_id": app }))
+}
+
+fn time(n: u64) -> Value {
+	use chrono::{DateTime, Datelike, Timelike, Utc};
+
+	let unix = MOCK_BOOT_UNIX as i64 + 12 * 3600 + n as i64;
+	let now = DateTime::<Utc>::from_timestamp(unix, 0).unwrap_or_default();
+	let date = now.date_naive();
+	let hour_12 = {
+		let h = now.hour() % 12;
+		if h == 0 { 12 } else { h }
+	};
+
+	json!({
+		"ok": true,
+		"iso": now.to_rfc3339(),
+		"utc_iso": now.to_rfc3339(),
+		"unix": unix,
+		"unix_ms": unix * 1000,
+		"year": date.year(),
+		"month": date.month(),
+		"day": date.day(),
+		"weekday": format!("{:?}", now.weekday()),
+		"day_of_year": date.ordinal(),
+		"iso_week": date.iso_week().week(),
+		"quarter": ((date.month() - 1) / 3) + 1,
+		"is_leap_year": date.leap_year(),
+		"hour": now.hour(),
+		"minute": now.minute(),
+		"second": now.second(),
+		"millisecond": 0,
+		"timezone": "+00:00",
+		"utc_offset_seconds": 0,
+		"utc_offset_hours": 0.0,
+		"uptime_seconds": unix as u64 - MOCK_BOOT_UNIX,
+		"boot_time_unix": MOCK_BOOT_UNIX,
+		"human": now.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+		"human_date": now.format("%Y-%m-%d").to_string(),
+		"date_formatted": now.format("%Y-%m-%d").to_string(),
+		"human_time": now.format("%H:%M:%S").to_string(),
+		"am_pm": if now.hour() < 12 { "AM" } else { "PM" },
+		"hour_12": hour_12,
+		"ntp_synchronized": true,
+		"last_sync_time": "1/1/2024 12:00:00 AM",
+		"source": "time.windows.com,0x9",
+	})
+}
+
+fn keyboard() -> Value {
+	json!({
+		"layout_id": "00000409",
+		"installed_layouts": [
+			{ "id": "00000409", "name": "US" },
+			{ "id": "00000407", "name": "German" },
+		],
+		"layout_change_seq": 0,
+		"type_name": "IBM Enhanced (101/102-key)",
+		"type_id": 4,
+		"subtype": 0,
+		"function_key_count": 12,
+		"toggle_states": {
+			"caps_lock": false,
+			"num_lock": true,
+			"scroll_lock": false,
+			"insert": false,
+		},
+		"pressed_keys": Vec::<String>::new(),
+		"pressed_count": 0,
+		"events": {
+			"down": Vec::<String>::new(),
+			"up": Vec::<String>::new(),
+		}
+	})
+}
+
+fn mouse(n: u64) -> Value {
+	json!({
+		"present": true,
+		"cursor": {
+			"x": wave(n, 100, 0.0, 2559.0) as i32,
+			"y": wave(n + 25, 100, 0.0, 1439.0) as i32,
+		},
+		"buttons": {
+			"count": 5,
+			"swapped": false,
+			"left_down": false,
+			"right_down": false,
+			"middle_down": false,
+			"left_clicks": n / 10,
+			"right_clicks": n / 50,
+			"middle_clicks": 0,
+		},
+		"events": {
+			"clicked": Vec::<String>::new(),
+		},
+		"wheel_present": true,
+		"movement_px_since_boot": n * 40,
+		"wheel_ticks_since_boot": n / 5,
+		"velocity_px_per_sec": wave(n, 20, 0.0, 1200.0),
+		"speed": 10,
+		"screen": {
+			"primary_width": 2560,
+			"primary_height": 1440,
+			"virtual_width": 2560,
+			"virtual_height": 1440,
+			"virtual_x": 0,
+			"virtual_y": 0,
+			"monitor_count": 1,
+		}
+	})
+}
+
+fn power(n: u64) -> Value {
+	let percent = 100 - (n / 60 % 60);
+	json!({
+		"ac_status": "offline",
+		"battery": {
+			"present": true,
+			"percent": percent,
+			"charging": false,
+			"critical": percent < 5,
+			"low": percent < 33,
+			"high": percent > 66,
+			"lifetime_seconds": percent * 120,
+			"fulllife_seconds": 12000,
+			"saver_active": false,
+			"design_capacity_mwh": 60000,
+			"full_charge_capacity_mwh": 54000,
+			"health_percent": 90.0,
+			"cycle_count": 212,
+			"details": {
+				"name": "Mock Battery",
+				"device_id": "MOCK-BAT0",
+				"design_capacity_mwh": 60000,
+				"full_charge_capacity_mwh": 54000,
+				"health_percent": 90.0,
+				"design_voltage_mv": 11400,
+				"chemistry": "Lithium Ion",
+				"status": "OK",
+				"estimated_charge_percent": percent,
+				"estimated_runtime_minutes": percent * 2,
+				"battery_status_code": 1,
+			},
+		},
+		"power_plan": "Balanced",
+	})
+}
+
+fn idle(n: u64) -> Value {
+	let idle_ms = (n % 600) * 1000;
+	let idle_seconds = idle_ms / 1000;
+	let idle_minutes = idle_seconds / 60;
+	let thresholds = crate::config::idle_thresholds();
+	let idle_state = if idle_seconds >= thresholds.away_secs {
+		"away"
+	} else if idle_seconds >= thresholds.idle_secs {
+		"idle"
+	} else {
+		"active"
+	};
+
+	json!({
+		"idle_ms": idle_ms,
+		"idle_time_ms": idle_ms,
+		"idle_seconds": idle_seconds,
+		"idle_minutes": idle_minutes,
+		"last_keyboard_activity_ms": idle_ms,
+		"last_mouse_activity_ms": idle_ms + 4000,
+		"idle_state": idle_state,
+		"is_idle": idle_state != "active",
+		"screen_locked": false,
+		"screensaver_active": false,
+	})
+}
+
+fn bluetooth() -> Value {
+	json!({
+		"adapter": {
+			"present": true,
+			"name": "Mock Bluetooth Adapter",
+			"status": "OK",
+			"instance_id": "MOCK\\BTH\\0001",
+		},
+		"devices": [
+			{
+				"name": "Mock Headphones",
+				"connected": true,
+				"address": "00:11:22:33:44:55",
+				"class": "AudioVideo",
+				"type": "Headphones",
+				"battery_percent": 72,
+			},
+			{
+				"name": "Mock Mouse",
+				"connected": false,
+				"address": "66:77:88:99:AA:BB",
+				"class": "Peripheral",
+				"type": "Mouse",
+			},
+		],
+	})
+}
+
+fn wifi(n: u64) -> Value {
+	let signal = wave(n, 30, 60.0, 95.0) as u64;
+	json!({
+		"connected": {
+			"is_connected": true,
+			"ssid": "MockNet",
+			"bssid": "02:00:00:00:00:aa",
+			"signal_percent": signal,
+			"signal_quality": if signal >= 80 { "excellent" } else { "good" },
+			"radio_type": "802.11ax",
+			"band": "5 GHz",
+			"channel": 36,
+			"authentication": "WPA2-Personal",
+			"cipher": "CCMP",
+			"receive_rate_mbps": 866.0,
+			"transmit_rate_mbps": 866.0,
+			"profile": "MockNet",
+			"interface_name": "Wi-Fi",
+			"interface_type": "Wireless",
+			"state": "connected",
+		},
+		"interfaces": [{
+			"name": "Wi-Fi",
+			"description": "Mock Wireless Adapter",
+			"status": "Up",
+			"mac_address": "02:00:00:00:00:02",
+			"link_speed": "866 Mbps",
+			"media_type": "Native 802.11",
+		}],
+	})
+}
+
+fn system(n: u64) -> Value {
+	// Same clock as `time`, so the two agree on uptime.
+	let uptime = 12 * 3600 + n;
+	json!({
+		"os": {
+			"name": "Windows",
+			"long_name": "Windows 11 Pro (mock)",
+			"version": "11",
+			"kernel_version": "10.0.22631",
+			"arch": "x86_64",
+			"cpu_arch": "x86_64",
+		},
+		"hostname": "MOCK-PC",
+		"computer_name": "MOCK-PC",
+		"username": "mock",
+		"user_domain": "MOCK-PC",
+		"user_profile": "C:\\Users\\mock",
+		"uptime_seconds": uptime,
+		"uptime_breakdown": {
+			"days": uptime / 86_400,
+			"hours": uptime % 86_400 / 3600,
+			"minutes": uptime % 3600 / 60,
+			"seconds": uptime % 60,
+		},
+		"boot_time_unix": MOCK_BOOT_UNIX,
+		"boot_time_iso": "2024-01-01T00:00:00+00:00",
+		"last_wake_unix": Value::Null,
+		"last_wake_time": Value::Null,
+		"locale": {
+			"language": {
+				"name": "en-US",
+				"display_name": "English (United States)",
+				"iso_two_letter": "en",
+				"iso_three_letter": "eng",
+			},
+			"timezone": {
+				"id": "UTC",
+				"display_name": "(UTC) Coordinated Universal Time",
+				"utc_offset_hours": 0.0,
+				"daylight_saving": false,
+			},
+			"region": {
+				"country": "United States",
+				"country_code": "US",
+				"currency_symbol": "$",
+				"currency_name": "US Dollar",
+			},
+		},
+		"theme": {
+			"app_theme": "dark",
+			"system_theme": "dark",
+			"transparency_enabled": true,
+			"accent_color_hex": "#0078D4",
+			"accent_color_raw": 4292114432u32,
+			"color_on_title_bars": false,
+		},
+		"bios": {
+			"vendor": "MockBIOS",
+			"manufacturer": "MockBIOS",
+			"name": "Mock BIOS",
+			"version": "1.00",
+			"release_date": "2024-01-01",
+			"serial_number": "MOCKBIOS0001",
+		},
+		"baseboard": mock_baseboard(),
+		"motherboard": mock_baseboard(),
+	})
+}
+
+fn mock_baseboard() -> Value {
+	json!({
+		"manufacturer": "MockBoard",
+		"product": "MB-1000",
+		"version": "1.0",
+		"serial": "MOCKMB0001",
+		"serial_number": "MOCKMB0001",
+	})
+}
+
+const MOCK_PROCESSES: &[&str] = &["veil.exe", "explorer.exe", "browser.exe", "editor.exe", "player.exe"];
+
+/// Entries shaped like `ram.top_processes`.
+fn mock_memory_processes() -> Vec<Value> {
+	MOCK_PROCESSES
+		.iter()
+		.enumerate()
+		.map(|(i, name)| {
+			let i = i as u64;
+			json!({
+				"pid": 1000 + i * 4,
+				"name": name,
+				"memory_bytes": (500 - i * 80) * 1024 * 1024,
+				"virtual_memory_bytes": (2000 - i * 200) * 1024 * 1024,
+			})
+		})
+		.collect()
+}
+
+fn processes(n: u64) -> Value {
+	let entry = |i: usize, name: &str, with_virtual: bool| {
+		let i = i as u64;
+		let mut e = json!({
+			"pid": 1000 + i * 4,
+			"name": name,
+			"parent_pid": if i == 1 { Value::Null } else { json!(1004) },
+			"cpu_percent": wave(n + i * 7, 30, 0.0, 25.0),
+			"memory_bytes": (500 - i * 80) * 1024 * 1024,
+			"status": "Run",
+		});
+		if with_virtual {
+			e["virtual_memory_bytes"] = json!((2000 - i * 200) * 1024 * 1024);
+		}
+		e
+	};
+	let top_cpu: Vec<Value> = MOCK_PROCESSES.iter().enumerate().map(|(i, name)| entry(i, name, false)).collect();
+	let top_memory: Vec<Value> = MOCK_PROCESSES.iter().enumerate().map(|(i, name)| entry(i, name, true)).collect();
+
+	json!({
+		"total_count": 180,
+		"total_cpu_usage": wave(n, 40, 10.0, 70.0),
+		"total_memory_bytes": 12 * GIB,
+		"status_counts": {
+			"running": 170,
+			"sleeping": 8,
+			"stopped": 0,
+			"zombie": 0,
+			"other": 2,
+		},
+		"top_cpu": top_cpu,
+		"top_memory": top_memory,
+	})
+}
+
+/// `processes.tree` over the mock process list, all started by explorer.exe.
+pub fn mock_process_tree(root: Option<u32>) -> Result<Value, String> {
+	let rows = MOCK_PROCESSES
+		.iter()
+		.enumerate()
+		.map(|(i, name)| processes::ProcRow {
+			pid: 1000 + i as u32 * 4,
+			parent_pid: if i == 1 { None } else { Some(1004) },
+			name: name.to_string(),
+			start_time: MOCK_BOOT_UNIX + i as u64,
+		})
+		.collect();
+	processes::build_tree(rows, root)
+}
+
+/// Two fixed monitors side by side.
+pub fn mock_monitors() -> Vec<MonitorInfo> {
+	let monitor = |idx: u32, x: i32, primary: bool| MonitorInfo {
+		id: format!("mock-monitor-{}", idx),
+		primary,
+		x,
+		y: 0,
+		width: 2560,
+		height: 1440,
+		scale: 1.0,
+		dpi: 96,
+		refresh_rate_hz: 144,
+		color_depth_bits: 32,
+		bits_per_channel: 8,
+		orientation: "landscape".into(),
+		aspect_ratio: "16:9".into(),
+		device_name: format!("\\\\.\\DISPLAY{}", idx + 1),
+		monitor_name: format!("Mock Monitor {}", idx + 1),
+		connection_type: "DisplayPort".into(),
+		hdr_supported: false,
+		physical_width_mm: 597,
+		physical_height_mm: 336,
+		manufacturer: "MCK".into(),
+		product_code: format!("MCK{:04}", idx),
+		serial_number: format!("MOCKMON{:04}", idx),
+		year_of_manufacture: 2024,
+		icc_profile_name: primary.then(|| "Mock Calibrated.icm".to_string()),
+		gamma_ramp_identity: !primary,
+		brightness_percent: primary.then_some(70),
+		device_id: format!("MONITOR\\MCK{:04}\\{{4d36e96e-e325-11ce-bfc1-08002be10318}}\\{:04}", idx, idx),
+		stable_id: String::new(),
+	};
+	let mut monitors = vec![monitor(0, 0, true), monitor(1, 2560, false)];
+	display::assign_stable_ids(&mut monitors);
+	monitors
+}
+
+/// One focused window per mock monitor, built from the real appdata struct.
+pub fn mock_active_windows() -> Vec<RegistryEntry> {
+	mock_monitors()
+		.into_iter()
+		.enumerate()
+		.map(|(i, m)| {
+			let (app_name, title) = if i == 0 {
+				("editor.exe", "main.rs - Mock Editor")
+			} else {
+				("browser.exe", "VEIL - Mock Browser")
+			};
+			let info = ActiveWindowInfo {
+				monitor_id: m.id.clone(),
+				focused: i == 0,
+				app_icon: format!("C:\\Mock\\{}\\icon.ico", app_name),
+				app_name: app_name.into(),
+				exe_path: format!("C:\\Mock\\{}", app_name),
+				window_title: title.into(),
+				pid: 2000 + i as u32,
+				window_state: "maximized".into(),
+				size: WindowSize { width: m.width, height: m.height },
+				position: WindowPosition { x: m.x, y: m.y },
+			};
+			RegistryEntry {
+				id: format!("active_window_{}_{}", m.id, 0x1000 + i),
+				category: "active_window".into(),
+				subtype: "monitor".into(),
+				metadata: json!(info),
+				path: PathBuf::new(),
+				exe_path: info.exe_path.clone(),
+			}
+		})
+		.collect()
+}
@@ -1,19 +1,49 @@
 // ~/veil/veil-backend/src/ipc/sysdata/mod.rs
 
+#[cfg(target_os = "windows")]
 pub mod cpu;
+#[cfg(target_os = "windows")]
 pub mod gpu;
+#[cfg(target_os = "windows")]
 pub mod ram;
+#[cfg(target_os = "windows")]
 pub mod storage;
+#[cfg(target_os = "windows")]
+pub mod smart;
+#[cfg(target_os = "windows")]
 pub mod network;
+#[cfg(target_os = "windows")]
 pub mod audio;
 pub mod display;
+#[cfg(target_os = "windows")]
+pub mod brightness;
 pub mod time;
+#[cfg(target_os = "windows")]
 pub mod keyboard;
+#[cfg(target_os = "windows")]
 pub mod mouse;
+#[cfg(target_os = "windows")]
+pub mod input_hooks;
+#[cfg(target_os = "windows")]
 pub mod power;
+#[cfg(target_os = "windows")]
 pub mod bluetooth;
+#[cfg(target_os = "windows")]
 pub mod wifi;
+#[cfg(target_os = "windows")]
 pub mod system;
+#[cfg(target_os = "windows")]
+pub mod static_hw;
 pub mod processes;
+pub mod process_snapshot;
 pub mod idle;
-pub mod media;
\ No newline at end of file
+#[cfg(target_os = "windows")]
+pub mod media;
+pub mod mock;
+#[cfg(target_os = "windows")]
+pub mod data_usage;
+
+#[cfg(not(target_os = "windows"))]
+pub use super::unsupported::{
+    audio, bluetooth, cpu, gpu, keyboard, media, mouse, network, power, ram, storage, system, wifi,
+};
@@ -2,11 +2,12 @@
 
 use serde_json::{json, Value};
 use std::sync::{OnceLock, RwLock};
+use std::time::{Duration, Instant};
 use windows::Win32::{
 	Foundation::POINT,
 	UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_LBUTTON, VK_MBUTTON, VK_RBUTTON},
 	UI::WindowsAndMessaging::{
-		GetCursorPos, GetSystemMetrics, SystemParametersInfoW,
+		GetCursorPos, GetPhysicalCursorPos, GetSystemMetrics, SystemParametersInfoW,
 		SM_CMOUSEBUTTONS, SM_MOUSEPRESENT, SM_MOUSEWHEELPRESENT, SM_SWAPBUTTON,
 		SM_CXSCREEN, SM_CYSCREEN, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN,
 		SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SM_CMONITORS,
@@ -14,6 +15,8 @@ use windows::Win32::{
 	},
 };
 
+use super::input_hooks;
+
 #[derive(Default, Clone)]
 struct MouseEventState {
 	left_down: bool,
@@ -30,6 +33,50 @@ fn mouse_state() -> &'static RwLock<MouseEventState> {
 	MOUSE_STATE.get_or_init(|| RwLock::new(MouseEventState::default()))
 }
 
+/// Samples further apart than this (sleep, a stalled tier, the secure desktop)
+/// are not counted as movement; the cursor may have been warped in between.
+const MAX_SAMPLE_GAP: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct MotionState {
+	last: Option<(POINT, Instant)>,
+	/// Sub-pixel remainder carried between samples.
+	movement_px: f64,
+	velocity_px_per_sec: f64,
+}
+
+static MOTION_STATE: OnceLock<RwLock<MotionState>> = OnceLock::new();
+
+/// Update the movement counter and velocity from the current physical
+/// cursor position. Returns `(movement_px_since_boot, velocity_px_per_sec)`.
+unsafe fn sample_motion() -> (u64, f64) {
+	let mut pos = POINT::default();
+	let ok = GetPhysicalCursorPos(&mut pos).is_ok();
+	let now = Instant::now();
+
+	let mut st = MOTION_STATE.get_or_init(|| RwLock::new(MotionState::default())).write().unwrap();
+	if !ok {
+		st.last = None;
+		st.velocity_px_per_sec = 0.0;
+		return (st.movement_px as u64, 0.0);
+	}
+
+	st.velocity_px_per_sec = 0.0;
+	if let Some((prev, at)) = st.last {
+		let elapsed = now.duration_since(at);
+		if elapsed <= MAX_SAMPLE_GAP && !elapsed.is_zero() {
+			let dx = (pos.x - prev.x) as f64;
+			let dy = (pos.y - prev.y) as f64;
+			let dist = (dx * dx + dy * dy).sqrt();
+			// f64 stays exact to 2^53 px; clamp so the u64 cast can never wrap.
+			st.movement_px = (st.movement_px + dist).min(u64::MAX as f64);
+			st.velocity_px_per_sec = dist / elapsed.as_secs_f64();
+		}
+	}
+	st.last = Some((pos, now));
+	(st.movement_px as u64, st.velocity_px_per_sec)
+}
+
 pub fn get_mouse_json() -> Value {
 	unsafe {
 		// Cursor position
@@ -64,6 +111,11 @@ pub fn get_mouse_json() -> Value {
 		let right_down = (GetAsyncKeyState(VK_RBUTTON.0.into()) as u16 & 0x8000) != 0;
 		let middle_down = (GetAsyncKeyState(VK_MBUTTON.0.into()) as u16 & 0x8000) != 0;
 
+		// Wheel input is invisible to polling; the hook thread counts it.
+		input_hooks::ensure_started();
+		let (movement_px, velocity_px_per_sec) = sample_motion();
+		let wheel_ticks = input_hooks::wheel_ticks();
+
 		let mut clicked = Vec::<&str>::new();
 		let (left_clicks, right_clicks, middle_clicks) = {
 			let mut state = mouse_state().write().unwrap();
@@ -108,6 +160,9 @@ pub fn get_mouse_json() -> Value {
 				"clicked": clicked,
 			},
 			"wheel_present": wheel_present,
+			"movement_px_since_boot": movement_px,
+			"wheel_ticks_since_boot": wheel_ticks,
+			"velocity_px_per_sec": velocity_px_per_sec,
 			"speed": mouse_speed,
 			"screen": {
 				"primary_width": primary_width,
@@ -1,17 +1,62 @@
 // ~/veil/veil-backend/src/ipc/sysdata/network.rs
 
 use serde_json::{json, Value};
-use std::{collections::HashMap, sync::{Mutex, OnceLock}, time::Instant};
+use std::{collections::HashMap, sync::{Mutex, OnceLock}, time::{Instant, SystemTime, UNIX_EPOCH}};
 use std::os::windows::process::CommandExt;
 use std::process::Command;
 use sysinfo::Networks;
+use windows::Networking::Connectivity::{NetworkCostType, NetworkInformation};
+use windows::Win32::NetworkManagement::IpHelper::{
+	GetBestInterface, GetExtendedTcpTable, GetPerTcpConnectionEStats, SetPerTcpConnectionEStats, TcpConnectionEstatsData,
+	MIB_TCPROW_LH, MIB_TCPROW_LH_0, MIB_TCPROW_OWNER_PID, MIB_TCPTABLE_OWNER_PID,
+	TCP_ESTATS_DATA_ROD_v0, TCP_ESTATS_DATA_RW_v0, TCP_TABLE_OWNER_PID_CONNECTIONS,
+};
+use windows::Win32::Networking::NetworkListManager::{
+	INetworkListManager, NetworkListManager, NLM_CONNECTIVITY_IPV4_INTERNET, NLM_CONNECTIVITY_IPV6_INTERNET,
+};
+use windows::Win32::Networking::WinSock::AF_INET;
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED};
+
+use super::data_usage;
+use super::process_snapshot::ProcessSnapshot;
 
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+/// How long the metered lookup is reused before asking Windows again.
+const METERED_CACHE_SECS: u64 = 30;
+
 #[derive(Default)]
 struct NetworkSnapshot {
 	totals_by_name: HashMap<String, (u64, u64)>,
+	/// Bytes (rx, tx) seen since the backend started or the last reset.
+	/// Accumulated from per-tick deltas so OS counter resets can't make it go backwards.
+	session_by_name: HashMap<String, (u64, u64)>,
 	last_tick: Option<Instant>,
+	/// Last known link state per adapter, from Get-NetAdapter's status.
+	link_up: HashMap<String, bool>,
+	/// Unix ms each adapter was first seen up, for adapters that are up.
+	up_since_ms: HashMap<String, u64>,
+	/// Link transitions of any adapter since the backend started. Never reset.
+	link_change_seq: u64,
+}
+
+static PREV: OnceLock<Mutex<NetworkSnapshot>> = OnceLock::new();
+
+fn snapshot_state() -> &'static Mutex<NetworkSnapshot> {
+	PREV.get_or_init(|| Mutex::new(NetworkSnapshot::default()))
+}
+
+/// Zero the per-session counters (`session_received_bytes` / `session_transmitted_bytes`).
+pub fn reset_session_counters() {
+	if let Ok(mut prev) = snapshot_state().lock() {
+		prev.session_by_name.clear();
+	}
+}
+
+/// Bytes added since the previous reading. A counter lower than last time
+/// means the adapter was reset, so everything it reports now is new traffic.
+fn counter_delta(current: u64, previous: u64) -> u64 {
+	if current >= previous { current - previous } else { current }
 }
 
 /// Query Get-NetAdapter for hardware details (description, link speed, media type, status)
@@ -29,6 +74,7 @@ foreach ($a in $adapters) {
 	"MediaConnectionState=$($a.MediaConnectionState)";
 	"DriverVersion=$($a.DriverVersionString)";
 	"DriverProvider=$($a.DriverProvider)";
+	"InterfaceGuid=$($a.InterfaceGuid)";
 	"";
 }
 "#;
@@ -56,6 +102,8 @@ foreach ($a in $adapters) {
 				let conn_state = fields.get("MediaConnectionState").map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
 				let driver_ver = fields.get("DriverVersion").map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
 				let driver_prov = fields.get("DriverProvider").map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+				let guid = fields.get("InterfaceGuid").map(|s| normalize_guid(s)).filter(|s| !s.is_empty());
+				let if_index = fields.get("InterfaceIndex").and_then(|s| s.trim().parse::<u32>().ok());
 
 				result.insert(name, json!({
 					"description": desc,
@@ -65,6 +113,8 @@ foreach ($a in $adapters) {
 					"media_connection_state": conn_state,
 					"driver_version": driver_ver,
 					"driver_provider": driver_prov,
+					"interface_guid": guid,
+					"interface_index": if_index,
 				}));
 			}
 			fields.clear();
@@ -83,6 +133,8 @@ foreach ($a in $adapters) {
 		let conn_state = fields.get("MediaConnectionState").map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
 		let driver_ver = fields.get("DriverVersion").map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
 		let driver_prov = fields.get("DriverProvider").map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+		let guid = fields.get("InterfaceGuid").map(|s| normalize_guid(s)).filter(|s| !s.is_empty());
+		let if_index = fields.get("InterfaceIndex").and_then(|s| s.trim().parse::<u32>().ok());
 		result.insert(name, json!({
 			"description": desc,
 			"link_speed": link_speed,
@@ -91,21 +143,256 @@ foreach ($a in $adapters) {
 			"media_connection_state": conn_state,
 			"driver_version": driver_ver,
 			"driver_provider": driver_prov,
+			"interface_guid": guid,
+			"interface_index": if_index,
 		}));
 	}
 	result
 }
 
-pub fn get_network_json() -> Value {
+/// Name of the adapter Windows would route public IPv4 traffic through,
+/// matched to `adapter_details` by interface index.
+fn default_route_interface(adapter_details: &HashMap<String, Value>) -> Option<String> {
+	// 8.8.8.8 reads the same in either byte order. Nothing is sent to it.
+	let dest = u32::from_ne_bytes([8, 8, 8, 8]);
+	let mut index = 0u32;
+	if unsafe { GetBestInterface(dest, &mut index) } != 0 {
+		return None;
+	}
+	adapter_details
+		.iter()
+		.find(|(_, hw)| hw.get("interface_index").and_then(|v| v.as_u64()) == Some(index as u64))
+		.map(|(name, _)| name.clone())
+}
+
+/// Update link states from this tick's Get-NetAdapter results, bumping
+/// `link_change_seq` once per adapter that went up or down. An empty result
+/// means the query failed, so the previous states are kept.
+fn track_links(prev: &mut NetworkSnapshot, adapter_details: &HashMap<String, Value>) {
+	if adapter_details.is_empty() {
+		return;
+	}
+	let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+	let mut current: HashMap<String, bool> = adapter_details
+		.iter()
+		.map(|(name, hw)| (name.clone(), hw.get("status").and_then(|v| v.as_str()) == Some("Up")))
+		.collect();
+	// An adapter that vanished (unplugged USB, removed driver) is down.
+	for (name, up) in &prev.link_up {
+		if *up && !current.contains_key(name) {
+			current.insert(name.clone(), false);
+		}
+	}
+
+	for (name, &up) in &current {
+		let previous = prev.link_up.get(name).copied();
+		if previous.is_some_and(|was| was != up) {
+			prev.link_change_seq += 1;
+		}
+		if up {
+			prev.up_since_ms.entry(name.clone()).or_insert(now_ms);
+		} else {
+			prev.up_since_ms.remove(name);
+		}
+	}
+	prev.link_up = current;
+}
+
+/// Whether Windows currently reports internet access, as decided by its own
+/// connectivity status indicator (which also catches captive portals). This
+/// only reads the Network List Manager's cached result; nothing is probed.
+/// `None` when `connectivity_check` is off or NLM can't be reached.
+fn connectivity() -> Option<Value> {
+	if !crate::config::connectivity_check() {
+		return None;
+	}
+	let internet = unsafe {
+		let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+		let nlm: INetworkListManager = CoCreateInstance(&NetworkListManager, None, CLSCTX_ALL).ok()?;
+		let flags = nlm.GetConnectivity().ok()?;
+		flags.0 & (NLM_CONNECTIVITY_IPV4_INTERNET.0 | NLM_CONNECTIVITY_IPV6_INTERNET.0) != 0
+	};
+	let checked_at_ms = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+	Some(json!({ "internet": internet, "checked_at_ms": checked_at_ms }))
+}
+
+/// `{XXXXXXXX-...}` → `xxxxxxxx-...` so PowerShell and WinRT GUIDs compare equal.
+fn normalize_guid(raw: &str) -> String {
+	raw.trim().trim_matches(|c| c == '{' || c == '}').to_ascii_lowercase()
+}
+
+/// Adapter GUID → whether its connection profile is metered (fixed or variable cost).
+fn query_metered_adapters() -> HashMap<String, bool> {
+	let mut result = HashMap::new();
+	let Ok(profiles) = NetworkInformation::GetConnectionProfiles() else { return result };
+	for profile in profiles {
+		let Ok(adapter_id) = profile.NetworkAdapter().and_then(|a| a.NetworkAdapterId()) else { continue };
+		let metered = profile
+			.GetConnectionCost()
+			.and_then(|c| c.NetworkCostType())
+			.map(|t| t == NetworkCostType::Fixed || t == NetworkCostType::Variable)
+			.unwrap_or(false);
+		let entry = result.entry(normalize_guid(&format!("{:?}", adapter_id))).or_insert(false);
+		*entry |= metered;
+	}
+	result
+}
+
+/// Cached wrapper around `query_metered_adapters`; profiles rarely change.
+fn metered_adapters() -> HashMap<String, bool> {
+	static CACHE: OnceLock<Mutex<Option<(Instant, HashMap<String, bool>)>>> = OnceLock::new();
+	let mut cache = CACHE.get_or_init(|| Mutex::new(None)).lock().unwrap();
+	if let Some((at, map)) = cache.as_ref() {
+		if at.elapsed().as_secs() < METERED_CACHE_SECS {
+			return map.clone();
+		}
+	}
+	let map = query_metered_adapters();
+	*cache = Some((Instant::now(), map.clone()));
+	map
+}
+
+// ── Per-process bandwidth ("top talkers") ──
+//
+// Windows keeps no per-process byte counters, so this walks the IPv4 TCP
+// table, turns on extended stats for each established connection and sums
+// DataBytesIn/Out per owning PID. UDP has no per-socket counters and isn't
+// attributed. Enabling collection needs admin rights; without them the list
+// stays empty.
+
+/// Entries reported in `top_talkers`.
+const MAX_TALKERS: usize = 5;
+const MIB_TCP_STATE_ESTAB: u32 = 5;
+
+/// Connection 4-tuple plus owning PID.
+type ConnKey = (u32, u32, u32, u32, u32);
+
+#[derive(Default)]
+struct TalkerState {
+	/// Last (bytes_in, bytes_out) seen per connection.
+	conn_bytes: HashMap<ConnKey, (u64, u64)>,
+	last_sample: Option<Instant>,
+}
+
+static TALKERS: OnceLock<Mutex<TalkerState>> = OnceLock::new();
+
+fn owned_tcp_rows() -> Vec<MIB_TCPROW_OWNER_PID> {
+	let mut size = 0u32;
+	unsafe {
+		let _ = GetExtendedTcpTable(None, &mut size, false, AF_INET.0 as u32, TCP_TABLE_OWNER_PID_CONNECTIONS, 0);
+	}
+	if size == 0 {
+		return Vec::new();
+	}
+	// u32 backing keeps the table aligned for the row structs.
+	let mut buf = vec![0u32; (size as usize).div_ceil(4)];
+	let status = unsafe {
+		GetExtendedTcpTable(Some(buf.as_mut_ptr() as *mut _), &mut size, false, AF_INET.0 as u32, TCP_TABLE_OWNER_PID_CONNECTIONS, 0)
+	};
+	if status != 0 {
+		return Vec::new();
+	}
+	let table = unsafe { &*(buf.as_ptr() as *const MIB_TCPTABLE_OWNER_PID) };
+	let rows = unsafe { std::slice::from_raw_parts(table.table.as_ptr(), table.dwNumEntries as usize) };
+	rows.to_vec()
+}
+
+/// Cumulative (bytes_in, bytes_out) for a connection, enabling stats on first sight.
+fn connection_bytes(row: &MIB_TCPROW_OWNER_PID, known: bool) -> Option<(u64, u64)> {
+	let lh = MIB_TCPROW_LH {
+		Anonymous: MIB_TCPROW_LH_0 { dwState: row.dwState },
+		dwLocalAddr: row.dwLocalAddr,
+		dwLocalPort: row.dwLocalPort,
+		dwRemoteAddr: row.dwRemoteAddr,
+		dwRemotePort: row.dwRemotePort,
+	};
+	unsafe {
+		if !known {
+			let rw = TCP_ESTATS_DATA_RW_v0 { EnableCollection: true.into() };
+			let rw_bytes = std::slice::from_raw_parts(&rw as *const _ as *const u8, std::mem::size_of::<TCP_ESTATS_DATA_RW_v0>());
+			if SetPerTcpConnectionEStats(&lh, TcpConnectionEstatsData, rw_bytes, 0, 0) != 0 {
+				return None;
+			}
+		}
+		let mut rod = TCP_ESTATS_DATA_ROD_v0::default();
+		let rod_bytes = std::slice::from_raw_parts_mut(&mut rod as *mut _ as *mut u8, std::mem::size_of::<TCP_ESTATS_DATA_ROD_v0>());
+		let status = GetPerTcpConnectionEStats(&lh, TcpConnectionEstatsData, None, 0, None, 0, Some(rod_bytes), 0);
+		(status == 0).then_some((rod.DataBytesIn, rod.DataBytesOut))
+	}
+}
+
+fn process_name(snapshot: &ProcessSnapshot, pid: u32) -> String {
+	match snapshot.name(pid) {
+		Some(name) => name.to_string(),
+		None if pid == 4 => "System".to_string(),
+		None => format!("PID {}", pid),
+	}
+}
+
+/// Busiest processes since the previous sample, highest combined rate first.
+fn top_talkers(snapshot: &ProcessSnapshot) -> Vec<Value> {
+	let mut state = TALKERS.get_or_init(|| Mutex::new(TalkerState::default())).lock().unwrap();
+	let now = Instant::now();
+	let elapsed_s = state
+		.last_sample
+		.map(|t| now.saturating_duration_since(t).as_secs_f64())
+		.unwrap_or(0.0);
+
+	let mut next_bytes = HashMap::<ConnKey, (u64, u64)>::new();
+	let mut per_pid = HashMap::<u32, (u64, u64)>::new();
+	for row in owned_tcp_rows().iter().filter(|r| r.dwState == MIB_TCP_STATE_ESTAB) {
+		let key = (row.dwLocalAddr, row.dwLocalPort, row.dwRemoteAddr, row.dwRemotePort, row.dwOwningPid);
+		let previous = state.conn_bytes.get(&key).copied();
+		let Some(current) = connection_bytes(row, previous.is_some()) else { continue };
+		next_bytes.insert(key, current);
+
+		// New connections only establish a baseline; closed ones simply drop out.
+		if let Some(previous) = previous {
+			let entry = per_pid.entry(row.dwOwningPid).or_insert((0, 0));
+			entry.0 = entry.0.saturating_add(counter_delta(current.0, previous.0));
+			entry.1 = entry.1.saturating_add(counter_delta(current.1, previous.1));
+		}
+	}
+	state.conn_bytes = next_bytes;
+	state.last_sample = Some(now);
+
+	if elapsed_s <= 0.0 {
+		return Vec::new();
+	}
+
+	let mut ranked: Vec<(u32, f64, f64)> = per_pid
+		.into_iter()
+		.map(|(pid, (rx, tx))| (pid, rx as f64 / elapsed_s, tx as f64 / elapsed_s))
+		.filter(|(_, rx, tx)| *rx > 0.0 || *tx > 0.0)
+		.collect();
+	ranked.sort_by(|a, b| (b.1 + b.2).partial_cmp(&(a.1 + a.2)).unwrap_or(std::cmp::Ordering::Equal));
+	ranked.truncate(MAX_TALKERS);
+
+	ranked
+		.into_iter()
+		.map(|(pid, recv_bps, sent_bps)| json!({
+			"pid": pid,
+			"name": process_name(snapshot, pid),
+			"sent_bps": sent_bps,
+			"recv_bps": recv_bps,
+		}))
+		.collect()
+}
+
+pub fn get_network_json(snapshot: &ProcessSnapshot) -> Value {
 	let mut networks = Networks::new_with_refreshed_list();
 	networks.refresh(false);
 
 	// Query PowerShell Get-NetAdapter for hardware details
 	let adapter_details = query_adapter_details();
+	let metered_by_guid = metered_adapters();
+	let top_talkers = top_talkers(snapshot);
+
+	let default_route = default_route_interface(&adapter_details);
+	let connectivity = connectivity();
 
-	static PREV: OnceLock<Mutex<NetworkSnapshot>> = OnceLock::new();
-	let prev_state = PREV.get_or_init(|| Mutex::new(NetworkSnapshot::default()));
-	let mut prev = prev_state.lock().unwrap();
+	let mut prev = snapshot_state().lock().unwrap();
+	track_links(&mut prev, &adapter_details);
 	let now = Instant::now();
 	let elapsed_s = prev
 		.last_tick
@@ -121,7 +408,10 @@ pub fn get_network_json() -> Value {
 	let mut aggregate_packets_tx: u64 = 0;
 	let mut aggregate_errors_rx: u64 = 0;
 	let mut aggregate_errors_tx: u64 = 0;
+	let mut tick_metered: u64 = 0;
 	let mut next_totals = HashMap::<String, (u64, u64)>::new();
+	// Carry over adapters that are briefly missing so their session bytes aren't lost.
+	let mut next_session = prev.session_by_name.clone();
 
 	let list: Vec<Value> = (&networks)
 		.into_iter()
@@ -165,10 +455,16 @@ pub fn get_network_json() -> Value {
 				.copied()
 				.unwrap_or((total_rx, total_tx));
 
-			let rx_per_second = ((total_rx.saturating_sub(prev_totals.0)) as f64 / elapsed_s).max(0.0);
-			let tx_per_second = ((total_tx.saturating_sub(prev_totals.1)) as f64 / elapsed_s).max(0.0);
+			let delta_rx = counter_delta(total_rx, prev_totals.0);
+			let delta_tx = counter_delta(total_tx, prev_totals.1);
+			let rx_per_second = delta_rx as f64 / elapsed_s;
+			let tx_per_second = delta_tx as f64 / elapsed_s;
+			let session = prev.session_by_name.get(name).copied().unwrap_or((0, 0));
+			let session_rx = session.0.saturating_add(delta_rx);
+			let session_tx = session.1.saturating_add(delta_tx);
 
 			next_totals.insert(name.to_string(), (total_rx, total_tx));
+			next_session.insert(name.to_string(), (session_rx, session_tx));
 
 			// Merge hardware details from Get-NetAdapter
 			let hw = adapter_details.get(name);
@@ -179,6 +475,16 @@ pub fn get_network_json() -> Value {
 			let conn_state = hw.and_then(|h| h.get("media_connection_state")).cloned().unwrap_or(Value::Null);
 			let driver_version = hw.and_then(|h| h.get("driver_version")).cloned().unwrap_or(Value::Null);
 			let driver_provider = hw.and_then(|h| h.get("driver_provider")).cloned().unwrap_or(Value::Null);
+			let metered = hw
+				.and_then(|h| h.get("interface_guid"))
+				.and_then(|v| v.as_str())
+				.and_then(|guid| metered_by_guid.get(guid).copied())
+				.unwrap_or(false);
+			if metered {
+				tick_metered = tick_metered.saturating_add(delta_rx).saturating_add(delta_tx);
+			}
+			let link_up = prev.link_up.get(name).copied();
+			let up_since_ms = prev.up_since_ms.get(name).copied();
 
 			json!({
 				"interface": name,
@@ -191,10 +497,15 @@ pub fn get_network_json() -> Value {
 				"media_connection_state": conn_state,
 				"driver_version": driver_version,
 				"driver_provider": driver_provider,
+				"metered": metered,
+				"link_up": link_up,
+				"up_since_ms": up_since_ms,
 				"received_bytes": rx,
 				"transmitted_bytes": tx,
 				"total_received_bytes": total_rx,
 				"total_transmitted_bytes": total_tx,
+				"session_received_bytes": session_rx,
+				"session_transmitted_bytes": session_tx,
 				"received_bytes_per_second": rx_per_second,
 				"transmitted_bytes_per_second": tx_per_second,
 				"packets": {
@@ -214,7 +525,15 @@ pub fn get_network_json() -> Value {
 		.collect();
 
 	prev.totals_by_name = next_totals;
+	let (aggregate_session_rx, aggregate_session_tx) = next_session
+		.values()
+		.fold((0u64, 0u64), |acc, v| (acc.0.saturating_add(v.0), acc.1.saturating_add(v.1)));
+	prev.session_by_name = next_session;
 	prev.last_tick = Some(now);
+	let link_change_seq = prev.link_change_seq;
+	drop(prev);
+
+	let (data_cap, data_cap_warning) = data_usage::record_metered_bytes(tick_metered);
 
 	// Sort interfaces by name for stable ordering across refreshes
 	let mut list = list;
@@ -229,6 +548,8 @@ pub fn get_network_json() -> Value {
 		"transmitted_bytes": tick_tx,
 		"total_received_bytes": aggregate_total_rx,
 		"total_transmitted_bytes": aggregate_total_tx,
+		"session_received_bytes": aggregate_session_rx,
+		"session_transmitted_bytes": aggregate_session_tx,
 		"received_bytes_per_second": if elapsed_s > 0.0 { tick_rx as f64 / elapsed_s } else { 0.0 },
 		"transmitted_bytes_per_second": if elapsed_s > 0.0 { tick_tx as f64 / elapsed_s } else { 0.0 },
 		"total_packets_received": aggregate_packets_rx,
@@ -237,5 +558,13 @@ pub fn get_network_json() -> Value {
 		"total_errors_transmitted": aggregate_errors_tx,
 		"interface_count": list.len(),
 		"interfaces": list,
+		"link_change_seq": link_change_seq,
+		"default_route_interface": default_route,
+		"connectivity": connectivity,
+		"top_talkers": top_talkers,
+		"data_cap": data_cap,
+		"events": {
+			"data_cap_warning": data_cap_warning,
+		},
 	})
 }
@@ -3,10 +3,33 @@
 use serde_json::{json, Value};
 use std::os::windows::process::CommandExt;
 use std::process::Command;
+use windows::core::{GUID, PCWSTR};
+use windows::Win32::Devices::DeviceAndDriverInstallation::{
+	SetupDiDestroyDeviceInfoList, SetupDiEnumDeviceInterfaces, SetupDiGetClassDevsW,
+	SetupDiGetDeviceInterfaceDetailW, DIGCF_DEVICEINTERFACE, DIGCF_PRESENT, SP_DEVICE_INTERFACE_DATA,
+	SP_DEVICE_INTERFACE_DETAIL_DATA_W,
+};
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::Storage::FileSystem::{
+	CreateFileW, FILE_ATTRIBUTE_NORMAL, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows::Win32::System::IO::DeviceIoControl;
 use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
 
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+const GENERIC_READ: u32 = 0x8000_0000;
+const GENERIC_WRITE: u32 = 0x4000_0000;
+/// GUID_DEVCLASS_BATTERY, also the battery device interface class.
+const GUID_DEVCLASS_BATTERY: GUID = GUID::from_u128(0x72631e54_78a4_11d0_bcf7_00aa00b7b32a);
+const IOCTL_BATTERY_QUERY_TAG: u32 = 0x0029_4040;
+const IOCTL_BATTERY_QUERY_INFORMATION: u32 = 0x0029_4044;
+/// BATTERY_QUERY_INFORMATION_LEVEL::BatteryInformation
+const BATTERY_INFORMATION_LEVEL: u32 = 0;
+/// Capabilities bits in BATTERY_INFORMATION.
+const BATTERY_SYSTEM_BATTERY: u32 = 0x8000_0000;
+const BATTERY_CAPACITY_RELATIVE: u32 = 0x4000_0000;
+
 pub fn get_power_json() -> Value {
 	unsafe {
 		let mut status = SYSTEM_POWER_STATUS::default();
@@ -45,6 +68,7 @@ pub fn get_power_json() -> Value {
 			let battery_saver = status.SystemStatusFlag != 0;
 			let power_plan = get_active_power_plan();
 			let battery_details = get_battery_details();
+			let capacity = query_battery_capacity();
 
 			json!({
 				"ac_status": ac_status,
@@ -58,6 +82,10 @@ pub fn get_power_json() -> Value {
 					"lifetime_seconds": battery_lifetime_seconds,
 					"fulllife_seconds": battery_fulllife_seconds,
 					"saver_active": battery_saver,
+					"design_capacity_mwh": capacity.map(|c| c.design_mwh),
+					"full_charge_capacity_mwh": capacity.map(|c| c.full_charge_mwh),
+					"health_percent": capacity.and_then(|c| c.health_percent()),
+					"cycle_count": capacity.and_then(|c| c.cycle_count),
 					"details": battery_details,
 				},
 				"power_plan": power_plan,
@@ -69,6 +97,10 @@ pub fn get_power_json() -> Value {
 					"present": false,
 					"percent": Value::Null,
 					"charging": false,
+					"design_capacity_mwh": Value::Null,
+					"full_charge_capacity_mwh": Value::Null,
+					"health_percent": Value::Null,
+					"cycle_count": Value::Null,
 				},
 				"power_plan": Value::Null,
 			})
@@ -99,6 +131,168 @@ fn get_active_power_plan() -> Value {
 	Value::Null
 }
 
+#[derive(Clone, Copy)]
+struct BatteryCapacity {
+	design_mwh: u64,
+	full_charge_mwh: u64,
+	/// `None` when the firmware reports 0 (not tracked).
+	cycle_count: Option<u32>,
+}
+
+impl BatteryCapacity {
+	/// Full-charge vs design capacity; capped at 100 because new packs often
+	/// report slightly more than their rating.
+	fn health_percent(&self) -> Option<f64> {
+		(self.design_mwh > 0)
+			.then(|| (self.full_charge_mwh as f64 / self.design_mwh as f64 * 100.0).min(100.0))
+	}
+}
+
+struct BatteryHandle(HANDLE);
+
+impl Drop for BatteryHandle {
+	fn drop(&mut self) {
+		unsafe {
+			let _ = CloseHandle(self.0);
+		}
+	}
+}
+
+/// Capacity and cycle count from the battery class driver (IOCTL_BATTERY_*),
+/// summed over all system batteries. `None` on machines without one.
+fn query_battery_capacity() -> Option<BatteryCapacity> {
+	let mut total: Option<BatteryCapacity> = None;
+	for path in battery_device_paths() {
+		let Some(info) = read_battery_information(&path) else { continue };
+		total = Some(match total {
+			None => info,
+			Some(t) => BatteryCapacity {
+				design_mwh: t.design_mwh + info.design_mwh,
+				full_charge_mwh: t.full_charge_mwh + info.full_charge_mwh,
+				cycle_count: t.cycle_count.max(info.cycle_count),
+			},
+		});
+	}
+	total
+}
+
+/// Device interface paths of present batteries, NUL-terminated for CreateFileW.
+fn battery_device_paths() -> Vec<Vec<u16>> {
+	let mut paths = Vec::new();
+	unsafe {
+		let Ok(set) = SetupDiGetClassDevsW(
+			Some(&GUID_DEVCLASS_BATTERY),
+			PCWSTR::null(),
+			None,
+			DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
+		) else {
+			return paths;
+		};
+
+		for index in 0..8 {
+			let mut iface = SP_DEVICE_INTERFACE_DATA {
+				cbSize: std::mem::size_of::<SP_DEVICE_INTERFACE_DATA>() as u32,
+				..Default::default()
+			};
+			if SetupDiEnumDeviceInterfaces(set, None, &GUID_DEVCLASS_BATTERY, index, &mut iface).is_err() {
+				break;
+			}
+
+			let mut required = 0u32;
+			let _ = SetupDiGetDeviceInterfaceDetailW(set, &iface, None, 0, Some(&mut required), None);
+			if required == 0 {
+				continue;
+			}
+			// u32 backing keeps the struct aligned; cbSize is the fixed header size.
+			let mut buf = vec![0u32; (required as usize).div_ceil(4)];
+			let detail = buf.as_mut_ptr() as *mut SP_DEVICE_INTERFACE_DETAIL_DATA_W;
+			(*detail).cbSize = std::mem::size_of::<SP_DEVICE_INTERFACE_DETAIL_DATA_W>() as u32;
+			if SetupDiGetDeviceInterfaceDetailW(set, &iface, Some(detail), required, None, None).is_err() {
+				continue;
+			}
+
+			let path_ptr = std::ptr::addr_of!((*detail).DevicePath) as *const u16;
+			let max_len = (required as usize - 4) / 2;
+			let path = std::slice::from_raw_parts(path_ptr, max_len);
+			let len = path.iter().position(|&c| c == 0).unwrap_or(max_len);
+			let mut owned = path[..len].to_vec();
+			owned.push(0);
+			paths.push(owned);
+		}
+
+		let _ = SetupDiDestroyDeviceInfoList(set);
+	}
+	paths
+}
+
+fn battery_ioctl(handle: &BatteryHandle, code: u32, input: &[u8], output: &mut [u8]) -> bool {
+	let mut returned = 0u32;
+	unsafe {
+		DeviceIoControl(
+			handle.0,
+			code,
+			Some(input.as_ptr() as *const _),
+			input.len() as u32,
+			Some(output.as_mut_ptr() as *mut _),
+			output.len() as u32,
+			Some(&mut returned),
+			None,
+		)
+	}
+	.is_ok()
+}
+
+fn read_battery_information(path: &[u16]) -> Option<BatteryCapacity> {
+	let handle = unsafe {
+		CreateFileW(
+			PCWSTR(path.as_ptr()),
+			GENERIC_READ | GENERIC_WRITE,
+			FILE_SHARE_READ | FILE_SHARE_WRITE,
+			None,
+			OPEN_EXISTING,
+			FILE_ATTRIBUTE_NORMAL,
+			None,
+		)
+	}
+	.ok()
+	.map(BatteryHandle)?;
+
+	// A zero wait returns the current tag without blocking for a battery to appear.
+	let mut tag = [0u8; 4];
+	if !battery_ioctl(&handle, IOCTL_BATTERY_QUERY_TAG, &0u32.to_le_bytes(), &mut tag) {
+		return None;
+	}
+	let tag = u32::from_le_bytes(tag);
+	if tag == 0 {
+		return None;
+	}
+
+	// BATTERY_QUERY_INFORMATION: BatteryTag, InformationLevel, AtRate.
+	let mut query = [0u8; 12];
+	query[0..4].copy_from_slice(&tag.to_le_bytes());
+	query[4..8].copy_from_slice(&BATTERY_INFORMATION_LEVEL.to_le_bytes());
+
+	// BATTERY_INFORMATION: Capabilities, Technology + Reserved, Chemistry,
+	// DesignedCapacity, FullChargedCapacity, DefaultAlert1/2, CriticalBias, CycleCount.
+	let mut info = [0u8; 36];
+	if !battery_ioctl(&handle, IOCTL_BATTERY_QUERY_INFORMATION, &query, &mut info) {
+		return None;
+	}
+	let field = |at: usize| u32::from_le_bytes([info[at], info[at + 1], info[at + 2], info[at + 3]]);
+
+	let capabilities = field(0);
+	// UPS units show up here too, and relative capacities are not in mWh.
+	if capabilities & BATTERY_SYSTEM_BATTERY == 0 || capabilities & BATTERY_CAPACITY_RELATIVE != 0 {
+		return None;
+	}
+	let cycle_count = field(32);
+	Some(BatteryCapacity {
+		design_mwh: field(12) as u64,
+		full_charge_mwh: field(16) as u64,
+		cycle_count: (cycle_count > 0).then_some(cycle_count),
+	})
+}
+
 fn get_battery_details() -> Value {
 	let script = r#"$ErrorActionPreference='SilentlyContinue';
 $b = Get-CimInstance -ClassName Win32_Battery -ErrorAction SilentlyContinue | Select-Object -First 1;
@@ -0,0 +1,73 @@
+// ~/veil/veil-backend/src/ipc/sysdata/process_snapshot.rs
+//
+// One walk of the process table, shared by every slow-tier collector that
+// reports per-process data (processes, ram, gpu, network). The sysinfo
+// `System` is kept between captures so CPU usage is measured against the
+// previous tick instead of a fresh blocking double sample.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use sysinfo::{ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System};
+
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+	pub name: String,
+	pub parent_pid: Option<u32>,
+	pub working_set_bytes: u64,
+	pub virtual_memory_bytes: u64,
+	/// Share of one core since the previous capture; 0 on the first one.
+	pub cpu_percent: f32,
+	pub status: ProcessStatus,
+	/// Seconds since the epoch.
+	pub start_time: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcessSnapshot {
+	pub processes: HashMap<u32, ProcessInfo>,
+}
+
+fn shared_system() -> &'static Mutex<System> {
+	static SYSTEM: OnceLock<Mutex<System>> = OnceLock::new();
+	SYSTEM.get_or_init(|| Mutex::new(System::new()))
+}
+
+impl ProcessSnapshot {
+	/// Enumerate running processes once.
+	pub fn capture() -> Self {
+		let mut sys = shared_system().lock().unwrap();
+		sys.refresh_processes_specifics(
+			ProcessesToUpdate::All,
+			true,
+			ProcessRefreshKind::nothing().with_cpu().with_memory(),
+		);
+
+		let processes = sys
+			.processes()
+			.iter()
+			.map(|(pid, p)| {
+				(pid.as_u32(), ProcessInfo {
+					name: p.name().to_string_lossy().to_string(),
+					parent_pid: p.parent().map(|pp| pp.as_u32()),
+					working_set_bytes: p.memory(),
+					virtual_memory_bytes: p.virtual_memory(),
+					cpu_percent: p.cpu_usage(),
+					status: p.status(),
+					start_time: p.start_time(),
+				})
+			})
+			.collect();
+		Self { processes }
+	}
+
+	pub fn name(&self, pid: u32) -> Option<&str> {
+		self.processes.get(&pid).map(|p| p.name.as_str())
+	}
+
+	/// Processes with the largest working set first.
+	pub fn by_memory(&self) -> Vec<(u32, &ProcessInfo)> {
+		let mut list: Vec<(u32, &ProcessInfo)> = self.processes.iter().map(|(pid, p)| (*pid, p)).collect();
+		list.sort_by(|a, b| b.1.working_set_bytes.cmp(&a.1.working_set_bytes));
+		list
+	}
+}
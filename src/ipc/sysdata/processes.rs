@@ -1,25 +1,26 @@
 // ~/veil/veil-backend/src/ipc/sysdata/processes.rs
 
 use serde_json::{json, Value};
-use sysinfo::System;
+use std::collections::{HashMap, HashSet, VecDeque};
+use super::process_snapshot::{ProcessInfo, ProcessSnapshot};
 
-pub fn get_processes_json() -> Value {
-	let mut sys = System::new_all();
-	sys.refresh_all();
-	// Second refresh to get accurate CPU usage (sysinfo needs two samples)
-	std::thread::sleep(std::time::Duration::from_millis(200));
-	sys.refresh_all();
-
-	let processes = sys.processes();
+pub fn get_processes_json(snapshot: &ProcessSnapshot) -> Value {
+	let processes = &snapshot.processes;
 	let total_processes = processes.len();
 
-	// Collect into a sortable vec
-	let mut proc_list: Vec<(&sysinfo::Pid, &sysinfo::Process)> = processes.iter().collect();
+	// Collect into a sortable vec, leaving out what `processes.ignore` /
+	// `processes.include_only` hide from the top lists
+	let filter = crate::config::process_filter();
+	let mut proc_list: Vec<(&u32, &ProcessInfo)> = processes
+		.iter()
+		.filter(|(_, p)| filter.allows(&p.name))
+		.collect();
+	let filtered_count = total_processes - proc_list.len();
 
 	// Top 15 by CPU usage
 	proc_list.sort_by(|a, b| {
-		b.1.cpu_usage()
-			.partial_cmp(&a.1.cpu_usage())
+		b.1.cpu_percent
+			.partial_cmp(&a.1.cpu_percent)
 			.unwrap_or(std::cmp::Ordering::Equal)
 	});
 	let top_cpu: Vec<Value> = proc_list
@@ -27,35 +28,37 @@ pub fn get_processes_json() -> Value {
 		.take(15)
 		.map(|(pid, p)| {
 			json!({
-				"pid": pid.as_u32(),
-				"name": p.name().to_string_lossy(),
-				"cpu_percent": p.cpu_usage(),
-				"memory_bytes": p.memory(),
-				"status": format!("{:?}", p.status()),
+				"pid": pid,
+				"name": p.name,
+				"parent_pid": p.parent_pid,
+				"cpu_percent": p.cpu_percent,
+				"memory_bytes": p.working_set_bytes,
+				"status": format!("{:?}", p.status),
 			})
 		})
 		.collect();
 
 	// Top 15 by memory
-	proc_list.sort_by(|a, b| b.1.memory().cmp(&a.1.memory()));
+	proc_list.sort_by(|a, b| b.1.working_set_bytes.cmp(&a.1.working_set_bytes));
 	let top_memory: Vec<Value> = proc_list
 		.iter()
 		.take(15)
 		.map(|(pid, p)| {
 			json!({
-				"pid": pid.as_u32(),
-				"name": p.name().to_string_lossy(),
-				"cpu_percent": p.cpu_usage(),
-				"memory_bytes": p.memory(),
-				"virtual_memory_bytes": p.virtual_memory(),
-				"status": format!("{:?}", p.status()),
+				"pid": pid,
+				"name": p.name,
+				"parent_pid": p.parent_pid,
+				"cpu_percent": p.cpu_percent,
+				"memory_bytes": p.working_set_bytes,
+				"virtual_memory_bytes": p.virtual_memory_bytes,
+				"status": format!("{:?}", p.status),
 			})
 		})
 		.collect();
 
 	// Aggregate stats
-	let total_cpu: f32 = processes.values().map(|p| p.cpu_usage()).sum();
-	let total_memory: u64 = processes.values().map(|p| p.memory()).sum();
+	let total_cpu: f32 = processes.values().map(|p| p.cpu_percent).sum();
+	let total_memory: u64 = processes.values().map(|p| p.working_set_bytes).sum();
 
 	// Count by status
 	let mut running = 0u32;
@@ -65,7 +68,7 @@ pub fn get_processes_json() -> Value {
 	let mut other = 0u32;
 
 	for p in processes.values() {
-		match p.status() {
+		match p.status {
 			sysinfo::ProcessStatus::Run => running += 1,
 			sysinfo::ProcessStatus::Sleep => sleeping += 1,
 			sysinfo::ProcessStatus::Stop => stopped += 1,
@@ -87,5 +90,101 @@ pub fn get_processes_json() -> Value {
 		},
 		"top_cpu": top_cpu,
 		"top_memory": top_memory,
+		"filtered_count": filtered_count,
 	})
 }
+
+/// One process as seen by the tree builder.
+pub struct ProcRow {
+	pub pid: u32,
+	pub parent_pid: Option<u32>,
+	pub name: String,
+	/// Seconds since the epoch; used to spot reused parent PIDs.
+	pub start_time: u64,
+}
+
+/// Spawn hierarchy for the `processes.tree` command: the whole system, or
+/// only `root` and its descendants.
+pub fn process_tree_json(root: Option<u32>) -> Result<Value, String> {
+	let rows = ProcessSnapshot::capture()
+		.processes
+		.into_iter()
+		.map(|(pid, p)| ProcRow {
+			pid,
+			parent_pid: p.parent_pid,
+			name: p.name,
+			start_time: p.start_time,
+		})
+		.collect();
+	build_tree(rows, root)
+}
+
+/// Flat, breadth-first node list; each node names its `children` by PID so
+/// clients can walk the tree without deeply nested JSON.
+///
+/// A parent link is only kept when the parent started no later than the
+/// child. Otherwise the original parent exited and its PID was handed to a
+/// newer process, and following it could form a cycle.
+pub fn build_tree(rows: Vec<ProcRow>, root: Option<u32>) -> Result<Value, String> {
+	let by_pid: HashMap<u32, &ProcRow> = rows.iter().map(|r| (r.pid, r)).collect();
+	let parent_of = |row: &ProcRow| {
+		row.parent_pid.filter(|pp| {
+			*pp != row.pid && by_pid.get(pp).map(|p| p.start_time <= row.start_time).unwrap_or(false)
+		})
+	};
+
+	let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+	let mut roots = Vec::new();
+	for row in &rows {
+		match parent_of(row) {
+			Some(pp) => children.entry(pp).or_default().push(row.pid),
+			None => roots.push(row.pid),
+		}
+	}
+	for list in children.values_mut() {
+		list.sort_unstable();
+	}
+	roots.sort_unstable();
+
+	let starts = match root {
+		Some(pid) if by_pid.contains_key(&pid) => vec![pid],
+		Some(pid) => return Err(format!("Process {} not found", pid)),
+		None => roots,
+	};
+
+	let mut nodes = Vec::new();
+	let mut visited = HashSet::new();
+	let mut queue: VecDeque<(u32, u32)> = starts.iter().map(|pid| (*pid, 0)).collect();
+	loop {
+		while let Some((pid, depth)) = queue.pop_front() {
+			if !visited.insert(pid) {
+				continue;
+			}
+			let row = by_pid[&pid];
+			let kids = children.get(&pid).cloned().unwrap_or_default();
+			queue.extend(kids.iter().map(|c| (*c, depth + 1)));
+			nodes.push(json!({
+				"pid": pid,
+				"parent_pid": parent_of(row),
+				"name": row.name,
+				"depth": depth,
+				"children": kids,
+			}));
+		}
+		// Processes started in the same second can still point at each other;
+		// break such loops by promoting the lowest unvisited PID to a root.
+		if root.is_some() {
+			break;
+		}
+		match rows.iter().map(|r| r.pid).filter(|pid| !visited.contains(pid)).min() {
+			Some(pid) => queue.push_back((pid, 0)),
+			None => break,
+		}
+	}
+
+	Ok(json!({
+		"root": root,
+		"count": nodes.len(),
+		"nodes": nodes,
+	}))
+}
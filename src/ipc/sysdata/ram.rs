@@ -4,17 +4,18 @@ use serde_json::{json, Value};
 use std::os::windows::process::CommandExt;
 use std::process::Command;
 use sysinfo::System;
+use super::process_snapshot::ProcessSnapshot;
+use super::static_hw::static_hardware;
 
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-pub fn get_ram_json() -> Value {
-	let mut sys = System::new_all();
-	sys.refresh_all();
+pub fn get_ram_json(snapshot: &ProcessSnapshot) -> Value {
+	let mut sys = System::new();
+	sys.refresh_memory();
 
 	let total = sys.total_memory();
 	let used = sys.used_memory();
 	let available = sys.available_memory();
-	let free = sys.free_memory();
 	let total_swap = sys.total_swap();
 	let used_swap = sys.used_swap();
 	let free_swap = sys.free_swap();
@@ -32,33 +33,30 @@ pub fn get_ram_json() -> Value {
 	};
 
 	// Top memory-consuming processes (top 10)
-	let mut processes: Vec<(&sysinfo::Pid, &sysinfo::Process)> = sys.processes().iter().collect();
-	processes.sort_by(|a, b| b.1.memory().cmp(&a.1.memory()));
-
-	let top_processes: Vec<Value> = processes
-		.iter()
+	let top_processes: Vec<Value> = snapshot
+		.by_memory()
+		.into_iter()
 		.take(10)
 		.map(|(pid, proc_info)| {
 			json!({
-				"pid": pid.as_u32(),
-				"name": proc_info.name().to_string_lossy(),
-				"memory_bytes": proc_info.memory(),
-				"virtual_memory_bytes": proc_info.virtual_memory(),
+				"pid": pid,
+				"name": proc_info.name,
+				"memory_bytes": proc_info.working_set_bytes,
+				"virtual_memory_bytes": proc_info.virtual_memory_bytes,
 			})
 		})
 		.collect();
 
-	// Query hardware RAM details (speed, slots, form factor, etc.)
-	let hw = query_ram_hardware();
+	// Speed, slots, form factor and sticks don't change while running.
+	let hw = &static_hardware().ram;
 
 	// Query OS memory counters (committed, cached, paged/non-paged pool, hardware reserved)
 	let counters = query_memory_counters(total);
 
-	json!({
+	let mut ram = json!({
 		"total_bytes": total,
 		"used_bytes": used,
 		"available_bytes": available,
-		"free_bytes": free,
 		"usage_percent": usage_percent,
 		"swap_total_bytes": total_swap,
 		"swap_used_bytes": used_swap,
@@ -78,10 +76,17 @@ pub fn get_ram_json() -> Value {
 		"non_paged_pool_bytes": counters.get("non_paged_pool_bytes").cloned().unwrap_or(Value::Null),
 		"compressed_bytes": counters.get("compressed_bytes").cloned().unwrap_or(Value::Null),
 		"top_processes": top_processes,
-	})
+	});
+	// Page list sizes are left out entirely where Windows doesn't report them.
+	for key in ["standby_bytes", "modified_bytes", "free_bytes"] {
+		if let Some(v) = counters.get(key) {
+			ram[key] = v.clone();
+		}
+	}
+	ram
 }
 
-fn query_ram_hardware() -> Value {
+pub(super) fn query_ram_hardware() -> Value {
 	let script = r#"$ErrorActionPreference='SilentlyContinue';
 $sticks = Get-CimInstance Win32_PhysicalMemory -ErrorAction SilentlyContinue;
 $totalSlots = (Get-CimInstance Win32_PhysicalMemoryArray -ErrorAction SilentlyContinue | Select-Object -First 1).MemoryDevices;
@@ -227,6 +232,11 @@ if ($perf) {
 	"CacheBytes=$($perf.CacheBytes)";
 	"PoolPagedBytes=$($perf.PoolPagedBytes)";
 	"PoolNonpagedBytes=$($perf.PoolNonpagedBytes)";
+	"StandbyCacheCoreBytes=$($perf.StandbyCacheCoreBytes)";
+	"StandbyCacheNormalPriorityBytes=$($perf.StandbyCacheNormalPriorityBytes)";
+	"StandbyCacheReserveBytes=$($perf.StandbyCacheReserveBytes)";
+	"ModifiedPageListBytes=$($perf.ModifiedPageListBytes)";
+	"FreeAndZeroPageListBytes=$($perf.FreeAndZeroPageListBytes)";
 }
 $cs = Get-CimInstance Win32_ComputerSystem -ErrorAction SilentlyContinue | Select-Object -First 1;
 if ($cs) {
@@ -253,6 +263,9 @@ if ($compressed) { "CompressedPages=$compressed" }
 	let mut pool_nonpaged: Option<u64> = None;
 	let mut cs_total_physical: Option<u64> = None;
 	let mut compressed_pages: Option<f64> = None;
+	let mut standby_parts: [Option<u64>; 3] = [None; 3];
+	let mut modified: Option<u64> = None;
+	let mut free: Option<u64> = None;
 
 	for raw in text.lines() {
 		let line = raw.trim();
@@ -264,6 +277,11 @@ if ($compressed) { "CompressedPages=$compressed" }
 		else if let Some(v) = line.strip_prefix("PoolNonpagedBytes=") { pool_nonpaged = v.trim().parse().ok(); }
 		else if let Some(v) = line.strip_prefix("TotalPhysicalMemory=") { cs_total_physical = v.trim().parse().ok(); }
 		else if let Some(v) = line.strip_prefix("CompressedPages=") { compressed_pages = v.trim().parse().ok(); }
+		else if let Some(v) = line.strip_prefix("StandbyCacheCoreBytes=") { standby_parts[0] = v.trim().parse().ok(); }
+		else if let Some(v) = line.strip_prefix("StandbyCacheNormalPriorityBytes=") { standby_parts[1] = v.trim().parse().ok(); }
+		else if let Some(v) = line.strip_prefix("StandbyCacheReserveBytes=") { standby_parts[2] = v.trim().parse().ok(); }
+		else if let Some(v) = line.strip_prefix("ModifiedPageListBytes=") { modified = v.trim().parse().ok(); }
+		else if let Some(v) = line.strip_prefix("FreeAndZeroPageListBytes=") { free = v.trim().parse().ok(); }
 	}
 
 	// Hardware reserved = physical installed - OS visible
@@ -279,7 +297,17 @@ if ($compressed) { "CompressedPages=$compressed" }
 	// Compressed memory: pages * 4096
 	let compressed_bytes = compressed_pages.map(|p| (p as u64) * 4096);
 
-	json!({
+	// Standby is the sum of its three priority bands; it counts only if all
+	// of them were read.
+	let standby = standby_parts.iter().try_fold(0u64, |sum, part| part.map(|p| sum + p));
+
+	// In use + modified + standby + free make up the visible total. A sample
+	// whose lists add up to more than that is skewed, so it is dropped.
+	let visible = total_visible_kb.map(|kb| kb * 1024).unwrap_or(total_physical);
+	let lists = [standby, modified, free].iter().flatten().sum::<u64>();
+	let (standby, modified, free) = if lists > visible { (None, None, None) } else { (standby, modified, free) };
+
+	let mut counters = json!({
 		"hardware_reserved_bytes": hardware_reserved,
 		"committed_bytes": committed,
 		"commit_limit_bytes": commit_limit,
@@ -287,5 +315,11 @@ if ($compressed) { "CompressedPages=$compressed" }
 		"paged_pool_bytes": pool_paged,
 		"non_paged_pool_bytes": pool_nonpaged,
 		"compressed_bytes": compressed_bytes,
-	})
+	});
+	for (key, value) in [("standby_bytes", standby), ("modified_bytes", modified), ("free_bytes", free)] {
+		if let Some(v) = value {
+			counters[key] = json!(v);
+		}
+	}
+	counters
 }
@@ -0,0 +1,264 @@
+// ~/veil/veil-backend/src/ipc/sysdata/smart.rs
+//
+// SMART / NVMe health readings per physical disk, read straight from the
+// drive with DeviceIoControl. Drives are queried on a background thread and
+// the storage collector only reads the cache, so a slow or hung disk never
+// holds up the slow-tier updater. Without admin rights most drives refuse
+// the handle and every field stays `null`.
+
+use serde_json::{json, Value};
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Mutex, OnceLock,
+	},
+	thread,
+	time::{Duration, Instant},
+};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::Storage::FileSystem::{
+	CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows::Win32::System::IO::DeviceIoControl;
+
+/// SMART data changes slowly; re-read drives at most this often.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+const GENERIC_READ: u32 = 0x8000_0000;
+const GENERIC_WRITE: u32 = 0x4000_0000;
+const IOCTL_STORAGE_QUERY_PROPERTY: u32 = 0x002D_1400;
+const SMART_RCV_DRIVE_DATA: u32 = 0x0007_C088;
+
+// STORAGE_PROPERTY_QUERY for the NVMe SMART / Health Information log page.
+const STORAGE_DEVICE_PROTOCOL_SPECIFIC_PROPERTY: u32 = 50;
+const PROPERTY_STANDARD_QUERY: u32 = 0;
+const PROTOCOL_TYPE_NVME: u32 = 3;
+const NVME_DATA_TYPE_LOG_PAGE: u32 = 2;
+const NVME_LOG_PAGE_HEALTH_INFO: u32 = 2;
+/// Size of STORAGE_PROTOCOL_SPECIFIC_DATA (ten u32 fields).
+const PROTOCOL_SPECIFIC_DATA_LEN: usize = 40;
+const NVME_LOG_LEN: usize = 512;
+
+struct SmartCache {
+	by_disk: HashMap<u32, Value>,
+	last_refresh: Option<Instant>,
+}
+
+static CACHE: OnceLock<Mutex<SmartCache>> = OnceLock::new();
+static REFRESHING: AtomicBool = AtomicBool::new(false);
+
+fn cache() -> &'static Mutex<SmartCache> {
+	CACHE.get_or_init(|| Mutex::new(SmartCache { by_disk: HashMap::new(), last_refresh: None }))
+}
+
+fn empty_smart() -> Value {
+	json!({
+		"temperature_c": Value::Null,
+		"power_on_hours": Value::Null,
+		"reallocated_sectors": Value::Null,
+		"wear_leveling_percent": Value::Null,
+	})
+}
+
+/// Cached SMART block for `disk_number`; all `null` until the first read finishes.
+pub fn smart_for_disk(disk_number: u32) -> Value {
+	cache()
+		.lock()
+		.unwrap()
+		.by_disk
+		.get(&disk_number)
+		.cloned()
+		.unwrap_or_else(empty_smart)
+}
+
+/// Start a background re-read of `disks` (`(disk_number, bus_type)`) if the
+/// cache is stale and no refresh is already running. Returns immediately.
+pub fn refresh_if_due(disks: Vec<(u32, String)>) {
+	let due = cache()
+		.lock()
+		.unwrap()
+		.last_refresh
+		.map(|t| t.elapsed() >= REFRESH_INTERVAL)
+		.unwrap_or(true);
+	if !due || disks.is_empty() || REFRESHING.swap(true, Ordering::SeqCst) {
+		return;
+	}
+
+	let spawned = thread::Builder::new().name("veil-smart".into()).spawn(move || {
+		let mut results = HashMap::new();
+		for (disk_number, bus_type) in disks {
+			results.insert(disk_number, read_disk(disk_number, &bus_type));
+		}
+		let mut cache = cache().lock().unwrap();
+		cache.by_disk = results;
+		cache.last_refresh = Some(Instant::now());
+		REFRESHING.store(false, Ordering::SeqCst);
+	});
+	if spawned.is_err() {
+		REFRESHING.store(false, Ordering::SeqCst);
+	}
+}
+
+struct DiskHandle(HANDLE);
+
+impl Drop for DiskHandle {
+	fn drop(&mut self) {
+		unsafe {
+			let _ = CloseHandle(self.0);
+		}
+	}
+}
+
+fn open_disk(disk_number: u32) -> Option<DiskHandle> {
+	let path: Vec<u16> = format!("\\\\.\\PhysicalDrive{}", disk_number)
+		.encode_utf16()
+		.chain(std::iter::once(0))
+		.collect();
+	unsafe {
+		CreateFileW(
+			PCWSTR(path.as_ptr()),
+			GENERIC_READ | GENERIC_WRITE,
+			FILE_SHARE_READ | FILE_SHARE_WRITE,
+			None,
+			OPEN_EXISTING,
+			FILE_FLAGS_AND_ATTRIBUTES(0),
+			None,
+		)
+	}
+	.ok()
+	.map(DiskHandle)
+}
+
+fn ioctl(handle: &DiskHandle, code: u32, input: &[u8], output: &mut [u8]) -> bool {
+	let mut returned = 0u32;
+	unsafe {
+		DeviceIoControl(
+			handle.0,
+			code,
+			Some(input.as_ptr() as *const _),
+			input.len() as u32,
+			Some(output.as_mut_ptr() as *mut _),
+			output.len() as u32,
+			Some(&mut returned),
+			None,
+		)
+	}
+	.is_ok()
+}
+
+fn read_disk(disk_number: u32, bus_type: &str) -> Value {
+	let Some(handle) = open_disk(disk_number) else { return empty_smart() };
+	let is_nvme = bus_type.eq_ignore_ascii_case("nvme");
+
+	let parsed = if is_nvme {
+		read_nvme_health(&handle)
+	} else {
+		read_ata_attributes(&handle)
+	};
+	parsed.unwrap_or_else(empty_smart)
+}
+
+fn le_u32(b: &[u8], at: usize) -> u32 {
+	u32::from_le_bytes([b[at], b[at + 1], b[at + 2], b[at + 3]])
+}
+
+/// NVMe SMART / Health Information (log page 02h).
+fn read_nvme_health(handle: &DiskHandle) -> Option<Value> {
+	let header = 8 + PROTOCOL_SPECIFIC_DATA_LEN;
+	let mut query = vec![0u8; header + NVME_LOG_LEN];
+	let fields = [
+		STORAGE_DEVICE_PROTOCOL_SPECIFIC_PROPERTY,
+		PROPERTY_STANDARD_QUERY,
+		PROTOCOL_TYPE_NVME,
+		NVME_DATA_TYPE_LOG_PAGE,
+		NVME_LOG_PAGE_HEALTH_INFO,
+		0,
+		PROTOCOL_SPECIFIC_DATA_LEN as u32,
+		NVME_LOG_LEN as u32,
+	];
+	for (i, v) in fields.iter().enumerate() {
+		query[i * 4..i * 4 + 4].copy_from_slice(&v.to_le_bytes());
+	}
+
+	let mut out = vec![0u8; header + NVME_LOG_LEN];
+	if !ioctl(handle, IOCTL_STORAGE_QUERY_PROPERTY, &query, &mut out) {
+		return None;
+	}
+
+	// STORAGE_PROTOCOL_DATA_DESCRIPTOR: Version, Size, then the specific data
+	// whose ProtocolDataOffset is relative to itself.
+	let offset = le_u32(&out, 8 + 16) as usize;
+	let log = out.get(8 + offset..8 + offset + NVME_LOG_LEN)?;
+
+	let temp_k = u16::from_le_bytes([log[1], log[2]]);
+	let percent_used = log[5];
+	let power_on_hours = u64::from_le_bytes(log[128..136].try_into().ok()?);
+
+	Some(json!({
+		"temperature_c": if temp_k > 0 { json!(temp_k as i32 - 273) } else { Value::Null },
+		"power_on_hours": power_on_hours,
+		// NVMe has no reallocated-sector counter; media errors are the closest analogue.
+		"reallocated_sectors": Value::Null,
+		"media_errors": u64::from_le_bytes(log[160..168].try_into().ok()?),
+		"wear_leveling_percent": 100u8.saturating_sub(percent_used),
+		"source": "nvme",
+	}))
+}
+
+/// Standard ATA SMART attribute table (SMART READ DATA).
+fn read_ata_attributes(handle: &DiskHandle) -> Option<Value> {
+	// SENDCMDINPARAMS (packed): cBufferSize, IDEREGS, bDriveNumber, reserved.
+	let mut input = [0u8; 33];
+	input[0..4].copy_from_slice(&512u32.to_le_bytes());
+	input[4] = 0xD0; // features: SMART READ DATA
+	input[5] = 1; // sector count
+	input[6] = 1; // sector number
+	input[7] = 0x4F; // cylinder low
+	input[8] = 0xC2; // cylinder high
+	input[9] = 0xA0; // drive/head
+	input[10] = 0xB0; // command: SMART
+
+	// SENDCMDOUTPARAMS: cBufferSize (4) + DRIVERSTATUS (12) + 512-byte buffer.
+	let mut out = vec![0u8; 16 + 512];
+	if !ioctl(handle, SMART_RCV_DRIVE_DATA, &input, &mut out) {
+		return None;
+	}
+	let data = &out[16..];
+
+	let mut attrs = HashMap::<u8, (u8, u64)>::new();
+	for i in 0..30 {
+		let at = 2 + i * 12;
+		let id = data[at];
+		if id == 0 {
+			continue;
+		}
+		let current = data[at + 3];
+		let mut raw = [0u8; 8];
+		raw[..6].copy_from_slice(&data[at + 5..at + 11]);
+		attrs.insert(id, (current, u64::from_le_bytes(raw)));
+	}
+	if attrs.is_empty() {
+		return None;
+	}
+
+	// 194 is the usual temperature attribute; its low byte is °C.
+	let temperature_c = attrs
+		.get(&194)
+		.or_else(|| attrs.get(&190))
+		.map(|(_, raw)| (raw & 0xFF) as i64);
+	// Vendors disagree on the SSD life attribute; the normalized value is percent remaining.
+	let wear = [177u8, 231, 233, 202]
+		.iter()
+		.find_map(|id| attrs.get(id))
+		.map(|(current, _)| (*current).min(100));
+
+	Some(json!({
+		"temperature_c": temperature_c,
+		"power_on_hours": attrs.get(&9).map(|(_, raw)| raw & 0xFFFF_FFFF),
+		"reallocated_sectors": attrs.get(&5).map(|(_, raw)| raw & 0xFFFF_FFFF),
+		"wear_leveling_percent": wear,
+		"source": "ata",
+	}))
+}
@@ -0,0 +1,55 @@
+// ~/veil/veil-backend/src/ipc/sysdata/static_hw.rs
+//
+// Hardware facts that can't change while VEIL runs: CPU caches and sockets,
+// RAM sticks and slots, BIOS and baseboard. Each used to cost a WMI round
+// trip per tick; they are gathered once and the collectors merge them into
+// every snapshot, re-sampling only the volatile fields.
+
+use serde_json::Value;
+use std::sync::OnceLock;
+use std::time::Instant;
+use sysinfo::System;
+
+use crate::{info, warn};
+
+pub struct StaticHardwareInfo {
+	pub physical_cores: Option<usize>,
+	/// Base speed, caches, sockets, virtualization (`cpu::query_cpu_static`).
+	pub cpu: Value,
+	/// Speed, form factor, slots and sticks (`ram::query_ram_hardware`).
+	pub ram: Value,
+	pub bios: Value,
+	pub baseboard: Value,
+}
+
+static STATIC_HW: OnceLock<StaticHardwareInfo> = OnceLock::new();
+
+/// The session's hardware info, gathered on first use.
+pub fn static_hardware() -> &'static StaticHardwareInfo {
+	STATIC_HW.get_or_init(|| {
+		let started = Instant::now();
+		let (bios, baseboard) = super::system::query_firmware_info();
+		let hw = StaticHardwareInfo {
+			physical_cores: System::physical_core_count(),
+			cpu: super::cpu::query_cpu_static(),
+			ram: super::ram::query_ram_hardware(),
+			bios,
+			baseboard,
+		};
+		info!("[sysdata] Static hardware info gathered in {}ms", started.elapsed().as_millis());
+		hw
+	})
+}
+
+/// Gather in the background at startup so the first collector tick doesn't
+/// pay for it.
+pub fn prefetch() {
+	let spawned = std::thread::Builder::new()
+		.name("veil-static-hw".into())
+		.spawn(|| {
+			static_hardware();
+		});
+	if let Err(e) = spawned {
+		warn!("[sysdata] Failed to start static hardware prefetch: {}", e);
+	}
+}
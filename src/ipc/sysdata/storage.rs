@@ -5,11 +5,14 @@ use std::os::windows::process::CommandExt;
 use std::process::Command;
 use sysinfo::Disks;
 
+use super::smart;
+
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
 pub fn get_storage_json() -> Value {
 	let disks = Disks::new_with_refreshed_list();
-	let physical_disks = query_physical_disks();
+	let mut physical_disks = query_physical_disks();
+	attach_smart(&mut physical_disks);
 
 	let mut total_bytes: u64 = 0;
 	let mut available_bytes: u64 = 0;
@@ -68,6 +71,7 @@ pub fn get_storage_json() -> Value {
 				if let Some(v) = pd.get("system_disk") { obj.insert("system_disk".into(), v.clone()); }
 				if let Some(v) = pd.get("page_file_disk") { obj.insert("page_file_disk".into(), v.clone()); }
 				if let Some(v) = pd.get("health_status") { obj.insert("health_status".into(), v.clone()); }
+				if let Some(v) = pd.get("smart") { obj.insert("smart".into(), v.clone()); }
 			}
 
 			entry
@@ -92,6 +96,27 @@ pub fn get_storage_json() -> Value {
 	})
 }
 
+/// Add the cached `smart` block to each physical disk and kick off a
+/// background re-read when it's stale.
+fn attach_smart(physical_disks: &mut [Value]) {
+	let targets: Vec<(u32, String)> = physical_disks
+		.iter()
+		.filter_map(|pd| {
+			let number = pd.get("disk_number").and_then(|v| v.as_u64())? as u32;
+			let bus = pd.get("bus_type").and_then(|v| v.as_str()).unwrap_or("").to_string();
+			Some((number, bus))
+		})
+		.collect();
+	smart::refresh_if_due(targets);
+
+	for pd in physical_disks.iter_mut() {
+		let Some(number) = pd.get("disk_number").and_then(|v| v.as_u64()) else { continue };
+		if let Some(obj) = pd.as_object_mut() {
+			obj.insert("smart".into(), smart::smart_for_disk(number as u32));
+		}
+	}
+}
+
 fn query_physical_disks() -> Vec<Value> {
 	let script = r#"$ErrorActionPreference='SilentlyContinue';
 $physDisks = Get-PhysicalDisk -ErrorAction SilentlyContinue;
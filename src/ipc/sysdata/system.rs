@@ -1,12 +1,24 @@
 // ~/veil/veil-backend/src/ipc/sysdata/system.rs
 
+use chrono::{DateTime, Local};
 use serde_json::{json, Value};
 use std::os::windows::process::CommandExt;
 use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use sysinfo::System;
 
+use super::static_hw::static_hardware;
+use super::time::boot_clock;
+
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+/// Reading the event log is a PowerShell spawn, so the last wake time is
+/// re-read at most this often.
+const WAKE_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+static LAST_WAKE: Mutex<Option<(Option<u64>, Instant)>> = Mutex::new(None);
+
 pub fn get_system_json() -> Value {
 	let os_name = System::name().unwrap_or_else(|| "unknown".into());
 	let os_long = System::long_os_version().unwrap_or_else(|| "unknown".into());
@@ -23,9 +35,9 @@ pub fn get_system_json() -> Value {
 
 	let locale = get_system_locale();
 	let theme = get_windows_theme();
-	let bios_info = get_bios_info();
-	let motherboard_info = get_motherboard_info();
-	let uptime_seconds = System::uptime();
+	let hw = static_hardware();
+	let (uptime_seconds, boot_time_unix) = boot_clock();
+	let last_wake_unix = last_wake_unix().filter(|t| *t >= boot_time_unix);
 
 	json!({
 		"os": {
@@ -42,13 +54,66 @@ pub fn get_system_json() -> Value {
 		"user_domain": user_domain,
 		"user_profile": user_profile,
 		"uptime_seconds": uptime_seconds,
+		"uptime_breakdown": uptime_breakdown(uptime_seconds),
+		"boot_time_unix": boot_time_unix,
+		"boot_time_iso": unix_to_iso(boot_time_unix),
+		"last_wake_unix": last_wake_unix,
+		"last_wake_time": last_wake_unix.map(unix_to_iso),
 		"locale": locale,
 		"theme": theme,
-		"bios": bios_info,
-		"motherboard": motherboard_info,
+		"bios": hw.bios,
+		"baseboard": hw.baseboard,
+		"motherboard": hw.baseboard,
+	})
+}
+
+fn uptime_breakdown(secs: u64) -> Value {
+	json!({
+		"days": secs / 86_400,
+		"hours": secs % 86_400 / 3600,
+		"minutes": secs % 3600 / 60,
+		"seconds": secs % 60,
 	})
 }
 
+fn unix_to_iso(secs: u64) -> String {
+	DateTime::from_timestamp(secs as i64, 0)
+		.map(|t| t.with_timezone(&Local).to_rfc3339())
+		.unwrap_or_default()
+}
+
+/// When the machine last resumed from sleep or hibernate, from the
+/// Power-Troubleshooter "returned from a low power state" event.
+fn last_wake_unix() -> Option<u64> {
+	let mut cached = LAST_WAKE.lock().unwrap();
+	if let Some((value, at)) = *cached {
+		if at.elapsed() < WAKE_REFRESH_INTERVAL {
+			return value;
+		}
+	}
+	let value = query_last_wake_unix();
+	*cached = Some((value, Instant::now()));
+	value
+}
+
+fn query_last_wake_unix() -> Option<u64> {
+	let script = r#"$ErrorActionPreference='SilentlyContinue';
+$e = Get-WinEvent -FilterHashtable @{LogName='System'; ProviderName='Microsoft-Windows-Power-Troubleshooter'; Id=1} -MaxEvents 1 -ErrorAction SilentlyContinue;
+if ($e) { "WakeUnix=$(([DateTimeOffset]$e.TimeCreated).ToUnixTimeSeconds())" }"#;
+
+	let output = Command::new("powershell")
+		.creation_flags(CREATE_NO_WINDOW)
+		.args(["-NoProfile", "-NonInteractive", "-Command", script])
+		.output()
+		.ok()?;
+	if !output.status.success() {
+		return None;
+	}
+	String::from_utf8_lossy(&output.stdout)
+		.lines()
+		.find_map(|line| line.trim().strip_prefix("WakeUnix=")?.trim().parse::<u64>().ok())
+}
+
 fn get_system_locale() -> Value {
 	let script = r#"$ErrorActionPreference='SilentlyContinue';
 $culture = [System.Globalization.CultureInfo]::CurrentCulture;
@@ -215,68 +280,46 @@ $colorizePrev = (Get-ItemProperty -Path $colorPath -Name ColorPrevalence -ErrorA
 	})
 }
 
-fn get_bios_info() -> Value {
-	let script = r#"$ErrorActionPreference='SilentlyContinue';
-$bios = Get-CimInstance -ClassName Win32_BIOS -ErrorAction SilentlyContinue | Select-Object -First 1;
-if ($bios) {
-	"Manufacturer=$($bios.Manufacturer)";
-	"Name=$($bios.Name)";
-	"Version=$($bios.SMBIOSBIOSVersion)";
-	"ReleaseDate=$($bios.ReleaseDate)";
-	"SerialNumber=$($bios.SerialNumber)";
-}"#;
-
-	let output = Command::new("powershell")
-		.creation_flags(CREATE_NO_WINDOW)
-		.args(["-NoProfile", "-NonInteractive", "-Command", script])
-		.output();
-
-	let Ok(output) = output else {
-		return Value::Null;
-	};
-	if !output.status.success() {
-		return Value::Null;
-	}
-
-	let text = String::from_utf8_lossy(&output.stdout);
-	let mut manufacturer = String::new();
-	let mut name = String::new();
-	let mut version = String::new();
-	let mut release_date = String::new();
-	let mut serial = String::new();
-
-	for raw in text.lines() {
-		let line = raw.trim();
-		if let Some(v) = line.strip_prefix("Manufacturer=") {
-			manufacturer = v.trim().to_string();
-		} else if let Some(v) = line.strip_prefix("Name=") {
-			name = v.trim().to_string();
-		} else if let Some(v) = line.strip_prefix("Version=") {
-			version = v.trim().to_string();
-		} else if let Some(v) = line.strip_prefix("ReleaseDate=") {
-			release_date = v.trim().to_string();
-		} else if let Some(v) = line.strip_prefix("SerialNumber=") {
-			serial = v.trim().to_string();
-		}
+/// OEM filler that some boards leave in SMBIOS string fields.
+const SMBIOS_PLACEHOLDERS: &[&str] = &[
+	"to be filled by o.e.m.",
+	"default string",
+	"system serial number",
+	"base board serial number",
+	"not applicable",
+	"not specified",
+	"none",
+	"n/a",
+];
+
+/// A trimmed SMBIOS string, or null when it is empty or OEM filler.
+fn smbios_value(raw: &str) -> Value {
+	let v = raw.trim();
+	if v.is_empty() || SMBIOS_PLACEHOLDERS.contains(&v.to_ascii_lowercase().as_str()) || v.chars().all(|c| c == '0') {
+		Value::Null
+	} else {
+		json!(v)
 	}
-
-	json!({
-		"manufacturer": if manufacturer.is_empty() { Value::Null } else { json!(manufacturer) },
-		"name": if name.is_empty() { Value::Null } else { json!(name) },
-		"version": if version.is_empty() { Value::Null } else { json!(version) },
-		"release_date": if release_date.is_empty() { Value::Null } else { json!(release_date) },
-		"serial_number": if serial.is_empty() { Value::Null } else { json!(serial) },
-	})
 }
 
-fn get_motherboard_info() -> Value {
+/// BIOS and baseboard identity, read once per run via `static_hw`.
+pub(super) fn query_firmware_info() -> (Value, Value) {
+	// Serial numbers can be blank without admin rights; they read as null then.
 	let script = r#"$ErrorActionPreference='SilentlyContinue';
+$bios = Get-CimInstance -ClassName Win32_BIOS -ErrorAction SilentlyContinue | Select-Object -First 1;
+if ($bios) {
+	"BiosManufacturer=$($bios.Manufacturer)";
+	"BiosName=$($bios.Name)";
+	"BiosVersion=$($bios.SMBIOSBIOSVersion)";
+	if ($bios.ReleaseDate) { "BiosReleaseDate=$($bios.ReleaseDate.ToString('yyyy-MM-dd'))" };
+	"BiosSerialNumber=$($bios.SerialNumber)";
+}
 $board = Get-CimInstance -ClassName Win32_BaseBoard -ErrorAction SilentlyContinue | Select-Object -First 1;
 if ($board) {
-	"Manufacturer=$($board.Manufacturer)";
-	"Product=$($board.Product)";
-	"Version=$($board.Version)";
-	"SerialNumber=$($board.SerialNumber)";
+	"BoardManufacturer=$($board.Manufacturer)";
+	"BoardProduct=$($board.Product)";
+	"BoardVersion=$($board.Version)";
+	"BoardSerialNumber=$($board.SerialNumber)";
 }"#;
 
 	let output = Command::new("powershell")
@@ -285,35 +328,38 @@ if ($board) {
 		.output();
 
 	let Ok(output) = output else {
-		return Value::Null;
+		return (Value::Null, Value::Null);
 	};
 	if !output.status.success() {
-		return Value::Null;
+		return (Value::Null, Value::Null);
 	}
 
 	let text = String::from_utf8_lossy(&output.stdout);
-	let mut manufacturer = String::new();
-	let mut product = String::new();
-	let mut version = String::new();
-	let mut serial = String::new();
+	let field = |key: &str| {
+		text.lines()
+			.find_map(|line| line.trim().strip_prefix(key)?.strip_prefix('='))
+			.map(smbios_value)
+			.unwrap_or(Value::Null)
+	};
 
-	for raw in text.lines() {
-		let line = raw.trim();
-		if let Some(v) = line.strip_prefix("Manufacturer=") {
-			manufacturer = v.trim().to_string();
-		} else if let Some(v) = line.strip_prefix("Product=") {
-			product = v.trim().to_string();
-		} else if let Some(v) = line.strip_prefix("Version=") {
-			version = v.trim().to_string();
-		} else if let Some(v) = line.strip_prefix("SerialNumber=") {
-			serial = v.trim().to_string();
-		}
-	}
+	let bios_vendor = field("BiosManufacturer");
+	let bios = json!({
+		"vendor": bios_vendor,
+		"manufacturer": bios_vendor,
+		"name": field("BiosName"),
+		"version": field("BiosVersion"),
+		"release_date": field("BiosReleaseDate"),
+		"serial_number": field("BiosSerialNumber"),
+	});
 
-	json!({
-		"manufacturer": if manufacturer.is_empty() { Value::Null } else { json!(manufacturer) },
-		"product": if product.is_empty() { Value::Null } else { json!(product) },
-		"version": if version.is_empty() { Value::Null } else { json!(version) },
-		"serial_number": if serial.is_empty() { Value::Null } else { json!(serial) },
-	})
+	let board_serial = field("BoardSerialNumber");
+	let baseboard = json!({
+		"manufacturer": field("BoardManufacturer"),
+		"product": field("BoardProduct"),
+		"version": field("BoardVersion"),
+		"serial": board_serial,
+		"serial_number": board_serial,
+	});
+
+	(bios, baseboard)
 }
@@ -1,7 +1,123 @@
 use chrono::{Local, Datelike, Timelike, Utc};
 use serde_json::json;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex, OnceLock,
+};
+use std::time::{Duration, Instant};
 use sysinfo::System;
 
+/// w32tm is a process spawn, far too slow for the fast tier; its status is
+/// refreshed in the background at most this often.
+const SYNC_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Default)]
+struct SyncStatus {
+    synchronized: bool,
+    last_sync_time: Option<String>,
+    source: Option<String>,
+}
+
+static SYNC: OnceLock<Mutex<(SyncStatus, Option<Instant>)>> = OnceLock::new();
+static SYNC_REFRESHING: AtomicBool = AtomicBool::new(false);
+
+/// Last known clock sync status; kicks off a refresh when it is stale.
+fn sync_status() -> SyncStatus {
+    let cell = SYNC.get_or_init(|| Mutex::new((SyncStatus::default(), None)));
+    let (status, due) = {
+        let guard = cell.lock().unwrap();
+        let due = guard.1.map(|t| t.elapsed() >= SYNC_REFRESH_INTERVAL).unwrap_or(true);
+        (guard.0.clone(), due)
+    };
+    if due && !SYNC_REFRESHING.swap(true, Ordering::SeqCst) {
+        let spawned = std::thread::Builder::new().name("veil-w32time".into()).spawn(move || {
+            let fresh = query_sync_status();
+            *cell.lock().unwrap() = (fresh, Some(Instant::now()));
+            SYNC_REFRESHING.store(false, Ordering::SeqCst);
+        });
+        if spawned.is_err() {
+            SYNC_REFRESHING.store(false, Ordering::SeqCst);
+        }
+    }
+    status
+}
+
+/// Parse `w32tm /query /status`. A stopped or disabled service makes w32tm
+/// fail, which reads as "not synchronized" with no source.
+#[cfg(target_os = "windows")]
+fn query_sync_status() -> SyncStatus {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let output = std::process::Command::new("w32tm")
+        .creation_flags(CREATE_NO_WINDOW)
+        .args(["/query", "/status"])
+        .output();
+    let Ok(output) = output else { return SyncStatus::default() };
+    if !output.status.success() {
+        return SyncStatus::default();
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut leap_unsynced = false;
+    let mut last_sync_time = None;
+    let mut source = None;
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match key.trim() {
+            // "3(not synchronized)" when the clock has never been set from a peer.
+            "Leap Indicator" => leap_unsynced = value.starts_with('3'),
+            "Last Successful Sync Time" if !value.eq_ignore_ascii_case("unspecified") => {
+                last_sync_time = Some(value.to_string());
+            }
+            "Source" if !value.is_empty() => source = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    // A free-running or CMOS source means the clock is only keeping local time.
+    let local_only = source
+        .as_deref()
+        .map(|s| s.contains("Free-running") || s.contains("Local CMOS"))
+        .unwrap_or(true);
+    SyncStatus {
+        synchronized: !leap_unsynced && !local_only && last_sync_time.is_some(),
+        last_sync_time,
+        source: source.filter(|_| !local_only),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn query_sync_status() -> SyncStatus {
+    SyncStatus::default()
+}
+
+/// `System::boot_time` is re-derived from the wall clock on every call, so it
+/// wobbles by a second between reads and jumps when the clock is stepped. The
+/// boot instant is pinned instead and only moved once it is off by more than this.
+const BOOT_DRIFT_TOLERANCE_SECS: u64 = 2;
+
+static BOOT_UNIX: Mutex<Option<u64>> = Mutex::new(None);
+
+/// Uptime in seconds and the boot instant as unix seconds, shared by every
+/// module that reports them. Uptime comes from the tick count, so NTP or
+/// manual clock changes can move the boot time but never make uptime negative.
+pub fn boot_clock() -> (u64, u64) {
+    let uptime = System::uptime();
+    let now = Utc::now().timestamp().max(0) as u64;
+    let derived = now.saturating_sub(uptime);
+    let mut pinned = BOOT_UNIX.lock().unwrap();
+    let boot = match *pinned {
+        Some(boot) if boot.abs_diff(derived) <= BOOT_DRIFT_TOLERANCE_SECS => boot,
+        _ => {
+            *pinned = Some(derived);
+            derived
+        }
+    };
+    (uptime, boot)
+}
+
 pub fn get_time_json() -> serde_json::Value {
     let now = Local::now();
     let utc_now = Utc::now();
@@ -18,8 +134,7 @@ pub fn get_time_json() -> serde_json::Value {
     let utc_offset_seconds = offset.local_minus_utc();
     let utc_offset_hours = utc_offset_seconds as f64 / 3600.0;
 
-    let uptime_seconds = System::uptime();
-    let boot_time_unix = System::boot_time();
+    let (uptime_seconds, boot_time_unix) = boot_clock();
 
     // Day of year (1-366)
     let day_of_year = date.ordinal();
@@ -39,6 +154,8 @@ pub fn get_time_json() -> serde_json::Value {
         if h == 0 { 12 } else { h }
     };
 
+    let sync = sync_status();
+
     json!({
         "ok": true,
         "iso": iso,
@@ -68,5 +185,8 @@ pub fn get_time_json() -> serde_json::Value {
         "human_time": now.format("%H:%M:%S").to_string(),
         "am_pm": am_pm,
         "hour_12": hour_12,
+        "ntp_synchronized": sync.synchronized,
+        "last_sync_time": sync.last_sync_time,
+        "source": sync.source,
     })
 }
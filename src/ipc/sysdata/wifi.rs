@@ -3,6 +3,8 @@
 use serde_json::{json, Value};
 use std::os::windows::process::CommandExt;
 use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
@@ -208,3 +210,129 @@ foreach ($a in $adapters) {
 
 	interfaces
 }
+
+/// Last scan result and when it was taken.
+static SCAN_CACHE: OnceLock<Mutex<Option<(Instant, Value)>>> = OnceLock::new();
+
+/// Nearby networks, one entry per access point (BSSID). Results are reused for
+/// `wifi_scan_cache_secs` unless `force` is set. A stopped WLAN service or a
+/// missing adapter yields an empty list with a `status`, not an error.
+pub fn scan_networks_json(force: bool) -> Value {
+	let cache = SCAN_CACHE.get_or_init(|| Mutex::new(None));
+	let ttl = Duration::from_secs(crate::config::wifi_scan_cache_secs());
+	if !force {
+		if let Some((at, value)) = cache.lock().unwrap().as_ref() {
+			if at.elapsed() < ttl {
+				let mut cached = value.clone();
+				cached["cached"] = json!(true);
+				return cached;
+			}
+		}
+	}
+
+	let result = run_scan();
+	*cache.lock().unwrap() = Some((Instant::now(), result.clone()));
+	result
+}
+
+fn scan_status(status: &str) -> Value {
+	json!({
+		"status": status,
+		"available": [],
+		"cached": false,
+		"scanned_at_unix": chrono::Utc::now().timestamp(),
+	})
+}
+
+fn run_scan() -> Value {
+	let output = Command::new("netsh")
+		.creation_flags(CREATE_NO_WINDOW)
+		.args(["wlan", "show", "networks", "mode=bssid"])
+		.output();
+
+	let Ok(output) = output else { return scan_status("unavailable") };
+	// netsh prints the reason to stdout and exits non-zero when WLAN isn't usable.
+	let text = String::from_utf8_lossy(&output.stdout).to_string();
+	let lower = text.to_ascii_lowercase();
+	if lower.contains("wlansvc") || lower.contains("autoconfig service") {
+		return scan_status("service_stopped");
+	}
+	if lower.contains("no wireless interface") {
+		return scan_status("no_adapter");
+	}
+	if !output.status.success() {
+		return scan_status("unavailable");
+	}
+
+	let mut available = Vec::<Value>::new();
+	let mut ssid = String::new();
+	let mut security = String::new();
+	let mut current: Option<serde_json::Map<String, Value>> = None;
+
+	let flush = |current: &mut Option<serde_json::Map<String, Value>>, available: &mut Vec<Value>| {
+		if let Some(mut ap) = current.take() {
+			// Older Windows builds don't print Band; derive it from the channel.
+			if ap.get("band").map(|b| b.is_null()).unwrap_or(true) {
+				let band = ap.get("channel").and_then(|c| c.as_u64()).map(|c| {
+					if c <= 14 { "2.4 GHz" } else if c <= 177 { "5 GHz" } else { "6 GHz" }
+				});
+				ap.insert("band".into(), json!(band));
+			}
+			available.push(Value::Object(ap));
+		}
+	};
+
+	for raw in text.lines() {
+		let line = raw.trim();
+		let Some((key, val)) = line.split_once(':') else { continue };
+		let key = key.trim();
+		let val = val.trim();
+
+		if key.starts_with("SSID") {
+			flush(&mut current, &mut available);
+			ssid = val.to_string();
+			security.clear();
+		} else if key == "Authentication" {
+			security = val.to_string();
+		} else if key.starts_with("BSSID") {
+			flush(&mut current, &mut available);
+			let mut ap = serde_json::Map::new();
+			ap.insert("ssid".into(), if ssid.is_empty() { Value::Null } else { json!(ssid) });
+			ap.insert("bssid".into(), json!(val));
+			ap.insert("signal_percent".into(), Value::Null);
+			ap.insert("channel".into(), Value::Null);
+			ap.insert("band".into(), Value::Null);
+			ap.insert("security".into(), if security.is_empty() { Value::Null } else { json!(security) });
+			current = Some(ap);
+		} else if let Some(ap) = current.as_mut() {
+			match key {
+				"Signal" => {
+					let signal: Option<u32> = val.trim_end_matches('%').trim().parse().ok();
+					ap.insert("signal_percent".into(), json!(signal));
+				}
+				"Channel" => {
+					let channel: Option<u32> = val.parse().ok();
+					ap.insert("channel".into(), json!(channel));
+				}
+				"Band" => {
+					ap.insert("band".into(), json!(val));
+				}
+				_ => {}
+			}
+		}
+	}
+	flush(&mut current, &mut available);
+
+	available.sort_by(|a, b| {
+		let sa = a.get("signal_percent").and_then(|v| v.as_u64()).unwrap_or(0);
+		let sb = b.get("signal_percent").and_then(|v| v.as_u64()).unwrap_or(0);
+		sb.cmp(&sa)
+	});
+
+	json!({
+		"status": "ok",
+		"available": available,
+		"cached": false,
+		"scanned_at_unix": chrono::Utc::now().timestamp(),
+	})
+}
@@ -0,0 +1,296 @@
+// ~/veil/veil-backend/src/ipc/token.rs
+//
+// Per-session secret for privileged IPC. The backend writes a fresh random
+// token to `<root>/ipc_token` on startup, and clients running as the same user
+// read it back and send it with each request. Namespaces that can start
+// addons, change config or install assets refuse requests without it;
+// read-only data stays open.
+//
+// Each addon the backend launches also gets a token of its own in
+// `VEIL_IPC_TOKEN`. It opens the same namespaces, and it is the only thing
+// that identifies an addon: a request is treated as coming from an addon
+// when, and only when, it carries that addon's token.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::ipc::response::{codes, IpcError};
+use crate::paths::ipc_token_path;
+use crate::{error, warn};
+
+/// Namespaces that need the token.
+const PRIVILEGED_NAMESPACES: &[&str] = &["addon", "assets", "backend", "config", "kv"];
+
+/// Environment variable a launched addon finds its own token in.
+pub const ADDON_TOKEN_ENV: &str = "VEIL_IPC_TOKEN";
+
+/// Who sent a request, as far as the IPC layer can tell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Caller {
+    /// Carries the session token: the UI, the CLI and the user's own scripts.
+    Session,
+    /// Carries the token issued to this addon at launch.
+    Addon(String),
+    /// No valid token. Under `enforce_addon_permissions` it may only do what
+    /// an addon with no declarations could.
+    Anonymous,
+}
+
+impl Caller {
+    pub fn addon_id(&self) -> Option<&str> {
+        match self {
+            Caller::Addon(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+/// Random bytes in a token (hex-encoded on disk and on the wire).
+const TOKEN_BYTES: usize = 32;
+
+static SESSION_TOKEN: OnceLock<Option<String>> = OnceLock::new();
+
+/// Addon tokens handed out this session, token → addon id.
+fn addon_tokens() -> &'static Mutex<HashMap<String, String>> {
+    static ADDON_TOKENS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    ADDON_TOKENS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Generate this session's token and write it to `ipc_token`. Call once before
+/// the IPC server and HTTP bridge start. If no token can be generated,
+/// privileged requests are refused for the whole session.
+pub fn init() {
+    let token = SESSION_TOKEN.get_or_init(|| {
+        let token = generate();
+        if token.is_none() {
+            error!("Failed to generate IPC token; privileged IPC commands are disabled");
+        }
+        token
+    });
+    if let Some(token) = token {
+        if let Err(e) = write_token_file(token) {
+            error!("Failed to write IPC token '{}': {}", ipc_token_path().display(), e);
+        }
+    }
+}
+
+/// A fresh token for `addon_id`, replacing any it was given before. `None`
+/// when no token could be generated; the addon then runs unauthenticated.
+pub fn issue_addon_token(addon_id: &str) -> Option<String> {
+    let token = generate();
+    let mut tokens = addon_tokens().lock().unwrap();
+    tokens.retain(|_, id| !id.eq_ignore_ascii_case(addon_id));
+    match &token {
+        Some(token) => {
+            tokens.insert(token.clone(), addon_id.to_string());
+        }
+        None => error!("Failed to generate an IPC token for addon '{}'", addon_id),
+    }
+    token
+}
+
+/// Read the token the running backend wrote, for clients.
+pub fn load() -> Option<String> {
+    std::fs::read_to_string(ipc_token_path())
+        .ok()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+/// Whether `ns.cmd` (or any entry of a `batch.run`) touches a privileged namespace.
+fn needs_token(ns: &str, args: Option<&Value>) -> bool {
+    if ns == "batch" {
+        return args
+            .and_then(|a| a.get("requests"))
+            .and_then(|v| v.as_array())
+            .map(|reqs| {
+                reqs.iter().any(|r| {
+                    let ns = r.get("ns").and_then(|v| v.as_str()).unwrap_or_default();
+                    PRIVILEGED_NAMESPACES.contains(&ns)
+                })
+            })
+            .unwrap_or(false);
+    }
+    PRIVILEGED_NAMESPACES.contains(&ns)
+}
+
+/// The caller `token` belongs to.
+fn identify(token: Option<&str>) -> Caller {
+    let Some(given) = token else { return Caller::Anonymous };
+    if let Some(Some(expected)) = SESSION_TOKEN.get() {
+        if constant_time_eq(expected.as_bytes(), given.as_bytes()) {
+            return Caller::Session;
+        }
+    }
+    let tokens = addon_tokens().lock().unwrap();
+    tokens
+        .iter()
+        .find(|(t, _)| constant_time_eq(t.as_bytes(), given.as_bytes()))
+        .map_or(Caller::Anonymous, |(_, id)| Caller::Addon(id.clone()))
+}
+
+/// Work out who sent a request from its token, and reject it if it needs a
+/// token it doesn't carry. `addon_id` is only what the caller says it is: an
+/// addon token must match it, and without one it identifies nothing.
+pub fn authorize(
+    ns: &str,
+    args: Option<&Value>,
+    token: Option<&str>,
+    addon_id: Option<&str>,
+) -> Result<Caller, IpcError> {
+    let caller = identify(token);
+    if let (Caller::Addon(id), Some(claimed)) = (&caller, addon_id) {
+        if !id.eq_ignore_ascii_case(claimed) {
+            warn!("[IPC] Rejected request naming addon '{}' with the token of '{}'", claimed, id);
+            return Err(IpcError::new(
+                codes::UNAUTHORIZED,
+                format!("The IPC token was issued to addon '{}', not '{}'", id, claimed),
+            ));
+        }
+    }
+    if caller == Caller::Anonymous && needs_token(ns, args) {
+        warn!("[IPC] Rejected unauthenticated '{}' request", ns);
+        return Err(IpcError::new(
+            codes::UNAUTHORIZED,
+            format!("'{}' requires the IPC token from ipc_token or {}", ns, ADDON_TOKEN_ENV),
+        ));
+    }
+    Ok(caller)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn generate() -> Option<String> {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    fill_random(&mut bytes)?;
+    Some(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+#[cfg(target_os = "windows")]
+fn fill_random(buf: &mut [u8]) -> Option<()> {
+    use windows::Win32::Security::Cryptography::{BCryptGenRandom, BCRYPT_USE_SYSTEM_PREFERRED_RNG};
+    unsafe { BCryptGenRandom(None, buf, BCRYPT_USE_SYSTEM_PREFERRED_RNG) }.ok().ok()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn fill_random(buf: &mut [u8]) -> Option<()> {
+    use std::io::Read;
+    std::fs::File::open("/dev/urandom").ok()?.read_exact(buf).ok()
+}
+
+/// The file sits in the user's profile, whose ACL already keeps other
+/// accounts out; elsewhere it is created owner-only.
+#[cfg(target_os = "windows")]
+fn write_token_file(token: &str) -> std::io::Result<()> {
+    std::fs::write(ipc_token_path(), token)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn write_token_file(token: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    let path = ipc_token_path();
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&path)?;
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(token.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const TEST_TOKEN: &str = "0123456789abcdef";
+
+    fn with_session_token() {
+        SESSION_TOKEN.get_or_init(|| Some(TEST_TOKEN.to_string()));
+    }
+
+    #[test]
+    fn privileged_request_without_token_is_rejected() {
+        with_session_token();
+        for ns in PRIVILEGED_NAMESPACES {
+            let err = authorize(ns, None, None, None).unwrap_err();
+            assert_eq!(err.code, Some(codes::UNAUTHORIZED), "{}", ns);
+        }
+        let err = authorize("config", None, Some("not-the-token"), None).unwrap_err();
+        assert_eq!(err.code, Some(codes::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn privileged_request_with_token_is_allowed() {
+        with_session_token();
+        assert!(authorize("config", None, Some(TEST_TOKEN), None).is_ok());
+        assert!(authorize("addon", None, Some(TEST_TOKEN), None).is_ok());
+    }
+
+    #[test]
+    fn batch_with_a_privileged_entry_needs_the_token() {
+        with_session_token();
+        let args = json!({ "requests": [
+            { "ns": "sysdata", "cmd": "cpu" },
+            { "ns": "config", "cmd": "set", "args": { "addon": "wallpaper", "path": "a", "value": 1 } },
+        ] });
+        assert!(authorize("batch", Some(&args), None, None).is_err());
+        assert!(authorize("batch", Some(&args), Some(TEST_TOKEN), None).is_ok());
+    }
+
+    #[test]
+    fn caller_is_identified_by_token() {
+        with_session_token();
+        let clock = issue_addon_token("test-clock").unwrap();
+        assert_eq!(authorize("sysdata", None, Some(TEST_TOKEN), None).unwrap(), Caller::Session);
+        assert_eq!(authorize("sysdata", None, None, None).unwrap(), Caller::Anonymous);
+        assert_eq!(
+            authorize("kv", None, Some(&clock), None).unwrap(),
+            Caller::Addon("test-clock".to_string())
+        );
+        assert_eq!(
+            authorize("kv", None, Some(&clock), Some("test-clock")).unwrap(),
+            Caller::Addon("test-clock".to_string())
+        );
+    }
+
+    #[test]
+    fn naming_an_addon_grants_nothing() {
+        with_session_token();
+        let clock = issue_addon_token("test-clock-2").unwrap();
+        // Without its token, a claimed addon id identifies nobody.
+        assert_eq!(authorize("sysdata", None, None, Some("test-clock-2")).unwrap(), Caller::Anonymous);
+        assert_eq!(
+            authorize("sysdata", None, Some(TEST_TOKEN), Some("test-clock-2")).unwrap(),
+            Caller::Session
+        );
+        // One addon's token can't be used to speak for another.
+        let err = authorize("kv", None, Some(&clock), Some("test-weather")).unwrap_err();
+        assert_eq!(err.code, Some(codes::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn reissued_addon_token_replaces_the_old_one() {
+        with_session_token();
+        let old = issue_addon_token("test-restarted").unwrap();
+        let new = issue_addon_token("test-restarted").unwrap();
+        assert_ne!(old, new);
+        assert_eq!(authorize("sysdata", None, Some(&old), None).unwrap(), Caller::Anonymous);
+        assert_eq!(
+            authorize("sysdata", None, Some(&new), None).unwrap(),
+            Caller::Addon("test-restarted".to_string())
+        );
+    }
+
+    #[test]
+    fn read_only_namespaces_stay_open() {
+        with_session_token();
+        assert!(authorize("sysdata", None, None, None).is_ok());
+        assert!(authorize("registry", None, None, None).is_ok());
+    }
+}
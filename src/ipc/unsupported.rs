@@ -0,0 +1,88 @@
+// ~/veil/veil-backend/src/ipc/unsupported.rs
+//
+// Stand-ins for the Win32/WMI/PowerShell collectors on other targets.
+// Each module keeps the public surface its Windows counterpart exposes to the
+// rest of the crate, so the updater, IPC and mock backend build everywhere.
+// Sections report `{ "supported": false }` instead of data.
+
+use serde_json::{json, Value};
+
+fn unsupported_json(section: &str) -> Value {
+	json!({
+		"supported": false,
+		"reason": format!("{} data is only collected on Windows", section),
+	})
+}
+
+macro_rules! unsupported_collector {
+	($module:ident, $func:ident) => {
+		pub mod $module {
+			pub fn $func() -> serde_json::Value {
+				super::unsupported_json(stringify!($module))
+			}
+		}
+	};
+}
+
+unsupported_collector!(cpu, get_cpu_json);
+unsupported_collector!(storage, get_storage_json);
+unsupported_collector!(audio, get_audio_json);
+unsupported_collector!(keyboard, get_keyboard_json);
+unsupported_collector!(mouse, get_mouse_json);
+unsupported_collector!(power, get_power_json);
+unsupported_collector!(bluetooth, get_bluetooth_json);
+unsupported_collector!(system, get_system_json);
+unsupported_collector!(notifications, get_notifications_json);
+unsupported_collector!(trayicons, get_tray_icons_json);
+
+pub mod gpu {
+	use crate::ipc::sysdata::process_snapshot::ProcessSnapshot;
+
+	pub fn get_gpu_json(_snapshot: &ProcessSnapshot) -> serde_json::Value {
+		super::unsupported_json("gpu")
+	}
+}
+
+pub mod ram {
+	use crate::ipc::sysdata::process_snapshot::ProcessSnapshot;
+
+	pub fn get_ram_json(_snapshot: &ProcessSnapshot) -> serde_json::Value {
+		super::unsupported_json("ram")
+	}
+}
+
+pub mod network {
+	use crate::ipc::sysdata::process_snapshot::ProcessSnapshot;
+
+	pub fn get_network_json(_snapshot: &ProcessSnapshot) -> serde_json::Value {
+		super::unsupported_json("network")
+	}
+
+	pub fn reset_session_counters() {}
+}
+
+pub mod wifi {
+	use serde_json::{json, Value};
+
+	pub fn get_wifi_json() -> Value {
+		super::unsupported_json("wifi")
+	}
+
+	pub fn scan_networks_json(_force: bool) -> Value {
+		json!({ "status": "unsupported", "available": [], "cached": false })
+	}
+}
+
+pub mod media {
+	use serde_json::{json, Value};
+
+	pub fn refresh_media_session_cache_if_due() {}
+
+	pub fn get_media_session_json() -> Value {
+		json!({ "playing": false, "sessions": [] })
+	}
+
+	pub fn control(_action: &str, _source_app_id: Option<&str>, _position_ms: Option<i64>) -> Result<Value, String> {
+		Err("Media control is only supported on Windows".to_string())
+	}
+}
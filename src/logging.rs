@@ -1,10 +1,13 @@
 // logging.rs — Universal drop-in logger for ProjectOpen applications.
 //
-// Logs are written to:
-//   ~/ProjectOpen/.Logs/<app_name>/<segment>/<date>_<app_name>_<segment>.log
+// Logs are written to one file chosen by the caller. When it grows past
+// `MAX_FILE_BYTES` it is rotated: `app.log` becomes `app.1.log`, `app.1.log`
+// becomes `app.2.log`, and so on, keeping `KEEP_FILES` files in total.
 //
-// A new log file is created each day. The background writer thread handles
-// I/O so logging never blocks the main/render thread.
+// A single background writer thread owns the file, so logging never blocks
+// the calling thread and rotation can't race with another thread's write.
+// `flush()` waits for it to catch up; call it before `std::process::exit`,
+// which would otherwise drop whatever is still queued.
 //
 // Implements `log::Log` so crates using `log::info!()` etc. are captured.
 // Also exports `info!`, `warn!`, `error!` macros for direct use.
@@ -13,34 +16,49 @@
 //   ```rust
 //   mod logging;
 //   // ...
-//   logging::init("VEIL", "Core", cfg!(debug_assertions));
+//   logging::init(root.join("logs").join("app.log"), log::LevelFilter::Info);
 //   info!("Hello from Core");
 //   ```
 
 use std::{
-    fs::{self, OpenOptions},
-    io::Write,
-    path::PathBuf,
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicUsize, Ordering},
         mpsc::{self, Sender},
         OnceLock,
     },
     thread,
+    time::Duration,
 };
 
 use chrono;
 use log::{Level, LevelFilter, Log, Metadata, Record};
 
+/// Size at which the current file is rotated.
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// Files kept, counting the current one.
+const KEEP_FILES: usize = 5;
+/// Longest `flush` waits for the writer thread.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
 // ---------------------------------------------------------------------------
 // Global state
 // ---------------------------------------------------------------------------
 
-/// Whether debug-level messages are enabled.
-static DEBUG_ENABLED: AtomicBool = AtomicBool::new(false);
+/// Most verbose level written, as a `LevelFilter` discriminant.
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Info as usize);
+
+/// What the background writer thread is sent.
+enum Message {
+    Line(String),
+    /// Write out everything queued before this, then signal.
+    Flush(Sender<()>),
+}
 
 /// Sender for the background writer thread.
-static LOG_TX: OnceLock<Sender<String>> = OnceLock::new();
+static LOG_TX: OnceLock<Sender<Message>> = OnceLock::new();
 
 /// Singleton logger instance (required by `log::set_logger`).
 static LOGGER: ProjectOpenLogger = ProjectOpenLogger;
@@ -51,63 +69,63 @@ static LOGGER: ProjectOpenLogger = ProjectOpenLogger;
 
 /// Initialise the logger.
 ///
-/// - `app_name`: application name (e.g. "VEIL", "OpenPeripheral").
-/// - `segment`: component name (e.g. "Core", "Wallpaper", "Server").
-/// - `debug`: if true, captures Debug-level and above; otherwise Warn and above.
+/// - `path`: log file; its directory is created if missing.
+/// - `level`: most verbose level written. Can be changed with `set_level`.
 ///
 /// Call once at startup. Panics if called more than once.
-pub fn init(app_name: &str, segment: &str, debug: bool) {
+pub fn init(path: PathBuf, level: LevelFilter) {
     if LOG_TX.get().is_some() {
         panic!("logging::init() called more than once");
     }
 
-    DEBUG_ENABLED.store(debug, Ordering::Relaxed);
-
-    let app = app_name.to_owned();
-    let seg = segment.to_owned();
+    MAX_LEVEL.store(level as usize, Ordering::Relaxed);
 
-    let (tx, rx) = mpsc::channel::<String>();
+    let (tx, rx) = mpsc::channel::<Message>();
     LOG_TX.set(tx).expect("LOG_TX already set");
 
-    // Background writer thread with daily rotation.
+    // Background writer thread with size-based rotation.
     thread::spawn(move || {
-        writer_loop(&app, &seg, rx);
+        writer_loop(&path, rx);
     });
 
     // Register as the global `log` crate backend.
-    let max_level = if debug {
-        LevelFilter::Debug
-    } else {
-        LevelFilter::Warn
-    };
-
     log::set_logger(&LOGGER)
-        .map(|()| log::set_max_level(max_level))
+        .map(|()| log::set_max_level(level))
         .expect("Failed to set logger");
 }
 
+/// Most verbose level currently written.
+pub fn level() -> LevelFilter {
+    match MAX_LEVEL.load(Ordering::Relaxed) {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
 /// Returns true if debug-level logging is active.
 #[inline]
 #[allow(dead_code)]
 pub fn enabled() -> bool {
-    DEBUG_ENABLED.load(Ordering::Relaxed)
+    level() >= LevelFilter::Debug
 }
 
 /// Returns true if a message at the given level should be logged.
 #[inline]
-pub fn should_log(level: &str) -> bool {
-    if !DEBUG_ENABLED.load(Ordering::Relaxed) {
-        return level == "WARN" || level == "ERROR";
+pub fn should_log(level_name: &str) -> bool {
+    match level_name.parse::<Level>() {
+        Ok(l) => l <= level(),
+        Err(_) => true,
     }
-    true
 }
 
-/// Set debug mode at runtime.
-#[allow(dead_code)]
-pub fn set_debug(debug: bool) {
-    DEBUG_ENABLED.store(debug, Ordering::Relaxed);
-    let max_level = if debug { LevelFilter::Debug } else { LevelFilter::Warn };
-    log::set_max_level(max_level);
+/// Change the level at runtime.
+pub fn set_level(level: LevelFilter) {
+    MAX_LEVEL.store(level as usize, Ordering::Relaxed);
+    log::set_max_level(level);
 }
 
 /// Enqueue a log message to the background writer.
@@ -117,7 +135,18 @@ pub fn enqueue(level: &str, msg: String) {
         let ts = chrono::Local::now()
             .format("%Y-%m-%d %H:%M:%S%.3f")
             .to_string();
-        let _ = tx.send(format!("{ts} [{level}] {msg}"));
+        let _ = tx.send(Message::Line(format!("{ts} [{level}] {msg}")));
+    }
+}
+
+/// Block until every message logged so far is on disk, or `FLUSH_TIMEOUT`
+/// passes. Does nothing before `init`.
+pub fn flush() {
+    if let Some(tx) = LOG_TX.get() {
+        let (done_tx, done_rx) = mpsc::channel();
+        if tx.send(Message::Flush(done_tx)).is_ok() {
+            let _ = done_rx.recv_timeout(FLUSH_TIMEOUT);
+        }
     }
 }
 
@@ -150,14 +179,10 @@ fn is_noisy_target(target: &str) -> bool {
 impl Log for ProjectOpenLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
         // Noisy third-party crates only get through at WARN or above.
-        if is_noisy_target(metadata.target()) {
-            return metadata.level() <= Level::Warn;
-        }
-        if DEBUG_ENABLED.load(Ordering::Relaxed) {
-            metadata.level() <= Level::Debug
-        } else {
-            metadata.level() <= Level::Warn
+        if is_noisy_target(metadata.target()) && metadata.level() > Level::Warn {
+            return false;
         }
+        metadata.level() <= level()
     }
 
     fn log(&self, record: &Record) {
@@ -169,14 +194,16 @@ impl Log for ProjectOpenLogger {
         let msg = format!("{}", record.args());
 
         // Also print to stderr for immediate visibility during development.
-        if DEBUG_ENABLED.load(Ordering::Relaxed) {
+        if enabled() {
             eprintln!("[{level}] {msg}");
         }
 
         enqueue(&level.to_string(), msg);
     }
 
-    fn flush(&self) {}
+    fn flush(&self) {
+        flush();
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -195,89 +222,110 @@ macro_rules! info {
 #[macro_export]
 macro_rules! warn {
     ($($arg:tt)*) => {{
-        $crate::logging::enqueue("WARN", format!($($arg)*));
+        if $crate::logging::should_log("WARN") {
+            $crate::logging::enqueue("WARN", format!($($arg)*));
+        }
     }};
 }
 
 #[macro_export]
 macro_rules! error {
     ($($arg:tt)*) => {{
-        $crate::logging::enqueue("ERROR", format!($($arg)*));
+        if $crate::logging::should_log("ERROR") {
+            $crate::logging::enqueue("ERROR", format!($($arg)*));
+        }
     }};
 }
 
 // ---------------------------------------------------------------------------
-// Background writer with daily rotation
+// Background writer with size-based rotation
 // ---------------------------------------------------------------------------
 
-/// Resolve the logs base directory:
-/// `~/ProjectOpen/.Logs/<app_name>/<segment>/`
-fn logs_dir(app_name: &str, segment: &str) -> PathBuf {
-    let home = std::env::var("USERPROFILE")
-        .ok()
-        .or_else(|| {
-            let drive = std::env::var("HOMEDRIVE").ok()?;
-            let path = std::env::var("HOMEPATH").ok()?;
-            Some(format!("{drive}{path}"))
-        })
-        .map(PathBuf::from)
-        .unwrap_or_else(|| {
-            // Fallback: next to exe
-            std::env::current_exe()
-                .ok()
-                .and_then(|p| p.parent().map(|d| d.to_path_buf()))
-                .unwrap_or_else(|| PathBuf::from("."))
-        });
-
-    home.join("ProjectOpen")
-        .join(".Logs")
-        .join(app_name)
-        .join(segment)
+/// `app.log` → `app.<n>.log`.
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("log");
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{stem}.{n}.{ext}"),
+        None => format!("{stem}.{n}"),
+    };
+    path.with_file_name(name)
 }
 
-/// Build the log filename for a given date:
-/// `<date>_<app_name>_<segment>.log`
-fn log_filename(app_name: &str, segment: &str, date: &str) -> String {
-    format!("{date}_{app_name}_{segment}.log")
+struct RotatingFile {
+    path: PathBuf,
+    file: Option<BufWriter<File>>,
+    size: u64,
 }
 
-/// Background writer loop. Opens a new file each day.
-fn writer_loop(app_name: &str, segment: &str, rx: mpsc::Receiver<String>) {
-    let dir = logs_dir(app_name, segment);
-    let _ = fs::create_dir_all(&dir);
-
-    let mut current_date = today();
-    let mut file = open_log_file(&dir, app_name, segment, &current_date);
+impl RotatingFile {
+    fn open(path: &Path) -> Self {
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path).ok();
+        let size = file
+            .as_ref()
+            .and_then(|f| f.metadata().ok())
+            .map(|m| m.len())
+            .unwrap_or(0);
+        Self { path: path.to_path_buf(), file: file.map(BufWriter::new), size }
+    }
 
-    while let Ok(line) = rx.recv() {
-        let now_date = today();
-        if now_date != current_date {
-            // Day changed — rotate to a new file.
-            current_date = now_date;
-            file = open_log_file(&dir, app_name, segment, &current_date);
+    fn write_line(&mut self, line: &str) {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > MAX_FILE_BYTES {
+            self.rotate();
+        }
+        if let Some(f) = self.file.as_mut() {
+            if writeln!(f, "{line}").is_ok() {
+                self.size += len;
+            }
         }
+    }
 
-        if let Some(ref mut f) = file {
-            let _ = writeln!(f, "{line}");
+    fn flush(&mut self) {
+        if let Some(f) = self.file.as_mut() {
             let _ = f.flush();
         }
     }
-}
 
-fn open_log_file(
-    dir: &PathBuf,
-    app_name: &str,
-    segment: &str,
-    date: &str,
-) -> Option<fs::File> {
-    let path = dir.join(log_filename(app_name, segment, date));
-    OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&path)
-        .ok()
+    /// Shift every kept file up one slot and start a fresh current file.
+    /// If a rename fails (e.g. another process holds the file), writing
+    /// simply continues in the current file.
+    fn rotate(&mut self) {
+        self.flush();
+        self.file = None;
+
+        let _ = fs::remove_file(rotated_path(&self.path, KEEP_FILES - 1));
+        for n in (1..KEEP_FILES - 1).rev() {
+            let from = rotated_path(&self.path, n);
+            if from.exists() {
+                let _ = fs::rename(&from, rotated_path(&self.path, n + 1));
+            }
+        }
+        let _ = fs::rename(&self.path, rotated_path(&self.path, 1));
+
+        *self = Self::open(&self.path);
+    }
 }
 
-fn today() -> String {
-    chrono::Local::now().format("%Y-%m-%d").to_string()
+/// Background writer loop. Flushes whenever the queue runs dry or a
+/// `Message::Flush` asks it to.
+fn writer_loop(path: &Path, rx: mpsc::Receiver<Message>) {
+    let mut out = RotatingFile::open(path);
+
+    while let Ok(msg) = rx.recv() {
+        let mut next = Some(msg);
+        while let Some(msg) = next {
+            match msg {
+                Message::Line(line) => out.write_line(&line),
+                Message::Flush(done) => {
+                    out.flush();
+                    let _ = done.send(());
+                }
+            }
+            next = rx.try_recv().ok();
+        }
+        out.flush();
+    }
 }
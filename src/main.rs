@@ -7,17 +7,25 @@ mod cli;
 mod paths;
 mod ipc;
 mod autostart;
+#[cfg(target_os = "windows")]
 mod utils;
 mod config_ui;
 mod config;
+mod config_archive;
+mod diagnostics;
+mod safe_mode;
+mod session_end;
+mod hotkey;
+mod notify;
 pub mod installer;
 
 use crate::{
-    cli::{run_cli, bootstrap_user_root},
+    cli::{run_cli, run_get, run_watch, run_export, run_import, run_diag, run_check_migrations, bootstrap_user_root},
     autostart::{start_configured_autostart_addons, ensure_user_config_dirs},
     ipc::{
         server::start_ipc_server,
         registry::registry_manager,
+        response::codes,
     },
 };
 
@@ -25,6 +33,7 @@ use include_dir::{include_dir, Dir};
 
 use std::path::PathBuf;
 use std::time::Duration;
+#[cfg(target_os = "windows")]
 use windows::{
     core::PCWSTR,
     Win32::{
@@ -57,6 +66,11 @@ pub struct Addon {
     exe_path: PathBuf,
     dir: PathBuf,
     package: String,
+    /// Extra `args` / `env` from addon.json, placeholders already expanded.
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+    /// Malformed `args` / `env` entries that were skipped.
+    launch_warnings: Vec<String>,
 }
 
 pub struct ODDaemon {
@@ -79,6 +93,12 @@ impl ODDaemon {
         info!("Loading backend config");
 
         let cfg = crate::config::load_config();
+        crate::config::start_config_watcher();
+
+        let requested_safe_mode = std::env::args().any(|a| a == "--safe-mode");
+        if crate::safe_mode::begin_startup(requested_safe_mode) {
+            warn!("Running in SAFE MODE: addon autostart and optional collectors are disabled");
+        }
 
         info!("Data pull rates: fast={}ms slow={}ms, paused: {}, refresh_on_request: {}",
             cfg.fast_pull_rate_ms, cfg.slow_pull_rate_ms, cfg.data_pull_paused, cfg.refresh_on_request);
@@ -88,6 +108,7 @@ impl ODDaemon {
         registry_manager();
 
         // 2. IPC server up immediately so tray & addons can connect
+        crate::ipc::token::init();
         info!("Spawning IPC server thread");
         std::thread::spawn(|| {
             info!("IPC server thread running");
@@ -105,30 +126,54 @@ impl ODDaemon {
         info!("Starting live data updater");
         crate::ipc::data_updater::start_registry_updater();
 
-        info!("Starting configured addon autostarts (background)");
+        if crate::safe_mode::safe_mode() {
+            info!("Safe mode: skipping configured addon autostarts");
+        } else {
+            info!("Starting configured addon autostarts (background)");
 
-        std::thread::spawn(|| {
-            start_configured_autostart_addons();
-        });
+            std::thread::spawn(|| {
+                start_configured_autostart_addons();
+            });
+        }
+
+        // Logoff and shutdown count as deliberate exits.
+        crate::session_end::start();
+
+        // 3b. Global hotkey that opens the UI
+        crate::hotkey::start();
+
+        // 4. Periodic asset-integrity scan (read-only; repairs via assets.verify)
+        crate::ipc::assets::start_integrity_scan();
+
+        // 5. Wallpaper playlist rotation and time-of-day schedules
+        crate::config_ui::start_playlist_rotation();
+        crate::config_ui::start_wallpaper_scheduler();
 
         // Ensure user config directories exist
         ensure_user_config_dirs();
 
         // Auto-launch the OpenRender UI process (owns the system tray).
         // The UI starts hidden — the tray icon appears immediately and the
-        // user can double-click it to show the window.
-        info!("Launching VEIL UI process (tray host)");
-        match std::env::current_exe() {
-            Ok(exe) => {
-                match std::process::Command::new(&exe)
-                    .arg("--veil-ui")
-                    .spawn()
-                {
-                    Ok(child) => info!("UI process started (PID {})", child.id()),
-                    Err(e) => error!("Failed to start UI process: {}", e),
+        // user can double-click it to show the window. After `backend.restart`
+        // the previous daemon's UI is still up, so it is left alone.
+        let relaunched = std::env::var_os(RELAUNCH_ENV).is_some();
+        std::env::remove_var(RELAUNCH_ENV);
+        if relaunched {
+            info!("Relaunched by backend.restart; keeping the running UI process");
+        } else {
+            info!("Launching VEIL UI process (tray host)");
+            match std::env::current_exe() {
+                Ok(exe) => {
+                    match std::process::Command::new(&exe)
+                        .arg("--veil-ui")
+                        .spawn()
+                    {
+                        Ok(child) => info!("UI process started (PID {})", child.id()),
+                        Err(e) => error!("Failed to start UI process: {}", e),
+                    }
                 }
+                Err(e) => error!("Failed to resolve executable for UI launch: {}", e),
             }
-            Err(e) => error!("Failed to resolve executable for UI launch: {}", e),
         }
 
         // Block main thread — the daemon stays alive until the process is killed.
@@ -140,7 +185,13 @@ impl ODDaemon {
     }
 }
 
-fn acquire_single_instance() -> Option<HANDLE> {
+#[cfg(target_os = "windows")]
+type InstanceGuard = HANDLE;
+#[cfg(not(target_os = "windows"))]
+type InstanceGuard = ();
+
+#[cfg(target_os = "windows")]
+fn acquire_single_instance() -> Option<InstanceGuard> {
     let mut name: Vec<u16> = "Global\\VEILBackendSingleton"
         .encode_utf16()
         .collect();
@@ -157,24 +208,190 @@ fn acquire_single_instance() -> Option<HANDLE> {
     }
 }
 
+// No system-wide mutex off Windows; every process is allowed to run.
+#[cfg(not(target_os = "windows"))]
+fn acquire_single_instance() -> Option<InstanceGuard> {
+    Some(())
+}
+
+fn release_single_instance(guard: Option<InstanceGuard>) {
+    #[cfg(target_os = "windows")]
+    if let Some(handle) = guard {
+        unsafe {
+            let _ = CloseHandle(handle);
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = guard;
+}
+
+/// Attempts, and the pause between them, at reaching a daemon that holds
+/// the mutex but may not have its IPC server up yet.
+const FOCUS_ATTEMPTS: u32 = 10;
+const FOCUS_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// A second launch found the daemon already running: show its UI instead of
+/// exiting without a trace.
+fn focus_running_instance() {
+    for attempt in 1..=FOCUS_ATTEMPTS {
+        let req = crate::ipc::request::IpcRequest {
+            ns: "ui".to_string(),
+            cmd: "focus".to_string(),
+            args: None,
+            addon_id: None,
+            token: None,
+        };
+        match crate::ipc::request::send_ipc_request(req) {
+            Ok(resp) if resp.ok => {
+                info!("Asked the running backend to show the UI ({:?})", resp.data);
+                return;
+            }
+            Ok(resp) if resp.error_code.as_deref() == Some(codes::TIMEOUT) && attempt < FOCUS_ATTEMPTS => {
+                info!("Running backend is busy; retrying");
+                std::thread::sleep(FOCUS_RETRY_DELAY);
+            }
+            Ok(resp) => {
+                warn!("Running backend refused to show the UI: {:?}", resp.error);
+                return;
+            }
+            Err(e) if attempt < FOCUS_ATTEMPTS => {
+                info!("Running backend not reachable yet ({}); retrying", e);
+                std::thread::sleep(FOCUS_RETRY_DELAY);
+            }
+            Err(e) => warn!("Couldn't reach the running backend to show the UI: {}", e),
+        }
+    }
+}
+
+/// Set on a daemon started by `restart_backend`.
+const RELAUNCH_ENV: &str = "VEIL_RELAUNCHED";
+/// Lets the IPC reply to `backend.restart` go out before the process exits.
+const RESTART_DELAY: Duration = Duration::from_millis(300);
+
+/// The running daemon's singleton mutex, as an integer so it can sit in a static.
+#[cfg(target_os = "windows")]
+static DAEMON_INSTANCE: std::sync::Mutex<Option<usize>> = std::sync::Mutex::new(None);
+
+fn keep_daemon_instance(guard: Option<InstanceGuard>) {
+    #[cfg(target_os = "windows")]
+    {
+        *DAEMON_INSTANCE.lock().unwrap() = guard.map(|h| h.0 as usize);
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = guard;
+}
+
+fn take_daemon_instance() -> Option<InstanceGuard> {
+    #[cfg(target_os = "windows")]
+    {
+        DAEMON_INSTANCE.lock().unwrap().take().map(|raw| HANDLE(raw as *mut _))
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Some(())
+    }
+}
+
+/// Shut the daemon down in the background: stop the addons, clear the
+/// unstable-start record, flush the log and exit. Delayed like
+/// `restart_backend` so the IPC reply goes out first.
+pub fn shutdown_backend() {
+    let spawned = std::thread::Builder::new()
+        .name("veil-shutdown".into())
+        .spawn(|| {
+            std::thread::sleep(RESTART_DELAY);
+            info!("Shutting down backend");
+            let stopped = crate::ipc::addon::stop_all();
+            info!("Stopped {} addon(s) before shutdown", stopped.len());
+            crate::safe_mode::mark_clean_exit();
+            release_single_instance(take_daemon_instance());
+            crate::logging::flush();
+            std::process::exit(0);
+        });
+    if let Err(e) = spawned {
+        error!("Failed to start backend shutdown: {}", e);
+    }
+}
+
+/// Restart the daemon in the background: stop the addons, release the
+/// singleton mutex so the new process can take it, relaunch this exe with
+/// the same arguments, then exit. Config is read from disk, so the new
+/// instance comes up with the same settings. If the relaunch fails, this
+/// daemon keeps running.
+pub fn restart_backend() {
+    let spawned = std::thread::Builder::new()
+        .name("veil-restart".into())
+        .spawn(|| {
+            std::thread::sleep(RESTART_DELAY);
+            info!("Restarting backend");
+            let stopped = crate::ipc::addon::stop_all();
+            info!("Stopped {} addon(s) before restart", stopped.len());
+
+            // Before the relaunch, so the new process's own start isn't wiped.
+            crate::safe_mode::mark_clean_exit();
+            release_single_instance(take_daemon_instance());
+            let relaunch = std::env::current_exe().and_then(|exe| {
+                std::process::Command::new(exe)
+                    .args(std::env::args().skip(1))
+                    .env(RELAUNCH_ENV, "1")
+                    .spawn()
+            });
+            match relaunch {
+                Ok(child) => {
+                    info!("New backend started (PID {}); exiting", child.id());
+                    crate::logging::flush();
+                    std::process::exit(0);
+                }
+                Err(e) => {
+                    error!("Failed to relaunch backend, keeping this one: {}", e);
+                    keep_daemon_instance(acquire_single_instance());
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        error!("Failed to start backend restart: {}", e);
+    }
+}
+
 fn main() {
     // Enable per-monitor DPI awareness so GetCursorPos, GetSystemMetrics, and
     // all display coordinates use physical pixels — matching the coordinate
     // space of DPI-aware addons (e.g. wallpaper).  Without this, cursor
     // positions are virtualised by Windows on non-primary monitors with
     // different DPI, causing wallpaper cursor trails to drift.
+    #[cfg(target_os = "windows")]
     unsafe {
         let _ = SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2);
     }
 
+    let args: Vec<String> = std::env::args().collect();
+
+    // `get` and `watch` only talk to the running backend over IPC: no
+    // self-install, no logger, no singleton, and nothing on stdout but the
+    // value. `export` and `import` likewise only touch files under the VEIL
+    // root, `diag` only reads them (plus one IPC request that may fail), and
+    // `check-migrations` only reads the addon folder it is given.
+    match args.get(1).map(String::as_str) {
+        Some("get") => std::process::exit(run_get(&args[2..])),
+        Some("watch") => std::process::exit(run_watch(&args[2..])),
+        Some("export") => std::process::exit(run_export(&args[2..])),
+        Some("import") => std::process::exit(run_import(&args[2..])),
+        Some("diag") => std::process::exit(run_diag(&args[2..])),
+        Some("check-migrations") => std::process::exit(run_check_migrations(&args[2..])),
+        _ => {}
+    }
+
     // Run self-install/bootstrap before singleton acquisition so a relaunch
     // from ~/VEIL/Core/VEIL.exe is not blocked by this process mutex.
     bootstrap_user_root();
-
-    let args: Vec<String> = std::env::args().collect();
     let is_ui_mode = args
         .iter()
-        .any(|a| a == "--addon-config-ui" || a == "--veil-ui" || a == "--addon-webview");
+        .any(|a| {
+            a == "--addon-config-ui"
+                || a == "--veil-ui"
+                || a == "--addon-webview"
+                || a == "--capture-wallpaper-preview"
+        });
 
     // `--no-backend` (alias `--ui-only`): launch JUST the PRISM-managed UI
     // (window, scene graph, system tray) without spinning up the IPC server,
@@ -191,7 +408,12 @@ fn main() {
 
     if !prism_owns_logging {
         // Enable logging before the singleton check so a silent exit is observable.
-        logging::init("VEIL", "Core", true);
+        // The level comes straight from disk; `load_config` re-applies it later.
+        let log_path = if is_ui_mode { paths::ui_log_path() } else { paths::backend_log_path() };
+        let log_level = config::read_config_from_disk()
+            .map(|cfg| cfg.log_level)
+            .unwrap_or_default();
+        logging::init(log_path, log_level.filter());
         info!("VEIL backend starting (args={:?})", &args[1..]);
     }
 
@@ -202,6 +424,10 @@ fn main() {
             Some(handle) => Some(handle),
             None => {
                 info!("Another VEIL backend instance already holds the singleton mutex — exiting.");
+                // A plain launch (e.g. double-clicking the exe) means the user wants the UI.
+                if args.iter().skip(1).all(|a| a == "--safe-mode") {
+                    focus_running_instance();
+                }
                 return;
             }
         }
@@ -212,33 +438,25 @@ fn main() {
         if let Err(e) = launch_ui() {
             error!("UI launch failed: {e}");
         }
-        if let Some(handle) = instance_guard {
-            unsafe { let _ = CloseHandle(handle); }
-        }
+        release_single_instance(instance_guard);
         return;
     }
 
-    if std::env::args().count() > 1 {
+    // `--safe-mode` runs the regular daemon; any other argument means CLI mode.
+    if args.iter().skip(1).any(|a| a != "--safe-mode") {
         info!("CLI mode detected");
         if let Err(e) = run_cli() {
             error!("CLI bridge error: {e}");
         }
-        if let Some(handle) = instance_guard {
-            unsafe {
-                let _ = CloseHandle(handle);
-            }
-        }
+        release_single_instance(instance_guard);
         return;
     }
 
+    keep_daemon_instance(instance_guard);
     let daemon = ODDaemon::new();
     daemon.run();
 
     info!("VEIL backend exiting");
 
-    if let Some(handle) = instance_guard {
-        unsafe {
-            let _ = CloseHandle(handle);
-        }
-    }
+    release_single_instance(take_daemon_instance());
 }
\ No newline at end of file
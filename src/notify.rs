@@ -0,0 +1,277 @@
+// ~/veil/veil-backend/src/notify.rs
+//
+// Desktop notifications for addon events: crashes, failed starts and the
+// autostart summary. Off unless `notifications_enabled` is set in
+// tray_settings.json. Each kind of event per addon is shown at most once per
+// COOLDOWN, so a crash loop produces one balloon rather than one per restart.
+//
+// The tray icon belongs to the UI process, so the daemon shows balloons from
+// a notification-area icon of its own that exists only while one is up.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::autostart::load_tray_settings;
+use crate::info;
+
+const COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Clone, Copy, PartialEq)]
+enum Level {
+    Info,
+    Warning,
+}
+
+/// When each `(event, addon)` was last shown, and how many were held back since.
+fn shown() -> &'static Mutex<HashMap<String, (Instant, u32)>> {
+    static SHOWN: OnceLock<Mutex<HashMap<String, (Instant, u32)>>> = OnceLock::new();
+    SHOWN.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Display name from addon.json, falling back to the id.
+fn addon_name(addon_id: &str) -> String {
+    crate::ipc::addon::utils::find_addon_entry(addon_id)
+        .ok()
+        .and_then(|entry| entry.metadata.get("name").and_then(|n| n.as_str()).map(str::to_string))
+        .unwrap_or_else(|| addon_id.to_string())
+}
+
+/// Show a notification unless they are off or `key` is cooling down.
+/// `text` gets a note about held-back repeats.
+fn notify(key: &str, level: Level, title: &str, text: String) {
+    if !load_tray_settings().notifications_enabled {
+        return;
+    }
+    let now = Instant::now();
+    let suppressed = {
+        let mut shown = shown().lock().unwrap();
+        match shown.get_mut(key) {
+            Some((last, held)) if now.duration_since(*last) < COOLDOWN => {
+                *held += 1;
+                info!("[notify] Holding back '{}' ({} since the last one shown)", key, held);
+                return;
+            }
+            Some((last, held)) => {
+                *last = now;
+                std::mem::take(held)
+            }
+            None => {
+                shown.insert(key.to_string(), (now, 0));
+                0
+            }
+        }
+    };
+    let text = match suppressed {
+        0 => text,
+        n => format!("{} ({} more since the last notice)", text, n),
+    };
+    platform::show(title, &text, level);
+}
+
+/// A tracked addon exited with a failure status.
+pub fn addon_crashed(addon_id: &str, reason: &str) {
+    let name = addon_name(addon_id);
+    notify(
+        &format!("crash:{}", addon_id),
+        Level::Warning,
+        &format!("{} stopped unexpectedly", name),
+        reason.to_string(),
+    );
+}
+
+/// An addon the backend started on its own (autostart or auto-restart)
+/// failed to launch.
+pub fn addon_start_failed(addon_id: &str, reason: &str) {
+    let name = addon_name(addon_id);
+    notify(
+        &format!("start:{}", addon_id),
+        Level::Warning,
+        &format!("{} failed to start", name),
+        reason.to_string(),
+    );
+}
+
+/// Autostart has run: `started` ids and `(id, reason)` for failures.
+pub fn autostart_finished(started: &[String], failed: &[(String, String)]) {
+    if started.is_empty() && failed.is_empty() {
+        return;
+    }
+    let (level, title, text) = if failed.is_empty() {
+        let names = started.iter().map(|id| addon_name(id)).collect::<Vec<_>>().join(", ");
+        (Level::Info, format!("Started {} addon(s)", started.len()), names)
+    } else {
+        let reasons = failed
+            .iter()
+            .map(|(id, reason)| format!("{}: {}", addon_name(id), reason))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let title = format!("{} of {} autostart addon(s) failed", failed.len(), started.len() + failed.len());
+        (Level::Warning, title, reasons)
+    };
+    notify("autostart", level, &title, text);
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+    use std::sync::{Mutex, OnceLock};
+    use std::time::{Duration, Instant};
+
+    use windows::core::{w, PCWSTR};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::{
+        ExtractIconW, Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_TIP, NIIF_INFO, NIIF_WARNING, NIM_ADD, NIM_DELETE,
+        NIM_MODIFY, NOTIFYICONDATAW,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DestroyIcon, DestroyWindow, DispatchMessageW, LoadIconW, PeekMessageW, TranslateMessage,
+        HICON, IDI_APPLICATION, MSG, PM_REMOVE, WINDOW_EX_STYLE, WINDOW_STYLE,
+    };
+
+    use super::Level;
+    use crate::warn;
+
+    /// How long the icon stays after the latest balloon.
+    const ICON_LIFETIME: Duration = Duration::from_secs(10);
+    const PUMP_INTERVAL: Duration = Duration::from_millis(100);
+    const ICON_ID: u32 = 1;
+
+    struct Balloon {
+        title: String,
+        text: String,
+        level: Level,
+    }
+
+    /// Queue a balloon for the notifier thread, starting it on first use.
+    pub fn show(title: &str, text: &str, level: Level) {
+        static QUEUE: OnceLock<Option<Mutex<Sender<Balloon>>>> = OnceLock::new();
+        let queue = QUEUE.get_or_init(|| {
+            let (tx, rx) = mpsc::channel();
+            match std::thread::Builder::new().name("veil-notify".into()).spawn(move || run(rx)) {
+                Ok(_) => Some(Mutex::new(tx)),
+                Err(e) => {
+                    warn!("[notify] Failed to start notifier thread: {}", e);
+                    None
+                }
+            }
+        });
+        if let Some(tx) = queue {
+            let balloon = Balloon { title: title.to_string(), text: text.to_string(), level };
+            let _ = tx.lock().unwrap().send(balloon);
+        }
+    }
+
+    /// Copy `s` into a fixed-size, NUL-terminated UTF-16 field, truncating.
+    fn fill(dst: &mut [u16], s: &str) {
+        let max = dst.len() - 1;
+        let mut n = 0;
+        for unit in s.encode_utf16().take(max) {
+            dst[n] = unit;
+            n += 1;
+        }
+        dst[n] = 0;
+    }
+
+    fn run(rx: Receiver<Balloon>) {
+        while let Ok(first) = rx.recv() {
+            unsafe { show_batch(first, &rx) };
+        }
+    }
+
+    /// Add the icon with `first`, then keep it up (updating the balloon for
+    /// anything queued meanwhile) until ICON_LIFETIME passes with nothing new.
+    unsafe fn show_batch(first: Balloon, rx: &Receiver<Balloon>) {
+        let hwnd = match CreateWindowExW(
+            WINDOW_EX_STYLE(0),
+            w!("STATIC"),
+            w!("VEIL notifications"),
+            WINDOW_STYLE(0),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            None,
+            None,
+        ) {
+            Ok(hwnd) => hwnd,
+            Err(e) => {
+                warn!("[notify] Failed to create notification window: {}", e);
+                return;
+            }
+        };
+        let extracted = exe_icon();
+        let icon = extracted.unwrap_or_else(|| LoadIconW(None, IDI_APPLICATION).unwrap_or_default());
+
+        let mut data = NOTIFYICONDATAW {
+            cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+            hWnd: hwnd,
+            uID: ICON_ID,
+            uFlags: NIF_ICON | NIF_TIP | NIF_INFO,
+            hIcon: icon,
+            ..Default::default()
+        };
+        fill(&mut data.szTip, "VEIL");
+        set_balloon(&mut data, &first);
+        if !Shell_NotifyIconW(NIM_ADD, &data).as_bool() {
+            warn!("[notify] Failed to show notification '{}'", first.title);
+        } else {
+            let mut until = Instant::now() + ICON_LIFETIME;
+            while Instant::now() < until {
+                pump(hwnd);
+                match rx.recv_timeout(PUMP_INTERVAL) {
+                    Ok(next) => {
+                        set_balloon(&mut data, &next);
+                        let _ = Shell_NotifyIconW(NIM_MODIFY, &data);
+                        until = Instant::now() + ICON_LIFETIME;
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            let _ = Shell_NotifyIconW(NIM_DELETE, &data);
+        }
+
+        if let Some(icon) = extracted {
+            let _ = DestroyIcon(icon);
+        }
+        let _ = DestroyWindow(hwnd);
+    }
+
+    fn set_balloon(data: &mut NOTIFYICONDATAW, balloon: &Balloon) {
+        fill(&mut data.szInfoTitle, &balloon.title);
+        fill(&mut data.szInfo, &balloon.text);
+        data.dwInfoFlags = match balloon.level {
+            Level::Info => NIIF_INFO,
+            Level::Warning => NIIF_WARNING,
+        };
+    }
+
+    unsafe fn pump(hwnd: HWND) {
+        let mut msg = MSG::default();
+        while PeekMessageW(&mut msg, Some(hwnd), 0, 0, PM_REMOVE).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+
+    /// VEIL's own icon from the running executable. The caller destroys it.
+    unsafe fn exe_icon() -> Option<HICON> {
+        let exe = std::env::current_exe().ok()?;
+        let wide: Vec<u16> = exe.to_string_lossy().encode_utf16().chain(Some(0)).collect();
+        // ExtractIconW returns 1 when the file has no icons.
+        let icon = ExtractIconW(None, PCWSTR(wide.as_ptr()), 0);
+        (!icon.is_invalid() && icon.0 as usize != 1).then_some(icon)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::Level;
+
+    pub fn show(title: &str, text: &str, _level: Level) {
+        crate::info!("[notify] {}: {}", title, text);
+    }
+}
@@ -4,36 +4,101 @@ use std::path::PathBuf;
 use std::sync::OnceLock;
 use crate::{info, warn};
 
+static CACHED_HOME: OnceLock<Option<PathBuf>> = OnceLock::new();
 static CACHED_ROOT: OnceLock<PathBuf> = OnceLock::new();
 
-pub fn user_home_dir() -> Option<PathBuf> {
-    // Primary (most reliable on Windows)
-    if let Ok(profile) = std::env::var("USERPROFILE") {
-        return Some(PathBuf::from(profile));
-    }
+/// Environment variable that redirects the VEIL root away from `~/VEIL/Core/`.
+pub const VEIL_HOME_ENV: &str = "VEIL_HOME";
 
-    // Fallback (older / edge cases)
-    let drive = std::env::var("HOMEDRIVE").ok();
-    let path = std::env::var("HOMEPATH").ok();
+/// Returns the `VEIL_HOME` override, if set to a non-empty value.
+fn veil_home_override() -> Option<PathBuf> {
+    std::env::var_os(VEIL_HOME_ENV)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
 
-    match (drive, path) {
-        (Some(d), Some(p)) => {
-            let full = PathBuf::from(format!("{}{}", d, p));
-            Some(full)
-        }
-        _ => {
-            warn!("Could not resolve home directory using USERPROFILE or HOMEDRIVE/HOMEPATH");
-            None
+/// Resolve the user's home directory, trying in order:
+/// `USERPROFILE` → `HOMEDRIVE`+`HOMEPATH` → the shell's known Profile folder.
+/// The last step keeps VEIL usable in service contexts with a sparse environment.
+/// The result (and which source won) is resolved and logged once.
+pub fn user_home_dir() -> Option<PathBuf> {
+    CACHED_HOME.get_or_init(|| {
+        let resolved = non_empty_env("USERPROFILE")
+            .map(|p| (PathBuf::from(p), "USERPROFILE"))
+            .or_else(|| {
+                let drive = non_empty_env("HOMEDRIVE")?;
+                let path = non_empty_env("HOMEPATH")?;
+                Some((PathBuf::from(format!("{}{}", drive, path)), "HOMEDRIVE/HOMEPATH"))
+            })
+            .or_else(|| known_folder_profile().map(|p| (p, "FOLDERID_Profile")));
+
+        match resolved {
+            Some((home, source)) => {
+                info!("Home directory resolved via {}: {}", source, home.display());
+                Some(home)
+            }
+            None => {
+                warn!("Could not resolve home directory using USERPROFILE, HOMEDRIVE/HOMEPATH or FOLDERID_Profile");
+                None
+            }
         }
+    }).clone()
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// The user's Downloads folder, or `~/Downloads` when the shell doesn't
+/// know it. Not created if missing.
+pub fn downloads_dir() -> Option<PathBuf> {
+    known_folder_downloads().or_else(|| user_home_dir().map(|home| home.join("Downloads")))
+}
+
+#[cfg(windows)]
+fn known_folder(id: &windows::core::GUID) -> Option<PathBuf> {
+    use windows::Win32::System::Com::CoTaskMemFree;
+    use windows::Win32::UI::Shell::{SHGetKnownFolderPath, KF_FLAG_DEFAULT};
+
+    unsafe {
+        let raw = SHGetKnownFolderPath(id, KF_FLAG_DEFAULT, None).ok()?;
+        let path = raw.to_string().ok();
+        CoTaskMemFree(Some(raw.0 as _));
+        path.filter(|p| !p.is_empty()).map(PathBuf::from)
     }
 }
 
-/// The canonical VEIL root is always `~/VEIL/Core/`.
+/// The shell's Profile folder; a last resort where no profile environment
+/// variables are set.
+#[cfg(windows)]
+fn known_folder_profile() -> Option<PathBuf> {
+    known_folder(&windows::Win32::UI::Shell::FOLDERID_Profile)
+}
+
+#[cfg(windows)]
+fn known_folder_downloads() -> Option<PathBuf> {
+    known_folder(&windows::Win32::UI::Shell::FOLDERID_Downloads)
+}
+
+#[cfg(not(windows))]
+fn known_folder_profile() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(not(windows))]
+fn known_folder_downloads() -> Option<PathBuf> {
+    None
+}
+
+/// The canonical VEIL root is `~/VEIL/Core/`, unless `VEIL_HOME` points elsewhere.
 /// All config, addons, and assets live here.
 /// Result is cached after the first successful resolution.
 pub fn veil_root_dir() -> PathBuf {
     CACHED_ROOT.get_or_init(|| {
-        let root = if let Some(home) = user_home_dir() {
+        let root = if let Some(custom) = veil_home_override() {
+            info!("Using {} override for VEIL root", VEIL_HOME_ENV);
+            custom
+        } else if let Some(home) = user_home_dir() {
             home.join("VEIL").join("Core")
         } else {
             warn!("Could not resolve home directory, falling back to exe parent");
@@ -49,4 +114,112 @@ pub fn veil_root_dir() -> PathBuf {
         info!("VEIL root resolved: {}", root.display());
         root
     }).clone()
-}
\ No newline at end of file
+}
+
+// ── Well-known locations under the VEIL root ──
+
+/// `<root>/Addons/` — one folder per installed addon.
+pub fn addons_dir() -> PathBuf {
+    veil_root_dir().join("Addons")
+}
+
+/// `<root>/Assets/` — asset categories live one level below.
+pub fn assets_dir() -> PathBuf {
+    veil_root_dir().join("Assets")
+}
+
+/// `<root>/Assets/<category>/` (exact spelling; callers that need a
+/// case-insensitive match should search `assets_dir()` themselves).
+pub fn asset_category_dir(category: &str) -> PathBuf {
+    assets_dir().join(category)
+}
+
+/// Folder under `Assets/` that holds per-addon private storage rather than
+/// an asset category.
+pub const ADDON_ASSETS_CATEGORY: &str = "Addons";
+
+/// `<root>/Assets/Addons/<addon_id>/` — per-addon private asset storage.
+pub fn addon_assets_dir(addon_id: &str) -> PathBuf {
+    assets_dir().join(ADDON_ASSETS_CATEGORY).join(addon_id)
+}
+
+/// `<root>/cache/` — regenerated files such as the shell HTML.
+pub fn cache_dir() -> PathBuf {
+    veil_root_dir().join("cache")
+}
+
+/// `<root>/logs/` — ad-hoc log files written through the control namespace.
+pub fn logs_dir() -> PathBuf {
+    veil_root_dir().join("logs")
+}
+
+/// `<root>/logs/veil.log` — rotating log of the backend and CLI.
+pub fn backend_log_path() -> PathBuf {
+    logs_dir().join("veil.log")
+}
+
+/// `<root>/logs/veil-ui.log` — rotating log of the config UI processes, kept
+/// apart so they never rotate a file the backend is writing.
+pub fn ui_log_path() -> PathBuf {
+    logs_dir().join("veil-ui.log")
+}
+
+/// `<root>/config.yaml` — persisted backend configuration.
+pub fn config_yaml_path() -> PathBuf {
+    veil_root_dir().join("config.yaml")
+}
+
+/// `<root>/registry.json` — on-disk registry snapshot.
+pub fn registry_json_path() -> PathBuf {
+    veil_root_dir().join("registry.json")
+}
+
+/// `<root>/registry.json.gz` — the snapshot when `compress_snapshot` is on.
+pub fn registry_json_gz_path() -> PathBuf {
+    veil_root_dir().join("registry.json.gz")
+}
+
+/// `<root>/asset_prefs.json` — favorite and recently-used asset ids.
+pub fn asset_prefs_path() -> PathBuf {
+    veil_root_dir().join("asset_prefs.json")
+}
+
+/// `<root>/data_usage.json` — this month's traffic on metered connections.
+pub fn data_usage_path() -> PathBuf {
+    veil_root_dir().join("data_usage.json")
+}
+
+/// `<root>/playlist_state.json` — current position of each rotating wallpaper playlist.
+pub fn playlist_state_path() -> PathBuf {
+    veil_root_dir().join("playlist_state.json")
+}
+
+/// `<root>/ipc_token` — this session's secret for privileged IPC requests.
+pub fn ipc_token_path() -> PathBuf {
+    veil_root_dir().join("ipc_token")
+}
+
+/// `<root>/startup_state.json` — launch bookkeeping used for automatic safe mode.
+pub fn startup_state_path() -> PathBuf {
+    veil_root_dir().join("startup_state.json")
+}
+
+/// `<root>/ui_state.json` — last section, addon and tab shown in the UI.
+pub fn ui_state_path() -> PathBuf {
+    veil_root_dir().join("ui_state.json")
+}
+
+/// `<root>/backups/` — files replaced by `VEIL import`, one folder per run.
+pub fn backups_dir() -> PathBuf {
+    veil_root_dir().join("backups")
+}
+
+/// `<root>/kv/` — each addon's `kv` store, as `<addon_id>.json`.
+pub fn kv_dir() -> PathBuf {
+    veil_root_dir().join("kv")
+}
+
+/// `<root>/tray_settings.json` — autostart and run-at-startup preferences.
+pub fn tray_settings_json_path() -> PathBuf {
+    veil_root_dir().join("tray_settings.json")
+}
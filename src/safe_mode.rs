@@ -0,0 +1,108 @@
+// ~/veil/veil-backend/src/safe_mode.rs
+//
+// Safe mode: boot the daemon without autostart addons and with only the core
+// collectors (see `SAFE_MODE_SECTIONS` in the data updater), so a bad addon or
+// collector can't take VEIL down on every launch. Entered explicitly with `--safe-mode`, or
+// automatically after several launches in a row died before becoming stable.
+//
+// Crash detection uses a small state file (`<root>/startup_state.json`):
+// each daemon launch appends its timestamp to `unstable_starts`, and the list
+// is cleared once the process has stayed up for `STABLE_AFTER`, or when it
+// shuts down on purpose (`mark_clean_exit`): `backend.restart`,
+// `backend.shutdown`, and Windows logoff or shutdown.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::paths::startup_state_path;
+use crate::{info, warn};
+
+/// Unstable launches within `CRASH_WINDOW` that trigger automatic safe mode.
+const CRASH_THRESHOLD: usize = 3;
+/// Launches older than this no longer count towards the threshold.
+const CRASH_WINDOW: Duration = Duration::from_secs(5 * 60);
+/// Uptime after which a launch counts as stable.
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StartupState {
+    #[serde(default)]
+    unstable_starts: Vec<u64>,
+}
+
+pub fn safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::Relaxed)
+}
+
+fn now_unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn load_state() -> StartupState {
+    std::fs::read_to_string(startup_state_path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &StartupState) {
+    let path = startup_state_path();
+    match serde_json::to_string_pretty(state) {
+        Ok(text) => {
+            if let Err(e) = std::fs::write(&path, text) {
+                warn!("Failed to write startup state '{}': {}", path.display(), e);
+            }
+        }
+        Err(e) => warn!("Failed to serialize startup state: {}", e),
+    }
+}
+
+/// Record this daemon launch and decide whether to run in safe mode.
+/// Call once at the top of the daemon run path.
+pub fn begin_startup(requested: bool) -> bool {
+    let now = now_unix_ms();
+    let window_ms = CRASH_WINDOW.as_millis() as u64;
+
+    let mut state = load_state();
+    state.unstable_starts.retain(|t| now.saturating_sub(*t) <= window_ms);
+    let recent_crashes = state.unstable_starts.len();
+    state.unstable_starts.push(now);
+    save_state(&state);
+
+    let auto = recent_crashes >= CRASH_THRESHOLD;
+    if requested {
+        info!("Safe mode requested via --safe-mode");
+    } else if auto {
+        warn!(
+            "{} unstable launches in the last {}s — entering safe mode",
+            recent_crashes,
+            CRASH_WINDOW.as_secs()
+        );
+    }
+
+    let enabled = requested || auto;
+    SAFE_MODE.store(enabled, Ordering::Relaxed);
+
+    std::thread::spawn(|| {
+        std::thread::sleep(STABLE_AFTER);
+        save_state(&StartupState::default());
+        info!("Startup marked stable after {}s", STABLE_AFTER.as_secs());
+    });
+
+    enabled
+}
+
+/// Clear the unstable-start record before a deliberate exit, so a restart
+/// within `STABLE_AFTER` of launch isn't counted as a crash. Call right
+/// before the daemon exits on its own.
+pub fn mark_clean_exit() {
+    save_state(&StartupState::default());
+    info!("Startup state cleared for a clean exit");
+}
@@ -0,0 +1,90 @@
+// ~/veil/veil-backend/src/session_end.rs
+//
+// Clean exit when Windows logs off or shuts down. The daemon has no window
+// of its own, and only top-level windows are sent WM_QUERYENDSESSION and
+// WM_ENDSESSION, so a hidden one is kept on a dedicated thread to hear them.
+// On WM_ENDSESSION the unstable-start record is cleared and the log flushed
+// before Windows ends the process.
+
+use crate::warn;
+
+pub fn start() {
+    if let Err(e) = std::thread::Builder::new().name("veil-session-end".into()).spawn(platform::run) {
+        warn!("[session] Failed to start session-end listener: {}", e);
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::core::w;
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW, TranslateMessage, MSG,
+        WINDOW_EX_STYLE, WM_ENDSESSION, WM_QUERYENDSESSION, WNDCLASSW, WS_OVERLAPPED,
+    };
+
+    use crate::{info, warn};
+
+    pub fn run() {
+        unsafe {
+            let instance = GetModuleHandleW(None).unwrap_or_default();
+            let class = WNDCLASSW {
+                lpfnWndProc: Some(window_proc),
+                hInstance: instance.into(),
+                lpszClassName: w!("VEILSessionEnd"),
+                ..Default::default()
+            };
+            if RegisterClassW(&class) == 0 {
+                warn!("[session] Failed to register session-end window class");
+                return;
+            }
+            // Never shown: a top-level window, unlike a message-only one,
+            // is included in the end-of-session broadcast.
+            let created = CreateWindowExW(
+                WINDOW_EX_STYLE(0),
+                w!("VEILSessionEnd"),
+                w!("VEIL session end"),
+                WS_OVERLAPPED,
+                0,
+                0,
+                0,
+                0,
+                None,
+                None,
+                Some(instance.into()),
+                None,
+            );
+            if let Err(e) = created {
+                warn!("[session] Failed to create session-end window: {}", e);
+                return;
+            }
+
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+    }
+
+    unsafe extern "system" fn window_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        match msg {
+            WM_QUERYENDSESSION => LRESULT(1),
+            WM_ENDSESSION => {
+                if wparam.0 != 0 {
+                    info!("Session ending; exiting");
+                    crate::safe_mode::mark_clean_exit();
+                    crate::logging::flush();
+                }
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    pub fn run() {}
+}